@@ -0,0 +1,2 @@
+-- orders に価格異常（過去購入価格より大幅に高い購入）の警告フラグを追加
+ALTER TABLE orders ADD COLUMN price_warning INTEGER NOT NULL DEFAULT 0 CHECK(price_warning IN (0, 1));
@@ -0,0 +1,4 @@
+-- orders に消費税額と税込/税抜表示フラグを追加
+-- tax_included = 0 の注文は統計で tax_amount（未記載なら商品合計の 10%）を加算して税込に揃える
+ALTER TABLE orders ADD COLUMN tax_amount INTEGER;
+ALTER TABLE orders ADD COLUMN tax_included INTEGER NOT NULL DEFAULT 1 CHECK(tax_included IN (0, 1));
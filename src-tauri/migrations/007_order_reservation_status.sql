@@ -0,0 +1,4 @@
+-- 予約注文の確保状況
+-- reservation_status: NULL = 未判定, 'secured' = 確保済み, 'at_risk' = 在庫切れリスク（数量制限・抽選等）, 'unavailable' = 確保不可
+ALTER TABLE orders ADD COLUMN reservation_status TEXT CHECK(reservation_status IN ('secured', 'at_risk', 'unavailable'));
+ALTER TABLE orders ADD COLUMN reservation_status_updated_at DATETIME;
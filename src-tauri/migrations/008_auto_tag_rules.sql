@@ -0,0 +1,24 @@
+-- 自動タグ付けルール
+-- パース時（注文保存時）に商品名・ショップに一致したアイテムへ tag を付与する
+CREATE TABLE IF NOT EXISTS auto_tag_rules (
+    id          INTEGER PRIMARY KEY AUTOINCREMENT,
+    tag         TEXT    NOT NULL,
+    target      TEXT    NOT NULL DEFAULT 'item_name'
+                        CHECK(target IN ('item_name', 'shop_domain', 'shop_name')),
+    keyword     TEXT    NOT NULL,
+    match_type  TEXT    NOT NULL DEFAULT 'contains'
+                        CHECK(match_type IN ('contains', 'starts_with', 'exact')),
+    created_at  TEXT    NOT NULL DEFAULT (datetime('now'))
+);
+
+-- アイテムに付与されたタグ
+CREATE TABLE IF NOT EXISTS item_tags (
+    id          INTEGER PRIMARY KEY AUTOINCREMENT,
+    item_id     INTEGER NOT NULL,
+    tag         TEXT    NOT NULL,
+    created_at  TEXT    NOT NULL DEFAULT (datetime('now')),
+    FOREIGN KEY (item_id) REFERENCES items(id) ON DELETE CASCADE,
+    UNIQUE (item_id, tag)
+);
+
+CREATE INDEX IF NOT EXISTS idx_item_tags_tag ON item_tags(tag);
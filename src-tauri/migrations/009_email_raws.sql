@@ -0,0 +1,8 @@
+-- Gmail API の raw 形式（RFC 822 原本）の保存先
+-- 設定 sync.save_raw_eml が有効な場合のみ同期時に保存される
+CREATE TABLE IF NOT EXISTS email_raws (
+    message_id  TEXT    PRIMARY KEY NOT NULL,
+    raw         BLOB    NOT NULL,
+    created_at  TEXT    NOT NULL DEFAULT (datetime('now')),
+    FOREIGN KEY (message_id) REFERENCES emails(message_id) ON DELETE CASCADE
+);
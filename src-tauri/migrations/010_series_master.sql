@@ -0,0 +1,21 @@
+-- シリーズ名の統合マスタ
+-- product_master.series の表記ゆれ（「機動戦士ガンダム」「ガンダム」等）を1つの正規名に束ねる
+CREATE TABLE IF NOT EXISTS series_master (
+    id              INTEGER PRIMARY KEY AUTOINCREMENT,
+    canonical_name  TEXT    NOT NULL UNIQUE,
+    created_at      TEXT    NOT NULL DEFAULT (datetime('now'))
+);
+
+-- シリーズ名のエイリアス（product_master.series に現れる表記そのもの）
+-- alias_key は照合用の正規化キー（NFKC・小文字化・記号/接頭辞除去）。表記違いで同じキーになることがある
+CREATE TABLE IF NOT EXISTS series_aliases (
+    id          INTEGER PRIMARY KEY AUTOINCREMENT,
+    series_id   INTEGER NOT NULL,
+    alias       TEXT    NOT NULL UNIQUE,
+    alias_key   TEXT    NOT NULL,
+    created_at  TEXT    NOT NULL DEFAULT (datetime('now')),
+    FOREIGN KEY (series_id) REFERENCES series_master(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_series_aliases_series_id ON series_aliases(series_id);
+CREATE INDEX IF NOT EXISTS idx_series_aliases_alias_key ON series_aliases(alias_key);
@@ -0,0 +1,7 @@
+-- orders / items の論理削除（ゴミ箱）
+-- deleted_at が NULL 以外の行は集計・一覧から除外し、restore_order 等で NULL に戻して復元する
+ALTER TABLE orders ADD COLUMN deleted_at DATETIME;
+ALTER TABLE items ADD COLUMN deleted_at DATETIME;
+
+CREATE INDEX IF NOT EXISTS idx_orders_deleted_at ON orders(deleted_at);
+CREATE INDEX IF NOT EXISTS idx_items_deleted_at ON items(deleted_at);
@@ -0,0 +1,3 @@
+-- キャンセルメールに記載されたキャンセル理由（原文）
+-- 入荷未定による自動キャンセル等、メールに理由が記載されている場合のみ設定する
+ALTER TABLE orders ADD COLUMN cancel_reason_detail TEXT;
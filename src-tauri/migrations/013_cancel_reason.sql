@@ -0,0 +1,19 @@
+-- キャンセル理由の分類と、キャンセルされた商品の記録
+-- cancel_reason: NULL = 不明, 'user_request' = ユーザー都合, 'out_of_stock' = 在庫なし・入荷未定, 'discontinued' = 発売中止, 'other' = その他
+ALTER TABLE orders ADD COLUMN cancel_reason TEXT CHECK(cancel_reason IN ('user_request', 'out_of_stock', 'discontinued', 'other'));
+
+-- apply_cancel で items から削除される前の商品を記録する（キャンセル理由別・メーカー別の統計用）
+CREATE TABLE IF NOT EXISTS cancelled_items (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    order_id INTEGER NOT NULL,
+    email_id INTEGER,
+    item_name TEXT NOT NULL,
+    maker TEXT,
+    quantity INTEGER NOT NULL DEFAULT 1,
+    cancel_reason TEXT CHECK(cancel_reason IN ('user_request', 'out_of_stock', 'discontinued', 'other')),
+    created_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    FOREIGN KEY (order_id) REFERENCES orders(id) ON DELETE CASCADE,
+    FOREIGN KEY (email_id) REFERENCES emails(id) ON DELETE SET NULL
+);
+CREATE INDEX IF NOT EXISTS idx_cancelled_items_order_id ON cancelled_items(order_id);
+CREATE INDEX IF NOT EXISTS idx_cancelled_items_reason_maker ON cancelled_items(cancel_reason, maker);
@@ -0,0 +1,13 @@
+-- 分割出荷対応: どの商品がどの荷物（deliveries）で発送されたかを記録する
+-- quantity: その荷物に含まれる数量（発送メールに記載された数量）
+CREATE TABLE IF NOT EXISTS delivery_items (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    delivery_id INTEGER NOT NULL,
+    item_id INTEGER NOT NULL,
+    quantity INTEGER NOT NULL DEFAULT 1,
+    created_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    FOREIGN KEY (delivery_id) REFERENCES deliveries(id) ON DELETE CASCADE,
+    FOREIGN KEY (item_id) REFERENCES items(id) ON DELETE CASCADE,
+    UNIQUE (delivery_id, item_id)
+);
+CREATE INDEX IF NOT EXISTS idx_delivery_items_item_id ON delivery_items(item_id);
@@ -0,0 +1,19 @@
+-- 注文の支払い予定（予約時の内金・発売時の残金など、1注文に複数の支払いを持つ）
+-- status: 'pending' = 未払い, 'paid' = 支払い済み, 'cancelled' = 取り消し
+-- email_id: 支払い完了メールから消し込んだ場合のメール
+CREATE TABLE IF NOT EXISTS payments (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    order_id INTEGER NOT NULL,
+    label TEXT,
+    amount INTEGER NOT NULL,
+    due_date DATE,
+    status TEXT NOT NULL DEFAULT 'pending' CHECK(status IN ('pending', 'paid', 'cancelled')),
+    paid_at DATETIME,
+    email_id INTEGER,
+    created_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    FOREIGN KEY (order_id) REFERENCES orders(id) ON DELETE CASCADE,
+    FOREIGN KEY (email_id) REFERENCES emails(id) ON DELETE SET NULL
+);
+CREATE INDEX IF NOT EXISTS idx_payments_order_id ON payments(order_id);
+CREATE INDEX IF NOT EXISTS idx_payments_status_due_date ON payments(status, due_date);
@@ -0,0 +1,8 @@
+-- 月初に自動生成する「先月のまとめ」レポート
+-- month: 対象月（YYYY-MM）。生成済みの月は再生成しない（手動生成時は上書き）
+CREATE TABLE IF NOT EXISTS monthly_reports (
+    month TEXT PRIMARY KEY,
+    summary_json TEXT NOT NULL,
+    markdown TEXT NOT NULL,
+    created_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
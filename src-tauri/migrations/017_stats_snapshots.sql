@@ -0,0 +1,20 @@
+-- 統計の日次スナップショット（積み数・未着数の推移グラフ用）
+-- snapshot_date: 日本時間の日付（YYYY-MM-DD）。同じ日は最新の値で上書きする
+-- not_shipped 〜 cancelled: 注文ごとの最新配送ステータス別件数（DeliveryStats と同じ）
+CREATE TABLE IF NOT EXISTS stats_snapshots (
+    snapshot_date TEXT PRIMARY KEY,
+    total_orders INTEGER NOT NULL DEFAULT 0,
+    total_items INTEGER NOT NULL DEFAULT 0,
+    total_amount INTEGER NOT NULL DEFAULT 0,
+    not_shipped INTEGER NOT NULL DEFAULT 0,
+    preparing INTEGER NOT NULL DEFAULT 0,
+    shipped INTEGER NOT NULL DEFAULT 0,
+    in_transit INTEGER NOT NULL DEFAULT 0,
+    out_for_delivery INTEGER NOT NULL DEFAULT 0,
+    delivered INTEGER NOT NULL DEFAULT 0,
+    failed INTEGER NOT NULL DEFAULT 0,
+    returned INTEGER NOT NULL DEFAULT 0,
+    cancelled INTEGER NOT NULL DEFAULT 0,
+    created_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
@@ -0,0 +1,28 @@
+-- 再販ウォッチ
+-- keyword: ウォッチする商品名（表示用）、keyword_normalized: 照合用に正規化した商品名
+-- item_id: 所持済み商品から登録した場合の items.id（商品が削除されたら NULL）
+CREATE TABLE IF NOT EXISTS reissue_watches (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    keyword TEXT NOT NULL,
+    keyword_normalized TEXT NOT NULL UNIQUE,
+    item_id INTEGER,
+    created_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    FOREIGN KEY (item_id) REFERENCES items(id) ON DELETE SET NULL
+);
+
+-- 検出した再販アナウンス
+-- source: 'email'（再入荷・再販メール）または 'news'（ニュースクリップ）
+-- source_ref: emails.id または news_clips.url。同じアナウンスは 1 回だけ記録する
+-- notified: デスクトップ通知済みか
+CREATE TABLE IF NOT EXISTS reissue_detections (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    watch_id INTEGER NOT NULL,
+    source TEXT NOT NULL CHECK(source IN ('email', 'news')),
+    source_ref TEXT NOT NULL,
+    title TEXT,
+    notified INTEGER NOT NULL DEFAULT 0 CHECK(notified IN (0, 1)),
+    detected_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    FOREIGN KEY (watch_id) REFERENCES reissue_watches(id) ON DELETE CASCADE,
+    UNIQUE (watch_id, source, source_ref)
+);
+CREATE INDEX IF NOT EXISTS idx_reissue_detections_notified ON reissue_detections(notified) WHERE notified = 0;
@@ -0,0 +1,121 @@
+-- 外部ツール（Metabase 等）向けの分析用ビュー
+-- アプリ本体はビューを参照しない。列の追加・変更時は新しいマイグレーションで DROP VIEW → CREATE VIEW し、
+-- analysis_view_versions の version を上げる（外部ツール側でビュー定義の変更を検知できるようにする）
+-- いずれのビューもゴミ箱に入れた注文・商品（deleted_at IS NOT NULL）を除外する
+CREATE TABLE IF NOT EXISTS analysis_view_versions (
+    view_name TEXT PRIMARY KEY,
+    version INTEGER NOT NULL,
+    description TEXT NOT NULL,
+    updated_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+-- 注文 1 行: 商品合計・税込合計と最新の配送状況
+DROP VIEW IF EXISTS analysis_orders;
+CREATE VIEW analysis_orders AS
+WITH item_totals AS (
+    SELECT order_id,
+           COUNT(*) AS item_count,
+           SUM(quantity) AS total_quantity,
+           SUM(price * quantity) AS items_amount
+    FROM items
+    WHERE deleted_at IS NULL
+    GROUP BY order_id
+),
+latest_delivery AS (
+    SELECT order_id, carrier, tracking_number, delivery_status, estimated_delivery, actual_delivery
+    FROM (
+        SELECT order_id, carrier, tracking_number, delivery_status, estimated_delivery, actual_delivery,
+               ROW_NUMBER() OVER (PARTITION BY order_id ORDER BY updated_at DESC, id DESC) AS rn
+        FROM deliveries
+    ) t
+    WHERE rn = 1
+)
+SELECT o.id AS order_id,
+       o.shop_name,
+       o.shop_domain,
+       o.order_number,
+       o.order_date,
+       date(o.order_date) AS order_day,
+       strftime('%Y-%m', o.order_date) AS order_month,
+       COALESCE(it.item_count, 0) AS item_count,
+       COALESCE(it.total_quantity, 0) AS total_quantity,
+       COALESCE(it.items_amount, 0) AS items_amount,
+       o.tax_amount,
+       o.tax_included,
+       CASE
+           WHEN o.tax_included = 1 THEN COALESCE(it.items_amount, 0)
+           ELSE COALESCE(it.items_amount, 0)
+                + COALESCE(o.tax_amount, CAST(COALESCE(it.items_amount, 0) * 0.1 AS INTEGER))
+       END AS total_amount_tax_included,
+       o.reservation_status,
+       o.cancel_reason,
+       ld.delivery_status,
+       ld.carrier,
+       ld.tracking_number,
+       ld.estimated_delivery,
+       ld.actual_delivery,
+       o.created_at,
+       o.updated_at
+FROM orders o
+LEFT JOIN item_totals it ON it.order_id = o.id
+LEFT JOIN latest_delivery ld ON ld.order_id = o.id
+WHERE o.deleted_at IS NULL;
+
+-- 商品 1 行: 注文情報と商品マスタ（メーカー・シリーズ等の解析結果）
+DROP VIEW IF EXISTS analysis_items;
+CREATE VIEW analysis_items AS
+SELECT i.id AS item_id,
+       i.order_id,
+       o.shop_name,
+       o.order_number,
+       o.order_date,
+       strftime('%Y-%m', o.order_date) AS order_month,
+       i.item_name,
+       i.price,
+       i.quantity,
+       i.price * i.quantity AS subtotal,
+       i.category,
+       i.brand,
+       pm.maker,
+       pm.series,
+       pm.product_name,
+       pm.scale,
+       pm.is_reissue,
+       i.created_at
+FROM items i
+INNER JOIN orders o ON o.id = i.order_id
+LEFT JOIN product_master pm ON TRIM(i.item_name) = pm.raw_name
+WHERE i.deleted_at IS NULL
+  AND o.deleted_at IS NULL;
+
+-- 配送 1 行: 荷物ごとの配送状況と注文情報（配送日数の集計用）
+DROP VIEW IF EXISTS analysis_deliveries;
+CREATE VIEW analysis_deliveries AS
+SELECT d.id AS delivery_id,
+       d.order_id,
+       o.shop_name,
+       o.order_number,
+       o.order_date,
+       d.carrier,
+       d.tracking_number,
+       d.delivery_status,
+       d.estimated_delivery,
+       d.actual_delivery,
+       CASE
+           WHEN d.actual_delivery IS NOT NULL AND o.order_date IS NOT NULL
+           THEN CAST(julianday(d.actual_delivery) - julianday(o.order_date) AS INTEGER)
+       END AS days_to_delivery,
+       d.created_at,
+       d.updated_at
+FROM deliveries d
+INNER JOIN orders o ON o.id = d.order_id
+WHERE o.deleted_at IS NULL;
+
+INSERT INTO analysis_view_versions (view_name, version, description) VALUES
+    ('analysis_orders', 1, '注文ごとの商品合計・税込合計・最新の配送状況'),
+    ('analysis_items', 1, '商品ごとの注文情報と商品マスタ（メーカー・シリーズ等）'),
+    ('analysis_deliveries', 1, '荷物ごとの配送状況と注文日からの配送日数')
+ON CONFLICT(view_name) DO UPDATE SET
+    version = excluded.version,
+    description = excluded.description,
+    updated_at = CURRENT_TIMESTAMP;
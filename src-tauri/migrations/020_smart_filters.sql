@@ -0,0 +1,12 @@
+-- スマートフィルタ（保存した注文検索条件）
+-- query_json: repository::order_search::OrderQuery を JSON にしたもの
+CREATE TABLE IF NOT EXISTS smart_filters (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    name TEXT NOT NULL UNIQUE,
+    query_json TEXT NOT NULL,
+    created_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+CREATE TRIGGER IF NOT EXISTS smart_filters_updated_at AFTER UPDATE ON smart_filters BEGIN
+    UPDATE smart_filters SET updated_at = CURRENT_TIMESTAMP WHERE id = NEW.id;
+END;
@@ -0,0 +1,5 @@
+-- 予約商品の発売予定
+-- release_date: 'YYYY-MM'（月まで）または 'YYYY-MM-DD'。確認メール等から抽出できなければ NULL
+ALTER TABLE items ADD COLUMN release_date TEXT;
+
+CREATE INDEX IF NOT EXISTS idx_items_release_date ON items(release_date) WHERE release_date IS NOT NULL;
@@ -0,0 +1,5 @@
+-- 発売予定の精度（「8月下旬」「2025年夏」等のあいまいな表記を表示時に元の表記へ戻すため）
+-- release_date_precision: 'day' / 'early_month' / 'mid_month' / 'late_month' / 'month' /
+--   'spring' / 'summer' / 'autumn' / 'winter' / 'year'。NULL の既存データは release_date の桁数から日 / 月とみなす
+-- release_date は並び替え用に期間の末日側へ寄せた値（下旬なら月末日、夏なら 'YYYY-08'、年のみなら 'YYYY-12'）
+ALTER TABLE items ADD COLUMN release_date_precision TEXT;
@@ -0,0 +1,5 @@
+-- shop_settings ごとのパース統計（各店舗設定が実際に使われているかの確認・無効化候補の提案に使う）
+-- last_matched_at: そのパーサーでパースに成功した最終日時（UTC, datetime('now') 形式）。未ヒットなら NULL
+-- matched_count: パースに成功した累計件数
+ALTER TABLE shop_settings ADD COLUMN last_matched_at DATETIME;
+ALTER TABLE shop_settings ADD COLUMN matched_count INTEGER NOT NULL DEFAULT 0;
@@ -0,0 +1,63 @@
+-- orders に調整額（メール記載の合計金額と商品合計の差額: 送料・手数料・割引など）を追加
+-- 統計・分析ビューでは商品合計（+ 税抜表示の税額）に調整額を加算して支払額に揃える
+ALTER TABLE orders ADD COLUMN amount_adjustment INTEGER NOT NULL DEFAULT 0;
+
+-- analysis_orders に調整額を追加し、税込合計に含める
+DROP VIEW IF EXISTS analysis_orders;
+CREATE VIEW analysis_orders AS
+WITH item_totals AS (
+    SELECT order_id,
+           COUNT(*) AS item_count,
+           SUM(quantity) AS total_quantity,
+           SUM(price * quantity) AS items_amount
+    FROM items
+    WHERE deleted_at IS NULL
+    GROUP BY order_id
+),
+latest_delivery AS (
+    SELECT order_id, carrier, tracking_number, delivery_status, estimated_delivery, actual_delivery
+    FROM (
+        SELECT order_id, carrier, tracking_number, delivery_status, estimated_delivery, actual_delivery,
+               ROW_NUMBER() OVER (PARTITION BY order_id ORDER BY updated_at DESC, id DESC) AS rn
+        FROM deliveries
+    ) t
+    WHERE rn = 1
+)
+SELECT o.id AS order_id,
+       o.shop_name,
+       o.shop_domain,
+       o.order_number,
+       o.order_date,
+       date(o.order_date) AS order_day,
+       strftime('%Y-%m', o.order_date) AS order_month,
+       COALESCE(it.item_count, 0) AS item_count,
+       COALESCE(it.total_quantity, 0) AS total_quantity,
+       COALESCE(it.items_amount, 0) AS items_amount,
+       o.tax_amount,
+       o.tax_included,
+       o.amount_adjustment,
+       CASE
+           WHEN o.tax_included = 1 THEN COALESCE(it.items_amount, 0)
+           ELSE COALESCE(it.items_amount, 0)
+                + COALESCE(o.tax_amount, CAST(COALESCE(it.items_amount, 0) * 0.1 AS INTEGER))
+       END + o.amount_adjustment AS total_amount_tax_included,
+       o.reservation_status,
+       o.cancel_reason,
+       ld.delivery_status,
+       ld.carrier,
+       ld.tracking_number,
+       ld.estimated_delivery,
+       ld.actual_delivery,
+       o.created_at,
+       o.updated_at
+FROM orders o
+LEFT JOIN item_totals it ON it.order_id = o.id
+LEFT JOIN latest_delivery ld ON ld.order_id = o.id
+WHERE o.deleted_at IS NULL;
+
+INSERT INTO analysis_view_versions (view_name, version, description) VALUES
+    ('analysis_orders', 2, '注文ごとの商品合計・調整額（送料・割引等）込みの税込合計・最新の配送状況')
+ON CONFLICT(view_name) DO UPDATE SET
+    version = excluded.version,
+    description = excluded.description,
+    updated_at = CURRENT_TIMESTAMP;
@@ -0,0 +1,18 @@
+-- 到着後アンケート（商品受け取り評価）
+-- 再パースで items が作り直されても残るよう、item_overrides と同じビジネスキーで記録する
+-- ビジネスキー: (shop_domain, order_number, item_name)
+-- satisfaction: 1〜5 の満足度, damaged: 破損・不良があったか
+CREATE TABLE IF NOT EXISTS item_receipt_reviews (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    shop_domain TEXT NOT NULL,
+    order_number TEXT NOT NULL COLLATE NOCASE,
+    item_name TEXT NOT NULL,
+    shop_name TEXT,
+    satisfaction INTEGER NOT NULL CHECK(satisfaction BETWEEN 1 AND 5),
+    damaged INTEGER NOT NULL DEFAULT 0 CHECK(damaged IN (0, 1)),
+    note TEXT,
+    created_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    UNIQUE (shop_domain, order_number, item_name)
+);
+CREATE INDEX IF NOT EXISTS idx_item_receipt_reviews_shop_domain ON item_receipt_reviews(shop_domain);
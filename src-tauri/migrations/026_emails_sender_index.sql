@@ -0,0 +1,21 @@
+-- バッチパースで shop_settings に登録された送信元のメールだけを取り出すための式インデックス
+-- from_address は "表示名 <address>" 形式のまま保存されているため、
+-- extract_email_address() と同じく山括弧内（なければ全体）を trim + 小文字化したアドレスで索引する。
+-- repository/parse.rs の FROM_EMAIL_EXPR と式を一致させること（一致しないとインデックスが使われない）。
+CREATE INDEX IF NOT EXISTS idx_emails_from_email ON emails(
+    lower(trim(
+        CASE
+            WHEN instr(from_address, '<') > 0
+                 AND instr(substr(from_address, instr(from_address, '<') + 1), '>') > 0
+            THEN substr(
+                from_address,
+                instr(from_address, '<') + 1,
+                instr(substr(from_address, instr(from_address, '<') + 1), '>') - 1
+            )
+            ELSE from_address
+        END
+    ))
+);
+
+-- 有効な送信元アドレスの一覧を小文字で引くためのインデックス
+CREATE INDEX IF NOT EXISTS idx_shop_settings_enabled_sender ON shop_settings(is_enabled, lower(sender_address));
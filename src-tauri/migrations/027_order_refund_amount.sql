@@ -0,0 +1,3 @@
+-- キャンセル・返金メールに記載された返金額（注文全体の返金額。記載がなければ NULL）
+-- 同じ注文で複数のメールに記載がある場合は最後に処理したメールの値で上書きする
+ALTER TABLE orders ADD COLUMN refund_amount INTEGER;
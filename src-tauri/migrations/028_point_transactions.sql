@@ -0,0 +1,17 @@
+-- 注文ごとの会員ポイント（メールに記載されたポイント利用・付与を蓄積し、ショップ別の残高推定に使う）
+-- kind: 'earned' = 付与, 'used' = 利用
+-- status: 'pending' = 付与予定（発送後付与など）, 'confirmed' = 確定（利用は常に確定）
+-- email_id: 最後に反映したメール
+CREATE TABLE IF NOT EXISTS point_transactions (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    order_id INTEGER NOT NULL,
+    kind TEXT NOT NULL CHECK(kind IN ('earned', 'used')),
+    points INTEGER NOT NULL,
+    status TEXT NOT NULL DEFAULT 'confirmed' CHECK(status IN ('pending', 'confirmed')),
+    email_id INTEGER,
+    created_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    UNIQUE (order_id, kind),
+    FOREIGN KEY (order_id) REFERENCES orders(id) ON DELETE CASCADE,
+    FOREIGN KEY (email_id) REFERENCES emails(id) ON DELETE SET NULL
+);
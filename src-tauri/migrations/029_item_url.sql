@@ -0,0 +1,3 @@
+-- ショップの商品詳細ページURL（注文確認メールのリンクから取得。トラッキング用パラメータは除去済み）
+-- 画像取得・価格ウォッチ・再購入時のリンクに使う。メールにリンクがない商品は NULL
+ALTER TABLE items ADD COLUMN item_url TEXT;
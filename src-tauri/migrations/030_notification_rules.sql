@@ -0,0 +1,33 @@
+-- スマート通知ルール
+-- 条件（NULL は条件なし）をすべて満たす注文が新たに現れたら、バッチ処理後にデスクトップ通知する
+-- min_amount: 注文合計（税込・調整額込み）の下限
+-- shop: ショップ名またはショップドメイン（大文字小文字を区別しない完全一致）
+-- tag: 注文内のいずれかの商品に付いたタグ（item_tags.tag）
+-- delivery_status: 最新の配送状況がこの値になったとき（例: 'shipped' =「発送されたら」）
+CREATE TABLE IF NOT EXISTS notification_rules (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    name TEXT NOT NULL,
+    is_enabled INTEGER NOT NULL DEFAULT 1 CHECK(is_enabled IN (0, 1)),
+    min_amount INTEGER,
+    shop TEXT,
+    tag TEXT,
+    delivery_status TEXT CHECK(delivery_status IS NULL OR delivery_status IN ('not_shipped', 'preparing', 'shipped', 'in_transit', 'out_for_delivery', 'delivered', 'failed', 'returned', 'cancelled')),
+    created_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+CREATE TRIGGER IF NOT EXISTS notification_rules_updated_at AFTER UPDATE ON notification_rules BEGIN
+    UPDATE notification_rules SET updated_at = CURRENT_TIMESTAMP WHERE id = NEW.id;
+END;
+
+-- ルールに一致済みの注文（同じ注文で繰り返し通知しない）
+-- order_key: 'ショップドメイン:注文番号'。全件再パースで orders.id が変わっても同じ注文とみなせるようにする
+-- notified: 0 はルール作成・変更時点で既に一致していた注文（通知せず記録のみ）
+CREATE TABLE IF NOT EXISTS notification_rule_hits (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    rule_id INTEGER NOT NULL,
+    order_key TEXT NOT NULL,
+    notified INTEGER NOT NULL DEFAULT 1 CHECK(notified IN (0, 1)),
+    created_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    FOREIGN KEY (rule_id) REFERENCES notification_rules(id) ON DELETE CASCADE,
+    UNIQUE (rule_id, order_key)
+);
@@ -0,0 +1,25 @@
+-- ウィッシュリスト（買うかどうか検討中の商品）
+-- name_normalized: normalize_product_name 済みの商品名。items.item_name_normalized と突合し、
+--   同名の商品を注文したらバッチパース後に status を 'purchased' にする
+-- item_url / target_price: 価格ウォッチで監視するショップの商品ページと希望価格
+-- status: 'considering'（検討中）/ 'purchased'（購入済み）/ 'dropped'（見送り）
+-- purchased_item_id / purchased_price: 突合した注文商品とその単価（希望価格との比較に使う）
+CREATE TABLE IF NOT EXISTS wishlist_items (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    name TEXT NOT NULL,
+    name_normalized TEXT NOT NULL UNIQUE,
+    item_url TEXT,
+    target_price INTEGER CHECK(target_price IS NULL OR target_price >= 0),
+    memo TEXT,
+    status TEXT NOT NULL DEFAULT 'considering' CHECK(status IN ('considering', 'purchased', 'dropped')),
+    purchased_item_id INTEGER,
+    purchased_price INTEGER,
+    purchased_at DATETIME,
+    created_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    FOREIGN KEY (purchased_item_id) REFERENCES items(id) ON DELETE SET NULL
+);
+CREATE INDEX IF NOT EXISTS idx_wishlist_items_status ON wishlist_items(status);
+CREATE TRIGGER IF NOT EXISTS wishlist_items_updated_at AFTER UPDATE ON wishlist_items BEGIN
+    UPDATE wishlist_items SET updated_at = CURRENT_TIMESTAMP WHERE id = NEW.id;
+END;
@@ -0,0 +1,17 @@
+-- 到着済み商品の保管場所（棚番号・箱番号）
+-- 再パースで items が作り直されても残るよう、item_receipt_reviews と同じビジネスキーで記録する
+-- ビジネスキー: (shop_domain, order_number, item_name)
+-- shelf_number / box_number: 自由入力（例: '押入れ上段' / 'B-3'）。どちらか一方は必須
+CREATE TABLE IF NOT EXISTS item_storage_locations (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    shop_domain TEXT NOT NULL,
+    order_number TEXT NOT NULL COLLATE NOCASE,
+    item_name TEXT NOT NULL,
+    shelf_number TEXT,
+    box_number TEXT,
+    created_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    CHECK(shelf_number IS NOT NULL OR box_number IS NOT NULL),
+    UNIQUE (shop_domain, order_number, item_name)
+);
+CREATE INDEX IF NOT EXISTS idx_item_storage_locations_place ON item_storage_locations(shelf_number, box_number);
@@ -0,0 +1,13 @@
+-- パーサー種別ごとの処理時間（バッチパースのボトルネック調査用）
+-- 候補パーサーでの dispatch（パース + 保存）1 回を 1 サンプルとし、失敗した試行も含める
+-- sample_count: 計測した試行の累計件数（うち failure_count 件は失敗）
+-- total_micros / max_micros: 処理時間の合計・最大（マイクロ秒）
+-- last_measured_at: 最後に計測した日時（UTC, datetime('now') 形式）
+CREATE TABLE IF NOT EXISTS parser_metrics (
+    parser_type TEXT PRIMARY KEY,
+    sample_count INTEGER NOT NULL DEFAULT 0,
+    failure_count INTEGER NOT NULL DEFAULT 0,
+    total_micros INTEGER NOT NULL DEFAULT 0,
+    max_micros INTEGER NOT NULL DEFAULT 0,
+    last_measured_at DATETIME
+);
@@ -0,0 +1,34 @@
+-- ゴミ箱（論理削除）の状態をビジネスキーで保持する
+-- 全件再パースで orders / items が作り直されても、同じキーの注文・商品は削除済みとして再登録する
+-- ビジネスキー: (shop_domain, order_number) / (shop_domain, order_number, item_name)
+CREATE TABLE IF NOT EXISTS trashed_orders (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    shop_domain TEXT NOT NULL,
+    order_number TEXT NOT NULL COLLATE NOCASE,
+    deleted_at DATETIME NOT NULL,
+    UNIQUE (shop_domain, order_number)
+);
+
+-- 注文は残したまま個別に削除した商品（注文ごと削除した商品は trashed_orders の deleted_at を引き継ぐ）
+CREATE TABLE IF NOT EXISTS trashed_items (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    shop_domain TEXT NOT NULL,
+    order_number TEXT NOT NULL COLLATE NOCASE,
+    item_name TEXT NOT NULL,
+    deleted_at DATETIME NOT NULL,
+    UNIQUE (shop_domain, order_number, item_name)
+);
+
+-- 既存のゴミ箱の内容を移行
+INSERT OR IGNORE INTO trashed_orders (shop_domain, order_number, deleted_at)
+SELECT COALESCE(shop_domain, ''), order_number, deleted_at
+FROM orders
+WHERE deleted_at IS NOT NULL AND order_number IS NOT NULL;
+
+INSERT OR IGNORE INTO trashed_items (shop_domain, order_number, item_name, deleted_at)
+SELECT COALESCE(o.shop_domain, ''), o.order_number, i.item_name, i.deleted_at
+FROM items i
+INNER JOIN orders o ON o.id = i.order_id
+WHERE i.deleted_at IS NOT NULL
+  AND o.order_number IS NOT NULL
+  AND (o.deleted_at IS NULL OR i.deleted_at <> o.deleted_at);
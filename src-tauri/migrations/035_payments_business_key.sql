@@ -0,0 +1,31 @@
+-- 支払い予定を注文のビジネスキーで保持する
+-- 全件再パースで orders が作り直されても手入力の支払い予定が消えないよう、
+-- order_id（ON DELETE CASCADE）ではなく item_overrides と同じビジネスキーで注文に紐付ける
+-- ビジネスキー: (shop_domain, order_number)
+CREATE TABLE IF NOT EXISTS payments_new (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    shop_domain TEXT NOT NULL,
+    order_number TEXT NOT NULL COLLATE NOCASE,
+    label TEXT,
+    amount INTEGER NOT NULL,
+    due_date DATE,
+    status TEXT NOT NULL DEFAULT 'pending' CHECK(status IN ('pending', 'paid', 'cancelled')),
+    paid_at DATETIME,
+    email_id INTEGER,
+    created_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    FOREIGN KEY (email_id) REFERENCES emails(id) ON DELETE SET NULL
+);
+
+INSERT INTO payments_new (id, shop_domain, order_number, label, amount, due_date, status, paid_at, email_id, created_at, updated_at)
+SELECT p.id, COALESCE(o.shop_domain, ''), o.order_number, p.label, p.amount, p.due_date, p.status,
+       p.paid_at, p.email_id, p.created_at, p.updated_at
+FROM payments p
+INNER JOIN orders o ON o.id = p.order_id
+WHERE o.order_number IS NOT NULL;
+
+DROP TABLE payments;
+ALTER TABLE payments_new RENAME TO payments;
+
+CREATE INDEX IF NOT EXISTS idx_payments_order_key ON payments(shop_domain, order_number);
+CREATE INDEX IF NOT EXISTS idx_payments_status_due_date ON payments(status, due_date);
@@ -0,0 +1,8 @@
+-- orders.order_number_normalized: 通知メールと元注文を突合するための正規化済み注文番号
+-- （全角→半角、空白・ハイフン類の除去、大文字統一、番号全体の先頭のゼロ埋め除去）。
+-- 値はバックエンドが normalize_order_number で設定する（SQL では同じ正規化ができないため、
+-- 既存の注文は正規化済み注文番号での検索前にバックエンドが補完する）。
+ALTER TABLE orders ADD COLUMN order_number_normalized TEXT;
+
+CREATE INDEX IF NOT EXISTS idx_orders_order_number_normalized_shop_domain
+ON orders(order_number_normalized, shop_domain);
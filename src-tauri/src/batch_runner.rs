@@ -34,6 +34,18 @@ use serde::Serialize;
 use std::time::Duration;
 use tauri::{Emitter, Runtime};
 use tokio::time::sleep;
+use ts_rs::TS;
+
+use crate::memory_usage::MemoryWatermark;
+
+/// `BatchProgressEvent` のスキーマバージョン
+///
+/// フィールドの追加・意味変更を行った場合はインクリメントし、
+/// フロントエンド側は `schema_version` を見て後方互換の処理を行う。
+pub const BATCH_PROGRESS_SCHEMA_VERSION: u32 = 1;
+
+/// 1件ごとの詳細ログを流すイベント名（デバッグモード時のみ送信）
+pub const BATCH_LOG_EVENT_NAME: &str = "batch-log";
 
 /// 進捗イベント送信用トレイト（テストでモック可能にするため）
 pub trait BatchEventEmitter: Send + Sync {
@@ -146,11 +158,76 @@ pub trait BatchTask: Send + Sync {
     ) -> Result<(), String> {
         Ok(())
     }
+
+    /// 詳細ログ（`batch-log`）に表示する入力の説明（メール件名など）。デフォルトは None。
+    fn describe_input(&self, _input: &Self::Input) -> Option<String> {
+        None
+    }
+
+    /// 詳細ログ（`batch-log`）に表示する処理結果の説明（選択パーサー・注文番号など）。デフォルトは None。
+    fn describe_output(&self, _output: &Self::Output) -> Option<String> {
+        None
+    }
+}
+
+/// 詳細ログの1件ごとの処理結果
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(export)]
+pub enum BatchLogStatus {
+    Success,
+    /// パーサー非マッチ等で対象外
+    Skipped,
+    Failed,
+}
+
+/// 処理中の1件ごとの詳細ログ（`BATCH_LOG_EVENT_NAME` で送信）
+///
+/// 進捗イベント（`BatchProgressEvent`）とは別チャネルで、デバッグモード時のみ送信する。
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export)]
+pub struct BatchLogEvent {
+    /// タスク名
+    pub task_name: String,
+    /// バッチ番号（1から開始）
+    pub batch_number: usize,
+    /// 全体での通番（0から開始）
+    pub item_index: usize,
+    /// 入力の説明（`BatchTask::describe_input`）
+    pub input: Option<String>,
+    pub status: BatchLogStatus,
+    /// 成功時は `BatchTask::describe_output`、失敗・スキップ時はエラーメッセージ
+    pub detail: Option<String>,
+}
+
+/// 進捗イベントの種別（全タスク共通）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(export)]
+pub enum BatchEventKind {
+    /// バッチ途中の進捗
+    Progress,
+    /// 正常完了
+    Complete,
+    /// エラー終了
+    Error,
+    /// ユーザーによるキャンセル
+    Cancelled,
+    /// タイムアウト
+    Timeout,
 }
 
 /// バッチ処理の進捗イベント（フロントエンドへの通知用）
-#[derive(Debug, Clone, Serialize)]
+///
+/// 全タスク（同期・パース・商品名解析・配送確認など）で同じスキーマを使用する。
+/// TS 型は `cargo test` 実行時に ts-rs で `src/bindings/` へ出力される。
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export)]
 pub struct BatchProgressEvent {
+    /// スキーマバージョン（`BATCH_PROGRESS_SCHEMA_VERSION`）
+    pub schema_version: u32,
+    /// イベント種別
+    pub kind: BatchEventKind,
     /// タスク名（"メール同期", "メールパース", "商品名パース" など）
     pub task_name: String,
     /// 現在のバッチ番号（1から開始）
@@ -197,6 +274,8 @@ impl BatchProgressEvent {
         status_message: String,
     ) -> Self {
         Self {
+            schema_version: BATCH_PROGRESS_SCHEMA_VERSION,
+            kind: BatchEventKind::Progress,
             task_name: task_name.to_string(),
             batch_number,
             batch_size,
@@ -220,6 +299,8 @@ impl BatchProgressEvent {
         status_message: String,
     ) -> Self {
         Self {
+            schema_version: BATCH_PROGRESS_SCHEMA_VERSION,
+            kind: BatchEventKind::Complete,
             task_name: task_name.to_string(),
             batch_number: 0,
             batch_size: 0,
@@ -244,6 +325,8 @@ impl BatchProgressEvent {
         error_message: String,
     ) -> Self {
         Self {
+            schema_version: BATCH_PROGRESS_SCHEMA_VERSION,
+            kind: BatchEventKind::Error,
             task_name: task_name.to_string(),
             batch_number: 0,
             batch_size: 0,
@@ -267,6 +350,8 @@ impl BatchProgressEvent {
         failed_count: usize,
     ) -> Self {
         Self {
+            schema_version: BATCH_PROGRESS_SCHEMA_VERSION,
+            kind: BatchEventKind::Cancelled,
             task_name: task_name.to_string(),
             batch_number: 0,
             batch_size: 0,
@@ -291,6 +376,8 @@ impl BatchProgressEvent {
         timeout_minutes: u64,
     ) -> Self {
         Self {
+            schema_version: BATCH_PROGRESS_SCHEMA_VERSION,
+            kind: BatchEventKind::Timeout,
             task_name: task_name.to_string(),
             batch_number: 0,
             batch_size: 0,
@@ -317,6 +404,44 @@ pub struct BatchResult<O> {
     pub failed_count: usize,
 }
 
+/// 低優先度モードのスロットリング（バッチサイズの上限とバッチ間の最小ディレイ）
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BatchThrottle {
+    /// 1バッチあたりの最大件数
+    pub max_batch_size: usize,
+    /// バッチ間の最小ディレイ（ミリ秒）
+    pub delay_ms: u64,
+}
+
+/// 入力をページ単位で供給する読み込み元（`BatchRunner::run_source` 用）
+///
+/// 全件を先に読み込むとメール本文などでメモリを圧迫するため、1 バッチ分ずつ取得する。
+#[async_trait]
+pub trait BatchInputSource<I: Send>: Send {
+    /// 次の最大 `limit` 件を返す。空の Vec を返すと終端とみなす
+    async fn next_page(&mut self, limit: usize) -> Result<Vec<I>, String>;
+}
+
+/// メモリ上の入力をそのまま順に返す読み込み元（`BatchRunner::run` 用）
+pub struct VecInputSource<I> {
+    inputs: std::vec::IntoIter<I>,
+}
+
+impl<I> VecInputSource<I> {
+    pub fn new(inputs: Vec<I>) -> Self {
+        Self {
+            inputs: inputs.into_iter(),
+        }
+    }
+}
+
+#[async_trait]
+impl<I: Send> BatchInputSource<I> for VecInputSource<I> {
+    async fn next_page(&mut self, limit: usize) -> Result<Vec<I>, String> {
+        Ok(self.inputs.by_ref().take(limit.max(1)).collect())
+    }
+}
+
 /// バッチ処理エンジン
 ///
 /// `BatchTask`を実装したタスクを、指定されたバッチサイズとディレイで実行します。
@@ -325,6 +450,8 @@ pub struct BatchRunner<T: BatchTask> {
     batch_size: usize,
     delay_ms: u64,
     timeout_minutes: Option<u64>,
+    log_stream: bool,
+    collect_outputs: bool,
 }
 
 impl<T: BatchTask> BatchRunner<T> {
@@ -340,6 +467,8 @@ impl<T: BatchTask> BatchRunner<T> {
             batch_size,
             delay_ms,
             timeout_minutes: None,
+            log_stream: false,
+            collect_outputs: true,
         }
     }
 
@@ -355,6 +484,33 @@ impl<T: BatchTask> BatchRunner<T> {
         self
     }
 
+    /// 低優先度モードのスロットリングを適用（ビルダーパターン）
+    ///
+    /// バッチサイズは `max_batch_size` で切り詰め、ディレイは元の値と長い方を使う。
+    /// `None` の場合は何もしない。
+    pub fn with_throttle(mut self, throttle: Option<BatchThrottle>) -> Self {
+        if let Some(throttle) = throttle {
+            self.batch_size = self.batch_size.min(throttle.max_batch_size.max(1));
+            self.delay_ms = self.delay_ms.max(throttle.delay_ms);
+        }
+        self
+    }
+
+    /// 1件ごとの詳細ログ（`batch-log` イベント）送信の有無を設定（ビルダーパターン）
+    pub fn with_log_stream(mut self, enabled: bool) -> Self {
+        self.log_stream = enabled;
+        self
+    }
+
+    /// 処理結果（`BatchResult::outputs`）を保持するかを設定（ビルダーパターン）
+    ///
+    /// 既定は保持する。出力を使わない呼び出し元は `false` にして、
+    /// 全件分の出力（同期ならメール本文）がメモリに残り続けないようにする。
+    pub fn with_collect_outputs(mut self, enabled: bool) -> Self {
+        self.collect_outputs = enabled;
+        self
+    }
+
     /// バッチ処理を実行
     ///
     /// # Arguments
@@ -373,6 +529,36 @@ impl<T: BatchTask> BatchRunner<T> {
         should_cancel: impl Fn() -> bool,
     ) -> Result<BatchResult<T::Output>, String> {
         let total_items = inputs.len();
+        self.run_source(
+            emitter,
+            total_items,
+            VecInputSource::new(inputs),
+            context,
+            should_cancel,
+        )
+        .await
+    }
+
+    /// 入力をページ単位で読み込みながらバッチ処理を実行
+    ///
+    /// 1 バッチ分（`batch_size` 件）ずつ `source` から取得し、処理後に解放する。
+    /// メール本文など大きな入力を全件メモリに載せずに処理できる。
+    ///
+    /// # Arguments
+    /// * `total_items` - 進捗表示用の処理対象件数（`source` が返す件数の見込み）
+    /// * `source` - 入力のページ読み込み元
+    pub async fn run_source<E, S>(
+        &self,
+        emitter: &E,
+        total_items: usize,
+        mut source: S,
+        context: &T::Context,
+        should_cancel: impl Fn() -> bool,
+    ) -> Result<BatchResult<T::Output>, String>
+    where
+        E: BatchEventEmitter,
+        S: BatchInputSource<T::Input>,
+    {
         let task_name = self.task.name();
         let event_name = self.task.event_name();
 
@@ -386,6 +572,7 @@ impl<T: BatchTask> BatchRunner<T> {
         );
 
         let start_time = std::time::Instant::now();
+        let mut memory = MemoryWatermark::start();
 
         if total_items == 0 {
             let event = BatchProgressEvent::complete(
@@ -403,13 +590,37 @@ impl<T: BatchTask> BatchRunner<T> {
             });
         }
 
-        let mut outputs: Vec<T::Output> = Vec::with_capacity(total_items);
+        let mut outputs: Vec<T::Output> = if self.collect_outputs {
+            Vec::with_capacity(total_items)
+        } else {
+            Vec::new()
+        };
         let mut success_count: usize = 0;
         let mut failed_count: usize = 0;
         let mut processed_count: usize = 0;
         let mut batch_number: usize = 0;
 
-        for chunk in inputs.chunks(self.batch_size) {
+        loop {
+            let chunk = match source.next_page(self.batch_size).await {
+                Ok(chunk) => chunk,
+                Err(e) => {
+                    log::error!("[{}] Failed to load inputs: {}", task_name, e);
+                    let event = BatchProgressEvent::error(
+                        task_name,
+                        total_items,
+                        processed_count,
+                        success_count,
+                        failed_count,
+                        format!("入力の読み込みエラー: {}", e),
+                    );
+                    emitter.emit_event(event_name, event);
+                    return Err(e);
+                }
+            };
+            if chunk.is_empty() {
+                break;
+            }
+
             // キャンセルチェック
             if should_cancel() {
                 log::info!("[{}] Processing cancelled by user", task_name);
@@ -477,7 +688,7 @@ impl<T: BatchTask> BatchRunner<T> {
             let batch_size = chunk.len();
 
             // before_batch フックを呼び出し
-            if let Err(e) = self.task.before_batch(chunk, context).await {
+            if let Err(e) = self.task.before_batch(&chunk, context).await {
                 log::error!("[{}] before_batch failed: {}", task_name, e);
                 let event = BatchProgressEvent::error(
                     task_name,
@@ -491,18 +702,33 @@ impl<T: BatchTask> BatchRunner<T> {
                 return Err(e);
             }
 
-            // process_batch でバッチ処理を実行
-            let chunk_vec: Vec<T::Input> = chunk.to_vec();
-            let batch_results = self.task.process_batch(chunk_vec, context).await;
+            // 詳細ログ用の入力説明は process_batch に入力を渡す前に取得しておく
+            let input_labels: Vec<Option<String>> = if self.log_stream {
+                chunk
+                    .iter()
+                    .map(|input| self.task.describe_input(input))
+                    .collect()
+            } else {
+                Vec::new()
+            };
+
+            // process_batch でバッチ処理を実行（入力はここで手放し、バッチ終了時に解放される）
+            let batch_results = self.task.process_batch(chunk, context).await;
 
             // 結果を集計
             let mut batch_success = 0;
             let mut batch_failed = 0;
-            for result in &batch_results {
-                match result {
-                    Ok(_) => {
+            for (index, result) in batch_results.iter().enumerate() {
+                let (status, detail) = match result {
+                    Ok(output) => {
                         success_count += 1;
                         batch_success += 1;
+                        let detail = if self.log_stream {
+                            self.task.describe_output(output)
+                        } else {
+                            None
+                        };
+                        (BatchLogStatus::Success, detail)
                     }
                     Err(e) => {
                         // パーサー非マッチ（設定対象外のメール）はスキップ扱い、失敗ではない
@@ -510,12 +736,25 @@ impl<T: BatchTask> BatchRunner<T> {
                             crate::parsers::email_parse_task::NO_MATCHING_PARSER_PREFIX,
                         ) {
                             log::debug!("[{}] Skipped (no matching shop): {}", task_name, e);
+                            (BatchLogStatus::Skipped, self.log_stream.then(|| e.clone()))
                         } else {
                             log::warn!("[{}] Item processing failed: {}", task_name, e);
                             failed_count += 1;
                             batch_failed += 1;
+                            (BatchLogStatus::Failed, self.log_stream.then(|| e.clone()))
                         }
                     }
+                };
+                if self.log_stream {
+                    let event = BatchLogEvent {
+                        task_name: task_name.to_string(),
+                        batch_number,
+                        item_index: processed_count,
+                        input: input_labels.get(index).cloned().flatten(),
+                        status,
+                        detail,
+                    };
+                    emitter.emit_event(BATCH_LOG_EVENT_NAME, event);
                 }
                 processed_count += 1;
             }
@@ -540,7 +779,9 @@ impl<T: BatchTask> BatchRunner<T> {
             }
 
             // 成功した結果を outputs に追加
-            outputs.extend(batch_results.into_iter().flatten());
+            if self.collect_outputs {
+                outputs.extend(batch_results.into_iter().flatten());
+            }
 
             // 進捗イベントを送信
             let event = BatchProgressEvent::progress(
@@ -565,6 +806,14 @@ impl<T: BatchTask> BatchRunner<T> {
                 batch_success,
                 batch_failed
             );
+            if let Some(usage) = memory.sample() {
+                log::debug!(
+                    "[{}] Memory after batch {}: {}",
+                    task_name,
+                    batch_number,
+                    usage
+                );
+            }
         }
 
         // 完了イベントを送信
@@ -586,6 +835,9 @@ impl<T: BatchTask> BatchRunner<T> {
             success_count,
             failed_count
         );
+        if let Some(summary) = memory.summary() {
+            log::info!("[{}] Memory usage: {}", task_name, summary);
+        }
 
         Ok(BatchResult {
             outputs,
@@ -640,6 +892,41 @@ mod tests {
                 Ok(format!("Result for {}", input))
             }
         }
+
+        fn describe_input(&self, input: &Self::Input) -> Option<String> {
+            Some(format!("input {}", input))
+        }
+
+        fn describe_output(&self, output: &Self::Output) -> Option<String> {
+            Some(output.clone())
+        }
+    }
+
+    /// 送信されたイベントを (イベント名, JSON) で記録するエミッター
+    #[derive(Default)]
+    struct RecordingEmitter {
+        events: std::sync::Mutex<Vec<(String, serde_json::Value)>>,
+    }
+
+    impl BatchEventEmitter for RecordingEmitter {
+        fn emit_event<S: Serialize + Clone>(&self, event: &str, payload: S) {
+            self.events
+                .lock()
+                .unwrap()
+                .push((event.to_string(), serde_json::to_value(payload).unwrap()));
+        }
+    }
+
+    impl RecordingEmitter {
+        fn logs(&self) -> Vec<serde_json::Value> {
+            self.events
+                .lock()
+                .unwrap()
+                .iter()
+                .filter(|(name, _)| name == BATCH_LOG_EVENT_NAME)
+                .map(|(_, payload)| payload.clone())
+                .collect()
+        }
     }
 
     #[test]
@@ -664,6 +951,8 @@ mod tests {
         assert!((event.progress_percent - 10.0).abs() < 0.01);
         assert!(!event.is_complete);
         assert!(event.error.is_none());
+        assert_eq!(event.schema_version, BATCH_PROGRESS_SCHEMA_VERSION);
+        assert_eq!(event.kind, BatchEventKind::Progress);
     }
 
     #[test]
@@ -696,6 +985,15 @@ mod tests {
         assert!(event.is_complete);
         assert_eq!(event.error, Some("Cancelled by user".to_string()));
         assert_eq!(event.status_message, "処理がキャンセルされました");
+        assert_eq!(event.kind, BatchEventKind::Cancelled);
+    }
+
+    #[test]
+    fn test_batch_progress_event_serializes_schema_fields() {
+        let event = BatchProgressEvent::complete("テスト", 1, 1, 0, "完了".to_string());
+        let json = serde_json::to_value(&event).unwrap();
+        assert_eq!(json["schema_version"], BATCH_PROGRESS_SCHEMA_VERSION);
+        assert_eq!(json["kind"], "complete");
     }
 
     #[test]
@@ -720,6 +1018,47 @@ mod tests {
         assert_eq!(runner.timeout_minutes, Some(30));
     }
 
+    #[test]
+    fn test_batch_runner_with_throttle() {
+        let throttle = BatchThrottle {
+            max_batch_size: 5,
+            delay_ms: 2000,
+        };
+        let runner = BatchRunner::new(
+            MockTask {
+                fail_indices: vec![],
+            },
+            10,
+            1000,
+        )
+        .with_throttle(Some(throttle));
+        assert_eq!(runner.batch_size, 5);
+        assert_eq!(runner.delay_ms, 2000);
+
+        // 元の設定の方が控えめならそのまま
+        let runner = BatchRunner::new(
+            MockTask {
+                fail_indices: vec![],
+            },
+            3,
+            5000,
+        )
+        .with_throttle(Some(throttle));
+        assert_eq!(runner.batch_size, 3);
+        assert_eq!(runner.delay_ms, 5000);
+
+        let runner = BatchRunner::new(
+            MockTask {
+                fail_indices: vec![],
+            },
+            10,
+            0,
+        )
+        .with_throttle(None);
+        assert_eq!(runner.batch_size, 10);
+        assert_eq!(runner.delay_ms, 0);
+    }
+
     #[test]
     fn test_batch_progress_event_timeout() {
         let event = BatchProgressEvent::timeout("テスト", 100, 50, 45, 5, 30);
@@ -785,6 +1124,44 @@ mod tests {
         assert_eq!(result.failed_count, 2);
     }
 
+    #[tokio::test]
+    async fn test_run_emits_batch_log_only_when_enabled() {
+        let emitter = RecordingEmitter::default();
+        BatchRunner::new(
+            MockTask {
+                fail_indices: vec![],
+            },
+            2,
+            0,
+        )
+        .run(&emitter, vec![0, 1], &(), || false)
+        .await
+        .unwrap();
+        assert!(emitter.logs().is_empty());
+
+        let emitter = RecordingEmitter::default();
+        BatchRunner::new(
+            MockTask {
+                fail_indices: vec![1],
+            },
+            2,
+            0,
+        )
+        .with_log_stream(true)
+        .run(&emitter, vec![0, 1, 2], &(), || false)
+        .await
+        .unwrap();
+        let logs = emitter.logs();
+        assert_eq!(logs.len(), 3);
+        assert_eq!(logs[0]["input"], "input 0");
+        assert_eq!(logs[0]["status"], "success");
+        assert_eq!(logs[0]["detail"], "Result for 0");
+        assert_eq!(logs[1]["status"], "failed");
+        assert_eq!(logs[1]["detail"], "Failed for index 1");
+        assert_eq!(logs[2]["batch_number"], 2);
+        assert_eq!(logs[2]["item_index"], 2);
+    }
+
     #[tokio::test]
     async fn test_run_cancelled() {
         use std::sync::atomic::{AtomicUsize, Ordering};
@@ -809,4 +1186,99 @@ mod tests {
         assert_eq!(result.success_count, 2);
         assert_eq!(result.failed_count, 0);
     }
+
+    /// ページ取得回数と要求件数を記録する読み込み元
+    struct PagedSource {
+        remaining: std::ops::Range<usize>,
+        requested: std::sync::Arc<std::sync::Mutex<Vec<usize>>>,
+        fail_on_page: Option<usize>,
+    }
+
+    #[async_trait]
+    impl BatchInputSource<usize> for PagedSource {
+        async fn next_page(&mut self, limit: usize) -> Result<Vec<usize>, String> {
+            let page_number = {
+                let mut requested = self.requested.lock().unwrap();
+                requested.push(limit);
+                requested.len()
+            };
+            if self.fail_on_page == Some(page_number) {
+                return Err("db error".to_string());
+            }
+            Ok(self.remaining.by_ref().take(limit).collect())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_run_source_reads_one_page_per_batch() {
+        let runner = BatchRunner::new(
+            MockTask {
+                fail_indices: vec![2],
+            },
+            2,
+            0,
+        );
+        let requested = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+        let source = PagedSource {
+            remaining: 0..5,
+            requested: requested.clone(),
+            fail_on_page: None,
+        };
+
+        let result = runner
+            .run_source(&NoopEmitter, 5, source, &(), || false)
+            .await
+            .unwrap();
+
+        assert_eq!(result.success_count, 4);
+        assert_eq!(result.failed_count, 1);
+        assert_eq!(result.outputs.len(), 4);
+        // 3 ページ（2+2+1）と終端確認の 1 回
+        assert_eq!(*requested.lock().unwrap(), vec![2, 2, 2, 2]);
+    }
+
+    #[tokio::test]
+    async fn test_run_source_error_stops_processing() {
+        let runner = BatchRunner::new(
+            MockTask {
+                fail_indices: vec![],
+            },
+            2,
+            0,
+        );
+        let requested = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+        let source = PagedSource {
+            remaining: 0..5,
+            requested: requested.clone(),
+            fail_on_page: Some(2),
+        };
+
+        let result = runner
+            .run_source(&NoopEmitter, 5, source, &(), || false)
+            .await;
+
+        assert_eq!(result.unwrap_err(), "db error");
+        assert_eq!(requested.lock().unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_run_without_collecting_outputs() {
+        let runner = BatchRunner::new(
+            MockTask {
+                fail_indices: vec![1],
+            },
+            2,
+            0,
+        )
+        .with_collect_outputs(false);
+
+        let result = runner
+            .run(&NoopEmitter, vec![0, 1, 2], &(), || false)
+            .await
+            .unwrap();
+
+        assert!(result.outputs.is_empty());
+        assert_eq!(result.success_count, 2);
+        assert_eq!(result.failed_count, 1);
+    }
 }
@@ -0,0 +1,245 @@
+//! サンプル .eml に対するパーサー出力のスナップショットテスト（開発者向け）
+//!
+//! ```text
+//! cargo run --bin parser_snapshot -- [--samples <dir>] [--update]
+//! ```
+//!
+//! - `<dir>`（既定はリポジトリ直下の `sample/`）配下の `.eml` を再帰的に列挙し、
+//!   候補パーサーの出力を `<ファイル名>.snapshot.json` と比較する
+//! - `--update` 指定時、またはスナップショットが無い場合は現在の出力で保存する
+//! - 差分があれば行差分を表示して終了コード 1 で終了する
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use paa_lib::gmail::eml::parse_eml;
+use paa_lib::parsers::snapshot::{default_shop_settings, diff_lines, snapshot_email};
+use paa_lib::plugins::build_registry;
+
+/// コマンドライン引数
+#[derive(Debug, Clone, PartialEq)]
+struct SnapshotArgs {
+    samples: PathBuf,
+    update: bool,
+}
+
+const USAGE: &str = "usage: parser_snapshot [--samples <dir>] [--update]";
+
+fn default_samples_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("..")
+        .join("sample")
+}
+
+fn parse_args(args: &[String]) -> Result<SnapshotArgs, String> {
+    let mut samples = None;
+    let mut update = false;
+
+    let mut iter = args.iter();
+    while let Some(flag) = iter.next() {
+        match flag.as_str() {
+            "--samples" => {
+                let value = iter
+                    .next()
+                    .ok_or_else(|| format!("Missing value for {flag}\n{USAGE}"))?;
+                samples = Some(PathBuf::from(value));
+            }
+            "--update" => update = true,
+            other => return Err(format!("Unknown argument: {other}\n{USAGE}")),
+        }
+    }
+
+    Ok(SnapshotArgs {
+        samples: samples.unwrap_or_else(default_samples_dir),
+        update,
+    })
+}
+
+/// `dir` 配下の `.eml` をパス順に列挙する
+fn collect_eml_files(dir: &Path) -> Result<Vec<PathBuf>, String> {
+    let mut files = Vec::new();
+    let entries =
+        fs::read_dir(dir).map_err(|e| format!("Failed to read {}: {e}", dir.display()))?;
+    for entry in entries {
+        let path = entry
+            .map_err(|e| format!("Failed to read {}: {e}", dir.display()))?
+            .path();
+        if path.is_dir() {
+            files.extend(collect_eml_files(&path)?);
+        } else if path
+            .extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("eml"))
+        {
+            files.push(path);
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+/// `foo.eml` → `foo.eml.snapshot.json`
+fn snapshot_path(eml_path: &Path) -> PathBuf {
+    let mut name = eml_path.as_os_str().to_os_string();
+    name.push(".snapshot.json");
+    PathBuf::from(name)
+}
+
+/// スナップショット 1 件の処理結果
+#[derive(Debug, Clone, PartialEq)]
+enum Outcome {
+    Unchanged,
+    Written,
+    Changed(Vec<String>),
+}
+
+fn run_one(
+    eml_path: &Path,
+    update: bool,
+    render: impl Fn(&[u8]) -> Result<String, String>,
+) -> Result<Outcome, String> {
+    let raw =
+        fs::read(eml_path).map_err(|e| format!("Failed to read {}: {e}", eml_path.display()))?;
+    let actual = render(&raw)?;
+
+    let path = snapshot_path(eml_path);
+    let expected = fs::read_to_string(&path).ok();
+    match expected {
+        Some(expected) if expected == actual => Ok(Outcome::Unchanged),
+        Some(expected) if !update => Ok(Outcome::Changed(diff_lines(&expected, &actual))),
+        _ => {
+            fs::write(&path, &actual)
+                .map_err(|e| format!("Failed to write {}: {e}", path.display()))?;
+            Ok(Outcome::Written)
+        }
+    }
+}
+
+fn main() {
+    let args = match parse_args(&std::env::args().skip(1).collect::<Vec<_>>()) {
+        Ok(args) => args,
+        Err(e) => {
+            eprintln!("{e}");
+            std::process::exit(2);
+        }
+    };
+
+    let registry = build_registry();
+    let settings = default_shop_settings(&registry);
+    let render = |raw: &[u8]| -> Result<String, String> {
+        let snapshot = snapshot_email(&registry, &settings, &parse_eml(raw));
+        serde_json::to_string_pretty(&snapshot)
+            .map(|json| json + "\n")
+            .map_err(|e| format!("Failed to serialize snapshot: {e}"))
+    };
+
+    let files = match collect_eml_files(&args.samples) {
+        Ok(files) => files,
+        Err(e) => {
+            eprintln!("{e}");
+            std::process::exit(2);
+        }
+    };
+
+    let mut changed = 0;
+    let mut failed = 0;
+    for path in &files {
+        match run_one(path, args.update, &render) {
+            Ok(Outcome::Unchanged) => {}
+            Ok(Outcome::Written) => println!("written: {}", snapshot_path(path).display()),
+            Ok(Outcome::Changed(diff)) => {
+                changed += 1;
+                println!("changed: {}", path.display());
+                for line in diff {
+                    println!("  {line}");
+                }
+            }
+            Err(e) => {
+                failed += 1;
+                eprintln!("error: {e}");
+            }
+        }
+    }
+
+    println!("{} files, {changed} changed, {failed} failed", files.len());
+    if changed > 0 {
+        println!("意図した変更であれば `--update` で更新してください。");
+    }
+    if changed > 0 || failed > 0 {
+        std::process::exit(1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn render(raw: &[u8]) -> Result<String, String> {
+        Ok(String::from_utf8_lossy(raw).to_uppercase())
+    }
+
+    #[test]
+    fn test_parse_args_defaults() {
+        let args = parse_args(&[]).unwrap();
+        assert_eq!(args.samples, default_samples_dir());
+        assert!(!args.update);
+    }
+
+    #[test]
+    fn test_parse_args_samples_and_update() {
+        let args = parse_args(&[
+            "--samples".to_string(),
+            "/tmp/mails".to_string(),
+            "--update".to_string(),
+        ])
+        .unwrap();
+        assert_eq!(args.samples, PathBuf::from("/tmp/mails"));
+        assert!(args.update);
+    }
+
+    #[test]
+    fn test_parse_args_rejects_unknown_and_missing_value() {
+        assert!(parse_args(&["--foo".to_string()]).is_err());
+        assert!(parse_args(&["--samples".to_string()]).is_err());
+    }
+
+    #[test]
+    fn test_collect_eml_files_recurses() {
+        let dir = TempDir::new().unwrap();
+        fs::create_dir(dir.path().join("shop")).unwrap();
+        fs::write(dir.path().join("b.eml"), "").unwrap();
+        fs::write(dir.path().join("shop").join("a.EML"), "").unwrap();
+        fs::write(dir.path().join("note.txt"), "").unwrap();
+
+        let files = collect_eml_files(dir.path()).unwrap();
+        assert_eq!(
+            files,
+            vec![
+                dir.path().join("b.eml"),
+                dir.path().join("shop").join("a.EML")
+            ]
+        );
+    }
+
+    #[test]
+    fn test_run_one_writes_then_compares() {
+        let dir = TempDir::new().unwrap();
+        let eml = dir.path().join("mail.eml");
+        fs::write(&eml, "abc\n").unwrap();
+
+        assert_eq!(run_one(&eml, false, render).unwrap(), Outcome::Written);
+        assert_eq!(
+            fs::read_to_string(dir.path().join("mail.eml.snapshot.json")).unwrap(),
+            "ABC\n"
+        );
+        assert_eq!(run_one(&eml, false, render).unwrap(), Outcome::Unchanged);
+
+        fs::write(&eml, "abd\n").unwrap();
+        assert_eq!(
+            run_one(&eml, false, render).unwrap(),
+            Outcome::Changed(vec!["- ABC".to_string(), "+ ABD".to_string()])
+        );
+        assert_eq!(run_one(&eml, true, render).unwrap(), Outcome::Written);
+        assert_eq!(run_one(&eml, false, render).unwrap(), Outcome::Unchanged);
+    }
+}
@@ -0,0 +1,634 @@
+//! 新しいパーサーのスキャフォールド生成（開発者向け）
+//!
+//! ```text
+//! cargo run --bin scaffold_parser -- --shop goodsmile --type send \
+//!     [--sender info@example.com] [--shop-name "ショップ名"]
+//! ```
+//!
+//! - `src/plugins/<shop>/` が無い場合: プラグイン（mod.rs）・parsers/mod.rs・パーサーを新規作成し、
+//!   `src/plugins/mod.rs` に `pub mod <shop>;` を追加する
+//! - 既存プラグインの場合: パーサーを追加し、parsers/mod.rs・`parser_types`・`get_parser`・
+//!   `default_shop_settings` に登録する
+//!
+//! 生成後は TODO を埋めて `cargo fmt` を実行すること。
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// コマンドライン引数
+#[derive(Debug, Clone, PartialEq)]
+struct ScaffoldArgs {
+    shop: String,
+    parser_kind: String,
+    sender: String,
+    shop_name: String,
+}
+
+impl ScaffoldArgs {
+    fn parser_type(&self) -> String {
+        format!("{}_{}", self.shop, self.parser_kind)
+    }
+
+    fn plugin_struct(&self) -> String {
+        format!("{}Plugin", pascal_case(&self.shop))
+    }
+
+    fn parser_struct(&self) -> String {
+        format!(
+            "{}{}Parser",
+            pascal_case(&self.shop),
+            pascal_case(&self.parser_kind)
+        )
+    }
+}
+
+const USAGE: &str =
+    "usage: scaffold_parser --shop <shop> --type <type> [--sender <address>] [--shop-name <name>]";
+
+/// モジュール名として使える識別子か（英小文字で始まる英小文字・数字・`_`）
+fn is_module_ident(s: &str) -> bool {
+    let mut chars = s.chars();
+    chars.next().is_some_and(|c| c.is_ascii_lowercase())
+        && chars.all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+}
+
+/// `premium_bandai` → `PremiumBandai`
+fn pascal_case(s: &str) -> String {
+    s.split('_')
+        .filter(|part| !part.is_empty())
+        .map(|part| {
+            let mut chars = part.chars();
+            chars
+                .next()
+                .map(|c| c.to_ascii_uppercase().to_string() + chars.as_str())
+                .unwrap_or_default()
+        })
+        .collect()
+}
+
+fn parse_args(args: &[String]) -> Result<ScaffoldArgs, String> {
+    let mut shop = None;
+    let mut parser_kind = None;
+    let mut sender = None;
+    let mut shop_name = None;
+
+    let mut iter = args.iter();
+    while let Some(flag) = iter.next() {
+        let slot = match flag.as_str() {
+            "--shop" => &mut shop,
+            "--type" => &mut parser_kind,
+            "--sender" => &mut sender,
+            "--shop-name" => &mut shop_name,
+            other => return Err(format!("Unknown argument: {other}\n{USAGE}")),
+        };
+        let value = iter
+            .next()
+            .ok_or_else(|| format!("Missing value for {flag}\n{USAGE}"))?;
+        *slot = Some(value.clone());
+    }
+
+    let shop = shop.ok_or_else(|| format!("--shop is required\n{USAGE}"))?;
+    let parser_kind = parser_kind.ok_or_else(|| format!("--type is required\n{USAGE}"))?;
+    for (name, value) in [("--shop", &shop), ("--type", &parser_kind)] {
+        if !is_module_ident(value) {
+            return Err(format!(
+                "{name} must be a lowercase identifier (a-z, 0-9, _): {value}"
+            ));
+        }
+    }
+
+    Ok(ScaffoldArgs {
+        sender: sender.unwrap_or_else(|| format!("TODO@{shop}.example.com")),
+        shop_name: shop_name.unwrap_or_else(|| shop.clone()),
+        shop,
+        parser_kind,
+    })
+}
+
+fn parser_template(args: &ScaffoldArgs) -> String {
+    let parser_struct = args.parser_struct();
+    format!(
+        r#"use crate::parsers::{{EmailParser, OrderInfo}};
+
+/// {shop_name} {kind} メール用パーサー
+///
+/// 件名：`TODO`
+/// 送信元：`{sender}`
+pub struct {parser_struct};
+
+impl EmailParser for {parser_struct} {{
+    fn parse(&self, email_body: &str) -> Result<OrderInfo, String> {{
+        let lines: Vec<&str> = email_body.lines().map(str::trim).collect();
+
+        // TODO: 実際のメール本文に合わせて注文番号の抽出を実装する
+        let order_number = lines
+            .iter()
+            .find_map(|line| line.strip_prefix("ご注文番号："))
+            .map(|s| s.trim().to_string())
+            .ok_or_else(|| "Order number not found".to_string())?;
+
+        // TODO: 商品・金額・配送情報の抽出を実装する
+        Ok(OrderInfo {{
+            order_number,
+            order_date: None,
+            delivery_address: None,
+            delivery_info: None,
+            items: Vec::new(),
+            subtotal: None,
+            shipping_fee: None,
+            total_amount: None,
+            tax_amount: None,
+            tax_included: true,
+        }})
+    }}
+}}
+
+#[cfg(test)]
+mod tests {{
+    use super::*;
+
+    // TODO: 匿名化した実メール本文に置き換える
+    fn sample_{kind}() -> &'static str {{
+        "ご注文番号：TEST-0001\n"
+    }}
+
+    #[test]
+    fn test_parse_{kind}_order_number() {{
+        let order = {parser_struct}.parse(sample_{kind}()).unwrap();
+        assert_eq!(order.order_number, "TEST-0001");
+    }}
+
+    #[test]
+    fn test_parse_{kind}_missing_order_number() {{
+        assert!({parser_struct}.parse("本文").is_err());
+    }}
+}}
+"#,
+        shop_name = args.shop_name,
+        kind = args.parser_kind,
+        sender = args.sender,
+    )
+}
+
+fn shop_setting_template(args: &ScaffoldArgs, indent: &str) -> String {
+    format!(
+        "{indent}DefaultShopSetting {{\n\
+         {indent}    shop_name: \"{shop_name}\".to_string(),\n\
+         {indent}    sender_address: \"{sender}\".to_string(),\n\
+         {indent}    parser_type: \"{parser_type}\".to_string(),\n\
+         {indent}    // TODO: 件名フィルターを設定する\n\
+         {indent}    subject_filters: None,\n\
+         {indent}}},\n",
+        shop_name = args.shop_name,
+        sender = args.sender,
+        parser_type = args.parser_type(),
+    )
+}
+
+fn parser_arm(args: &ScaffoldArgs) -> String {
+    format!(
+        "            \"{}\" => Some(Box::new(parsers::{}::{})),\n",
+        args.parser_type(),
+        args.parser_kind,
+        args.parser_struct()
+    )
+}
+
+fn plugin_template(args: &ScaffoldArgs) -> String {
+    format!(
+        r#"//! {shop_name} プラグイン
+//!
+//! `{sender}` から配信されるメールを取り込む。
+
+pub mod parsers;
+
+use async_trait::async_trait;
+
+use crate::parsers::EmailParser;
+use crate::repository::SqliteOrderRepository;
+
+use super::{{
+    apply_internal_date, apply_tax_info, derive_shop_domain, DefaultShopSetting, DispatchError,
+    DispatchOutcome, PluginRegistration, VendorPlugin,
+}};
+
+pub struct {plugin_struct};
+
+#[async_trait]
+impl VendorPlugin for {plugin_struct} {{
+    fn parser_types(&self) -> &[&str] {{
+        &["{parser_type}"]
+    }}
+
+    fn priority(&self) -> i32 {{
+        10
+    }}
+
+    fn get_parser(&self, parser_type: &str) -> Option<Box<dyn EmailParser>> {{
+        match parser_type {{
+{arm}            _ => None,
+        }}
+    }}
+
+    fn shop_name(&self) -> &str {{
+        "{shop_name}"
+    }}
+
+    fn default_shop_settings(&self) -> Vec<DefaultShopSetting> {{
+        vec![
+{setting}        ]
+    }}
+
+    #[allow(clippy::too_many_arguments)]
+    async fn dispatch(
+        &self,
+        parser_type: &str,
+        email_id: i64,
+        from_address: Option<&str>,
+        shop_name: &str,
+        internal_date: Option<i64>,
+        body: &str,
+        tx: &mut sqlx::Transaction<'_, sqlx::Sqlite>,
+    ) -> Result<DispatchOutcome, DispatchError> {{
+        let shop_domain = derive_shop_domain(from_address);
+
+        let mut order_info = {{
+            let parser = self.get_parser(parser_type).ok_or_else(|| {{
+                DispatchError::ParseFailed(format!("No parser for type: {{}}", parser_type))
+            }})?;
+            parser.parse(body).map_err(DispatchError::ParseFailed)?
+        }};
+
+        apply_internal_date(&mut order_info, internal_date);
+        apply_tax_info(&mut order_info, body);
+
+        log::debug!(
+            "[{{}}] email_id={{}} order_number={{}}",
+            parser_type,
+            email_id,
+            order_info.order_number
+        );
+
+        SqliteOrderRepository::save_order_in_tx(
+            tx,
+            &order_info,
+            Some(email_id),
+            shop_domain,
+            Some(shop_name.to_string()),
+        )
+        .await
+        .map_err(DispatchError::SaveFailed)?;
+
+        Ok(DispatchOutcome::OrderSaved(Box::new(order_info)))
+    }}
+}}
+
+inventory::submit!(PluginRegistration {{
+    factory: || Box::new({plugin_struct}),
+}});
+"#,
+        shop_name = args.shop_name,
+        sender = args.sender,
+        plugin_struct = args.plugin_struct(),
+        parser_type = args.parser_type(),
+        arm = parser_arm(args),
+        setting = shop_setting_template(args, "            "),
+    )
+}
+
+/// `pub mod <name>;` 行をアルファベット順の位置に追加する（既にあれば何もしない）
+fn add_pub_mod(source: &str, name: &str) -> String {
+    let line = format!("pub mod {name};");
+    if source.lines().any(|l| l.trim() == line) {
+        return source.to_string();
+    }
+
+    let mut lines: Vec<&str> = source.lines().collect();
+    let mod_positions: Vec<usize> = lines
+        .iter()
+        .enumerate()
+        .filter(|(_, l)| l.starts_with("pub mod ") && l.ends_with(';'))
+        .map(|(i, _)| i)
+        .collect();
+    let insert_at = mod_positions
+        .iter()
+        .find(|&&i| lines[i] > line.as_str())
+        .copied()
+        .or_else(|| mod_positions.last().map(|i| i + 1))
+        .unwrap_or(0);
+    lines.insert(insert_at, &line);
+
+    let mut out = lines.join("\n");
+    if source.ends_with('\n') {
+        out.push('\n');
+    }
+    out
+}
+
+/// `from` 以降で最初の `open` に対応する閉じ括弧の位置を返す
+fn find_matching_close(source: &str, from: usize, open: char, close: char) -> Option<usize> {
+    let start = from + source[from..].find(open)?;
+    let mut depth = 0usize;
+    for (i, c) in source[start..].char_indices() {
+        if c == open {
+            depth += 1;
+        } else if c == close {
+            depth -= 1;
+            if depth == 0 {
+                return Some(start + i);
+            }
+        }
+    }
+    None
+}
+
+/// 閉じ括弧の直前の要素にカンマがなければ補う
+fn needs_separator(before_close: &str) -> bool {
+    !matches!(
+        before_close.trim_end().chars().last(),
+        Some(',' | '[') | None
+    )
+}
+
+/// 既存プラグインの `parser_types` / `get_parser` / `default_shop_settings` に登録する
+fn register_in_plugin(source: &str, args: &ScaffoldArgs) -> Result<String, String> {
+    let parser_type = args.parser_type();
+    if source.contains(&format!("\"{parser_type}\"")) {
+        return Err(format!("{parser_type} is already registered in the plugin"));
+    }
+    let mut out = source.to_string();
+
+    // default_shop_settings（後方から挿入して前方の位置をずらさない）
+    let fn_pos = out
+        .find("fn default_shop_settings")
+        .ok_or("fn default_shop_settings not found")?;
+    let vec_pos = fn_pos + out[fn_pos..].find("vec![").ok_or("vec![ not found")?;
+    let close = find_matching_close(&out, vec_pos, '[', ']').ok_or("Unclosed vec![")?;
+    let sep = if needs_separator(&out[..close]) {
+        ","
+    } else {
+        ""
+    };
+    let insert_at = out[..close].trim_end().len();
+    out.replace_range(
+        insert_at..close,
+        &format!(
+            "{sep}\n{}        ",
+            shop_setting_template(args, "            ")
+        ),
+    );
+
+    // get_parser
+    let fn_pos = out.find("fn get_parser").ok_or("fn get_parser not found")?;
+    let fallback = fn_pos
+        + out[fn_pos..]
+            .find("_ => None")
+            .ok_or("`_ => None` arm not found in get_parser")?;
+    let line_start = out[..fallback].rfind('\n').map_or(0, |i| i + 1);
+    out.insert_str(line_start, &parser_arm(args));
+
+    // parser_types
+    let fn_pos = out
+        .find("fn parser_types")
+        .ok_or("fn parser_types not found")?;
+    // 戻り値の型 `&[&str]` ではなく関数本体の配列を探す
+    let body_pos = fn_pos
+        + out[fn_pos..]
+            .find('{')
+            .ok_or("parser_types body not found")?;
+    let array_pos = body_pos + out[body_pos..].find("&[").ok_or("&[ not found")?;
+    let close = find_matching_close(&out, array_pos, '[', ']').ok_or("Unclosed &[")?;
+    let sep = if needs_separator(&out[..close]) {
+        ", "
+    } else {
+        ""
+    };
+    out.insert_str(close, &format!("{sep}\"{parser_type}\""));
+
+    Ok(out)
+}
+
+fn write_new_file(path: &Path, content: &str) -> Result<(), String> {
+    if path.exists() {
+        return Err(format!("{} already exists", path.display()));
+    }
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create {}: {e}", parent.display()))?;
+    }
+    fs::write(path, content).map_err(|e| format!("Failed to write {}: {e}", path.display()))
+}
+
+fn read(path: &Path) -> Result<String, String> {
+    fs::read_to_string(path).map_err(|e| format!("Failed to read {}: {e}", path.display()))
+}
+
+fn write(path: &Path, content: &str) -> Result<(), String> {
+    fs::write(path, content).map_err(|e| format!("Failed to write {}: {e}", path.display()))
+}
+
+/// `plugins_dir`（`src/plugins`）配下にスキャフォールドを生成し、作成・更新したファイルを返す
+fn scaffold(plugins_dir: &Path, args: &ScaffoldArgs) -> Result<Vec<PathBuf>, String> {
+    let plugin_dir = plugins_dir.join(&args.shop);
+    let parser_path = plugin_dir
+        .join("parsers")
+        .join(format!("{}.rs", args.parser_kind));
+    if parser_path.exists() {
+        return Err(format!("{} already exists", parser_path.display()));
+    }
+
+    let mut touched = Vec::new();
+    if plugin_dir.exists() {
+        let plugin_mod = plugin_dir.join("mod.rs");
+        let registered = register_in_plugin(&read(&plugin_mod)?, args)?;
+
+        let parsers_mod = plugin_dir.join("parsers").join("mod.rs");
+        let parsers_source = read(&parsers_mod)?;
+
+        write_new_file(&parser_path, &parser_template(args))?;
+        write(
+            &parsers_mod,
+            &add_pub_mod(&parsers_source, &args.parser_kind),
+        )?;
+        write(&plugin_mod, &registered)?;
+        touched.extend([parser_path, parsers_mod, plugin_mod]);
+    } else {
+        let plugins_mod = plugins_dir.join("mod.rs");
+        let plugins_source = read(&plugins_mod)?;
+
+        write_new_file(&parser_path, &parser_template(args))?;
+        let parsers_mod = plugin_dir.join("parsers").join("mod.rs");
+        write_new_file(&parsers_mod, &format!("pub mod {};\n", args.parser_kind))?;
+        let plugin_mod = plugin_dir.join("mod.rs");
+        write_new_file(&plugin_mod, &plugin_template(args))?;
+        write(&plugins_mod, &add_pub_mod(&plugins_source, &args.shop))?;
+        touched.extend([parser_path, parsers_mod, plugin_mod, plugins_mod]);
+    }
+    Ok(touched)
+}
+
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let result = parse_args(&args).and_then(|args| {
+        let plugins_dir = Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("src")
+            .join("plugins");
+        scaffold(&plugins_dir, &args)
+    });
+
+    match result {
+        Ok(touched) => {
+            for path in touched {
+                println!("generated: {}", path.display());
+            }
+            println!("TODO を埋めてから `cargo fmt` と `cargo test` を実行してください。");
+        }
+        Err(e) => {
+            eprintln!("{e}");
+            std::process::exit(1);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn args(shop: &str, kind: &str) -> ScaffoldArgs {
+        parse_args(&[
+            "--shop".to_string(),
+            shop.to_string(),
+            "--type".to_string(),
+            kind.to_string(),
+            "--sender".to_string(),
+            "info@shop.example".to_string(),
+            "--shop-name".to_string(),
+            "テストショップ".to_string(),
+        ])
+        .unwrap()
+    }
+
+    #[test]
+    fn test_parse_args_validates_identifiers() {
+        let a = args("premium_bandai", "send");
+        assert_eq!(a.parser_type(), "premium_bandai_send");
+        assert_eq!(a.parser_struct(), "PremiumBandaiSendParser");
+        assert_eq!(a.plugin_struct(), "PremiumBandaiPlugin");
+
+        let err = parse_args(&[
+            "--shop".to_string(),
+            "Good-Smile".to_string(),
+            "--type".to_string(),
+            "send".to_string(),
+        ]);
+        assert!(err.is_err());
+        assert!(parse_args(&["--shop".to_string()]).is_err());
+    }
+
+    #[test]
+    fn test_add_pub_mod_keeps_alphabetical_order() {
+        let source = "//! doc\n\npub mod amazon;\npub mod dmm;\n\nuse x;\n";
+        assert_eq!(
+            add_pub_mod(source, "bandai"),
+            "//! doc\n\npub mod amazon;\npub mod bandai;\npub mod dmm;\n\nuse x;\n"
+        );
+        assert_eq!(
+            add_pub_mod(source, "zozo"),
+            "//! doc\n\npub mod amazon;\npub mod dmm;\npub mod zozo;\n\nuse x;\n"
+        );
+        assert_eq!(add_pub_mod(source, "dmm"), source);
+    }
+
+    #[test]
+    fn test_register_in_plugin_single_element_vec() {
+        let source = r#"    fn parser_types(&self) -> &[&str] {
+        &["shop_confirm"]
+    }
+
+    fn get_parser(&self, parser_type: &str) -> Option<Box<dyn EmailParser>> {
+        match parser_type {
+            "shop_confirm" => Some(Box::new(parsers::confirm::ShopConfirmParser)),
+            _ => None,
+        }
+    }
+
+    fn default_shop_settings(&self) -> Vec<DefaultShopSetting> {
+        vec![DefaultShopSetting {
+            shop_name: "テストショップ".to_string(),
+            sender_address: "info@shop.example".to_string(),
+            parser_type: "shop_confirm".to_string(),
+            subject_filters: Some(vec!["注文".to_string()]),
+        }]
+    }
+"#;
+        let out = register_in_plugin(source, &args("shop", "send")).unwrap();
+        assert!(out.contains(r#"&["shop_confirm", "shop_send"]"#));
+        assert!(out.contains(
+            "            \"shop_send\" => Some(Box::new(parsers::send::ShopSendParser)),\n            _ => None,"
+        ));
+        assert!(out.contains("        },\n            DefaultShopSetting {"));
+        assert!(out.contains("parser_type: \"shop_send\".to_string(),"));
+        assert!(out.contains("subject_filters: None,\n            },\n        ]\n    }"));
+
+        assert!(register_in_plugin(&out, &args("shop", "send")).is_err());
+    }
+
+    #[test]
+    fn test_scaffold_new_plugin() {
+        let tmp = TempDir::new().unwrap();
+        let plugins_dir = tmp.path();
+        fs::write(
+            plugins_dir.join("mod.rs"),
+            "pub mod amazon;\npub mod zozo;\n",
+        )
+        .unwrap();
+
+        let touched = scaffold(plugins_dir, &args("goodsmile", "send")).unwrap();
+        assert_eq!(touched.len(), 4);
+
+        let plugin = fs::read_to_string(plugins_dir.join("goodsmile/mod.rs")).unwrap();
+        assert!(plugin.contains("pub struct GoodsmilePlugin;"));
+        assert!(plugin.contains(r#"&["goodsmile_send"]"#));
+        assert!(plugin.contains("parsers::send::GoodsmileSendParser"));
+        assert!(plugin.contains("sender_address: \"info@shop.example\".to_string(),"));
+
+        let parsers_mod = fs::read_to_string(plugins_dir.join("goodsmile/parsers/mod.rs")).unwrap();
+        assert_eq!(parsers_mod, "pub mod send;\n");
+        let parser = fs::read_to_string(plugins_dir.join("goodsmile/parsers/send.rs")).unwrap();
+        assert!(parser.contains("impl EmailParser for GoodsmileSendParser"));
+        assert!(parser.contains("fn test_parse_send_order_number()"));
+
+        let plugins_mod = fs::read_to_string(plugins_dir.join("mod.rs")).unwrap();
+        assert_eq!(
+            plugins_mod,
+            "pub mod amazon;\npub mod goodsmile;\npub mod zozo;\n"
+        );
+
+        // 同じパーサーは二重に生成しない
+        assert!(scaffold(plugins_dir, &args("goodsmile", "send")).is_err());
+    }
+
+    #[test]
+    fn test_scaffold_adds_parser_to_existing_plugin() {
+        let tmp = TempDir::new().unwrap();
+        let plugins_dir = tmp.path();
+        fs::write(plugins_dir.join("mod.rs"), "pub mod goodsmile;\n").unwrap();
+        scaffold(plugins_dir, &args("goodsmile", "confirm")).unwrap();
+
+        let touched = scaffold(plugins_dir, &args("goodsmile", "cancel")).unwrap();
+        assert_eq!(touched.len(), 3);
+
+        let plugin = fs::read_to_string(plugins_dir.join("goodsmile/mod.rs")).unwrap();
+        assert!(plugin.contains(r#"&["goodsmile_confirm", "goodsmile_cancel"]"#));
+        assert!(plugin.contains("parsers::cancel::GoodsmileCancelParser"));
+        assert!(plugin.contains("parser_type: \"goodsmile_cancel\".to_string(),"));
+
+        let parsers_mod = fs::read_to_string(plugins_dir.join("goodsmile/parsers/mod.rs")).unwrap();
+        assert_eq!(parsers_mod, "pub mod cancel;\npub mod confirm;\n");
+        assert_eq!(
+            fs::read_to_string(plugins_dir.join("mod.rs")).unwrap(),
+            "pub mod goodsmile;\n"
+        );
+    }
+}
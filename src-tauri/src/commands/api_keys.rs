@@ -1,9 +1,44 @@
+use serde::Serialize;
 use tauri::Manager;
+use ts_rs::TS;
 
+use crate::e2e_mocks::is_e2e_mock_mode;
 use crate::gemini;
 use crate::gmail;
 use crate::google_search;
 
+/// APIキー検証（テスト呼び出し）の結果
+#[derive(Debug, Clone, PartialEq, Serialize, TS)]
+#[ts(export)]
+pub struct ApiKeyValidationResult {
+    pub valid: bool,
+    /// 利用者向けの説明（無効な場合は原因）
+    pub message: String,
+    /// 今月の残り検索回数（SerpApi のみ）
+    pub searches_left: Option<i64>,
+}
+
+impl ApiKeyValidationResult {
+    fn invalid(message: String) -> Self {
+        Self {
+            valid: false,
+            message,
+            searches_left: None,
+        }
+    }
+}
+
+/// 検証対象のキーを決める（未指定・空なら保存済みのキー）
+fn resolve_key_to_validate(
+    api_key: Option<String>,
+    load_saved: impl FnOnce() -> Result<String, String>,
+) -> Result<String, String> {
+    match api_key.map(|k| k.trim().to_string()) {
+        Some(key) if !key.is_empty() => Ok(key),
+        _ => load_saved(),
+    }
+}
+
 // =============================================================================
 // Gemini API Commands
 // =============================================================================
@@ -36,6 +71,40 @@ pub async fn save_gemini_api_key(
     Ok(())
 }
 
+/// Gemini APIキーで実際にモデル情報を取得し、キーの有効性とモデルへのアクセス権を確認する
+///
+/// `api_key` を省略すると保存済みのキーを検証する（保存前の確認には入力値を渡す）。
+#[tauri::command]
+pub async fn validate_gemini_api_key(
+    app_handle: tauri::AppHandle,
+    api_key: Option<String>,
+) -> Result<ApiKeyValidationResult, String> {
+    if is_e2e_mock_mode() {
+        log::info!("[E2E Mock] Gemini API key validation: returning valid");
+        return Ok(ApiKeyValidationResult {
+            valid: true,
+            message: "E2E mock".to_string(),
+            searches_left: None,
+        });
+    }
+
+    let app_data_dir = app_handle
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to get app data dir: {e}"))?;
+    let api_key = resolve_key_to_validate(api_key, || gemini::load_api_key(&app_data_dir))?;
+
+    let client = gemini::GeminiClient::new(api_key)?;
+    Ok(match client.validate_api_key().await {
+        Ok(()) => ApiKeyValidationResult {
+            valid: true,
+            message: "Gemini APIキーは有効です".to_string(),
+            searches_left: None,
+        },
+        Err(e) => ApiKeyValidationResult::invalid(e),
+    })
+}
+
 /// Gemini APIキーを削除
 #[tauri::command]
 pub async fn delete_gemini_api_key(app_handle: tauri::AppHandle) -> Result<(), String> {
@@ -65,18 +134,27 @@ pub async fn has_gmail_oauth_credentials(app_handle: tauri::AppHandle) -> Result
 }
 
 /// Gmail OAuth認証情報を保存（JSONから）
+///
+/// 戻り値はスコープに関する警告（JSON に gmail.readonly 以外のスコープがある、
+/// 保存済みトークンが広いスコープで許可されている等）。問題がなければ空。
 #[tauri::command]
 pub async fn save_gmail_oauth_credentials(
     app_handle: tauri::AppHandle,
     json_content: String,
-) -> Result<(), String> {
+) -> Result<Vec<String>, String> {
     let app_data_dir = app_handle
         .path()
         .app_data_dir()
         .map_err(|e| format!("Failed to get app data dir: {e}"))?;
     gmail::save_oauth_credentials_from_json(&app_data_dir, &json_content)?;
     log::info!("Gmail OAuth credentials saved successfully");
-    Ok(())
+
+    let mut warnings = gmail::credentials_json_scope_warnings(&json_content);
+    warnings.extend(gmail::token_scope_warnings(&app_data_dir));
+    for warning in &warnings {
+        log::warn!("{warning}");
+    }
+    Ok(warnings)
 }
 
 /// Gmail OAuth認証情報を削除
@@ -123,6 +201,43 @@ pub async fn save_google_search_api_key(
     Ok(())
 }
 
+/// SerpApi のアカウント情報を取得し、キーの有効性と残り検索回数を確認する
+///
+/// Account API は検索回数を消費しない。`api_key` を省略すると保存済みのキーを検証する。
+#[tauri::command]
+pub async fn validate_google_search_api_key(
+    app_handle: tauri::AppHandle,
+    api_key: Option<String>,
+) -> Result<ApiKeyValidationResult, String> {
+    if is_e2e_mock_mode() {
+        log::info!("[E2E Mock] SerpApi API key validation: returning valid");
+        return Ok(ApiKeyValidationResult {
+            valid: true,
+            message: "E2E mock".to_string(),
+            searches_left: None,
+        });
+    }
+
+    let app_data_dir = app_handle
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to get app data dir: {e}"))?;
+    let api_key = resolve_key_to_validate(api_key, || google_search::load_api_key(&app_data_dir))?;
+
+    let client = google_search::SerpApiClient::new(api_key)?;
+    Ok(match client.validate_api_key().await {
+        Ok(account) => ApiKeyValidationResult {
+            valid: true,
+            message: match account.plan_name {
+                Some(plan) => format!("SerpApi APIキーは有効です（{plan}）"),
+                None => "SerpApi APIキーは有効です".to_string(),
+            },
+            searches_left: account.total_searches_left,
+        },
+        Err(e) => ApiKeyValidationResult::invalid(e),
+    })
+}
+
 /// SerpApi API 設定を削除
 #[tauri::command]
 pub async fn delete_google_search_config(app_handle: tauri::AppHandle) -> Result<(), String> {
@@ -136,3 +251,25 @@ pub async fn delete_google_search_config(app_handle: tauri::AppHandle) -> Result
     log::info!("SerpApi config deleted successfully");
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_key_to_validate_prefers_input() {
+        let key = resolve_key_to_validate(Some(" new-key ".to_string()), || {
+            panic!("saved key should not be loaded")
+        })
+        .unwrap();
+        assert_eq!(key, "new-key");
+    }
+
+    #[test]
+    fn test_resolve_key_to_validate_falls_back_to_saved() {
+        let key = resolve_key_to_validate(Some("  ".to_string()), || Ok("saved".to_string()));
+        assert_eq!(key.unwrap(), "saved");
+        let err = resolve_key_to_validate(None, || Err("not found".to_string()));
+        assert_eq!(err.unwrap_err(), "not found");
+    }
+}
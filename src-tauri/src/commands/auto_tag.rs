@@ -0,0 +1,61 @@
+use sqlx::sqlite::SqlitePool;
+
+use crate::repository;
+
+#[tauri::command]
+pub async fn list_auto_tag_rules(
+    pool: tauri::State<'_, SqlitePool>,
+) -> Result<Vec<repository::AutoTagRule>, String> {
+    let repo = repository::SqliteAutoTagRuleRepository::new(pool.inner().clone());
+    repo.get_all().await
+}
+
+#[tauri::command]
+pub async fn add_auto_tag_rule(
+    pool: tauri::State<'_, SqlitePool>,
+    tag: String,
+    target: String,
+    keyword: String,
+    match_type: String,
+) -> Result<i64, String> {
+    let repo = repository::SqliteAutoTagRuleRepository::new(pool.inner().clone());
+    repo.add(tag, target, keyword, match_type).await
+}
+
+#[tauri::command]
+pub async fn update_auto_tag_rule(
+    pool: tauri::State<'_, SqlitePool>,
+    id: i64,
+    tag: String,
+    target: String,
+    keyword: String,
+    match_type: String,
+) -> Result<(), String> {
+    let repo = repository::SqliteAutoTagRuleRepository::new(pool.inner().clone());
+    repo.update(id, tag, target, keyword, match_type).await
+}
+
+#[tauri::command]
+pub async fn delete_auto_tag_rule(
+    pool: tauri::State<'_, SqlitePool>,
+    id: i64,
+) -> Result<(), String> {
+    let repo = repository::SqliteAutoTagRuleRepository::new(pool.inner().clone());
+    repo.delete(id).await
+}
+
+/// 既存の全アイテムにルールを遡及適用し、付与したタグ数を返す
+#[tauri::command]
+pub async fn apply_auto_tag_rules(pool: tauri::State<'_, SqlitePool>) -> Result<usize, String> {
+    let repo = repository::SqliteAutoTagRuleRepository::new(pool.inner().clone());
+    repo.apply_to_all_items().await
+}
+
+#[tauri::command]
+pub async fn get_item_tags(
+    pool: tauri::State<'_, SqlitePool>,
+    item_id: i64,
+) -> Result<Vec<String>, String> {
+    let repo = repository::SqliteAutoTagRuleRepository::new(pool.inner().clone());
+    repo.get_item_tags(item_id).await
+}
@@ -0,0 +1,33 @@
+use tauri_plugin_autostart::ManagerExt;
+
+/// OS のスタートアップから起動されたことを示すコマンドライン引数
+pub const AUTOSTART_ARG: &str = "--autostart";
+
+/// OS のスタートアップ（ログイン時の自動起動）から起動されたか
+pub fn is_autostart_launch() -> bool {
+    std::env::args().any(|arg| arg == AUTOSTART_ARG)
+}
+
+/// OS のスタートアップに登録されているかを取得する
+#[tauri::command]
+pub async fn get_autostart_enabled(app_handle: tauri::AppHandle) -> Result<bool, String> {
+    app_handle
+        .autolaunch()
+        .is_enabled()
+        .map_err(|e| format!("Failed to get autostart status: {e}"))
+}
+
+/// OS のスタートアップへの登録・解除を行う
+///
+/// 登録すると、ログイン時にウィンドウを表示せずトレイに常駐した状態で起動する（自動同期スケジューラ用）。
+#[tauri::command]
+pub async fn set_autostart(app_handle: tauri::AppHandle, enabled: bool) -> Result<(), String> {
+    log::info!("Updating autostart: {enabled}");
+    let autolaunch = app_handle.autolaunch();
+    let result = if enabled {
+        autolaunch.enable()
+    } else {
+        autolaunch.disable()
+    };
+    result.map_err(|e| format!("Failed to update autostart: {e}"))
+}
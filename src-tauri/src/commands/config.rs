@@ -19,6 +19,24 @@ pub fn validate_gemini_delay_seconds(delay_seconds: i64) -> Result<(), String> {
     Ok(())
 }
 
+/// Gemini RPM 上限のバリデーション（1〜10000）
+pub fn validate_gemini_rpm_limit(rpm_limit: i64) -> Result<(), String> {
+    if !(1..=10_000).contains(&rpm_limit) {
+        return Err("1分あたりのリクエスト数上限は1〜10000の範囲である必要があります".to_string());
+    }
+    Ok(())
+}
+
+/// Gemini TPM 上限のバリデーション（1000〜100000000）
+pub fn validate_gemini_tpm_limit(tpm_limit: i64) -> Result<(), String> {
+    if !(1_000..=100_000_000).contains(&tpm_limit) {
+        return Err(
+            "1分あたりのトークン数上限は1000〜100000000の範囲である必要があります".to_string(),
+        );
+    }
+    Ok(())
+}
+
 #[tauri::command]
 pub async fn get_gemini_config(
     app_handle: tauri::AppHandle,
@@ -63,6 +81,25 @@ pub async fn update_gemini_delay_seconds(
     config::save(&app_config_dir, &config)
 }
 
+#[tauri::command]
+pub async fn update_gemini_rate_limits(
+    app_handle: tauri::AppHandle,
+    rpm_limit: i64,
+    tpm_limit: i64,
+) -> Result<(), String> {
+    validate_gemini_rpm_limit(rpm_limit)?;
+    validate_gemini_tpm_limit(tpm_limit)?;
+    log::info!("Updating Gemini rate limits to: {rpm_limit} RPM, {tpm_limit} TPM");
+    let app_config_dir = app_handle
+        .path()
+        .app_config_dir()
+        .map_err(|e| format!("Failed to get app config dir: {e}"))?;
+    let mut config = config::load(&app_config_dir)?;
+    config.gemini.rpm_limit = rpm_limit;
+    config.gemini.tpm_limit = tpm_limit;
+    config::save(&app_config_dir, &config)
+}
+
 // ---------------------------------------------------------------------------
 // スケジューラ設定
 // ---------------------------------------------------------------------------
@@ -136,6 +173,147 @@ pub async fn update_scheduler_enabled(
     Ok(())
 }
 
+#[tauri::command]
+pub async fn get_debug_config(app_handle: tauri::AppHandle) -> Result<config::DebugConfig, String> {
+    let app_config_dir = app_handle
+        .path()
+        .app_config_dir()
+        .map_err(|e| format!("Failed to get app config dir: {e}"))?;
+    let config = config::load(&app_config_dir)?;
+    Ok(config.debug)
+}
+
+/// バッチ処理の詳細ログ（`batch-log` イベント）の有効/無効を切り替える（次回のバッチ実行から反映）
+#[tauri::command]
+pub async fn update_batch_log_stream(
+    app_handle: tauri::AppHandle,
+    enabled: bool,
+) -> Result<(), String> {
+    log::info!("Updating batch_log_stream to: {enabled}");
+    let app_config_dir = app_handle
+        .path()
+        .app_config_dir()
+        .map_err(|e| format!("Failed to get app config dir: {e}"))?;
+    let mut config = config::load(&app_config_dir)?;
+    config.debug.batch_log_stream = enabled;
+    config::save(&app_config_dir, &config)
+}
+
+#[tauri::command]
+pub async fn get_updater_config(
+    app_handle: tauri::AppHandle,
+) -> Result<config::UpdaterConfig, String> {
+    let app_config_dir = app_handle
+        .path()
+        .app_config_dir()
+        .map_err(|e| format!("Failed to get app config dir: {e}"))?;
+    let config = config::load(&app_config_dir)?;
+    Ok(config.updater)
+}
+
+/// アップデートの定期確認の有効/無効を切り替える（次回の定期確認から反映）
+#[tauri::command]
+pub async fn update_auto_update_check(
+    app_handle: tauri::AppHandle,
+    enabled: bool,
+) -> Result<(), String> {
+    log::info!("Updating updater.auto_check to: {enabled}");
+    let app_config_dir = app_handle
+        .path()
+        .app_config_dir()
+        .map_err(|e| format!("Failed to get app config dir: {e}"))?;
+    let mut config = config::load(&app_config_dir)?;
+    config.updater.auto_check = enabled;
+    config::save(&app_config_dir, &config)
+}
+
+#[tauri::command]
+pub async fn get_monthly_report_config(
+    app_handle: tauri::AppHandle,
+) -> Result<config::MonthlyReportConfig, String> {
+    let app_config_dir = app_handle
+        .path()
+        .app_config_dir()
+        .map_err(|e| format!("Failed to get app config dir: {e}"))?;
+    let config = config::load(&app_config_dir)?;
+    Ok(config.monthly_report)
+}
+
+/// 月初の「先月のまとめ」自動生成・通知の有効/無効を切り替える
+#[tauri::command]
+pub async fn update_monthly_report_enabled(
+    app_handle: tauri::AppHandle,
+    enabled: bool,
+) -> Result<(), String> {
+    log::info!("Updating monthly_report.enabled to: {enabled}");
+    let app_config_dir = app_handle
+        .path()
+        .app_config_dir()
+        .map_err(|e| format!("Failed to get app config dir: {e}"))?;
+    let mut config = config::load(&app_config_dir)?;
+    config.monthly_report.enabled = enabled;
+    config::save(&app_config_dir, &config)
+}
+
+#[tauri::command]
+pub async fn get_low_priority_config(
+    app_handle: tauri::AppHandle,
+) -> Result<config::LowPriorityConfig, String> {
+    let app_config_dir = app_handle
+        .path()
+        .app_config_dir()
+        .map_err(|e| format!("Failed to get app config dir: {e}"))?;
+    let config = config::load(&app_config_dir)?;
+    Ok(config.low_priority)
+}
+
+/// 低優先度モード（バッチ処理のスロットリング）の有効/無効を切り替える
+///
+/// 実行中のバッチには影響せず、次に開始するバッチから反映される。
+#[tauri::command]
+pub async fn update_low_priority_enabled(
+    app_handle: tauri::AppHandle,
+    enabled: bool,
+) -> Result<(), String> {
+    log::info!("Updating low_priority.enabled to: {enabled}");
+    let app_config_dir = app_handle
+        .path()
+        .app_config_dir()
+        .map_err(|e| format!("Failed to get app config dir: {e}"))?;
+    let mut config = config::load(&app_config_dir)?;
+    config.low_priority.enabled = enabled;
+    config::save(&app_config_dir, &config)
+}
+
+#[tauri::command]
+pub async fn get_image_search_config(
+    app_handle: tauri::AppHandle,
+) -> Result<config::ImageSearchConfig, String> {
+    let app_config_dir = app_handle
+        .path()
+        .app_config_dir()
+        .map_err(|e| format!("Failed to get app config dir: {e}"))?;
+    let config = config::load(&app_config_dir)?;
+    Ok(config.image_search)
+}
+
+/// 画像検索クエリの自動生成に使うテンプレートを更新する
+#[tauri::command]
+pub async fn update_image_search_query_template(
+    app_handle: tauri::AppHandle,
+    query_template: String,
+) -> Result<(), String> {
+    crate::google_search::validate_query_template(&query_template)?;
+    log::info!("Updating image_search.query_template to: {query_template}");
+    let app_config_dir = app_handle
+        .path()
+        .app_config_dir()
+        .map_err(|e| format!("Failed to get app config dir: {e}"))?;
+    let mut config = config::load(&app_config_dir)?;
+    config.image_search.query_template = query_template;
+    config::save(&app_config_dir, &config)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -148,6 +326,16 @@ mod tests {
         assert!(validate_gemini_batch_size(51).is_err());
     }
 
+    #[test]
+    fn test_validate_gemini_rate_limits_boundaries() {
+        assert!(validate_gemini_rpm_limit(1).is_ok());
+        assert!(validate_gemini_rpm_limit(10_000).is_ok());
+        assert!(validate_gemini_rpm_limit(0).is_err());
+        assert!(validate_gemini_tpm_limit(1_000).is_ok());
+        assert!(validate_gemini_tpm_limit(999).is_err());
+        assert!(validate_gemini_tpm_limit(100_000_001).is_err());
+    }
+
     #[test]
     fn test_validate_gemini_delay_seconds_boundaries() {
         assert!(validate_gemini_delay_seconds(0).is_ok());
@@ -1,6 +1,9 @@
 use sqlx::sqlite::SqlitePool;
+use tauri_plugin_opener::OpenerExt;
 
+use crate::delivery_check::tracking_page_url;
 use crate::orchestration;
+use crate::repository::SqliteDeliveryRepository;
 
 /// 配送状況確認バッチの多重実行ガード・キャンセル制御用状態（`BatchRunState` の薄いラッパー）
 #[derive(Clone, Default)]
@@ -62,3 +65,130 @@ pub async fn cancel_delivery_check(
     check_state.request_cancel();
     Ok(())
 }
+
+/// 追跡番号の区切り文字（ハイフン・空白）を除去する。英数字以外が残る場合は None
+fn normalize_tracking_number(tracking_number: &str) -> Option<String> {
+    let normalized: String = tracking_number
+        .chars()
+        .filter(|c| !c.is_whitespace() && !matches!(c, '-' | 'ー' | '－'))
+        .collect();
+    if normalized.is_empty() || !normalized.chars().all(|c| c.is_ascii_alphanumeric()) {
+        return None;
+    }
+    Some(normalized)
+}
+
+/// 発送メールに追跡番号が記載されない注文に、マイページ等で確認した追跡番号を手動登録する。
+/// 登録した配送は配送状況確認バッチの対象になる。登録（または更新）した deliveries.id を返す。
+#[tauri::command]
+pub async fn add_tracking_number(
+    pool: tauri::State<'_, SqlitePool>,
+    order_id: i64,
+    carrier: String,
+    tracking_number: String,
+) -> Result<i64, String> {
+    let carrier = carrier.trim();
+    if carrier.is_empty() {
+        return Err("配送業者を指定してください".to_string());
+    }
+    let tracking_number = normalize_tracking_number(&tracking_number)
+        .ok_or_else(|| format!("追跡番号の形式が正しくありません: {tracking_number}"))?;
+
+    let repo = SqliteDeliveryRepository::new(pool.inner().clone());
+    repo.add_tracking_number(order_id, carrier, &tracking_number)
+        .await
+}
+
+/// 通知のアクションボタンから追跡ページを開く際の action id
+pub const OPEN_TRACKING_ACTION_ID: &str = "open_tracking";
+
+/// 注文の追跡ページの URL を組み立てる（追跡番号のない注文・対応外の配送業者は Err）
+async fn resolve_tracking_page_url(pool: &SqlitePool, order_id: i64) -> Result<String, String> {
+    let repo = SqliteDeliveryRepository::new(pool.clone());
+    let delivery = repo
+        .get_latest_tracked_delivery(order_id)
+        .await?
+        .ok_or_else(|| format!("注文 {order_id} に追跡番号が登録されていません"))?;
+    tracking_page_url(&delivery.carrier, &delivery.tracking_number).ok_or_else(|| {
+        format!(
+            "配送業者「{}」の追跡ページには対応していません",
+            delivery.carrier
+        )
+    })
+}
+
+/// 注文の追跡ページを既定のブラウザで開き、開いた URL を返す
+///
+/// 配送通知のアクションボタン（`OPEN_TRACKING_ACTION_ID`）からも呼ばれる。
+pub async fn open_tracking_page_for_order(
+    app_handle: &tauri::AppHandle,
+    pool: &SqlitePool,
+    order_id: i64,
+) -> Result<String, String> {
+    let url = resolve_tracking_page_url(pool, order_id).await?;
+    app_handle
+        .opener()
+        .open_url(url.as_str(), None::<&str>)
+        .map_err(|e| format!("Failed to open tracking page: {e}"))?;
+    log::info!("Opened tracking page: order_id={order_id}");
+    Ok(url)
+}
+
+/// 注文の追跡ページを既定のブラウザで開く
+#[tauri::command]
+pub async fn open_tracking_page(
+    app_handle: tauri::AppHandle,
+    pool: tauri::State<'_, SqlitePool>,
+    order_id: i64,
+) -> Result<String, String> {
+    open_tracking_page_for_order(&app_handle, pool.inner(), order_id).await
+}
+
+/// notification-action イベントのペイロードから、追跡ページを開く対象の order_id を取り出す
+///
+/// `actionId` が `OPEN_TRACKING_ACTION_ID` で、通知の `extra.order_id` に注文IDがある場合のみ Some。
+pub fn tracking_order_id_from_notification_action(payload: &str) -> Option<i64> {
+    let value: serde_json::Value = serde_json::from_str(payload).ok()?;
+    if value.get("actionId")?.as_str()? != OPEN_TRACKING_ACTION_ID {
+        return None;
+    }
+    value
+        .get("notification")?
+        .get("extra")?
+        .get("order_id")?
+        .as_i64()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_tracking_number() {
+        assert_eq!(
+            normalize_tracking_number(" 3646-3189-0991 "),
+            Some("364631890991".to_string())
+        );
+        assert_eq!(
+            normalize_tracking_number("RR123456789JP"),
+            Some("RR123456789JP".to_string())
+        );
+        assert_eq!(normalize_tracking_number("-"), None);
+        assert_eq!(normalize_tracking_number("1234/5678"), None);
+    }
+
+    #[test]
+    fn test_tracking_order_id_from_notification_action() {
+        let payload =
+            r#"{"actionId":"open_tracking","notification":{"id":1,"extra":{"order_id":42}}}"#;
+        assert_eq!(
+            tracking_order_id_from_notification_action(payload),
+            Some(42)
+        );
+
+        let other = r#"{"actionId":"dismiss","notification":{"extra":{"order_id":42}}}"#;
+        assert_eq!(tracking_order_id_from_notification_action(other), None);
+        assert_eq!(tracking_order_id_from_notification_action("{}"), None);
+        assert_eq!(tracking_order_id_from_notification_action("invalid"), None);
+    }
+}
@@ -0,0 +1,193 @@
+use sqlx::sqlite::SqlitePool;
+use std::path::Path;
+use tauri::Manager;
+
+use crate::gmail::body_limit;
+use crate::gmail::eml::{build_eml, EmlParts};
+use crate::logic::anonymize::{anonymize_html, anonymize_text};
+use crate::repository::{EmailExportSource, SqliteEmailRepository};
+
+/// 同期時に切り詰めた body_html は、ファイルに保存した元の HTML があれば差し替える
+fn restore_truncated_body_html(source: &mut EmailExportSource, app_data_dir: &Path) {
+    body_limit::restore_truncated_body_html(
+        &mut source.body_html,
+        &source.message_id,
+        app_data_dir,
+    );
+}
+
+/// 切り詰めた body_html を元に戻すため、app_data_dir を取得してから差し替える
+fn restore_with_app_handle(app_handle: &tauri::AppHandle, source: &mut EmailExportSource) {
+    match app_handle.path().app_data_dir() {
+        Ok(dir) => restore_truncated_body_html(source, &dir),
+        Err(e) => log::warn!("Failed to get app data dir: {e}"),
+    }
+}
+
+/// エクスポートする .eml のバイト列を決定する
+///
+/// 同期時に保存した原本があればそれを、なければ DB の情報から再構築したものを返す。
+fn eml_bytes_for_export(source: EmailExportSource) -> Vec<u8> {
+    if let Some(raw) = source.raw {
+        return raw;
+    }
+    build_eml(&EmlParts {
+        message_id: &source.message_id,
+        subject: source.subject.as_deref(),
+        from_address: source.from_address.as_deref(),
+        internal_date: source.internal_date,
+        body_plain: source.body_plain.as_deref(),
+        body_html: source.body_html.as_deref(),
+    })
+    .into_bytes()
+}
+
+/// メールを .eml ファイルとして `path` に書き出す
+///
+/// 原本（sync.save_raw_eml 有効時に保存）があればそのまま、なければ本文から再構築する。
+/// 戻り値は原本をそのまま書き出した場合に true。
+#[tauri::command]
+pub async fn export_email_raw(
+    app_handle: tauri::AppHandle,
+    pool: tauri::State<'_, SqlitePool>,
+    email_id: i64,
+    path: String,
+) -> Result<bool, String> {
+    let repo = SqliteEmailRepository::new(pool.inner().clone());
+    let mut source = repo
+        .get_export_source(email_id)
+        .await?
+        .ok_or_else(|| format!("Email not found: {email_id}"))?;
+    let is_original = source.raw.is_some();
+    if !is_original {
+        restore_with_app_handle(&app_handle, &mut source);
+    }
+
+    let bytes = eml_bytes_for_export(source);
+    tokio::fs::write(&path, bytes)
+        .await
+        .map_err(|e| format!("Failed to write {path}: {e}"))?;
+
+    log::info!("Exported email {email_id} to {path} (original: {is_original})");
+    Ok(is_original)
+}
+
+/// 匿名化した .eml のバイト列を組み立てる
+///
+/// 原本は MIME エンコードされており安全に置換できないため、常に DB の本文から再構築する。
+/// 送信元アドレスはパーサー判定に必要なためそのまま残す。
+fn anonymized_eml_bytes(source: &EmailExportSource) -> Vec<u8> {
+    let from = source.from_address.as_deref();
+    let subject = source.subject.as_deref().map(|s| anonymize_text(s, from));
+    let body_plain = source
+        .body_plain
+        .as_deref()
+        .map(|b| anonymize_text(b, from));
+    let body_html = source.body_html.as_deref().map(|b| anonymize_html(b, from));
+    build_eml(&EmlParts {
+        message_id: &source.message_id,
+        subject: subject.as_deref(),
+        from_address: from,
+        internal_date: source.internal_date,
+        body_plain: body_plain.as_deref(),
+        body_html: body_html.as_deref(),
+    })
+    .into_bytes()
+}
+
+/// 氏名・住所・電話番号・メールアドレスを置換した匿名化コピーを .eml として `path` に書き出す
+///
+/// issue 添付用のサンプルメール作成を想定。置換は機械的なため、添付前に内容を確認すること。
+#[tauri::command]
+pub async fn anonymize_email(
+    app_handle: tauri::AppHandle,
+    pool: tauri::State<'_, SqlitePool>,
+    email_id: i64,
+    path: String,
+) -> Result<(), String> {
+    let repo = SqliteEmailRepository::new(pool.inner().clone());
+    let mut source = repo
+        .get_export_source(email_id)
+        .await?
+        .ok_or_else(|| format!("Email not found: {email_id}"))?;
+    restore_with_app_handle(&app_handle, &mut source);
+
+    let bytes = anonymized_eml_bytes(&source);
+    tokio::fs::write(&path, bytes)
+        .await
+        .map_err(|e| format!("Failed to write {path}: {e}"))?;
+
+    log::info!("Exported anonymized email {email_id} to {path}");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn source(raw: Option<Vec<u8>>) -> EmailExportSource {
+        EmailExportSource {
+            message_id: "msg1".to_string(),
+            subject: Some("件名".to_string()),
+            from_address: Some("shop@example.com".to_string()),
+            internal_date: Some(1704067200000),
+            body_plain: Some("本文".to_string()),
+            body_html: None,
+            raw,
+        }
+    }
+
+    #[test]
+    fn test_eml_bytes_prefers_stored_raw() {
+        let bytes = eml_bytes_for_export(source(Some(b"original".to_vec())));
+        assert_eq!(bytes, b"original");
+    }
+
+    #[test]
+    fn test_eml_bytes_rebuilds_without_raw() {
+        let bytes = eml_bytes_for_export(source(None));
+        let eml = String::from_utf8(bytes).unwrap();
+        assert!(eml.contains("From: shop@example.com\r\n"));
+        assert!(eml.contains("X-Gmail-Message-Id: msg1\r\n"));
+    }
+
+    #[test]
+    fn test_restore_truncated_body_html_from_attachment() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let limit = crate::gmail::BodyHtmlLimit {
+            max_bytes: 4,
+            overflow: crate::config::BodyHtmlOverflow::Attachment,
+            attachment_dir: Some(
+                dir.path()
+                    .join(crate::gmail::body_limit::EMAIL_BODIES_DIRNAME),
+            ),
+        };
+        let mut message = crate::gmail::GmailMessage {
+            message_id: "msg1".to_string(),
+            snippet: String::new(),
+            subject: None,
+            body_plain: None,
+            body_html: Some("<p>full body</p>".to_string()),
+            internal_date: 0,
+            from_address: None,
+        };
+        limit.apply(&mut message).unwrap();
+
+        let mut src = source(None);
+        src.body_html = message.body_html;
+        restore_truncated_body_html(&mut src, dir.path());
+        assert_eq!(src.body_html.as_deref(), Some("<p>full body</p>"));
+    }
+
+    #[test]
+    fn test_anonymized_eml_ignores_raw_and_keeps_sender() {
+        let mut src = source(Some(b"original hanako@gmail.com".to_vec()));
+        src.body_plain = Some("お名前：山田 花子 様\nhanako@gmail.com".to_string());
+        let eml = String::from_utf8(anonymized_eml_bytes(&src)).unwrap();
+        assert!(!eml.contains("original"));
+        assert!(eml.contains("From: shop@example.com\r\n"));
+
+        let body = anonymize_text(src.body_plain.as_deref().unwrap(), Some("shop@example.com"));
+        assert_eq!(body, "お名前：テスト 太郎 様\ntest@example.com");
+    }
+}
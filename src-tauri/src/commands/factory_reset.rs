@@ -0,0 +1,256 @@
+//! アプリ内データの完全消去（工場出荷リセット）
+//!
+//! 端末を手放す際に DB・画像・設定・認証情報・ログをすべて削除し、初期状態で再起動する。
+//!
+//! # 使用フロー
+//! 1. `prepare_factory_reset` で確認トークンを発行し、フロントエンドで最終確認を表示する
+//! 2. ユーザーが確認したら `factory_reset` にトークンを渡して実行する
+//!
+//! トークンは発行から `CONFIRM_TOKEN_TTL` の間だけ有効で、1 回使うと無効になる。
+//! 誤操作や古い画面からの呼び出しで削除が走らないようにするため。
+
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use sqlx::sqlite::SqlitePool;
+use tauri::Manager;
+
+use crate::db_lock::DbLockState;
+use crate::gemini;
+use crate::gmail;
+use crate::google_search;
+use crate::parsers::ParseState;
+
+/// 確認トークンの有効期間
+const CONFIRM_TOKEN_TTL: Duration = Duration::from_secs(5 * 60);
+
+/// DB 接続を閉じるまでの待ち時間（実行中のクエリが終わるのを待つ）
+const POOL_CLOSE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// 工場出荷リセットの確認トークン
+#[derive(Default)]
+pub struct FactoryResetState {
+    pending: Mutex<Option<(String, Instant)>>,
+}
+
+impl FactoryResetState {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 新しいトークンを発行する（発行済みのトークンは無効になる）
+    fn issue(&self, now: Instant) -> Result<String, String> {
+        let token = uuid::Uuid::new_v4().to_string();
+        let mut pending = self
+            .pending
+            .lock()
+            .map_err(|e| format!("Failed to lock factory reset state: {e}"))?;
+        *pending = Some((token.clone(), now));
+        Ok(token)
+    }
+
+    /// トークンを検証して消費する（一致しない・期限切れの場合はエラー）
+    fn consume(&self, token: &str, now: Instant) -> Result<(), String> {
+        let mut pending = self
+            .pending
+            .lock()
+            .map_err(|e| format!("Failed to lock factory reset state: {e}"))?;
+        match pending.take() {
+            Some((expected, issued_at))
+                if expected == token && now.duration_since(issued_at) <= CONFIRM_TOKEN_TTL =>
+            {
+                Ok(())
+            }
+            Some((_, issued_at)) if now.duration_since(issued_at) > CONFIRM_TOKEN_TTL => {
+                Err("確認の有効期限が切れました。もう一度やり直してください".to_string())
+            }
+            _ => Err("確認トークンが一致しません。もう一度やり直してください".to_string()),
+        }
+    }
+}
+
+/// 削除対象のディレクトリを重複・入れ子を除いて返す（存在しないものは除く）
+fn collect_reset_dirs(candidates: Vec<PathBuf>) -> Vec<PathBuf> {
+    let mut dirs: Vec<PathBuf> = candidates.into_iter().filter(|d| d.exists()).collect();
+    dirs.sort();
+    dirs.dedup();
+    let mut result: Vec<PathBuf> = Vec::new();
+    for dir in dirs {
+        // ソート済みのため、親ディレクトリは子より先に現れる
+        if !result.iter().any(|parent| dir.starts_with(parent)) {
+            result.push(dir);
+        }
+    }
+    result
+}
+
+/// ディレクトリを削除し、失敗したものを `パス: 原因` で返す
+fn remove_dirs(dirs: &[PathBuf]) -> Vec<String> {
+    dirs.iter()
+        .filter_map(|dir| {
+            std::fs::remove_dir_all(dir)
+                .err()
+                .map(|e| format!("{}: {e}", dir.display()))
+        })
+        .collect()
+}
+
+/// 工場出荷リセットの確認トークンを発行する
+#[tauri::command]
+pub async fn prepare_factory_reset(
+    state: tauri::State<'_, FactoryResetState>,
+) -> Result<String, String> {
+    state.issue(Instant::now())
+}
+
+/// DB・画像・設定・認証情報・ログをすべて削除し、アプリを再起動する
+///
+/// `token` は `prepare_factory_reset` で発行したもの。同期・パース中と、他の端末が DB を
+/// 使用中（読み取り専用で起動した場合）は実行できない。DB 接続を閉じた後は、一部のファイルを
+/// 削除できなかった場合も閉じた接続のまま動き続けないよう再起動する（失敗はログに残す）。
+#[tauri::command]
+pub async fn factory_reset(
+    app_handle: tauri::AppHandle,
+    pool: tauri::State<'_, SqlitePool>,
+    db_lock: tauri::State<'_, DbLockState>,
+    state: tauri::State<'_, FactoryResetState>,
+    sync_state: tauri::State<'_, gmail::SyncState>,
+    parse_state: tauri::State<'_, ParseState>,
+    token: String,
+) -> Result<(), String> {
+    state.consume(&token, Instant::now())?;
+    // 他の端末が使用中の DB を削除しない
+    db_lock.ensure_writable()?;
+    if sync_state.is_running() || parse_state.is_running() {
+        return Err(
+            "同期またはパースの実行中は初期化できません。完了後にやり直してください".to_string(),
+        );
+    }
+
+    // 失敗しうる処理は DB 接続を閉じる前に済ませる
+    let path = app_handle.path();
+    let app_data_dir = path
+        .app_data_dir()
+        .map_err(|e| format!("Failed to get app data dir: {e}"))?;
+
+    log::warn!("Factory reset started");
+
+    // DB ファイルを削除できるよう接続をすべて閉じる（ここから先は必ず再起動する）
+    if tokio::time::timeout(POOL_CLOSE_TIMEOUT, pool.close())
+        .await
+        .is_err()
+    {
+        log::warn!("Timed out waiting for database connections to close");
+    }
+    db_lock.release();
+
+    // 認証情報（OS のセキュアストレージ）。未登録の場合もエラーになるため警告のみ
+    for (name, result) in [
+        (
+            "Gmail OAuth",
+            gmail::delete_oauth_credentials(&app_data_dir),
+        ),
+        (
+            "Gemini API key",
+            gemini::config::delete_api_key(&app_data_dir),
+        ),
+        ("SerpApi key", google_search::delete_api_key(&app_data_dir)),
+    ] {
+        if let Err(e) = result {
+            log::warn!("Factory reset: skipped {name}: {e}");
+        }
+    }
+
+    // DB・設定（app_config_dir）、画像・トークン（app_data_dir）、WebView のセッション等
+    let dirs = collect_reset_dirs(
+        [
+            Ok(app_data_dir),
+            path.app_config_dir(),
+            path.app_local_data_dir(),
+            path.app_cache_dir(),
+            path.app_log_dir(),
+        ]
+        .into_iter()
+        .filter_map(Result::ok)
+        .collect(),
+    );
+    let failures = remove_dirs(&dirs);
+
+    // メモリ上のログも破棄する
+    crate::commands::init_log_buffer();
+
+    // 削除できなかったデータがあっても、閉じた DB 接続のまま動き続けないよう再起動する
+    if failures.is_empty() {
+        log::warn!("Factory reset completed, restarting");
+    } else {
+        log::error!(
+            "Factory reset: failed to remove {}, restarting",
+            failures.join(", ")
+        );
+    }
+    app_handle.restart()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_token_is_single_use() {
+        let state = FactoryResetState::new();
+        let now = Instant::now();
+        let token = state.issue(now).unwrap();
+
+        assert!(state.consume("wrong", now).is_err());
+        // 一致しないトークンで呼んでも発行済みトークンは破棄される
+        assert!(state.consume(&token, now).is_err());
+
+        let token = state.issue(now).unwrap();
+        assert!(state.consume(&token, now).is_ok());
+        assert!(state.consume(&token, now).is_err());
+    }
+
+    #[test]
+    fn test_token_expires() {
+        let state = FactoryResetState::new();
+        let now = Instant::now();
+        let token = state.issue(now).unwrap();
+        let err = state
+            .consume(&token, now + CONFIRM_TOKEN_TTL + Duration::from_secs(1))
+            .unwrap_err();
+        assert!(err.contains("有効期限"));
+    }
+
+    #[test]
+    fn test_collect_reset_dirs_skips_nested_and_missing() {
+        let root = tempfile::tempdir().unwrap();
+        let data = root.path().join("data");
+        let nested = data.join("images");
+        let config = root.path().join("config");
+        std::fs::create_dir_all(&nested).unwrap();
+        std::fs::create_dir_all(&config).unwrap();
+
+        let dirs = collect_reset_dirs(vec![
+            nested.clone(),
+            data.clone(),
+            config.clone(),
+            data.clone(),
+            root.path().join("missing"),
+        ]);
+        assert_eq!(dirs, vec![config, data]);
+    }
+
+    #[test]
+    fn test_remove_dirs() {
+        let root = tempfile::tempdir().unwrap();
+        let data = root.path().join("data");
+        std::fs::create_dir_all(data.join("images")).unwrap();
+        std::fs::write(data.join("paa_data.db"), b"db").unwrap();
+
+        assert!(remove_dirs(std::slice::from_ref(&data)).is_empty());
+        assert!(!data.exists());
+        // 存在しないディレクトリは失敗として返す
+        assert_eq!(remove_dirs(&[data]).len(), 1);
+    }
+}
@@ -0,0 +1,15 @@
+use sqlx::sqlite::SqlitePool;
+use std::path::PathBuf;
+
+use crate::report::household_csv::{self, HouseholdCsvOptions};
+
+/// 注文を家計簿アプリ（Zaim / MoneyForward ME）のインポート形式の CSV として `path` に保存し、
+/// 出力した支出の件数を返す
+#[tauri::command]
+pub async fn export_household_csv(
+    pool: tauri::State<'_, SqlitePool>,
+    options: HouseholdCsvOptions,
+    path: String,
+) -> Result<usize, String> {
+    household_csv::export_household_csv(pool.inner(), &options, &PathBuf::from(path)).await
+}
@@ -1,20 +1,38 @@
 use sqlx::sqlite::SqlitePool;
 use tauri::Manager;
 
+use crate::config;
 use crate::e2e_mocks::{is_e2e_mock_mode, E2EMockImageSearchClient};
+use crate::gemini::normalize_product_name;
 use crate::google_search;
 use crate::image_utils;
+use crate::repository::{ProductMasterRepository, SqliteProductMasterRepository};
 
 /// 商品画像を検索（SerpApi）
+///
+/// `optimize_query` が true の場合、`query`（商品名）の product_master 解析結果から
+/// 設定のテンプレートで検索クエリを組み立てる。未解析の商品は `query` をそのまま使う。
 #[tauri::command]
 pub async fn search_product_images(
     app_handle: tauri::AppHandle,
+    pool: tauri::State<'_, SqlitePool>,
     query: String,
     num_results: Option<u32>,
+    optimize_query: Option<bool>,
 ) -> Result<Vec<google_search::ImageSearchResult>, String> {
     use google_search::ImageSearchClientTrait;
 
     let num = num_results.unwrap_or(10);
+    let query = if optimize_query.unwrap_or(false) {
+        let app_config_dir = app_handle
+            .path()
+            .app_config_dir()
+            .map_err(|e| format!("Failed to get app config dir: {e}"))?;
+        let template = config::load(&app_config_dir)?.image_search.query_template;
+        build_optimized_query(pool.inner(), &query, &template).await?
+    } else {
+        query
+    };
 
     // E2Eモック時は外部APIを呼ばない
     if is_e2e_mock_mode() {
@@ -40,6 +58,27 @@ pub async fn search_product_images(
     client.search_images(&query, num).await
 }
 
+/// 商品名の product_master 解析結果から検索クエリを組み立てる（未解析なら商品名のまま）
+async fn build_optimized_query(
+    pool: &SqlitePool,
+    raw_name: &str,
+    template: &str,
+) -> Result<String, String> {
+    let repo = SqliteProductMasterRepository::new(pool.clone());
+    let product = match repo.find_by_raw_name(raw_name).await? {
+        Some(product) => Some(product),
+        None => {
+            repo.find_by_normalized_name(&normalize_product_name(raw_name))
+                .await?
+        }
+    };
+
+    Ok(match product {
+        Some(product) => google_search::build_image_search_query(template, raw_name, &product),
+        None => raw_name.to_string(),
+    })
+}
+
 /// 画像URLから画像をダウンロードしてimagesテーブルに保存
 #[tauri::command]
 pub async fn save_image_from_url(
@@ -50,13 +89,14 @@ pub async fn save_image_from_url(
 ) -> Result<String, String> {
     log::info!("Downloading image for item_id: {}", item_id);
 
-    let item_name_normalized: Option<String> =
-        sqlx::query_scalar("SELECT item_name_normalized FROM items WHERE id = ?")
-            .bind(item_id)
-            .fetch_optional(pool.inner())
-            .await
-            .map_err(|e| format!("Failed to get item_name_normalized: {e}"))?
-            .flatten();
+    let item_name_normalized: Option<String> = sqlx::query_scalar(
+        "SELECT item_name_normalized FROM items WHERE id = ? AND deleted_at IS NULL",
+    )
+    .bind(item_id)
+    .fetch_optional(pool.inner())
+    .await
+    .map_err(|e| format!("Failed to get item_name_normalized: {e}"))?
+    .flatten();
 
     let normalized = item_name_normalized.as_ref().ok_or_else(|| {
         "この商品は正規化できないため画像を登録できません。商品名に記号のみなどが含まれている可能性があります。".to_string()
@@ -1,8 +1,10 @@
 use serde::{Deserialize, Serialize};
 use std::collections::VecDeque;
 use std::sync::Mutex;
+use ts_rs::TS;
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
 pub struct LogEntry {
     pub timestamp: String,
     pub level: String,
@@ -79,10 +81,77 @@ pub fn add_log_entry(level: &str, message: &str) {
     }
 }
 
+/// ログの検索条件
+#[derive(Debug, Clone, Default)]
+pub struct LogQuery {
+    /// 対象とするログレベル（大文字小文字を区別しない）。空なら全レベル
+    pub levels: Vec<String>,
+    /// この時刻以降（`YYYY-MM-DD HH:MM:SS` 形式の JST。`T` 区切りや日付のみも可）
+    pub since: Option<String>,
+    /// この時刻以前（前方一致で比較するため `2026-10-16` ならその日の終わりまでを含む）
+    pub until: Option<String>,
+    /// 先頭（最新）から読み飛ばす件数
+    pub offset: usize,
+    /// 返却する最大件数
+    pub limit: Option<usize>,
+}
+
+/// 時刻指定を LogEntry.timestamp と比較できる形式に揃える
+fn normalize_timestamp_bound(value: &str) -> String {
+    value.trim().replacen('T', " ", 1)
+}
+
+impl LogQuery {
+    fn matches(&self, entry: &LogEntry) -> bool {
+        if !self.levels.is_empty()
+            && !self
+                .levels
+                .iter()
+                .any(|level| level.eq_ignore_ascii_case(&entry.level))
+        {
+            return false;
+        }
+        if let Some(since) = self.since.as_deref().map(normalize_timestamp_bound) {
+            if entry.timestamp.as_str() < since.as_str() {
+                return false;
+            }
+        }
+        if let Some(until) = self.until.as_deref().map(normalize_timestamp_bound) {
+            let prefix = entry
+                .timestamp
+                .get(..until.len())
+                .unwrap_or(&entry.timestamp);
+            if prefix > until.as_str() {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+/// ログエントリ（古い順）を検索条件で絞り込み、新しい順にページングして返す
+///
+/// メモリ上のバッファ以外のログ（ファイルログ等）も、古い順のイテレータとして渡せば同じ条件で検索できる。
+pub fn query_logs<'a>(
+    entries: impl DoubleEndedIterator<Item = &'a LogEntry>,
+    query: &LogQuery,
+) -> Vec<LogEntry> {
+    entries
+        .rev()
+        .filter(|entry| query.matches(entry))
+        .skip(query.offset)
+        .take(query.limit.unwrap_or(usize::MAX))
+        .cloned()
+        .collect()
+}
+
 /// ログエントリを取得
 ///
 /// # パラメータ
 /// - `level_filter`: ログレベルでフィルタリング（例: "ERROR", "INFO"）。Noneの場合は全てのレベルを返す
+/// - `levels`: 複数レベルの指定（例: `["ERROR", "WARN"]`）。`level_filter` と併用した場合は両方を対象にする
+/// - `since` / `until`: 時刻範囲（JST。`2026-10-16 12:00:00` / `2026-10-16T12:00` / `2026-10-16` 等）
+/// - `offset`: 新しい順で読み飛ばす件数（ページング用）
 /// - `limit`: 返却する最大件数。フィルタリング後のログに対して適用される
 ///
 /// # 戻り値
@@ -94,35 +163,28 @@ pub fn add_log_entry(level: &str, message: &str) {
 #[tauri::command]
 pub fn get_logs(
     level_filter: Option<String>,
+    levels: Option<Vec<String>>,
+    since: Option<String>,
+    until: Option<String>,
+    offset: Option<usize>,
     limit: Option<usize>,
 ) -> Result<Vec<LogEntry>, String> {
     let buffer = LOG_BUFFER
         .lock()
         .map_err(|e| format!("Failed to lock log buffer: {e}"))?;
 
-    if let Some(ref logs) = *buffer {
-        let mut filtered_logs: Vec<LogEntry> = logs
-            .iter()
-            .filter(|entry| {
-                if let Some(ref filter) = level_filter {
-                    &entry.level == filter
-                } else {
-                    true
-                }
-            })
-            .cloned()
-            .collect();
-
-        filtered_logs.reverse();
-
-        if let Some(limit) = limit {
-            filtered_logs.truncate(limit);
-        }
+    let Some(ref logs) = *buffer else {
+        return Ok(Vec::new());
+    };
 
-        Ok(filtered_logs)
-    } else {
-        Ok(Vec::new())
-    }
+    let query = LogQuery {
+        levels: levels.into_iter().flatten().chain(level_filter).collect(),
+        since,
+        until,
+        offset: offset.unwrap_or(0),
+        limit,
+    };
+    Ok(query_logs(logs.iter(), &query))
 }
 
 #[cfg(test)]
@@ -133,7 +195,7 @@ mod tests {
     fn test_log_buffer_initialization() {
         init_log_buffer();
         add_log_entry("INFO", "Test message");
-        let logs = get_logs(None, None);
+        let logs = get_logs(None, None, None, None, None, None);
         assert!(logs.is_ok());
     }
 
@@ -144,7 +206,7 @@ mod tests {
         init_log_buffer();
 
         add_log_entry("INFO", "Test after multiple init");
-        let logs = get_logs(None, None);
+        let logs = get_logs(None, None, None, None, None, None);
         assert!(logs.is_ok());
     }
 
@@ -163,7 +225,7 @@ mod tests {
             add_log_entry("INFO", &format!("Log entry {i}"));
         }
 
-        let logs = get_logs(None, None).unwrap();
+        let logs = get_logs(None, None, None, None, None, None).unwrap();
         assert!(logs.len() <= MAX_LOG_ENTRIES);
     }
 
@@ -175,7 +237,7 @@ mod tests {
         add_log_entry("ERROR", "Error message");
         add_log_entry("DEBUG", "Debug message");
 
-        let error_logs = get_logs(Some("ERROR".to_string()), None).unwrap();
+        let error_logs = get_logs(Some("ERROR".to_string()), None, None, None, None, None).unwrap();
         assert!(error_logs.iter().all(|log| log.level == "ERROR"));
     }
 
@@ -187,11 +249,83 @@ mod tests {
             add_log_entry("LIMIT_TEST", &format!("Message {i}"));
         }
 
-        let logs = get_logs(Some("LIMIT_TEST".to_string()), Some(5)).unwrap();
+        let logs = get_logs(
+            Some("LIMIT_TEST".to_string()),
+            None,
+            None,
+            None,
+            None,
+            Some(5),
+        )
+        .unwrap();
         assert!(
             logs.len() <= 5,
             "limit should restrict results to at most 5 entries"
         );
         assert!(logs.iter().all(|log| log.level == "LIMIT_TEST"));
     }
+
+    fn entry(timestamp: &str, level: &str, message: &str) -> LogEntry {
+        LogEntry {
+            timestamp: timestamp.to_string(),
+            level: level.to_string(),
+            message: message.to_string(),
+        }
+    }
+
+    fn sample_entries() -> Vec<LogEntry> {
+        vec![
+            entry("2026-10-15 23:59:59.000", "ERROR", "e1"),
+            entry("2026-10-16 09:00:00.000", "INFO", "i1"),
+            entry("2026-10-16 10:00:00.000", "WARN", "w1"),
+            entry("2026-10-16 11:00:00.000", "ERROR", "e2"),
+            entry("2026-10-17 00:00:00.000", "WARN", "w2"),
+        ]
+    }
+
+    fn messages(logs: &[LogEntry]) -> Vec<&str> {
+        logs.iter().map(|l| l.message.as_str()).collect()
+    }
+
+    #[test]
+    fn test_query_logs_multiple_levels() {
+        let entries = sample_entries();
+        let query = LogQuery {
+            levels: vec!["error".to_string(), "WARN".to_string()],
+            ..Default::default()
+        };
+        let logs = query_logs(entries.iter(), &query);
+        assert_eq!(messages(&logs), vec!["w2", "e2", "w1", "e1"]);
+    }
+
+    #[test]
+    fn test_query_logs_time_range() {
+        let entries = sample_entries();
+        // 日付のみの until はその日の終わりまでを含む
+        let query = LogQuery {
+            since: Some("2026-10-16T09:30".to_string()),
+            until: Some("2026-10-16".to_string()),
+            ..Default::default()
+        };
+        let logs = query_logs(entries.iter(), &query);
+        assert_eq!(messages(&logs), vec!["e2", "w1"]);
+    }
+
+    #[test]
+    fn test_query_logs_offset_and_limit() {
+        let entries = sample_entries();
+        let query = LogQuery {
+            offset: 1,
+            limit: Some(2),
+            ..Default::default()
+        };
+        let logs = query_logs(entries.iter(), &query);
+        assert_eq!(messages(&logs), vec!["e2", "w1"]);
+
+        let query = LogQuery {
+            offset: 10,
+            ..Default::default()
+        };
+        assert!(query_logs(entries.iter(), &query).is_empty());
+    }
 }
@@ -1,41 +1,83 @@
 pub mod amazon_session;
 pub mod api_keys;
+pub mod auto_tag;
+pub mod autostart;
 pub mod config;
 pub mod delivery_check;
+pub mod email_export;
 pub mod exclusion_patterns;
+pub mod factory_reset;
+pub mod household_export;
 pub mod image_search;
 pub mod log;
 pub mod metadata;
+pub mod monthly_report;
 pub mod news;
+pub mod notification_rules;
 pub mod ocr;
+pub mod order_document;
+pub mod order_search;
 pub mod overrides;
 pub mod parse;
+pub mod payment;
+pub mod point;
+pub mod price_anomaly;
 pub mod product_master;
 pub mod product_parse;
+pub mod receipt_review;
+pub mod reissue;
+pub mod reservation;
+pub mod series_master;
+pub mod setup;
 pub mod shop_settings;
 pub mod stats;
+pub mod storage_location;
 pub mod surugaya_session;
 pub mod sync;
+pub mod trash;
 pub mod ui_pipeline;
+pub mod updater;
 pub mod window;
+pub mod wishlist;
 
 pub use amazon_session::*;
 pub use api_keys::*;
+pub use auto_tag::*;
+pub use autostart::*;
 pub use config::*;
 pub use delivery_check::*;
+pub use email_export::*;
 pub use exclusion_patterns::*;
+pub use factory_reset::*;
+pub use household_export::*;
 pub use image_search::*;
 pub use log::*;
 pub use metadata::*;
+pub use monthly_report::*;
 pub use news::*;
+pub use notification_rules::*;
 pub use ocr::*;
+pub use order_document::*;
+pub use order_search::*;
 pub use overrides::*;
 pub use parse::*;
+pub use payment::*;
+pub use point::*;
+pub use price_anomaly::*;
 pub use product_master::*;
 pub use product_parse::*;
+pub use receipt_review::*;
+pub use reissue::*;
+pub use reservation::*;
+pub use series_master::*;
+pub use setup::*;
 pub use shop_settings::*;
 pub use stats::*;
+pub use storage_location::*;
 pub use surugaya_session::*;
 pub use sync::*;
+pub use trash::*;
 pub use ui_pipeline::*;
+pub use updater::*;
 pub use window::*;
+pub use wishlist::*;
@@ -0,0 +1,28 @@
+use sqlx::sqlite::SqlitePool;
+
+use crate::report::monthly_summary::{self, MonthlyReport};
+use crate::repository::{MonthlyReportRecord, SqliteMonthlyReportRepository};
+
+/// 月次レポート（まとめ）を手動で生成する。`month`（YYYY-MM）省略時は先月分。
+///
+/// 生成済みの月は上書きする。通知は行わない。
+#[tauri::command]
+pub async fn generate_monthly_report(
+    pool: tauri::State<'_, SqlitePool>,
+    month: Option<String>,
+) -> Result<MonthlyReport, String> {
+    let month_first = match month.as_deref().map(str::trim).filter(|m| !m.is_empty()) {
+        Some(m) => monthly_summary::parse_month(m)?,
+        None => monthly_summary::previous_month(monthly_summary::today_jst()),
+    };
+    monthly_summary::generate_monthly_report(pool.inner(), month_first).await
+}
+
+/// 保存済みの月次レポートを新しい月から取得する
+#[tauri::command]
+pub async fn list_monthly_reports(
+    pool: tauri::State<'_, SqlitePool>,
+) -> Result<Vec<MonthlyReportRecord>, String> {
+    let repo = SqliteMonthlyReportRepository::new(pool.inner().clone());
+    repo.list().await
+}
@@ -4,6 +4,7 @@ use serde::{Deserialize, Serialize};
 use sqlx::sqlite::SqlitePool;
 use std::time::Duration;
 use tauri::Manager;
+use ts_rs::TS;
 
 const MEDIA_NS: &str = "http://search.yahoo.com/mrss/";
 /// Dublin Core 名前空間（RDF/RSS 1.0 の dc:date など）
@@ -19,7 +20,8 @@ const GEMINI_TIMEOUT_SECS: u64 = 30;
 // RSS フィード取得
 // =============================================================================
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, TS)]
+#[ts(export)]
 pub struct NewsFeedItem {
     pub id: String,
     pub title: String,
@@ -192,7 +194,8 @@ fn normalize_jp_date(s: &str) -> Option<String> {
 }
 
 /// HTML スクレイピング用セレクタ設定（フロントエンドから受け取る）
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, TS)]
+#[ts(export)]
 pub struct HtmlScrapeSelectors {
     pub item: String,
     pub title: Option<String>,
@@ -384,7 +387,8 @@ pub async fn fetch_news_html(
 // =============================================================================
 
 /// AI が抽出したイベント日付
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, TS)]
+#[ts(export)]
 pub struct NewsClipEvent {
     pub date: String, // YYYY-MM-DD
     pub label: String,
@@ -430,7 +434,8 @@ fn is_valid_date_key(s: &str) -> bool {
     chrono::NaiveDate::from_ymd_opt(y, m, d).is_some()
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, TS)]
+#[ts(export)]
 pub struct NewsClip {
     pub id: i64,
     pub title: String,
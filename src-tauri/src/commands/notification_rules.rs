@@ -0,0 +1,49 @@
+use sqlx::sqlite::SqlitePool;
+
+use crate::repository::{
+    NotificationRule, NotificationRuleInput, SqliteNotificationRuleRepository,
+};
+
+/// スマート通知ルールの一覧を取得する
+#[tauri::command]
+pub async fn list_notification_rules(
+    pool: tauri::State<'_, SqlitePool>,
+) -> Result<Vec<NotificationRule>, String> {
+    SqliteNotificationRuleRepository::new(pool.inner().clone())
+        .list()
+        .await
+}
+
+/// スマート通知ルールを追加し、notification_rules.id を返す
+#[tauri::command]
+pub async fn add_notification_rule(
+    pool: tauri::State<'_, SqlitePool>,
+    rule: NotificationRuleInput,
+) -> Result<i64, String> {
+    SqliteNotificationRuleRepository::new(pool.inner().clone())
+        .add(&rule)
+        .await
+}
+
+/// スマート通知ルールを更新する
+#[tauri::command]
+pub async fn update_notification_rule(
+    pool: tauri::State<'_, SqlitePool>,
+    id: i64,
+    rule: NotificationRuleInput,
+) -> Result<(), String> {
+    SqliteNotificationRuleRepository::new(pool.inner().clone())
+        .update(id, &rule)
+        .await
+}
+
+/// スマート通知ルールを削除する
+#[tauri::command]
+pub async fn delete_notification_rule(
+    pool: tauri::State<'_, SqlitePool>,
+    id: i64,
+) -> Result<(), String> {
+    SqliteNotificationRuleRepository::new(pool.inner().clone())
+        .delete(id)
+        .await
+}
@@ -0,0 +1,25 @@
+use sqlx::sqlite::SqlitePool;
+use std::path::PathBuf;
+
+use crate::report::order_document::write_order_document;
+use crate::repository::SqliteOrderDocumentRepository;
+
+/// 注文情報（商品・金額・メール受信日・追跡番号）を印刷用の書類として `path` に保存する
+///
+/// `path` の拡張子が `.pdf` なら PDF、それ以外は HTML で出力する。
+#[tauri::command]
+pub async fn render_order_document(
+    pool: tauri::State<'_, SqlitePool>,
+    order_id: i64,
+    path: String,
+) -> Result<(), String> {
+    let doc = SqliteOrderDocumentRepository::new(pool.inner().clone())
+        .get(order_id)
+        .await?
+        .ok_or_else(|| format!("Order not found: {order_id}"))?;
+
+    let path = PathBuf::from(path);
+    tokio::task::spawn_blocking(move || write_order_document(&doc, &path))
+        .await
+        .map_err(|e| format!("Order document rendering task failed: {e}"))?
+}
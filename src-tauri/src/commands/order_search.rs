@@ -0,0 +1,47 @@
+use sqlx::sqlite::SqlitePool;
+
+use crate::repository::{OrderQuery, OrderSearchResult, SmartFilter, SqliteOrderSearchRepository};
+
+/// 複合条件で注文を検索する
+#[tauri::command]
+pub async fn search_orders(
+    pool: tauri::State<'_, SqlitePool>,
+    query: OrderQuery,
+) -> Result<Vec<OrderSearchResult>, String> {
+    SqliteOrderSearchRepository::new(pool.inner().clone())
+        .search(&query)
+        .await
+}
+
+/// 保存済みのスマートフィルタを取得する
+#[tauri::command]
+pub async fn list_smart_filters(
+    pool: tauri::State<'_, SqlitePool>,
+) -> Result<Vec<SmartFilter>, String> {
+    SqliteOrderSearchRepository::new(pool.inner().clone())
+        .list_filters()
+        .await
+}
+
+/// 検索条件をスマートフィルタとして保存し、smart_filters.id を返す（同名は上書き）
+#[tauri::command]
+pub async fn save_smart_filter(
+    pool: tauri::State<'_, SqlitePool>,
+    name: String,
+    query: OrderQuery,
+) -> Result<i64, String> {
+    SqliteOrderSearchRepository::new(pool.inner().clone())
+        .save_filter(&name, &query)
+        .await
+}
+
+/// スマートフィルタを削除する
+#[tauri::command]
+pub async fn delete_smart_filter(
+    pool: tauri::State<'_, SqlitePool>,
+    filter_id: i64,
+) -> Result<(), String> {
+    SqliteOrderSearchRepository::new(pool.inner().clone())
+        .delete_filter(filter_id)
+        .await
+}
@@ -2,12 +2,16 @@ use sqlx::sqlite::SqlitePool;
 use tauri::Manager;
 
 use crate::config;
-use crate::logic::email_parser::get_candidate_parsers;
+use crate::logic::email_parser::{
+    get_candidate_parsers, narrow_candidates_by_language, parser_language,
+};
+use crate::logic::language::detect_language;
 use crate::orchestration;
 use crate::parsers;
 use crate::plugins::{build_registry, find_plugin};
 use crate::repository::{
-    OrderRepository, ShopSettingsRepository, SqliteOrderRepository, SqliteShopSettingsRepository,
+    OrderRepository, ShopSettingsRepository, SqliteOrderRepository, SqliteParseUndoRepository,
+    SqliteShopSettingsRepository,
 };
 
 #[tauri::command]
@@ -53,6 +57,11 @@ pub async fn parse_and_save_email(
     // パーサーの参照をawaitの前で解放するため、同期ブロック内で完了させる
     let order_info = {
         let registry = build_registry();
+        // 日英両方のメールを送るショップ向けに、本文の言語に合うパーサーを優先する
+        let candidate_parsers =
+            narrow_candidates_by_language(candidate_parsers, detect_language(&email_body), |p| {
+                parser_language(&registry, p)
+            });
         let mut last_error = String::new();
         let mut result = None;
 
@@ -139,6 +148,88 @@ pub async fn cancel_parse(
     Ok(())
 }
 
+/// 直前のバッチパースを元に戻す（パース開始前の注文関連テーブルを復元する）
+///
+/// スナップショットは1回の復元で削除される。戻り値は復元後の注文数。
+#[tauri::command]
+pub async fn undo_last_parse(
+    app_handle: tauri::AppHandle,
+    pool: tauri::State<'_, SqlitePool>,
+    parse_state: tauri::State<'_, parsers::ParseState>,
+) -> Result<i64, String> {
+    let app_config_dir = app_handle
+        .path()
+        .app_config_dir()
+        .map_err(|e| format!("Failed to get app config dir: {e}"))?;
+
+    // 復元中にパースが始まらないよう、実行状態を確保してから復元する
+    parse_state
+        .try_start()
+        .map_err(|e| format!("Parse is running, cannot undo: {e}"))?;
+    let result = SqliteParseUndoRepository::new(pool.inner().clone())
+        .restore_snapshot(&orchestration::parse_undo_snapshot_path(&app_config_dir))
+        .await;
+    parse_state.finish();
+
+    let order_count = result?;
+    log::info!("Restored order tables from parse snapshot ({order_count} orders)");
+    Ok(order_count)
+}
+
+/// 1 注文に紐づくメールだけを再パースして注文を作り直す
+///
+/// 全体のフルリパースを行わずに 1 注文だけ直す用途。注文 ID は維持される。
+#[tauri::command]
+pub async fn reparse_order(
+    app_handle: tauri::AppHandle,
+    pool: tauri::State<'_, SqlitePool>,
+    parse_state: tauri::State<'_, parsers::ParseState>,
+    order_id: i64,
+) -> Result<parsers::ReparseOrderResult, String> {
+    // バッチパースと同時に注文を書き換えないよう、実行状態を確保してから再パースする
+    parse_state
+        .try_start()
+        .map_err(|e| format!("Parse is running, cannot reparse order: {e}"))?;
+    let app_data_dir = app_handle.path().app_data_dir().ok();
+    let result =
+        orchestration::reparse_order(pool.inner(), app_data_dir.as_deref(), order_id).await;
+    parse_state.finish();
+
+    let result = result?;
+    log::info!(
+        "Reparsed order {order_id}: {}/{} emails parsed",
+        result.parsed_count,
+        result.email_count
+    );
+    Ok(result)
+}
+
+/// DMM のまとめ完了・分割完了メールを適用し、適用前後の注文・商品の差分を返す
+///
+/// `dry_run` を省略した場合はシミュレーション（DB に反映しない）として扱う。
+#[tauri::command]
+pub async fn verify_order_restructure(
+    app_handle: tauri::AppHandle,
+    pool: tauri::State<'_, SqlitePool>,
+    parse_state: tauri::State<'_, parsers::ParseState>,
+    email_id: i64,
+    dry_run: Option<bool>,
+) -> Result<crate::repository::OrderRestructureReport, String> {
+    parse_state
+        .try_start()
+        .map_err(|e| format!("Parse is running, cannot verify order restructure: {e}"))?;
+    let app_data_dir = app_handle.path().app_data_dir().ok();
+    let result = orchestration::verify_order_restructure(
+        pool.inner(),
+        app_data_dir.as_deref(),
+        email_id,
+        dry_run.unwrap_or(true),
+    )
+    .await;
+    parse_state.finish();
+    result
+}
+
 #[tauri::command]
 pub async fn get_parse_status(
     app_handle: tauri::AppHandle,
@@ -0,0 +1,80 @@
+use sqlx::sqlite::SqlitePool;
+
+use crate::repository;
+
+/// 注文の支払い予定（内金・残金など）を期日順に取得する
+#[tauri::command]
+pub async fn list_order_payments(
+    pool: tauri::State<'_, SqlitePool>,
+    order_id: i64,
+) -> Result<Vec<repository::Payment>, String> {
+    let repo = repository::SqlitePaymentRepository::new(pool.inner().clone());
+    repo.list_by_order(order_id).await
+}
+
+/// 未払いの支払い予定を期日の近い順に取得する
+#[tauri::command]
+pub async fn list_pending_payments(
+    pool: tauri::State<'_, SqlitePool>,
+) -> Result<Vec<repository::Payment>, String> {
+    let repo = repository::SqlitePaymentRepository::new(pool.inner().clone());
+    repo.list_pending().await
+}
+
+/// 注文に支払い予定を追加し、payments.id を返す
+#[tauri::command]
+pub async fn add_order_payment(
+    pool: tauri::State<'_, SqlitePool>,
+    order_id: i64,
+    label: Option<String>,
+    amount: i64,
+    due_date: Option<String>,
+) -> Result<i64, String> {
+    if amount <= 0 {
+        return Err("支払い金額は 1 円以上を指定してください".to_string());
+    }
+    let due_date = due_date
+        .map(|d| d.trim().to_string())
+        .filter(|d| !d.is_empty());
+    if let Some(d) = &due_date {
+        chrono::NaiveDate::parse_from_str(d, "%Y-%m-%d")
+            .map_err(|_| format!("支払い期日の形式が正しくありません（YYYY-MM-DD）: {d}"))?;
+    }
+    let label = label
+        .map(|l| l.trim().to_string())
+        .filter(|l| !l.is_empty());
+
+    let repo = repository::SqlitePaymentRepository::new(pool.inner().clone());
+    repo.add(order_id, label.as_deref(), amount, due_date.as_deref())
+        .await
+}
+
+/// 支払い予定の状態を手動で更新する
+#[tauri::command]
+pub async fn set_payment_status(
+    pool: tauri::State<'_, SqlitePool>,
+    payment_id: i64,
+    status: repository::PaymentStatus,
+) -> Result<(), String> {
+    let repo = repository::SqlitePaymentRepository::new(pool.inner().clone());
+    repo.set_status(payment_id, status).await
+}
+
+/// 支払い予定を削除する
+#[tauri::command]
+pub async fn delete_order_payment(
+    pool: tauri::State<'_, SqlitePool>,
+    payment_id: i64,
+) -> Result<(), String> {
+    let repo = repository::SqlitePaymentRepository::new(pool.inner().clone());
+    repo.delete(payment_id).await
+}
+
+/// 支払い完了メールから未払いの支払い予定を消し込み、消し込んだ件数を返す
+#[tauri::command]
+pub async fn reconcile_payments_from_emails(
+    pool: tauri::State<'_, SqlitePool>,
+) -> Result<usize, String> {
+    let repo = repository::SqlitePaymentRepository::new(pool.inner().clone());
+    repo.reconcile_from_emails().await
+}
@@ -0,0 +1,21 @@
+use sqlx::sqlite::SqlitePool;
+
+use crate::repository;
+
+/// ショップごとの推定ポイント残高（確定分・付与予定分）を取得する
+#[tauri::command]
+pub async fn list_shop_point_balances(
+    pool: tauri::State<'_, SqlitePool>,
+) -> Result<Vec<repository::ShopPointBalance>, String> {
+    let repo = repository::SqlitePointRepository::new(pool.inner().clone());
+    repo.list_balances().await
+}
+
+/// メールに記載されたポイント利用・付与を注文ごとに記録し、追加・更新した件数を返す
+#[tauri::command]
+pub async fn collect_points_from_emails(
+    pool: tauri::State<'_, SqlitePool>,
+) -> Result<usize, String> {
+    let repo = repository::SqlitePointRepository::new(pool.inner().clone());
+    repo.collect_from_emails().await
+}
@@ -0,0 +1,19 @@
+use sqlx::sqlite::SqlitePool;
+
+use crate::repository;
+
+/// 過去購入価格と比較して大幅に高い購入（プレ値・定価超え）を検出し、注文に警告フラグを付ける
+///
+/// `ratio` は過去平均単価に対する倍率の閾値（省略時は 1.5）。
+#[tauri::command]
+pub async fn detect_price_anomalies(
+    pool: tauri::State<'_, SqlitePool>,
+    ratio: Option<f64>,
+) -> Result<Vec<repository::PriceAnomaly>, String> {
+    let ratio = ratio.unwrap_or(repository::DEFAULT_PRICE_ANOMALY_RATIO);
+    if !ratio.is_finite() || ratio <= 1.0 {
+        return Err(format!("ratio must be greater than 1.0: {ratio}"));
+    }
+    let repo = repository::SqlitePriceAnomalyRepository::new(pool.inner().clone());
+    repo.detect_and_flag(ratio).await
+}
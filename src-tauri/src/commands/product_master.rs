@@ -4,18 +4,21 @@ use crate::repository::{
 };
 use serde::{Deserialize, Serialize};
 use sqlx::sqlite::SqlitePool;
+use ts_rs::TS;
 
 const DEFAULT_LIMIT: i64 = 50;
 
 /// `get_product_master_list` のレスポンス
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, TS)]
+#[ts(export)]
 pub struct ProductMasterListResponse {
     pub items: Vec<ProductMaster>,
     pub total: i64,
 }
 
 /// `get_product_master_list` のフィルターパラメーター
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, TS)]
+#[ts(export)]
 pub struct ProductMasterFilterParams {
     pub raw_name: Option<String>,
     pub maker: Option<String>,
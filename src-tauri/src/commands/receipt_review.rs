@@ -0,0 +1,50 @@
+use sqlx::sqlite::SqlitePool;
+
+use crate::repository;
+
+/// 到着済みの商品に受け取り評価（満足度・破損有無）を記録する
+#[tauri::command]
+pub async fn save_item_receipt_review(
+    pool: tauri::State<'_, SqlitePool>,
+    item_id: i64,
+    satisfaction: i64,
+    damaged: bool,
+    note: Option<String>,
+) -> Result<(), String> {
+    if !(1..=5).contains(&satisfaction) {
+        return Err("満足度は 1〜5 で指定してください".to_string());
+    }
+    let note = note.map(|n| n.trim().to_string()).filter(|n| !n.is_empty());
+    let repo = repository::SqliteReceiptReviewRepository::new(pool.inner().clone());
+    repo.upsert(item_id, satisfaction, damaged, note.as_deref())
+        .await
+}
+
+/// 商品の受け取り評価を取得する（未評価なら None）
+#[tauri::command]
+pub async fn get_item_receipt_review(
+    pool: tauri::State<'_, SqlitePool>,
+    item_id: i64,
+) -> Result<Option<repository::ItemReceiptReview>, String> {
+    let repo = repository::SqliteReceiptReviewRepository::new(pool.inner().clone());
+    repo.get_by_item(item_id).await
+}
+
+/// 商品の受け取り評価を削除する
+#[tauri::command]
+pub async fn delete_item_receipt_review(
+    pool: tauri::State<'_, SqlitePool>,
+    item_id: i64,
+) -> Result<(), String> {
+    let repo = repository::SqliteReceiptReviewRepository::new(pool.inner().clone());
+    repo.delete_by_item(item_id).await
+}
+
+/// ショップ別の破損率・平均満足度を取得する
+#[tauri::command]
+pub async fn get_shop_damage_stats(
+    pool: tauri::State<'_, SqlitePool>,
+) -> Result<Vec<repository::ShopDamageStats>, String> {
+    let repo = repository::SqliteReceiptReviewRepository::new(pool.inner().clone());
+    repo.shop_damage_stats().await
+}
@@ -0,0 +1,73 @@
+use sqlx::sqlite::SqlitePool;
+
+use crate::repository::{ReissueDetection, ReissueWatch, SqliteReissueRepository};
+
+/// 再販検出結果の取得件数（省略時）
+const DEFAULT_REISSUE_DETECTION_LIMIT: i64 = 100;
+
+/// 再販ウォッチの一覧を取得する
+#[tauri::command]
+pub async fn list_reissue_watches(
+    pool: tauri::State<'_, SqlitePool>,
+) -> Result<Vec<ReissueWatch>, String> {
+    SqliteReissueRepository::new(pool.inner().clone())
+        .list_watches()
+        .await
+}
+
+/// 商品名を指定して再販ウォッチを追加し、reissue_watches.id を返す
+#[tauri::command]
+pub async fn add_reissue_watch(
+    pool: tauri::State<'_, SqlitePool>,
+    keyword: String,
+) -> Result<i64, String> {
+    SqliteReissueRepository::new(pool.inner().clone())
+        .add_watch(&keyword, None)
+        .await
+}
+
+/// 所持済みの商品を再販ウォッチに追加し、reissue_watches.id を返す
+#[tauri::command]
+pub async fn watch_item_reissue(
+    pool: tauri::State<'_, SqlitePool>,
+    item_id: i64,
+) -> Result<i64, String> {
+    SqliteReissueRepository::new(pool.inner().clone())
+        .add_watch_for_item(item_id)
+        .await
+}
+
+/// 再販ウォッチを削除する（検出結果も削除される）
+#[tauri::command]
+pub async fn delete_reissue_watch(
+    pool: tauri::State<'_, SqlitePool>,
+    watch_id: i64,
+) -> Result<(), String> {
+    SqliteReissueRepository::new(pool.inner().clone())
+        .delete_watch(watch_id)
+        .await
+}
+
+/// 再販の検出結果を新しい順に取得する（`limit` 省略時は 100 件）
+#[tauri::command]
+pub async fn list_reissue_detections(
+    pool: tauri::State<'_, SqlitePool>,
+    limit: Option<i64>,
+) -> Result<Vec<ReissueDetection>, String> {
+    let limit = limit.unwrap_or(DEFAULT_REISSUE_DETECTION_LIMIT);
+    if limit < 1 {
+        return Err(format!("limit must be at least 1: {limit}"));
+    }
+    SqliteReissueRepository::new(pool.inner().clone())
+        .list_detections(limit)
+        .await
+}
+
+/// メール・ニュースクリップから再販アナウンスを今すぐ検出し、新たに検出した件数を返す（未通知分は通知する）
+#[tauri::command]
+pub async fn check_reissues(
+    app: tauri::AppHandle,
+    pool: tauri::State<'_, SqlitePool>,
+) -> Result<usize, String> {
+    crate::reissue_watch::check_and_notify(&app, pool.inner()).await
+}
@@ -0,0 +1,66 @@
+use sqlx::sqlite::SqlitePool;
+
+use crate::repository;
+
+/// 未発送（予約）注文を確保状況つきで取得する
+#[tauri::command]
+pub async fn list_reservation_orders(
+    pool: tauri::State<'_, SqlitePool>,
+) -> Result<Vec<repository::ReservationOrder>, String> {
+    let repo = repository::SqliteReservationRepository::new(pool.inner().clone());
+    repo.list_unshipped().await
+}
+
+/// 予約注文の確保状況を手動（またはマイページ連携の結果）で更新する
+#[tauri::command]
+pub async fn set_reservation_status(
+    pool: tauri::State<'_, SqlitePool>,
+    order_id: i64,
+    status: Option<repository::ReservationStatus>,
+) -> Result<(), String> {
+    let repo = repository::SqliteReservationRepository::new(pool.inner().clone());
+    repo.set_status(order_id, status).await
+}
+
+/// 在庫確保・数量制限のお知らせ等のメールから確保状況を更新し、更新件数を返す
+#[tauri::command]
+pub async fn update_reservation_status_from_emails(
+    pool: tauri::State<'_, SqlitePool>,
+) -> Result<usize, String> {
+    let repo = repository::SqliteReservationRepository::new(pool.inner().clone());
+    repo.update_from_emails().await
+}
+
+/// 同一商品を複数ショップで予約している重複予約を検出する（ダブり予約のキャンセル判断用）
+#[tauri::command]
+pub async fn find_duplicate_preorders(
+    pool: tauri::State<'_, SqlitePool>,
+) -> Result<Vec<repository::DuplicatePreorder>, String> {
+    let repo = repository::SqliteReservationRepository::new(pool.inner().clone());
+    repo.find_duplicate_preorders().await
+}
+
+/// 発売予定のある商品を発売日順に取得する（`include_released` が false なら未発売のみ）
+#[tauri::command]
+pub async fn list_release_schedule(
+    pool: tauri::State<'_, SqlitePool>,
+    include_released: Option<bool>,
+) -> Result<Vec<repository::ReleaseScheduleItem>, String> {
+    let today = chrono::Utc::now()
+        .with_timezone(&chrono_tz::Asia::Tokyo)
+        .date_naive();
+    let repo = repository::SqliteReservationRepository::new(pool.inner().clone());
+    repo.list_by_release_date(include_released.unwrap_or(false), today)
+        .await
+}
+
+/// 指定月のお届け予定日・発売予定日を日付ごとにまとめて取得する（カレンダー表示用）
+#[tauri::command]
+pub async fn get_arrival_calendar(
+    pool: tauri::State<'_, SqlitePool>,
+    year: i32,
+    month: u32,
+) -> Result<repository::ArrivalCalendar, String> {
+    let repo = repository::SqliteArrivalCalendarRepository::new(pool.inner().clone());
+    repo.get_month(year, month).await
+}
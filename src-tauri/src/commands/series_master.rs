@@ -0,0 +1,69 @@
+use sqlx::sqlite::SqlitePool;
+
+use crate::repository;
+
+/// 統合済みシリーズ一覧（エイリアス・商品数つき）
+#[tauri::command]
+pub async fn list_series_master(
+    pool: tauri::State<'_, SqlitePool>,
+) -> Result<Vec<repository::SeriesMaster>, String> {
+    let repo = repository::SqliteSeriesMasterRepository::new(pool.inner().clone());
+    repo.list().await
+}
+
+/// product_master のシリーズ名を辞書・類似度で自動統合し、新規登録したエイリアス数を返す
+#[tauri::command]
+pub async fn sync_series_master(pool: tauri::State<'_, SqlitePool>) -> Result<usize, String> {
+    let repo = repository::SqliteSeriesMasterRepository::new(pool.inner().clone());
+    repo.sync_from_product_master().await
+}
+
+/// 表記を手動でシリーズに紐付ける
+#[tauri::command]
+pub async fn set_series_alias(
+    pool: tauri::State<'_, SqlitePool>,
+    series_id: i64,
+    alias: String,
+) -> Result<(), String> {
+    let repo = repository::SqliteSeriesMasterRepository::new(pool.inner().clone());
+    repo.set_alias(series_id, alias).await
+}
+
+#[tauri::command]
+pub async fn delete_series_alias(
+    pool: tauri::State<'_, SqlitePool>,
+    alias_id: i64,
+) -> Result<(), String> {
+    let repo = repository::SqliteSeriesMasterRepository::new(pool.inner().clone());
+    repo.delete_alias(alias_id).await
+}
+
+#[tauri::command]
+pub async fn rename_series(
+    pool: tauri::State<'_, SqlitePool>,
+    series_id: i64,
+    canonical_name: String,
+) -> Result<(), String> {
+    let repo = repository::SqliteSeriesMasterRepository::new(pool.inner().clone());
+    repo.rename(series_id, canonical_name).await
+}
+
+/// `source_id` のシリーズを `target_id` に統合する
+#[tauri::command]
+pub async fn merge_series(
+    pool: tauri::State<'_, SqlitePool>,
+    source_id: i64,
+    target_id: i64,
+) -> Result<(), String> {
+    let repo = repository::SqliteSeriesMasterRepository::new(pool.inner().clone());
+    repo.merge(source_id, target_id).await
+}
+
+/// シリーズ別の購入集計（統合後の正規名単位）
+#[tauri::command]
+pub async fn get_series_stats(
+    pool: tauri::State<'_, SqlitePool>,
+) -> Result<Vec<repository::SeriesStats>, String> {
+    let repo = repository::SqliteSeriesMasterRepository::new(pool.inner().clone());
+    repo.get_series_stats().await
+}
@@ -0,0 +1,62 @@
+use sqlx::sqlite::SqlitePool;
+use tauri::Manager;
+
+use crate::config::{self, SetupStep};
+use crate::gemini;
+use crate::gmail;
+use crate::logic::setup_status::{build_setup_status, SetupChecks, SetupStatus};
+
+/// 初回セットアップウィザード向けに、各ステップの設定状況と完了マークをまとめて返す
+#[tauri::command]
+pub async fn get_setup_status(
+    app_handle: tauri::AppHandle,
+    pool: tauri::State<'_, SqlitePool>,
+) -> Result<SetupStatus, String> {
+    let app_data_dir = app_handle
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to get app data dir: {e}"))?;
+    let app_config_dir = app_handle
+        .path()
+        .app_config_dir()
+        .map_err(|e| format!("Failed to get app config dir: {e}"))?;
+    let config = config::load(&app_config_dir)?;
+
+    let has_shop_settings: bool =
+        sqlx::query_scalar("SELECT EXISTS(SELECT 1 FROM shop_settings WHERE is_enabled = 1)")
+            .fetch_one(pool.inner())
+            .await
+            .map_err(|e| format!("Failed to check shop settings: {e}"))?;
+    let initial_sync_done: bool = sqlx::query_scalar("SELECT EXISTS(SELECT 1 FROM emails)")
+        .fetch_one(pool.inner())
+        .await
+        .map_err(|e| format!("Failed to check synced emails: {e}"))?;
+
+    let checks = SetupChecks {
+        has_oauth_credentials: gmail::has_oauth_credentials(&app_data_dir),
+        gmail_authorized: gmail::has_oauth_token(&app_data_dir),
+        has_shop_settings,
+        initial_sync_done,
+        has_gemini_api_key: gemini::has_api_key(&app_data_dir),
+    };
+    Ok(build_setup_status(checks, &config.setup.completed_steps))
+}
+
+/// セットアップステップの完了マークを保存する（`completed = false` で取り消す）
+///
+/// 任意ステップのスキップや、自動検出できない完了をウィザードから記録するために使う。
+#[tauri::command]
+pub async fn mark_setup_step(
+    app_handle: tauri::AppHandle,
+    step: SetupStep,
+    completed: bool,
+) -> Result<(), String> {
+    log::info!("Marking setup step {step:?} as completed={completed}");
+    let app_config_dir = app_handle
+        .path()
+        .app_config_dir()
+        .map_err(|e| format!("Failed to get app config dir: {e}"))?;
+    let mut config = config::load(&app_config_dir)?;
+    config.setup.mark(step, completed);
+    config::save(&app_config_dir, &config)
+}
@@ -1,8 +1,9 @@
 use sqlx::sqlite::SqlitePool;
 
 use crate::gmail;
+use crate::logic::parser_catalog::{build_parser_catalog, ParserCatalogEntry};
 use crate::plugins::{build_registry, ensure_default_settings};
-use crate::repository::SqliteShopSettingsRepository;
+use crate::repository::{self, SqliteShopSettingsRepository};
 
 #[tauri::command]
 pub async fn get_all_shop_settings(
@@ -73,3 +74,56 @@ pub async fn init_default_shop_settings(pool: tauri::State<'_, SqlitePool>) -> R
     let repo = SqliteShopSettingsRepository::new(pool.inner().clone());
     ensure_default_settings(&registry, &repo).await
 }
+
+/// 受信メールの送信元ドメインを集計し、shop_settings 未登録で件数の多いドメインを対応候補として返す
+///
+/// `min_count` は候補とする最小メール件数（省略時は 3）、`limit` は最大件数（省略時は 20）。
+#[tauri::command]
+pub async fn suggest_new_shops(
+    pool: tauri::State<'_, SqlitePool>,
+    min_count: Option<i64>,
+    limit: Option<i64>,
+) -> Result<Vec<repository::ShopCandidate>, String> {
+    let min_count = min_count.unwrap_or(repository::DEFAULT_SHOP_SUGGESTION_MIN_COUNT);
+    let limit = limit.unwrap_or(repository::DEFAULT_SHOP_SUGGESTION_LIMIT);
+    if min_count < 1 {
+        return Err(format!("min_count must be at least 1: {min_count}"));
+    }
+    if limit < 1 {
+        return Err(format!("limit must be at least 1: {limit}"));
+    }
+    let repo = repository::SqliteShopSuggestionRepository::new(pool.inner().clone());
+    repo.suggest_new_shops(min_count, limit).await
+}
+
+/// 全ショップ設定のパース統計（累計ヒット件数・最終ヒット日時）を返す
+#[tauri::command]
+pub async fn get_shop_setting_match_stats(
+    pool: tauri::State<'_, SqlitePool>,
+) -> Result<Vec<repository::ShopSettingMatchStats>, String> {
+    let repo = repository::SqliteShopSettingStatsRepository::new(pool.inner().clone());
+    repo.list_match_stats().await
+}
+
+/// 長期間パースにヒットしていない有効なショップ設定を無効化候補として返す
+///
+/// `inactive_days` は候補とするヒットなし日数（省略時は 365）。
+#[tauri::command]
+pub async fn suggest_shop_settings_to_disable(
+    pool: tauri::State<'_, SqlitePool>,
+    inactive_days: Option<i64>,
+) -> Result<Vec<repository::ShopSettingMatchStats>, String> {
+    let inactive_days =
+        inactive_days.unwrap_or(repository::DEFAULT_DISABLE_SUGGESTION_INACTIVE_DAYS);
+    if inactive_days < 1 {
+        return Err(format!("inactive_days must be at least 1: {inactive_days}"));
+    }
+    let repo = repository::SqliteShopSettingStatsRepository::new(pool.inner().clone());
+    repo.suggest_disable_candidates(inactive_days).await
+}
+
+/// 設定画面向けに、対応している全パーサーの表示名・対象ショップ・メール種別・想定件名例を返す
+#[tauri::command]
+pub fn get_parser_catalog() -> Vec<ParserCatalogEntry> {
+    build_parser_catalog(&build_registry())
+}
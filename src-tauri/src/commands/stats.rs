@@ -1,12 +1,33 @@
+use serde::Serialize;
 use sqlx::sqlite::SqlitePool;
+use std::path::{Path, PathBuf};
+use tauri::Manager;
+use ts_rs::TS;
 
+use crate::parsers::cancel_info::CancelReason;
+use crate::report::spending_chart::{parse_spending_period, render_spending_chart_png};
 use crate::repository::{
-    DeliveryStats, DeliveryStatsRepository, EmailStats, EmailStatsRepository, MiscStats,
-    MiscStatsRepository, OrderStats, OrderStatsRepository, ProductMasterStats,
-    ProductMasterStatsRepository, SqliteDeliveryStatsRepository, SqliteEmailStatsRepository,
-    SqliteMiscStatsRepository, SqliteOrderStatsRepository, SqliteProductMasterStatsRepository,
+    summarize_latencies, AnalysisViewVersion, CancelReasonStats, CancelStatsRepository,
+    DeliveryStats, DeliveryStatsRepository, EmailStats, EmailStatsRepository,
+    IngestionLatencyMetrics, LatencyMetricsRepository, MiscStats, MiscStatsRepository, OrderStats,
+    OrderStatsRepository, OverviewRepository, ParserPerformance, ProductMasterStats,
+    ProductMasterStatsRepository, SpendingStatsRepository, SqliteAnalysisViewRepository,
+    SqliteCancelStatsRepository, SqliteDeliveryStatsRepository, SqliteEmailStatsRepository,
+    SqliteLatencyMetricsRepository, SqliteMiscStatsRepository, SqliteOrderStatsRepository,
+    SqliteOverviewRepository, SqliteParserMetricsRepository, SqliteProductMasterStatsRepository,
+    SqliteSpendingStatsRepository, SqliteStatsSnapshotRepository, SqliteStorageStatsRepository,
+    StatsSnapshot, StatsSnapshotRepository, TableStorage, TodayOverview,
 };
 
+/// レイテンシ指標のデフォルト集計期間（日）
+const DEFAULT_LATENCY_METRICS_DAYS: i64 = 30;
+
+/// 統計推移のデフォルト取得期間（日）
+const DEFAULT_STATS_HISTORY_DAYS: i64 = 90;
+
+/// キャンセル統計のメーカー別ランキングのデフォルト件数
+const DEFAULT_CANCEL_MAKER_LIMIT: i64 = 20;
+
 /// E2E モード時に DB シードを実行。フロントエンドのマウント後に呼ぶ（マイグレーション完了後）
 #[tauri::command]
 pub async fn seed_e2e_db(pool: tauri::State<'_, SqlitePool>) -> Result<(), String> {
@@ -24,6 +45,14 @@ pub fn get_db_filename() -> &'static str {
     }
 }
 
+/// DB のアクセスモードを返す。他の端末が使用中で読み取り専用で起動した場合は使用中の端末も返す
+#[tauri::command]
+pub fn get_db_access_status(
+    state: tauri::State<'_, crate::db_lock::DbLockState>,
+) -> crate::db_lock::DbAccessStatus {
+    state.status()
+}
+
 /// メール統計情報を取得
 #[tauri::command]
 pub async fn get_email_stats(pool: tauri::State<'_, SqlitePool>) -> Result<EmailStats, String> {
@@ -63,6 +92,240 @@ pub async fn get_misc_stats(pool: tauri::State<'_, SqlitePool>) -> Result<MiscSt
     repo.get_misc_stats().await
 }
 
+/// `get_dashboard_stats` のレスポンス（ダッシュボード表示用の統計まとめ）
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export)]
+pub struct DashboardStats {
+    pub email: EmailStats,
+    pub order: OrderStats,
+    pub delivery: DeliveryStats,
+    pub product_master: ProductMasterStats,
+    pub misc: MiscStats,
+}
+
+/// ダッシュボード用の統計をまとめて取得（各統計クエリは並列に実行）
+#[tauri::command]
+pub async fn get_dashboard_stats(
+    pool: tauri::State<'_, SqlitePool>,
+) -> Result<DashboardStats, String> {
+    let pool = pool.inner();
+    let email_repo = SqliteEmailStatsRepository::new(pool.clone());
+    let order_repo = SqliteOrderStatsRepository::new(pool.clone());
+    let delivery_repo = SqliteDeliveryStatsRepository::new(pool.clone());
+    let product_master_repo = SqliteProductMasterStatsRepository::new(pool.clone());
+    let misc_repo = SqliteMiscStatsRepository::new(pool.clone());
+
+    let (email, order, delivery, product_master, misc) = tokio::try_join!(
+        email_repo.get_email_stats(),
+        order_repo.get_order_stats(),
+        delivery_repo.get_delivery_stats(),
+        product_master_repo.get_product_master_stats(),
+        misc_repo.get_misc_stats(),
+    )?;
+
+    Ok(DashboardStats {
+        email,
+        order,
+        delivery,
+        product_master,
+        misc,
+    })
+}
+
+/// ホーム画面ウィジェット・トレイ通知用の「今日の概要」を取得（基準日は JST の本日）
+#[tauri::command]
+pub async fn get_today_overview(
+    pool: tauri::State<'_, SqlitePool>,
+) -> Result<TodayOverview, String> {
+    let today = chrono::Utc::now()
+        .with_timezone(&chrono_tz::Asia::Tokyo)
+        .format("%Y-%m-%d")
+        .to_string();
+    let repo = SqliteOverviewRepository::new(pool.inner().clone());
+    repo.get_today_overview(today).await
+}
+
+/// メール受信〜同期〜注文登録のレイテンシ指標を取得（スケジューラ間隔調整の判断材料）
+///
+/// `days` は集計対象とする受信日時の期間（省略時は 30 日）。過去メールの一括同期分を
+/// 含めないよう、直近に受信したメールのみを対象とする。
+#[tauri::command]
+pub async fn get_ingestion_latency_metrics(
+    pool: tauri::State<'_, SqlitePool>,
+    days: Option<i64>,
+) -> Result<IngestionLatencyMetrics, String> {
+    let days = days.unwrap_or(DEFAULT_LATENCY_METRICS_DAYS);
+    if days <= 0 {
+        return Err(format!("days must be positive: {days}"));
+    }
+    let since_ms = (chrono::Utc::now() - chrono::Duration::days(days)).timestamp_millis();
+
+    let repo = SqliteLatencyMetricsRepository::new(pool.inner().clone());
+    let samples = repo.get_ingestion_latency(since_ms).await?;
+
+    Ok(IngestionLatencyMetrics {
+        days,
+        sync: summarize_latencies(samples.sync),
+        parse: summarize_latencies(samples.parse),
+        total: summarize_latencies(samples.total),
+        pending_parse_count: samples.pending_parse_count,
+    })
+}
+
+/// パーサー種別ごとの 1 通あたりの平均・最大処理時間を取得（バッチパースのボトルネック調査用）
+#[tauri::command]
+pub async fn get_parser_performance(
+    pool: tauri::State<'_, SqlitePool>,
+) -> Result<Vec<ParserPerformance>, String> {
+    let repo = SqliteParserMetricsRepository::new(pool.inner().clone());
+    repo.list_performance().await
+}
+
+/// 月次支出グラフを PNG として `path` に保存する（レポート ZIP 同梱用）
+///
+/// `period` は `all` / `YYYY` / `YYYY-MM..YYYY-MM` のいずれか。
+#[tauri::command]
+pub async fn render_spending_chart(
+    pool: tauri::State<'_, SqlitePool>,
+    period: String,
+    path: String,
+) -> Result<(), String> {
+    let (from, to) = parse_spending_period(&period)?;
+    let repo = SqliteSpendingStatsRepository::new(pool.inner().clone());
+    let data = repo.get_monthly_spending(from, to).await?;
+
+    let path = PathBuf::from(path);
+    tokio::task::spawn_blocking(move || render_spending_chart_png(&data, &path))
+        .await
+        .map_err(|e| format!("Chart rendering task failed: {e}"))?
+}
+
+/// ストレージ統計（DB ファイル・テーブル別・画像ディレクトリ）
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export)]
+pub struct StorageStats {
+    pub db_file_bytes: u64,
+    /// WAL ファイル（-wal）のサイズ。存在しない場合は 0
+    pub wal_file_bytes: u64,
+    /// VACUUM で回収できる未使用ページの合計サイズ
+    pub freelist_bytes: i64,
+    /// テーブル別の行数・概算サイズ（サイズの大きい順）
+    pub tables: Vec<TableStorage>,
+    pub images_dir_bytes: u64,
+    pub image_file_count: u64,
+}
+
+/// ファイルサイズ（存在しない場合は 0）
+fn file_size(path: &Path) -> u64 {
+    std::fs::metadata(path).map(|m| m.len()).unwrap_or(0)
+}
+
+/// ディレクトリ配下のファイルサイズ合計とファイル数を再帰的に集計する（存在しない場合は 0）
+pub(crate) fn dir_size(dir: &Path) -> Result<(u64, u64), String> {
+    let entries = match std::fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok((0, 0)),
+        Err(e) => return Err(format!("Failed to read dir {}: {e}", dir.display())),
+    };
+
+    let mut bytes = 0;
+    let mut files = 0;
+    for entry in entries {
+        let entry = entry.map_err(|e| format!("Failed to read dir entry: {e}"))?;
+        let file_type = entry
+            .file_type()
+            .map_err(|e| format!("Failed to get file type: {e}"))?;
+        if file_type.is_dir() {
+            let (sub_bytes, sub_files) = dir_size(&entry.path())?;
+            bytes += sub_bytes;
+            files += sub_files;
+        } else if file_type.is_file() {
+            bytes += entry.metadata().map(|m| m.len()).unwrap_or(0);
+            files += 1;
+        }
+    }
+    Ok((bytes, files))
+}
+
+/// DB ファイルサイズ、テーブル別の行数・概算サイズ、images ディレクトリのサイズを取得
+#[tauri::command]
+pub async fn get_storage_stats(
+    app_handle: tauri::AppHandle,
+    pool: tauri::State<'_, SqlitePool>,
+) -> Result<StorageStats, String> {
+    let app_config_dir = app_handle
+        .path()
+        .app_config_dir()
+        .map_err(|e| format!("Failed to get app config dir: {e}"))?;
+    let images_dir = app_handle
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to get app data dir: {e}"))?
+        .join("images");
+
+    let db_path = app_config_dir.join(get_db_filename());
+    let wal_path = app_config_dir.join(format!("{}-wal", get_db_filename()));
+
+    let repo = SqliteStorageStatsRepository::new(pool.inner().clone());
+    let page_stats = repo.get_page_stats().await?;
+    let tables = repo.get_table_storage().await?;
+
+    let (images_dir_bytes, image_file_count) =
+        tokio::task::spawn_blocking(move || dir_size(&images_dir))
+            .await
+            .map_err(|e| format!("Directory size task failed: {e}"))??;
+
+    Ok(StorageStats {
+        db_file_bytes: file_size(&db_path),
+        wal_file_bytes: file_size(&wal_path),
+        freelist_bytes: page_stats.freelist_count * page_stats.page_size,
+        tables,
+        images_dir_bytes,
+        image_file_count,
+    })
+}
+
+/// キャンセル理由別の件数と、キャンセルの多いメーカーのランキングを取得
+///
+/// `reason` を指定するとメーカー別ランキングをその理由に絞り込む（例: 発売中止の多いメーカー）。
+#[tauri::command]
+pub async fn get_cancel_reason_stats(
+    pool: tauri::State<'_, SqlitePool>,
+    reason: Option<CancelReason>,
+    limit: Option<i64>,
+) -> Result<CancelReasonStats, String> {
+    let limit = limit.unwrap_or(DEFAULT_CANCEL_MAKER_LIMIT).max(1);
+    let repo = SqliteCancelStatsRepository::new(pool.inner().clone());
+    repo.get_cancel_reason_stats(reason, limit).await
+}
+
+/// 統計の日次スナップショット（積み数・未着数の推移）を日付の昇順で取得
+///
+/// `days` は取得する期間（省略時は 90 日）。0 以下を指定すると全期間を返す。
+#[tauri::command]
+pub async fn get_stats_history(
+    pool: tauri::State<'_, SqlitePool>,
+    days: Option<i64>,
+) -> Result<Vec<StatsSnapshot>, String> {
+    let days = days.unwrap_or(DEFAULT_STATS_HISTORY_DAYS);
+    let from = (days > 0).then(|| {
+        (chrono::Utc::now().with_timezone(&chrono_tz::Asia::Tokyo) - chrono::Duration::days(days))
+            .format("%Y-%m-%d")
+            .to_string()
+    });
+    let repo = SqliteStatsSnapshotRepository::new(pool.inner().clone());
+    repo.get_snapshots(from).await
+}
+
+/// 外部ツール（Metabase 等）向けの分析用ビューと定義バージョンを取得
+#[tauri::command]
+pub async fn list_analysis_views(
+    pool: tauri::State<'_, SqlitePool>,
+) -> Result<Vec<AnalysisViewVersion>, String> {
+    let repo = SqliteAnalysisViewRepository::new(pool.inner().clone());
+    repo.list_views().await
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -88,4 +351,21 @@ mod tests {
             None => std::env::remove_var("PAA_E2E_MOCK"),
         }
     }
+
+    #[test]
+    fn test_dir_size_counts_nested_files() {
+        let tmp = tempfile::tempdir().unwrap();
+        let images = tmp.path().join("images");
+        std::fs::create_dir_all(images.join("sub")).unwrap();
+        std::fs::write(images.join("a.jpg"), [0u8; 10]).unwrap();
+        std::fs::write(images.join("sub").join("b.png"), [0u8; 5]).unwrap();
+
+        assert_eq!(dir_size(&images).unwrap(), (15, 2));
+    }
+
+    #[test]
+    fn test_dir_size_missing_dir_is_zero() {
+        let tmp = tempfile::tempdir().unwrap();
+        assert_eq!(dir_size(&tmp.path().join("missing")).unwrap(), (0, 0));
+    }
 }
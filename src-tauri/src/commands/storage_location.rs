@@ -0,0 +1,57 @@
+use sqlx::sqlite::SqlitePool;
+
+use crate::repository;
+
+fn normalize_place(value: Option<String>) -> Option<String> {
+    value
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty())
+}
+
+/// 商品の保管場所（棚番号・箱番号）を記録する。両方とも空なら記録を削除する
+#[tauri::command]
+pub async fn set_item_storage_location(
+    pool: tauri::State<'_, SqlitePool>,
+    item_id: i64,
+    shelf_number: Option<String>,
+    box_number: Option<String>,
+) -> Result<(), String> {
+    let shelf_number = normalize_place(shelf_number);
+    let box_number = normalize_place(box_number);
+    let repo = repository::SqliteStorageLocationRepository::new(pool.inner().clone());
+    repo.set(item_id, shelf_number.as_deref(), box_number.as_deref())
+        .await
+}
+
+/// 商品の保管場所を取得する（未登録なら None）
+#[tauri::command]
+pub async fn get_item_storage_location(
+    pool: tauri::State<'_, SqlitePool>,
+    item_id: i64,
+) -> Result<Option<repository::ItemStorageLocation>, String> {
+    let repo = repository::SqliteStorageLocationRepository::new(pool.inner().clone());
+    repo.get_by_item(item_id).await
+}
+
+/// 使用中の保管場所と商品数を取得する
+#[tauri::command]
+pub async fn list_storage_locations(
+    pool: tauri::State<'_, SqlitePool>,
+) -> Result<Vec<repository::StorageLocationSummary>, String> {
+    let repo = repository::SqliteStorageLocationRepository::new(pool.inner().clone());
+    repo.list_locations().await
+}
+
+/// 保管場所に入っている商品を取得する（棚番号・箱番号は省略すると絞り込まない）
+#[tauri::command]
+pub async fn list_items_by_storage_location(
+    pool: tauri::State<'_, SqlitePool>,
+    shelf_number: Option<String>,
+    box_number: Option<String>,
+) -> Result<Vec<repository::StoredItem>, String> {
+    let shelf_number = normalize_place(shelf_number);
+    let box_number = normalize_place(box_number);
+    let repo = repository::SqliteStorageLocationRepository::new(pool.inner().clone());
+    repo.list_items(shelf_number.as_deref(), box_number.as_deref())
+        .await
+}
@@ -43,6 +43,17 @@ pub fn validate_timeout_minutes(timeout_minutes: i64) -> Result<(), String> {
     Ok(())
 }
 
+/// body_html の最大保存サイズ（バイト）のバリデーション（0 = 無制限、または 10KiB〜100MiB）
+pub fn validate_max_body_html_bytes(max_body_html_bytes: i64) -> Result<(), String> {
+    if max_body_html_bytes != 0 && !(10 * 1024..=100 * 1024 * 1024).contains(&max_body_html_bytes) {
+        return Err(
+            "本文の最大保存サイズは0（無制限）または10KiB〜100MiBの範囲である必要があります"
+                .to_string(),
+        );
+    }
+    Ok(())
+}
+
 /// Gmail同期処理を開始
 /// BatchRunner<GmailSyncTask> を使用
 #[tauri::command]
@@ -79,6 +90,16 @@ pub async fn start_incremental_sync(
     Ok(())
 }
 
+/// 同期のドライラン（メールは保存せず、取得対象メール数と推定所要時間のみ返す）
+#[tauri::command]
+pub async fn estimate_sync(
+    app_handle: tauri::AppHandle,
+    pool: tauri::State<'_, SqlitePool>,
+    incremental: bool,
+) -> Result<gmail::SyncEstimate, String> {
+    orchestration::estimate_sync(app_handle, pool.inner().clone(), incremental).await
+}
+
 #[tauri::command]
 pub async fn cancel_sync(sync_state: tauri::State<'_, gmail::SyncState>) -> Result<(), String> {
     log::info!("Cancelling sync...");
@@ -116,7 +137,10 @@ pub async fn get_sync_status(
         max_iterations: config.sync.max_iterations,
         max_results_per_page: config.sync.max_results_per_page,
         timeout_minutes: config.sync.timeout_minutes,
+        max_body_html_bytes: config.sync.max_body_html_bytes,
+        body_html_overflow: config.sync.body_html_overflow,
         last_error_message,
+        misfiled_shop_mail: sync_state.inner().misfiled_shop_mail(),
     })
 }
 
@@ -177,6 +201,30 @@ pub async fn update_timeout_minutes(
     update_sync_config(app_handle, |s| s.timeout_minutes = timeout_minutes).await
 }
 
+#[tauri::command]
+pub async fn update_save_raw_eml(
+    app_handle: tauri::AppHandle,
+    enabled: bool,
+) -> Result<(), String> {
+    log::info!("Updating save_raw_eml to: {enabled}");
+    update_sync_config(app_handle, |s| s.save_raw_eml = enabled).await
+}
+
+#[tauri::command]
+pub async fn update_body_html_limit(
+    app_handle: tauri::AppHandle,
+    max_body_html_bytes: i64,
+    overflow: config::BodyHtmlOverflow,
+) -> Result<(), String> {
+    validate_max_body_html_bytes(max_body_html_bytes)?;
+    log::info!("Updating body_html limit to: {max_body_html_bytes} bytes ({overflow:?})");
+    update_sync_config(app_handle, |s| {
+        s.max_body_html_bytes = max_body_html_bytes;
+        s.body_html_overflow = overflow;
+    })
+    .await
+}
+
 /// Gmail メール取得（BatchRunner 経由で start_sync と同等の処理を実行）
 ///
 /// 進捗は `batch-progress` イベントで通知される。
@@ -204,6 +252,16 @@ mod tests {
         assert!(validate_max_results_per_page(501).is_err());
     }
 
+    #[test]
+    fn test_validate_max_body_html_bytes_boundaries() {
+        assert!(validate_max_body_html_bytes(0).is_ok());
+        assert!(validate_max_body_html_bytes(10 * 1024).is_ok());
+        assert!(validate_max_body_html_bytes(100 * 1024 * 1024).is_ok());
+        assert!(validate_max_body_html_bytes(10 * 1024 - 1).is_err());
+        assert!(validate_max_body_html_bytes(100 * 1024 * 1024 + 1).is_err());
+        assert!(validate_max_body_html_bytes(-1).is_err());
+    }
+
     #[test]
     fn test_validate_timeout_minutes_boundaries() {
         assert!(validate_timeout_minutes(1).is_ok());
@@ -0,0 +1,41 @@
+use sqlx::sqlite::SqlitePool;
+
+use crate::repository;
+
+/// ゴミ箱（論理削除済みの注文・商品）一覧
+#[tauri::command]
+pub async fn list_trash(pool: tauri::State<'_, SqlitePool>) -> Result<repository::Trash, String> {
+    let repo = repository::SqliteTrashRepository::new(pool.inner().clone());
+    repo.list().await
+}
+
+/// 注文を論理削除する（商品も含めてゴミ箱へ移動）
+#[tauri::command]
+pub async fn delete_order(pool: tauri::State<'_, SqlitePool>, order_id: i64) -> Result<(), String> {
+    let repo = repository::SqliteTrashRepository::new(pool.inner().clone());
+    repo.delete_order(order_id).await
+}
+
+/// ゴミ箱から注文を復元する
+#[tauri::command]
+pub async fn restore_order(
+    pool: tauri::State<'_, SqlitePool>,
+    order_id: i64,
+) -> Result<(), String> {
+    let repo = repository::SqliteTrashRepository::new(pool.inner().clone());
+    repo.restore_order(order_id).await
+}
+
+/// 商品を論理削除する
+#[tauri::command]
+pub async fn delete_item(pool: tauri::State<'_, SqlitePool>, item_id: i64) -> Result<(), String> {
+    let repo = repository::SqliteTrashRepository::new(pool.inner().clone());
+    repo.delete_item(item_id).await
+}
+
+/// ゴミ箱から商品を復元する
+#[tauri::command]
+pub async fn restore_item(pool: tauri::State<'_, SqlitePool>, item_id: i64) -> Result<(), String> {
+    let repo = repository::SqliteTrashRepository::new(pool.inner().clone());
+    repo.restore_item(item_id).await
+}
@@ -0,0 +1,10 @@
+use crate::updater;
+
+/// GitHub Releases の最新リリースを確認し、実行中のバージョンとリリースノートを返す
+#[tauri::command]
+pub async fn check_for_updates(
+    app_handle: tauri::AppHandle,
+) -> Result<updater::UpdateInfo, String> {
+    let current_version = app_handle.package_info().version.to_string();
+    updater::check_for_updates(&current_version).await
+}
@@ -41,10 +41,39 @@ pub async fn save_window_settings(
         x,
         y,
         maximized,
+        close_behavior: config.window.close_behavior,
     };
     config::save(&app_config_dir, &config)
 }
 
+#[tauri::command]
+pub async fn get_close_behavior(
+    app_handle: tauri::AppHandle,
+) -> Result<config::CloseBehavior, String> {
+    let app_config_dir = app_handle
+        .path()
+        .app_config_dir()
+        .map_err(|e| format!("Failed to get app config dir: {e}"))?;
+    let config = config::load(&app_config_dir)?;
+    Ok(config.window.close_behavior)
+}
+
+/// ウィンドウを閉じたときの挙動（hide: トレイに隠す / quit: 終了 / ask: 毎回確認）を更新する
+#[tauri::command]
+pub async fn update_close_behavior(
+    app_handle: tauri::AppHandle,
+    close_behavior: config::CloseBehavior,
+) -> Result<(), String> {
+    log::info!("Updating close_behavior to: {close_behavior:?}");
+    let app_config_dir = app_handle
+        .path()
+        .app_config_dir()
+        .map_err(|e| format!("Failed to get app config dir: {e}"))?;
+    let mut config = config::load(&app_config_dir)?;
+    config.window.close_behavior = close_behavior;
+    config::save(&app_config_dir, &config)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
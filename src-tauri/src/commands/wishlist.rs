@@ -0,0 +1,59 @@
+use sqlx::sqlite::SqlitePool;
+
+use crate::repository::{SqliteWishlistRepository, WishlistItem, WishlistItemInput};
+
+/// ウィッシュリストを取得する（検討中の商品が先頭）
+#[tauri::command]
+pub async fn list_wishlist_items(
+    pool: tauri::State<'_, SqlitePool>,
+) -> Result<Vec<WishlistItem>, String> {
+    SqliteWishlistRepository::new(pool.inner().clone())
+        .list()
+        .await
+}
+
+/// 検討中の商品を登録し、wishlist_items.id を返す
+#[tauri::command]
+pub async fn add_wishlist_item(
+    pool: tauri::State<'_, SqlitePool>,
+    item: WishlistItemInput,
+) -> Result<i64, String> {
+    SqliteWishlistRepository::new(pool.inner().clone())
+        .add(&item)
+        .await
+}
+
+/// ウィッシュリストの商品名・商品ページ・希望価格・メモを更新する
+#[tauri::command]
+pub async fn update_wishlist_item(
+    pool: tauri::State<'_, SqlitePool>,
+    id: i64,
+    item: WishlistItemInput,
+) -> Result<(), String> {
+    SqliteWishlistRepository::new(pool.inner().clone())
+        .update(id, &item)
+        .await
+}
+
+/// ウィッシュリストの状態（considering / purchased / dropped）を手動で変更する
+#[tauri::command]
+pub async fn set_wishlist_item_status(
+    pool: tauri::State<'_, SqlitePool>,
+    id: i64,
+    status: String,
+) -> Result<(), String> {
+    SqliteWishlistRepository::new(pool.inner().clone())
+        .set_status(id, &status)
+        .await
+}
+
+/// ウィッシュリストから削除する
+#[tauri::command]
+pub async fn delete_wishlist_item(
+    pool: tauri::State<'_, SqlitePool>,
+    id: i64,
+) -> Result<(), String> {
+    SqliteWishlistRepository::new(pool.inner().clone())
+        .delete(id)
+        .await
+}
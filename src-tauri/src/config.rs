@@ -6,8 +6,9 @@
 use serde::{Deserialize, Serialize};
 use std::fs;
 use std::path::Path;
+use ts_rs::TS;
 
-const CONFIG_FILENAME: &str = "paa_config.json";
+pub(crate) const CONFIG_FILENAME: &str = "paa_config.json";
 
 /// アプリケーション設定
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -20,9 +21,35 @@ pub struct AppConfig {
     pub gemini: GeminiConfig,
     #[serde(default)]
     pub scheduler: SchedulerConfig,
+    #[serde(default)]
+    pub debug: DebugConfig,
+    #[serde(default)]
+    pub updater: UpdaterConfig,
+    #[serde(default)]
+    pub monthly_report: MonthlyReportConfig,
+    #[serde(default)]
+    pub low_priority: LowPriorityConfig,
+    #[serde(default)]
+    pub setup: SetupConfig,
+    #[serde(default)]
+    pub image_search: ImageSearchConfig,
+}
+
+/// ウィンドウを閉じたときの挙動
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "lowercase")]
+#[ts(export)]
+pub enum CloseBehavior {
+    /// トレイに隠す（アプリは常駐を続ける）
+    #[default]
+    Hide,
+    /// アプリを終了する
+    Quit,
+    /// 閉じるたびに確認ダイアログで選ぶ
+    Ask,
 }
 
-/// ウィンドウ設定（サイズ・位置・最大化状態）
+/// ウィンドウ設定（サイズ・位置・最大化状態・閉じたときの挙動）
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WindowConfig {
     pub width: i64,
@@ -30,6 +57,8 @@ pub struct WindowConfig {
     pub x: Option<i64>,
     pub y: Option<i64>,
     pub maximized: bool,
+    #[serde(default)]
+    pub close_behavior: CloseBehavior,
 }
 
 impl Default for WindowConfig {
@@ -40,6 +69,7 @@ impl Default for WindowConfig {
             x: None,
             y: None,
             maximized: false,
+            close_behavior: CloseBehavior::Hide,
         }
     }
 }
@@ -55,18 +85,45 @@ pub struct SyncConfig {
     /// 同期処理のタイムアウト（分）
     #[serde(default = "default_sync_timeout_minutes")]
     pub timeout_minutes: i64,
+    /// 同期時に Gmail API の raw 形式（.eml 原本）も取得・保存するか
+    #[serde(default)]
+    pub save_raw_eml: bool,
+    /// DB に保存する body_html の最大バイト数（0 なら無制限）
+    #[serde(default = "default_max_body_html_bytes")]
+    pub max_body_html_bytes: i64,
+    /// body_html が上限を超えた場合の扱い
+    #[serde(default)]
+    pub body_html_overflow: BodyHtmlOverflow,
+}
+
+/// 上限を超えた body_html の扱い
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(export)]
+pub enum BodyHtmlOverflow {
+    /// 上限で切り詰めて保存する（超過分は破棄）
+    Truncate,
+    /// 上限で切り詰め、元の HTML 全体は app_data_dir の `email_bodies/` にファイルとして保存する
+    #[default]
+    Attachment,
 }
 
 fn default_max_results_per_page() -> i64 {
     100
 }
 
+/// メルマガ等の巨大な HTML メールで DB が肥大化しないよう 1 MiB に制限する
+fn default_max_body_html_bytes() -> i64 {
+    1024 * 1024
+}
+
 fn default_sync_timeout_minutes() -> i64 {
     30
 }
 
 /// Gemini API（商品名パース）設定
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
 pub struct GeminiConfig {
     /// 1リクエストあたりの商品数
     #[serde(default = "default_gemini_batch_size")]
@@ -74,6 +131,12 @@ pub struct GeminiConfig {
     /// リクエスト間の待機秒数（レート制限対策）
     #[serde(default = "default_gemini_delay_seconds")]
     pub delay_seconds: i64,
+    /// 1分あたりのリクエスト数上限（RPM）
+    #[serde(default = "default_gemini_rpm_limit")]
+    pub rpm_limit: i64,
+    /// 1分あたりのトークン数上限（TPM）
+    #[serde(default = "default_gemini_tpm_limit")]
+    pub tpm_limit: i64,
 }
 
 fn default_gemini_batch_size() -> i64 {
@@ -84,11 +147,21 @@ fn default_gemini_delay_seconds() -> i64 {
     10
 }
 
+fn default_gemini_rpm_limit() -> i64 {
+    i64::from(crate::gemini::client::DEFAULT_GEMINI_RPM_LIMIT)
+}
+
+fn default_gemini_tpm_limit() -> i64 {
+    i64::from(crate::gemini::client::DEFAULT_GEMINI_TPM_LIMIT)
+}
+
 impl Default for GeminiConfig {
     fn default() -> Self {
         Self {
             batch_size: 10,
             delay_seconds: 10,
+            rpm_limit: default_gemini_rpm_limit(),
+            tpm_limit: default_gemini_tpm_limit(),
         }
     }
 }
@@ -100,7 +173,8 @@ pub struct ParseConfig {
 }
 
 /// スケジューラ設定（定期パイプライン実行）
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
 pub struct SchedulerConfig {
     /// パイプラインの実行間隔（分）
     #[serde(default = "default_scheduler_interval_minutes")]
@@ -127,6 +201,160 @@ impl Default for SchedulerConfig {
     }
 }
 
+/// デバッグ設定
+#[derive(Debug, Clone, Default, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct DebugConfig {
+    /// バッチ処理中に1件ごとの詳細ログ（`batch-log` イベント）をフロントへ流すか
+    #[serde(default)]
+    pub batch_log_stream: bool,
+}
+
+/// アップデート確認設定
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct UpdaterConfig {
+    /// 常駐中に GitHub Releases を定期確認し、新バージョンを通知するか
+    #[serde(default = "default_updater_auto_check")]
+    pub auto_check: bool,
+}
+
+fn default_updater_auto_check() -> bool {
+    true
+}
+
+impl Default for UpdaterConfig {
+    fn default() -> Self {
+        Self { auto_check: true }
+    }
+}
+
+/// 月次レポート設定
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct MonthlyReportConfig {
+    /// 毎月1日に「先月のまとめ」を生成してデスクトップ通知するか
+    #[serde(default = "default_monthly_report_enabled")]
+    pub enabled: bool,
+}
+
+fn default_monthly_report_enabled() -> bool {
+    true
+}
+
+impl Default for MonthlyReportConfig {
+    fn default() -> Self {
+        Self { enabled: true }
+    }
+}
+
+/// 低優先度モード設定（ゲーム中などにバッチ処理の負荷を抑える）
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct LowPriorityConfig {
+    /// 同期・パース等のバッチ処理を低優先度（スロットリング）で実行するか
+    #[serde(default)]
+    pub enabled: bool,
+    /// 低優先度モード時の1バッチあたりの最大件数
+    #[serde(default = "default_low_priority_batch_size")]
+    pub batch_size: i64,
+    /// 低優先度モード時のバッチ間の待機ミリ秒
+    #[serde(default = "default_low_priority_delay_ms")]
+    pub delay_ms: i64,
+}
+
+fn default_low_priority_batch_size() -> i64 {
+    5
+}
+
+fn default_low_priority_delay_ms() -> i64 {
+    1000
+}
+
+impl Default for LowPriorityConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            batch_size: default_low_priority_batch_size(),
+            delay_ms: default_low_priority_delay_ms(),
+        }
+    }
+}
+
+/// 画像検索設定
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct ImageSearchConfig {
+    /// 商品マスタから検索クエリを組み立てるテンプレート
+    /// （`{maker}` `{series}` `{product_name}` `{scale}` `{raw_name}` を置換する）
+    #[serde(default = "default_image_search_query_template")]
+    pub query_template: String,
+}
+
+fn default_image_search_query_template() -> String {
+    crate::google_search::DEFAULT_QUERY_TEMPLATE.to_string()
+}
+
+impl Default for ImageSearchConfig {
+    fn default() -> Self {
+        Self {
+            query_template: default_image_search_query_template(),
+        }
+    }
+}
+
+/// 初回セットアップウィザードのステップ（表示順）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(export)]
+pub enum SetupStep {
+    /// Gmail OAuth 認証情報（client_id / client_secret）の登録
+    OauthCredentials,
+    /// Gmail へのアクセス許可（OAuth トークンの取得）
+    GmailAuthorization,
+    /// 店舗設定の登録
+    ShopSettings,
+    /// 初回同期
+    InitialSync,
+    /// Gemini API キーの登録（商品名解析用・任意）
+    Gemini,
+}
+
+impl SetupStep {
+    pub const ALL: [SetupStep; 5] = [
+        SetupStep::OauthCredentials,
+        SetupStep::GmailAuthorization,
+        SetupStep::ShopSettings,
+        SetupStep::InitialSync,
+        SetupStep::Gemini,
+    ];
+}
+
+/// 初回セットアップウィザードの進捗
+#[derive(Debug, Clone, Default, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct SetupConfig {
+    /// ユーザーが完了（任意ステップはスキップ）とマークしたステップ
+    #[serde(default)]
+    pub completed_steps: Vec<SetupStep>,
+}
+
+impl SetupConfig {
+    /// ステップの完了マークを付ける / 外す（重複しない・表示順を保つ）
+    pub fn mark(&mut self, step: SetupStep, completed: bool) {
+        self.completed_steps.retain(|s| *s != step);
+        if completed {
+            self.completed_steps.push(step);
+            self.completed_steps.sort_by_key(|s| {
+                SetupStep::ALL
+                    .iter()
+                    .position(|a| a == s)
+                    .unwrap_or(usize::MAX)
+            });
+        }
+    }
+}
+
 impl Default for AppConfig {
     fn default() -> Self {
         Self {
@@ -135,11 +363,20 @@ impl Default for AppConfig {
                 max_iterations: 1000,
                 max_results_per_page: 100,
                 timeout_minutes: 30,
+                save_raw_eml: false,
+                max_body_html_bytes: default_max_body_html_bytes(),
+                body_html_overflow: BodyHtmlOverflow::Attachment,
             },
             parse: ParseConfig { batch_size: 100 },
             window: WindowConfig::default(),
             gemini: GeminiConfig::default(),
             scheduler: SchedulerConfig::default(),
+            debug: DebugConfig::default(),
+            updater: UpdaterConfig::default(),
+            monthly_report: MonthlyReportConfig::default(),
+            low_priority: LowPriorityConfig::default(),
+            setup: SetupConfig::default(),
+            image_search: ImageSearchConfig::default(),
         }
     }
 }
@@ -186,10 +423,22 @@ mod tests {
         assert_eq!(config.parse.batch_size, 100);
         assert_eq!(config.window.width, 800);
         assert_eq!(config.window.height, 600);
+        assert_eq!(config.window.close_behavior, CloseBehavior::Hide);
         assert_eq!(config.gemini.batch_size, 10);
         assert_eq!(config.gemini.delay_seconds, 10);
         assert_eq!(config.scheduler.interval_minutes, 1440);
         assert!(config.scheduler.enabled);
+        assert!(!config.debug.batch_log_stream);
+        assert!(config.updater.auto_check);
+        assert!(config.monthly_report.enabled);
+        assert!(!config.low_priority.enabled);
+        assert_eq!(config.low_priority.batch_size, 5);
+        assert_eq!(config.low_priority.delay_ms, 1000);
+        assert!(config.setup.completed_steps.is_empty());
+        assert_eq!(
+            config.image_search.query_template,
+            crate::google_search::DEFAULT_QUERY_TEMPLATE
+        );
 
         // ファイルが作成されている
         assert!(dir.path().join(CONFIG_FILENAME).exists());
@@ -204,6 +453,9 @@ mod tests {
                 max_iterations: 500,
                 max_results_per_page: 200,
                 timeout_minutes: 60,
+                save_raw_eml: true,
+                max_body_html_bytes: 0,
+                body_html_overflow: BodyHtmlOverflow::Truncate,
             },
             parse: ParseConfig { batch_size: 200 },
             window: WindowConfig {
@@ -212,15 +464,34 @@ mod tests {
                 x: Some(100),
                 y: Some(200),
                 maximized: true,
+                close_behavior: CloseBehavior::Ask,
             },
             gemini: GeminiConfig {
                 batch_size: 20,
                 delay_seconds: 5,
+                rpm_limit: 15,
+                tpm_limit: 250_000,
             },
             scheduler: SchedulerConfig {
                 interval_minutes: 15,
                 enabled: false,
             },
+            debug: DebugConfig {
+                batch_log_stream: true,
+            },
+            updater: UpdaterConfig { auto_check: false },
+            monthly_report: MonthlyReportConfig { enabled: false },
+            low_priority: LowPriorityConfig {
+                enabled: true,
+                batch_size: 3,
+                delay_ms: 2000,
+            },
+            setup: SetupConfig {
+                completed_steps: vec![SetupStep::OauthCredentials, SetupStep::Gemini],
+            },
+            image_search: ImageSearchConfig {
+                query_template: "{product_name} {scale}".to_string(),
+            },
         };
 
         save(dir.path(), &config).unwrap();
@@ -229,13 +500,45 @@ mod tests {
         assert_eq!(loaded.sync.max_iterations, 500);
         assert_eq!(loaded.sync.max_results_per_page, 200);
         assert_eq!(loaded.sync.timeout_minutes, 60);
+        assert!(loaded.sync.save_raw_eml);
+        assert_eq!(loaded.sync.max_body_html_bytes, 0);
+        assert_eq!(loaded.sync.body_html_overflow, BodyHtmlOverflow::Truncate);
         assert_eq!(loaded.parse.batch_size, 200);
         assert_eq!(loaded.window.width, 1024);
         assert!(loaded.window.maximized);
+        assert_eq!(loaded.window.close_behavior, CloseBehavior::Ask);
         assert_eq!(loaded.gemini.batch_size, 20);
         assert_eq!(loaded.gemini.delay_seconds, 5);
+        assert_eq!(loaded.gemini.rpm_limit, 15);
+        assert_eq!(loaded.gemini.tpm_limit, 250_000);
         assert_eq!(loaded.scheduler.interval_minutes, 15);
         assert!(!loaded.scheduler.enabled);
+        assert!(loaded.debug.batch_log_stream);
+        assert!(!loaded.updater.auto_check);
+        assert!(!loaded.monthly_report.enabled);
+        assert!(loaded.low_priority.enabled);
+        assert_eq!(loaded.low_priority.batch_size, 3);
+        assert_eq!(loaded.low_priority.delay_ms, 2000);
+        assert_eq!(
+            loaded.setup.completed_steps,
+            vec![SetupStep::OauthCredentials, SetupStep::Gemini]
+        );
+        assert_eq!(loaded.image_search.query_template, "{product_name} {scale}");
+    }
+
+    #[test]
+    fn test_setup_config_mark_keeps_step_order() {
+        let mut setup = SetupConfig::default();
+        setup.mark(SetupStep::Gemini, true);
+        setup.mark(SetupStep::ShopSettings, true);
+        setup.mark(SetupStep::ShopSettings, true);
+        assert_eq!(
+            setup.completed_steps,
+            vec![SetupStep::ShopSettings, SetupStep::Gemini]
+        );
+
+        setup.mark(SetupStep::Gemini, false);
+        assert_eq!(setup.completed_steps, vec![SetupStep::ShopSettings]);
     }
 
     #[test]
@@ -278,9 +581,17 @@ mod tests {
             default_max_results_per_page()
         );
         assert_eq!(loaded.sync.timeout_minutes, default_sync_timeout_minutes());
+        assert!(!loaded.sync.save_raw_eml);
+        assert_eq!(
+            loaded.sync.max_body_html_bytes,
+            default_max_body_html_bytes()
+        );
+        assert_eq!(loaded.sync.body_html_overflow, BodyHtmlOverflow::Attachment);
         let default_gemini = GeminiConfig::default();
         assert_eq!(loaded.gemini.batch_size, default_gemini.batch_size);
         assert_eq!(loaded.gemini.delay_seconds, default_gemini.delay_seconds);
+        assert_eq!(loaded.gemini.rpm_limit, default_gemini.rpm_limit);
+        assert_eq!(loaded.gemini.tpm_limit, default_gemini.tpm_limit);
 
         // window は JSON から省略 → AppConfig の #[serde(default)] で WindowConfig::default
         let default_window = WindowConfig::default();
@@ -321,4 +632,31 @@ mod tests {
         );
         assert_eq!(loaded.scheduler.enabled, default_scheduler.enabled);
     }
+
+    #[test]
+    fn test_close_behavior_defaults_to_hide_for_existing_window_config() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join(CONFIG_FILENAME);
+
+        // close_behavior 追加前の設定ファイル
+        let json = r#"
+        {
+          "sync": { "batch_size": 1, "max_iterations": 2 },
+          "parse": { "batch_size": 3 },
+          "window": { "width": 900, "height": 700, "x": null, "y": null, "maximized": false }
+        }
+        "#;
+        fs::write(&path, json).unwrap();
+
+        let loaded = load(dir.path()).unwrap();
+        assert_eq!(loaded.window.width, 900);
+        assert_eq!(loaded.window.close_behavior, CloseBehavior::Hide);
+
+        let quit: CloseBehavior = serde_json::from_str(r#""quit""#).unwrap();
+        assert_eq!(quit, CloseBehavior::Quit);
+        assert_eq!(
+            serde_json::to_string(&CloseBehavior::Ask).unwrap(),
+            r#""ask""#
+        );
+    }
 }
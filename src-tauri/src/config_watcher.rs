@@ -0,0 +1,191 @@
+//! 設定ファイルのホットリロード
+//!
+//! paa_config.json を直接編集したときに再起動せず反映するため、設定ディレクトリを `notify` で監視する。
+//! ほとんどの設定は使う時点で `config::load` から読み直すのでそのまま反映される。
+//! 起動時に読み込んで状態として保持している設定（スケジューラの有効/無効・実行間隔）はここで更新する。
+//! 変更されたセクション名は `config-reloaded` イベントでフロントエンドに通知し、設定画面を再読み込みさせる。
+
+use std::path::Path;
+use std::time::Duration;
+
+use notify::{Event, EventKind, RecursiveMode, Watcher};
+use serde::Serialize;
+use tauri::{Emitter, Manager};
+
+use crate::config::{self, AppConfig, CONFIG_FILENAME};
+use crate::db_lock::DbLockState;
+use crate::scheduler::SchedulerState;
+
+/// 設定を読み直したときにフロントエンドへ送るイベント
+pub const CONFIG_RELOADED_EVENT: &str = "config-reloaded";
+
+/// 編集後の設定ファイルが読めなかったときのイベント（以前の設定のまま動作を続ける）
+pub const CONFIG_RELOAD_FAILED_EVENT: &str = "config-reload-failed";
+
+/// エディタの保存は書き込み・リネームなど複数のイベントになるため、落ち着くまで待ってから読み直す
+const RELOAD_DEBOUNCE: Duration = Duration::from_millis(500);
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ConfigReloadedPayload {
+    /// 変更された設定のセクション（`scheduler` / `gemini` など）
+    pub sections: Vec<String>,
+}
+
+/// 設定ファイルの変更を表すイベントか（エディタの一時ファイル・読み取りアクセスは無視する）
+pub fn is_config_event(event: &Event) -> bool {
+    !matches!(event.kind, EventKind::Access(_))
+        && event
+            .paths
+            .iter()
+            .any(|p| p.file_name().is_some_and(|name| name == CONFIG_FILENAME))
+}
+
+/// 新旧の設定で値が変わったトップレベルのセクション名を返す
+pub fn changed_sections(old: &AppConfig, new: &AppConfig) -> Vec<String> {
+    let (Ok(old), Ok(new)) = (serde_json::to_value(old), serde_json::to_value(new)) else {
+        return Vec::new();
+    };
+    let (Some(old), Some(new)) = (old.as_object(), new.as_object()) else {
+        return Vec::new();
+    };
+    new.iter()
+        .filter(|(key, value)| old.get(*key) != Some(value))
+        .map(|(key, _)| key.clone())
+        .collect()
+}
+
+/// 起動時に読み込んで保持している状態へ新しい設定を反映する
+fn apply_to_subsystems(app: &tauri::AppHandle, config: &AppConfig) {
+    if let Some(sched_state) = app.try_state::<SchedulerState>() {
+        // 読み取り専用で起動した場合はスケジューラを有効にしない
+        let read_only = app
+            .try_state::<DbLockState>()
+            .is_some_and(|s| s.is_read_only());
+        let enabled = config.scheduler.enabled && !read_only;
+        if sched_state.is_enabled() != enabled {
+            sched_state.set_enabled(enabled);
+        }
+        let interval = config.scheduler.interval_minutes;
+        if sched_state.interval_minutes() != interval {
+            match crate::commands::validate_scheduler_interval(interval) {
+                Ok(()) => sched_state.set_interval_minutes(interval),
+                Err(e) => log::warn!("Ignoring scheduler interval from edited config: {e}"),
+            }
+        }
+    }
+}
+
+/// 設定ファイルを読み直し、変更があればサブシステムとフロントエンドに伝える
+fn reload(app: &tauri::AppHandle, config_dir: &Path, current: &mut AppConfig) {
+    // 削除された場合に load がデフォルト設定を書き戻さないよう、存在するときだけ読む
+    if !config_dir.join(CONFIG_FILENAME).exists() {
+        return;
+    }
+    let new_config = match config::load(config_dir) {
+        Ok(c) => c,
+        Err(e) => {
+            log::warn!("Failed to reload config, keeping previous settings: {e}");
+            let _ = app.emit(CONFIG_RELOAD_FAILED_EVENT, e);
+            return;
+        }
+    };
+
+    let sections = changed_sections(current, &new_config);
+    if sections.is_empty() {
+        return;
+    }
+    log::info!("Config reloaded: changed sections {:?}", sections);
+    apply_to_subsystems(app, &new_config);
+    *current = new_config;
+    let _ = app.emit(CONFIG_RELOADED_EVENT, ConfigReloadedPayload { sections });
+}
+
+/// 設定ディレクトリを監視し、paa_config.json が変更されるたびに読み直す（常駐タスク）
+pub async fn run_config_watcher(app: tauri::AppHandle) {
+    let config_dir = match app.path().app_config_dir() {
+        Ok(dir) => dir,
+        Err(e) => {
+            log::error!("Config watcher: failed to get app config dir: {e}");
+            return;
+        }
+    };
+    let mut current = match config::load(&config_dir) {
+        Ok(c) => c,
+        Err(e) => {
+            log::warn!("Config watcher: failed to load initial config: {e}");
+            AppConfig::default()
+        }
+    };
+
+    let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+    let mut watcher = match notify::recommended_watcher(move |res: notify::Result<Event>| match res
+    {
+        Ok(event) if is_config_event(&event) => {
+            let _ = tx.send(());
+        }
+        Ok(_) => {}
+        Err(e) => log::warn!("Config watcher error: {e}"),
+    }) {
+        Ok(w) => w,
+        Err(e) => {
+            log::error!("Failed to create config watcher: {e}");
+            return;
+        }
+    };
+    // エディタは一時ファイルからのリネームで保存することがあるため、ファイルではなくディレクトリを監視する
+    if let Err(e) = watcher.watch(&config_dir, RecursiveMode::NonRecursive) {
+        log::error!("Failed to watch {}: {e}", config_dir.display());
+        return;
+    }
+    log::info!("Config watcher started: {}", config_dir.display());
+
+    while rx.recv().await.is_some() {
+        tokio::time::sleep(RELOAD_DEBOUNCE).await;
+        while rx.try_recv().is_ok() {}
+        reload(&app, &config_dir, &mut current);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use notify::event::{AccessKind, CreateKind, ModifyKind};
+    use std::path::PathBuf;
+
+    fn event(kind: EventKind, path: &str) -> Event {
+        Event::new(kind).add_path(PathBuf::from(path))
+    }
+
+    #[test]
+    fn test_is_config_event() {
+        assert!(is_config_event(&event(
+            EventKind::Modify(ModifyKind::Any),
+            "/config/paa_config.json"
+        )));
+        assert!(is_config_event(&event(
+            EventKind::Create(CreateKind::File),
+            "/config/paa_config.json"
+        )));
+        assert!(!is_config_event(&event(
+            EventKind::Modify(ModifyKind::Any),
+            "/config/paa_config.json.swp"
+        )));
+        assert!(!is_config_event(&event(
+            EventKind::Access(AccessKind::Any),
+            "/config/paa_config.json"
+        )));
+    }
+
+    #[test]
+    fn test_changed_sections() {
+        let old = AppConfig::default();
+        assert!(changed_sections(&old, &old.clone()).is_empty());
+
+        let mut new = old.clone();
+        new.scheduler.interval_minutes = 60;
+        new.gemini.batch_size = 20;
+        let mut sections = changed_sections(&old, &new);
+        sections.sort();
+        assert_eq!(sections, vec!["gemini", "scheduler"]);
+    }
+}
@@ -0,0 +1,539 @@
+//! DB の多重使用検出
+//!
+//! 共有フォルダに置いた DB を複数の端末から同時に開くと、ネットワーク越しでは SQLite のファイルロックが
+//! 正しく効かず DB が破損することがある。DB と同じフォルダにロックファイル（`<DB ファイル名>.lock`）を置いて
+//! 使用中の端末を記録し、他の端末が使用中なら 2 台目は読み取り専用で起動する。
+//! 異常終了でロックファイルが残っても起動できなくならないよう、使用中の端末はハートビートを定期的に更新し、
+//! 一定時間更新されていないロックは放置されたものとして引き継ぐ。
+
+use std::fs::OpenOptions;
+use std::io::{ErrorKind, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+/// ハートビートの更新間隔
+pub const HEARTBEAT_INTERVAL: Duration = Duration::from_secs(60);
+
+/// この秒数ハートビートが更新されていないロックは放置されたものとみなす（更新間隔の 3 倍）
+pub const STALE_AFTER_SECS: i64 = 180;
+
+/// ロックファイルに記録する使用中の端末の情報
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct DbLockInfo {
+    /// 起動ごとに発行する ID（自分のロックかどうかの判定に使う）
+    pub instance_id: String,
+    pub hostname: String,
+    pub pid: u32,
+    /// ロックを取得した日時（RFC3339）
+    pub acquired_at: String,
+    /// 最後にハートビートを更新した日時（RFC3339）
+    pub heartbeat_at: String,
+}
+
+impl DbLockInfo {
+    /// この端末・プロセスのロック情報を作成する
+    pub fn new_for_current_process(now: DateTime<Utc>) -> Self {
+        Self {
+            instance_id: uuid::Uuid::new_v4().to_string(),
+            hostname: current_hostname(),
+            pid: std::process::id(),
+            acquired_at: now.to_rfc3339(),
+            heartbeat_at: now.to_rfc3339(),
+        }
+    }
+
+    /// ハートビートが途絶えて放置されたロックか（日時が読めない場合も放置扱い）
+    pub fn is_stale(&self, now: DateTime<Utc>) -> bool {
+        match DateTime::parse_from_rfc3339(&self.heartbeat_at) {
+            Ok(heartbeat) => (now - heartbeat.with_timezone(&Utc)).num_seconds() > STALE_AFTER_SECS,
+            Err(_) => true,
+        }
+    }
+}
+
+/// ロック取得の結果
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DbLockStatus {
+    /// ロックを取得した（読み書き可能）
+    Acquired(DbLockInfo),
+    /// 他の端末・プロセスが使用中（読み取り専用で開く）
+    HeldByOther(DbLockInfo),
+}
+
+/// フロントエンドに返す DB のアクセスモード
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, TS)]
+#[ts(export)]
+pub struct DbAccessStatus {
+    pub read_only: bool,
+    /// 読み取り専用の場合、DB を使用中の端末
+    pub holder: Option<DbLockInfo>,
+}
+
+/// DB ファイルに対応するロックファイルのパス
+pub fn lock_path(db_path: &Path) -> PathBuf {
+    let mut name = db_path.file_name().unwrap_or_default().to_os_string();
+    name.push(".lock");
+    db_path.with_file_name(name)
+}
+
+fn current_hostname() -> String {
+    ["COMPUTERNAME", "HOSTNAME"]
+        .iter()
+        .find_map(|key| std::env::var(key).ok().filter(|v| !v.is_empty()))
+        .unwrap_or_else(|| "unknown".to_string())
+}
+
+fn read_lock(path: &Path) -> Result<Option<DbLockInfo>, String> {
+    match std::fs::read_to_string(path) {
+        Ok(content) => Ok(serde_json::from_str(&content).ok()),
+        Err(e) if e.kind() == ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(format!("Failed to read DB lock file: {e}")),
+    }
+}
+
+fn write_lock(path: &Path, info: &DbLockInfo) -> Result<(), String> {
+    let json = serde_json::to_string_pretty(info)
+        .map_err(|e| format!("Failed to serialize DB lock: {e}"))?;
+    std::fs::write(path, json).map_err(|e| format!("Failed to write DB lock file: {e}"))
+}
+
+/// ロックファイルを作成してロックを取得する
+///
+/// 他の端末が使用中（ハートビートが新しい）なら `HeldByOther` を返す。
+/// 放置されたロック・壊れたロックファイルは削除して取得し直す。
+pub fn try_acquire(path: &Path, now: DateTime<Utc>) -> Result<DbLockStatus, String> {
+    let info = DbLockInfo::new_for_current_process(now);
+    // 同時に起動した端末と競合しないよう、既存ファイルがあれば作成に失敗する create_new で取得する
+    for _ in 0..2 {
+        match OpenOptions::new().write(true).create_new(true).open(path) {
+            Ok(mut file) => {
+                let json = serde_json::to_string_pretty(&info)
+                    .map_err(|e| format!("Failed to serialize DB lock: {e}"))?;
+                file.write_all(json.as_bytes())
+                    .map_err(|e| format!("Failed to write DB lock file: {e}"))?;
+                return Ok(DbLockStatus::Acquired(info));
+            }
+            Err(e) if e.kind() == ErrorKind::AlreadyExists => {
+                if let Some(holder) = read_lock(path)? {
+                    if !holder.is_stale(now) {
+                        return Ok(DbLockStatus::HeldByOther(holder));
+                    }
+                    log::warn!(
+                        "Taking over stale DB lock held by {} (pid {}, heartbeat {})",
+                        holder.hostname,
+                        holder.pid,
+                        holder.heartbeat_at
+                    );
+                }
+                match std::fs::remove_file(path) {
+                    Ok(()) => {}
+                    Err(e) if e.kind() == ErrorKind::NotFound => {}
+                    Err(e) => return Err(format!("Failed to remove stale DB lock file: {e}")),
+                }
+            }
+            Err(e) => return Err(format!("Failed to create DB lock file: {e}")),
+        }
+    }
+    Err("Failed to acquire DB lock: lock file was recreated concurrently".to_string())
+}
+
+/// 自分のロックのハートビートを更新する
+///
+/// ロックファイルが消えていれば作り直す。他の端末に引き継がれていた場合は `Ok(false)` を返す。
+pub fn refresh_heartbeat(
+    path: &Path,
+    own: &mut DbLockInfo,
+    now: DateTime<Utc>,
+) -> Result<bool, String> {
+    if let Some(current) = read_lock(path)? {
+        if current.instance_id != own.instance_id {
+            return Ok(false);
+        }
+    }
+    own.heartbeat_at = now.to_rfc3339();
+    write_lock(path, own)?;
+    Ok(true)
+}
+
+/// 自分のロックであればロックファイルを削除する
+pub fn release(path: &Path, instance_id: &str) -> Result<(), String> {
+    match read_lock(path)? {
+        Some(current) if current.instance_id == instance_id => {
+            std::fs::remove_file(path).map_err(|e| format!("Failed to remove DB lock file: {e}"))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// 読み取り専用で起動した場合も呼び出せるコマンド（DB に書き込まないもの）。
+/// ここにないコマンドは拒否するため、追加したコマンドは書き込まないことを確認してから登録する。
+pub const DB_READ_ONLY_COMMANDS: &[&str] = &[
+    "get_db_filename",
+    "get_db_access_status",
+    "estimate_sync",
+    "cancel_sync",
+    "get_sync_status",
+    "update_batch_size",
+    "update_max_iterations",
+    "update_max_results_per_page",
+    "update_timeout_minutes",
+    "update_save_raw_eml",
+    "update_body_html_limit",
+    "save_window_settings",
+    "get_close_behavior",
+    "update_close_behavior",
+    "get_email_stats",
+    "get_order_stats",
+    "get_delivery_stats",
+    "get_product_master_stats",
+    "get_misc_stats",
+    "get_dashboard_stats",
+    "get_today_overview",
+    "get_ingestion_latency_metrics",
+    "get_parser_performance",
+    "render_spending_chart",
+    "get_storage_stats",
+    "get_cancel_reason_stats",
+    "get_stats_history",
+    "list_analysis_views",
+    "search_orders",
+    "list_smart_filters",
+    "list_reservation_orders",
+    "find_duplicate_preorders",
+    "list_release_schedule",
+    "get_arrival_calendar",
+    "list_order_payments",
+    "list_pending_payments",
+    "list_shop_point_balances",
+    "get_item_receipt_review",
+    "get_shop_damage_stats",
+    "get_item_storage_location",
+    "list_storage_locations",
+    "list_items_by_storage_location",
+    "render_order_document",
+    "export_household_csv",
+    "list_reissue_watches",
+    "list_reissue_detections",
+    "list_series_master",
+    "get_series_stats",
+    "list_trash",
+    "get_logs",
+    "get_all_shop_settings",
+    "get_parser_catalog",
+    "suggest_new_shops",
+    "get_shop_setting_match_stats",
+    "suggest_shop_settings_to_disable",
+    "parse_email",
+    "cancel_parse",
+    "get_parse_status",
+    "update_parse_batch_size",
+    "get_gemini_config",
+    "update_gemini_batch_size",
+    "update_gemini_delay_seconds",
+    "update_gemini_rate_limits",
+    "get_setup_status",
+    "mark_setup_step",
+    "has_gemini_api_key",
+    "save_gemini_api_key",
+    "validate_gemini_api_key",
+    "delete_gemini_api_key",
+    "cancel_product_name_parse",
+    "has_gmail_oauth_credentials",
+    "save_gmail_oauth_credentials",
+    "delete_gmail_oauth_credentials",
+    "is_google_search_configured",
+    "save_google_search_api_key",
+    "validate_google_search_api_key",
+    "delete_google_search_config",
+    "search_product_images",
+    "export_metadata",
+    "get_all_excluded_items",
+    "get_all_excluded_orders",
+    "get_product_master_list",
+    "cancel_delivery_check",
+    "open_tracking_page",
+    "get_scheduler_config",
+    "update_scheduler_interval",
+    "update_scheduler_enabled",
+    "get_autostart_enabled",
+    "set_autostart",
+    "get_debug_config",
+    "update_batch_log_stream",
+    "get_updater_config",
+    "update_auto_update_check",
+    "get_monthly_report_config",
+    "update_monthly_report_enabled",
+    "get_low_priority_config",
+    "update_low_priority_enabled",
+    "get_image_search_config",
+    "update_image_search_query_template",
+    "list_monthly_reports",
+    "check_for_updates",
+    "open_surugaya_login_window",
+    "cancel_surugaya_mypage_fetch",
+    "get_surugaya_mypage_fetch_status",
+    "open_amazon_login_window",
+    "cancel_amazon_order_fetch",
+    "get_amazon_order_fetch_status",
+    "show_screen_overlay",
+    "close_screen_overlay",
+    "capture_and_ocr",
+    "fetch_news_feed",
+    "fetch_news_html",
+    "get_news_clips",
+    "get_clipped_urls",
+    "list_exclusion_patterns",
+    "list_auto_tag_rules",
+    "get_item_tags",
+    "list_notification_rules",
+    "list_wishlist_items",
+    "export_email_raw",
+    "anonymize_email",
+];
+
+/// 読み取り専用で起動した場合に無効にするトレイメニュー（DB に書き込む処理を起動するもの）
+pub const DB_WRITE_TRAY_MENU_IDS: &[&str] = &[
+    "tray_sync",
+    "tray_incremental_sync",
+    "tray_parse",
+    "tray_product_name_parse",
+    "tray_delivery_check",
+    "tray_full_parse_pipeline",
+    "tray_scheduler_toggle",
+];
+
+/// アプリが読み取り専用で起動したか（常駐タスク・トレイメニューから使用）
+pub fn is_read_only(app: &tauri::AppHandle) -> bool {
+    use tauri::Manager;
+
+    app.try_state::<DbLockState>()
+        .is_some_and(|state| state.is_read_only())
+}
+
+/// 起動時に決まった DB のアクセスモード（Tauri state として管理）
+pub struct DbLockState {
+    path: PathBuf,
+    /// 自分が取得したロック（読み取り専用・ロックなしの場合は None）
+    own: Mutex<Option<DbLockInfo>>,
+    /// 読み取り専用で起動した場合、DB を使用中の端末
+    holder: Option<DbLockInfo>,
+}
+
+impl DbLockState {
+    /// ロック取得の結果から状態を作る（取得に失敗した場合は `status` を None とし、ロックなしで読み書きする）
+    pub fn new(path: PathBuf, status: Option<DbLockStatus>) -> Self {
+        let (own, holder) = match status {
+            Some(DbLockStatus::Acquired(info)) => (Some(info), None),
+            Some(DbLockStatus::HeldByOther(info)) => (None, Some(info)),
+            None => (None, None),
+        };
+        Self {
+            path,
+            own: Mutex::new(own),
+            holder,
+        }
+    }
+
+    pub fn is_read_only(&self) -> bool {
+        self.holder.is_some()
+    }
+
+    /// 読み取り専用で起動した場合はエラーを返す（書き込みを伴う処理の前に呼ぶ）
+    pub fn ensure_writable(&self) -> Result<(), String> {
+        match &self.holder {
+            Some(holder) => Err(format!(
+                "他の端末（{}）が DB を使用中のため、読み取り専用で開いています",
+                holder.hostname
+            )),
+            None => Ok(()),
+        }
+    }
+
+    /// コマンドの呼び出しを許可するか（読み取り専用の場合は `DB_READ_ONLY_COMMANDS` 以外を拒否する）
+    pub fn check_command(&self, command: &str) -> Result<(), String> {
+        if DB_READ_ONLY_COMMANDS.contains(&command) {
+            Ok(())
+        } else {
+            self.ensure_writable()
+        }
+    }
+
+    pub fn status(&self) -> DbAccessStatus {
+        DbAccessStatus {
+            read_only: self.is_read_only(),
+            holder: self.holder.clone(),
+        }
+    }
+
+    /// 自分のロックのハートビートを更新し、引き続きロックを保持しているかを返す
+    ///
+    /// ロックを持っていない場合、または他の端末に引き継がれていた場合（ロックを手放す）は `false` を返す。
+    pub fn refresh(&self) -> bool {
+        let Ok(mut own) = self.own.lock() else {
+            return false;
+        };
+        let Some(info) = own.as_mut() else {
+            return false;
+        };
+        match refresh_heartbeat(&self.path, info, Utc::now()) {
+            Ok(true) => true,
+            Ok(false) => {
+                log::error!(
+                    "DB lock was taken over by another instance; avoid using the same DB from multiple PCs"
+                );
+                *own = None;
+                false
+            }
+            Err(e) => {
+                // 共有フォルダの一時的な切断などは次回の更新で回復するため、ロックは保持したままにする
+                log::warn!("{e}");
+                true
+            }
+        }
+    }
+
+    /// 終了時にロックを解放する
+    pub fn release(&self) {
+        let Ok(mut own) = self.own.lock() else {
+            return;
+        };
+        if let Some(info) = own.take() {
+            if let Err(e) = release(&self.path, &info.instance_id) {
+                log::warn!("{e}");
+            }
+        }
+    }
+}
+
+/// ロックのハートビートを定期的に更新する（常駐タスク。ロックを失ったら終了する）
+pub async fn run_heartbeat(app: tauri::AppHandle) {
+    use tauri::Manager;
+
+    loop {
+        tokio::time::sleep(HEARTBEAT_INTERVAL).await;
+        let Some(state) = app.try_state::<DbLockState>() else {
+            return;
+        };
+        if !state.refresh() {
+            return;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn at(secs: i64) -> DateTime<Utc> {
+        Utc.timestamp_opt(1_750_000_000 + secs, 0).unwrap()
+    }
+
+    #[test]
+    fn test_lock_path() {
+        assert_eq!(
+            lock_path(Path::new("/share/paa/paa_data.db")),
+            PathBuf::from("/share/paa/paa_data.db.lock")
+        );
+    }
+
+    #[test]
+    fn test_try_acquire_detects_other_instance_and_takes_over_stale_lock() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = lock_path(&dir.path().join("paa_data.db"));
+
+        let DbLockStatus::Acquired(first) = try_acquire(&path, at(0)).unwrap() else {
+            panic!("first instance should acquire the lock");
+        };
+        // 2 台目はハートビートが新しい間は読み取り専用
+        assert_eq!(
+            try_acquire(&path, at(STALE_AFTER_SECS)).unwrap(),
+            DbLockStatus::HeldByOther(first.clone())
+        );
+
+        // 1 台目のハートビートが途絶えたら引き継げる
+        let DbLockStatus::Acquired(second) = try_acquire(&path, at(STALE_AFTER_SECS + 1)).unwrap()
+        else {
+            panic!("stale lock should be taken over");
+        };
+        assert_ne!(second.instance_id, first.instance_id);
+
+        // 引き継がれた 1 台目はハートビートを更新せず、解放してもロックファイルを消さない
+        let mut first = first;
+        assert!(!refresh_heartbeat(&path, &mut first, at(200)).unwrap());
+        release(&path, &first.instance_id).unwrap();
+        assert!(path.exists());
+
+        release(&path, &second.instance_id).unwrap();
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn test_refresh_heartbeat_and_corrupt_lock_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = lock_path(&dir.path().join("paa_data.db"));
+
+        let DbLockStatus::Acquired(mut own) = try_acquire(&path, at(0)).unwrap() else {
+            panic!("should acquire the lock");
+        };
+        assert!(refresh_heartbeat(&path, &mut own, at(150)).unwrap());
+        // 更新したハートビートから数えるため、最初の取得から STALE_AFTER_SECS を過ぎても使用中のまま
+        assert!(matches!(
+            try_acquire(&path, at(300)).unwrap(),
+            DbLockStatus::HeldByOther(_)
+        ));
+
+        // 壊れたロックファイルは放置されたものとして取得し直す
+        std::fs::write(&path, "not json").unwrap();
+        assert!(matches!(
+            try_acquire(&path, at(300)).unwrap(),
+            DbLockStatus::Acquired(_)
+        ));
+    }
+
+    #[test]
+    fn test_db_lock_state_status() {
+        let holder = DbLockInfo::new_for_current_process(at(0));
+        let state = DbLockState::new(
+            PathBuf::from("paa_data.db.lock"),
+            Some(DbLockStatus::HeldByOther(holder.clone())),
+        );
+        assert_eq!(
+            state.status(),
+            DbAccessStatus {
+                read_only: true,
+                holder: Some(holder),
+            }
+        );
+        assert!(!DbLockState::new(PathBuf::from("paa_data.db.lock"), None).is_read_only());
+    }
+
+    #[test]
+    fn test_check_command_rejects_writes_when_read_only() {
+        let read_only = DbLockState::new(
+            PathBuf::from("paa_data.db.lock"),
+            Some(DbLockStatus::HeldByOther(
+                DbLockInfo::new_for_current_process(at(0)),
+            )),
+        );
+        assert!(read_only.check_command("start_batch_parse").is_err());
+        assert!(read_only.check_command("factory_reset").is_err());
+        assert!(read_only.check_command("detect_price_anomalies").is_err());
+        assert!(read_only.check_command("verify_order_restructure").is_err());
+        // 一覧にない（新しく追加された）コマンドは既定で拒否する
+        assert!(read_only.check_command("unknown_command").is_err());
+        assert!(read_only.check_command("search_orders").is_ok());
+        assert!(read_only.check_command("get_db_access_status").is_ok());
+
+        let writable = DbLockState::new(PathBuf::from("paa_data.db.lock"), None);
+        assert!(writable.check_command("start_batch_parse").is_ok());
+        assert!(writable.check_command("unknown_command").is_ok());
+        assert!(writable.ensure_writable().is_ok());
+    }
+}
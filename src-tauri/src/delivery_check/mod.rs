@@ -95,6 +95,23 @@ fn build_yamato_post_body(tracking_number: &str) -> String {
     )
 }
 
+/// ブラウザで開く追跡ページの URL を構築する（対応外の配送業者・追跡番号なしは None）
+///
+/// ヤマト運輸の確認バッチ用 URL は POST 専用のため、GET で開ける追跡番号付きページを返す。
+pub fn tracking_page_url(carrier: &str, tracking_number: &str) -> Option<String> {
+    let tracking_number = tracking_number.trim();
+    if tracking_number.is_empty() || tracking_number == "-" {
+        return None;
+    }
+    if carrier.contains("ヤマト") || carrier.contains("クロネコ") {
+        let num = urlencoding::encode(tracking_number).into_owned();
+        return Some(format!(
+            "https://jizen.kuronekoyamato.co.jp/jizen/servlet/crjz.b.NQ0010?id={num}"
+        ));
+    }
+    build_tracking_url(carrier, tracking_number)
+}
+
 // ---------------------------------------------------------------------------
 // HTMLエンティティデコード
 // ---------------------------------------------------------------------------
@@ -645,6 +662,19 @@ mod tests {
         assert!(url.contains("japanpost.jp"));
     }
 
+    #[test]
+    fn test_tracking_page_url() {
+        let url = tracking_page_url("ヤマト運輸", "111222333444").unwrap();
+        assert!(url.contains("kuronekoyamato.co.jp"));
+        assert!(url.ends_with("id=111222333444"));
+
+        let url = tracking_page_url("佐川急便", " 123456789 ").unwrap();
+        assert!(url.ends_with("okurijoNo=123456789"));
+
+        assert!(tracking_page_url("日本郵便", "-").is_none());
+        assert!(tracking_page_url("不明業者", "123").is_none());
+    }
+
     #[test]
     fn test_build_tracking_url_unknown() {
         assert!(build_tracking_url("不明業者", "123").is_none());
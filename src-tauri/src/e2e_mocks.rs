@@ -37,6 +37,11 @@ impl GmailClientTrait for E2EMockGmailClient {
         );
         Err("E2E mock: get_message_metadata should not be called with empty list".to_string())
     }
+
+    async fn get_message_raw(&self, message_id: &str) -> Result<Vec<u8>, String> {
+        log::info!("[E2E Mock] Gmail get_message_raw: {} (unused)", message_id);
+        Err("E2E mock: get_message_raw should not be called with empty list".to_string())
+    }
 }
 
 /// E2E用 Gemini API モック（入力商品名をそのままパース結果として返す）
@@ -162,6 +167,13 @@ impl GmailClientTrait for GmailClientForE2E {
             Self::Mock(m) => m.get_message_metadata(message_id).await,
         }
     }
+
+    async fn get_message_raw(&self, message_id: &str) -> Result<Vec<u8>, String> {
+        match self {
+            Self::Real(c) => c.get_message_raw(message_id).await,
+            Self::Mock(m) => m.get_message_raw(message_id).await,
+        }
+    }
 }
 
 /// Gemini クライアントの E2E 対応ラッパー（実機 or モックを切り替え）
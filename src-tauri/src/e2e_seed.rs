@@ -17,6 +17,7 @@ pub async fn seed_if_e2e_and_empty(pool: &SqlitePool) {
     }
 
     // orders テーブルが存在するか確認（マイグレーション未実行ならスキップ）
+    // ゴミ箱内の注文もシードの固定 id と衝突するため、deleted_at に関係なく数える
     let count: Result<(i64,), _> = sqlx::query_as("SELECT COUNT(*) FROM orders")
         .fetch_one(pool)
         .await;
@@ -50,8 +51,8 @@ pub async fn seed_if_e2e_and_empty(pool: &SqlitePool) {
     // orders
     sqlx::query(
         r#"
-        INSERT INTO orders (id, shop_domain, shop_name, order_number, order_date, created_at, updated_at)
-        VALUES (1, 'example.com', 'Example Shop', 'ORD-E2E-001', '2024-01-15 12:00:00', '2024-01-15 12:00:00', '2024-01-15 12:00:00')
+        INSERT INTO orders (id, shop_domain, shop_name, order_number, order_number_normalized, order_date, created_at, updated_at)
+        VALUES (1, 'example.com', 'Example Shop', 'ORD-E2E-001', 'ORDE2E001', '2024-01-15 12:00:00', '2024-01-15 12:00:00', '2024-01-15 12:00:00')
         "#,
     )
     .execute(pool)
@@ -121,6 +122,7 @@ mod tests {
               shop_domain TEXT,
               shop_name TEXT,
               order_number TEXT,
+              order_number_normalized TEXT,
               order_date TEXT,
               created_at TEXT,
               updated_at TEXT
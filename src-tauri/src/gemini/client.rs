@@ -6,8 +6,9 @@
 //!
 //! # レート制限対策
 //! - 1リクエストで最大10件処理
-//! - リクエスト間に10秒のディレイ
-//! - RESOURCE_EXHAUSTED エラー時は処理をスキップ
+//! - RPM（リクエスト数/分）・TPM（トークン数/分）のトークンバケットで送信ペースを自動調整
+//! - 429 / RESOURCE_EXHAUSTED 時は retryDelay（なければ既定値）だけ待機して再試行
+//! - 再試行上限を超えた場合は処理をスキップ
 
 use async_trait::async_trait;
 use bytes::Bytes;
@@ -18,7 +19,8 @@ use hyper_util::client::legacy::connect::HttpConnector;
 use hyper_util::client::legacy::Client;
 use hyper_util::rt::TokioExecutor;
 use serde::{Deserialize, Serialize};
-use std::time::Duration;
+use std::time::{Duration, Instant};
+use tokio::sync::Mutex;
 use tokio::time::sleep;
 
 /// Gemini API がパースした商品情報
@@ -175,12 +177,215 @@ fn normalize_series(series: &str) -> String {
 
 /// Gemini API のレート制限関連定数
 pub const GEMINI_BATCH_SIZE: usize = 10;
-pub const GEMINI_DELAY_SECONDS: u64 = 10;
+
+/// RPM（1分あたりのリクエスト数）のデフォルト上限
+pub const DEFAULT_GEMINI_RPM_LIMIT: u32 = 30;
+/// TPM（1分あたりのトークン数）のデフォルト上限
+pub const DEFAULT_GEMINI_TPM_LIMIT: u32 = 1_000_000;
+
+/// 429 時の最大再試行回数
+const GEMINI_MAX_RETRIES: u32 = 3;
+/// 429 レスポンスに retryDelay が含まれない場合の待機秒数
+const GEMINI_DEFAULT_RETRY_DELAY_SECS: u64 = 30;
+/// 429 レスポンスで指定された待機秒数の上限
+const GEMINI_MAX_RETRY_DELAY_SECS: u64 = 120;
+/// 出力トークン上限（リクエストボディの maxOutputTokens と一致させる）
+const GEMINI_MAX_OUTPUT_TOKENS: u32 = 4096;
+
+/// トークンバケット
+///
+/// `capacity` まで貯まり、`refill_per_sec` の速度で補充される。
+/// 時刻は引数で受け取り、テストで経過時間を制御できるようにしている。
+#[derive(Debug, Clone)]
+pub struct TokenBucket {
+    capacity: f64,
+    available: f64,
+    refill_per_sec: f64,
+    last_refill: Instant,
+}
+
+impl TokenBucket {
+    /// 1分あたり `per_minute` を上限とするバケット（満タン状態で開始）
+    pub fn per_minute(per_minute: u32, now: Instant) -> Self {
+        let capacity = f64::from(per_minute.max(1));
+        Self {
+            capacity,
+            available: capacity,
+            refill_per_sec: capacity / 60.0,
+            last_refill: now,
+        }
+    }
+
+    fn refill(&mut self, now: Instant) {
+        let elapsed = now
+            .saturating_duration_since(self.last_refill)
+            .as_secs_f64();
+        self.available = (self.available + elapsed * self.refill_per_sec).min(self.capacity);
+        self.last_refill = now;
+    }
+
+    /// `amount` を取り出せるまでの待機時間（取り出せる場合は ZERO）
+    ///
+    /// 容量を超える要求は容量まで切り詰める（永久に待たないため）。
+    pub fn wait_time(&mut self, amount: f64, now: Instant) -> Duration {
+        self.refill(now);
+        let amount = amount.min(self.capacity);
+        if self.available >= amount {
+            Duration::ZERO
+        } else {
+            Duration::from_secs_f64((amount - self.available) / self.refill_per_sec)
+        }
+    }
+
+    /// `amount` を消費する（wait_time が ZERO であることを確認してから呼ぶ）
+    pub fn consume(&mut self, amount: f64, now: Instant) {
+        self.refill(now);
+        self.available -= amount.min(self.capacity);
+    }
+}
+
+/// RPM / TPM の2つのトークンバケットで送信ペースを制御するレートリミッタ
+#[derive(Debug)]
+pub struct GeminiRateLimiter {
+    state: Mutex<RateLimiterState>,
+}
+
+#[derive(Debug)]
+struct RateLimiterState {
+    requests: TokenBucket,
+    tokens: TokenBucket,
+    /// 429 受信後、この時刻まで送信を止める
+    blocked_until: Option<Instant>,
+}
+
+impl RateLimiterState {
+    /// リクエスト1件（推定 `estimated_tokens` トークン）を送信可能になるまでの待機時間
+    fn wait_time(&mut self, estimated_tokens: u32, now: Instant) -> Duration {
+        let blocked = self
+            .blocked_until
+            .map(|until| until.saturating_duration_since(now))
+            .unwrap_or(Duration::ZERO);
+        blocked
+            .max(self.requests.wait_time(1.0, now))
+            .max(self.tokens.wait_time(f64::from(estimated_tokens), now))
+    }
+}
+
+impl GeminiRateLimiter {
+    pub fn new(rpm_limit: u32, tpm_limit: u32) -> Self {
+        let now = Instant::now();
+        Self {
+            state: Mutex::new(RateLimiterState {
+                requests: TokenBucket::per_minute(rpm_limit, now),
+                tokens: TokenBucket::per_minute(tpm_limit, now),
+                blocked_until: None,
+            }),
+        }
+    }
+
+    /// 送信枠を確保できるまで待機してから消費する
+    pub async fn acquire(&self, estimated_tokens: u32) {
+        loop {
+            let wait = {
+                let mut state = self.state.lock().await;
+                let now = Instant::now();
+                let wait = state.wait_time(estimated_tokens, now);
+                if wait.is_zero() {
+                    state.requests.consume(1.0, now);
+                    state.tokens.consume(f64::from(estimated_tokens), now);
+                    return;
+                }
+                wait
+            };
+            log::info!(
+                "Gemini rate limiter: waiting {:.1}s before next request",
+                wait.as_secs_f64()
+            );
+            sleep(wait).await;
+        }
+    }
+
+    /// 429 受信時に `delay` の間すべての送信を止める
+    pub async fn block_for(&self, delay: Duration) {
+        let mut state = self.state.lock().await;
+        let until = Instant::now() + delay;
+        state.blocked_until = Some(state.blocked_until.map_or(until, |u| u.max(until)));
+    }
+}
+
+impl Default for GeminiRateLimiter {
+    fn default() -> Self {
+        Self::new(DEFAULT_GEMINI_RPM_LIMIT, DEFAULT_GEMINI_TPM_LIMIT)
+    }
+}
+
+/// リクエストの推定トークン数（入力はおおよそ1文字1トークン + 出力上限）
+pub fn estimate_request_tokens(prompt: &str) -> u32 {
+    u32::try_from(prompt.chars().count())
+        .unwrap_or(u32::MAX)
+        .saturating_add(GEMINI_MAX_OUTPUT_TOKENS)
+}
+
+/// 429 レスポンスから再試行までの待機時間を取り出す
+///
+/// Retry-After ヘッダー（秒）、または RetryInfo の `"retryDelay": "30s"` を参照する。
+/// 負数・NaN など不正な値は無視し、長すぎる値は `GEMINI_MAX_RETRY_DELAY_SECS` に切り詰める。
+pub fn parse_retry_delay(retry_after_header: Option<&str>, body: &str) -> Option<Duration> {
+    let max_delay = Duration::from_secs(GEMINI_MAX_RETRY_DELAY_SECS);
+    if let Some(secs) = retry_after_header.and_then(|v| v.trim().parse::<u64>().ok()) {
+        return Some(Duration::from_secs(secs).min(max_delay));
+    }
+    let value: serde_json::Value = serde_json::from_str(body).ok()?;
+    let details = value.get("error")?.get("details")?.as_array()?;
+    details.iter().find_map(|d| {
+        let delay = d.get("retryDelay")?.as_str()?;
+        let secs: f64 = delay.strip_suffix('s')?.parse().ok()?;
+        if secs.is_nan() || secs < 0.0 {
+            return None;
+        }
+        // 上限を超える値（inf を含む）は変換前に切り詰める
+        Duration::try_from_secs_f64(secs.min(GEMINI_MAX_RETRY_DELAY_SECS as f64)).ok()
+    })
+}
 
 /// リクエスト送信〜レスポンスボディ取得のタイムアウト（秒）
 /// ネットワークハング時に ProductNameParseState が永久に実行中のままになるのを防ぐ
 const GEMINI_REQUEST_TIMEOUT_SECS: u64 = 120;
 
+/// APIキー検証リクエストのタイムアウト（秒）
+const GEMINI_VALIDATION_TIMEOUT_SECS: u64 = 15;
+
+/// モデル情報取得（APIキー検証）のレスポンスを判定する
+///
+/// モデル情報のエラーメッセージには商品名等を含まないため、そのまま利用者向けメッセージに含める。
+fn check_model_access_response(status: u16, body: &[u8], model: &str) -> Result<(), String> {
+    if (200..300).contains(&status) {
+        return Ok(());
+    }
+    let body_text = String::from_utf8_lossy(body);
+    let detail = serde_json::from_slice::<GeminiResponse>(body)
+        .ok()
+        .and_then(|r| r.error)
+        .map(|e| e.message)
+        .unwrap_or_else(|| format!("status {status}"));
+    let is_invalid_key = status == 401 || (status == 400 && body_text.contains("API_KEY_INVALID"));
+    match status {
+        _ if is_invalid_key => Err(format!(
+            "APIキーが無効です。設定画面でGemini APIキーを確認してください。\n詳細: {detail}"
+        )),
+        403 => Err(format!(
+            "このAPIキーではGemini APIを利用できません。APIの有効化とキーの制限設定を確認してください。\n詳細: {detail}"
+        )),
+        404 => Err(format!(
+            "モデル {model} にアクセスできません。\n詳細: {detail}"
+        )),
+        429 => Err(
+            "API利用制限に達しました。しばらく待ってから再度お試しください。".to_string(),
+        ),
+        _ => Err(format!("Gemini API error: {detail}")),
+    }
+}
+
 /// Gemini クライアントトレイト（テスト用モック対応）
 #[cfg_attr(test, mockall::automock)]
 #[async_trait]
@@ -194,7 +399,7 @@ pub trait GeminiClientTrait: Send + Sync {
     async fn parse_single_chunk(&self, product_names: &[String]) -> Option<Vec<ParsedProduct>>;
 
     /// 複数の商品名を一括パース（バッチ処理用）
-    /// 内部で GEMINI_BATCH_SIZE 件ずつに分割し、間に GEMINI_DELAY_SECONDS 秒のディレイを入れる
+    /// 内部で GEMINI_BATCH_SIZE 件ずつに分割し、レート制限に従って順に送信する
     async fn parse_product_names_batch(
         &self,
         product_names: &[String],
@@ -207,6 +412,7 @@ pub struct GeminiClient {
     api_key: String,
     http_client: Client<HttpsConnector<HttpConnector>, Full<Bytes>>,
     model: String,
+    rate_limiter: GeminiRateLimiter,
 }
 
 impl GeminiClient {
@@ -231,9 +437,56 @@ impl GeminiClient {
             api_key,
             http_client,
             model: "gemini-2.0-flash-lite".to_string(),
+            rate_limiter: GeminiRateLimiter::default(),
         })
     }
 
+    /// RPM / TPM の上限を設定する
+    pub fn with_rate_limits(mut self, rpm_limit: u32, tpm_limit: u32) -> Self {
+        self.rate_limiter = GeminiRateLimiter::new(rpm_limit, tpm_limit);
+        self
+    }
+
+    /// APIキーの有効性とモデルへのアクセス権を確認する
+    ///
+    /// 生成リクエストは送らず、モデル情報の取得（`models.get`）だけを行うためトークンを消費しない。
+    pub async fn validate_api_key(&self) -> Result<(), String> {
+        let endpoint = format!(
+            "https://generativelanguage.googleapis.com/v1beta/models/{}",
+            self.model
+        );
+        let req = Request::builder()
+            .method(Method::GET)
+            .uri(&endpoint)
+            .header("X-goog-api-key", &self.api_key)
+            .body(Full::new(Bytes::new()))
+            .map_err(|e| format!("Failed to build request: {e}"))?;
+
+        let (status, body_bytes) =
+            tokio::time::timeout(Duration::from_secs(GEMINI_VALIDATION_TIMEOUT_SECS), async {
+                let response = self
+                    .http_client
+                    .request(req)
+                    .await
+                    .map_err(|e| format!("Failed to send request to Gemini API: {e}"))?;
+                let status = response.status();
+                let body_bytes = response
+                    .into_body()
+                    .collect()
+                    .await
+                    .map_err(|e| format!("Failed to read response body: {e}"))?
+                    .to_bytes();
+                Ok::<_, String>((status, body_bytes))
+            })
+            .await
+            .map_err(|_| {
+                format!("Request timed out after {GEMINI_VALIDATION_TIMEOUT_SECS} seconds")
+            })??;
+
+        log::info!("Gemini API key validation finished with status {status}");
+        check_model_access_response(status.as_u16(), &body_bytes, &self.model)
+    }
+
     /// プロンプト構築
     fn build_prompt(&self, product_names: &[String]) -> String {
         let products_list = product_names
@@ -316,7 +569,7 @@ impl GeminiClient {
             "generationConfig": {
                 "responseMimeType": "application/json",
                 "temperature": 0.1,
-                "maxOutputTokens": 4096
+                "maxOutputTokens": GEMINI_MAX_OUTPUT_TOKENS
             }
         })
         .to_string()
@@ -333,30 +586,18 @@ impl GeminiClient {
         Ok(products)
     }
 
-    /// 単一のAPIリクエストを実行（内部用）
-    /// RESOURCE_EXHAUSTED などのエラー時は None を返す（呼び出し元でフォールバック処理）
-    async fn execute_single_request(&self, product_names: &[String]) -> Option<Vec<ParsedProduct>> {
-        if product_names.is_empty() {
-            return Some(Vec::new());
-        }
-
-        log::info!("Calling Gemini API for {} product(s)", product_names.len());
-
-        let prompt = self.build_prompt(product_names);
-        let request_body = self.build_request_body(&prompt);
-        let endpoint = self.get_endpoint();
-
-        // リクエストのメトリクスのみログに出力（内容や商品名は含めない）
-        log::info!("Gemini API endpoint: {}", endpoint);
-        log::debug!(
-            "Gemini API request body length: {} bytes",
-            request_body.len()
-        );
-
-        let body = Full::new(Bytes::from(request_body));
+    /// HTTP リクエストを送信し、ステータス・Retry-After ヘッダー・ボディを返す
+    ///
+    /// 送信失敗・タイムアウト時は None。
+    async fn send_request(
+        &self,
+        endpoint: &str,
+        request_body: &str,
+    ) -> Option<(hyper::StatusCode, Option<String>, Bytes)> {
+        let body = Full::new(Bytes::from(request_body.to_string()));
         let req = match Request::builder()
             .method(Method::POST)
-            .uri(&endpoint)
+            .uri(endpoint)
             .header("Content-Type", "application/json")
             .header("X-goog-api-key", &self.api_key)
             .body(body)
@@ -377,32 +618,69 @@ impl GeminiClient {
                     .await
                     .map_err(|e| format!("Failed to send request to Gemini API: {e}"))?;
                 let status = response.status();
+                let retry_after = response
+                    .headers()
+                    .get(hyper::header::RETRY_AFTER)
+                    .and_then(|v| v.to_str().ok())
+                    .map(str::to_string);
                 let body_bytes = response
                     .into_body()
                     .collect()
                     .await
                     .map_err(|e| format!("Failed to read response body: {e}"))?
                     .to_bytes();
-                Ok::<_, String>((status, body_bytes))
+                Ok::<_, String>((status, retry_after, body_bytes))
             })
             .await;
 
-        let (status, body_bytes) = match request_result {
-            Ok(Ok((s, b))) => (s, b),
+        match request_result {
+            Ok(Ok(result)) => Some(result),
             Ok(Err(e)) => {
                 log::error!("Failed to complete Gemini API request: {e}");
-                return None;
+                None
             }
             Err(_) => {
                 log::error!(
                     "Gemini API request timed out after {} seconds",
                     GEMINI_REQUEST_TIMEOUT_SECS
                 );
-                return None;
+                None
+            }
+        }
+    }
+
+    /// 単一のAPIリクエストを実行（内部用）
+    /// レートリミッタで送信枠を確保し、RESOURCE_EXHAUSTED 時は待機して再試行する。
+    /// 再試行上限超過やその他のエラー時は None を返す（呼び出し元でフォールバック処理）
+    async fn execute_single_request(&self, product_names: &[String]) -> Option<Vec<ParsedProduct>> {
+        if product_names.is_empty() {
+            return Some(Vec::new());
+        }
+
+        log::info!("Calling Gemini API for {} product(s)", product_names.len());
+
+        let prompt = self.build_prompt(product_names);
+        let request_body = self.build_request_body(&prompt);
+        let endpoint = self.get_endpoint();
+
+        // リクエストのメトリクスのみログに出力（内容や商品名は含めない）
+        log::info!("Gemini API endpoint: {}", endpoint);
+        log::debug!(
+            "Gemini API request body length: {} bytes",
+            request_body.len()
+        );
+
+        let estimated_tokens = estimate_request_tokens(&prompt);
+        let mut attempt = 0;
+        let body_bytes = loop {
+            self.rate_limiter.acquire(estimated_tokens).await;
+            let (status, retry_after, body_bytes) =
+                self.send_request(&endpoint, &request_body).await?;
+
+            if status.is_success() {
+                break body_bytes;
             }
-        };
 
-        if !status.is_success() {
             // レスポンスボディ全文はログに出さず、ステータスコードやボディ長などのメタ情報のみを出力
             // （API側のエラーメッセージがプロンプト=商品名を含むケースがあり、商品データがログに漏れる可能性があるため）
             log::error!(
@@ -412,12 +690,29 @@ impl GeminiClient {
             );
 
             let error_text = String::from_utf8_lossy(&body_bytes);
-            // RESOURCE_EXHAUSTED (429) やその他のエラーは None を返してスキップ
-            if status.as_u16() == 429 || error_text.contains("RESOURCE_EXHAUSTED") {
-                log::warn!("Gemini API quota exceeded, skipping this batch");
+            if status.as_u16() != 429 && !error_text.contains("RESOURCE_EXHAUSTED") {
+                return None;
             }
-            return None;
-        }
+
+            // RESOURCE_EXHAUSTED (429): 指定時間待機して再試行
+            attempt += 1;
+            if attempt > GEMINI_MAX_RETRIES {
+                log::warn!(
+                    "Gemini API quota exceeded after {} retries, skipping this batch",
+                    GEMINI_MAX_RETRIES
+                );
+                return None;
+            }
+            let delay = parse_retry_delay(retry_after.as_deref(), &error_text)
+                .unwrap_or(Duration::from_secs(GEMINI_DEFAULT_RETRY_DELAY_SECS));
+            log::warn!(
+                "Gemini API quota exceeded, retrying in {:.1}s (attempt {}/{})",
+                delay.as_secs_f64(),
+                attempt,
+                GEMINI_MAX_RETRIES
+            );
+            self.rate_limiter.block_for(delay).await;
+        };
 
         let response_text = String::from_utf8_lossy(&body_bytes);
         let gemini_response: GeminiResponse = match serde_json::from_str(&response_text) {
@@ -495,7 +790,7 @@ impl GeminiClientTrait for GeminiClient {
 
     /// 複数の商品名を一括パース
     /// - GEMINI_BATCH_SIZE 件ずつに分割して処理
-    /// - 各リクエスト間に GEMINI_DELAY_SECONDS 秒のディレイ
+    /// - 送信ペースはレートリミッタ（RPM/TPM）が調整する
     /// - エラー時はフォールバックとしてデフォルト値（元の商品名）を返す
     async fn parse_product_names_batch(
         &self,
@@ -509,25 +804,15 @@ impl GeminiClientTrait for GeminiClient {
         let chunk_count = (total_count + GEMINI_BATCH_SIZE - 1) / GEMINI_BATCH_SIZE;
 
         log::info!(
-            "Gemini batch parse: {} items in {} chunk(s) (batch size: {}, delay: {}s)",
+            "Gemini batch parse: {} items in {} chunk(s) (batch size: {})",
             total_count,
             chunk_count,
-            GEMINI_BATCH_SIZE,
-            GEMINI_DELAY_SECONDS
+            GEMINI_BATCH_SIZE
         );
 
         let mut all_results: Vec<ParsedProduct> = Vec::with_capacity(total_count);
 
         for (chunk_idx, chunk) in product_names.chunks(GEMINI_BATCH_SIZE).enumerate() {
-            // 2回目以降のリクエスト前にディレイを入れる
-            if chunk_idx > 0 {
-                log::info!(
-                    "Waiting {} seconds before next Gemini API request...",
-                    GEMINI_DELAY_SECONDS
-                );
-                sleep(Duration::from_secs(GEMINI_DELAY_SECONDS)).await;
-            }
-
             log::info!(
                 "Processing Gemini chunk {}/{}: {} items",
                 chunk_idx + 1,
@@ -605,6 +890,7 @@ mod tests {
                     .build(),
             ),
             model: "gemini-2.0-flash-lite".to_string(),
+            rate_limiter: GeminiRateLimiter::default(),
         };
 
         let prompt = client.build_prompt(&["KADOKAWA 1/7 レム".to_string()]);
@@ -628,6 +914,7 @@ mod tests {
                     .build(),
             ),
             model: "gemini-2.0-flash-lite".to_string(),
+            rate_limiter: GeminiRateLimiter::default(),
         };
 
         let prompt = client.build_prompt(&[
@@ -654,6 +941,7 @@ mod tests {
                     .build(),
             ),
             model: "gemini-2.0-flash-lite".to_string(),
+            rate_limiter: GeminiRateLimiter::default(),
         };
 
         let response_text = r#"[
@@ -694,6 +982,7 @@ mod tests {
                     .build(),
             ),
             model: "gemini-2.0-flash-lite".to_string(),
+            rate_limiter: GeminiRateLimiter::default(),
         };
 
         let invalid_json = "not valid json";
@@ -716,6 +1005,7 @@ mod tests {
                     .build(),
             ),
             model: "gemini-2.0-flash-lite".to_string(),
+            rate_limiter: GeminiRateLimiter::default(),
         };
 
         // 配列形式だが要素がParsedProductの型と合わない
@@ -724,6 +1014,140 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_token_bucket_waits_until_refilled() {
+        let start = Instant::now();
+        let mut bucket = TokenBucket::per_minute(60, start);
+
+        // 満タン（60）から60個消費すると空になる
+        assert_eq!(bucket.wait_time(60.0, start), Duration::ZERO);
+        bucket.consume(60.0, start);
+
+        // 1個/秒で補充されるため、1個取り出すには1秒待つ
+        let wait = bucket.wait_time(1.0, start);
+        assert!((wait.as_secs_f64() - 1.0).abs() < 1e-6);
+
+        // 2秒後には待機不要
+        assert_eq!(
+            bucket.wait_time(1.0, start + Duration::from_secs(2)),
+            Duration::ZERO
+        );
+    }
+
+    #[test]
+    fn test_token_bucket_caps_at_capacity() {
+        let start = Instant::now();
+        let mut bucket = TokenBucket::per_minute(10, start);
+
+        // 長時間経過しても容量以上には貯まらない
+        let later = start + Duration::from_secs(3600);
+        bucket.consume(10.0, later);
+        assert!(bucket.wait_time(1.0, later) > Duration::ZERO);
+
+        // 容量を超える要求は容量まで切り詰める
+        let mut bucket = TokenBucket::per_minute(10, start);
+        assert_eq!(bucket.wait_time(100.0, start), Duration::ZERO);
+    }
+
+    #[test]
+    fn test_rate_limiter_state_uses_longest_wait() {
+        let start = Instant::now();
+        let mut state = RateLimiterState {
+            requests: TokenBucket::per_minute(60, start),
+            tokens: TokenBucket::per_minute(6000, start),
+            blocked_until: None,
+        };
+        assert_eq!(state.wait_time(100, start), Duration::ZERO);
+
+        // TPM 側が不足（6000 消費済み → 100 トークン/秒で補充）
+        state.tokens.consume(6000.0, start);
+        let wait = state.wait_time(500, start);
+        assert!((wait.as_secs_f64() - 5.0).abs() < 1e-6);
+
+        // 429 によるブロックの方が長ければそちらを優先
+        state.blocked_until = Some(start + Duration::from_secs(30));
+        assert_eq!(state.wait_time(500, start), Duration::from_secs(30));
+    }
+
+    #[test]
+    fn test_parse_retry_delay() {
+        assert_eq!(
+            parse_retry_delay(Some("12"), ""),
+            Some(Duration::from_secs(12))
+        );
+
+        let body = r#"{
+            "error": {
+                "code": 429,
+                "status": "RESOURCE_EXHAUSTED",
+                "details": [
+                    {"@type": "type.googleapis.com/google.rpc.QuotaFailure"},
+                    {"@type": "type.googleapis.com/google.rpc.RetryInfo", "retryDelay": "41s"}
+                ]
+            }
+        }"#;
+        assert_eq!(parse_retry_delay(None, body), Some(Duration::from_secs(41)));
+        assert_eq!(parse_retry_delay(None, "not json"), None);
+    }
+
+    #[test]
+    fn test_parse_retry_delay_rejects_invalid_and_clamps_huge_values() {
+        let body_with = |delay: &str| {
+            format!(
+                r#"{{"error": {{"details": [{{"@type": "type.googleapis.com/google.rpc.RetryInfo", "retryDelay": "{delay}"}}]}}}}"#
+            )
+        };
+        let max_delay = Duration::from_secs(GEMINI_MAX_RETRY_DELAY_SECS);
+
+        // 負数・NaN はパニックせず無視する
+        assert_eq!(parse_retry_delay(None, &body_with("-5s")), None);
+        assert_eq!(parse_retry_delay(None, &body_with("NaNs")), None);
+        // Duration に収まらない値・inf は上限に切り詰める
+        assert_eq!(
+            parse_retry_delay(None, &body_with("1e30s")),
+            Some(max_delay)
+        );
+        assert_eq!(parse_retry_delay(None, &body_with("infs")), Some(max_delay));
+        assert_eq!(
+            parse_retry_delay(None, &body_with("1.5s")),
+            Some(Duration::from_millis(1500))
+        );
+        assert_eq!(
+            parse_retry_delay(Some("18446744073709551615"), ""),
+            Some(max_delay)
+        );
+    }
+
+    #[test]
+    fn test_estimate_request_tokens_includes_output_limit() {
+        assert_eq!(
+            estimate_request_tokens("商品A"),
+            3 + GEMINI_MAX_OUTPUT_TOKENS
+        );
+    }
+
+    #[test]
+    fn test_check_model_access_response() {
+        let model = "gemini-2.0-flash-lite";
+        assert!(check_model_access_response(200, b"{}", model).is_ok());
+
+        let invalid_key = br#"{"error":{"code":400,"message":"API key not valid. Please pass a valid API key.","status":"INVALID_ARGUMENT","details":[{"reason":"API_KEY_INVALID"}]}}"#;
+        let err = check_model_access_response(400, invalid_key, model).unwrap_err();
+        assert!(err.contains("APIキーが無効です"));
+        assert!(err.contains("API key not valid"));
+
+        let err = check_model_access_response(403, b"{}", model).unwrap_err();
+        assert!(err.contains("利用できません"));
+
+        let err = check_model_access_response(404, b"not json", model).unwrap_err();
+        assert!(err.contains(model));
+        assert!(err.contains("status 404"));
+
+        // API_KEY_INVALID 以外の 400 は汎用エラー
+        let err = check_model_access_response(400, b"{}", model).unwrap_err();
+        assert_eq!(err, "Gemini API error: status 400");
+    }
+
     #[test]
     fn test_parsed_product_default() {
         let product = ParsedProduct::default();
@@ -4,8 +4,12 @@
 //!
 //! # フック活用
 //! - `before_batch`: キャッシュ一括取得（N+1クエリ回避）
-//! - `process_batch`: Gemini API でチャンク一括パース
-//! - `after_batch`: パース結果を product_master に一括保存
+//! - `process_batch`: Gemini API でチャンク一括パースし、結果を1件ずつ product_master にコミット
+//! - `after_batch`: 保存結果の集計
+//!
+//! # 部分コミット
+//! パース結果は API 応答を受け取った時点で1件ずつ保存する。after_batch まで保存を
+//! 遅延させないため、途中でキャンセル・タイムアウト・エラーが発生しても完了分は失われない。
 
 use crate::batch_runner::BatchTask;
 use crate::gemini::client::{GeminiClientTrait, ParsedProduct};
@@ -37,6 +41,8 @@ pub struct ProductNameParseOutput {
     pub parsed: ParsedProduct,
     /// キャッシュヒットしたか
     pub cache_hit: bool,
+    /// product_master に保存済みか（キャッシュヒット時は false）
+    pub saved: bool,
 }
 
 /// 商品名パースのコンテキスト
@@ -98,6 +104,60 @@ where
     }
 }
 
+impl<C, R> ProductNameParseTask<C, R>
+where
+    C: GeminiClientTrait + 'static,
+    R: ProductMasterRepository + 'static,
+{
+    /// パース結果を product_master に即時コミットし、後続バッチ向けにキャッシュへも反映する
+    ///
+    /// 保存に失敗してもパース結果自体は有効なため `Ok` を返し、`saved: false` で区別する。
+    async fn commit_parsed(
+        &self,
+        input: ProductNameParseInput,
+        parsed: ParsedProduct,
+        context: &ProductNameParseContext<C, R>,
+    ) -> ProductNameParseOutput {
+        let saved = match context
+            .repository
+            .save(
+                &input.raw_name,
+                &input.normalized_name,
+                &parsed,
+                input.platform_hint.clone(),
+            )
+            .await
+        {
+            Ok(_) => {
+                let mut cache = context.cache.lock().await;
+                cache
+                    .raw_name_cache
+                    .insert(input.raw_name.clone(), parsed.clone());
+                cache
+                    .normalized_cache
+                    .insert(input.normalized_name.clone(), parsed.clone());
+                true
+            }
+            Err(e) => {
+                log::error!(
+                    "[{}] Failed to save product master for '{}': {}",
+                    self.name(),
+                    input.raw_name,
+                    e
+                );
+                false
+            }
+        };
+
+        ProductNameParseOutput {
+            input,
+            parsed,
+            cache_hit: false,
+            saved,
+        }
+    }
+}
+
 #[async_trait]
 impl<C, R> BatchTask for ProductNameParseTask<C, R>
 where
@@ -116,6 +176,25 @@ where
         PRODUCT_NAME_PARSE_EVENT_NAME
     }
 
+    fn describe_input(&self, input: &Self::Input) -> Option<String> {
+        Some(input.raw_name.clone())
+    }
+
+    fn describe_output(&self, output: &Self::Output) -> Option<String> {
+        let parsed = &output.parsed;
+        Some(format!(
+            "{}{} / maker={} series={}",
+            parsed.name,
+            if output.cache_hit {
+                "（キャッシュ）"
+            } else {
+                ""
+            },
+            parsed.maker.as_deref().unwrap_or("-"),
+            parsed.series.as_deref().unwrap_or("-")
+        ))
+    }
+
     /// バッチ処理前にキャッシュを一括取得（N+1クエリ回避）
     async fn before_batch(
         &self,
@@ -169,7 +248,7 @@ where
         Ok(())
     }
 
-    /// バッチ処理：キャッシュチェック後、キャッシュミスを Gemini API でパース
+    /// バッチ処理：キャッシュチェック後、キャッシュミスを Gemini API でパースして1件ずつ保存
     async fn process_batch(
         &self,
         inputs: Vec<Self::Input>,
@@ -189,6 +268,7 @@ where
                         input: input.clone(),
                         parsed: cached.clone(),
                         cache_hit: true,
+                        saved: false,
                     }));
                     continue;
                 }
@@ -200,6 +280,7 @@ where
                         input: input.clone(),
                         parsed: cached.clone(),
                         cache_hit: true,
+                        saved: false,
                     }));
                     continue;
                 }
@@ -254,13 +335,9 @@ where
                             Err(format!("API result count mismatch for: {}", input.raw_name));
                     }
                 } else {
-                    // API 結果を results に反映
-                    for ((idx, input), parsed) in cache_misses.iter().zip(parsed_products) {
-                        results[*idx] = Ok(ProductNameParseOutput {
-                            input: input.clone(),
-                            parsed,
-                            cache_hit: false,
-                        });
+                    // API 結果を1件ずつコミットして results に反映
+                    for ((idx, input), parsed) in cache_misses.into_iter().zip(parsed_products) {
+                        results[idx] = Ok(self.commit_parsed(input, parsed, context).await);
                     }
                 }
             }
@@ -280,56 +357,21 @@ where
         results
     }
 
-    /// バッチ処理後：パース結果を product_master に保存
+    /// バッチ処理後：保存結果を集計（保存自体は process_batch で1件ずつ実施済み）
     async fn after_batch(
         &self,
         batch_number: usize,
         results: &[Result<Self::Output, String>],
-        context: &Self::Context,
+        _context: &Self::Context,
     ) -> Result<(), String> {
-        log::debug!(
-            "[{}] after_batch: batch {} with {} results",
-            self.name(),
-            batch_number,
-            results.len()
-        );
-
-        // 成功した結果のうち、キャッシュミス（API呼び出し結果）のみを保存
-        let mut saved_count = 0;
-        let mut save_errors = 0;
-
-        for output in results.iter().filter_map(|r| r.as_ref().ok()) {
-            // キャッシュヒットは保存不要
-            if output.cache_hit {
-                continue;
-            }
-
-            // DB に保存
-            if let Err(e) = context
-                .repository
-                .save(
-                    &output.input.raw_name,
-                    &output.input.normalized_name,
-                    &output.parsed,
-                    output.input.platform_hint.clone(),
-                )
-                .await
-            {
-                log::error!(
-                    "[{}] Failed to save product master for '{}': {}",
-                    self.name(),
-                    output.input.raw_name,
-                    e
-                );
-                save_errors += 1;
-            } else {
-                saved_count += 1;
-            }
-        }
+        let outputs: Vec<&ProductNameParseOutput> =
+            results.iter().filter_map(|r| r.as_ref().ok()).collect();
+        let saved_count = outputs.iter().filter(|o| o.saved).count();
+        let save_errors = outputs.iter().filter(|o| !o.cache_hit && !o.saved).count();
 
         // 成功件数と失敗件数をログ
-        let success = results.iter().filter(|r| r.is_ok()).count();
-        let failed = results.iter().filter(|r| r.is_err()).count();
+        let success = outputs.len();
+        let failed = results.len() - success;
         log::info!(
             "[{}] Batch {} complete: {} success, {} failed, {} saved, {} save_errors",
             self.name(),
@@ -359,6 +401,7 @@ where
                     input: input.clone(),
                     parsed: cached.clone(),
                     cache_hit: true,
+                    saved: false,
                 });
             }
             if let Some(cached) = cache.normalized_cache.get(&input.normalized_name) {
@@ -366,6 +409,7 @@ where
                     input: input.clone(),
                     parsed: cached.clone(),
                     cache_hit: true,
+                    saved: false,
                 });
             }
         }
@@ -391,6 +435,7 @@ where
             input,
             parsed: result,
             cache_hit: false,
+            saved: true,
         })
     }
 }
@@ -572,7 +617,8 @@ mod tests {
                 ])
             });
 
-        let repo = MockProductMasterRepository::new();
+        let mut repo = MockProductMasterRepository::new();
+        repo.expect_save().times(2).returning(|_, _, _, _| Ok(1));
         let context = ProductNameParseContext {
             gemini_client: Arc::new(client),
             repository: Arc::new(repo),
@@ -588,8 +634,71 @@ mod tests {
         assert_eq!(results.len(), 2);
         assert_eq!(results[0].as_ref().unwrap().parsed.name, "A-api");
         assert!(!results[0].as_ref().unwrap().cache_hit);
+        assert!(results[0].as_ref().unwrap().saved);
         assert_eq!(results[1].as_ref().unwrap().parsed.name, "B-api");
         assert!(!results[1].as_ref().unwrap().cache_hit);
+        assert!(results[1].as_ref().unwrap().saved);
+
+        // 保存済みの結果は後続バッチ向けにキャッシュへ反映される
+        let cache = context.cache.lock().await;
+        assert_eq!(cache.raw_name_cache.get("A").unwrap().name, "A-api");
+        assert_eq!(
+            cache
+                .normalized_cache
+                .get(&input_b.normalized_name)
+                .unwrap()
+                .name,
+            "B-api"
+        );
+    }
+
+    #[tokio::test]
+    async fn process_batch_commits_each_item_and_keeps_results_on_save_error() {
+        let input_a = create_input("A".to_string(), None);
+        let input_b = create_input("B".to_string(), Some("amazon".to_string()));
+
+        let mut client = MockGeminiClientTrait::new();
+        client.expect_parse_single_chunk().times(1).returning(|_| {
+            Some(vec![
+                ParsedProduct {
+                    name: "A-api".to_string(),
+                    ..Default::default()
+                },
+                ParsedProduct {
+                    name: "B-api".to_string(),
+                    ..Default::default()
+                },
+            ])
+        });
+
+        let mut repo = MockProductMasterRepository::new();
+        repo.expect_save()
+            .withf(|raw, _normalized, parsed, _hint| raw == "A" && parsed.name == "A-api")
+            .times(1)
+            .returning(|_, _, _, _| Err("db error".to_string()));
+        repo.expect_save()
+            .withf(|raw, _normalized, parsed, platform_hint| {
+                raw == "B" && parsed.name == "B-api" && platform_hint.as_deref() == Some("amazon")
+            })
+            .times(1)
+            .returning(|_, _, _, _| Ok(2));
+
+        let context = ProductNameParseContext {
+            gemini_client: Arc::new(client),
+            repository: Arc::new(repo),
+            cache: Arc::new(Mutex::new(ProductNameParseCache::default())),
+        };
+
+        let task: ProductNameParseTask<MockGeminiClientTrait, MockProductMasterRepository> =
+            ProductNameParseTask::new();
+        let results = task.process_batch(vec![input_a, input_b], &context).await;
+
+        // 保存に失敗した A もパース結果は返し、B は保存済みになる
+        assert!(!results[0].as_ref().unwrap().saved);
+        assert!(results[1].as_ref().unwrap().saved);
+        let cache = context.cache.lock().await;
+        assert!(!cache.raw_name_cache.contains_key("A"));
+        assert!(cache.raw_name_cache.contains_key("B"));
     }
 
     #[tokio::test]
@@ -663,17 +772,13 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn after_batch_saves_only_cache_misses_and_ignores_save_errors() {
+    async fn after_batch_does_not_save_again() {
         let input_a = create_input("A".to_string(), None);
         let input_b = create_input("B".to_string(), Some("amazon".to_string()));
 
+        // 保存は process_batch で完了しているため after_batch では呼ばれない
         let mut repo = MockProductMasterRepository::new();
-        repo.expect_save()
-            .withf(|raw, _normalized, parsed, platform_hint| {
-                raw == "B" && parsed.name == "B-api" && platform_hint.as_deref() == Some("amazon")
-            })
-            .times(1)
-            .returning(|_, _, _, _| Err("db error".to_string()));
+        repo.expect_save().times(0);
 
         let client = MockGeminiClientTrait::new();
         let context = ProductNameParseContext {
@@ -693,6 +798,7 @@ mod tests {
                     ..Default::default()
                 },
                 cache_hit: true,
+                saved: false,
             }),
             Ok(ProductNameParseOutput {
                 input: input_b,
@@ -701,7 +807,9 @@ mod tests {
                     ..Default::default()
                 },
                 cache_hit: false,
+                saved: true,
             }),
+            Err("Gemini API failed for: C".to_string()),
         ];
 
         task.after_batch(1, &results, &context).await.unwrap();
@@ -0,0 +1,265 @@
+//! 同期時の body_html サイズ上限
+//!
+//! メルマガ等の巨大な HTML メールで DB が肥大化しないよう、保存前に body_html を
+//! `SyncConfig::max_body_html_bytes` で切り詰める。`BodyHtmlOverflow::Attachment` の場合は
+//! 元の HTML 全体を app_data_dir の `email_bodies/{message_id}.html` に保存し、DB には切り詰めた本文のみ残す。
+//! パース・エクスポート時は `restore_truncated_body_html` で保存した元の HTML に差し替える。
+
+use std::path::{Path, PathBuf};
+
+use crate::config::{BodyHtmlOverflow, SyncConfig};
+use crate::gmail::GmailMessage;
+
+/// 元の HTML を保存するディレクトリ名（app_data_dir 配下）
+pub const EMAIL_BODIES_DIRNAME: &str = "email_bodies";
+
+/// 切り詰めた本文の末尾に付ける目印
+const TRUNCATED_MARKER_PREFIX: &str = "<!-- paa: body_html truncated";
+
+/// body_html のサイズ上限と超過時の扱い
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BodyHtmlLimit {
+    /// 最大バイト数（0 なら無制限）
+    pub max_bytes: usize,
+    pub overflow: BodyHtmlOverflow,
+    /// 元の HTML を保存するディレクトリ（`Attachment` でも None なら切り詰めのみ）
+    pub attachment_dir: Option<PathBuf>,
+}
+
+impl BodyHtmlLimit {
+    /// 制限なし（テスト用・設定を読めない場合用）
+    pub fn unlimited() -> Self {
+        Self {
+            max_bytes: 0,
+            overflow: BodyHtmlOverflow::Truncate,
+            attachment_dir: None,
+        }
+    }
+
+    pub fn from_config(sync: &SyncConfig, app_data_dir: Option<&Path>) -> Self {
+        Self {
+            max_bytes: usize::try_from(sync.max_body_html_bytes).unwrap_or(0),
+            overflow: sync.body_html_overflow,
+            attachment_dir: app_data_dir.map(|d| d.join(EMAIL_BODIES_DIRNAME)),
+        }
+    }
+
+    /// 上限を超える body_html を切り詰める（`Attachment` なら先に元の HTML をファイルへ保存する）
+    ///
+    /// 切り詰めた場合は true。ファイル保存に失敗した場合は本文を失わないよう切り詰めずにエラーを返す。
+    pub fn apply(&self, message: &mut GmailMessage) -> Result<bool, String> {
+        let Some(html) = message.body_html.as_deref() else {
+            return Ok(false);
+        };
+        let Some(trimmed) = trim_body_html(html, self.max_bytes) else {
+            return Ok(false);
+        };
+
+        if self.overflow == BodyHtmlOverflow::Attachment {
+            if let Some(dir) = &self.attachment_dir {
+                std::fs::create_dir_all(dir)
+                    .map_err(|e| format!("Failed to create email bodies dir: {e}"))?;
+                std::fs::write(body_attachment_path(dir, &message.message_id), html).map_err(
+                    |e| {
+                        format!(
+                            "Failed to save body_html of {} as file: {e}",
+                            message.message_id
+                        )
+                    },
+                )?;
+            }
+        }
+
+        log::info!(
+            "Truncated body_html of {} ({} -> {} bytes, {:?})",
+            message.message_id,
+            html.len(),
+            trimmed.len(),
+            self.overflow
+        );
+        message.body_html = Some(trimmed);
+        Ok(true)
+    }
+}
+
+/// `max_bytes` を超える HTML を文字境界で切り詰め、末尾に目印のコメントを付ける
+///
+/// 上限以内（または `max_bytes` が 0）の場合は None。
+pub fn trim_body_html(html: &str, max_bytes: usize) -> Option<String> {
+    if max_bytes == 0 || html.len() <= max_bytes {
+        return None;
+    }
+    let mut end = max_bytes;
+    while !html.is_char_boundary(end) {
+        end -= 1;
+    }
+    Some(format!(
+        "{}\n{TRUNCATED_MARKER_PREFIX} ({} bytes) -->",
+        &html[..end],
+        html.len()
+    ))
+}
+
+/// body_html が同期時に切り詰められたものか
+pub fn is_body_html_truncated(html: &str) -> bool {
+    html.trim_end()
+        .rsplit_once('\n')
+        .map_or(html, |(_, last)| last)
+        .starts_with(TRUNCATED_MARKER_PREFIX)
+}
+
+/// 元の HTML の保存先（message_id のファイル名に使えない文字は `_` に置き換える）
+pub fn body_attachment_path(dir: &Path, message_id: &str) -> PathBuf {
+    let file_stem: String = message_id
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    dir.join(format!("{file_stem}.html"))
+}
+
+/// ファイルに保存した元の HTML を読み込む（保存していない場合は None）
+pub fn load_body_attachment(app_data_dir: &Path, message_id: &str) -> Option<String> {
+    std::fs::read_to_string(body_attachment_path(
+        &app_data_dir.join(EMAIL_BODIES_DIRNAME),
+        message_id,
+    ))
+    .ok()
+}
+
+/// 同期時に切り詰めた body_html を、ファイルに保存した元の HTML に差し替える
+///
+/// パース・エクスポートで本文全体が必要な場合に使う。切り詰めていない場合と
+/// 元の HTML を保存していない場合（`Truncate`）は何もしない。差し替えた場合は true。
+pub fn restore_truncated_body_html(
+    body_html: &mut Option<String>,
+    message_id: &str,
+    app_data_dir: &Path,
+) -> bool {
+    if !body_html.as_deref().is_some_and(is_body_html_truncated) {
+        return false;
+    }
+    match load_body_attachment(app_data_dir, message_id) {
+        Some(html) => {
+            *body_html = Some(html);
+            true
+        }
+        None => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn message(body_html: &str) -> GmailMessage {
+        GmailMessage {
+            message_id: "msg-1".to_string(),
+            snippet: String::new(),
+            subject: None,
+            body_plain: None,
+            body_html: Some(body_html.to_string()),
+            internal_date: 0,
+            from_address: None,
+        }
+    }
+
+    #[test]
+    fn test_trim_body_html_respects_char_boundary() {
+        assert_eq!(trim_body_html("<p>abc</p>", 0), None);
+        assert_eq!(trim_body_html("<p>abc</p>", 10), None);
+
+        // 「あ」は 3 バイト。4 バイト目で切ると文字の途中になるため 3 バイトに丸める
+        let trimmed = trim_body_html("あいう", 4).unwrap();
+        assert!(trimmed.starts_with("あ\n"));
+        assert!(is_body_html_truncated(&trimmed));
+        assert!(!is_body_html_truncated("あいう"));
+    }
+
+    #[test]
+    fn test_apply_truncate_only() {
+        let limit = BodyHtmlLimit {
+            max_bytes: 5,
+            overflow: BodyHtmlOverflow::Truncate,
+            attachment_dir: None,
+        };
+        let mut msg = message("0123456789");
+        assert!(limit.apply(&mut msg).unwrap());
+        assert!(msg.body_html.as_deref().unwrap().starts_with("01234\n"));
+
+        let mut small = message("0123");
+        assert!(!limit.apply(&mut small).unwrap());
+        assert_eq!(small.body_html.as_deref(), Some("0123"));
+    }
+
+    #[test]
+    fn test_apply_attachment_saves_original() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let sync = SyncConfig {
+            batch_size: 50,
+            max_iterations: 1000,
+            max_results_per_page: 100,
+            timeout_minutes: 30,
+            save_raw_eml: false,
+            max_body_html_bytes: 5,
+            body_html_overflow: BodyHtmlOverflow::Attachment,
+        };
+        let limit = BodyHtmlLimit::from_config(&sync, Some(dir.path()));
+
+        let mut msg = message("0123456789");
+        assert!(limit.apply(&mut msg).unwrap());
+        assert!(is_body_html_truncated(msg.body_html.as_deref().unwrap()));
+        assert_eq!(
+            load_body_attachment(dir.path(), "msg-1").as_deref(),
+            Some("0123456789")
+        );
+        assert_eq!(load_body_attachment(dir.path(), "msg-2"), None);
+    }
+
+    #[test]
+    fn test_restore_truncated_body_html() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let limit = BodyHtmlLimit {
+            max_bytes: 5,
+            overflow: BodyHtmlOverflow::Attachment,
+            attachment_dir: Some(dir.path().join(EMAIL_BODIES_DIRNAME)),
+        };
+        let mut msg = message("0123456789");
+        limit.apply(&mut msg).unwrap();
+
+        let mut body_html = msg.body_html.clone();
+        assert!(restore_truncated_body_html(
+            &mut body_html,
+            "msg-1",
+            dir.path()
+        ));
+        assert_eq!(body_html.as_deref(), Some("0123456789"));
+
+        // 切り詰めていない本文・元の HTML がない本文はそのまま
+        let mut small = Some("0123".to_string());
+        assert!(!restore_truncated_body_html(
+            &mut small,
+            "msg-1",
+            dir.path()
+        ));
+        assert_eq!(small.as_deref(), Some("0123"));
+        let mut missing = msg.body_html.clone();
+        assert!(!restore_truncated_body_html(
+            &mut missing,
+            "msg-2",
+            dir.path()
+        ));
+        assert_eq!(missing, msg.body_html);
+    }
+
+    #[test]
+    fn test_body_attachment_path_sanitizes_message_id() {
+        let path = body_attachment_path(Path::new("/tmp"), "../a/b");
+        assert_eq!(path, Path::new("/tmp").join("___a_b.html"));
+    }
+}
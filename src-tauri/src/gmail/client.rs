@@ -23,6 +23,7 @@ use sqlx::sqlite::SqlitePool;
 use std::path::PathBuf;
 use std::sync::{Arc, Mutex};
 use tauri::{AppHandle, Manager};
+use ts_rs::TS;
 use yup_oauth2 as oauth2;
 
 // カスタムInstalledFlowDelegateでブラウザを自動的に開く
@@ -78,7 +79,8 @@ pub struct FetchResult {
     pub skipped_count: usize,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, TS)]
+#[ts(export)]
 pub struct SyncMetadata {
     pub sync_status: String,
     pub oldest_fetched_date: Option<String>,
@@ -91,11 +93,51 @@ pub struct SyncMetadata {
     pub max_results_per_page: i64,
     /// 同期処理のタイムアウト（分）
     pub timeout_minutes: i64,
+    /// DB に保存する body_html の最大バイト数（0 なら無制限）
+    pub max_body_html_bytes: i64,
+    /// body_html が上限を超えた場合の扱い
+    pub body_html_overflow: crate::config::BodyHtmlOverflow,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub last_error_message: Option<String>,
+    /// 直近の同期で迷惑メール・プロモーションに振り分けられていた対象ショップのメール数（なければ省略）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub misfiled_shop_mail: Option<MisfiledShopMail>,
+}
+
+/// 迷惑メール・プロモーションタブに振り分けられた対象ショップのメール数
+///
+/// 確認用の検索は1ページ分しか取得しないため、各件数は `MISFILED_COUNT_LIMIT` で頭打ちになる。
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, TS)]
+#[ts(export)]
+pub struct MisfiledShopMail {
+    /// SPAM ラベル付きの件数
+    pub spam_count: i64,
+    /// CATEGORY_PROMOTIONS ラベル付きの件数
+    pub promotions_count: i64,
+}
+
+impl MisfiledShopMail {
+    pub fn is_empty(&self) -> bool {
+        self.spam_count == 0 && self.promotions_count == 0
+    }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+/// 同期のドライラン結果（実際には保存せず件数と所要時間の見積もりのみ）
+#[derive(Debug, Clone, PartialEq, Serialize, TS)]
+#[ts(export)]
+pub struct SyncEstimate {
+    /// 差分同期として見積もったか（DBが空の場合は全件同期にフォールバックするため false）
+    pub incremental: bool,
+    /// Gmail検索クエリに一致したメール数
+    pub matched_message_count: i64,
+    /// うちDB未取得のメール数（実際の同期対象）
+    pub new_message_count: i64,
+    /// 推定所要時間（秒）
+    pub estimated_seconds: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow, TS)]
+#[ts(export)]
 pub struct ShopSettings {
     pub id: i64,
     pub shop_name: String,
@@ -119,7 +161,8 @@ impl ShopSettings {
     }
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, TS)]
+#[ts(export)]
 pub struct CreateShopSettings {
     pub shop_name: String,
     pub sender_address: String,
@@ -127,7 +170,8 @@ pub struct CreateShopSettings {
     pub subject_filters: Option<Vec<String>>, // Frontend sends array, we'll convert to JSON
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, TS)]
+#[ts(export)]
 pub struct UpdateShopSettings {
     pub shop_name: Option<String>,
     pub sender_address: Option<String>,
@@ -153,6 +197,8 @@ pub struct SyncState {
     pub is_running: Arc<Mutex<bool>>,
     /// 直近のエラーメッセージ（エラー時のみ。try_start でクリア）
     pub last_error: Arc<Mutex<Option<String>>>,
+    /// 直近の同期で検出した迷惑メール・プロモーション内の対象メール数（検出なし・未確認なら None）
+    pub misfiled_shop_mail: Arc<Mutex<Option<MisfiledShopMail>>>,
 }
 
 impl Default for SyncState {
@@ -161,6 +207,7 @@ impl Default for SyncState {
             should_cancel: Arc::new(Mutex::new(false)),
             is_running: Arc::new(Mutex::new(false)),
             last_error: Arc::new(Mutex::new(None)),
+            misfiled_shop_mail: Arc::new(Mutex::new(None)),
         }
     }
 }
@@ -199,6 +246,18 @@ impl SyncState {
         }
     }
 
+    /// 迷惑メール・プロモーション内の対象メール数を記録（get_sync_status で警告として返す）
+    pub fn set_misfiled_shop_mail(&self, counts: Option<MisfiledShopMail>) {
+        if let Ok(mut misfiled) = self.misfiled_shop_mail.lock() {
+            *misfiled = counts.filter(|c| !c.is_empty());
+        }
+    }
+
+    /// 直近の同期で検出した迷惑メール・プロモーション内の対象メール数を取得する
+    pub fn misfiled_shop_mail(&self) -> Option<MisfiledShopMail> {
+        self.misfiled_shop_mail.lock().ok().and_then(|g| g.clone())
+    }
+
     /// 強制的に idle にリセット（reset_sync_status コマンド用）
     pub fn force_idle(&self) {
         if let Ok(mut running) = self.is_running.lock() {
@@ -318,7 +377,7 @@ impl GmailClient {
             .map_err(|e| format!("Failed to create app data dir: {e}"))?;
 
         // トークンはファイルに保存（既存の動作を維持）
-        let token_path = app_data_dir.join("gmail_token.json");
+        let token_path = app_data_dir.join(crate::gmail::config::GMAIL_TOKEN_FILENAME);
 
         // keyringから認証情報を取得
         let (client_id, client_secret) =
@@ -329,6 +388,11 @@ impl GmailClient {
                 )
             })?;
 
+        // 旧バージョン等で広いスコープを許可したトークンが残っている場合は再認証を促す
+        for warning in crate::gmail::config::token_scope_warnings(&app_data_dir) {
+            log::warn!("{warning}");
+        }
+
         let auth = Self::authenticate_from_keyring(&client_id, &client_secret, &token_path).await?;
 
         // トークンを取得して認証を確実にする
@@ -336,7 +400,7 @@ impl GmailClient {
         // ※get_token が None を返すと Authorization ヘッダーが付与されず 403 エラーになる
         log::info!("Requesting OAuth token...");
         let token = auth
-            .token(&["https://www.googleapis.com/auth/gmail.readonly"])
+            .token(&[crate::gmail::config::GMAIL_READONLY_SCOPE])
             .await
             .map_err(|e| format!("Failed to get OAuth token: {e}"))?;
         let token_str = token.token().unwrap_or("");
@@ -420,6 +484,7 @@ impl GmailClient {
                 .hub
                 .users()
                 .messages_list("me")
+                .add_scope(Scope::Readonly)
                 .q(query)
                 .include_spam_trash(true);
 
@@ -569,6 +634,29 @@ impl GmailClient {
         })
     }
 
+    /// メッセージの原本（RFC 822 形式）を取得
+    ///
+    /// `format("raw")` のレスポンスの `raw` フィールドはクライアントライブラリ側で
+    /// Base64URL デコード済みのバイト列として返される。
+    async fn get_message_raw(&self, message_id: &str) -> Result<Vec<u8>, String> {
+        log::debug!("Fetching raw message: {message_id}");
+
+        let (_, message) = self
+            .hub
+            .users()
+            .messages_get("me", message_id)
+            .add_scope(Scope::Readonly)
+            .format("raw")
+            .doit()
+            .await
+            .map_err(|e| format!("Failed to get raw message {message_id}: {e}"))?;
+
+        message
+            .raw
+            .filter(|raw| !raw.is_empty())
+            .ok_or_else(|| format!("Raw message {message_id} has no content"))
+    }
+
     /// body.data のバイト列を文字列にデコードする
     ///
     /// mime_type に charset が指定されている場合はそれを優先し、Shift_JIS/ISO-2022-JP の
@@ -796,6 +884,7 @@ impl GmailClientTrait for GmailClient {
             .hub
             .users()
             .messages_list("me")
+            .add_scope(Scope::Readonly)
             .q(query)
             .max_results(max_results)
             .include_spam_trash(true);
@@ -827,6 +916,10 @@ impl GmailClientTrait for GmailClient {
     async fn get_message_metadata(&self, message_id: &str) -> Result<GmailMessage, String> {
         GmailClient::get_message_metadata(self, message_id).await
     }
+
+    async fn get_message_raw(&self, message_id: &str) -> Result<Vec<u8>, String> {
+        GmailClient::get_message_raw(self, message_id).await
+    }
 }
 
 pub async fn save_messages_to_db(
@@ -1408,6 +1501,23 @@ mod tests {
         assert!(!sync_state.should_stop());
     }
 
+    #[test]
+    fn test_sync_state_misfiled_shop_mail() {
+        let sync_state = SyncState::new();
+        assert_eq!(sync_state.misfiled_shop_mail(), None);
+
+        let counts = MisfiledShopMail {
+            spam_count: 2,
+            promotions_count: 0,
+        };
+        sync_state.set_misfiled_shop_mail(Some(counts.clone()));
+        assert_eq!(sync_state.misfiled_shop_mail(), Some(counts));
+
+        // 0 件なら警告なしとして扱う
+        sync_state.set_misfiled_shop_mail(Some(MisfiledShopMail::default()));
+        assert_eq!(sync_state.misfiled_shop_mail(), None);
+    }
+
     #[tokio::test]
     async fn test_save_messages_to_db_empty() {
         let pool = create_test_db().await;
@@ -1657,7 +1767,10 @@ mod tests {
             max_iterations: 100,
             max_results_per_page: 100,
             timeout_minutes: 30,
+            max_body_html_bytes: 1024 * 1024,
+            body_html_overflow: crate::config::BodyHtmlOverflow::Attachment,
             last_error_message: None,
+            misfiled_shop_mail: None,
         };
 
         assert_eq!(metadata.sync_status, "idle");
@@ -2073,7 +2186,10 @@ mod tests {
             max_iterations: 100,
             max_results_per_page: 100,
             timeout_minutes: 30,
+            max_body_html_bytes: 1024 * 1024,
+            body_html_overflow: crate::config::BodyHtmlOverflow::Attachment,
             last_error_message: None,
+            misfiled_shop_mail: None,
         };
 
         assert_eq!(metadata.sync_status, "idle");
@@ -2789,7 +2905,10 @@ mod tests {
             max_iterations: 100,
             max_results_per_page: 100,
             timeout_minutes: 30,
+            max_body_html_bytes: 1024 * 1024,
+            body_html_overflow: crate::config::BodyHtmlOverflow::Attachment,
             last_error_message: None,
+            misfiled_shop_mail: None,
         };
 
         let json = serde_json::to_string(&metadata).unwrap();
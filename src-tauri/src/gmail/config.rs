@@ -11,6 +11,12 @@ use std::path::Path;
 /// keyring のサービス名
 const KEYRING_SERVICE: &str = "paa-gmail-oauth";
 
+/// OAuth トークンの保存ファイル名（app_data_dir 配下）
+pub const GMAIL_TOKEN_FILENAME: &str = "gmail_token.json";
+
+/// 要求する OAuth スコープ（メールの読み取りのみ）
+pub const GMAIL_READONLY_SCOPE: &str = "https://www.googleapis.com/auth/gmail.readonly";
+
 /// keyring 用のエントリを取得（client_id用）
 fn client_id_entry() -> Result<Entry, String> {
     Entry::new(KEYRING_SERVICE, "gmail-client-id")
@@ -40,6 +46,11 @@ pub fn has_oauth_credentials(_app_data_dir: &Path) -> bool {
     has_client_id && has_client_secret
 }
 
+/// Gmail へのアクセスが許可済み（OAuth トークンを取得済み）かチェック
+pub fn has_oauth_token(app_data_dir: &Path) -> bool {
+    app_data_dir.join(GMAIL_TOKEN_FILENAME).is_file()
+}
+
 /// OAuth認証情報を読み込み
 ///
 /// # セキュリティ
@@ -108,6 +119,89 @@ pub fn delete_oauth_credentials(_app_data_dir: &Path) -> Result<(), String> {
     Ok(())
 }
 
+/// gmail.readonly 以外のスコープを返す（重複は除く）
+fn excess_scopes<'a>(scopes: impl IntoIterator<Item = &'a str>) -> Vec<String> {
+    let mut excess: Vec<String> = Vec::new();
+    for scope in scopes {
+        let scope = scope.trim();
+        if scope.is_empty() || scope == GMAIL_READONLY_SCOPE {
+            continue;
+        }
+        if !excess.iter().any(|s| s == scope) {
+            excess.push(scope.to_string());
+        }
+    }
+    excess
+}
+
+/// JSON 値から `scopes`（配列）または `scope`（空白区切り文字列）を集める
+fn collect_scopes(value: &serde_json::Value, out: &mut Vec<String>) {
+    if let Some(scopes) = value.get("scopes").and_then(|v| v.as_array()) {
+        out.extend(scopes.iter().filter_map(|s| s.as_str()).map(str::to_string));
+    }
+    if let Some(scope) = value.get("scope").and_then(|v| v.as_str()) {
+        out.extend(scope.split_whitespace().map(str::to_string));
+    }
+}
+
+/// 取り込む認証情報 JSON に記載されたスコープを検証し、警告メッセージを返す
+///
+/// Google Cloud Console の client_secret.json には通常スコープは含まれないが、
+/// 他ツールの設定やトークン JSON を取り込んだ場合に広いスコープの記載があれば警告する。
+pub fn credentials_json_scope_warnings(json_content: &str) -> Vec<String> {
+    let Ok(value) = serde_json::from_str::<serde_json::Value>(json_content) else {
+        return Vec::new();
+    };
+    let mut scopes = Vec::new();
+    collect_scopes(&value, &mut scopes);
+    for key in ["installed", "web"] {
+        if let Some(section) = value.get(key) {
+            collect_scopes(section, &mut scopes);
+        }
+    }
+    if value.get("refresh_token").is_some() {
+        return vec![
+            "取り込んだ JSON はトークン情報のようです。Google Cloud Console からダウンロードした OAuth クライアント（client_secret.json）を指定してください。".to_string(),
+        ];
+    }
+    excess_scopes(scopes.iter().map(String::as_str))
+        .into_iter()
+        .map(|scope| {
+            format!(
+                "認証情報に不要なスコープ {scope} が含まれています。このアプリは gmail.readonly（読み取り専用）のみを要求します。"
+            )
+        })
+        .collect()
+}
+
+/// 保存済みの OAuth トークンが gmail.readonly 以外のスコープを含む場合の警告メッセージを返す
+///
+/// トークンファイルはスコープの組み合わせごとに `[{"scopes": [...], "token": {...}}]` の形式で保存される。
+pub fn token_scope_warnings(app_data_dir: &Path) -> Vec<String> {
+    let Ok(content) = std::fs::read_to_string(app_data_dir.join(GMAIL_TOKEN_FILENAME)) else {
+        return Vec::new();
+    };
+    token_content_scope_warnings(&content)
+}
+
+fn token_content_scope_warnings(content: &str) -> Vec<String> {
+    let Ok(entries) = serde_json::from_str::<Vec<serde_json::Value>>(content) else {
+        return Vec::new();
+    };
+    let mut scopes = Vec::new();
+    for entry in &entries {
+        collect_scopes(entry, &mut scopes);
+    }
+    let excess = excess_scopes(scopes.iter().map(String::as_str));
+    if excess.is_empty() {
+        return Vec::new();
+    }
+    vec![format!(
+        "保存済みの Gmail トークンに不要なスコープ（{}）が含まれています。{GMAIL_TOKEN_FILENAME} を削除して再認証すると gmail.readonly のみに絞り込めます。",
+        excess.join(", ")
+    )]
+}
+
 /// Google Cloud ConsoleからダウンロードしたJSONの構造
 #[derive(Debug, Deserialize)]
 struct ClientSecretJson {
@@ -154,6 +248,43 @@ pub fn save_oauth_credentials_from_json(
     save_oauth_credentials(_app_data_dir, &client_id, &client_secret)
 }
 
+#[cfg(test)]
+mod scope_tests {
+    use super::*;
+
+    #[test]
+    fn test_credentials_json_scope_warnings() {
+        let client_secret = r#"{"installed":{"client_id":"id","client_secret":"secret"}}"#;
+        assert!(credentials_json_scope_warnings(client_secret).is_empty());
+
+        let readonly =
+            format!(r#"{{"installed":{{"client_id":"id","scopes":["{GMAIL_READONLY_SCOPE}"]}}}}"#);
+        assert!(credentials_json_scope_warnings(&readonly).is_empty());
+
+        let broad = r#"{"scope":"https://mail.google.com/ https://www.googleapis.com/auth/gmail.readonly","installed":{"client_id":"id","client_secret":"secret"}}"#;
+        let warnings = credentials_json_scope_warnings(broad);
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].contains("https://mail.google.com/"));
+
+        let token = r#"{"client_id":"id","refresh_token":"rt"}"#;
+        assert!(credentials_json_scope_warnings(token)[0].contains("トークン情報"));
+    }
+
+    #[test]
+    fn test_token_content_scope_warnings() {
+        let readonly = format!(r#"[{{"scopes":["{GMAIL_READONLY_SCOPE}"],"token":{{}}}}]"#);
+        assert!(token_content_scope_warnings(&readonly).is_empty());
+
+        let broad = r#"[{"scopes":["https://www.googleapis.com/auth/gmail.modify"],"token":{}},{"scopes":["https://www.googleapis.com/auth/gmail.modify"],"token":{}}]"#;
+        let warnings = token_content_scope_warnings(broad);
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].contains("gmail.modify"));
+        assert!(warnings[0].contains(GMAIL_TOKEN_FILENAME));
+
+        assert!(token_content_scope_warnings("not json").is_empty());
+    }
+}
+
 #[cfg(test)]
 #[cfg(not(ci))]
 mod tests {
@@ -0,0 +1,441 @@
+//! .eml（RFC 822）形式の組み立てと読み込み
+//!
+//! 同期時に原本（Gmail API の raw 形式）を保存していないメールについて、
+//! DB に保存済みの件名・送信者・日時・本文から最小限の .eml を再構築する。
+//! 読み込み（`parse_eml`）はパーサーのスナップショットテスト用で、件名・送信者・本文だけを取り出す。
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+/// RFC 2047 の encoded-word（`=?charset?B?...?=` / `=?charset?Q?...?=`）
+static ENCODED_WORD_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"=\?([^?\s]+)\?([BbQq])\?([^?\s]*)\?=").expect("ENCODED_WORD_RE"));
+
+/// multipart/alternative の境界文字列
+const MULTIPART_BOUNDARY: &str = "paa-eml-boundary";
+
+/// Base64 本文の1行あたりの文字数（RFC 2045）
+const BASE64_LINE_LENGTH: usize = 76;
+
+/// .eml 再構築の入力
+#[derive(Debug, Clone, Default)]
+pub struct EmlParts<'a> {
+    pub message_id: &'a str,
+    pub subject: Option<&'a str>,
+    pub from_address: Option<&'a str>,
+    /// 受信日時（ミリ秒Unix時刻）
+    pub internal_date: Option<i64>,
+    pub body_plain: Option<&'a str>,
+    pub body_html: Option<&'a str>,
+}
+
+/// ヘッダー値を RFC 2047 の encoded-word（UTF-8 / Base64）に変換する。ASCII のみの場合はそのまま返す。
+pub fn encode_header_value(value: &str) -> String {
+    if value.is_ascii() {
+        return value.to_string();
+    }
+    format!("=?UTF-8?B?{}?=", STANDARD.encode(value.as_bytes()))
+}
+
+/// 本文を Base64 エンコードし、76文字ごとに CRLF で折り返す
+fn encode_body_base64(body: &str) -> String {
+    let encoded = STANDARD.encode(body.as_bytes());
+    encoded
+        .as_bytes()
+        .chunks(BASE64_LINE_LENGTH)
+        .map(|chunk| std::str::from_utf8(chunk).unwrap_or_default())
+        .collect::<Vec<_>>()
+        .join("\r\n")
+}
+
+/// 単一パート（ヘッダー + 空行 + 本文）を組み立てる
+fn build_text_part(content_type: &str, body: &str) -> String {
+    format!(
+        "Content-Type: {content_type}; charset=UTF-8\r\nContent-Transfer-Encoding: base64\r\n\r\n{}\r\n",
+        encode_body_base64(body)
+    )
+}
+
+/// DB に保存済みの情報から .eml を組み立てる
+///
+/// - 本文が plain / html の両方ある場合は multipart/alternative
+/// - 片方のみの場合はそのパートのみ、どちらもない場合は空の text/plain
+pub fn build_eml(parts: &EmlParts<'_>) -> String {
+    let mut eml = String::new();
+
+    if let Some(from) = parts.from_address {
+        eml.push_str(&format!("From: {}\r\n", encode_header_value(from)));
+    }
+    if let Some(subject) = parts.subject {
+        eml.push_str(&format!("Subject: {}\r\n", encode_header_value(subject)));
+    }
+    if let Some(date) = parts
+        .internal_date
+        .filter(|d| *d > 0)
+        .and_then(chrono::DateTime::from_timestamp_millis)
+    {
+        eml.push_str(&format!("Date: {}\r\n", date.to_rfc2822()));
+    }
+    eml.push_str(&format!("X-Gmail-Message-Id: {}\r\n", parts.message_id));
+    eml.push_str("MIME-Version: 1.0\r\n");
+
+    match (parts.body_plain, parts.body_html) {
+        (Some(plain), Some(html)) => {
+            eml.push_str(&format!(
+                "Content-Type: multipart/alternative; boundary=\"{MULTIPART_BOUNDARY}\"\r\n\r\n"
+            ));
+            eml.push_str(&format!("--{MULTIPART_BOUNDARY}\r\n"));
+            eml.push_str(&build_text_part("text/plain", plain));
+            eml.push_str(&format!("--{MULTIPART_BOUNDARY}\r\n"));
+            eml.push_str(&build_text_part("text/html", html));
+            eml.push_str(&format!("--{MULTIPART_BOUNDARY}--\r\n"));
+        }
+        (None, Some(html)) => eml.push_str(&build_text_part("text/html", html)),
+        (plain, None) => eml.push_str(&build_text_part("text/plain", plain.unwrap_or_default())),
+    }
+
+    eml
+}
+
+/// .eml から取り出したパース用の情報
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ParsedEml {
+    pub from_address: Option<String>,
+    pub subject: Option<String>,
+    /// 受信日時（ミリ秒Unix時刻。`Date` ヘッダーから）
+    pub internal_date: Option<i64>,
+    pub body_plain: Option<String>,
+    pub body_html: Option<String>,
+}
+
+/// ヘッダー部と本文部に分ける（空行がなければ全体をヘッダーとみなす）
+fn split_header_body(raw: &[u8]) -> (&[u8], &[u8]) {
+    let crlf = raw.windows(4).position(|w| w == b"\r\n\r\n");
+    let lf = raw.windows(2).position(|w| w == b"\n\n");
+    match (crlf, lf) {
+        (Some(c), Some(l)) if l < c => (&raw[..l], &raw[l + 2..]),
+        (Some(c), _) => (&raw[..c], &raw[c + 4..]),
+        (None, Some(l)) => (&raw[..l], &raw[l + 2..]),
+        (None, None) => (raw, &[]),
+    }
+}
+
+/// 折り返しを戻したヘッダーを (小文字の名前, 値) で返す
+fn parse_headers(header: &[u8]) -> Vec<(String, String)> {
+    let text = String::from_utf8_lossy(header);
+    let mut headers: Vec<(String, String)> = Vec::new();
+    for line in text.lines() {
+        if line.starts_with([' ', '\t']) {
+            if let Some((_, value)) = headers.last_mut() {
+                value.push(' ');
+                value.push_str(line.trim());
+            }
+            continue;
+        }
+        if let Some((name, value)) = line.split_once(':') {
+            headers.push((name.trim().to_ascii_lowercase(), value.trim().to_string()));
+        }
+    }
+    headers
+}
+
+fn header<'a>(headers: &'a [(String, String)], name: &str) -> Option<&'a str> {
+    headers
+        .iter()
+        .find(|(n, _)| n == name)
+        .map(|(_, v)| v.as_str())
+}
+
+/// `text/plain; charset="UTF-8"` を (小文字の MIME タイプ, パラメーター) に分ける
+fn parse_content_type(value: &str) -> (String, Vec<(String, String)>) {
+    let mut parts = value.split(';');
+    let mime = parts.next().unwrap_or("").trim().to_ascii_lowercase();
+    let params = parts
+        .filter_map(|p| p.split_once('='))
+        .map(|(k, v)| {
+            (
+                k.trim().to_ascii_lowercase(),
+                v.trim().trim_matches('"').to_string(),
+            )
+        })
+        .collect();
+    (mime, params)
+}
+
+fn decode_charset(bytes: &[u8], charset: &str) -> String {
+    match encoding_rs::Encoding::for_label(charset.trim().as_bytes()) {
+        Some(encoding) => encoding.decode_without_bom_handling(bytes).0.into_owned(),
+        None => String::from_utf8_lossy(bytes).into_owned(),
+    }
+}
+
+/// quoted-printable をデコードする（`=` + 改行はソフト改行）
+fn decode_quoted_printable(input: &[u8], underscore_as_space: bool) -> Vec<u8> {
+    let mut out = Vec::with_capacity(input.len());
+    let mut i = 0;
+    while i < input.len() {
+        match input[i] {
+            b'=' if input[i + 1..].starts_with(b"\r\n") => i += 3,
+            b'=' if input[i + 1..].starts_with(b"\n") => i += 2,
+            b'=' if i + 2 < input.len() => {
+                let hex = std::str::from_utf8(&input[i + 1..i + 3]).unwrap_or("");
+                match u8::from_str_radix(hex, 16) {
+                    Ok(byte) => {
+                        out.push(byte);
+                        i += 3;
+                    }
+                    Err(_) => {
+                        out.push(b'=');
+                        i += 1;
+                    }
+                }
+            }
+            b'_' if underscore_as_space => {
+                out.push(b' ');
+                i += 1;
+            }
+            byte => {
+                out.push(byte);
+                i += 1;
+            }
+        }
+    }
+    out
+}
+
+fn decode_transfer_encoding(body: &[u8], encoding: Option<&str>) -> Vec<u8> {
+    match encoding.map(|e| e.trim().to_ascii_lowercase()).as_deref() {
+        Some("base64") => {
+            let compact: Vec<u8> = body
+                .iter()
+                .copied()
+                .filter(|b| !b.is_ascii_whitespace())
+                .collect();
+            STANDARD.decode(compact).unwrap_or_default()
+        }
+        Some("quoted-printable") => decode_quoted_printable(body, false),
+        _ => body.to_vec(),
+    }
+}
+
+/// ヘッダー値の encoded-word をデコードする（隣り合う encoded-word 間の空白は除去する）
+pub fn decode_header_value(value: &str) -> String {
+    let mut out = String::new();
+    let mut last_end = 0;
+    let mut prev_was_word = false;
+    for caps in ENCODED_WORD_RE.captures_iter(value) {
+        let m = caps.get(0).expect("match");
+        let between = &value[last_end..m.start()];
+        if !(prev_was_word && between.trim().is_empty()) {
+            out.push_str(between);
+        }
+        let bytes = if caps[2].eq_ignore_ascii_case("B") {
+            STANDARD.decode(&caps[3]).unwrap_or_default()
+        } else {
+            decode_quoted_printable(caps[3].as_bytes(), true)
+        };
+        out.push_str(&decode_charset(&bytes, &caps[1]));
+        last_end = m.end();
+        prev_was_word = true;
+    }
+    out.push_str(&value[last_end..]);
+    out
+}
+
+/// パートを再帰的にたどり、最初の text/plain と text/html を取り出す
+fn collect_text_parts(raw: &[u8], parsed: &mut ParsedEml, depth: usize) {
+    let (header_bytes, body) = split_header_body(raw);
+    let headers = parse_headers(header_bytes);
+    let (mime, params) = header(&headers, "content-type")
+        .map(parse_content_type)
+        .unwrap_or_else(|| ("text/plain".to_string(), Vec::new()));
+    let param = |name: &str| {
+        params
+            .iter()
+            .find(|(k, _)| k == name)
+            .map(|(_, v)| v.as_str())
+    };
+
+    if mime.starts_with("multipart/") {
+        let Some(boundary) = param("boundary") else {
+            return;
+        };
+        // 入れ子が深すぎるメールは壊れているとみなして打ち切る
+        if depth >= 8 {
+            return;
+        }
+        let delimiter = format!("--{boundary}");
+        // 行頭の `--boundary` だけを区切りとみなす（末尾の `--boundary--` も終端として含まれる）
+        let positions: Vec<usize> = body
+            .windows(delimiter.len())
+            .enumerate()
+            .filter(|(i, w)| *w == delimiter.as_bytes() && (*i == 0 || body[i - 1] == b'\n'))
+            .map(|(i, _)| i)
+            .collect();
+        for pair in positions.windows(2) {
+            let start = pair[0] + delimiter.len();
+            let part = &body[start..pair[1]];
+            let part = part
+                .strip_prefix(b"\r\n")
+                .or_else(|| part.strip_prefix(b"\n"))
+                .unwrap_or(part);
+            collect_text_parts(part, parsed, depth + 1);
+        }
+        return;
+    }
+
+    let slot = match mime.as_str() {
+        "text/plain" => &mut parsed.body_plain,
+        "text/html" => &mut parsed.body_html,
+        _ => return,
+    };
+    if slot.is_some() {
+        return;
+    }
+    let decoded = decode_transfer_encoding(body, header(&headers, "content-transfer-encoding"));
+    *slot = Some(decode_charset(
+        &decoded,
+        param("charset").unwrap_or("utf-8"),
+    ));
+}
+
+/// .eml を読み込み、送信者・件名・受信日時・本文（plain / html）を取り出す
+pub fn parse_eml(raw: &[u8]) -> ParsedEml {
+    let (header_bytes, _) = split_header_body(raw);
+    let headers = parse_headers(header_bytes);
+
+    let mut parsed = ParsedEml {
+        from_address: header(&headers, "from").map(decode_header_value),
+        subject: header(&headers, "subject").map(decode_header_value),
+        internal_date: header(&headers, "date")
+            .and_then(|d| chrono::DateTime::parse_from_rfc2822(d).ok())
+            .map(|d| d.timestamp_millis()),
+        ..Default::default()
+    };
+    collect_text_parts(raw, &mut parsed, 0);
+    parsed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn decode_base64_lines(encoded: &str) -> String {
+        let joined: String = encoded.split("\r\n").collect();
+        String::from_utf8(STANDARD.decode(joined).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_encode_header_value_ascii_passthrough() {
+        assert_eq!(encode_header_value("Order confirmed"), "Order confirmed");
+    }
+
+    #[test]
+    fn test_encode_header_value_non_ascii() {
+        let encoded = encode_header_value("ご注文確認");
+        assert!(encoded.starts_with("=?UTF-8?B?"));
+        assert!(encoded.ends_with("?="));
+        let inner = &encoded["=?UTF-8?B?".len()..encoded.len() - 2];
+        assert_eq!(decode_base64_lines(inner), "ご注文確認");
+    }
+
+    #[test]
+    fn test_encode_body_base64_wraps_lines() {
+        let body = "あ".repeat(100);
+        let encoded = encode_body_base64(&body);
+        assert!(encoded
+            .split("\r\n")
+            .all(|line| line.len() <= BASE64_LINE_LENGTH));
+        assert_eq!(decode_base64_lines(&encoded), body);
+    }
+
+    #[test]
+    fn test_build_eml_plain_only() {
+        let eml = build_eml(&EmlParts {
+            message_id: "msg1",
+            subject: Some("Test"),
+            from_address: Some("shop@example.com"),
+            internal_date: Some(1704067200000),
+            body_plain: Some("hello"),
+            body_html: None,
+        });
+        assert!(eml.starts_with("From: shop@example.com\r\nSubject: Test\r\n"));
+        assert!(eml.contains("Date: Mon, 1 Jan 2024 00:00:00 +0000\r\n"));
+        assert!(eml.contains("X-Gmail-Message-Id: msg1\r\n"));
+        assert!(eml.contains("Content-Type: text/plain; charset=UTF-8\r\n"));
+        assert!(!eml.contains("multipart"));
+        let body = eml.split("\r\n\r\n").nth(1).unwrap().trim_end();
+        assert_eq!(decode_base64_lines(body), "hello");
+    }
+
+    #[test]
+    fn test_build_eml_multipart_alternative() {
+        let eml = build_eml(&EmlParts {
+            message_id: "msg2",
+            body_plain: Some("plain"),
+            body_html: Some("<p>html</p>"),
+            ..Default::default()
+        });
+        assert!(eml.contains("Content-Type: multipart/alternative"));
+        assert_eq!(
+            eml.matches(&format!("--{MULTIPART_BOUNDARY}\r\n")).count(),
+            2
+        );
+        assert!(eml.ends_with(&format!("--{MULTIPART_BOUNDARY}--\r\n")));
+        assert!(eml.contains("Content-Type: text/html; charset=UTF-8\r\n"));
+        // 日時・件名・送信者がない場合はヘッダーを出力しない
+        assert!(!eml.contains("Date:"));
+        assert!(!eml.contains("Subject:"));
+        assert!(!eml.contains("From:"));
+    }
+
+    #[test]
+    fn test_build_eml_without_body() {
+        let eml = build_eml(&EmlParts {
+            message_id: "msg3",
+            internal_date: Some(0),
+            ..Default::default()
+        });
+        assert!(eml.contains("Content-Type: text/plain; charset=UTF-8\r\n"));
+        assert!(!eml.contains("Date:"));
+    }
+
+    #[test]
+    fn test_parse_eml_roundtrip_multipart() {
+        let eml = build_eml(&EmlParts {
+            message_id: "msg4",
+            subject: Some("ご注文確認"),
+            from_address: Some("ショップ <shop@example.com>"),
+            internal_date: Some(1704067200000),
+            body_plain: Some("注文番号：123"),
+            body_html: Some("<p>注文番号：123</p>"),
+        });
+        let parsed = parse_eml(eml.as_bytes());
+        assert_eq!(parsed.subject.as_deref(), Some("ご注文確認"));
+        assert_eq!(
+            parsed.from_address.as_deref(),
+            Some("ショップ <shop@example.com>")
+        );
+        assert_eq!(parsed.internal_date, Some(1704067200000));
+        assert_eq!(parsed.body_plain.as_deref(), Some("注文番号：123"));
+        assert_eq!(parsed.body_html.as_deref(), Some("<p>注文番号：123</p>"));
+    }
+
+    #[test]
+    fn test_parse_eml_quoted_printable_iso_2022_jp_subject() {
+        let (subject, _, _) = encoding_rs::ISO_2022_JP.encode("発送のお知らせ");
+        let raw = format!(
+            "From: shop@example.com\nSubject: =?ISO-2022-JP?B?{}?=\n \
+             =?UTF-8?Q?=E3=80=90=E5=BF=85=E8=AA=AD=E3=80=91?=\n\
+             Content-Type: text/plain; charset=\"utf-8\"\n\
+             Content-Transfer-Encoding: quoted-printable\n\n\
+             =E6=B3=A8=E6=96=87=\n=E7=95=AA=E5=8F=B7 A-1\n",
+            STANDARD.encode(&subject)
+        );
+        let parsed = parse_eml(raw.as_bytes());
+        assert_eq!(parsed.subject.as_deref(), Some("発送のお知らせ【必読】"));
+        assert_eq!(parsed.body_plain.as_deref(), Some("注文番号 A-1\n"));
+        assert_eq!(parsed.body_html, None);
+    }
+}
@@ -16,10 +16,19 @@
 //! # フック活用
 //! - `before_batch`: ショップ設定の取得、同期ステータスの更新
 //! - `process_batch`: メッセージの取得（Gmail API）
-//! - `after_batch`: メッセージのDB保存
+//! - `after_batch`: メッセージのDB保存（設定により原本 .eml も保存）、処理済みIDのチェックポイント記録
+//!
+//! # ネットワーク断からの復帰
+//! Gmail API 呼び出しはネットワークエラーの間 `retry_on_network_error` で待機・再試行する。
+//! メッセージID一覧のページ位置と処理済みIDは `SyncCheckpoint` に保存され、
+//! 同期が中断しても次回はその続きから再開できる。
 
 use crate::batch_runner::BatchTask;
+use crate::gmail::body_limit::BodyHtmlLimit;
 use crate::gmail::client::GmailMessage;
+use crate::gmail::sync_checkpoint::{
+    retry_on_network_error, NetworkRetryPolicy, SyncCheckpoint, SyncCheckpointStore,
+};
 use crate::gmail_client::GmailClientTrait;
 use crate::repository::{EmailRepository, ShopSettingsRepository};
 use async_trait::async_trait;
@@ -67,6 +76,14 @@ where
     pub shop_settings_repo: Arc<S>,
     /// ショップ設定キャッシュ
     pub shop_settings_cache: Arc<Mutex<ShopSettingsCacheForSync>>,
+    /// 保存したメッセージの原本（raw 形式）も取得・保存するか
+    pub save_raw_eml: bool,
+    /// 保存前に適用する body_html のサイズ上限
+    pub body_html_limit: BodyHtmlLimit,
+    /// 処理済みIDを記録するチェックポイント（None なら記録しない）
+    pub checkpoint: Option<Arc<SyncCheckpointStore>>,
+    /// ネットワークエラー時の再試行設定
+    pub network_retry: NetworkRetryPolicy,
 }
 
 /// Gmail同期タスク
@@ -160,6 +177,45 @@ pub async fn fetch_all_message_ids<C: GmailClientTrait>(
     Ok(all_ids)
 }
 
+/// チェックポイントの続きからメッセージIDを取得する
+///
+/// 1ページ取得するごとに `checkpoint` を更新して `on_page` を呼ぶ（呼び出し側で保存する）。
+/// ネットワークエラーの間は同じページを再試行するため、復帰後は途中のページから続行する。
+/// 戻り値はチェックポイントに記録された全メッセージID（再開前に取得した分を含む）。
+pub async fn fetch_message_ids_with_checkpoint<C, F>(
+    client: &C,
+    max_results_per_page: u32,
+    checkpoint: &mut SyncCheckpoint,
+    network_retry: &NetworkRetryPolicy,
+    mut on_page: F,
+) -> Result<Vec<String>, String>
+where
+    C: GmailClientTrait,
+    F: FnMut(&SyncCheckpoint),
+{
+    let query = checkpoint.query.clone();
+    while !checkpoint.listing_complete {
+        let page_token = checkpoint.next_page_token.clone();
+        let (ids, next_token) = retry_on_network_error(network_retry, "list messages", || {
+            client.list_message_ids(&query, max_results_per_page, page_token.clone())
+        })
+        .await?;
+
+        checkpoint.listing_complete = ids.is_empty() || next_token.is_none();
+        checkpoint.message_ids.extend(ids);
+        checkpoint.next_page_token = next_token;
+        on_page(checkpoint);
+    }
+
+    log::info!(
+        "[Gmail Sync] Fetched {} message IDs with checkpoint (query: {}...)",
+        checkpoint.message_ids.len(),
+        query.chars().take(50).collect::<String>()
+    );
+
+    Ok(checkpoint.message_ids.clone())
+}
+
 #[async_trait]
 impl<C, E, S> BatchTask for GmailSyncTask<C, E, S>
 where
@@ -179,6 +235,25 @@ where
         GMAIL_SYNC_EVENT_NAME
     }
 
+    fn describe_input(&self, input: &Self::Input) -> Option<String> {
+        Some(input.message_id.clone())
+    }
+
+    fn describe_output(&self, output: &Self::Output) -> Option<String> {
+        let status = if output.filtered_out {
+            "除外"
+        } else if output.saved {
+            "保存"
+        } else {
+            "未保存"
+        };
+        Some(format!(
+            "{}: {}",
+            status,
+            output.message.subject.as_deref().unwrap_or("(件名なし)")
+        ))
+    }
+
     /// バッチ処理前にショップ設定を取得
     async fn before_batch(
         &self,
@@ -226,10 +301,10 @@ where
         let mut candidates: Vec<(String, usize)> = Vec::new(); // (message_id, results内のindex)
 
         for input in &inputs {
-            match context
-                .gmail_client
-                .get_message_metadata(&input.message_id)
-                .await
+            match retry_on_network_error(&context.network_retry, "get message metadata", || {
+                context.gmail_client.get_message_metadata(&input.message_id)
+            })
+            .await
             {
                 Ok(metadata) => {
                     if crate::logic::sync_logic::should_save_message(&metadata, &enabled_shops) {
@@ -283,7 +358,11 @@ where
 
         // Phase 2: 候補のみ本文(full)を取得
         for (message_id, idx) in candidates {
-            match context.gmail_client.get_message(&message_id).await {
+            match retry_on_network_error(&context.network_retry, "get message", || {
+                context.gmail_client.get_message(&message_id)
+            })
+            .await
+            {
                 Ok(full_message) => {
                     results[idx] = Ok(GmailSyncOutput {
                         message: full_message,
@@ -329,7 +408,7 @@ where
         let mut save_errors = 0;
 
         // 成功したメッセージを収集（メタデータ段階でフィルタ除外されたものは除く）
-        let messages: Vec<GmailMessage> = results
+        let mut messages: Vec<GmailMessage> = results
             .iter()
             .filter_map(|r| r.as_ref().ok())
             .filter(|o| !o.filtered_out)
@@ -342,9 +421,27 @@ where
                 self.name(),
                 batch_number
             );
+            record_processed_ids(context, results, false);
             return Ok(());
         }
 
+        let raw_target_ids: Vec<String> = if context.save_raw_eml {
+            messages
+                .iter()
+                .filter(|m| crate::logic::sync_logic::should_save_message(m, &enabled_shops))
+                .map(|m| m.message_id.clone())
+                .collect()
+        } else {
+            Vec::new()
+        };
+
+        // 巨大な body_html は保存前に切り詰める（失敗した場合は切り詰めずに保存する）
+        for message in &mut messages {
+            if let Err(e) = context.body_html_limit.apply(message) {
+                log::warn!("[{}] {}", self.name(), e);
+            }
+        }
+
         // DBに保存
         match crate::gmail::client::save_messages_to_db_with_repo(
             context.email_repo.as_ref(),
@@ -374,6 +471,27 @@ where
             }
         }
 
+        // 保存に失敗したメッセージは再開時に再取得するため、処理済みに含めない
+        record_processed_ids(context, results, save_errors == 0);
+
+        // 原本（raw 形式）の保存。失敗しても同期自体は継続する
+        if save_errors == 0 {
+            for message_id in &raw_target_ids {
+                let saved = match context.gmail_client.get_message_raw(message_id).await {
+                    Ok(raw) => context.email_repo.save_raw_message(message_id, &raw).await,
+                    Err(e) => Err(e),
+                };
+                if let Err(e) = saved {
+                    log::warn!(
+                        "[{}] Failed to save raw message {}: {}",
+                        self.name(),
+                        message_id,
+                        e
+                    );
+                }
+            }
+        }
+
         // 成功件数と失敗件数をログ
         let success = results.iter().filter(|r| r.is_ok()).count();
         let failed = results.iter().filter(|r| r.is_err()).count();
@@ -400,9 +518,10 @@ where
         context: &Self::Context,
     ) -> Result<Self::Output, String> {
         // Phase 1: メタデータ取得
-        let metadata = context
-            .gmail_client
-            .get_message_metadata(&input.message_id)
+        let metadata =
+            retry_on_network_error(&context.network_retry, "get message metadata", || {
+                context.gmail_client.get_message_metadata(&input.message_id)
+            })
             .await?;
 
         // ショップ設定を取得
@@ -425,7 +544,10 @@ where
         }
 
         // Phase 2: フィルタ通過 → 本文(full)を取得
-        let full_message = context.gmail_client.get_message(&input.message_id).await?;
+        let full_message = retry_on_network_error(&context.network_retry, "get message", || {
+            context.gmail_client.get_message(&input.message_id)
+        })
+        .await?;
 
         Ok(GmailSyncOutput {
             message: full_message,
@@ -435,6 +557,31 @@ where
     }
 }
 
+/// 処理済みのメッセージIDをチェックポイントに記録する
+///
+/// フィルタ除外されたメッセージは常に、保存対象のメッセージは `include_saved` が true の場合のみ記録する。
+fn record_processed_ids<C, E, S>(
+    context: &GmailSyncContext<C, E, S>,
+    results: &[Result<GmailSyncOutput, String>],
+    include_saved: bool,
+) where
+    C: GmailClientTrait + 'static,
+    E: EmailRepository + 'static,
+    S: ShopSettingsRepository + 'static,
+{
+    let Some(store) = &context.checkpoint else {
+        return;
+    };
+    let ids = results
+        .iter()
+        .filter_map(|r| r.as_ref().ok())
+        .filter(|o| o.filtered_out || include_saved)
+        .map(|o| o.message.message_id.as_str());
+    if let Err(e) = store.mark_processed(ids) {
+        log::warn!("[Gmail Sync] Failed to save sync checkpoint: {e}");
+    }
+}
+
 /// 入力データを生成するヘルパー関数
 pub fn create_sync_input(message_id: String) -> GmailSyncInput {
     GmailSyncInput { message_id }
@@ -554,6 +701,44 @@ mod tests {
         assert_eq!(ids, vec!["a".to_string(), "b".to_string(), "c".to_string()]);
     }
 
+    #[tokio::test]
+    async fn fetch_message_ids_with_checkpoint_resumes_and_retries_network_error() {
+        let mut client = MockGmailClientTrait::new();
+        let calls = std::sync::atomic::AtomicU32::new(0);
+
+        // 再開時は保存済みのページトークンから取得し、ネットワークエラーは同じページを再試行する
+        client
+            .expect_list_message_ids()
+            .withf(|q, max, token| q == "q" && *max == 10 && token.as_deref() == Some("t1"))
+            .times(2)
+            .returning(move |_, _, _| {
+                if calls.fetch_add(1, std::sync::atomic::Ordering::SeqCst) == 0 {
+                    Err("Failed to list messages: error sending request".to_string())
+                } else {
+                    Ok((vec!["c".to_string()], None))
+                }
+            });
+
+        let mut checkpoint = SyncCheckpoint::new("q".to_string(), true);
+        checkpoint.next_page_token = Some("t1".to_string());
+        checkpoint.message_ids = vec!["a".to_string(), "b".to_string()];
+        let retry = NetworkRetryPolicy {
+            max_retries: 1,
+            initial_delay: std::time::Duration::ZERO,
+            max_delay: std::time::Duration::ZERO,
+        };
+        let mut pages = 0;
+
+        let ids =
+            fetch_message_ids_with_checkpoint(&client, 10, &mut checkpoint, &retry, |_| pages += 1)
+                .await
+                .unwrap();
+        assert_eq!(ids, vec!["a".to_string(), "b".to_string(), "c".to_string()]);
+        assert!(checkpoint.listing_complete);
+        assert_eq!(checkpoint.next_page_token, None);
+        assert_eq!(pages, 1);
+    }
+
     #[tokio::test]
     async fn before_batch_loads_shop_settings_into_cache() {
         let mut shop_repo = MockShopSettingsRepository::new();
@@ -570,6 +755,10 @@ mod tests {
             email_repo: Arc::new(email_repo),
             shop_settings_repo: Arc::new(shop_repo),
             shop_settings_cache: Arc::new(Mutex::new(ShopSettingsCacheForSync::default())),
+            save_raw_eml: false,
+            body_html_limit: BodyHtmlLimit::unlimited(),
+            checkpoint: None,
+            network_retry: NetworkRetryPolicy::disabled(),
         };
 
         let task: GmailSyncTask<
@@ -619,6 +808,10 @@ mod tests {
             shop_settings_cache: Arc::new(Mutex::new(ShopSettingsCacheForSync {
                 enabled_shops: vec![dummy_shop_settings(1, "sender@example.com")],
             })),
+            save_raw_eml: false,
+            body_html_limit: BodyHtmlLimit::unlimited(),
+            checkpoint: None,
+            network_retry: NetworkRetryPolicy::disabled(),
         };
 
         let task: GmailSyncTask<
@@ -692,6 +885,10 @@ mod tests {
             shop_settings_cache: Arc::new(Mutex::new(ShopSettingsCacheForSync {
                 enabled_shops: vec![dummy_shop_settings(1, "sender@example.com")],
             })),
+            save_raw_eml: false,
+            body_html_limit: BodyHtmlLimit::unlimited(),
+            checkpoint: None,
+            network_retry: NetworkRetryPolicy::disabled(),
         };
 
         let task: GmailSyncTask<
@@ -763,6 +960,10 @@ mod tests {
                 // 対象メッセージがフィルタ除外されないようにする
                 enabled_shops: vec![dummy_shop_settings(1, "a@example.com")],
             })),
+            save_raw_eml: false,
+            body_html_limit: BodyHtmlLimit::unlimited(),
+            checkpoint: None,
+            network_retry: NetworkRetryPolicy::disabled(),
         };
 
         let task: GmailSyncTask<
@@ -796,6 +997,10 @@ mod tests {
             shop_settings_cache: Arc::new(Mutex::new(ShopSettingsCacheForSync {
                 enabled_shops: vec![dummy_shop_settings(1, "a@example.com")],
             })),
+            save_raw_eml: false,
+            body_html_limit: BodyHtmlLimit::unlimited(),
+            checkpoint: None,
+            network_retry: NetworkRetryPolicy::disabled(),
         };
 
         let task: GmailSyncTask<
@@ -807,4 +1012,51 @@ mod tests {
         let results: Vec<Result<GmailSyncOutput, String>> = vec![Err("x".to_string())];
         task.after_batch(1, &results, &context).await.unwrap();
     }
+
+    #[tokio::test]
+    async fn after_batch_saves_raw_message_when_enabled() {
+        let mut client = MockGmailClientTrait::new();
+        client
+            .expect_get_message_raw()
+            .withf(|id| id == "raw-id")
+            .times(1)
+            .returning(|_| Ok(b"Subject: raw".to_vec()));
+
+        let mut email_repo = MockEmailRepository::new();
+        email_repo
+            .expect_save_messages()
+            .times(1)
+            .returning(|msgs| Ok((msgs.len(), 0)));
+        email_repo
+            .expect_save_raw_message()
+            .withf(|id, raw| id == "raw-id" && raw == b"Subject: raw")
+            .times(1)
+            .returning(|_, _| Ok(()));
+
+        let context = GmailSyncContext {
+            gmail_client: Arc::new(client),
+            email_repo: Arc::new(email_repo),
+            shop_settings_repo: Arc::new(MockShopSettingsRepository::new()),
+            shop_settings_cache: Arc::new(Mutex::new(ShopSettingsCacheForSync {
+                enabled_shops: vec![dummy_shop_settings(1, "sender@example.com")],
+            })),
+            save_raw_eml: true,
+            body_html_limit: BodyHtmlLimit::unlimited(),
+            checkpoint: None,
+            network_retry: NetworkRetryPolicy::disabled(),
+        };
+
+        let task: GmailSyncTask<
+            MockGmailClientTrait,
+            MockEmailRepository,
+            MockShopSettingsRepository,
+        > = GmailSyncTask::new();
+
+        let results: Vec<Result<GmailSyncOutput, String>> = vec![Ok(GmailSyncOutput {
+            message: dummy_message("raw-id"),
+            saved: false,
+            filtered_out: false,
+        })];
+        task.after_batch(1, &results, &context).await.unwrap();
+    }
 }
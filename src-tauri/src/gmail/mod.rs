@@ -1,8 +1,11 @@
 //! Gmail関連モジュール
 
+pub mod body_limit;
 pub mod client;
 pub mod config;
+pub mod eml;
 pub mod gmail_sync_task;
+pub mod sync_checkpoint;
 
 // clientモジュールから公開されている型と関数をre-export
 pub use client::{
@@ -19,7 +22,9 @@ pub use client::{
     FetchResult,
     GmailClient,
     GmailMessage,
+    MisfiledShopMail,
     ShopSettings,
+    SyncEstimate,
     SyncGuard,
     SyncMetadata,
     SyncState,
@@ -28,12 +33,20 @@ pub use client::{
 
 // 認証設定をre-export
 pub use config::{
-    delete_oauth_credentials, has_oauth_credentials, load_oauth_credentials,
-    save_oauth_credentials, save_oauth_credentials_from_json,
+    credentials_json_scope_warnings, delete_oauth_credentials, has_oauth_credentials,
+    has_oauth_token, load_oauth_credentials, save_oauth_credentials,
+    save_oauth_credentials_from_json, token_scope_warnings, GMAIL_READONLY_SCOPE,
 };
 
 // BatchTask実装をre-export
 pub use gmail_sync_task::{
-    create_sync_input, fetch_all_message_ids, GmailSyncContext, GmailSyncInput, GmailSyncOutput,
-    GmailSyncTask, ShopSettingsCacheForSync, GMAIL_SYNC_EVENT_NAME, GMAIL_SYNC_TASK_NAME,
+    create_sync_input, fetch_all_message_ids, fetch_message_ids_with_checkpoint, GmailSyncContext,
+    GmailSyncInput, GmailSyncOutput, GmailSyncTask, ShopSettingsCacheForSync,
+    GMAIL_SYNC_EVENT_NAME, GMAIL_SYNC_TASK_NAME,
 };
+
+// body_html のサイズ上限をre-export
+pub use body_limit::BodyHtmlLimit;
+
+// 同期チェックポイントをre-export
+pub use sync_checkpoint::{NetworkRetryPolicy, SyncCheckpoint, SyncCheckpointStore};
@@ -0,0 +1,335 @@
+//! Gmail同期のチェックポイントとネットワーク断からの復帰
+//!
+//! 長時間の同期中にネットワークが切れても最初からやり直さずに済むよう、
+//! メッセージID一覧のページング位置（`nextPageToken`）と処理済みメッセージIDを
+//! app_config_dir の `sync_checkpoint.json` に保存する。
+//!
+//! - 同期中のネットワークエラーは `retry_on_network_error` で待機・再試行し、復帰後にそのまま続行する
+//! - 再試行の上限を超えて同期が失敗した場合は、次回の同期開始時にチェックポイントから再開する
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::future::Future;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::Duration;
+
+const SYNC_CHECKPOINT_FILENAME: &str = "sync_checkpoint.json";
+
+/// チェックポイントから再開する期限（これより古いものは破棄して最初から同期する）
+const CHECKPOINT_TTL_MS: i64 = 24 * 60 * 60 * 1000;
+
+/// ネットワークエラーとみなすエラーメッセージの断片（小文字で比較）
+const NETWORK_ERROR_PATTERNS: &[&str] = &[
+    "error sending request",
+    "error trying to connect",
+    "connection refused",
+    "connection reset",
+    "connection closed",
+    "connection aborted",
+    "broken pipe",
+    "dns error",
+    "failed to lookup address",
+    "network is unreachable",
+    "no route to host",
+    "timed out",
+    "operation timed out",
+];
+
+/// Gmail同期のチェックポイント
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SyncCheckpoint {
+    /// 全件同期か（差分同期の途中経過で全件同期を再開しないよう区別する）
+    pub full_sync: bool,
+    /// メッセージID一覧の取得に使った検索クエリ
+    pub query: String,
+    /// 次に取得するページのトークン
+    pub next_page_token: Option<String>,
+    /// メッセージID一覧を最後のページまで取得したか
+    pub listing_complete: bool,
+    /// 取得済みのメッセージID
+    pub message_ids: Vec<String>,
+    /// 処理済み（保存済み・フィルタ除外）のメッセージID
+    pub processed_ids: BTreeSet<String>,
+    /// 作成日時（Unix ミリ秒）
+    pub created_at: i64,
+}
+
+impl SyncCheckpoint {
+    pub fn new(query: String, full_sync: bool) -> Self {
+        Self {
+            full_sync,
+            query,
+            next_page_token: None,
+            listing_complete: false,
+            message_ids: Vec::new(),
+            processed_ids: BTreeSet::new(),
+            created_at: chrono::Utc::now().timestamp_millis(),
+        }
+    }
+
+    fn path(config_dir: &Path) -> PathBuf {
+        config_dir.join(SYNC_CHECKPOINT_FILENAME)
+    }
+
+    /// 再開可能なチェックポイントを読み込む
+    ///
+    /// ファイルがない・読めない・同期モードが異なる・期限切れの場合は `None`。
+    pub fn load_resumable(config_dir: &Path, full_sync: bool, now_ms: i64) -> Option<Self> {
+        let contents = std::fs::read_to_string(Self::path(config_dir)).ok()?;
+        let checkpoint: Self = match serde_json::from_str(&contents) {
+            Ok(c) => c,
+            Err(e) => {
+                log::warn!("Ignoring invalid sync checkpoint: {e}");
+                return None;
+            }
+        };
+        if checkpoint.full_sync != full_sync || now_ms - checkpoint.created_at > CHECKPOINT_TTL_MS {
+            return None;
+        }
+        Some(checkpoint)
+    }
+
+    pub fn save(&self, config_dir: &Path) -> Result<(), String> {
+        let contents = serde_json::to_string(self)
+            .map_err(|e| format!("Failed to serialize sync checkpoint: {e}"))?;
+        std::fs::write(Self::path(config_dir), contents)
+            .map_err(|e| format!("Failed to write sync checkpoint: {e}"))
+    }
+
+    pub fn clear(config_dir: &Path) -> Result<(), String> {
+        match std::fs::remove_file(Self::path(config_dir)) {
+            Ok(()) => Ok(()),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+            Err(e) => Err(format!("Failed to remove sync checkpoint: {e}")),
+        }
+    }
+
+    /// 取得済みで未処理のメッセージID（取得順）
+    pub fn pending_ids(&self) -> Vec<String> {
+        self.message_ids
+            .iter()
+            .filter(|id| !self.processed_ids.contains(*id))
+            .cloned()
+            .collect()
+    }
+}
+
+/// 同期中にチェックポイントを更新・保存する（`GmailSyncContext` から共有）
+pub struct SyncCheckpointStore {
+    config_dir: PathBuf,
+    checkpoint: Mutex<SyncCheckpoint>,
+}
+
+impl SyncCheckpointStore {
+    pub fn new(config_dir: PathBuf, checkpoint: SyncCheckpoint) -> Self {
+        Self {
+            config_dir,
+            checkpoint: Mutex::new(checkpoint),
+        }
+    }
+
+    /// 処理済みのメッセージIDを記録して保存する
+    pub fn mark_processed<'a>(&self, ids: impl IntoIterator<Item = &'a str>) -> Result<(), String> {
+        let mut checkpoint = self
+            .checkpoint
+            .lock()
+            .map_err(|e| format!("Lock error: {e}"))?;
+        checkpoint
+            .processed_ids
+            .extend(ids.into_iter().map(str::to_string));
+        checkpoint.save(&self.config_dir)
+    }
+
+    /// 同期完了時にチェックポイントを削除する
+    pub fn clear(&self) -> Result<(), String> {
+        SyncCheckpoint::clear(&self.config_dir)
+    }
+}
+
+/// Gmail API のエラーがネットワーク断によるものか
+pub fn is_network_error(error: &str) -> bool {
+    let error = error.to_lowercase();
+    NETWORK_ERROR_PATTERNS.iter().any(|p| error.contains(p))
+}
+
+/// ネットワークエラー時の再試行設定
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NetworkRetryPolicy {
+    /// 最大再試行回数（0 なら再試行しない）
+    pub max_retries: u32,
+    /// 初回の待機時間（以降は倍々で増やす）
+    pub initial_delay: Duration,
+    /// 待機時間の上限
+    pub max_delay: Duration,
+}
+
+impl Default for NetworkRetryPolicy {
+    /// 最大で約 30 分間、ネットワークの復帰を待つ
+    fn default() -> Self {
+        Self {
+            max_retries: 35,
+            initial_delay: Duration::from_secs(5),
+            max_delay: Duration::from_secs(60),
+        }
+    }
+}
+
+impl NetworkRetryPolicy {
+    /// 再試行しない（テスト用・単発の呼び出し用）
+    pub fn disabled() -> Self {
+        Self {
+            max_retries: 0,
+            initial_delay: Duration::ZERO,
+            max_delay: Duration::ZERO,
+        }
+    }
+
+    fn delay_for(&self, attempt: u32) -> Duration {
+        self.initial_delay
+            .saturating_mul(2u32.saturating_pow(attempt))
+            .min(self.max_delay)
+    }
+}
+
+/// ネットワークエラーの間は待機して `f` を再試行する（それ以外のエラーは即座に返す）
+pub async fn retry_on_network_error<T, F, Fut>(
+    policy: &NetworkRetryPolicy,
+    label: &str,
+    mut f: F,
+) -> Result<T, String>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, String>>,
+{
+    let mut attempt = 0;
+    loop {
+        match f().await {
+            Err(e) if is_network_error(&e) && attempt < policy.max_retries => {
+                let delay = policy.delay_for(attempt);
+                attempt += 1;
+                log::warn!(
+                    "[Gmail Sync] Network error on {label}, retrying in {}s ({attempt}/{}): {e}",
+                    delay.as_secs(),
+                    policy.max_retries
+                );
+                tokio::time::sleep(delay).await;
+            }
+            result => return result,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    fn fast_retry(max_retries: u32) -> NetworkRetryPolicy {
+        NetworkRetryPolicy {
+            max_retries,
+            initial_delay: Duration::ZERO,
+            max_delay: Duration::ZERO,
+        }
+    }
+
+    #[test]
+    fn test_checkpoint_save_and_load_resumable() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let mut checkpoint = SyncCheckpoint::new("from:shop@example.com".to_string(), true);
+        checkpoint.next_page_token = Some("t1".to_string());
+        checkpoint.message_ids = vec!["a".to_string(), "b".to_string(), "c".to_string()];
+        checkpoint.processed_ids.insert("b".to_string());
+        checkpoint.save(dir.path()).unwrap();
+
+        let now = checkpoint.created_at;
+        let loaded = SyncCheckpoint::load_resumable(dir.path(), true, now).unwrap();
+        assert_eq!(loaded, checkpoint);
+        assert_eq!(loaded.pending_ids(), vec!["a".to_string(), "c".to_string()]);
+
+        // 同期モードが異なる・期限切れの場合は再開しない
+        assert!(SyncCheckpoint::load_resumable(dir.path(), false, now).is_none());
+        assert!(
+            SyncCheckpoint::load_resumable(dir.path(), true, now + CHECKPOINT_TTL_MS + 1).is_none()
+        );
+
+        SyncCheckpoint::clear(dir.path()).unwrap();
+        assert!(SyncCheckpoint::load_resumable(dir.path(), true, now).is_none());
+        SyncCheckpoint::clear(dir.path()).unwrap();
+    }
+
+    #[test]
+    fn test_store_mark_processed_persists() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let mut checkpoint = SyncCheckpoint::new("q".to_string(), false);
+        checkpoint.message_ids = vec!["a".to_string(), "b".to_string()];
+        let now = checkpoint.created_at;
+        let store = SyncCheckpointStore::new(dir.path().to_path_buf(), checkpoint);
+
+        store.mark_processed(["a"]).unwrap();
+        let loaded = SyncCheckpoint::load_resumable(dir.path(), false, now).unwrap();
+        assert_eq!(loaded.pending_ids(), vec!["b".to_string()]);
+
+        store.clear().unwrap();
+        assert!(SyncCheckpoint::load_resumable(dir.path(), false, now).is_none());
+    }
+
+    #[test]
+    fn test_is_network_error() {
+        assert!(is_network_error(
+            "Failed to list messages: error sending request for url (https://gmail.googleapis.com/)"
+        ));
+        assert!(is_network_error(
+            "Failed to get message abc: client error (Connect): dns error: failed to lookup address information"
+        ));
+        assert!(is_network_error("Operation timed out"));
+        assert!(!is_network_error(
+            "Failed to get message abc: Bad Request: Invalid id value"
+        ));
+    }
+
+    #[test]
+    fn test_retry_delay_is_capped() {
+        let policy = NetworkRetryPolicy::default();
+        assert_eq!(policy.delay_for(0), Duration::from_secs(5));
+        assert_eq!(policy.delay_for(2), Duration::from_secs(20));
+        assert_eq!(policy.delay_for(10), Duration::from_secs(60));
+        assert_eq!(policy.delay_for(40), Duration::from_secs(60));
+    }
+
+    #[tokio::test]
+    async fn test_retry_on_network_error_recovers() {
+        let calls = AtomicU32::new(0);
+        let result = retry_on_network_error(&fast_retry(3), "test", || async {
+            if calls.fetch_add(1, Ordering::SeqCst) < 2 {
+                Err("error sending request".to_string())
+            } else {
+                Ok(42)
+            }
+        })
+        .await;
+        assert_eq!(result, Ok(42));
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_retry_on_network_error_gives_up_and_skips_other_errors() {
+        let calls = AtomicU32::new(0);
+        let result: Result<(), String> = retry_on_network_error(&fast_retry(2), "test", || async {
+            calls.fetch_add(1, Ordering::SeqCst);
+            Err("connection reset by peer".to_string())
+        })
+        .await;
+        assert!(result.is_err());
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+
+        let calls = AtomicU32::new(0);
+        let result: Result<(), String> = retry_on_network_error(&fast_retry(2), "test", || async {
+            calls.fetch_add(1, Ordering::SeqCst);
+            Err("Invalid id value".to_string())
+        })
+        .await;
+        assert!(result.is_err());
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+}
@@ -33,6 +33,11 @@ pub trait GmailClientTrait: Send + Sync {
     /// 返される `GmailMessage` の `body_plain`, `body_html` は常に `None`。
     /// フィルタリング判定（送信者・件名チェック）に必要な情報のみ取得する。
     async fn get_message_metadata(&self, message_id: &str) -> Result<GmailMessage, String>;
+
+    /// メッセージの原本（RFC 822 形式のバイト列）を取得
+    ///
+    /// `format("raw")` を使用する。.eml としてそのまま保存・エクスポートできる。
+    async fn get_message_raw(&self, message_id: &str) -> Result<Vec<u8>, String>;
 }
 
 #[cfg(test)]
@@ -17,9 +17,11 @@ use hyper_util::client::legacy::Client;
 use hyper_util::rt::TokioExecutor;
 use serde::{Deserialize, Serialize};
 use std::time::Duration;
+use ts_rs::TS;
 
 /// 画像検索結果の1件
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
 pub struct ImageSearchResult {
     /// 画像のURL
     pub url: String,
@@ -51,6 +53,46 @@ struct SerpApiImageResult {
     title: Option<String>,
 }
 
+/// SerpApi アカウント情報（APIキー検証結果）
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct SerpApiAccount {
+    pub plan_name: Option<String>,
+    /// 今月の残り検索回数
+    pub total_searches_left: Option<i64>,
+}
+
+/// アカウント情報レスポンス（エラー判定用）
+#[derive(Debug, Deserialize)]
+struct SerpApiAccountResponse {
+    #[serde(flatten)]
+    account: SerpApiAccount,
+    error: Option<String>,
+}
+
+/// アカウント情報取得（APIキー検証）のレスポンスを判定する
+fn parse_account_response(status: u16, body: &[u8]) -> Result<SerpApiAccount, String> {
+    let response = serde_json::from_slice::<SerpApiAccountResponse>(body);
+    let error = response.as_ref().ok().and_then(|r| r.error.clone());
+    if status == 401 || (status == 400 && error.is_some()) {
+        return Err(format!(
+            "APIキーが無効です。設定画面でSerpApi APIキーを確認してください。\n詳細: {}",
+            error.unwrap_or_else(|| format!("status {status}"))
+        ));
+    }
+    if status == 429 {
+        return Err("API利用制限に達しました。しばらく待ってから再度お試しください。".to_string());
+    }
+    if let Some(error) = error {
+        return Err(format!("SerpApi error: {}", error));
+    }
+    if !(200..300).contains(&status) {
+        return Err(format!("SerpApi returned status {}", status));
+    }
+    response
+        .map(|r| r.account)
+        .map_err(|e| format!("Failed to parse SerpApi account response: {e}"))
+}
+
 /// リクエストタイムアウト（秒）
 const REQUEST_TIMEOUT_SECS: u64 = 30;
 
@@ -103,6 +145,44 @@ impl SerpApiClient {
         })
     }
 
+    /// APIキーの有効性を確認し、アカウント情報（残り検索回数など）を返す
+    ///
+    /// Account API は検索回数を消費しないため、キー保存時の確認に使う。
+    pub async fn validate_api_key(&self) -> Result<SerpApiAccount, String> {
+        let url = format!(
+            "https://serpapi.com/account.json?api_key={}",
+            urlencoding::encode(&self.api_key)
+        );
+        let req = Request::builder()
+            .method(Method::GET)
+            .uri(&url)
+            .header("Accept", "application/json")
+            .body(Full::new(Bytes::new()))
+            .map_err(|e| format!("Failed to build request: {e}"))?;
+
+        let (status, body_bytes) =
+            tokio::time::timeout(Duration::from_secs(REQUEST_TIMEOUT_SECS), async {
+                let response = self
+                    .http_client
+                    .request(req)
+                    .await
+                    .map_err(|e| format!("Failed to send request to SerpApi: {e}"))?;
+                let status = response.status();
+                let body_bytes = response
+                    .into_body()
+                    .collect()
+                    .await
+                    .map_err(|e| format!("Failed to read response body: {e}"))?
+                    .to_bytes();
+                Ok::<_, String>((status, body_bytes))
+            })
+            .await
+            .map_err(|_| format!("Request timed out after {} seconds", REQUEST_TIMEOUT_SECS))??;
+
+        log::info!("SerpApi key validation finished with status {}", status);
+        parse_account_response(status.as_u16(), &body_bytes)
+    }
+
     /// API エンドポイント URL を構築
     fn build_url(&self, query: &str, num_results: u32) -> String {
         let encoded_query = urlencoding::encode(query);
@@ -331,6 +411,24 @@ mod tests {
         assert_eq!(images[0].url, "https://example.com/image.jpg");
     }
 
+    #[test]
+    fn test_parse_account_response() {
+        let body = br#"{"account_id":"x","plan_name":"Free Plan","searches_per_month":100,"total_searches_left":42}"#;
+        let account = parse_account_response(200, body).unwrap();
+        assert_eq!(account.plan_name.as_deref(), Some("Free Plan"));
+        assert_eq!(account.total_searches_left, Some(42));
+
+        let err = parse_account_response(401, br#"{"error":"Invalid API key."}"#).unwrap_err();
+        assert!(err.contains("APIキーが無効です"));
+        assert!(err.contains("Invalid API key."));
+
+        let err = parse_account_response(429, b"{}").unwrap_err();
+        assert!(err.contains("API利用制限"));
+
+        let err = parse_account_response(500, b"oops").unwrap_err();
+        assert_eq!(err, "SerpApi returned status 500");
+    }
+
     #[test]
     fn test_is_gif_url() {
         assert!(is_gif_url("https://example.com/image.gif"));
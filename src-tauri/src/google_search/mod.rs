@@ -10,6 +10,8 @@
 
 pub mod client;
 pub mod config;
+pub mod query;
 
-pub use client::{ImageSearchClientTrait, ImageSearchResult, SerpApiClient};
+pub use client::{ImageSearchClientTrait, ImageSearchResult, SerpApiAccount, SerpApiClient};
 pub use config::{delete_api_key, has_api_key, is_configured, load_api_key, save_api_key};
+pub use query::{build_image_search_query, validate_query_template, DEFAULT_QUERY_TEMPLATE};
@@ -0,0 +1,132 @@
+//! 画像検索クエリの組み立て
+//!
+//! product_master の解析結果（メーカー・シリーズ・商品名・スケール）をテンプレートに当てはめ、
+//! 通販サイトの長い商品名（予約・特典表記など）より的中しやすい検索クエリを作る。
+
+use crate::repository::ProductMaster;
+
+/// 既定の検索クエリテンプレート
+pub const DEFAULT_QUERY_TEMPLATE: &str = "{maker} {series} {product_name} {scale}";
+
+/// テンプレートで使えるプレースホルダー
+pub const QUERY_PLACEHOLDERS: [&str; 5] = [
+    "{maker}",
+    "{series}",
+    "{product_name}",
+    "{scale}",
+    "{raw_name}",
+];
+
+/// テンプレートのバリデーション（プレースホルダーを 1 つ以上含むこと）
+pub fn validate_query_template(template: &str) -> Result<(), String> {
+    if template.trim().is_empty() {
+        return Err("検索クエリのテンプレートを入力してください".to_string());
+    }
+    if !QUERY_PLACEHOLDERS.iter().any(|p| template.contains(p)) {
+        return Err(format!(
+            "検索クエリのテンプレートには {} のいずれかを含めてください",
+            QUERY_PLACEHOLDERS.join(" ")
+        ));
+    }
+    Ok(())
+}
+
+/// テンプレートに商品マスタの値を当てはめて検索クエリを作る
+///
+/// 値がない項目は空文字に置き換え、連続する空白は 1 つにまとめる。
+/// 商品名が取れていない場合（解析失敗など）や組み立て結果が空の場合は `raw_name` を返す。
+pub fn build_image_search_query(template: &str, raw_name: &str, product: &ProductMaster) -> String {
+    let product_name = match product.product_name.as_deref().map(str::trim) {
+        Some(name) if !name.is_empty() => name,
+        _ => return raw_name.to_string(),
+    };
+
+    let query = template
+        .replace("{maker}", product.maker.as_deref().unwrap_or(""))
+        .replace("{series}", product.series.as_deref().unwrap_or(""))
+        .replace("{product_name}", product_name)
+        .replace("{scale}", product.scale.as_deref().unwrap_or(""))
+        .replace("{raw_name}", raw_name);
+    let query = query.split_whitespace().collect::<Vec<_>>().join(" ");
+
+    if query.is_empty() {
+        raw_name.to_string()
+    } else {
+        query
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn product(
+        maker: Option<&str>,
+        series: Option<&str>,
+        product_name: Option<&str>,
+        scale: Option<&str>,
+    ) -> ProductMaster {
+        ProductMaster {
+            id: 1,
+            raw_name: "【予約】1/7 初音ミク フィギュア【特典付】".to_string(),
+            normalized_name: "17初音ミクフィギュア特典付".to_string(),
+            maker: maker.map(str::to_string),
+            series: series.map(str::to_string),
+            product_name: product_name.map(str::to_string),
+            scale: scale.map(str::to_string),
+            is_reissue: false,
+            platform_hint: None,
+            created_at: String::new(),
+            updated_at: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_build_query_with_default_template() {
+        let p = product(
+            Some("グッドスマイルカンパニー"),
+            Some("初音ミク"),
+            Some("初音ミク 10th Anniversary Ver."),
+            Some("1/7"),
+        );
+        assert_eq!(
+            build_image_search_query(DEFAULT_QUERY_TEMPLATE, &p.raw_name, &p),
+            "グッドスマイルカンパニー 初音ミク 初音ミク 10th Anniversary Ver. 1/7"
+        );
+    }
+
+    #[test]
+    fn test_build_query_skips_missing_fields() {
+        let p = product(None, None, Some("RX-78-2 ガンダム"), Some("1/144"));
+        assert_eq!(
+            build_image_search_query(DEFAULT_QUERY_TEMPLATE, &p.raw_name, &p),
+            "RX-78-2 ガンダム 1/144"
+        );
+    }
+
+    #[test]
+    fn test_build_query_custom_template() {
+        let p = product(Some("BANDAI"), None, Some("RX-78-2 ガンダム"), None);
+        assert_eq!(
+            build_image_search_query("{product_name} {maker} 箱", &p.raw_name, &p),
+            "RX-78-2 ガンダム BANDAI 箱"
+        );
+    }
+
+    #[test]
+    fn test_build_query_falls_back_to_raw_name() {
+        let p = product(Some("BANDAI"), None, None, None);
+        assert_eq!(
+            build_image_search_query(DEFAULT_QUERY_TEMPLATE, "生の商品名", &p),
+            "生の商品名"
+        );
+    }
+
+    #[test]
+    fn test_validate_query_template() {
+        assert!(validate_query_template(DEFAULT_QUERY_TEMPLATE).is_ok());
+        assert!(validate_query_template("{raw_name} 画像").is_ok());
+        assert!(validate_query_template("   ").is_err());
+        assert!(validate_query_template("{name}").is_err());
+    }
+}
@@ -5,6 +5,7 @@ use std::sync::Arc;
 use tauri::menu::{Menu, MenuItem, Submenu};
 use tauri::tray::{MouseButton, TrayIconBuilder, TrayIconEvent};
 use tauri::{Emitter, Listener, Manager};
+use tauri_plugin_dialog::{DialogExt, MessageDialogButtons};
 use tauri_plugin_global_shortcut::{Code, GlobalShortcutExt, Modifiers, Shortcut};
 use tauri_plugin_sql::{Migration, MigrationKind};
 use tokio::sync::Notify;
@@ -15,6 +16,8 @@ pub mod clipboard_watcher;
 pub use batch_run_state::BatchRunState;
 pub mod commands;
 pub mod config;
+pub mod config_watcher;
+pub mod db_lock;
 pub mod delivery_check;
 pub mod e2e_mocks;
 pub mod e2e_seed;
@@ -24,12 +27,19 @@ pub mod gmail_client;
 pub mod google_search;
 pub mod image_utils;
 pub mod logic;
+pub mod memory_usage;
 pub mod metadata;
+pub mod notification_rules;
 pub mod orchestration;
 pub mod parsers;
 pub mod plugins;
+pub mod reissue_watch;
+pub mod report;
 pub mod repository;
 pub mod scheduler;
+pub mod stats_snapshot;
+pub mod updater;
+pub mod wishlist;
 
 /// items_fts の trigram トークナイザーは SQLite 3.43 で追加。3.43 以降であることを確認する。
 fn is_sqlite_version_supported(version: &str) -> bool {
@@ -42,6 +52,86 @@ fn is_sqlite_version_supported(version: &str) -> bool {
     major > 3 || (major == 3 && minor >= 43)
 }
 
+/// バックグラウンド処理にシャットダウンを通知してアプリを終了する（トレイの「終了」・ウィンドウを閉じたとき）
+fn shutdown_and_exit(app: &tauri::AppHandle) {
+    // スケジューラを即時起床させてシャットダウンを検出させる
+    if let Some(notify) = app.try_state::<Arc<Notify>>() {
+        notify.notify_one();
+    }
+
+    // 他の端末が DB を使えるようロックファイルを削除する
+    if let Some(db_lock) = app.try_state::<db_lock::DbLockState>() {
+        db_lock.release();
+    }
+
+    // クリップボード監視をグレースフルに停止
+    if let Some(shutdown_signal) = app.try_state::<Arc<AtomicBool>>() {
+        let shutdown_signal = shutdown_signal.inner().clone();
+        // シャットダウン要求を通知
+        shutdown_signal.store(true, Ordering::Relaxed);
+    }
+
+    // 監視スレッドの終了完了を明示的に待つ仕組みは現状ないため、
+    // シャットダウン要求を送ったら即座にアプリケーションを終了する。
+    app.exit(0);
+}
+
+/// ウィンドウの閉じる要求を設定（window.close_behavior）に従って処理する
+fn handle_close_requested(window: &tauri::WebviewWindow) {
+    let app = window.app_handle().clone();
+    let close_behavior = app
+        .path()
+        .app_config_dir()
+        .map_err(|e| format!("Failed to get app config dir: {e}"))
+        .and_then(|dir| config::load(&dir))
+        .map(|c| c.window.close_behavior)
+        .unwrap_or_else(|e| {
+            log::error!("Failed to load close_behavior, hiding window: {e}");
+            config::CloseBehavior::Hide
+        });
+
+    match close_behavior {
+        config::CloseBehavior::Hide => {
+            let _ = window.hide();
+        }
+        config::CloseBehavior::Quit => shutdown_and_exit(&app),
+        config::CloseBehavior::Ask => {
+            let window = window.clone();
+            app.dialog()
+                .message("アプリを終了しますか？\n「トレイに最小化」を選ぶとバックグラウンドで動作を続けます。")
+                .title("paa")
+                .buttons(MessageDialogButtons::OkCancelCustom(
+                    "終了".to_string(),
+                    "トレイに最小化".to_string(),
+                ))
+                .show(move |quit| {
+                    if quit {
+                        shutdown_and_exit(window.app_handle());
+                    } else {
+                        let _ = window.hide();
+                    }
+                });
+        }
+    }
+}
+
+/// 低優先度モードが有効か（設定を読み込めない場合は無効）
+fn low_priority_enabled(app: &tauri::AppHandle) -> bool {
+    app.path()
+        .app_config_dir()
+        .map_err(|e| format!("Failed to get app config dir: {e}"))
+        .and_then(|dir| config::load(&dir))
+        .is_ok_and(|c| c.low_priority.enabled)
+}
+
+fn low_priority_label(enabled: bool) -> &'static str {
+    if enabled {
+        "低優先度モード: ON"
+    } else {
+        "低優先度モード: OFF"
+    }
+}
+
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
     let migrations = || {
@@ -70,14 +160,212 @@ pub fn run() {
                 sql: include_str!("../migrations/004_news_clip_events.sql"),
                 kind: MigrationKind::Up,
             },
+            Migration {
+                version: 5,
+                description: "order_price_warning",
+                sql: include_str!("../migrations/005_order_price_warning.sql"),
+                kind: MigrationKind::Up,
+            },
+            Migration {
+                version: 6,
+                description: "order_tax",
+                sql: include_str!("../migrations/006_order_tax.sql"),
+                kind: MigrationKind::Up,
+            },
+            Migration {
+                version: 7,
+                description: "order_reservation_status",
+                sql: include_str!("../migrations/007_order_reservation_status.sql"),
+                kind: MigrationKind::Up,
+            },
+            Migration {
+                version: 8,
+                description: "auto_tag_rules",
+                sql: include_str!("../migrations/008_auto_tag_rules.sql"),
+                kind: MigrationKind::Up,
+            },
+            Migration {
+                version: 9,
+                description: "email_raws",
+                sql: include_str!("../migrations/009_email_raws.sql"),
+                kind: MigrationKind::Up,
+            },
+            Migration {
+                version: 10,
+                description: "series_master",
+                sql: include_str!("../migrations/010_series_master.sql"),
+                kind: MigrationKind::Up,
+            },
+            Migration {
+                version: 11,
+                description: "soft_delete",
+                sql: include_str!("../migrations/011_soft_delete.sql"),
+                kind: MigrationKind::Up,
+            },
+            Migration {
+                version: 12,
+                description: "cancel_reason_detail",
+                sql: include_str!("../migrations/012_cancel_reason_detail.sql"),
+                kind: MigrationKind::Up,
+            },
+            Migration {
+                version: 13,
+                description: "cancel_reason",
+                sql: include_str!("../migrations/013_cancel_reason.sql"),
+                kind: MigrationKind::Up,
+            },
+            Migration {
+                version: 14,
+                description: "delivery_items",
+                sql: include_str!("../migrations/014_delivery_items.sql"),
+                kind: MigrationKind::Up,
+            },
+            Migration {
+                version: 15,
+                description: "payments",
+                sql: include_str!("../migrations/015_payments.sql"),
+                kind: MigrationKind::Up,
+            },
+            Migration {
+                version: 16,
+                description: "monthly_reports",
+                sql: include_str!("../migrations/016_monthly_reports.sql"),
+                kind: MigrationKind::Up,
+            },
+            Migration {
+                version: 17,
+                description: "stats_snapshots",
+                sql: include_str!("../migrations/017_stats_snapshots.sql"),
+                kind: MigrationKind::Up,
+            },
+            Migration {
+                version: 18,
+                description: "reissue_watches",
+                sql: include_str!("../migrations/018_reissue_watches.sql"),
+                kind: MigrationKind::Up,
+            },
+            Migration {
+                version: 19,
+                description: "analysis_views",
+                sql: include_str!("../migrations/019_analysis_views.sql"),
+                kind: MigrationKind::Up,
+            },
+            Migration {
+                version: 20,
+                description: "smart_filters",
+                sql: include_str!("../migrations/020_smart_filters.sql"),
+                kind: MigrationKind::Up,
+            },
+            Migration {
+                version: 21,
+                description: "item_release_date",
+                sql: include_str!("../migrations/021_item_release_date.sql"),
+                kind: MigrationKind::Up,
+            },
+            Migration {
+                version: 22,
+                description: "item_release_date_precision",
+                sql: include_str!("../migrations/022_item_release_date_precision.sql"),
+                kind: MigrationKind::Up,
+            },
+            Migration {
+                version: 23,
+                description: "shop_settings_match_stats",
+                sql: include_str!("../migrations/023_shop_settings_match_stats.sql"),
+                kind: MigrationKind::Up,
+            },
+            Migration {
+                version: 24,
+                description: "order_amount_adjustment",
+                sql: include_str!("../migrations/024_order_amount_adjustment.sql"),
+                kind: MigrationKind::Up,
+            },
+            Migration {
+                version: 25,
+                description: "item_receipt_reviews",
+                sql: include_str!("../migrations/025_item_receipt_reviews.sql"),
+                kind: MigrationKind::Up,
+            },
+            Migration {
+                version: 26,
+                description: "emails_sender_index",
+                sql: include_str!("../migrations/026_emails_sender_index.sql"),
+                kind: MigrationKind::Up,
+            },
+            Migration {
+                version: 27,
+                description: "order_refund_amount",
+                sql: include_str!("../migrations/027_order_refund_amount.sql"),
+                kind: MigrationKind::Up,
+            },
+            Migration {
+                version: 28,
+                description: "point_transactions",
+                sql: include_str!("../migrations/028_point_transactions.sql"),
+                kind: MigrationKind::Up,
+            },
+            Migration {
+                version: 29,
+                description: "item_url",
+                sql: include_str!("../migrations/029_item_url.sql"),
+                kind: MigrationKind::Up,
+            },
+            Migration {
+                version: 30,
+                description: "notification_rules",
+                sql: include_str!("../migrations/030_notification_rules.sql"),
+                kind: MigrationKind::Up,
+            },
+            Migration {
+                version: 31,
+                description: "wishlist",
+                sql: include_str!("../migrations/031_wishlist.sql"),
+                kind: MigrationKind::Up,
+            },
+            Migration {
+                version: 32,
+                description: "item_storage_locations",
+                sql: include_str!("../migrations/032_item_storage_locations.sql"),
+                kind: MigrationKind::Up,
+            },
+            Migration {
+                version: 33,
+                description: "parser_metrics",
+                sql: include_str!("../migrations/033_parser_metrics.sql"),
+                kind: MigrationKind::Up,
+            },
+            Migration {
+                version: 34,
+                description: "trashed_keys",
+                sql: include_str!("../migrations/034_trashed_keys.sql"),
+                kind: MigrationKind::Up,
+            },
+            Migration {
+                version: 35,
+                description: "payments_business_key",
+                sql: include_str!("../migrations/035_payments_business_key.sql"),
+                kind: MigrationKind::Up,
+            },
+            Migration {
+                version: 36,
+                description: "order_number_normalized",
+                sql: include_str!("../migrations/036_order_number_normalized.sql"),
+                kind: MigrationKind::Up,
+            },
         ]
     };
 
+    let invoke_handler = invoke_handler();
+
     tauri::Builder::default()
         .plugin(tauri_plugin_global_shortcut::Builder::new().build())
         .plugin(tauri_plugin_opener::init())
         .plugin(tauri_plugin_dialog::init())
         .plugin(tauri_plugin_notification::init())
+        .plugin(tauri_plugin_autostart::init(
+            tauri_plugin_autostart::MacosLauncher::LaunchAgent,
+            Some(vec![commands::AUTOSTART_ARG]),
+        ))
         .plugin(tauri_plugin_single_instance::init(|app, _args, _cwd| {
             // 二重起動が検知された場合、既存のウィンドウを最前面に表示
             if let Some(window) = app.get_webview_window("main") {
@@ -160,13 +448,38 @@ pub fn run() {
                 crate::e2e_mocks::is_e2e_mock_mode()
             );
 
+            // 共有フォルダの DB を他の端末が使用中なら、破損を避けるため読み取り専用で開く
+            let db_lock_path = db_lock::lock_path(&db_path);
+            let db_lock_status = match db_lock::try_acquire(&db_lock_path, chrono::Utc::now()) {
+                Ok(status) => Some(status),
+                Err(e) => {
+                    log::warn!("Failed to acquire DB lock, opening database without lock: {e}");
+                    None
+                }
+            };
+            let db_lock_state = db_lock::DbLockState::new(db_lock_path, db_lock_status);
+            let db_read_only = db_lock_state.is_read_only();
+            if let Some(holder) = db_lock_state.status().holder {
+                log::warn!(
+                    "Database is in use by {} (pid {}, since {}); opening read-only",
+                    holder.hostname,
+                    holder.pid,
+                    holder.acquired_at
+                );
+            }
+            app.manage(db_lock_state);
+            tauri::async_runtime::spawn(db_lock::run_heartbeat(app.handle().clone()));
+
             // tauri-plugin-sqlを登録。両DBにマイグレーションを登録（E2E/通常でどちらか一方のみ使用）
-            app.handle().plugin(
+            // 読み取り専用で起動した場合、DB を使用中の端末と競合しないようマイグレーションは登録しない
+            let sql_plugin = if db_read_only {
+                tauri_plugin_sql::Builder::default()
+            } else {
                 tauri_plugin_sql::Builder::default()
                     .add_migrations("sqlite:paa_data.db", migrations())
                     .add_migrations("sqlite:paa_e2e.db", migrations())
-                    .build(),
-            )?;
+            };
+            app.handle().plugin(sql_plugin.build())?;
 
             log::info!("tauri-plugin-sql registered with migrations");
 
@@ -184,7 +497,8 @@ pub fn run() {
                 // busy_timeout: tauri-plugin-sql 側の接続が書き込み中の場合に最大 10 秒待機してリトライ。
                 let options = SqliteConnectOptions::from_str(&db_url)
                     .expect("Failed to parse database URL")
-                    .create_if_missing(true)
+                    .create_if_missing(!db_read_only)
+                    .read_only(db_read_only)
                     .foreign_keys(true)
                     .journal_mode(sqlx::sqlite::SqliteJournalMode::Wal)
                     .busy_timeout(std::time::Duration::from_secs(10));
@@ -243,13 +557,17 @@ pub fn run() {
             app.manage(commands::AmazonSessionState::new());
             log::info!("Amazon session state initialized");
 
+            // Initialize factory reset confirmation state
+            app.manage(commands::FactoryResetState::new());
+
             // Initialize and start scheduler
             {
                 let scheduler_config = config::load(&app_config_dir)
                     .map(|c| c.scheduler)
                     .unwrap_or_default();
+                // 読み取り専用で起動した場合は書き込みを伴う自動実行を止める
                 let scheduler_state = scheduler::SchedulerState::new(
-                    scheduler_config.enabled,
+                    scheduler_config.enabled && !db_read_only,
                     scheduler_config.interval_minutes,
                 );
                 app.manage(scheduler_state.clone());
@@ -269,17 +587,60 @@ pub fn run() {
                 );
             }
 
+            // Start periodic update check (GitHub Releases). E2E では外部通信しない
+            if !crate::e2e_mocks::is_e2e_mock_mode() {
+                tauri::async_runtime::spawn(updater::run_update_checker(app.handle().clone()));
+            }
+
+            // DB に書き込む常駐タスクは読み取り専用で起動した場合は動かさない
+            // Record daily stats snapshots (for trend charts)
+            if !db_read_only {
+                tauri::async_runtime::spawn(stats_snapshot::run_stats_snapshot_recorder(
+                    app.handle().clone(),
+                ));
+            }
+
+            // Start monthly summary report (generated on the first day of each month)
+            if !crate::e2e_mocks::is_e2e_mock_mode() && !db_read_only {
+                tauri::async_runtime::spawn(report::monthly_summary::run_monthly_report_scheduler(
+                    app.handle().clone(),
+                ));
+            }
+
+            // Watch reissue announcements of watched / owned items
+            if !crate::e2e_mocks::is_e2e_mock_mode() && !db_read_only {
+                tauri::async_runtime::spawn(reissue_watch::run_reissue_watcher(
+                    app.handle().clone(),
+                ));
+            }
+
+            // Reload paa_config.json when it is edited outside the app
+            tauri::async_runtime::spawn(config_watcher::run_config_watcher(
+                app.handle().clone(),
+            ));
+
             // Restore window settings and setup close handler
             let window = app
                 .get_webview_window("main")
                 .expect("Failed to get main window");
 
-            // Handle window close request - hide instead of closing
+            if db_read_only {
+                let title = window.title().unwrap_or_default();
+                let _ = window.set_title(&format!("{title}（読み取り専用）"));
+            }
+
+            // OS のスタートアップから起動された場合はウィンドウを出さずトレイに常駐する
+            if commands::is_autostart_launch() {
+                let _ = window.hide();
+                log::info!("Launched at login - starting hidden in tray");
+            }
+
+            // Handle window close request - hide / quit / ask according to config
             let window_clone = window.clone();
             window.on_window_event(move |event| {
                 if let tauri::WindowEvent::CloseRequested { api, .. } = event {
                     api.prevent_close();
-                    let _ = window_clone.hide();
+                    handle_close_requested(&window_clone);
                 }
             });
 
@@ -368,6 +729,13 @@ pub fn run() {
                 true,
                 None::<&str>,
             )?;
+            let low_priority_item = MenuItem::with_id(
+                app,
+                "tray_low_priority_toggle",
+                low_priority_label(low_priority_enabled(app.handle())),
+                true,
+                None::<&str>,
+            )?;
             let full_parse_item = MenuItem::with_id(
                 app,
                 "tray_full_parse_pipeline",
@@ -388,6 +756,7 @@ pub fn run() {
                     &product_item,
                     &delivery_check_item,
                     &scheduler_toggle_item,
+                    &low_priority_item,
                 ],
             )?;
             let quit_item = MenuItem::with_id(app, "quit", "終了", true, None::<&str>)?;
@@ -404,208 +773,218 @@ pub fn run() {
             }
 
             let scheduler_toggle_for_tray = scheduler_toggle_item.clone();
+            let low_priority_for_tray = low_priority_item.clone();
             let _tray = tray_builder
                 .menu(&menu)
                 .show_menu_on_left_click(false)
-                .on_menu_event(move |app, event| match event.id.as_ref() {
-                    "show" => {
-                        if let Some(window) = app.get_webview_window("main") {
-                            let _ = window.show();
-                            let _ = window.set_focus();
-                        }
+                .on_menu_event(move |app, event| {
+                    if db_lock::is_read_only(app)
+                        && db_lock::DB_WRITE_TRAY_MENU_IDS.contains(&event.id.as_ref())
+                    {
+                        log::warn!(
+                            "Database is read-only; ignoring tray menu {}",
+                            event.id.as_ref()
+                        );
+                        return;
                     }
-                    "tray_ocr_search" => {
-                        let app_clone = app.clone();
-                        tauri::async_runtime::spawn(async move {
-                            if let Err(e) = commands::show_screen_overlay(app_clone).await {
-                                log::error!("Failed to show screen overlay from tray: {e}");
+                    match event.id.as_ref() {
+                        "show" => {
+                            if let Some(window) = app.get_webview_window("main") {
+                                let _ = window.show();
+                                let _ = window.set_focus();
                             }
-                        });
-                    }
-                    "tray_sync" => {
-                        if let (Some(pool), Some(sync_state)) = (
-                            app.try_state::<SqlitePool>(),
-                            app.try_state::<gmail::SyncState>(),
-                        ) {
-                            let app_clone = app.clone();
-                            let pool_clone = pool.inner().clone();
-                            let sync_state_clone = sync_state.inner().clone();
-                            tauri::async_runtime::spawn(orchestration::run_sync_task(
-                                app_clone,
-                                pool_clone,
-                                sync_state_clone,
-                            ));
-                        } else {
-                            log::warn!("Cannot run tray sync: pool or sync_state not initialized");
                         }
-                    }
-                    "tray_incremental_sync" => {
-                        if let (Some(pool), Some(sync_state)) = (
-                            app.try_state::<SqlitePool>(),
-                            app.try_state::<gmail::SyncState>(),
-                        ) {
+                        "tray_ocr_search" => {
                             let app_clone = app.clone();
-                            let pool_clone = pool.inner().clone();
-                            let sync_state_clone = sync_state.inner().clone();
-                            tauri::async_runtime::spawn(orchestration::run_incremental_sync_task(
-                                app_clone,
-                                pool_clone,
-                                sync_state_clone,
-                                false, // トレイ経由では try_start を本関数内で行う
-                            ));
-                        } else {
-                            log::warn!("Cannot run tray incremental sync: pool or sync_state not initialized");
+                            tauri::async_runtime::spawn(async move {
+                                if let Err(e) = commands::show_screen_overlay(app_clone).await {
+                                    log::error!("Failed to show screen overlay from tray: {e}");
+                                }
+                            });
                         }
-                    }
-                    "tray_parse" => {
-                        if let (Some(pool), Some(parse_state)) = (
-                            app.try_state::<SqlitePool>(),
-                            app.try_state::<parsers::ParseState>(),
-                        ) {
-                            let app_clone = app.clone();
-                            let pool_clone = pool.inner().clone();
-                            let parse_state_clone = parse_state.inner().clone();
-                            let batch_size = match app.path().app_config_dir() {
-                                Ok(dir) => match config::load(&dir) {
-                                    Ok(c) => orchestration::clamp_batch_size(c.parse.batch_size, 100),
+                        "tray_sync" => {
+                            if let (Some(pool), Some(sync_state)) = (
+                                app.try_state::<SqlitePool>(),
+                                app.try_state::<gmail::SyncState>(),
+                            ) {
+                                let app_clone = app.clone();
+                                let pool_clone = pool.inner().clone();
+                                let sync_state_clone = sync_state.inner().clone();
+                                tauri::async_runtime::spawn(orchestration::run_sync_task(
+                                    app_clone,
+                                    pool_clone,
+                                    sync_state_clone,
+                                ));
+                            } else {
+                                log::warn!("Cannot run tray sync: pool or sync_state not initialized");
+                            }
+                        }
+                        "tray_incremental_sync" => {
+                            if let (Some(pool), Some(sync_state)) = (
+                                app.try_state::<SqlitePool>(),
+                                app.try_state::<gmail::SyncState>(),
+                            ) {
+                                let app_clone = app.clone();
+                                let pool_clone = pool.inner().clone();
+                                let sync_state_clone = sync_state.inner().clone();
+                                tauri::async_runtime::spawn(orchestration::run_incremental_sync_task(
+                                    app_clone,
+                                    pool_clone,
+                                    sync_state_clone,
+                                    false, // トレイ経由では try_start を本関数内で行う
+                                ));
+                            } else {
+                                log::warn!("Cannot run tray incremental sync: pool or sync_state not initialized");
+                            }
+                        }
+                        "tray_parse" => {
+                            if let (Some(pool), Some(parse_state)) = (
+                                app.try_state::<SqlitePool>(),
+                                app.try_state::<parsers::ParseState>(),
+                            ) {
+                                let app_clone = app.clone();
+                                let pool_clone = pool.inner().clone();
+                                let parse_state_clone = parse_state.inner().clone();
+                                let batch_size = match app.path().app_config_dir() {
+                                    Ok(dir) => match config::load(&dir) {
+                                        Ok(c) => orchestration::clamp_batch_size(c.parse.batch_size, 100),
+                                        Err(e) => {
+                                            log::warn!(
+                                                "Failed to load config from {:?}: {}. Falling back to default batch_size=100",
+                                                dir, e
+                                            );
+                                            100
+                                        }
+                                    },
                                     Err(e) => {
                                         log::warn!(
-                                            "Failed to load config from {:?}: {}. Falling back to default batch_size=100",
-                                            dir, e
+                                            "Failed to get app_config_dir: {}. Falling back to default batch_size=100",
+                                            e
                                         );
                                         100
                                     }
-                                },
-                                Err(e) => {
-                                    log::warn!(
-                                        "Failed to get app_config_dir: {}. Falling back to default batch_size=100",
-                                        e
-                                    );
-                                    100
-                                }
-                            };
-                            tauri::async_runtime::spawn(orchestration::run_batch_parse_task(
-                                app_clone,
-                                pool_clone,
-                                parse_state_clone,
-                                batch_size,
-                            ));
-                        } else {
-                            log::warn!("Cannot run tray parse: pool or parse_state not initialized");
-                        }
-                    }
-                    "tray_product_name_parse" => {
-                        if let (Some(pool), Some(parse_state)) = (
-                            app.try_state::<SqlitePool>(),
-                            app.try_state::<commands::ProductNameParseState>(),
-                        ) {
-                            let app_clone = app.clone();
-                            let pool_clone = pool.inner().clone();
-                            let parse_state_clone = parse_state.inner().clone();
-                            tauri::async_runtime::spawn(
-                                orchestration::run_product_name_parse_task(
+                                };
+                                tauri::async_runtime::spawn(orchestration::run_batch_parse_task(
                                     app_clone,
                                     pool_clone,
                                     parse_state_clone,
-                                    false, // トレイ経由では try_start を本関数内で行う
-                                ),
-                            );
-                        } else {
-                            log::warn!(
-                                "Cannot run tray product name parse: pool or parse_state not initialized"
-                            );
+                                    batch_size,
+                                ));
+                            } else {
+                                log::warn!("Cannot run tray parse: pool or parse_state not initialized");
+                            }
                         }
-                    }
-                    "tray_delivery_check" => {
-                        if let (Some(pool), Some(check_state)) = (
-                            app.try_state::<SqlitePool>(),
-                            app.try_state::<commands::DeliveryCheckState>(),
-                        ) {
-                            let app_clone = app.clone();
-                            let pool_clone = pool.inner().clone();
-                            let check_state_clone = check_state.inner().clone();
-                            if let Err(e) = check_state_clone.try_start() {
-                                log::warn!("Cannot start delivery check from tray: {e}");
+                        "tray_product_name_parse" => {
+                            if let (Some(pool), Some(parse_state)) = (
+                                app.try_state::<SqlitePool>(),
+                                app.try_state::<commands::ProductNameParseState>(),
+                            ) {
+                                let app_clone = app.clone();
+                                let pool_clone = pool.inner().clone();
+                                let parse_state_clone = parse_state.inner().clone();
+                                tauri::async_runtime::spawn(
+                                    orchestration::run_product_name_parse_task(
+                                        app_clone,
+                                        pool_clone,
+                                        parse_state_clone,
+                                        false, // トレイ経由では try_start を本関数内で行う
+                                    ),
+                                );
                             } else {
-                                tauri::async_runtime::spawn(orchestration::run_delivery_check_task(
-                                    app_clone,
-                                    pool_clone,
-                                    check_state_clone,
-                                ));
+                                log::warn!(
+                                    "Cannot run tray product name parse: pool or parse_state not initialized"
+                                );
                             }
-                        } else {
-                            log::warn!(
-                                "Cannot run tray delivery check: pool or check_state not initialized"
-                            );
                         }
-                    }
-                    "tray_full_parse_pipeline" => {
-                        if let Some(pool) = app.try_state::<SqlitePool>() {
-                            let app_clone = app.clone();
-                            let pool_clone = pool.inner().clone();
-                            tauri::async_runtime::spawn(
-                                orchestration::run_full_parse_pipeline(app_clone, pool_clone),
-                            );
-                        } else {
-                            log::warn!(
-                                "Cannot run tray full parse pipeline: pool not initialized"
-                            );
+                        "tray_delivery_check" => {
+                            if let (Some(pool), Some(check_state)) = (
+                                app.try_state::<SqlitePool>(),
+                                app.try_state::<commands::DeliveryCheckState>(),
+                            ) {
+                                let app_clone = app.clone();
+                                let pool_clone = pool.inner().clone();
+                                let check_state_clone = check_state.inner().clone();
+                                if let Err(e) = check_state_clone.try_start() {
+                                    log::warn!("Cannot start delivery check from tray: {e}");
+                                } else {
+                                    tauri::async_runtime::spawn(orchestration::run_delivery_check_task(
+                                        app_clone,
+                                        pool_clone,
+                                        check_state_clone,
+                                    ));
+                                }
+                            } else {
+                                log::warn!(
+                                    "Cannot run tray delivery check: pool or check_state not initialized"
+                                );
+                            }
                         }
-                    }
-                    "tray_scheduler_toggle" => {
-                        if let Some(sched_state) = app.try_state::<scheduler::SchedulerState>() {
-                            let new_enabled = sched_state.toggle();
+                        "tray_full_parse_pipeline" => {
+                            if let Some(pool) = app.try_state::<SqlitePool>() {
+                                let app_clone = app.clone();
+                                let pool_clone = pool.inner().clone();
+                                tauri::async_runtime::spawn(
+                                    orchestration::run_full_parse_pipeline(app_clone, pool_clone),
+                                );
+                            } else {
+                                log::warn!(
+                                    "Cannot run tray full parse pipeline: pool not initialized"
+                                );
+                            }
+                        }
+                        "tray_scheduler_toggle" => {
+                            if let Some(sched_state) = app.try_state::<scheduler::SchedulerState>() {
+                                let new_enabled = sched_state.toggle();
 
-                            // 設定ファイル側の scheduler.enabled も更新しておくことで、
-                            // get_scheduler_config や設定画面と実際の動作の乖離を防ぐ。
+                                // 設定ファイル側の scheduler.enabled も更新しておくことで、
+                                // get_scheduler_config や設定画面と実際の動作の乖離を防ぐ。
+                                let app_clone = app.clone();
+                                tauri::async_runtime::spawn(async move {
+                                    if let Err(e) =
+                                        commands::update_scheduler_enabled(app_clone, new_enabled).await
+                                    {
+                                        log::warn!(
+                                            "[Scheduler] Failed to persist enabled state from tray: {e}"
+                                        );
+                                    }
+                                });
+
+                                let label = if new_enabled {
+                                    format!(
+                                        "スケジューラ: ON ({}間隔)",
+                                        scheduler::format_interval(sched_state.interval_minutes())
+                                    )
+                                } else {
+                                    "スケジューラ: OFF".to_string()
+                                };
+                                let _ = scheduler_toggle_for_tray.set_text(&label);
+                                let _ = app.emit(
+                                    scheduler::SCHEDULER_STATUS_EVENT,
+                                    scheduler::SchedulerStatusPayload {
+                                        enabled: new_enabled,
+                                        interval_minutes: sched_state.interval_minutes(),
+                                    },
+                                );
+                                log::info!("[Scheduler] Toggled: enabled={}", new_enabled);
+                            }
+                        }
+                        "tray_low_priority_toggle" => {
+                            let new_enabled = !low_priority_enabled(app);
+                            let _ = low_priority_for_tray.set_text(low_priority_label(new_enabled));
                             let app_clone = app.clone();
                             tauri::async_runtime::spawn(async move {
                                 if let Err(e) =
-                                    commands::update_scheduler_enabled(app_clone, new_enabled).await
+                                    commands::update_low_priority_enabled(app_clone, new_enabled).await
                                 {
-                                    log::warn!(
-                                        "[Scheduler] Failed to persist enabled state from tray: {e}"
-                                    );
+                                    log::warn!("Failed to persist low priority mode from tray: {e}");
                                 }
                             });
-
-                            let label = if new_enabled {
-                                format!(
-                                    "スケジューラ: ON ({}間隔)",
-                                    scheduler::format_interval(sched_state.interval_minutes())
-                                )
-                            } else {
-                                "スケジューラ: OFF".to_string()
-                            };
-                            let _ = scheduler_toggle_for_tray.set_text(&label);
-                            let _ = app.emit(
-                                scheduler::SCHEDULER_STATUS_EVENT,
-                                scheduler::SchedulerStatusPayload {
-                                    enabled: new_enabled,
-                                    interval_minutes: sched_state.interval_minutes(),
-                                },
-                            );
-                            log::info!("[Scheduler] Toggled: enabled={}", new_enabled);
                         }
-                    }
-                    "quit" => {
-                        // スケジューラを即時起床させてシャットダウンを検出させる
-                        if let Some(notify) = app.try_state::<Arc<Notify>>() {
-                            notify.notify_one();
+                        "quit" => {
+                            shutdown_and_exit(app);
                         }
-
-                        // クリップボード監視をグレースフルに停止
-                        if let Some(shutdown_signal) = app.try_state::<Arc<AtomicBool>>() {
-                            let shutdown_signal = shutdown_signal.inner().clone();
-                            // シャットダウン要求を通知
-                            shutdown_signal.store(true, Ordering::Relaxed);
-                        }
-
-                        // 監視スレッドの終了完了を明示的に待つ仕組みは現状ないため、
-                        // シャットダウン要求を送ったら即座にアプリケーションを終了する。
-                        app.exit(0);
+                        _ => {}
                     }
-                    _ => {}
                 })
                 .on_tray_icon_event(|tray, event| {
                     if let TrayIconEvent::Click {
@@ -628,6 +1007,22 @@ pub fn run() {
             let app_handle = app.handle().clone();
             app.listen("notification-action", move |event| {
                 log::info!("Notification action event: {event:?}");
+                // 配送通知の「追跡ページを開く」ボタン
+                if let Some(order_id) =
+                    commands::tracking_order_id_from_notification_action(event.payload())
+                {
+                    let app_clone = app_handle.clone();
+                    tauri::async_runtime::spawn(async move {
+                        let pool = app_clone.state::<SqlitePool>().inner().clone();
+                        if let Err(e) =
+                            commands::open_tracking_page_for_order(&app_clone, &pool, order_id)
+                                .await
+                        {
+                            log::warn!("Failed to open tracking page from notification: {e}");
+                        }
+                    });
+                    return;
+                }
                 // Show main window when notification is clicked
                 if let Some(window) = app_handle.get_webview_window("main") {
                     let _ = window.show();
@@ -652,104 +1047,231 @@ pub fn run() {
             }
             Ok(())
         })
-        .invoke_handler(tauri::generate_handler![
-            commands::seed_e2e_db,
-            commands::get_db_filename,
-            commands::fetch_gmail_emails,
-            commands::start_sync,
-            commands::start_incremental_sync,
-            commands::cancel_sync,
-            commands::get_sync_status,
-            commands::update_batch_size,
-            commands::update_max_iterations,
-            commands::update_max_results_per_page,
-            commands::update_timeout_minutes,
-            commands::reset_sync_status,
-            commands::reset_sync_date,
-            commands::save_window_settings,
-            commands::get_email_stats,
-            commands::get_order_stats,
-            commands::get_delivery_stats,
-            commands::get_product_master_stats,
-            commands::get_misc_stats,
-            commands::get_logs,
-            commands::get_all_shop_settings,
-            commands::create_shop_setting,
-            commands::update_shop_setting,
-            commands::delete_shop_setting,
-            commands::toggle_shop_enabled,
-            commands::init_default_shop_settings,
-            commands::parse_email,
-            commands::parse_and_save_email,
-            commands::start_batch_parse,
-            commands::cancel_parse,
-            commands::get_parse_status,
-            commands::update_parse_batch_size,
-            commands::get_gemini_config,
-            commands::update_gemini_batch_size,
-            commands::update_gemini_delay_seconds,
-            commands::has_gemini_api_key,
-            commands::save_gemini_api_key,
-            commands::delete_gemini_api_key,
-            commands::start_product_name_parse,
-            commands::cancel_product_name_parse,
-            commands::has_gmail_oauth_credentials,
-            commands::save_gmail_oauth_credentials,
-            commands::delete_gmail_oauth_credentials,
-            commands::is_google_search_configured,
-            commands::save_google_search_api_key,
-            commands::delete_google_search_config,
-            commands::search_product_images,
-            commands::save_image_from_url,
-            commands::export_metadata,
-            commands::import_metadata,
-            commands::restore_metadata,
-            commands::save_item_override,
-            commands::save_order_override,
-            commands::delete_item_override,
-            commands::delete_item_override_by_key,
-            commands::delete_order_override,
-            commands::delete_order_override_by_key,
-            commands::exclude_item,
-            commands::exclude_order,
-            commands::restore_excluded_item,
-            commands::restore_excluded_order,
-            commands::get_all_excluded_items,
-            commands::get_all_excluded_orders,
-            commands::get_product_master_list,
-            commands::update_product_master,
-            commands::start_delivery_check,
-            commands::cancel_delivery_check,
-            commands::get_scheduler_config,
-            commands::update_scheduler_interval,
-            commands::update_scheduler_enabled,
-            commands::open_surugaya_login_window,
-            commands::start_surugaya_mypage_fetch,
-            commands::cancel_surugaya_mypage_fetch,
-            commands::get_surugaya_mypage_fetch_status,
-            commands::open_amazon_login_window,
-            commands::start_amazon_order_fetch,
-            commands::cancel_amazon_order_fetch,
-            commands::get_amazon_order_fetch_status,
-            commands::start_full_parse_pipeline,
-            commands::show_screen_overlay,
-            commands::close_screen_overlay,
-            commands::capture_and_ocr,
-            commands::fetch_news_feed,
-            commands::fetch_news_html,
-            commands::clip_news_article,
-            commands::get_news_clips,
-            commands::delete_news_clip,
-            commands::get_clipped_urls,
-            commands::refresh_clip_events,
-            commands::list_exclusion_patterns,
-            commands::add_exclusion_pattern,
-            commands::delete_exclusion_pattern,
-        ])
+        .invoke_handler(move |invoke| {
+            // 読み取り専用で起動した場合は DB に書き込むコマンドを拒否する
+            let checked = invoke
+                .message
+                .webview()
+                .try_state::<db_lock::DbLockState>()
+                .map_or(Ok(()), |state| state.check_command(invoke.message.command()));
+            if let Err(e) = checked {
+                invoke.resolver.reject(e);
+                return true;
+            }
+            invoke_handler(invoke)
+        })
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
 }
 
+/// フロントエンドから呼び出せるコマンド
+fn invoke_handler() -> impl Fn(tauri::ipc::Invoke<tauri::Wry>) -> bool + Send + Sync + 'static {
+    tauri::generate_handler![
+        commands::seed_e2e_db,
+        commands::get_db_filename,
+        commands::get_db_access_status,
+        commands::fetch_gmail_emails,
+        commands::start_sync,
+        commands::start_incremental_sync,
+        commands::estimate_sync,
+        commands::cancel_sync,
+        commands::get_sync_status,
+        commands::update_batch_size,
+        commands::update_max_iterations,
+        commands::update_max_results_per_page,
+        commands::update_timeout_minutes,
+        commands::update_save_raw_eml,
+        commands::update_body_html_limit,
+        commands::reset_sync_status,
+        commands::reset_sync_date,
+        commands::save_window_settings,
+        commands::get_close_behavior,
+        commands::update_close_behavior,
+        commands::get_email_stats,
+        commands::get_order_stats,
+        commands::get_delivery_stats,
+        commands::get_product_master_stats,
+        commands::get_misc_stats,
+        commands::get_dashboard_stats,
+        commands::get_today_overview,
+        commands::get_ingestion_latency_metrics,
+        commands::get_parser_performance,
+        commands::render_spending_chart,
+        commands::get_storage_stats,
+        commands::get_cancel_reason_stats,
+        commands::get_stats_history,
+        commands::list_analysis_views,
+        commands::search_orders,
+        commands::list_smart_filters,
+        commands::save_smart_filter,
+        commands::delete_smart_filter,
+        commands::detect_price_anomalies,
+        commands::list_reservation_orders,
+        commands::set_reservation_status,
+        commands::update_reservation_status_from_emails,
+        commands::find_duplicate_preorders,
+        commands::list_release_schedule,
+        commands::get_arrival_calendar,
+        commands::list_order_payments,
+        commands::list_pending_payments,
+        commands::add_order_payment,
+        commands::set_payment_status,
+        commands::delete_order_payment,
+        commands::reconcile_payments_from_emails,
+        commands::list_shop_point_balances,
+        commands::collect_points_from_emails,
+        commands::save_item_receipt_review,
+        commands::get_item_receipt_review,
+        commands::delete_item_receipt_review,
+        commands::get_shop_damage_stats,
+        commands::set_item_storage_location,
+        commands::get_item_storage_location,
+        commands::list_storage_locations,
+        commands::list_items_by_storage_location,
+        commands::render_order_document,
+        commands::export_household_csv,
+        commands::prepare_factory_reset,
+        commands::factory_reset,
+        commands::list_reissue_watches,
+        commands::add_reissue_watch,
+        commands::watch_item_reissue,
+        commands::delete_reissue_watch,
+        commands::list_reissue_detections,
+        commands::check_reissues,
+        commands::list_series_master,
+        commands::sync_series_master,
+        commands::set_series_alias,
+        commands::delete_series_alias,
+        commands::rename_series,
+        commands::merge_series,
+        commands::get_series_stats,
+        commands::list_trash,
+        commands::delete_order,
+        commands::restore_order,
+        commands::delete_item,
+        commands::restore_item,
+        commands::get_logs,
+        commands::get_all_shop_settings,
+        commands::create_shop_setting,
+        commands::update_shop_setting,
+        commands::delete_shop_setting,
+        commands::toggle_shop_enabled,
+        commands::init_default_shop_settings,
+        commands::get_parser_catalog,
+        commands::suggest_new_shops,
+        commands::get_shop_setting_match_stats,
+        commands::suggest_shop_settings_to_disable,
+        commands::parse_email,
+        commands::parse_and_save_email,
+        commands::start_batch_parse,
+        commands::cancel_parse,
+        commands::undo_last_parse,
+        commands::reparse_order,
+        commands::verify_order_restructure,
+        commands::get_parse_status,
+        commands::update_parse_batch_size,
+        commands::get_gemini_config,
+        commands::update_gemini_batch_size,
+        commands::update_gemini_delay_seconds,
+        commands::update_gemini_rate_limits,
+        commands::get_setup_status,
+        commands::mark_setup_step,
+        commands::has_gemini_api_key,
+        commands::save_gemini_api_key,
+        commands::validate_gemini_api_key,
+        commands::delete_gemini_api_key,
+        commands::start_product_name_parse,
+        commands::cancel_product_name_parse,
+        commands::has_gmail_oauth_credentials,
+        commands::save_gmail_oauth_credentials,
+        commands::delete_gmail_oauth_credentials,
+        commands::is_google_search_configured,
+        commands::save_google_search_api_key,
+        commands::validate_google_search_api_key,
+        commands::delete_google_search_config,
+        commands::search_product_images,
+        commands::save_image_from_url,
+        commands::export_metadata,
+        commands::import_metadata,
+        commands::restore_metadata,
+        commands::save_item_override,
+        commands::save_order_override,
+        commands::delete_item_override,
+        commands::delete_item_override_by_key,
+        commands::delete_order_override,
+        commands::delete_order_override_by_key,
+        commands::exclude_item,
+        commands::exclude_order,
+        commands::restore_excluded_item,
+        commands::restore_excluded_order,
+        commands::get_all_excluded_items,
+        commands::get_all_excluded_orders,
+        commands::get_product_master_list,
+        commands::update_product_master,
+        commands::start_delivery_check,
+        commands::cancel_delivery_check,
+        commands::add_tracking_number,
+        commands::open_tracking_page,
+        commands::get_scheduler_config,
+        commands::update_scheduler_interval,
+        commands::update_scheduler_enabled,
+        commands::get_autostart_enabled,
+        commands::set_autostart,
+        commands::get_debug_config,
+        commands::update_batch_log_stream,
+        commands::get_updater_config,
+        commands::update_auto_update_check,
+        commands::get_monthly_report_config,
+        commands::update_monthly_report_enabled,
+        commands::get_low_priority_config,
+        commands::update_low_priority_enabled,
+        commands::get_image_search_config,
+        commands::update_image_search_query_template,
+        commands::generate_monthly_report,
+        commands::list_monthly_reports,
+        commands::check_for_updates,
+        commands::open_surugaya_login_window,
+        commands::start_surugaya_mypage_fetch,
+        commands::cancel_surugaya_mypage_fetch,
+        commands::get_surugaya_mypage_fetch_status,
+        commands::open_amazon_login_window,
+        commands::start_amazon_order_fetch,
+        commands::cancel_amazon_order_fetch,
+        commands::get_amazon_order_fetch_status,
+        commands::start_full_parse_pipeline,
+        commands::show_screen_overlay,
+        commands::close_screen_overlay,
+        commands::capture_and_ocr,
+        commands::fetch_news_feed,
+        commands::fetch_news_html,
+        commands::clip_news_article,
+        commands::get_news_clips,
+        commands::delete_news_clip,
+        commands::get_clipped_urls,
+        commands::refresh_clip_events,
+        commands::list_exclusion_patterns,
+        commands::add_exclusion_pattern,
+        commands::delete_exclusion_pattern,
+        commands::list_auto_tag_rules,
+        commands::add_auto_tag_rule,
+        commands::update_auto_tag_rule,
+        commands::delete_auto_tag_rule,
+        commands::apply_auto_tag_rules,
+        commands::get_item_tags,
+        commands::list_notification_rules,
+        commands::add_notification_rule,
+        commands::update_notification_rule,
+        commands::delete_notification_rule,
+        commands::list_wishlist_items,
+        commands::add_wishlist_item,
+        commands::update_wishlist_item,
+        commands::set_wishlist_item_status,
+        commands::delete_wishlist_item,
+        commands::export_email_raw,
+        commands::anonymize_email,
+    ]
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,136 @@
+//! 注文金額の検算
+//!
+//! メールに記載された合計金額と商品合計（+ 税抜表示の税額）を突き合わせ、差額（送料・手数料・割引など）を
+//! 注文の調整額として求める。統計は商品合計に調整額を加算するため、送料の取りこぼしや割引の二重計上を防げる。
+
+use crate::parsers::OrderInfo;
+
+/// 注文の調整額を求める
+///
+/// `tax_amount` / `tax_included` は注文に保存済みの税情報（メールの値で更新した後のもの）を渡す。
+/// 統計は保存済みの税情報で税額を加算するため、同じ基準で差額を求める。
+///
+/// 次の場合は判定できないため None を返す（既存の調整額を変更しない）。
+/// - 合計金額の記載がない、または商品がない（商品合計が 0 以下）
+/// - 小計の記載と商品合計が一致しない（分割発送メールで一部の商品しか載っていない等）
+/// - 差額が商品合計を超える（複数注文の合算金額や金額の読み取り誤りとみなす）
+pub fn compute_amount_adjustment(
+    order_info: &OrderInfo,
+    tax_amount: Option<i64>,
+    tax_included: bool,
+) -> Option<i64> {
+    let total = order_info.total_amount?;
+    let items_amount: i64 = order_info
+        .items
+        .iter()
+        .map(|item| item.unit_price * item.quantity)
+        .sum();
+    if items_amount <= 0 {
+        return None;
+    }
+    if order_info.subtotal.is_some_and(|s| s != items_amount) {
+        log::debug!(
+            "Skip amount adjustment for order {}: subtotal does not match items amount",
+            order_info.order_number
+        );
+        return None;
+    }
+
+    // 統計と同じく、税抜表示の注文は税額（未記載なら商品合計の 10%）を加算して税込に揃える
+    let tax = if tax_included {
+        0
+    } else {
+        tax_amount.unwrap_or(items_amount / 10)
+    };
+    let adjustment = total - (items_amount + tax);
+    if adjustment.abs() > items_amount {
+        log::warn!(
+            "Skip implausible amount adjustment for order {} (adjustment={}, items_amount={})",
+            order_info.order_number,
+            adjustment,
+            items_amount
+        );
+        return None;
+    }
+    Some(adjustment)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parsers::OrderItem;
+
+    fn item(unit_price: i64, quantity: i64) -> OrderItem {
+        OrderItem {
+            name: "商品".to_string(),
+            manufacturer: None,
+            model_number: None,
+            unit_price,
+            quantity,
+            subtotal: unit_price * quantity,
+            image_url: None,
+            item_url: None,
+            release_date: None,
+        }
+    }
+
+    fn order(items: Vec<OrderItem>, subtotal: Option<i64>, total: Option<i64>) -> OrderInfo {
+        OrderInfo {
+            order_number: "ORD-1".to_string(),
+            order_date: None,
+            delivery_address: None,
+            delivery_info: None,
+            items,
+            subtotal,
+            shipping_fee: None,
+            total_amount: total,
+            tax_amount: None,
+            tax_included: true,
+        }
+    }
+
+    fn adjustment(info: &OrderInfo) -> Option<i64> {
+        compute_amount_adjustment(info, None, true)
+    }
+
+    #[test]
+    fn test_adjustment_for_shipping_and_discount() {
+        // 送料 660 円
+        let info = order(vec![item(1000, 2), item(500, 1)], Some(2500), Some(3160));
+        assert_eq!(adjustment(&info), Some(660));
+
+        // クーポン割引 300 円
+        let info = order(vec![item(1000, 2)], None, Some(1700));
+        assert_eq!(adjustment(&info), Some(-300));
+
+        // 一致していれば 0（既存の調整額を 0 に戻す）
+        let info = order(vec![item(1000, 2)], None, Some(2000));
+        assert_eq!(adjustment(&info), Some(0));
+    }
+
+    #[test]
+    fn test_adjustment_includes_tax_for_tax_excluded_orders() {
+        let info = order(vec![item(1000, 1)], None, Some(1600));
+        // 税額未記載は 10% とみなす: 1600 - (1000 + 100)
+        assert_eq!(compute_amount_adjustment(&info, None, false), Some(500));
+        assert_eq!(compute_amount_adjustment(&info, Some(80), false), Some(520));
+    }
+
+    #[test]
+    fn test_adjustment_skipped_when_undeterminable() {
+        // 合計の記載なし
+        assert_eq!(adjustment(&order(vec![item(1000, 1)], None, None)), None);
+        // 商品価格が不明（発送メール等）
+        assert_eq!(adjustment(&order(vec![item(0, 1)], None, Some(1000))), None);
+        // 一部の商品しか載っていない
+        assert_eq!(
+            adjustment(&order(vec![item(1000, 1)], Some(3000), Some(3500))),
+            None
+        );
+        // 差額が商品合計を超える
+        assert_eq!(
+            adjustment(&order(vec![item(1000, 1)], None, Some(5000))),
+            None
+        );
+    }
+}
@@ -0,0 +1,223 @@
+//! サンプルメールの匿名化
+//!
+//! issue 添付用のサンプル .eml を作るため、メール本文の氏名・住所・電話番号・メールアドレスを
+//! パーサーのテストデータと同じダミー値に置換する。ラベル（「お名前：」等）や行構成は残すため、
+//! 匿名化後のメールもパーサーで解析できる。あくまで機械的な置換のため、添付前に目視確認すること。
+
+use once_cell::sync::Lazy;
+use regex::{Captures, Regex};
+
+use crate::logic::sync_logic::extract_email_address;
+
+/// 置換後の氏名
+pub const ANONYMIZED_NAME: &str = "テスト 太郎";
+/// 置換後の住所
+pub const ANONYMIZED_ADDRESS: &str = "東京都テスト市テスト町1-1";
+/// 置換後の電話番号
+pub const ANONYMIZED_PHONE: &str = "000-0000-0000";
+/// 置換後の郵便番号（〒 や「郵便番号」ラベルの後ろ）
+pub const ANONYMIZED_POSTAL_CODE: &str = "000-0000";
+/// 置換後のメールアドレス
+pub const ANONYMIZED_EMAIL: &str = "test@example.com";
+
+const PREFECTURES: &str = "北海道|青森県|岩手県|宮城県|秋田県|山形県|福島県|茨城県|栃木県|群馬県|埼玉県|千葉県|東京都|神奈川県|新潟県|富山県|石川県|福井県|山梨県|長野県|岐阜県|静岡県|愛知県|三重県|滋賀県|京都府|大阪府|兵庫県|奈良県|和歌山県|鳥取県|島根県|岡山県|広島県|山口県|徳島県|香川県|愛媛県|高知県|福岡県|佐賀県|長崎県|熊本県|大分県|宮崎県|鹿児島県|沖縄県";
+
+static EMAIL_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"[A-Za-z0-9._%+\-]+@([A-Za-z0-9\-]+(?:\.[A-Za-z0-9\-]+)+)")
+        .expect("Invalid EMAIL_RE")
+});
+
+/// 区切り付きの電話番号（全角数字・全角ハイフンを含む）。前後が数字・ハイフンの場合は注文番号等とみなし除外する。
+static PHONE_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(^|[^0-9０-９\-－‐])([0０][0-9０-９]{1,4}[\-－‐(（][0-9０-９]{1,4}[\-－‐)）][0-9０-９]{3,4})($|[^0-9０-９\-－‐])")
+        .expect("Invalid PHONE_RE")
+});
+
+/// 「電話番号：0312345678」のようなラベル付きの電話番号（区切りなしを含む）
+static LABELED_PHONE_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"((?:電話|TEL|Tel|tel|携帯)[^\n0-9０-９：:]{0,6}[^\S\n]*[：:]?[^\S\n]*)[0-9０-９][0-9０-９\-－‐()（） ]{8,}[0-9０-９]")
+        .expect("Invalid LABELED_PHONE_RE")
+});
+
+static POSTAL_CODE_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"((?:〒|郵便番号[^\S\n]*[：:]?)[^\S\n]*)[0-9０-９]{3}[\-－‐]?[0-9０-９]{4}")
+        .expect("Invalid POSTAL_CODE_RE")
+});
+
+/// 氏名ラベル付きの行（値の末尾の「様」は残す）
+static LABELED_NAME_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?m)^([^\n：:]*?(?:氏名|お名前|注文者|購入者|受取人|宛名|依頼主)[】\]]?(?:[^\S\n]*[：:][^\S\n]*|[^\S\n]+))([^\n]*?[^\s様])([^\S\n]*様)?[^\S\n]*$")
+        .expect("Invalid LABELED_NAME_RE")
+});
+
+/// 住所ラベル付きの行
+static LABELED_ADDRESS_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?m)^([^\n：:]*?(?:住所|お届け先|配送先|送付先)[】\]]?(?:[^\S\n]*[：:][^\S\n]*|[^\S\n]+))([^\n]*?\S)[^\S\n]*$")
+        .expect("Invalid LABELED_ADDRESS_RE")
+});
+
+/// 都道府県から始まり市区町村を含む行（ラベルなしの住所）
+static BARE_ADDRESS_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(&format!(
+        r"(?m)^([^\S\n]*)(?:{PREFECTURES})[^\n]*?[市区町村郡][^\n]*$"
+    ))
+    .expect("Invalid BARE_ADDRESS_RE")
+});
+
+/// 「山田 太郎 様」のような敬称付きの短い行
+static HONORIFIC_NAME_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?m)^([^\S\n]*)([^\n：:]{1,20}?)([^\S\n]*様)[^\S\n]*$")
+        .expect("Invalid HONORIFIC_NAME_RE")
+});
+
+static HTML_TAG_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"<[^>]*>").expect("Invalid HTML_TAG_RE"));
+
+/// 敬称行のうち氏名ではないもの（「お客様」「会員様」など）
+fn is_generic_honorific(name: &str) -> bool {
+    ["お客", "会員", "皆", "各位"]
+        .iter()
+        .any(|word| name.trim().ends_with(word))
+}
+
+/// `domain` が送信元ドメイン（またはそのサブドメイン）か
+fn is_sender_domain(domain: &str, sender_domain: Option<&str>) -> bool {
+    let domain = domain.to_lowercase();
+    sender_domain.is_some_and(|s| domain == s || domain.ends_with(&format!(".{s}")))
+}
+
+/// 送信元アドレスのドメイン部（小文字）
+fn sender_domain_of(from_address: Option<&str>) -> Option<String> {
+    from_address
+        .and_then(extract_email_address)
+        .and_then(|email| email.split('@').nth(1).map(String::from))
+}
+
+/// 送信元ドメイン以外のメールアドレスを置換する
+fn replace_emails(text: &str, sender_domain: Option<&str>) -> String {
+    EMAIL_RE
+        .replace_all(text, |caps: &Captures| {
+            if is_sender_domain(&caps[1], sender_domain) {
+                caps[0].to_string()
+            } else {
+                ANONYMIZED_EMAIL.to_string()
+            }
+        })
+        .into_owned()
+}
+
+/// テキストを匿名化する
+///
+/// `from_address` のドメインと同じメールアドレス（ショップのサポート窓口など）は置換しない。
+pub fn anonymize_text(text: &str, from_address: Option<&str>) -> String {
+    let sender_domain = sender_domain_of(from_address);
+    let text = replace_emails(text, sender_domain.as_deref());
+    let text = POSTAL_CODE_RE.replace_all(&text, format!("${{1}}{ANONYMIZED_POSTAL_CODE}"));
+    let text = LABELED_PHONE_RE.replace_all(&text, format!("${{1}}{ANONYMIZED_PHONE}"));
+    let text = PHONE_RE.replace_all(&text, format!("${{1}}{ANONYMIZED_PHONE}${{3}}"));
+    let text = LABELED_NAME_RE.replace_all(&text, format!("${{1}}{ANONYMIZED_NAME}${{3}}"));
+    let text = LABELED_ADDRESS_RE.replace_all(&text, |caps: &Captures| {
+        // 「お届け先：山田 太郎 様」のように住所ラベルの値が氏名の場合
+        if caps[2].ends_with('様') {
+            format!("{}{ANONYMIZED_NAME} 様", &caps[1])
+        } else {
+            format!("{}{ANONYMIZED_ADDRESS}", &caps[1])
+        }
+    });
+    let text = BARE_ADDRESS_RE.replace_all(&text, format!("${{1}}{ANONYMIZED_ADDRESS}"));
+    let text = HONORIFIC_NAME_RE.replace_all(&text, |caps: &Captures| {
+        if is_generic_honorific(&caps[2]) || caps[2].contains(ANONYMIZED_NAME) {
+            caps[0].to_string()
+        } else {
+            format!("{}{ANONYMIZED_NAME}{}", &caps[1], &caps[3])
+        }
+    });
+    text.into_owned()
+}
+
+/// HTML を匿名化する。タグ間のテキストごとに [`anonymize_text`] を適用し、
+/// タグ内（`mailto:` 等の属性値）はメールアドレスのみ置換する。
+pub fn anonymize_html(html: &str, from_address: Option<&str>) -> String {
+    let sender_domain = sender_domain_of(from_address);
+    let mut out = String::with_capacity(html.len());
+    let mut last = 0;
+    for tag in HTML_TAG_RE.find_iter(html) {
+        out.push_str(&anonymize_text(&html[last..tag.start()], from_address));
+        out.push_str(&replace_emails(tag.as_str(), sender_domain.as_deref()));
+        last = tag.end();
+    }
+    out.push_str(&anonymize_text(&html[last..], from_address));
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FROM: Option<&str> = Some("ショップ <order@shop.example>");
+
+    #[test]
+    fn test_anonymize_text_replaces_labeled_fields() {
+        let body = "\
+ご注文者：山田 花子 様
+お届け先住所：〒123-4567 大阪府大阪市北区梅田1-2-3
+電話番号：09012345678
+メール：hanako@gmail.com
+お問い合わせ：support@shop.example
+注文番号：0123-4567-890123
+";
+        let anonymized = anonymize_text(body, FROM);
+        assert_eq!(
+            anonymized,
+            "\
+ご注文者：テスト 太郎 様
+お届け先住所：東京都テスト市テスト町1-1
+電話番号：000-0000-0000
+メール：test@example.com
+お問い合わせ：support@shop.example
+注文番号：0123-4567-890123
+"
+        );
+    }
+
+    #[test]
+    fn test_anonymize_text_replaces_unlabeled_fields() {
+        let body = "\
+山田花子様
+
+このたびはご注文ありがとうございます。
+お客様
+〒１２３－４５６７
+神奈川県横浜市中区1-1
+TEL 03-1234-5678
+";
+        let anonymized = anonymize_text(body, FROM);
+        assert_eq!(
+            anonymized,
+            "\
+テスト 太郎様
+
+このたびはご注文ありがとうございます。
+お客様
+〒000-0000
+東京都テスト市テスト町1-1
+TEL 000-0000-0000
+"
+        );
+    }
+
+    #[test]
+    fn test_anonymize_text_keeps_item_lines() {
+        let body = "商品名：フィギュア 1/7スケール\n価格：12,800円\n";
+        assert_eq!(anonymize_text(body, FROM), body);
+    }
+
+    #[test]
+    fn test_anonymize_html_keeps_tags() {
+        let html = r#"<tr><td>お名前</td><td>山田 花子 様</td></tr><a href="mailto:hanako@gmail.com">hanako@gmail.com</a>"#;
+        assert_eq!(
+            anonymize_html(html, FROM),
+            r#"<tr><td>お名前</td><td>テスト 太郎 様</td></tr><a href="mailto:test@example.com">test@example.com</a>"#
+        );
+    }
+}
@@ -3,8 +3,9 @@
 //! このモジュールはメールパースに関する純粋関数を提供します。
 //! 外部依存を持たないため、テストが容易です。
 
+use crate::logic::language::EmailLanguage;
 use crate::logic::sync_logic::extract_email_address;
-use crate::plugins::{build_registry, find_plugin};
+use crate::plugins::{build_registry, find_plugin, VendorPlugin};
 
 /// パーサータイプ名からパーサーが存在するかチェックする
 ///
@@ -21,6 +22,14 @@ pub fn is_valid_parser_type(parser_type: &str) -> bool {
     find_plugin(&registry, parser_type).is_some()
 }
 
+/// キャンセルメール用のパーサーか（`{shop}_cancel` / `{shop}_auto_cancel`）
+///
+/// キャンセルパーサーは `CancelInfo` を返して `apply_cancel` で既存注文に適用するため、
+/// `get_parser` を持たずプラグインの `dispatch` 内で直接処理する（バッチパース専用）。
+pub fn is_cancel_parser(parser_type: &str) -> bool {
+    parser_type.ends_with("_cancel")
+}
+
 /// 送信者アドレスと件名からパーサータイプの候補を取得する
 ///
 /// # Arguments
@@ -34,7 +43,7 @@ pub fn is_valid_parser_type(parser_type: &str) -> bool {
 /// # Note
 /// - メールアドレスは正規化（小文字化）して完全一致で比較
 /// - 大文字小文字は無視される
-/// - hobbysearch_cancel はバッチパース専用のため、単一メール用の候補からは除外する
+/// - キャンセル（`is_cancel_parser`）・hobbysearch_preparing・dmm_stock_arrival・amiami_delay・hobbysearch_delay 等はバッチパース専用のため、単一メール用の候補からは除外する
 pub fn get_candidate_parsers<'a>(
     from_address: &str,
     subject: Option<&str>,
@@ -81,13 +90,51 @@ pub fn get_candidate_parsers<'a>(
             }
         })
         .filter(|parser_type| {
-            *parser_type != "hobbysearch_cancel"
-                && *parser_type != "dmm_cancel"
+            !is_cancel_parser(parser_type)
+                && *parser_type != "hobbysearch_preparing"
+                && *parser_type != "dmm_stock_arrival"
                 && *parser_type != "dmm_order_number_change"
+                && *parser_type != "amiami_delay"
+                && *parser_type != "hobbysearch_delay"
         }) // バッチパース専用、get_parser 非対応のため除外
         .collect()
 }
 
+/// パーサータイプが対象とするメールの言語（プラグインが見つからない場合は日本語）
+pub fn parser_language(registry: &[Box<dyn VendorPlugin>], parser_type: &str) -> EmailLanguage {
+    find_plugin(registry, parser_type)
+        .map(|plugin| plugin.parser_language(parser_type))
+        .unwrap_or(EmailLanguage::Japanese)
+}
+
+/// 本文の言語に合うパーサーだけに候補を絞り込む
+///
+/// # Arguments
+/// * `candidates` - 候補パーサー（`get_candidate_parsers` の結果）
+/// * `language` - 本文の言語（`detect_language` の結果）
+/// * `language_of` - 候補のパーサー言語を返す関数
+///
+/// # Note
+/// - 言語が判定できない場合、または一致する候補が1つもない場合は絞り込まずにそのまま返す
+///   （判定ミスでパース可能なメールを取りこぼさないため）
+/// - 候補の順序は維持する
+pub fn narrow_candidates_by_language<T>(
+    candidates: Vec<T>,
+    language: Option<EmailLanguage>,
+    language_of: impl Fn(&T) -> EmailLanguage,
+) -> Vec<T> {
+    let Some(language) = language else {
+        return candidates;
+    };
+    if !candidates.iter().any(|c| language_of(c) == language) {
+        return candidates;
+    }
+    candidates
+        .into_iter()
+        .filter(|c| language_of(c) == language)
+        .collect()
+}
+
 /// ドメインをメールアドレスから抽出する
 ///
 /// # Arguments
@@ -133,12 +180,145 @@ mod tests {
         assert!(is_valid_parser_type("hobbysearch_cancel"));
     }
 
+    #[test]
+    fn test_is_cancel_parser() {
+        assert!(is_cancel_parser("amiami_cancel"));
+        assert!(is_cancel_parser("hobbysearch_cancel"));
+        assert!(is_cancel_parser("dmm_auto_cancel"));
+        assert!(!is_cancel_parser("amiami_confirm"));
+        assert!(!is_cancel_parser("hobbysearch_preparing"));
+
+        // 登録済みのキャンセルパーサーはすべて get_parser を持たない（dispatch で直接処理する）
+        let registry = build_registry();
+        for plugin in &registry {
+            for parser_type in plugin.parser_types() {
+                if is_cancel_parser(parser_type) {
+                    assert!(
+                        plugin.get_parser(parser_type).is_none(),
+                        "{parser_type} should be handled in dispatch"
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_get_candidate_parsers_excludes_amiami_cancel() {
+        let settings = vec![(
+            "shop@amiami.com".to_string(),
+            "amiami_cancel".to_string(),
+            Some(r#"["キャンセル"]"#.to_string()),
+        )];
+
+        let candidates = get_candidate_parsers(
+            "shop@amiami.com",
+            Some("あみあみ　キャンセルを承りました"),
+            &settings,
+        );
+        assert!(candidates.is_empty());
+    }
+
+    #[test]
+    fn test_get_candidate_parsers_excludes_amiami_delay() {
+        let settings = vec![(
+            "shop@amiami.com".to_string(),
+            "amiami_delay".to_string(),
+            Some(r#"["発売延期"]"#.to_string()),
+        )];
+
+        let candidates = get_candidate_parsers(
+            "shop@amiami.com",
+            Some("あみあみ　発売延期のお知らせ"),
+            &settings,
+        );
+        assert!(candidates.is_empty());
+    }
+
+    #[test]
+    fn test_get_candidate_parsers_excludes_hobbysearch_delay() {
+        let settings = vec![(
+            "hs-support@1999.co.jp".to_string(),
+            "hobbysearch_delay".to_string(),
+            Some(r#"["【ホビーサーチ】入荷予定変更のお知らせ"]"#.to_string()),
+        )];
+
+        let candidates = get_candidate_parsers(
+            "hs-support@1999.co.jp",
+            Some("【ホビーサーチ】入荷予定変更のお知らせ"),
+            &settings,
+        );
+        assert!(candidates.is_empty());
+    }
+
     #[test]
     fn test_is_valid_parser_type_unknown_returns_false() {
         assert!(!is_valid_parser_type("unknown_parser"));
         assert!(!is_valid_parser_type(""));
     }
 
+    // ==================== narrow_candidates_by_language Tests ====================
+
+    fn test_language_of(parser_type: &&str) -> EmailLanguage {
+        if parser_type.contains("_en_") {
+            EmailLanguage::English
+        } else {
+            EmailLanguage::Japanese
+        }
+    }
+
+    #[test]
+    fn test_narrow_candidates_by_language_keeps_matching_language() {
+        let candidates = vec!["shop_confirm", "shop_en_confirm"];
+        assert_eq!(
+            narrow_candidates_by_language(
+                candidates.clone(),
+                Some(EmailLanguage::English),
+                test_language_of
+            ),
+            vec!["shop_en_confirm"]
+        );
+        assert_eq!(
+            narrow_candidates_by_language(
+                candidates,
+                Some(EmailLanguage::Japanese),
+                test_language_of
+            ),
+            vec!["shop_confirm"]
+        );
+    }
+
+    #[test]
+    fn test_narrow_candidates_by_language_falls_back_to_all() {
+        let candidates = vec!["shop_confirm", "shop_send"];
+        // 一致する言語の候補がない場合は絞り込まない
+        assert_eq!(
+            narrow_candidates_by_language(
+                candidates.clone(),
+                Some(EmailLanguage::English),
+                test_language_of
+            ),
+            candidates
+        );
+        // 判定不能の場合も絞り込まない
+        assert_eq!(
+            narrow_candidates_by_language(candidates.clone(), None, test_language_of),
+            candidates
+        );
+    }
+
+    #[test]
+    fn test_parser_language_defaults_to_japanese() {
+        let registry = build_registry();
+        assert_eq!(
+            parser_language(&registry, "hobbysearch_confirm"),
+            EmailLanguage::Japanese
+        );
+        assert_eq!(
+            parser_language(&registry, "unknown_parser"),
+            EmailLanguage::Japanese
+        );
+    }
+
     // ==================== get_candidate_parsers Tests ====================
 
     #[test]
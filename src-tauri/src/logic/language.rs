@@ -0,0 +1,101 @@
+//! メール本文の言語判定
+//!
+//! 日英両方のメールを送ってくるショップ（あみあみ等）で、本文の言語に合うパーサーだけを
+//! 候補にするために使う。HTML タグ・URL を除いた本文の文字種の比率で判定する。
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+/// メール本文・パーサーの言語
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(export)]
+pub enum EmailLanguage {
+    Japanese,
+    English,
+}
+
+/// 日本語 1 文字を英字何文字分として数えるか（日本語は 1 文字あたりの情報量が多いため）
+const JAPANESE_CHAR_WEIGHT: usize = 4;
+
+/// 判定に必要な最小文字数（英字換算）。これ未満は判定不能
+const MIN_WEIGHTED_CHARS: usize = 20;
+
+/// HTML タグ・文字参照
+static HTML_TAG_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?s)<[^>]*>|&[a-zA-Z#0-9]+;").expect("Invalid HTML_TAG_RE"));
+
+/// URL・メールアドレス（言語に関係なく英字が多いため除外する）
+static URL_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"https?://\S+|[\w.+-]+@[\w-]+\.[\w.-]+").expect("Invalid URL_RE"));
+
+fn is_japanese_char(c: char) -> bool {
+    matches!(c,
+        '\u{3040}'..='\u{309F}' // ひらがな
+        | '\u{30A0}'..='\u{30FF}' // カタカナ
+        | '\u{4E00}'..='\u{9FFF}' // CJK 統合漢字
+        | '\u{FF66}'..='\u{FF9F}' // 半角カタカナ
+    )
+}
+
+/// 本文の言語を判定する
+///
+/// 日本語の文字数 × `JAPANESE_CHAR_WEIGHT` が英字数以上なら日本語、未満なら英語とする。
+/// 英語メールに日本語の商品名が混ざる程度では英語のまま判定される。
+/// 文字が少なすぎる場合は `None`（判定不能）を返す。
+pub fn detect_language(body: &str) -> Option<EmailLanguage> {
+    let text = HTML_TAG_RE.replace_all(body, " ");
+    let text = URL_RE.replace_all(&text, " ");
+
+    let (japanese, latin) = text.chars().fold((0usize, 0usize), |(ja, en), c| {
+        if is_japanese_char(c) {
+            (ja + 1, en)
+        } else if c.is_ascii_alphabetic() {
+            (ja, en + 1)
+        } else {
+            (ja, en)
+        }
+    });
+
+    let weighted_japanese = japanese * JAPANESE_CHAR_WEIGHT;
+    if weighted_japanese + latin < MIN_WEIGHTED_CHARS {
+        return None;
+    }
+    if weighted_japanese >= latin {
+        Some(EmailLanguage::Japanese)
+    } else {
+        Some(EmailLanguage::English)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_japanese() {
+        let body = "この度はご注文いただき誠にありがとうございます。\n注文番号: 12345\nHG 1/144 GUNDAM AERIAL";
+        assert_eq!(detect_language(body), Some(EmailLanguage::Japanese));
+    }
+
+    #[test]
+    fn test_detect_english_with_japanese_product_name() {
+        let body = "Thank you for your order.\nOrder Number: 12345\nItem: ねんどろいど 初音ミク\nWe will notify you when your order has shipped.";
+        assert_eq!(detect_language(body), Some(EmailLanguage::English));
+    }
+
+    #[test]
+    fn test_detect_ignores_html_tags_and_urls() {
+        let body = r#"<html><head><style>body { font-family: sans-serif; }</style></head>
+<body><p>ご注文ありがとうございます。</p><a href="https://example.com/order/detail?id=12345">注文詳細</a></body></html>"#;
+        assert_eq!(detect_language(body), Some(EmailLanguage::Japanese));
+    }
+
+    #[test]
+    fn test_detect_too_short_is_none() {
+        assert_eq!(detect_language(""), None);
+        assert_eq!(detect_language("12345 OK"), None);
+    }
+}
@@ -4,5 +4,10 @@
 //! テスト容易性を高めるため、外部 I/O を最小限に抑えた関数群として実装されています
 //! （ログ出力などの限定的な副作用は含まれます）。
 
+pub mod amount_reconcile;
+pub mod anonymize;
 pub mod email_parser;
+pub mod language;
+pub mod parser_catalog;
+pub mod setup_status;
 pub mod sync_logic;
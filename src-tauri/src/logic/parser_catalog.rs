@@ -0,0 +1,247 @@
+//! パーサーカタログ
+//!
+//! 設定画面で「どのショップのどのメールに対応しているか」を一覧表示するため、
+//! プラグインレジストリから全パーサーの表示名・対象ショップ・メール種別・想定件名例を組み立てる。
+//! 送信元アドレスと件名例は各プラグインの `default_shop_settings()` から取得する。
+
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+use crate::logic::language::EmailLanguage;
+use crate::plugins::{find_plugin, VendorPlugin};
+
+/// パーサーが対象とするメールの種別
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(export)]
+pub enum ParserEmailKind {
+    /// 注文確認
+    OrderConfirmation,
+    /// 発送案内
+    Shipping,
+    /// キャンセル
+    Cancellation,
+    /// 注文内容・注文番号の変更
+    OrderChange,
+    /// おまとめ（複数注文の統合）
+    Consolidation,
+    /// 注文分割
+    Split,
+    /// 出荷準備中
+    Preparing,
+    /// お届け予定日の変更
+    DeliveryDateChange,
+    /// 発売延期
+    ReleaseDelay,
+    /// 配達完了
+    DeliveryComplete,
+    /// 上記以外
+    Other,
+}
+
+/// parser_type の接尾辞とメール種別の対応（長い接尾辞から順に照合する）
+const KIND_SUFFIXES: &[(&str, ParserEmailKind)] = &[
+    ("_delivery_date_change", ParserEmailKind::DeliveryDateChange),
+    ("_order_number_change", ParserEmailKind::OrderChange),
+    ("_delivery_complete", ParserEmailKind::DeliveryComplete),
+    ("_merge_complete", ParserEmailKind::Consolidation),
+    ("_stock_arrival", ParserEmailKind::Preparing),
+    ("_split_complete", ParserEmailKind::Split),
+    ("_confirm_yoyaku", ParserEmailKind::OrderConfirmation),
+    ("_change_yoyaku", ParserEmailKind::OrderChange),
+    ("_auto_cancel", ParserEmailKind::Cancellation),
+    ("_preparing", ParserEmailKind::Preparing),
+    ("_delay", ParserEmailKind::ReleaseDelay),
+    ("_omatome", ParserEmailKind::Consolidation),
+    ("_confirm", ParserEmailKind::OrderConfirmation),
+    ("_change", ParserEmailKind::OrderChange),
+    ("_cancel", ParserEmailKind::Cancellation),
+    ("_send", ParserEmailKind::Shipping),
+];
+
+impl ParserEmailKind {
+    /// parser_type の命名規則（`{shop}_{kind}`）からメール種別を判定する
+    pub fn from_parser_type(parser_type: &str) -> Self {
+        KIND_SUFFIXES
+            .iter()
+            .find(|(suffix, _)| parser_type.ends_with(suffix))
+            .map(|(_, kind)| *kind)
+            .unwrap_or(Self::Other)
+    }
+
+    /// 表示用の名称
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::OrderConfirmation => "注文確認",
+            Self::Shipping => "発送案内",
+            Self::Cancellation => "キャンセル",
+            Self::OrderChange => "注文変更",
+            Self::Consolidation => "おまとめ",
+            Self::Split => "注文分割",
+            Self::Preparing => "出荷準備中",
+            Self::DeliveryDateChange => "お届け予定日変更",
+            Self::ReleaseDelay => "発売延期",
+            Self::DeliveryComplete => "配達完了",
+            Self::Other => "その他",
+        }
+    }
+}
+
+/// パーサー1件分のカタログ情報
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct ParserCatalogEntry {
+    pub parser_type: String,
+    /// 表示名（例: 「あみあみ 発送案内」）
+    pub display_name: String,
+    /// 対象ショップ名
+    pub shop_name: String,
+    pub email_kind: ParserEmailKind,
+    /// メール種別の表示名
+    pub email_kind_label: String,
+    pub language: EmailLanguage,
+    /// 想定する送信元アドレス（デフォルト設定のもの）
+    pub sender_addresses: Vec<String>,
+    /// 想定する件名の例（デフォルト設定の件名フィルタ）
+    pub subject_examples: Vec<String>,
+}
+
+fn push_unique(values: &mut Vec<String>, value: &str) {
+    if !values.iter().any(|v| v == value) {
+        values.push(value.to_string());
+    }
+}
+
+/// プラグインレジストリから全パーサーのカタログを組み立てる
+///
+/// 同じ parser_type を複数のプラグインが持つ場合は `find_plugin` と同様に `priority()` が最大のものを使う。
+/// 並び順はショップ名 → parser_type。
+pub fn build_parser_catalog(registry: &[Box<dyn VendorPlugin>]) -> Vec<ParserCatalogEntry> {
+    let mut parser_types: Vec<&str> = registry
+        .iter()
+        .flat_map(|p| p.parser_types().iter().copied())
+        .collect();
+    parser_types.sort_unstable();
+    parser_types.dedup();
+
+    let mut catalog: Vec<ParserCatalogEntry> = parser_types
+        .into_iter()
+        .filter_map(|parser_type| {
+            let plugin = find_plugin(registry, parser_type)?;
+            let settings: Vec<_> = plugin
+                .default_shop_settings()
+                .into_iter()
+                .filter(|s| s.parser_type == parser_type)
+                .collect();
+
+            let shop_name = settings
+                .first()
+                .map(|s| s.shop_name.clone())
+                .unwrap_or_else(|| plugin.shop_name().to_string());
+            let mut sender_addresses = Vec::new();
+            let mut subject_examples = Vec::new();
+            for setting in &settings {
+                push_unique(&mut sender_addresses, &setting.sender_address);
+                for subject in setting.subject_filters.iter().flatten() {
+                    push_unique(&mut subject_examples, subject);
+                }
+            }
+
+            let email_kind = ParserEmailKind::from_parser_type(parser_type);
+            Some(ParserCatalogEntry {
+                parser_type: parser_type.to_string(),
+                display_name: format!("{} {}", shop_name, email_kind.label()),
+                shop_name,
+                email_kind,
+                email_kind_label: email_kind.label().to_string(),
+                language: plugin.parser_language(parser_type),
+                sender_addresses,
+                subject_examples,
+            })
+        })
+        .collect();
+
+    catalog.sort_by(|a, b| {
+        a.shop_name
+            .cmp(&b.shop_name)
+            .then_with(|| a.parser_type.cmp(&b.parser_type))
+    });
+    catalog
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::plugins::build_registry;
+
+    #[test]
+    fn test_email_kind_from_parser_type() {
+        use ParserEmailKind::*;
+        assert_eq!(ParserEmailKind::from_parser_type("amiami_send"), Shipping);
+        assert_eq!(
+            ParserEmailKind::from_parser_type("hobbysearch_confirm_yoyaku"),
+            OrderConfirmation
+        );
+        assert_eq!(
+            ParserEmailKind::from_parser_type("yodobashi_delivery_date_change"),
+            DeliveryDateChange
+        );
+        assert_eq!(
+            ParserEmailKind::from_parser_type("dmm_order_number_change"),
+            OrderChange
+        );
+        assert_eq!(
+            ParserEmailKind::from_parser_type("dmm_auto_cancel"),
+            Cancellation
+        );
+        assert_eq!(
+            ParserEmailKind::from_parser_type("premium_bandai_omatome"),
+            Consolidation
+        );
+        assert_eq!(
+            ParserEmailKind::from_parser_type("sagawa_delivery_complete"),
+            DeliveryComplete
+        );
+        assert_eq!(
+            ParserEmailKind::from_parser_type("dmm_stock_arrival"),
+            Preparing
+        );
+        assert_eq!(
+            ParserEmailKind::from_parser_type("amiami_delay"),
+            ReleaseDelay
+        );
+        assert_eq!(
+            ParserEmailKind::from_parser_type("hobbysearch_delay"),
+            ReleaseDelay
+        );
+        assert_eq!(ParserEmailKind::from_parser_type("unknown"), Other);
+    }
+
+    #[test]
+    fn test_build_parser_catalog_covers_registry() {
+        let registry = build_registry();
+        let catalog = build_parser_catalog(&registry);
+
+        let total: usize = {
+            let mut types: Vec<&str> = registry
+                .iter()
+                .flat_map(|p| p.parser_types().iter().copied())
+                .collect();
+            types.sort_unstable();
+            types.dedup();
+            types.len()
+        };
+        assert_eq!(catalog.len(), total);
+
+        let entry = catalog
+            .iter()
+            .find(|e| e.parser_type == "amiami_rakuten_send")
+            .expect("amiami_rakuten_send should be in catalog");
+        assert_eq!(entry.shop_name, "あみあみ");
+        assert_eq!(entry.display_name, "あみあみ 発送案内");
+        assert_eq!(entry.email_kind, ParserEmailKind::Shipping);
+        assert_eq!(entry.language, EmailLanguage::Japanese);
+        assert_eq!(entry.sender_addresses, vec!["amiami_2@shop.rakuten.co.jp"]);
+        assert_eq!(entry.subject_examples, vec!["発送案内"]);
+    }
+}
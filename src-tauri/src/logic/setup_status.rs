@@ -0,0 +1,132 @@
+//! 初回セットアップウィザードの進捗判定
+//!
+//! 実際の設定状況（OAuth 認証情報・店舗設定・同期済みメール等）とユーザーが付けた完了マークから、
+//! 各ステップの完了状態と次に案内すべきステップを決める。
+
+use serde::Serialize;
+use ts_rs::TS;
+
+use crate::config::SetupStep;
+
+/// 実際の設定状況
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, TS)]
+#[ts(export)]
+pub struct SetupChecks {
+    /// Gmail OAuth 認証情報を登録済みか
+    pub has_oauth_credentials: bool,
+    /// Gmail へのアクセスを許可済み（OAuth トークン取得済み）か
+    pub gmail_authorized: bool,
+    /// 有効な店舗設定があるか
+    pub has_shop_settings: bool,
+    /// 同期済みのメールがあるか
+    pub initial_sync_done: bool,
+    /// Gemini API キーを登録済みか
+    pub has_gemini_api_key: bool,
+}
+
+impl SetupChecks {
+    /// 設定状況からステップの完了を検出できるか
+    pub fn detects(&self, step: SetupStep) -> bool {
+        match step {
+            SetupStep::OauthCredentials => self.has_oauth_credentials,
+            SetupStep::GmailAuthorization => self.gmail_authorized,
+            SetupStep::ShopSettings => self.has_shop_settings,
+            SetupStep::InitialSync => self.initial_sync_done,
+            SetupStep::Gemini => self.has_gemini_api_key,
+        }
+    }
+}
+
+/// ステップごとの完了状態
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, TS)]
+#[ts(export)]
+pub struct SetupStepStatus {
+    pub step: SetupStep,
+    /// 設定状況から完了を検出した
+    pub detected: bool,
+    /// ユーザーが完了（スキップ）とマークした
+    pub marked: bool,
+    /// detected または marked
+    pub completed: bool,
+}
+
+/// オンボーディング画面向けのセットアップ状態
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, TS)]
+#[ts(export)]
+pub struct SetupStatus {
+    pub checks: SetupChecks,
+    /// 表示順のステップ一覧
+    pub steps: Vec<SetupStepStatus>,
+    /// 最初の未完了ステップ（全ステップ完了なら None）
+    pub next_step: Option<SetupStep>,
+    pub is_complete: bool,
+}
+
+/// 設定状況と完了マークからセットアップ状態を組み立てる
+pub fn build_setup_status(checks: SetupChecks, marked_steps: &[SetupStep]) -> SetupStatus {
+    let steps: Vec<SetupStepStatus> = SetupStep::ALL
+        .iter()
+        .map(|&step| {
+            let detected = checks.detects(step);
+            let marked = marked_steps.contains(&step);
+            SetupStepStatus {
+                step,
+                detected,
+                marked,
+                completed: detected || marked,
+            }
+        })
+        .collect();
+    let next_step = steps.iter().find(|s| !s.completed).map(|s| s.step);
+
+    SetupStatus {
+        checks,
+        steps,
+        next_step,
+        is_complete: next_step.is_none(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_setup_status_fresh_install() {
+        let status = build_setup_status(SetupChecks::default(), &[]);
+        assert_eq!(status.steps.len(), SetupStep::ALL.len());
+        assert_eq!(status.next_step, Some(SetupStep::OauthCredentials));
+        assert!(!status.is_complete);
+    }
+
+    #[test]
+    fn test_build_setup_status_detected_and_marked_steps() {
+        let checks = SetupChecks {
+            has_oauth_credentials: true,
+            gmail_authorized: true,
+            has_shop_settings: true,
+            initial_sync_done: false,
+            has_gemini_api_key: false,
+        };
+        let status = build_setup_status(checks.clone(), &[SetupStep::Gemini]);
+        assert_eq!(status.next_step, Some(SetupStep::InitialSync));
+
+        let gemini = status
+            .steps
+            .iter()
+            .find(|s| s.step == SetupStep::Gemini)
+            .unwrap();
+        assert!(!gemini.detected);
+        assert!(gemini.marked);
+        assert!(gemini.completed);
+
+        // 任意ステップをスキップ済みなら、初回同期の完了で全体完了になる
+        let checks = SetupChecks {
+            initial_sync_done: true,
+            ..checks
+        };
+        let status = build_setup_status(checks, &[SetupStep::Gemini]);
+        assert_eq!(status.next_step, None);
+        assert!(status.is_complete);
+    }
+}
@@ -4,7 +4,7 @@
 //! Gmail API や DB へのアクセスといった外部依存を持たないためテストが容易ですが、
 //! ログ出力などの副作用は発生する場合があります。
 
-use crate::gmail::{GmailMessage, ShopSettings};
+use crate::gmail::{GmailMessage, MisfiledShopMail, ShopSettings};
 use crate::gmail_client::GmailClientTrait;
 
 /// Gmail検索クエリを構築する
@@ -70,6 +70,126 @@ pub fn build_sync_query(
     query
 }
 
+/// 迷惑メールに振り分けられたメールの検索条件（SPAM ラベル）
+pub const SPAM_SEARCH: &str = "in:spam";
+
+/// プロモーションタブに振り分けられたメールの検索条件（CATEGORY_PROMOTIONS ラベル）
+pub const PROMOTIONS_SEARCH: &str = "category:promotions";
+
+/// 迷惑メール・プロモーション内の対象メール数を数えるときの上限（1ページ分）
+pub const MISFILED_COUNT_LIMIT: u32 = 500;
+
+/// 対象ショップのメールのうち、指定した場所（`in:spam` など）にあるものを探す検索クエリを構築する
+///
+/// 送信元が未設定の場合は確認しようがないため None を返す。
+/// `after_date`（RFC3339形式）を指定すると、差分同期と同じくその日付以降のメールに絞り込む。
+///
+/// # Examples
+/// ```
+/// use paa_lib::logic::sync_logic::{build_misfiled_query, SPAM_SEARCH};
+///
+/// let query = build_misfiled_query(
+///     &["a@example.com".to_string(), "b@example.com".to_string()],
+///     SPAM_SEARCH,
+///     &None,
+/// );
+/// assert_eq!(
+///     query.as_deref(),
+///     Some("in:spam (from:a@example.com OR from:b@example.com)")
+/// );
+/// assert_eq!(build_misfiled_query(&[], SPAM_SEARCH, &None), None);
+/// ```
+pub fn build_misfiled_query(
+    sender_addresses: &[String],
+    location: &str,
+    after_date: &Option<String>,
+) -> Option<String> {
+    if sender_addresses.is_empty() {
+        return None;
+    }
+    let from_clauses: Vec<String> = sender_addresses
+        .iter()
+        .map(|addr| format!("from:{addr}"))
+        .collect();
+    let mut query = format!("{location} ({})", from_clauses.join(" OR "));
+
+    if let Some(date) = after_date {
+        if let Ok(dt) = chrono::DateTime::parse_from_rfc3339(date) {
+            query = format!("{query} after:{}", dt.format("%Y/%m/%d"));
+        }
+    }
+
+    Some(query)
+}
+
+/// 迷惑メール・プロモーション内に対象メールがある場合の通知用警告文を返す（なければ None）
+///
+/// # Examples
+/// ```
+/// use paa_lib::gmail::MisfiledShopMail;
+/// use paa_lib::logic::sync_logic::misfiled_warning_message;
+///
+/// let counts = MisfiledShopMail { spam_count: 3, promotions_count: 0 };
+/// assert_eq!(
+///     misfiled_warning_message(&counts).as_deref(),
+///     Some("対象ショップのメールが迷惑メールに3件振り分けられています。Gmailのフィルタ設定を確認してください")
+/// );
+/// assert_eq!(misfiled_warning_message(&MisfiledShopMail::default()), None);
+/// ```
+pub fn misfiled_warning_message(counts: &MisfiledShopMail) -> Option<String> {
+    let limit = i64::from(MISFILED_COUNT_LIMIT);
+    let format_count = |count: i64| {
+        if count >= limit {
+            format!("{limit}件以上")
+        } else {
+            format!("{count}件")
+        }
+    };
+    let mut places = Vec::new();
+    if counts.spam_count > 0 {
+        places.push(format!("迷惑メールに{}", format_count(counts.spam_count)));
+    }
+    if counts.promotions_count > 0 {
+        places.push(format!(
+            "プロモーションタブに{}",
+            format_count(counts.promotions_count)
+        ));
+    }
+    if places.is_empty() {
+        return None;
+    }
+    Some(format!(
+        "対象ショップのメールが{}振り分けられています。Gmailのフィルタ設定を確認してください",
+        places.join("・")
+    ))
+}
+
+/// 1メッセージあたりの取得・保存にかかる目安時間（ミリ秒）
+const ESTIMATED_MS_PER_MESSAGE: i64 = 250;
+
+/// 生の .eml も保存する場合の1メッセージあたりの追加時間（ミリ秒）
+const ESTIMATED_MS_PER_RAW_EML: i64 = 250;
+
+/// 同期対象メール数から推定所要時間（秒、切り上げ）を計算する
+///
+/// # Examples
+/// ```
+/// use paa_lib::logic::sync_logic::estimate_sync_seconds;
+///
+/// assert_eq!(estimate_sync_seconds(0, false), 0);
+/// assert_eq!(estimate_sync_seconds(4, false), 1);
+/// assert_eq!(estimate_sync_seconds(4, true), 2);
+/// ```
+pub fn estimate_sync_seconds(new_message_count: i64, save_raw_eml: bool) -> i64 {
+    let per_message_ms = if save_raw_eml {
+        ESTIMATED_MS_PER_MESSAGE + ESTIMATED_MS_PER_RAW_EML
+    } else {
+        ESTIMATED_MS_PER_MESSAGE
+    };
+    let total_ms = new_message_count.max(0).saturating_mul(per_message_ms);
+    (total_ms + 999) / 1000
+}
+
 /// "From"ヘッダーからメールアドレスを抽出する
 ///
 /// # Arguments
@@ -307,10 +427,50 @@ pub async fn fetch_batch_with_client(
     Ok((messages, next_page_token))
 }
 
+/// 迷惑メール・プロモーションタブに振り分けられた対象ショップのメール数を数える
+///
+/// 各場所で1ページ（`MISFILED_COUNT_LIMIT` 件）だけ検索し、本文は取得しない。
+/// 送信元が未設定の場合は 0 件として返す。
+pub async fn count_misfiled_shop_mail(
+    client: &dyn GmailClientTrait,
+    sender_addresses: &[String],
+    after_date: &Option<String>,
+) -> Result<MisfiledShopMail, String> {
+    let mut counts = MisfiledShopMail::default();
+    for (location, count) in [
+        (SPAM_SEARCH, &mut counts.spam_count),
+        (PROMOTIONS_SEARCH, &mut counts.promotions_count),
+    ] {
+        let Some(query) = build_misfiled_query(sender_addresses, location, after_date) else {
+            continue;
+        };
+        let (ids, _) = client
+            .list_message_ids(&query, MISFILED_COUNT_LIMIT, None)
+            .await?;
+        *count = ids.len() as i64;
+    }
+    Ok(counts)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    // ==================== estimate_sync_seconds Tests ====================
+
+    #[test]
+    fn test_estimate_sync_seconds_rounds_up() {
+        assert_eq!(estimate_sync_seconds(0, false), 0);
+        assert_eq!(estimate_sync_seconds(1, false), 1);
+        assert_eq!(estimate_sync_seconds(400, false), 100);
+        assert_eq!(estimate_sync_seconds(400, true), 200);
+    }
+
+    #[test]
+    fn test_estimate_sync_seconds_ignores_negative_count() {
+        assert_eq!(estimate_sync_seconds(-5, true), 0);
+    }
+
     // ==================== build_sync_query Tests ====================
 
     #[test]
@@ -733,6 +893,33 @@ mod tests {
         assert_eq!(filtered_out, 0);
     }
 
+    // ==================== build_misfiled_query / misfiled_warning_message Tests ====================
+
+    #[test]
+    fn test_build_misfiled_query_with_after_date() {
+        let query = build_misfiled_query(
+            &["shop@example.com".to_string()],
+            PROMOTIONS_SEARCH,
+            &Some("2024-01-15T00:00:00Z".to_string()),
+        );
+        assert_eq!(
+            query.as_deref(),
+            Some("category:promotions (from:shop@example.com) after:2024/01/15")
+        );
+    }
+
+    #[test]
+    fn test_misfiled_warning_message_both_and_capped() {
+        let counts = MisfiledShopMail {
+            spam_count: 1,
+            promotions_count: i64::from(MISFILED_COUNT_LIMIT),
+        };
+        assert_eq!(
+            misfiled_warning_message(&counts).as_deref(),
+            Some("対象ショップのメールが迷惑メールに1件・プロモーションタブに500件以上振り分けられています。Gmailのフィルタ設定を確認してください")
+        );
+    }
+
     // ==================== fetch_batch_with_client Tests ====================
 
     use crate::gmail_client::MockGmailClientTrait;
@@ -796,6 +983,34 @@ mod tests {
         assert_eq!(result.unwrap_err(), "API error");
     }
 
+    #[tokio::test]
+    async fn test_count_misfiled_shop_mail() {
+        let mut mock = MockGmailClientTrait::new();
+
+        mock.expect_list_message_ids()
+            .withf(|q, m, t| q == "in:spam (from:shop@example.com)" && *m == 500 && t.is_none())
+            .times(1)
+            .returning(|_, _, _| Ok((vec!["spam1".to_string(), "spam2".to_string()], None)));
+        mock.expect_list_message_ids()
+            .withf(|q, _, _| q == "category:promotions (from:shop@example.com)")
+            .times(1)
+            .returning(|_, _, _| Ok((vec![], None)));
+
+        let counts =
+            super::count_misfiled_shop_mail(&mock, &["shop@example.com".to_string()], &None)
+                .await
+                .unwrap();
+        assert_eq!(counts.spam_count, 2);
+        assert_eq!(counts.promotions_count, 0);
+
+        // 送信元が未設定なら Gmail API を呼ばない
+        let empty = MockGmailClientTrait::new();
+        let counts = super::count_misfiled_shop_mail(&empty, &[], &None)
+            .await
+            .unwrap();
+        assert!(counts.is_empty());
+    }
+
     #[tokio::test]
     async fn test_fetch_batch_with_client_partial_fetch_error() {
         let mut mock = MockGmailClientTrait::new();
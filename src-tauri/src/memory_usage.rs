@@ -0,0 +1,179 @@
+//! プロセスのメモリ使用量の取得（ログ出力用）
+//!
+//! 大量同期・パース時のメモリ増加を追えるよう、バッチ処理の開始・各バッチ後・終了時に
+//! 常駐メモリ（RSS / Windows ではワーキングセット）とピーク値をログに出す。
+//! 取得できない OS では `None` を返し、ログを省略する。
+
+use std::fmt;
+
+/// ある時点のメモリ使用量
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ProcessMemory {
+    /// 現在の常駐メモリ（バイト）
+    pub resident_bytes: u64,
+    /// プロセス起動以降の常駐メモリのピーク（バイト）
+    pub peak_resident_bytes: u64,
+}
+
+impl fmt::Display for ProcessMemory {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "rss={} peak={}",
+            format_mb(self.resident_bytes),
+            format_mb(self.peak_resident_bytes)
+        )
+    }
+}
+
+/// バイト数を `12.3MB` 形式にする
+pub fn format_mb(bytes: u64) -> String {
+    format!("{:.1}MB", bytes as f64 / (1024.0 * 1024.0))
+}
+
+/// 現在のメモリ使用量を取得する（非対応 OS・取得失敗時は None）
+pub fn current() -> Option<ProcessMemory> {
+    imp::current()
+}
+
+/// `/proc/self/status` の `VmRSS` / `VmHWM`（kB 単位）を読む
+#[cfg(any(target_os = "linux", test))]
+fn parse_proc_status(status: &str) -> Option<ProcessMemory> {
+    let kb = |key: &str| -> Option<u64> {
+        status
+            .lines()
+            .find_map(|line| line.strip_prefix(key))
+            .and_then(|rest| rest.trim().trim_end_matches("kB").trim().parse().ok())
+    };
+    let resident_kb = kb("VmRSS:")?;
+    let peak_kb = kb("VmHWM:").unwrap_or(resident_kb);
+    Some(ProcessMemory {
+        resident_bytes: resident_kb * 1024,
+        peak_resident_bytes: peak_kb.max(resident_kb) * 1024,
+    })
+}
+
+#[cfg(target_os = "linux")]
+mod imp {
+    use super::{parse_proc_status, ProcessMemory};
+
+    pub fn current() -> Option<ProcessMemory> {
+        let status = std::fs::read_to_string("/proc/self/status").ok()?;
+        parse_proc_status(&status)
+    }
+}
+
+#[cfg(windows)]
+mod imp {
+    use super::ProcessMemory;
+    use windows_sys::Win32::System::ProcessStatus::{
+        GetProcessMemoryInfo, PROCESS_MEMORY_COUNTERS,
+    };
+    use windows_sys::Win32::System::Threading::GetCurrentProcess;
+
+    pub fn current() -> Option<ProcessMemory> {
+        let mut counters: PROCESS_MEMORY_COUNTERS = unsafe { std::mem::zeroed() };
+        let size = std::mem::size_of::<PROCESS_MEMORY_COUNTERS>() as u32;
+        counters.cb = size;
+        // SAFETY: GetCurrentProcess は疑似ハンドルを返し、counters は cb に合ったサイズで確保している
+        let ok = unsafe { GetProcessMemoryInfo(GetCurrentProcess(), &mut counters, size) };
+        (ok != 0).then(|| ProcessMemory {
+            resident_bytes: counters.WorkingSetSize as u64,
+            peak_resident_bytes: counters.PeakWorkingSetSize as u64,
+        })
+    }
+}
+
+#[cfg(not(any(target_os = "linux", windows)))]
+mod imp {
+    use super::ProcessMemory;
+
+    pub fn current() -> Option<ProcessMemory> {
+        None
+    }
+}
+
+/// バッチ処理中のメモリ使用量の推移を記録する
+///
+/// OS のピーク値はプロセス起動以降の値のため、処理中にサンプリングした最大値も別に保持し、
+/// 終了時に「開始時からどれだけ増えたか」をログに出す。
+#[derive(Debug, Clone, Copy)]
+pub struct MemoryWatermark {
+    start: Option<ProcessMemory>,
+    max_sampled_bytes: u64,
+}
+
+impl MemoryWatermark {
+    /// 現在の使用量を起点に記録を開始する
+    pub fn start() -> Self {
+        Self::from_sample(current())
+    }
+
+    fn from_sample(start: Option<ProcessMemory>) -> Self {
+        Self {
+            start,
+            max_sampled_bytes: start.map(|m| m.resident_bytes).unwrap_or(0),
+        }
+    }
+
+    /// 現在の使用量をサンプリングして返す
+    pub fn sample(&mut self) -> Option<ProcessMemory> {
+        let now = current();
+        self.record(now);
+        now
+    }
+
+    fn record(&mut self, sample: Option<ProcessMemory>) {
+        if let Some(m) = sample {
+            self.max_sampled_bytes = self.max_sampled_bytes.max(m.resident_bytes);
+        }
+    }
+
+    /// 終了時のログ用の要約（取得できない OS では None）
+    pub fn summary(&self) -> Option<String> {
+        let start = self.start?;
+        Some(format!(
+            "start={} max={} (+{}) process_peak={}",
+            format_mb(start.resident_bytes),
+            format_mb(self.max_sampled_bytes),
+            format_mb(self.max_sampled_bytes.saturating_sub(start.resident_bytes)),
+            format_mb(start.peak_resident_bytes.max(self.max_sampled_bytes))
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mem(resident_mb: u64, peak_mb: u64) -> ProcessMemory {
+        ProcessMemory {
+            resident_bytes: resident_mb * 1024 * 1024,
+            peak_resident_bytes: peak_mb * 1024 * 1024,
+        }
+    }
+
+    #[test]
+    fn test_parse_proc_status() {
+        let status = "Name:\tpaa\nVmHWM:\t  204800 kB\nVmRSS:\t  102400 kB\nThreads:\t12\n";
+        assert_eq!(parse_proc_status(status), Some(mem(100, 200)));
+        assert_eq!(parse_proc_status("Name:\tpaa\n"), None);
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(mem(100, 200).to_string(), "rss=100.0MB peak=200.0MB");
+    }
+
+    #[test]
+    fn test_watermark_summary() {
+        let mut watermark = MemoryWatermark::from_sample(Some(mem(100, 120)));
+        watermark.record(Some(mem(150, 150)));
+        watermark.record(Some(mem(110, 150)));
+        assert_eq!(
+            watermark.summary().as_deref(),
+            Some("start=100.0MB max=150.0MB (+50.0MB) process_peak=150.0MB")
+        );
+        assert_eq!(MemoryWatermark::from_sample(None).summary(), None);
+    }
+}
@@ -1,6 +1,7 @@
 //! テーブル行の型定義（エクスポート用タプル型・インポート用デシリアライズ構造体）
 
 use serde::{Deserialize, Serialize};
+use ts_rs::TS;
 
 /// shop_settings テーブル行 (id, shop_name, sender_address, parser_type, is_enabled, subject_filters, created_at, updated_at)
 pub(super) type ShopSettingsRow = (
@@ -129,7 +130,8 @@ pub(super) type NewsClipRow = (
 pub(super) type ItemExclusionPatternRow =
     (i64, Option<String>, String, String, Option<String>, String);
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, TS)]
+#[ts(export)]
 pub struct ExportResult {
     pub images_count: usize,
     pub shop_settings_count: usize,
@@ -154,7 +156,8 @@ pub struct ExportResult {
     pub restore_point_error: Option<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, TS)]
+#[ts(export)]
 pub struct ImportResult {
     pub images_inserted: usize,
     pub shop_settings_inserted: usize,
@@ -0,0 +1,106 @@
+//! スマート通知ルール
+//!
+//! 「○○円以上の注文が発送されたら」のように金額・ショップ・タグ・配送状況を組み合わせたルールを
+//! パース・配送状況確認のバッチ処理後に評価し、新たに一致した注文があればデスクトップ通知する。
+//! ルール作成時点で既に一致していた注文は通知しない。
+
+use sqlx::sqlite::SqlitePool;
+
+use crate::batch_runner::BatchEventEmitter;
+use crate::orchestration::BatchCommandsApp;
+use crate::report::spending_chart::format_amount;
+use crate::repository::{NotificationRuleMatch, SqliteNotificationRuleRepository};
+
+/// 一致した注文をフロントエンドへ知らせるイベント
+pub const NOTIFICATION_RULE_MATCHED_EVENT: &str = "notification-rule-matched";
+
+/// 通知本文に列挙する注文数の上限（超過分は件数のみ）
+const MAX_NOTIFIED_ORDERS: usize = 3;
+
+/// デスクトップ通知のタイトルと本文（一致がなければ None）
+///
+/// 一致したルールが 1 つならルール名をタイトルにする。
+pub fn build_notification(matches: &[NotificationRuleMatch]) -> Option<(String, String)> {
+    let first = matches.first()?;
+    let title = if matches.iter().all(|m| m.rule_id == first.rule_id) {
+        first.rule_name.clone()
+    } else {
+        "通知ルールに一致した注文があります".to_string()
+    };
+
+    let mut body = matches
+        .iter()
+        .take(MAX_NOTIFIED_ORDERS)
+        .map(|m| {
+            format!(
+                "{} {}（{}円）",
+                m.shop_name.as_deref().unwrap_or("不明なショップ"),
+                m.order_number.as_deref().unwrap_or("-"),
+                format_amount(m.total_amount)
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("／");
+    if matches.len() > MAX_NOTIFIED_ORDERS {
+        body.push_str(&format!(" ほか {}件", matches.len() - MAX_NOTIFIED_ORDERS));
+    }
+    Some((title, body))
+}
+
+/// ルールを評価し、新たに一致した注文を通知する。バッチ処理の結果には影響させず、失敗はログのみ。
+pub(crate) async fn notify_matched_rules<A: BatchCommandsApp>(app: &A, pool: &SqlitePool) {
+    let matches = match SqliteNotificationRuleRepository::new(pool.clone())
+        .evaluate()
+        .await
+    {
+        Ok(matches) => matches,
+        Err(e) => {
+            log::warn!("[NotificationRules] Failed to evaluate rules: {e}");
+            return;
+        }
+    };
+    if let Some((title, body)) = build_notification(&matches) {
+        log::info!(
+            "[NotificationRules] {} order(s) matched notification rules",
+            matches.len()
+        );
+        app.notify(&title, &body);
+        app.emit_event(NOTIFICATION_RULE_MATCHED_EVENT, &matches);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn matched(rule_id: i64, order_number: &str, total_amount: i64) -> NotificationRuleMatch {
+        NotificationRuleMatch {
+            rule_id,
+            rule_name: format!("ルール{rule_id}"),
+            order_id: 1,
+            shop_name: Some("ホビーショップ".to_string()),
+            order_number: Some(order_number.to_string()),
+            total_amount,
+            delivery_status: Some("shipped".to_string()),
+        }
+    }
+
+    #[test]
+    fn test_build_notification() {
+        assert_eq!(build_notification(&[]), None);
+
+        let (title, body) = build_notification(&[matched(1, "A-1", 12000)]).unwrap();
+        assert_eq!(title, "ルール1");
+        assert_eq!(body, "ホビーショップ A-1（12,000円）");
+
+        let (title, body) = build_notification(&[
+            matched(1, "A-1", 12000),
+            matched(2, "A-2", 1000),
+            matched(2, "A-3", 1000),
+            matched(2, "A-4", 1000),
+        ])
+        .unwrap();
+        assert_eq!(title, "通知ルールに一致した注文があります");
+        assert!(body.ends_with(" ほか 1件"));
+    }
+}
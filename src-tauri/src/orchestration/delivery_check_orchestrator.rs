@@ -2,7 +2,7 @@
 
 use sqlx::sqlite::SqlitePool;
 
-use super::{BatchCommandsApp, TauriBatchCommandsApp};
+use super::{low_priority_throttle, BatchCommandsApp, TauriBatchCommandsApp};
 use crate::batch_runner::{BatchProgressEvent, BatchRunner};
 use crate::commands::DeliveryCheckState;
 use crate::delivery_check::{
@@ -85,7 +85,9 @@ async fn run_delivery_check_task_with<A: BatchCommandsApp>(
         .collect();
 
     // バッチサイズ 5・バッチ間 3 秒（配送業者サイトへの負荷を抑える）
-    let runner = BatchRunner::new(DeliveryCheckTask, 5, 3_000);
+    let runner = BatchRunner::new(DeliveryCheckTask, 5, 3_000)
+        .with_throttle(low_priority_throttle(app))
+        .with_collect_outputs(false);
     let check_state_for_cancel = check_state.clone();
 
     match runner
@@ -112,5 +114,7 @@ async fn run_delivery_check_task_with<A: BatchCommandsApp>(
         }
     }
 
+    crate::notification_rules::notify_matched_rules(app, &pool).await;
+
     check_state.finish();
 }
@@ -25,13 +25,15 @@ mod ui_pipeline;
 
 // — re-exports —
 pub use delivery_check_orchestrator::run_delivery_check_task;
-pub use parse_orchestrator::run_batch_parse_task;
+pub use parse_orchestrator::{
+    parse_undo_snapshot_path, reparse_order, run_batch_parse_task, verify_order_restructure,
+};
 pub use pipeline_orchestrator::run_pipeline;
 pub use product_parse_orchestrator::run_product_name_parse_task;
-pub use sync_orchestrator::{run_incremental_sync_task, run_sync_task};
+pub use sync_orchestrator::{estimate_sync, run_incremental_sync_task, run_sync_task};
 pub use ui_pipeline::run_full_parse_pipeline;
 
-use crate::batch_runner::BatchEventEmitter;
+use crate::batch_runner::{BatchEventEmitter, BatchThrottle};
 use crate::e2e_mocks::{is_e2e_mock_mode, E2EMockGmailClient, GmailClientForE2E};
 use tauri::{Emitter, Manager};
 use tauri_plugin_notification::NotificationExt;
@@ -99,6 +101,34 @@ impl BatchCommandsApp for TauriBatchCommandsApp {
 // ユーティリティ
 // ---------------------------------------------------------------------------
 
+/// config.debug.batch_log_stream（詳細ログ `batch-log` の送信有無）を読み込む。
+/// 設定を読み込めない場合は無効とする。
+pub(crate) fn batch_log_stream_enabled<A: BatchCommandsApp>(app: &A) -> bool {
+    app.app_config_dir()
+        .ok()
+        .and_then(|dir| crate::config::load(&dir).ok())
+        .is_some_and(|config| config.debug.batch_log_stream)
+}
+
+/// 低優先度モードの設定からスロットリングを求める（無効なら None）。
+pub(crate) fn throttle_from_config(
+    config: &crate::config::LowPriorityConfig,
+) -> Option<BatchThrottle> {
+    config.enabled.then(|| BatchThrottle {
+        max_batch_size: clamp_batch_size(config.batch_size, 5),
+        delay_ms: u64::try_from(config.delay_ms).unwrap_or(0),
+    })
+}
+
+/// config.low_priority を読み込み、低優先度モードならスロットリングを返す。
+/// 設定を読み込めない場合は通常モードとする。
+pub(crate) fn low_priority_throttle<A: BatchCommandsApp>(app: &A) -> Option<BatchThrottle> {
+    app.app_config_dir()
+        .ok()
+        .and_then(|dir| crate::config::load(&dir).ok())
+        .and_then(|config| throttle_from_config(&config.low_priority))
+}
+
 /// config.parse.batch_size (i64) を usize へ安全に変換。
 /// 0 以下は default にフォールバック。変換失敗時（32-bit で i64 が大きい等）も default。
 /// 上限はクランプしない（大きい i64 は usize::try_from で失敗→default）。
@@ -130,6 +160,32 @@ mod tests {
         assert_eq!(clamp_batch_size(200, 100), 200);
         assert_eq!(clamp_batch_size(i64::MIN, 100), 100);
     }
+
+    #[test]
+    fn test_throttle_from_config() {
+        let mut config = crate::config::LowPriorityConfig::default();
+        assert_eq!(throttle_from_config(&config), None);
+
+        config.enabled = true;
+        assert_eq!(
+            throttle_from_config(&config),
+            Some(BatchThrottle {
+                max_batch_size: 5,
+                delay_ms: 1000,
+            })
+        );
+
+        // 不正値は既定のバッチサイズ・ディレイなしにフォールバック
+        config.batch_size = 0;
+        config.delay_ms = -1;
+        assert_eq!(
+            throttle_from_config(&config),
+            Some(BatchThrottle {
+                max_batch_size: 5,
+                delay_ms: 0,
+            })
+        );
+    }
 }
 
 /// テスト用ヘルパー。各オーケストレーターのテストモジュールから共有される。
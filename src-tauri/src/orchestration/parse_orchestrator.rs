@@ -1,24 +1,48 @@
 //! メール解析オーケストレーション。
 
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
+use async_trait::async_trait;
+
 use sqlx::sqlite::SqlitePool;
+use sqlx::Connection;
 use tokio::sync::Mutex;
 
 use super::error_handler::ErrorReporter;
-use super::{BatchCommandsApp, TauriBatchCommandsApp};
-use crate::batch_runner::{BatchProgressEvent, BatchRunner};
-use crate::parsers::EmailRow;
+use super::{
+    batch_log_stream_enabled, low_priority_throttle, BatchCommandsApp, TauriBatchCommandsApp,
+};
+use crate::batch_runner::{BatchInputSource, BatchProgressEvent, BatchRunner};
+use crate::logic::email_parser::{narrow_candidates_by_language, parser_language};
+use crate::logic::language::detect_language;
+use crate::parsers::email_parse_task::{get_candidate_parsers, NO_MATCHING_PARSER_PREFIX};
 use crate::parsers::{
-    EmailParseContext, EmailParseTask, HtmlParseContext, HtmlParseInput, HtmlParseTask,
-    ShopSettingsCache, SurugayaHtmlParseContext, SurugayaHtmlParseInput, SurugayaHtmlParseTask,
-    EMAIL_PARSE_EVENT_NAME, EMAIL_PARSE_TASK_NAME, HTML_PARSE_EVENT_NAME, HTML_PARSE_TASK_NAME,
-    SURUGAYA_HTML_PARSE_EVENT_NAME, SURUGAYA_HTML_PARSE_TASK_NAME,
+    EmailParseContext, EmailParseInput, EmailParseTask, HtmlParseContext, HtmlParseInput,
+    HtmlParseTask, ReparseOrderResult, ShopSettingsCache, SurugayaHtmlParseContext,
+    SurugayaHtmlParseInput, SurugayaHtmlParseTask, EMAIL_PARSE_EVENT_NAME, EMAIL_PARSE_TASK_NAME,
+    HTML_PARSE_EVENT_NAME, HTML_PARSE_TASK_NAME, SURUGAYA_HTML_PARSE_EVENT_NAME,
+    SURUGAYA_HTML_PARSE_TASK_NAME,
+};
+use crate::plugins::{
+    build_registry, derive_shop_domain, find_plugin, DispatchError, VendorPlugin,
 };
 use crate::repository::{
-    ParseRepository, ShopSettingsRepository, SqliteParseRepository, SqliteShopSettingsRepository,
+    diff_order_snapshots, snapshot_orders_in_tx, OrderRestructureReport, ParseRepository,
+    ShopSettingsRepository, SqliteParseRepository, SqliteParseUndoRepository,
+    SqliteShopSettingsRepository,
 };
 
+/// バッチパース直前の注文関連テーブルのスナップショット（`undo_last_parse` で復元）
+pub fn parse_undo_snapshot_path(app_config_dir: &std::path::Path) -> std::path::PathBuf {
+    let filename = if crate::e2e_mocks::is_e2e_mock_mode() {
+        "paa_e2e_parse_undo.db"
+    } else {
+        "paa_parse_undo.db"
+    };
+    app_config_dir.join(filename)
+}
+
 /// メールパースタスクの本体。コマンド・トレイ両方から呼ぶ。
 pub async fn run_batch_parse_task(
     app: tauri::AppHandle,
@@ -51,6 +75,23 @@ async fn run_batch_parse_task_with<A: BatchCommandsApp>(
     let parse_repo = SqliteParseRepository::new(pool.clone());
     let shop_settings_repo = SqliteShopSettingsRepository::new(pool.clone());
 
+    // フルクリアの前に、誤動作時に元に戻せるようスナップショットを取る
+    let snapshot_result = match app.app_config_dir() {
+        Ok(dir) => {
+            SqliteParseUndoRepository::new(pool.clone())
+                .create_snapshot(&parse_undo_snapshot_path(&dir))
+                .await
+        }
+        Err(e) => Err(e),
+    };
+    if let Err(e) = snapshot_result {
+        let msg = format!("Failed to create parse undo snapshot: {}", e);
+        err.report_zero(&msg);
+        parse_state.finish();
+        parse_state.set_error(&e);
+        return;
+    }
+
     log::info!("Clearing order_emails, deliveries, items, and orders tables for fresh parse...");
     if let Err(e) = parse_repo.clear_order_tables().await {
         let msg = format!("Failed to clear order tables: {}", e);
@@ -112,41 +153,17 @@ async fn run_batch_parse_task_with<A: BatchCommandsApp>(
         return;
     }
 
-    let all_unparsed_emails = match parse_repo.get_unparsed_emails(total_email_count).await {
-        Ok(emails) => emails,
+    let unparsed_count = match parse_repo.count_unparsed_emails().await {
+        Ok(count) => count as usize,
         Err(e) => {
-            let msg = format!("Failed to fetch unparsed emails: {}", e);
+            let msg = format!("Failed to count unparsed emails: {}", e);
             err.report(&msg, total_email_count, 0, 0, 0);
             parse_state.finish();
             parse_state.set_error(&e);
             return;
         }
     };
-
-    let inputs: Vec<_> = all_unparsed_emails
-        .into_iter()
-        .map(|row: EmailRow| row.into())
-        .collect();
-    let inputs_len = inputs.len();
-    log::info!("Fetched {} unparsed emails", inputs_len);
-    if !inputs.is_empty() {
-        let first: &crate::parsers::EmailParseInput = &inputs[0];
-        let last = inputs.last().unwrap();
-        log::debug!(
-            "[batch] first email_id={} internal_date={:?} subject={:?}",
-            first.email_id,
-            first.internal_date,
-            first.subject
-        );
-        if inputs_len > 1 {
-            log::debug!(
-                "[batch] last email_id={} internal_date={:?} subject={:?}",
-                last.email_id,
-                last.internal_date,
-                last.subject
-            );
-        }
-    }
+    log::info!("Unparsed emails: {}", unparsed_count);
 
     let task: EmailParseTask<SqliteParseRepository, SqliteShopSettingsRepository> =
         EmailParseTask::new();
@@ -156,20 +173,30 @@ async fn run_batch_parse_task_with<A: BatchCommandsApp>(
         .ok()
         .map(|dir| (std::sync::Arc::new(pool.clone()), dir.join("images")));
 
+    let parse_repo = Arc::new(parse_repo);
     let context = EmailParseContext {
         pool: Arc::new(pool.clone()),
-        parse_repo: Arc::new(parse_repo),
+        parse_repo: Arc::clone(&parse_repo),
         shop_settings_repo: Arc::new(shop_settings_repo),
         shop_settings_cache: Arc::new(Mutex::new(ShopSettingsCache::default())),
         parse_state: Arc::new(parse_state.clone()),
         image_save_ctx,
+        parser_timings: Arc::new(Mutex::new(Vec::new())),
     };
 
-    let runner = BatchRunner::new(task, batch_size, 0);
+    let runner = BatchRunner::new(task, batch_size, 0)
+        .with_log_stream(batch_log_stream_enabled(app))
+        .with_throttle(low_priority_throttle(app))
+        .with_collect_outputs(false);
     let parse_state_for_cancel = parse_state.clone();
+    let source = UnparsedEmailSource {
+        repo: parse_repo,
+        after: None,
+        app_data_dir: app.app_data_dir().ok(),
+    };
 
     match runner
-        .run(app, inputs, &context, || {
+        .run_source(app, unparsed_count, source, &context, || {
             parse_state_for_cancel.is_cancelled()
         })
         .await
@@ -201,9 +228,345 @@ async fn run_batch_parse_task_with<A: BatchCommandsApp>(
         run_html_parse_step(app, &pool, &parse_state, batch_size).await;
     }
 
+    crate::wishlist::reconcile_purchases(app, &pool).await;
+    crate::notification_rules::notify_matched_rules(app, &pool).await;
+
     parse_state.finish();
 }
 
+/// 1 注文に紐づくメールだけを再パースして注文を作り直す
+///
+/// 注文の商品・配送を削除してから、紐づくメールを受信順にバッチパースと同じ候補パーサーで再適用する。
+/// 全体を 1 トランザクションで実行し、1 通もパースできなかった場合は元の状態のまま残す。
+/// `app_data_dir` があれば、同期時に切り詰めた本文は保存済みの元の HTML でパースする。
+pub async fn reparse_order(
+    pool: &SqlitePool,
+    app_data_dir: Option<&Path>,
+    order_id: i64,
+) -> Result<ReparseOrderResult, String> {
+    let parse_repo = SqliteParseRepository::new(pool.clone());
+    let emails = parse_repo.get_order_emails(order_id).await?;
+    if emails.is_empty() {
+        return Err(format!("No emails linked to order {order_id}"));
+    }
+
+    let settings: Vec<(String, String, Option<String>, String)> =
+        SqliteShopSettingsRepository::new(pool.clone())
+            .get_enabled()
+            .await?
+            .into_iter()
+            .map(|s| {
+                (
+                    s.sender_address,
+                    s.parser_type,
+                    s.subject_filters,
+                    s.shop_name,
+                )
+            })
+            .collect();
+    let registry = build_registry();
+
+    let mut tx = pool
+        .begin()
+        .await
+        .map_err(|e| format!("Failed to begin transaction: {e}"))?;
+    SqliteParseRepository::reset_order_in_tx(&mut tx, order_id).await?;
+
+    let mut result = ReparseOrderResult {
+        email_count: emails.len() as i64,
+        parsed_count: 0,
+        failures: Vec::new(),
+    };
+    for row in emails {
+        let input: EmailParseInput = row.with_full_body_html(app_data_dir).into();
+        match dispatch_email_in_tx(&mut tx, &registry, &settings, &input).await {
+            Ok(parser_type) => {
+                log::info!(
+                    "[reparse_order] order_id={} email_id={} parsed with {}",
+                    order_id,
+                    input.email_id,
+                    parser_type
+                );
+                result.parsed_count += 1;
+            }
+            Err(e) => {
+                log::warn!(
+                    "[reparse_order] order_id={} email_id={} failed: {}",
+                    order_id,
+                    input.email_id,
+                    e
+                );
+                result
+                    .failures
+                    .push(format!("email {}: {}", input.email_id, e));
+            }
+        }
+    }
+
+    if result.parsed_count == 0 {
+        // tx を drop してロールバックし、注文を再パース前の状態に戻す
+        return Err(format!(
+            "All emails failed to parse for order {order_id}: {}",
+            result.failures.join("; ")
+        ));
+    }
+
+    tx.commit()
+        .await
+        .map_err(|e| format!("Failed to commit transaction: {e}"))?;
+    Ok(result)
+}
+
+/// 適用前後の差分を検証できる分割・統合系の parser_type
+const RESTRUCTURE_PARSER_TYPES: &[&str] = &[
+    "dmm_merge_complete",
+    "dmm_split_complete",
+    "pbandai_merge_complete",
+];
+
+/// 分割・統合メール 1 通を適用し、適用前後の注文・商品の差分を返す
+///
+/// 同じショップ（別ドメインを含む）の注文を適用前後でスナップショットして比較する。
+/// `dry_run` が true の場合はトランザクションをロールバックし、差分だけを返す。
+/// 既に注文に紐づいているメールは二重適用になるため受け付けない。
+pub async fn verify_order_restructure(
+    pool: &SqlitePool,
+    app_data_dir: Option<&Path>,
+    email_id: i64,
+    dry_run: bool,
+) -> Result<OrderRestructureReport, String> {
+    let row = SqliteParseRepository::new(pool.clone())
+        .get_email(email_id)
+        .await?
+        .ok_or_else(|| format!("Email not found: {email_id}"))?
+        .with_full_body_html(app_data_dir);
+
+    let settings: Vec<(String, String, Option<String>, String)> =
+        SqliteShopSettingsRepository::new(pool.clone())
+            .get_enabled()
+            .await?
+            .into_iter()
+            .filter(|s| RESTRUCTURE_PARSER_TYPES.contains(&s.parser_type.as_str()))
+            .map(|s| {
+                (
+                    s.sender_address,
+                    s.parser_type,
+                    s.subject_filters,
+                    s.shop_name,
+                )
+            })
+            .collect();
+    let registry = build_registry();
+
+    let mut shop_domains: Vec<String> = Vec::new();
+    if let Some(domain) = derive_shop_domain(row.from_address.as_deref()) {
+        for parser_type in RESTRUCTURE_PARSER_TYPES {
+            if let Some(alternates) = find_plugin(&registry, parser_type)
+                .and_then(|plugin| plugin.alternate_domains(&domain))
+            {
+                shop_domains.extend(alternates);
+            }
+        }
+        shop_domains.push(domain);
+    }
+    shop_domains.sort();
+    shop_domains.dedup();
+
+    let mut tx = pool
+        .begin()
+        .await
+        .map_err(|e| format!("Failed to begin transaction: {e}"))?;
+    let linked: Option<i64> =
+        sqlx::query_scalar("SELECT order_id FROM order_emails WHERE email_id = ? LIMIT 1")
+            .bind(email_id)
+            .fetch_optional(tx.as_mut())
+            .await
+            .map_err(|e| format!("Failed to check order_email link: {e}"))?;
+    if let Some(order_id) = linked {
+        return Err(format!(
+            "Email {email_id} is already applied to order {order_id}"
+        ));
+    }
+
+    let before = snapshot_orders_in_tx(&mut tx, &shop_domains).await?;
+    let input: EmailParseInput = row.into();
+    let parser_type = dispatch_email_in_tx(&mut tx, &registry, &settings, &input).await?;
+    let after = snapshot_orders_in_tx(&mut tx, &shop_domains).await?;
+
+    let report = diff_order_snapshots(email_id, &parser_type, dry_run, &before, &after);
+    if dry_run {
+        tx.rollback()
+            .await
+            .map_err(|e| format!("Failed to rollback transaction: {e}"))?;
+    } else {
+        tx.commit()
+            .await
+            .map_err(|e| format!("Failed to commit transaction: {e}"))?;
+    }
+    log::info!(
+        "[verify_order_restructure] email_id={} parser={} dry_run={} added={} removed={} changed={} warnings={}",
+        email_id,
+        parser_type,
+        dry_run,
+        report.added_orders.len(),
+        report.removed_orders.len(),
+        report.changed_orders.len(),
+        report.warnings.len()
+    );
+    Ok(report)
+}
+
+/// 1 通のメールを候補パーサーで順に dispatch する（成功したパーサー種別を返す）
+///
+/// パーサー試行ごとにセーブポイントを張り、パース失敗時はその試行分だけ巻き戻す。
+async fn dispatch_email_in_tx(
+    tx: &mut sqlx::Transaction<'_, sqlx::Sqlite>,
+    registry: &[Box<dyn VendorPlugin>],
+    settings: &[(String, String, Option<String>, String)],
+    input: &EmailParseInput,
+) -> Result<String, String> {
+    let candidate_parsers = get_candidate_parsers(
+        settings,
+        input.from_address.as_deref(),
+        input.subject.as_deref(),
+    );
+    let candidate_parsers = narrow_candidates_by_language(
+        candidate_parsers,
+        detect_language(&input.body_plain),
+        |(parser_type, _)| parser_language(registry, parser_type),
+    );
+    if candidate_parsers.is_empty() {
+        return Err(format!(
+            "{} (from: {:?})",
+            NO_MATCHING_PARSER_PREFIX, input.from_address
+        ));
+    }
+
+    let mut last_error = String::new();
+    for (parser_type, shop_name) in &candidate_parsers {
+        let Some(plugin) = find_plugin(registry, parser_type) else {
+            last_error = format!("No plugin for parser_type: {}", parser_type);
+            continue;
+        };
+        let body = if plugin.prefer_plain_text() {
+            &input.body_plain_raw
+        } else {
+            &input.body_plain
+        };
+
+        let mut savepoint = tx
+            .begin()
+            .await
+            .map_err(|e| format!("Failed to begin savepoint: {e}"))?;
+        match plugin
+            .dispatch(
+                parser_type,
+                input.email_id,
+                input.from_address.as_deref(),
+                shop_name,
+                input.internal_date,
+                body,
+                &mut savepoint,
+            )
+            .await
+        {
+            Ok(_) => {
+                savepoint
+                    .commit()
+                    .await
+                    .map_err(|e| format!("Failed to release savepoint: {e}"))?;
+                return Ok(parser_type.clone());
+            }
+            Err(DispatchError::ParseFailed(e)) => last_error = e,
+            Err(DispatchError::SaveFailed(e)) => return Err(format!("Save failed: {e}")),
+        }
+    }
+
+    Err(last_error)
+}
+
+/// 未パースのメールを受信順にページ単位で読み込む（本文を全件メモリに載せない）
+struct UnparsedEmailSource<R: ParseRepository> {
+    repo: Arc<R>,
+    after: Option<(i64, i64)>,
+    /// 同期時に切り詰めた本文の元の HTML を読み込むディレクトリ
+    app_data_dir: Option<PathBuf>,
+}
+
+#[async_trait]
+impl<R: ParseRepository + 'static> BatchInputSource<EmailParseInput> for UnparsedEmailSource<R> {
+    async fn next_page(&mut self, limit: usize) -> Result<Vec<EmailParseInput>, String> {
+        let rows = self
+            .repo
+            .get_unparsed_emails_after(self.after, limit)
+            .await?;
+        if let Some(last) = rows.last() {
+            self.after = Some((last.internal_date.unwrap_or(0), last.email_id));
+            log::debug!(
+                "[batch] loaded {} emails up to email_id={} internal_date={:?}",
+                rows.len(),
+                last.email_id,
+                last.internal_date
+            );
+        }
+        Ok(rows
+            .into_iter()
+            .map(|row| EmailParseInput::from(row.with_full_body_html(self.app_data_dir.as_deref())))
+            .collect())
+    }
+}
+
+/// 駿河屋マイページ HTML の対象条件
+const SURUGAYA_HTML_CONDITION: &str = "url LIKE 'https://www.suruga-ya.jp/pcmypage/%'";
+
+/// Amazon 注文詳細 HTML の対象条件
+const AMAZON_HTML_CONDITION: &str =
+    "url LIKE 'https://www.amazon.co.jp/your-orders/order-details%' \
+     OR url LIKE 'https://www.amazon.co.jp/gp/your-account/order-details%'";
+
+async fn count_stored_html(pool: &SqlitePool, condition: &str) -> Result<usize, String> {
+    let sql =
+        format!("SELECT COUNT(*) FROM htmls WHERE html_content IS NOT NULL AND ({condition})");
+    let count: i64 = sqlx::query_scalar(&sql)
+        .fetch_one(pool)
+        .await
+        .map_err(|e| format!("Failed to count html targets: {e}"))?;
+    Ok(count as usize)
+}
+
+/// 保存済み HTML を id 順にページ単位で読み込む（`html_content` を全件メモリに載せない）
+struct StoredHtmlSource<I> {
+    pool: SqlitePool,
+    condition: &'static str,
+    after_id: i64,
+    to_input: fn(i64, String, String) -> I,
+}
+
+#[async_trait]
+impl<I: Send> BatchInputSource<I> for StoredHtmlSource<I> {
+    async fn next_page(&mut self, limit: usize) -> Result<Vec<I>, String> {
+        let sql = format!(
+            "SELECT id, url, html_content FROM htmls \
+             WHERE html_content IS NOT NULL AND ({}) AND id > ? \
+             ORDER BY id LIMIT ?",
+            self.condition
+        );
+        let rows: Vec<(i64, String, String)> = sqlx::query_as(&sql)
+            .bind(self.after_id)
+            .bind(limit as i64)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| format!("Failed to fetch html targets: {e}"))?;
+        if let Some((id, _, _)) = rows.last() {
+            self.after_id = *id;
+        }
+        Ok(rows
+            .into_iter()
+            .map(|(html_id, url, html_content)| (self.to_input)(html_id, url, html_content))
+            .collect())
+    }
+}
+
 /// 駿河屋マイページ HTML のパースステップ
 async fn run_surugaya_html_parse_step<A: BatchCommandsApp>(
     app: &A,
@@ -211,23 +574,15 @@ async fn run_surugaya_html_parse_step<A: BatchCommandsApp>(
     parse_state: &crate::parsers::ParseState,
     batch_size: usize,
 ) {
-    let targets: Vec<(i64, String, String)> = match sqlx::query_as(
-        "SELECT id, url, html_content FROM htmls \
-         WHERE html_content IS NOT NULL \
-           AND url LIKE 'https://www.suruga-ya.jp/pcmypage/%' \
-         ORDER BY id",
-    )
-    .fetch_all(pool)
-    .await
-    {
-        Ok(rows) => rows,
+    let target_count = match count_stored_html(pool, SURUGAYA_HTML_CONDITION).await {
+        Ok(count) => count,
         Err(e) => {
-            log::error!("[surugaya_html_parse] Failed to fetch html targets: {e}");
+            log::error!("[surugaya_html_parse] Failed to count html targets: {e}");
             return;
         }
     };
 
-    if targets.is_empty() {
+    if target_count == 0 {
         log::info!("[surugaya_html_parse] No stored HTML to parse");
         let complete_event = BatchProgressEvent::complete(
             SURUGAYA_HTML_PARSE_TASK_NAME,
@@ -240,25 +595,32 @@ async fn run_surugaya_html_parse_step<A: BatchCommandsApp>(
         return;
     }
 
-    log::info!("[surugaya_html_parse] {} HTML(s) to parse", targets.len());
+    log::info!("[surugaya_html_parse] {} HTML(s) to parse", target_count);
 
-    let inputs: Vec<SurugayaHtmlParseInput> = targets
-        .into_iter()
-        .map(|(html_id, url, html_content)| SurugayaHtmlParseInput {
+    let source = StoredHtmlSource {
+        pool: pool.clone(),
+        condition: SURUGAYA_HTML_CONDITION,
+        after_id: 0,
+        to_input: |html_id, url, html_content| SurugayaHtmlParseInput {
             html_id,
             url,
             html_content,
-        })
-        .collect();
+        },
+    };
 
     let task = SurugayaHtmlParseTask;
     let context = SurugayaHtmlParseContext {
         pool: Arc::new(pool.clone()),
     };
-    let runner = BatchRunner::new(task, batch_size, 0);
+    let runner = BatchRunner::new(task, batch_size, 0)
+        .with_log_stream(batch_log_stream_enabled(app))
+        .with_throttle(low_priority_throttle(app))
+        .with_collect_outputs(false);
 
     match runner
-        .run(app, inputs, &context, || parse_state.is_cancelled())
+        .run_source(app, target_count, source, &context, || {
+            parse_state.is_cancelled()
+        })
         .await
     {
         Ok(result) => {
@@ -281,24 +643,15 @@ async fn run_html_parse_step<A: BatchCommandsApp>(
     parse_state: &crate::parsers::ParseState,
     batch_size: usize,
 ) {
-    let targets: Vec<(i64, String, String)> = match sqlx::query_as(
-        "SELECT id, url, html_content FROM htmls \
-         WHERE html_content IS NOT NULL \
-           AND (url LIKE 'https://www.amazon.co.jp/your-orders/order-details%' \
-             OR url LIKE 'https://www.amazon.co.jp/gp/your-account/order-details%') \
-         ORDER BY id",
-    )
-    .fetch_all(pool)
-    .await
-    {
-        Ok(rows) => rows,
+    let target_count = match count_stored_html(pool, AMAZON_HTML_CONDITION).await {
+        Ok(count) => count,
         Err(e) => {
-            log::error!("[html_parse] Failed to fetch html targets: {e}");
+            log::error!("[html_parse] Failed to count html targets: {e}");
             return;
         }
     };
 
-    if targets.is_empty() {
+    if target_count == 0 {
         log::info!("[html_parse] No stored HTML to parse");
         let complete_event = BatchProgressEvent::complete(
             HTML_PARSE_TASK_NAME,
@@ -311,25 +664,32 @@ async fn run_html_parse_step<A: BatchCommandsApp>(
         return;
     }
 
-    log::info!("[html_parse] {} HTML(s) to parse", targets.len());
+    log::info!("[html_parse] {} HTML(s) to parse", target_count);
 
-    let inputs: Vec<HtmlParseInput> = targets
-        .into_iter()
-        .map(|(html_id, url, html_content)| HtmlParseInput {
+    let source = StoredHtmlSource {
+        pool: pool.clone(),
+        condition: AMAZON_HTML_CONDITION,
+        after_id: 0,
+        to_input: |html_id, url, html_content| HtmlParseInput {
             html_id,
             url,
             html_content,
-        })
-        .collect();
+        },
+    };
 
     let task = HtmlParseTask;
     let context = HtmlParseContext {
         pool: Arc::new(pool.clone()),
     };
-    let runner = BatchRunner::new(task, batch_size, 0);
+    let runner = BatchRunner::new(task, batch_size, 0)
+        .with_log_stream(batch_log_stream_enabled(app))
+        .with_throttle(low_priority_throttle(app))
+        .with_collect_outputs(false);
 
     match runner
-        .run(app, inputs, &context, || parse_state.is_cancelled())
+        .run_source(app, target_count, source, &context, || {
+            parse_state.is_cancelled()
+        })
         .await
     {
         Ok(result) => {
@@ -6,7 +6,7 @@ use sqlx::sqlite::SqlitePool;
 use tokio::sync::Mutex;
 
 use super::error_handler::ErrorReporter;
-use super::{BatchCommandsApp, TauriBatchCommandsApp};
+use super::{throttle_from_config, BatchCommandsApp, TauriBatchCommandsApp};
 use crate::batch_runner::{BatchProgressEvent, BatchRunner};
 use crate::commands::ProductNameParseState;
 use crate::config;
@@ -70,6 +70,15 @@ async fn run_product_name_parse_task_with<A: BatchCommandsApp>(
         }
     };
 
+    let config = app
+        .app_config_dir()
+        .ok()
+        .and_then(|dir| config::load(&dir).ok())
+        .unwrap_or_else(|| {
+            log::warn!("Failed to load config, using Gemini defaults");
+            config::AppConfig::default()
+        });
+
     let gemini_client = if is_e2e_mock_mode() {
         log::info!("Using E2E mock Gemini client");
         GeminiClientForE2E::Mock(crate::e2e_mocks::E2EMockGeminiClient)
@@ -82,7 +91,10 @@ async fn run_product_name_parse_task_with<A: BatchCommandsApp>(
         }
         match crate::gemini::load_api_key(&app_data_dir) {
             Ok(api_key) => match GeminiClient::new(api_key) {
-                Ok(client) => GeminiClientForE2E::Real(Box::new(client)),
+                Ok(client) => GeminiClientForE2E::Real(Box::new(client.with_rate_limits(
+                    config.gemini.rpm_limit.clamp(1, 10_000) as u32,
+                    config.gemini.tpm_limit.clamp(1_000, 100_000_000) as u32,
+                ))),
                 Err(e) => {
                     err.report_zero(&format!("Failed to create Gemini client: {}", e));
                     return;
@@ -160,14 +172,6 @@ async fn run_product_name_parse_task_with<A: BatchCommandsApp>(
         .map(|(raw_name, platform_hint)| create_product_parse_input(raw_name, platform_hint))
         .collect();
 
-    let config = app
-        .app_config_dir()
-        .ok()
-        .and_then(|dir| config::load(&dir).ok())
-        .unwrap_or_else(|| {
-            log::warn!("Failed to load config, using Gemini defaults");
-            config::AppConfig::default()
-        });
     let gemini_batch_size = (config.gemini.batch_size.clamp(1, 50)) as usize;
     let gemini_delay_ms = (config.gemini.delay_seconds.clamp(0, 60)) as u64 * 1000;
 
@@ -179,7 +183,10 @@ async fn run_product_name_parse_task_with<A: BatchCommandsApp>(
         cache: Arc::new(Mutex::new(ProductNameParseCache::default())),
     };
 
-    let runner = BatchRunner::new(task, gemini_batch_size, gemini_delay_ms);
+    let runner = BatchRunner::new(task, gemini_batch_size, gemini_delay_ms)
+        .with_log_stream(config.debug.batch_log_stream)
+        .with_throttle(throttle_from_config(&config.low_priority))
+        .with_collect_outputs(false);
 
     match runner.run(app, inputs, &context, || false).await {
         Ok(batch_result) => {
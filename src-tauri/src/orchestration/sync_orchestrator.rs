@@ -6,13 +6,15 @@ use sqlx::sqlite::SqlitePool;
 use tokio::sync::Mutex;
 
 use super::error_handler::ErrorReporter;
-use super::{clamp_batch_size, BatchCommandsApp, TauriBatchCommandsApp};
+use super::{clamp_batch_size, throttle_from_config, BatchCommandsApp, TauriBatchCommandsApp};
 use crate::batch_runner::{BatchProgressEvent, BatchRunner};
 use crate::config;
 use crate::e2e_mocks::GmailClientForE2E;
 use crate::gmail::{
-    create_sync_input, fetch_all_message_ids, GmailSyncContext, GmailSyncTask,
-    ShopSettingsCacheForSync, SyncGuard, SyncState, GMAIL_SYNC_EVENT_NAME, GMAIL_SYNC_TASK_NAME,
+    create_sync_input, fetch_all_message_ids, fetch_message_ids_with_checkpoint, BodyHtmlLimit,
+    GmailSyncContext, GmailSyncTask, NetworkRetryPolicy, ShopSettingsCacheForSync, SyncCheckpoint,
+    SyncCheckpointStore, SyncEstimate, SyncGuard, SyncState, GMAIL_SYNC_EVENT_NAME,
+    GMAIL_SYNC_TASK_NAME,
 };
 use crate::logic::sync_logic;
 use crate::repository::{
@@ -35,6 +37,14 @@ enum TryStartPolicy {
     CallerDidTryStart,
 }
 
+/// 通知本文に警告があれば改行して付け加える
+fn with_warning(body: &str, warning: &Option<String>) -> String {
+    match warning {
+        Some(warning) => format!("{body}\n{warning}"),
+        None => body.to_string(),
+    }
+}
+
 /// DB内の最新 internal_date から差分同期の after_date を計算する。
 /// 安全マージンとして1日（86,400,000ms）前にずらし、RFC3339形式で返す。
 /// タイムスタンプが無効な場合は None を返す。
@@ -44,6 +54,104 @@ pub(crate) fn compute_incremental_after_date(latest_ts: i64) -> Option<String> {
     chrono::DateTime::from_timestamp_millis(safe_ts).map(|dt| dt.to_rfc3339())
 }
 
+/// 差分同期の after_date をDB内の最新 internal_date から求める。
+/// DBが空・取得失敗・タイムスタンプ不正の場合は None（全件同期にフォールバック）。
+async fn resolve_incremental_after_date(email_repo: &SqliteEmailRepository) -> Option<String> {
+    match email_repo.get_latest_internal_date().await {
+        Ok(Some(ts)) => {
+            // 安全マージンとして1日（86,400,000ms）前にずらす（Gmail API の after: は日単位のため）
+            match compute_incremental_after_date(ts) {
+                Some(rfc) => {
+                    log::info!(
+                        "Incremental sync: using after_date={} (original latest={})",
+                        rfc,
+                        ts
+                    );
+                    Some(rfc)
+                }
+                None => {
+                    log::warn!("Invalid latest internal_date {ts}, falling back to full sync");
+                    None
+                }
+            }
+        }
+        Ok(None) => {
+            log::info!("No existing emails in DB, falling back to full sync");
+            None
+        }
+        Err(e) => {
+            log::warn!("Failed to get latest internal_date: {e}, falling back to full sync");
+            None
+        }
+    }
+}
+
+/// 同期のドライラン。メールの取得・保存は行わず、同期対象メール数と推定所要時間のみ返す。
+/// 同期中でも実行できる（SyncState には触れない）。
+pub async fn estimate_sync(
+    app: tauri::AppHandle,
+    pool: SqlitePool,
+    incremental: bool,
+) -> Result<SyncEstimate, String> {
+    let app = TauriBatchCommandsApp { app };
+    estimate_sync_with(&app, pool, incremental).await
+}
+
+async fn estimate_sync_with<A: BatchCommandsApp>(
+    app: &A,
+    pool: SqlitePool,
+    incremental: bool,
+) -> Result<SyncEstimate, String> {
+    let email_repo = SqliteEmailRepository::new(pool.clone());
+    let shop_repo = SqliteShopSettingsRepository::new(pool);
+
+    let sender_addresses: Vec<String> = shop_repo
+        .get_enabled()
+        .await
+        .map_err(|e| format!("Failed to fetch shop settings: {e}"))?
+        .into_iter()
+        .map(|s| s.sender_address)
+        .collect();
+
+    let app_config_dir = app.app_config_dir()?;
+    let config = config::load(&app_config_dir).unwrap_or_else(|e| {
+        log::error!("Failed to load config: {}", e);
+        config::AppConfig::default()
+    });
+
+    let gmail_client = app
+        .create_gmail_client()
+        .await
+        .map_err(|e| format!("Failed to create Gmail client: {e}"))?;
+
+    let after_date = if incremental {
+        resolve_incremental_after_date(&email_repo).await
+    } else {
+        None
+    };
+
+    let query = sync_logic::build_sync_query(&sender_addresses, &None, &after_date);
+    let max_results = (config.sync.max_results_per_page.clamp(1, 500)) as u32;
+    let all_ids = fetch_all_message_ids(&gmail_client, &query, max_results, None)
+        .await
+        .map_err(|e| format!("Failed to fetch message IDs: {e}"))?;
+    let new_ids = email_repo
+        .filter_new_message_ids(&all_ids)
+        .await
+        .map_err(|e| format!("Failed to filter new message IDs: {e}"))?;
+
+    let new_message_count = new_ids.len() as i64;
+    Ok(SyncEstimate {
+        incremental: after_date.is_some(),
+        matched_message_count: all_ids.len() as i64,
+        new_message_count,
+        estimated_seconds: sync_logic::estimate_sync_seconds(
+            new_message_count,
+            config.sync.save_raw_eml,
+        ),
+    })
+}
+
 /// Gmail全件同期タスクの本体。コマンド・トレイ両方から呼ぶ。
 pub async fn run_sync_task(app: tauri::AppHandle, pool: SqlitePool, sync_state: SyncState) {
     let app = TauriBatchCommandsApp { app };
@@ -162,52 +270,74 @@ async fn run_sync_core<A: BatchCommandsApp>(
 
     // 差分同期の場合、DB内の最新 internal_date を起点にする
     let after_date = if mode == SyncMode::Incremental {
-        match email_repo.get_latest_internal_date().await {
-            Ok(Some(ts)) => {
-                // 安全マージンとして1日（86,400,000ms）前にずらす（Gmail API の after: は日単位のため）
-                match compute_incremental_after_date(ts) {
-                    Some(rfc) => {
-                        log::info!(
-                            "Incremental sync: using after_date={} (original latest={})",
-                            rfc,
-                            ts
-                        );
-                        Some(rfc)
-                    }
-                    None => {
-                        log::warn!("Invalid latest internal_date {ts}, falling back to full sync");
-                        None
-                    }
-                }
-            }
-            Ok(None) => {
-                log::info!("No existing emails in DB, falling back to full sync");
-                None
-            }
-            Err(e) => {
-                log::warn!("Failed to get latest internal_date: {e}, falling back to full sync");
-                None
-            }
-        }
+        resolve_incremental_after_date(&email_repo).await
     } else {
         None
     };
 
     let query = sync_logic::build_sync_query(&sender_addresses, &None, &after_date);
     let max_results = (config.sync.max_results_per_page.clamp(1, 500)) as u32;
+    let network_retry = NetworkRetryPolicy::default();
 
-    let all_ids = match fetch_all_message_ids(&gmail_client, &query, max_results, None).await {
-        Ok(ids) => ids,
-        Err(e) => {
-            let msg = format!("Failed to fetch message IDs: {}", e);
-            err.report_zero(&msg);
-            sync_state.set_error(&e);
-            return;
+    // 中断した同期のチェックポイントがあれば、そのクエリ・ページ位置・処理済みIDから再開する
+    let full_sync = mode == SyncMode::Full;
+    let now_ms = chrono::Utc::now().timestamp_millis();
+    let mut checkpoint = match SyncCheckpoint::load_resumable(&app_config_dir, full_sync, now_ms) {
+        Some(checkpoint) => {
+            log::info!(
+                    "Resuming interrupted sync ({mode_label}): {} IDs fetched, {} processed, listing_complete={}",
+                    checkpoint.message_ids.len(),
+                    checkpoint.processed_ids.len(),
+                    checkpoint.listing_complete
+                );
+            checkpoint
         }
+        None => SyncCheckpoint::new(query, full_sync),
     };
 
+    if let Err(e) = fetch_message_ids_with_checkpoint(
+        &gmail_client,
+        max_results,
+        &mut checkpoint,
+        &network_retry,
+        |checkpoint| {
+            if let Err(e) = checkpoint.save(&app_config_dir) {
+                log::warn!("Failed to save sync checkpoint: {e}");
+            }
+        },
+    )
+    .await
+    {
+        // チェックポイントは残し、次回の同期で続きのページから再開する
+        let msg = format!("Failed to fetch message IDs: {}", e);
+        err.report_zero(&msg);
+        sync_state.set_error(&e);
+        return;
+    }
+    let all_ids = checkpoint.pending_ids();
+
+    // 迷惑メール・プロモーションに振り分けられた対象メールを数えて警告する（失敗しても同期は続ける）
+    let misfiled_warning =
+        match sync_logic::count_misfiled_shop_mail(&gmail_client, &sender_addresses, &after_date)
+            .await
+        {
+            Ok(counts) => {
+                let warning = sync_logic::misfiled_warning_message(&counts);
+                if let Some(warning) = &warning {
+                    log::warn!("{warning} ({counts:?})");
+                }
+                sync_state.set_misfiled_shop_mail(Some(counts));
+                warning
+            }
+            Err(e) => {
+                log::warn!("Failed to count shop mail in spam/promotions: {e}");
+                sync_state.set_misfiled_shop_mail(None);
+                None
+            }
+        };
+
     log::info!(
-        "Fetched {} message IDs from Gmail ({mode_label})",
+        "Fetched {} unprocessed message IDs from Gmail ({mode_label})",
         all_ids.len()
     );
 
@@ -228,6 +358,9 @@ async fn run_sync_core<A: BatchCommandsApp>(
 
     if new_ids.is_empty() {
         log::info!("No new messages to sync ({mode_label})");
+        if let Err(e) = SyncCheckpoint::clear(&app_config_dir) {
+            log::warn!("{e}");
+        }
         let complete_event = BatchProgressEvent::complete(
             GMAIL_SYNC_TASK_NAME,
             0,
@@ -236,10 +369,14 @@ async fn run_sync_core<A: BatchCommandsApp>(
             "同期対象の新規メッセージがありません".to_string(),
         );
         app.emit_event(GMAIL_SYNC_EVENT_NAME, complete_event);
-        app.notify("Gmail同期完了", "新規メッセージはありませんでした");
+        app.notify(
+            "Gmail同期完了",
+            &with_warning("新規メッセージはありませんでした", &misfiled_warning),
+        );
         return;
     }
 
+    // メッセージ ID はチェックポイント用に全件保持するが、本文は 1 バッチずつ取得・保存・解放する
     let inputs: Vec<_> = new_ids.into_iter().map(create_sync_input).collect();
     let total_items = inputs.len();
 
@@ -249,15 +386,34 @@ async fn run_sync_core<A: BatchCommandsApp>(
         SqliteShopSettingsRepository,
     >::new();
 
+    let checkpoint_store = Arc::new(SyncCheckpointStore::new(app_config_dir.clone(), checkpoint));
+    // app_data_dir が取れない場合は元の HTML をファイル保存せず切り詰めのみ行う
+    let app_data_dir = match app.app_data_dir() {
+        Ok(dir) => Some(dir),
+        Err(e) => {
+            log::warn!("Failed to get app data dir: {e}");
+            None
+        }
+    };
+    let body_html_limit = BodyHtmlLimit::from_config(&config.sync, app_data_dir.as_deref());
     let context = GmailSyncContext {
         gmail_client: Arc::new(gmail_client),
         email_repo: Arc::new(email_repo),
         shop_settings_repo: Arc::new(shop_repo),
         shop_settings_cache: Arc::new(Mutex::new(ShopSettingsCacheForSync::default())),
+        save_raw_eml: config.sync.save_raw_eml,
+        body_html_limit,
+        checkpoint: Some(checkpoint_store.clone()),
+        network_retry,
     };
 
     let timeout_minutes = config.sync.timeout_minutes.clamp(1, 120);
-    let runner = BatchRunner::new(task, batch_size, 0).with_timeout(timeout_minutes as u64);
+    let runner = BatchRunner::new(task, batch_size, 0)
+        .with_timeout(timeout_minutes as u64)
+        .with_log_stream(config.debug.batch_log_stream)
+        .with_throttle(throttle_from_config(&config.low_priority))
+        // 取得したメッセージ本文は保存後すぐ手放す（全件分を結果として保持しない）
+        .with_collect_outputs(false);
     let sync_state_for_cancel = sync_state.clone();
 
     match runner
@@ -273,11 +429,18 @@ async fn run_sync_core<A: BatchCommandsApp>(
                 batch_result.failed_count
             );
             if !sync_state.should_stop() {
+                // 最後まで処理したのでチェックポイントは不要（キャンセル時は次回再開用に残す）
+                if let Err(e) = checkpoint_store.clear() {
+                    log::warn!("{e}");
+                }
                 let notification_body = format!(
                     "同期完了：新たに{}件のメールを取り込みました",
                     batch_result.success_count
                 );
-                app.notify("Gmail同期完了", &notification_body);
+                app.notify(
+                    "Gmail同期完了",
+                    &with_warning(&notification_body, &misfiled_warning),
+                );
             }
         }
         Err(e) => {
@@ -493,6 +656,56 @@ mod tests {
         assert_eq!(app.notify_count.load(Ordering::SeqCst), 1);
     }
 
+    #[tokio::test]
+    async fn estimate_sync_falls_back_to_full_and_returns_zero_when_no_messages() {
+        let pool = create_pool().await;
+        create_shop_settings_table(&pool).await;
+        insert_enabled_shop(&pool).await;
+        create_emails_table(&pool).await;
+
+        let tmp = TempDir::new().unwrap();
+        let app = FakeApp {
+            config_dir: tmp.path().to_path_buf(),
+            data_dir: Some(tmp.path().to_path_buf()),
+            emitted_events: std::sync::Mutex::new(Vec::new()),
+            notify_count: std::sync::atomic::AtomicUsize::new(0),
+            fail_create_gmail_client: false,
+        };
+
+        let estimate = estimate_sync_with(&app, pool, true).await.unwrap();
+        assert_eq!(
+            estimate,
+            SyncEstimate {
+                incremental: false,
+                matched_message_count: 0,
+                new_message_count: 0,
+                estimated_seconds: 0,
+            }
+        );
+        // ドライランなのでイベント・通知は発生しない
+        assert!(app.emitted_events.lock().unwrap().is_empty());
+        assert_eq!(app.notify_count.load(Ordering::SeqCst), 0);
+    }
+
+    #[tokio::test]
+    async fn estimate_sync_returns_error_when_gmail_client_factory_fails() {
+        let pool = create_pool().await;
+        create_shop_settings_table(&pool).await;
+        insert_enabled_shop(&pool).await;
+
+        let tmp = TempDir::new().unwrap();
+        let app = FakeApp {
+            config_dir: tmp.path().to_path_buf(),
+            data_dir: Some(tmp.path().to_path_buf()),
+            emitted_events: std::sync::Mutex::new(Vec::new()),
+            notify_count: std::sync::atomic::AtomicUsize::new(0),
+            fail_create_gmail_client: true,
+        };
+
+        let result = estimate_sync_with(&app, pool, false).await;
+        assert!(result.is_err());
+    }
+
     // ==================== compute_incremental_after_date Tests ====================
 
     #[test]
@@ -11,13 +11,15 @@
 
 use sqlx::sqlite::SqlitePool;
 use tauri::Emitter;
+use ts_rs::TS;
 
 use super::pipeline_steps::{
     run_delivery_check_step, run_parse_step, run_product_parse_step, StepOutcome,
 };
 
 /// 各ステップの名前（`full-parse:step_started` イベントのペイロード）
-#[derive(Debug, Clone, serde::Serialize)]
+#[derive(Debug, Clone, serde::Serialize, TS)]
+#[ts(export)]
 #[serde(rename_all = "snake_case")]
 pub enum PipelineStep {
     Parse,
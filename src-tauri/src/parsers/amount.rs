@@ -0,0 +1,69 @@
+//! 金額表記の解析（全店舗共通）
+//!
+//! 日本語メールの `¥1,234` / `1,234円` / `-500円`、英語メールの `¥1,234` / `JPY 1,234` /
+//! `1,234.00 JPY` を円単位の整数にする。英語メールは円でも小数点以下（`.00`）を付けることがあるため、
+//! 言語ごとに受け付ける表記を分けている（日本語メールの `.` は小数点として扱わない）。
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+use crate::logic::language::EmailLanguage;
+
+/// 日本語表記: `-¥1,234` / `￥1,234` / `1,234円`
+static JAPANESE_AMOUNT_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"([-−])?\s*[¥￥]?\s*([-−])?\s*(\d{1,3}(?:,\d{3})+|\d+)\s*円?")
+        .expect("Invalid JAPANESE_AMOUNT_RE")
+});
+
+/// 英語表記: `-¥1,234` / `JPY 1,234.00` / `1,234 JPY`
+static ENGLISH_AMOUNT_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?i)(-)?\s*(?:JPY|JP¥|[¥￥])?\s*(-)?\s*(\d{1,3}(?:,\d{3})+|\d+)(?:\.(\d{1,2}))?")
+        .expect("Invalid ENGLISH_AMOUNT_RE")
+});
+
+/// テキスト中の最初の金額を円単位で返す（見つからなければ None）
+///
+/// 英語表記の小数点以下は四捨五入する。
+pub fn parse_amount(text: &str, language: EmailLanguage) -> Option<i64> {
+    let (caps, fraction) = match language {
+        EmailLanguage::Japanese => (JAPANESE_AMOUNT_RE.captures(text)?, None),
+        EmailLanguage::English => {
+            let caps = ENGLISH_AMOUNT_RE.captures(text)?;
+            let fraction = caps.get(4).map(|m| format!("{:0<2}", m.as_str()));
+            (caps, fraction)
+        }
+    };
+
+    let negative = caps.get(1).is_some() || caps.get(2).is_some();
+    let mut amount: i64 = caps[3].replace(',', "").parse().ok()?;
+    if fraction.is_some_and(|f| f.as_str() >= "50") {
+        amount += 1;
+    }
+    Some(if negative { -amount } else { amount })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_amount_japanese() {
+        let ja = EmailLanguage::Japanese;
+        assert_eq!(parse_amount("¥1,234", ja), Some(1234));
+        assert_eq!(parse_amount("12,100円(税込)", ja), Some(12100));
+        assert_eq!(parse_amount("-500円", ja), Some(-500));
+        assert_eq!(parse_amount("￥-1,000", ja), Some(-1000));
+        assert_eq!(parse_amount("なし", ja), None);
+    }
+
+    #[test]
+    fn test_parse_amount_english() {
+        let en = EmailLanguage::English;
+        assert_eq!(parse_amount("¥4,180", en), Some(4180));
+        assert_eq!(parse_amount("JPY 23,980.00", en), Some(23980));
+        assert_eq!(parse_amount("1,200 JPY", en), Some(1200));
+        assert_eq!(parse_amount("jpy1,000.5", en), Some(1001));
+        assert_eq!(parse_amount("-¥500", en), Some(-500));
+        assert_eq!(parse_amount("Free", en), None);
+    }
+}
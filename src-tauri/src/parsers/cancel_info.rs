@@ -1,9 +1,125 @@
 //! キャンセルメールから抽出した情報（全店舗共通）
 
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+/// キャンセル理由の分類（orders.cancel_reason / cancelled_items.cancel_reason に保存）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(export)]
+pub enum CancelReason {
+    /// ユーザー都合（購入者からのキャンセル依頼）
+    UserRequest,
+    /// 在庫なし・入荷未定
+    OutOfStock,
+    /// 発売中止・生産中止
+    Discontinued,
+    /// その他
+    Other,
+}
+
+impl CancelReason {
+    /// DB に保存する値
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::UserRequest => "user_request",
+            Self::OutOfStock => "out_of_stock",
+            Self::Discontinued => "discontinued",
+            Self::Other => "other",
+        }
+    }
+
+    /// DB の値から変換する（未知の値は None）
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "user_request" => Some(Self::UserRequest),
+            "out_of_stock" => Some(Self::OutOfStock),
+            "discontinued" => Some(Self::Discontinued),
+            "other" => Some(Self::Other),
+            _ => None,
+        }
+    }
+}
+
+/// メールに記載されたキャンセル理由の文言を分類する
+///
+/// 「入荷未定により発売中止」のように複数該当する場合は、発売中止 → 在庫なし → ユーザー都合 の順で優先する。
+pub fn classify_cancel_reason(text: &str) -> CancelReason {
+    const DISCONTINUED: &[&str] = &["発売中止", "販売中止", "生産中止", "製造中止"];
+    const OUT_OF_STOCK: &[&str] = &[
+        "入荷未定",
+        "入荷の見込み",
+        "入荷数",
+        "在庫",
+        "品切",
+        "欠品",
+        "確保でき",
+        "完売",
+    ];
+    const USER_REQUEST: &[&str] = &["ご依頼", "ご要望", "ご都合", "お客様都合"];
+
+    if DISCONTINUED.iter().any(|k| text.contains(k)) {
+        CancelReason::Discontinued
+    } else if OUT_OF_STOCK.iter().any(|k| text.contains(k)) {
+        CancelReason::OutOfStock
+    } else if USER_REQUEST.iter().any(|k| text.contains(k)) {
+        CancelReason::UserRequest
+    } else {
+        CancelReason::Other
+    }
+}
+
 /// キャンセルメールから抽出した情報
 #[derive(Debug, Clone)]
 pub struct CancelInfo {
     pub order_number: String,
     pub product_name: String,
     pub cancel_quantity: i64,
+    /// キャンセル理由の分類（メールから判別できない場合は None）
+    pub reason: Option<CancelReason>,
+    /// メールに記載されたキャンセル理由（原文。記載がない場合は None）
+    pub reason_detail: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_classify_cancel_reason() {
+        assert_eq!(
+            classify_cancel_reason("メーカーにて発売中止となったため"),
+            CancelReason::Discontinued
+        );
+        assert_eq!(
+            classify_cancel_reason("メーカーからの入荷の見込みが立たないため"),
+            CancelReason::OutOfStock
+        );
+        assert_eq!(
+            classify_cancel_reason("入荷未定により発売中止"),
+            CancelReason::Discontinued
+        );
+        assert_eq!(
+            classify_cancel_reason("お客様のご依頼によるキャンセル"),
+            CancelReason::UserRequest
+        );
+        assert_eq!(classify_cancel_reason("システム都合"), CancelReason::Other);
+    }
+
+    #[test]
+    fn test_cancel_reason_round_trip() {
+        for reason in [
+            CancelReason::UserRequest,
+            CancelReason::OutOfStock,
+            CancelReason::Discontinued,
+            CancelReason::Other,
+        ] {
+            assert_eq!(CancelReason::parse(reason.as_str()), Some(reason));
+            assert_eq!(
+                serde_json::to_string(&reason).unwrap(),
+                format!("\"{}\"", reason.as_str())
+            );
+        }
+        assert_eq!(CancelReason::parse("unknown"), None);
+    }
 }
@@ -0,0 +1,25 @@
+//! 発売延期メールから抽出した情報（全店舗共通）
+//!
+//! 発売延期の通知は注文金額・数量が変わらないため `OrderInfo` ではなく `DelayInfo` で扱い、
+//! 既存注文の商品（items）の発売予定だけを更新する。
+
+use super::release_date::FuzzyReleaseDate;
+
+/// 発売延期メールから抽出した情報
+#[derive(Debug, Clone)]
+pub struct DelayInfo {
+    pub order_number: String,
+    /// 延期対象の商品（1通に複数商品が含まれる場合がある）
+    pub items: Vec<DelayedItem>,
+}
+
+/// 発売延期の対象商品
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DelayedItem {
+    /// 商品名（空文字の場合は注文内の全商品が対象）
+    pub product_name: String,
+    /// 変更前の発売予定（記載がない場合は None）
+    pub old_release_date: Option<FuzzyReleaseDate>,
+    /// 変更後の発売予定
+    pub new_release_date: FuzzyReleaseDate,
+}
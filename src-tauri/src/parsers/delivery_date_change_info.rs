@@ -0,0 +1,11 @@
+//! お届け予定日変更メールから抽出した情報（全店舗共通）
+
+/// お届け予定日変更メールから抽出した情報
+#[derive(Debug, Clone)]
+pub struct DeliveryDateChangeInfo {
+    pub order_number: String,
+    /// 変更前のお届け予定日（`YYYY-MM-DD`、記載がない場合は None）
+    pub old_estimated_delivery: Option<String>,
+    /// 変更後のお届け予定日（`YYYY-MM-DD`）
+    pub new_estimated_delivery: String,
+}
@@ -14,19 +14,24 @@
 //! # フック活用
 //! - `before_batch`: shop_settings の取得（バッチごとにキャッシュ）
 //! - `process_batch`: メールの正規表現パース
-//! - `after_batch`: パース結果のDB保存
+//! - `after_batch`: パース結果のDB保存・shop_settings のパース統計更新・パーサー処理時間の記録
 
 use crate::batch_runner::BatchTask;
-use crate::logic::email_parser::extract_domain;
+use crate::logic::email_parser::{extract_domain, narrow_candidates_by_language, parser_language};
+use crate::logic::language::detect_language;
 use crate::logic::sync_logic::extract_email_address;
 use crate::parsers::{EmailRow, OrderInfo, ParseState};
 use crate::plugins::{
     build_registry, find_plugin, save_images_for_order, DispatchError, DispatchOutcome,
 };
-use crate::repository::{ParseRepository, ShopSettingsRepository};
+use crate::repository::{
+    ParseRepository, ParserTiming, ShopSettingsRepository, SqliteParserMetricsRepository,
+    SqliteShopSettingStatsRepository,
+};
 use async_trait::async_trait;
 use std::marker::PhantomData;
 use std::sync::Arc;
+use std::time::Instant;
 use tokio::sync::Mutex;
 
 /// メールパースタスクの入力
@@ -75,6 +80,10 @@ pub struct EmailParseOutput {
     pub shop_name: String,
     /// ショップドメイン
     pub shop_domain: Option<String>,
+    /// 採用されたパーサー（parser_type）
+    pub parser_type: String,
+    /// 採用された shop_settings の送信元アドレス（パース統計の記録に使う）
+    pub sender_address: Option<String>,
     /// キャンセルメールを適用済み（apply_cancel 済みのため save_order 不要）
     pub cancel_applied: bool,
 }
@@ -113,6 +122,8 @@ where
     pub parse_state: Arc<ParseState>,
     /// 画像保存用: (pool, images_dir)。None の場合は画像登録をスキップ
     pub image_save_ctx: Option<(std::sync::Arc<sqlx::SqlitePool>, std::path::PathBuf)>,
+    /// process_batch で計測したパーサーごとの処理時間（after_batch で記録してクリア）
+    pub parser_timings: Arc<Mutex<Vec<ParserTiming>>>,
 }
 
 /// メールパースタスク
@@ -162,7 +173,7 @@ where
 /// 旧実装 (`logic/email_parser.rs`) と同じロジックを使用:
 /// - from_address からメールアドレスを抽出して正規化
 /// - sender_address と完全一致（大文字小文字無視）でチェック
-fn get_candidate_parsers(
+pub(crate) fn get_candidate_parsers(
     settings: &[(String, String, Option<String>, String)],
     from_address: Option<&str>,
     subject: Option<&str>,
@@ -250,7 +261,7 @@ async fn save_images_for_dispatch_outcome(
 /// `DispatchOutcome` を `EmailParseOutput` 組み立てに必要な `(OrderInfo, cancel_applied)` に変換する
 ///
 /// - `OrderSaved` / `MultiOrderSaved` → cancel_applied = false（通常保存）
-/// - `CancelApplied` / `OrderNumberChanged` / `ConsolidationApplied` → cancel_applied = true（特殊適用済み）
+/// - `CancelApplied` / `OrderNumberChanged` / `ConsolidationApplied` / `DeliveryDateChanged` / `ReleaseDateChanged` / `PreparingApplied` → cancel_applied = true（特殊適用済み）
 fn outcome_to_order_info(outcome: DispatchOutcome, email_id: i64) -> (OrderInfo, bool) {
     match outcome {
         DispatchOutcome::OrderSaved(order_info) => (*order_info, false),
@@ -264,6 +275,8 @@ fn outcome_to_order_info(outcome: DispatchOutcome, email_id: i64) -> (OrderInfo,
                 subtotal: None,
                 shipping_fee: None,
                 total_amount: None,
+                tax_amount: None,
+                tax_included: true,
             };
             (info, true)
         }
@@ -277,6 +290,8 @@ fn outcome_to_order_info(outcome: DispatchOutcome, email_id: i64) -> (OrderInfo,
                 subtotal: None,
                 shipping_fee: None,
                 total_amount: None,
+                tax_amount: None,
+                tax_included: true,
             };
             (info, true)
         }
@@ -290,6 +305,25 @@ fn outcome_to_order_info(outcome: DispatchOutcome, email_id: i64) -> (OrderInfo,
                 subtotal: None,
                 shipping_fee: None,
                 total_amount: None,
+                tax_amount: None,
+                tax_included: true,
+            };
+            (info, true)
+        }
+        DispatchOutcome::DeliveryDateChanged { order_number }
+        | DispatchOutcome::ReleaseDateChanged { order_number }
+        | DispatchOutcome::PreparingApplied { order_number } => {
+            let info = OrderInfo {
+                order_number,
+                order_date: None,
+                delivery_address: None,
+                delivery_info: None,
+                items: vec![],
+                subtotal: None,
+                shipping_fee: None,
+                total_amount: None,
+                tax_amount: None,
+                tax_included: true,
             };
             (info, true)
         }
@@ -303,6 +337,8 @@ fn outcome_to_order_info(outcome: DispatchOutcome, email_id: i64) -> (OrderInfo,
                 subtotal: None,
                 shipping_fee: None,
                 total_amount: None,
+                tax_amount: None,
+                tax_included: true,
             };
             (info, true)
         }
@@ -321,6 +357,8 @@ fn outcome_to_order_info(outcome: DispatchOutcome, email_id: i64) -> (OrderInfo,
                     subtotal: None,
                     shipping_fee: None,
                     total_amount: None,
+                    tax_amount: None,
+                    tax_included: true,
                 }
             });
             (first, false)
@@ -346,6 +384,30 @@ where
         EMAIL_PARSE_EVENT_NAME
     }
 
+    fn describe_input(&self, input: &Self::Input) -> Option<String> {
+        Some(format!(
+            "[{}] {}",
+            input.email_id,
+            input.subject.as_deref().unwrap_or("(件名なし)")
+        ))
+    }
+
+    fn describe_output(&self, output: &Self::Output) -> Option<String> {
+        let result = if output.cancel_applied {
+            "キャンセル適用".to_string()
+        } else {
+            format!(
+                "注文番号 {}（{} 件）",
+                output.order_info.order_number,
+                output.order_info.items.len()
+            )
+        };
+        Some(format!(
+            "{} / {}: {}",
+            output.parser_type, output.shop_name, result
+        ))
+    }
+
     /// バッチ処理前にショップ設定を取得してキャッシュ
     async fn before_batch(
         &self,
@@ -409,6 +471,13 @@ where
                 input.subject.as_deref(),
             );
 
+            // 日英両方のメールを送るショップ向けに、本文の言語に合うパーサーに絞り込む
+            let candidate_parsers = narrow_candidates_by_language(
+                candidate_parsers,
+                detect_language(&input.body_plain),
+                |(parser_type, _)| parser_language(&registry, parser_type),
+            );
+
             if candidate_parsers.is_empty() {
                 log::debug!(
                     "No parser found for address: {:?} with subject: {:?}",
@@ -423,7 +492,7 @@ where
             }
 
             let mut last_error = String::new();
-            let mut dispatch_outcome: Option<(DispatchOutcome, String)> = None; // (outcome, shop_name)
+            let mut dispatch_outcome: Option<(DispatchOutcome, String, String)> = None; // (outcome, shop_name, parser_type)
 
             'parser_loop: for (parser_type, shop_name) in &candidate_parsers {
                 let plugin = match find_plugin(&registry, parser_type) {
@@ -461,7 +530,8 @@ where
                     &input.body_plain
                 };
 
-                match plugin
+                let started = Instant::now();
+                let dispatch_result = plugin
                     .dispatch(
                         parser_type,
                         input.email_id,
@@ -471,8 +541,14 @@ where
                         body_for_dispatch,
                         &mut tx,
                     )
-                    .await
-                {
+                    .await;
+                context.parser_timings.lock().await.push(ParserTiming {
+                    parser_type: parser_type.clone(),
+                    elapsed: started.elapsed(),
+                    succeeded: dispatch_result.is_ok(),
+                });
+
+                match dispatch_result {
                     Ok(outcome) => {
                         // コミット。失敗時は保存エラーとして扱いリトライ対象にする。
                         if let Err(e) = tx.commit().await {
@@ -487,7 +563,7 @@ where
                             parser_type,
                             input.email_id
                         );
-                        dispatch_outcome = Some((outcome, shop_name.clone()));
+                        dispatch_outcome = Some((outcome, shop_name.clone(), parser_type.clone()));
                         break 'parser_loop;
                     }
                     Err(DispatchError::ParseFailed(e)) => {
@@ -520,11 +596,13 @@ where
 
             // dispatch_outcome が None の場合は全パーサーが ParseFailed
             let from_address = input.from_address.as_deref().unwrap_or("");
-            let shop_domain = extract_email_address(from_address)
-                .and_then(|email| extract_domain(&email).map(|s| s.to_string()));
+            let sender_address = extract_email_address(from_address);
+            let shop_domain = sender_address
+                .as_deref()
+                .and_then(|email| extract_domain(email).map(|s| s.to_string()));
 
             match dispatch_outcome {
-                Some((outcome, shop_name)) => {
+                Some((outcome, shop_name, parser_type)) => {
                     // tx.commit() 後に画像登録を実行する。
                     // dispatch() 内ではトランザクションの RESERVED LOCK が保持されており、
                     // 別コネクションからの INSERT が SQLITE_BUSY になるため、
@@ -538,6 +616,8 @@ where
                         order_info,
                         shop_name,
                         shop_domain,
+                        parser_type,
+                        sender_address,
                         cancel_applied,
                     }));
                 }
@@ -563,7 +643,7 @@ where
         &self,
         batch_number: usize,
         results: &[Result<Self::Output, String>],
-        _context: &Self::Context,
+        context: &Self::Context,
     ) -> Result<(), String> {
         log::debug!(
             "[{}] after_batch: batch {} with {} results",
@@ -588,6 +668,32 @@ where
             }
         }
 
+        // shop_settings ごとのパース統計を更新（失敗してもパース結果には影響させない）
+        let matches: Vec<(String, String)> = results
+            .iter()
+            .filter_map(|r| r.as_ref().ok())
+            .filter_map(|o| {
+                o.sender_address
+                    .clone()
+                    .map(|addr| (addr, o.parser_type.clone()))
+            })
+            .collect();
+        let stats_repo = SqliteShopSettingStatsRepository::new(context.pool.as_ref().clone());
+        if let Err(e) = stats_repo.record_matches(&matches).await {
+            log::warn!(
+                "[{}] Failed to record shop setting matches: {}",
+                self.name(),
+                e
+            );
+        }
+
+        // パーサーごとの処理時間を記録（失敗してもパース結果には影響させない）
+        let timings = std::mem::take(&mut *context.parser_timings.lock().await);
+        let metrics_repo = SqliteParserMetricsRepository::new(context.pool.as_ref().clone());
+        if let Err(e) = metrics_repo.record_timings(&timings).await {
+            log::warn!("[{}] Failed to record parser metrics: {}", self.name(), e);
+        }
+
         // 成功件数と失敗件数をログ
         let success = results.iter().filter(|r| r.is_ok()).count();
         let failed = results.iter().filter(|r| r.is_err()).count();
@@ -860,6 +966,7 @@ mod tests {
             shop_settings_cache: Arc::new(Mutex::new(ShopSettingsCache::default())),
             parse_state: Arc::new(ParseState::new()),
             image_save_ctx: None,
+            parser_timings: Arc::new(Mutex::new(Vec::new())),
         };
 
         let task: EmailParseTask<MockParseRepository, MockShopSettingsRepository> =
@@ -891,6 +998,7 @@ mod tests {
             shop_settings_cache: Arc::new(Mutex::new(ShopSettingsCache::default())),
             parse_state: Arc::new(ParseState::new()),
             image_save_ctx: None,
+            parser_timings: Arc::new(Mutex::new(Vec::new())),
         };
 
         let task: EmailParseTask<MockParseRepository, MockShopSettingsRepository> =
@@ -921,6 +1029,7 @@ mod tests {
             })),
             parse_state: Arc::new(ParseState::new()),
             image_save_ctx: None,
+            parser_timings: Arc::new(Mutex::new(Vec::new())),
         };
 
         let task: EmailParseTask<MockParseRepository, MockShopSettingsRepository> =
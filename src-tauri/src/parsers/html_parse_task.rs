@@ -111,15 +111,15 @@ async fn apply_cancelled_order(
     tx: &mut sqlx::Transaction<'_, sqlx::Sqlite>,
     order_number: &str,
 ) -> Result<(), String> {
-    let order: Option<(i64,)> = sqlx::query_as(
-        "SELECT id FROM orders WHERE order_number = ? AND shop_domain = 'amazon.co.jp' LIMIT 1",
+    let order = SqliteOrderRepository::find_order_id_by_number_in_tx(
+        tx,
+        order_number,
+        Some("amazon.co.jp"),
     )
-    .bind(order_number)
-    .fetch_optional(tx.as_mut())
     .await
     .map_err(|e| format!("DB error: {e}"))?;
 
-    let Some((order_id,)) = order else {
+    let Some(order_id) = order else {
         log::warn!(
             "[html_parse] キャンセル済み注文が未登録: order_number={}",
             order_number
@@ -0,0 +1,72 @@
+//! 商品詳細ページ URL の正規化（全店舗共通）
+//!
+//! メール内のリンクはメール配信用のトラッキングパラメータ（`utm_*` / `dmmref` など）を含むため、
+//! 除去してから `OrderItem::item_url` に保存する。同じ商品ページが常に同じ URL になるようにし、
+//! 価格ウォッチや再購入時のリンクとしてそのまま使えるようにする。
+
+/// 除去するトラッキング用クエリパラメータ（`utm_` で始まるものは別途すべて除去する）
+const TRACKING_PARAMS: &[&str] = &["dmmref", "i3_ref", "gclid", "fbclid", "mc_cid", "mc_eid"];
+
+fn is_tracking_param(name: &str) -> bool {
+    name.starts_with("utm_") || TRACKING_PARAMS.contains(&name)
+}
+
+/// リンク先 URL を正規化する
+///
+/// - http / https 以外（`mailto:` や相対パス）は None
+/// - HTML エスケープされた `&amp;` を戻し、フラグメントとトラッキング用パラメータを除去する
+pub fn normalize_item_url(href: &str) -> Option<String> {
+    let href = href.trim().replace("&amp;", "&");
+    let rest = href
+        .strip_prefix("https://")
+        .or_else(|| href.strip_prefix("http://"))?;
+    if rest.is_empty() || rest.starts_with('/') {
+        return None;
+    }
+
+    let without_fragment = href.split('#').next().unwrap_or_default();
+    let (base, query) = match without_fragment.split_once('?') {
+        Some((base, query)) => (base, Some(query)),
+        None => (without_fragment, None),
+    };
+    let params: Vec<&str> = query
+        .map(|q| {
+            q.split('&')
+                .filter(|p| !p.is_empty())
+                .filter(|p| !is_tracking_param(p.split('=').next().unwrap_or_default()))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    Some(if params.is_empty() {
+        base.to_string()
+    } else {
+        format!("{base}?{}", params.join("&"))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_item_url_strips_tracking_params() {
+        assert_eq!(
+            normalize_item_url(
+                "https://www.dmm.com/mono/hobby/-/detail/=/cid=abc123/?dmmref=gMono_Mail_Purchase&amp;i3_ref=mail"
+            ),
+            Some("https://www.dmm.com/mono/hobby/-/detail/=/cid=abc123/".to_string())
+        );
+        assert_eq!(
+            normalize_item_url("https://shop.example.com/item?id=1&utm_source=mail&color=red#top"),
+            Some("https://shop.example.com/item?id=1&color=red".to_string())
+        );
+    }
+
+    #[test]
+    fn test_normalize_item_url_rejects_non_http() {
+        assert_eq!(normalize_item_url("mailto:info@example.com"), None);
+        assert_eq!(normalize_item_url("/detail/123"), None);
+        assert_eq!(normalize_item_url("https://"), None);
+    }
+}
@@ -0,0 +1,218 @@
+//! ラベル行からの値抽出（全店舗共通）
+//!
+//! `[注文番号] 25-0807-1624` や `商品名 ： ○○` のような「ラベル + 値」の行から値を取り出す。
+//! ラベル名・書式・値の形式を [`Label`] のビルダーで宣言し、パーサー側は
+//! `static` に定義したラベルの `find` / `find_all` を呼ぶだけにする。
+//!
+//! ```ignore
+//! static ORDER_NUMBER: Lazy<Label> = Lazy::new(|| {
+//!     Label::new("注文番号")
+//!         .alias("代表注文番号")
+//!         .bracket()
+//!         .colon()
+//!         .value(r"\d+-\d+-\d+")
+//!         .build()
+//! });
+//! let order_number = ORDER_NUMBER.find(body);
+//! ```
+
+use regex::Regex;
+
+use super::amount::parse_amount;
+use crate::logic::language::EmailLanguage;
+
+/// 値の形式を指定しない場合の既定パターン（行末まで）
+const DEFAULT_VALUE_PATTERN: &str = r".+";
+
+/// ラベル定義のビルダー
+#[derive(Debug, Clone)]
+pub struct LabelBuilder {
+    names: Vec<String>,
+    bracket: bool,
+    colon: bool,
+    value: String,
+}
+
+impl LabelBuilder {
+    /// 同じ意味で使われる別名を追加する（例: `注文番号` と `代表注文番号`）
+    pub fn alias(mut self, name: &str) -> Self {
+        self.names.push(name.to_string());
+        self
+    }
+
+    /// `[ラベル] 値` 形式を受け付ける
+    pub fn bracket(mut self) -> Self {
+        self.bracket = true;
+        self
+    }
+
+    /// `ラベル ： 値` 形式を受け付ける（区切りは全角/半角の `：` `=`）
+    pub fn colon(mut self) -> Self {
+        self.colon = true;
+        self
+    }
+
+    /// 値の形式を正規表現で指定する（既定は行末まで）
+    pub fn value(mut self, pattern: &str) -> Self {
+        self.value = pattern.to_string();
+        self
+    }
+
+    /// ラベルを構築する
+    ///
+    /// 書式を 1 つも指定しない場合は `bracket()` と `colon()` の両方を受け付ける。
+    /// 定数定義から呼ぶ想定のため、値のパターンが不正な場合は panic する。
+    pub fn build(self) -> Label {
+        let names = self
+            .names
+            .iter()
+            .map(|n| regex::escape(n))
+            .collect::<Vec<_>>()
+            .join("|");
+        let (bracket, colon) = if self.bracket || self.colon {
+            (self.bracket, self.colon)
+        } else {
+            (true, true)
+        };
+
+        let mut forms: Vec<String> = Vec::new();
+        if bracket {
+            forms.push(format!(r"\[(?:{names})\]\s*"));
+        }
+        if colon {
+            forms.push(format!(r"(?:{names})\s*[：:＝=]\s*"));
+        }
+        let pattern = format!("(?:{})({})", forms.join("|"), self.value);
+        let re =
+            Regex::new(&pattern).unwrap_or_else(|e| panic!("Invalid label pattern {pattern}: {e}"));
+        Label { re }
+    }
+}
+
+/// 値を抽出するラベル定義
+#[derive(Debug, Clone)]
+pub struct Label {
+    re: Regex,
+}
+
+impl Label {
+    /// ラベル名からビルダーを作成する
+    pub fn new(name: &str) -> LabelBuilder {
+        LabelBuilder {
+            names: vec![name.to_string()],
+            bracket: false,
+            colon: false,
+            value: DEFAULT_VALUE_PATTERN.to_string(),
+        }
+    }
+
+    /// 最初に見つかった値を返す（前後の空白は除去する）
+    pub fn find(&self, text: &str) -> Option<String> {
+        text.lines().find_map(|line| self.match_line(line))
+    }
+
+    /// 行のスライスから最初に見つかった値を返す
+    pub fn find_in_lines(&self, lines: &[&str]) -> Option<String> {
+        lines.iter().find_map(|line| self.match_line(line))
+    }
+
+    /// 見つかったすべての値を出現順（重複なし）で返す
+    pub fn find_all(&self, text: &str) -> Vec<String> {
+        let mut values: Vec<String> = Vec::new();
+        for value in text.lines().filter_map(|line| self.match_line(line)) {
+            if !values.contains(&value) {
+                values.push(value);
+            }
+        }
+        values
+    }
+
+    /// 最初に見つかった値を数値として返す（桁区切りのカンマは無視する）
+    pub fn find_i64(&self, text: &str) -> Option<i64> {
+        self.find(text)
+            .and_then(|v| v.replace(',', "").parse::<i64>().ok())
+    }
+
+    /// 最初に見つかった値を金額として返す（`¥` / `円` / `JPY` 等の表記は言語に合わせて解釈する）
+    pub fn find_amount(&self, text: &str, language: EmailLanguage) -> Option<i64> {
+        self.find(text).and_then(|v| parse_amount(&v, language))
+    }
+
+    /// 1 行がラベル行であれば値を返す（値が空の場合は None）
+    pub fn match_line(&self, line: &str) -> Option<String> {
+        let value = self.re.captures(line)?.get(1)?.as_str().trim();
+        (!value.is_empty()).then(|| value.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bracket_label() {
+        let label = Label::new("注文番号")
+            .bracket()
+            .value(r"\d+-\d+-\d+")
+            .build();
+        assert_eq!(
+            label.find("ご注文ありがとうございます\n[注文番号] 25-0821-1050\n"),
+            Some("25-0821-1050".to_string())
+        );
+        // colon 形式は受け付けない
+        assert_eq!(label.find("注文番号：25-0821-1050"), None);
+    }
+
+    #[test]
+    fn test_colon_label_separators_and_trim() {
+        let label = Label::new("商品名").colon().build();
+        assert_eq!(
+            label.find("商品名 ： 30MS オプションパーツ  \n"),
+            Some("30MS オプションパーツ".to_string())
+        );
+        assert_eq!(label.find("商品名=ニッパー"), Some("ニッパー".to_string()));
+        assert_eq!(label.find("商品名："), None);
+    }
+
+    #[test]
+    fn test_alias_and_both_forms_find_all() {
+        let label = Label::new("注文番号")
+            .alias("代表注文番号")
+            .value(r"\d+-\d+-\d+")
+            .build();
+        let body =
+            "[代表注文番号] 25-0807-1624\n[注文番号] 25-0807-1624\n注文番号 ： 25-0810-0001\n";
+        assert_eq!(
+            label.find_all(body),
+            vec!["25-0807-1624".to_string(), "25-0810-0001".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_find_i64_and_lines() {
+        let label = Label::new("キャンセル個数")
+            .colon()
+            .value(r"[\d,]+")
+            .build();
+        assert_eq!(label.find_i64("キャンセル個数：1,200"), Some(1200));
+        assert_eq!(
+            label.find_in_lines(&["a", "キャンセル個数 = 2"]),
+            Some("2".to_string())
+        );
+        assert_eq!(label.find_i64("個数：2"), None);
+    }
+
+    #[test]
+    fn test_find_amount() {
+        let label = Label::new("合計").alias("Total").colon().build();
+        assert_eq!(
+            label.find_amount("合計：12,100円", EmailLanguage::Japanese),
+            Some(12100)
+        );
+        assert_eq!(
+            label.find_amount("Total: JPY 23,980.00", EmailLanguage::English),
+            Some(23980)
+        );
+        assert_eq!(label.find_amount("Total: -", EmailLanguage::English), None);
+    }
+}
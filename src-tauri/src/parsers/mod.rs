@@ -1,5 +1,6 @@
 ﻿use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
+use ts_rs::TS;
 
 // 定数はemail_parse_taskモジュールからエクスポート
 pub use email_parse_task::{EMAIL_PARSE_EVENT_NAME, EMAIL_PARSE_TASK_NAME};
@@ -17,6 +18,22 @@ pub struct EmailRow {
     pub internal_date: Option<i64>,
 }
 
+impl EmailRow {
+    /// 同期時に切り詰めた body_html を、`email_bodies/` に保存した元の HTML に差し替える
+    ///
+    /// ショップのメールが上限を超えて切り詰められていても、パースは本文全体で行う。
+    pub fn with_full_body_html(mut self, app_data_dir: Option<&std::path::Path>) -> Self {
+        if let Some(dir) = app_data_dir {
+            crate::gmail::body_limit::restore_truncated_body_html(
+                &mut self.body_html,
+                &self.message_id,
+                dir,
+            );
+        }
+        self
+    }
+}
+
 /// body_html があれば使用、なければ body_plain を返す（タグ除去は行わない）。
 /// DMM 等は HTML から直接パースするため、HTML 優先で精度が上がる。
 pub fn get_body_for_parse(row: &EmailRow) -> String {
@@ -33,6 +50,28 @@ pub mod cancel_info;
 pub mod order_number_change_info;
 // まとめ完了情報（全店舗共通）
 pub mod consolidation_info;
+// お届け予定日変更情報（全店舗共通）
+pub mod delivery_date_change_info;
+// 発売延期情報（全店舗共通）
+pub mod delay_info;
+// 消費税情報（全店舗共通）
+pub mod tax_info;
+// 予約商品の発売予定（全店舗共通）
+pub mod release_date;
+// 注文番号の正規化（全店舗共通）
+pub mod order_number;
+pub use order_number::{normalize_order_number, order_numbers_match};
+// 金額表記の解析（全店舗共通・日英対応）
+pub mod amount;
+pub use amount::parse_amount;
+// ラベル行からの値抽出（全店舗共通）
+pub mod label_value;
+pub use label_value::Label;
+// 商品詳細ページURLの正規化（全店舗共通）
+pub mod item_url;
+pub use item_url::normalize_item_url;
+// パーサー出力のスナップショット（開発用）
+pub mod snapshot;
 
 // BatchTask 実装
 pub mod email_parse_task;
@@ -118,7 +157,8 @@ impl ParseState {
 }
 
 /// 注文情報を表す構造体
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
 pub struct OrderInfo {
     /// 注文番号
     pub order_number: String,
@@ -136,10 +176,21 @@ pub struct OrderInfo {
     pub shipping_fee: Option<i64>,
     /// 合計金額
     pub total_amount: Option<i64>,
+    /// 消費税額（メールに記載がある場合のみ）
+    #[serde(default)]
+    pub tax_amount: Option<i64>,
+    /// 金額が税込表示か（税抜表示のメールは false）
+    #[serde(default = "default_tax_included")]
+    pub tax_included: bool,
+}
+
+fn default_tax_included() -> bool {
+    true
 }
 
 /// 配送先情報
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
 pub struct DeliveryAddress {
     /// 宛名
     pub name: String,
@@ -150,7 +201,8 @@ pub struct DeliveryAddress {
 }
 
 /// 配送情報
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
 pub struct DeliveryInfo {
     /// 配送会社
     pub carrier: String,
@@ -167,7 +219,8 @@ pub struct DeliveryInfo {
 }
 
 /// 商品情報
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
 pub struct OrderItem {
     /// 商品名
     pub name: String,
@@ -183,6 +236,12 @@ pub struct OrderItem {
     pub subtotal: i64,
     /// 商品画像URL（注文確認メールに含まれる場合、images テーブルへ登録する）
     pub image_url: Option<String>,
+    /// ショップの商品詳細ページURL（注文確認メールにリンクがある場合のみ。トラッキング用パラメータは除去済み）
+    #[serde(default)]
+    pub item_url: Option<String>,
+    /// 発売予定（予約商品でメールに記載がある場合のみ。「8月下旬」等のあいまいな表記は精度付きで保持する）
+    #[serde(default)]
+    pub release_date: Option<release_date::FuzzyReleaseDate>,
 }
 
 /// メールパーサーのトレイト
@@ -199,7 +258,8 @@ pub trait EmailParser {
 }
 
 /// パースメタデータ
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
 pub struct ParseMetadata {
     pub parse_status: String,
     pub last_parse_started_at: Option<String>,
@@ -209,6 +269,18 @@ pub struct ParseMetadata {
     pub batch_size: i64,
 }
 
+/// 単体再パース（`reparse_order`）の結果
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct ReparseOrderResult {
+    /// 再パース対象のメール数
+    pub email_count: i64,
+    /// パースに成功したメール数
+    pub parsed_count: i64,
+    /// パースに失敗したメールと理由
+    pub failures: Vec<String>,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -382,6 +454,8 @@ mod tests {
             subtotal: Some(1000),
             shipping_fee: Some(500),
             total_amount: Some(1500),
+            tax_amount: None,
+            tax_included: true,
         };
 
         assert_eq!(order.order_number, "ORD-001");
@@ -399,6 +473,8 @@ mod tests {
             quantity: 2,
             subtotal: 2000,
             image_url: None,
+            item_url: None,
+            release_date: None,
         };
 
         let order = OrderInfo {
@@ -410,6 +486,8 @@ mod tests {
             subtotal: None,
             shipping_fee: None,
             total_amount: None,
+            tax_amount: None,
+            tax_included: true,
         };
 
         assert_eq!(order.items.len(), 1);
@@ -472,6 +550,8 @@ mod tests {
             subtotal: Some(1000),
             shipping_fee: None,
             total_amount: Some(1000),
+            tax_amount: None,
+            tax_included: true,
         };
 
         let json = serde_json::to_string(&order).unwrap();
@@ -509,6 +589,8 @@ mod tests {
             quantity: 1,
             subtotal: 2500,
             image_url: None,
+            item_url: None,
+            release_date: None,
         };
 
         let json = serde_json::to_string(&item).unwrap();
@@ -567,10 +649,14 @@ mod tests {
                 quantity: 1,
                 subtotal: 100,
                 image_url: None,
+                item_url: None,
+                release_date: None,
             }],
             subtotal: None,
             shipping_fee: None,
             total_amount: None,
+            tax_amount: None,
+            tax_included: true,
         };
 
         let cloned = order.clone();
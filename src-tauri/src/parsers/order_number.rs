@@ -0,0 +1,78 @@
+//! 注文番号の正規化（全店舗共通）
+//!
+//! ショップや通知の種類によって注文番号の表記（ハイフン有無・空白・ゼロ埋め・全角/半角・大小文字）が
+//! 揺れるため、発送・キャンセル等の通知メールと元注文の突合は正規化した値同士で比較する。
+
+use unicode_normalization::UnicodeNormalization;
+
+/// 区切り文字として除去する文字（空白とハイフン類）
+fn is_order_number_separator(c: char) -> bool {
+    c.is_whitespace() || matches!(c, '-' | '‐' | '‑' | '‒' | '–' | '—' | '―' | '−')
+}
+
+/// 注文番号を照合用に正規化する
+///
+/// - 全角→半角統一（NFKC正規化）
+/// - 空白・ハイフン類の除去
+/// - 大文字統一
+/// - 番号全体の先頭のゼロ埋めを除去（`00006` と `6` は一致する。区切りごとには判定しないため
+///   `A-01-2` と `A-1-02` のような別の番号は一致しない）
+///
+/// `orders.order_number_normalized` に保存し、突合はこの値の一致で検索する。
+pub fn normalize_order_number(order_number: &str) -> String {
+    let normalized: String = order_number
+        .nfkc()
+        .filter(|c| !is_order_number_separator(*c))
+        .flat_map(char::to_uppercase)
+        .collect();
+    let trimmed = normalized.trim_start_matches('0');
+    if trimmed.is_empty() && !normalized.is_empty() {
+        "0".to_string()
+    } else {
+        trimmed.to_string()
+    }
+}
+
+/// 2つの注文番号が正規化後に一致するか（空の注文番号は一致とみなさない）
+pub fn order_numbers_match(a: &str, b: &str) -> bool {
+    let a = normalize_order_number(a);
+    !a.is_empty() && a == normalize_order_number(b)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_order_number_removes_separators_and_uppercases() {
+        assert_eq!(normalize_order_number("kc-26407532"), "KC26407532");
+        assert_eq!(normalize_order_number(" 99 1111 1111 "), "9911111111");
+        assert_eq!(normalize_order_number("ＢＳ－１２３４５"), "BS12345");
+        assert_eq!(
+            normalize_order_number("250-1234567-1234567"),
+            "25012345671234567"
+        );
+    }
+
+    #[test]
+    fn test_normalize_order_number_strips_zero_padding() {
+        assert_eq!(normalize_order_number("00006"), "6");
+        assert_eq!(normalize_order_number("0-0012345"), "12345");
+        assert_eq!(normalize_order_number("000"), "0");
+        // 番号の途中のゼロ埋めは残す
+        assert_eq!(normalize_order_number("KC-0012345"), "KC0012345");
+        assert_eq!(normalize_order_number("2024-0001"), "20240001");
+    }
+
+    #[test]
+    fn test_order_numbers_match() {
+        assert!(order_numbers_match("KC-12345", "kc12345"));
+        assert!(order_numbers_match("0012345", "12345"));
+        assert!(order_numbers_match("99-1111-1111", "9911111111"));
+        assert!(!order_numbers_match("KC-12345", "KC-12346"));
+        assert!(!order_numbers_match("", " - "));
+        // 区切りごとのゼロ埋めの違いは別の番号とみなす
+        assert!(!order_numbers_match("A-01-2", "A-1-02"));
+        assert!(!order_numbers_match("2024-0001", "2024-1"));
+    }
+}
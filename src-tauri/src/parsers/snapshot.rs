@@ -0,0 +1,207 @@
+//! パーサー出力のスナップショット（開発用）
+//!
+//! サンプルの .eml に対して、単一メール用の候補パーサー（`get_candidate_parsers`）を
+//! すべて実行し、結果を JSON として保存・比較する。パーサー改修時のリグレッション検出に使う。
+//! `dispatch()` 側で行う補完（受信日時の注文日化・税情報）は含まない、パーサー単体の出力である。
+
+use serde::{Deserialize, Serialize};
+
+use crate::gmail::eml::ParsedEml;
+use crate::logic::email_parser::{
+    get_candidate_parsers, narrow_candidates_by_language, parser_language,
+};
+use crate::logic::language::detect_language;
+use crate::parsers::{get_body_for_parse, EmailRow, OrderInfo};
+use crate::plugins::{find_plugin, VendorPlugin};
+
+/// 1 パーサーの実行結果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ParserSnapshotEntry {
+    pub parser_type: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub order: Option<OrderInfo>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// 1 通のメールに対するスナップショット
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ParserSnapshot {
+    pub from_address: Option<String>,
+    pub subject: Option<String>,
+    /// 候補パーサーごとの結果（候補がなければ空）
+    pub results: Vec<ParserSnapshotEntry>,
+}
+
+/// プラグインの `default_shop_settings()` を `get_candidate_parsers` の入力形式に変換する
+///
+/// DB の shop_settings（ユーザーによる無効化・件名フィルター変更）に左右されないよう既定値を使う。
+pub fn default_shop_settings(
+    registry: &[Box<dyn VendorPlugin>],
+) -> Vec<(String, String, Option<String>)> {
+    registry
+        .iter()
+        .flat_map(|plugin| plugin.default_shop_settings())
+        .map(|s| {
+            let filters = s
+                .subject_filters
+                .and_then(|f| serde_json::to_string(&f).ok());
+            (s.sender_address, s.parser_type, filters)
+        })
+        .collect()
+}
+
+/// メールに候補パーサーを順に適用した結果を返す
+pub fn snapshot_email(
+    registry: &[Box<dyn VendorPlugin>],
+    shop_settings: &[(String, String, Option<String>)],
+    eml: &ParsedEml,
+) -> ParserSnapshot {
+    let row = EmailRow {
+        email_id: 0,
+        message_id: String::new(),
+        body_plain: eml.body_plain.clone(),
+        body_html: eml.body_html.clone(),
+        from_address: eml.from_address.clone(),
+        subject: eml.subject.clone(),
+        internal_date: eml.internal_date,
+    };
+    let body = get_body_for_parse(&row);
+    let body_plain_raw = eml.body_plain.clone().unwrap_or_default();
+
+    let candidates = get_candidate_parsers(
+        eml.from_address.as_deref().unwrap_or(""),
+        eml.subject.as_deref(),
+        shop_settings,
+    );
+    let candidates = narrow_candidates_by_language(candidates, detect_language(&body), |p| {
+        parser_language(registry, p)
+    });
+
+    let results = candidates
+        .into_iter()
+        .map(|parser_type| {
+            let result = match find_plugin(registry, parser_type) {
+                None => Err(format!("No plugin for parser_type: {parser_type}")),
+                Some(plugin) => match plugin.get_parser(parser_type) {
+                    // キャンセル等は dispatch() 内で DB を更新するためパーサー単体では実行できない
+                    None => Err("dispatch 専用のパーサーのためスナップショット対象外".to_string()),
+                    Some(parser) => {
+                        let body = if plugin.prefer_plain_text() {
+                            &body_plain_raw
+                        } else {
+                            &body
+                        };
+                        parser.parse(body)
+                    }
+                },
+            };
+            let (order, error) = match result {
+                Ok(order) => (Some(order), None),
+                Err(e) => (None, Some(e)),
+            };
+            ParserSnapshotEntry {
+                parser_type: parser_type.to_string(),
+                order,
+                error,
+            }
+        })
+        .collect();
+
+    ParserSnapshot {
+        from_address: eml.from_address.clone(),
+        subject: eml.subject.clone(),
+        results,
+    }
+}
+
+/// 2 つのテキストの行差分を `- ` / `+ ` 付きで返す（一致する行は含めない）
+pub fn diff_lines(expected: &str, actual: &str) -> Vec<String> {
+    let a: Vec<&str> = expected.lines().collect();
+    let b: Vec<&str> = actual.lines().collect();
+
+    // 最長共通部分列の長さ表（lcs[i][j] = a[i..] と b[j..] の LCS 長）
+    let mut lcs = vec![vec![0usize; b.len() + 1]; a.len() + 1];
+    for i in (0..a.len()).rev() {
+        for j in (0..b.len()).rev() {
+            lcs[i][j] = if a[i] == b[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut out = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < a.len() && j < b.len() {
+        if a[i] == b[j] {
+            i += 1;
+            j += 1;
+        } else if lcs[i + 1][j] >= lcs[i][j + 1] {
+            out.push(format!("- {}", a[i]));
+            i += 1;
+        } else {
+            out.push(format!("+ {}", b[j]));
+            j += 1;
+        }
+    }
+    out.extend(a[i..].iter().map(|line| format!("- {line}")));
+    out.extend(b[j..].iter().map(|line| format!("+ {line}")));
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gmail::eml::{build_eml, parse_eml, EmlParts};
+    use crate::plugins::build_registry;
+
+    #[test]
+    fn test_snapshot_email_runs_candidate_parsers() {
+        let registry = build_registry();
+        let settings = default_shop_settings(&registry);
+        let eml = build_eml(&EmlParts {
+            message_id: "sample",
+            subject: Some("商品出荷のお知らせ［コトブキヤオンラインショップ］"),
+            from_address: Some("onlineshop@kotobukiya-ec.com"),
+            body_plain: Some(
+                "【オーダーID】0434429495\n【配送業者】ヤマト運輸\n【お問い合わせ伝票番号】123456789012\n【出荷明細】\n\u{3000}1.PUNI☆MOFU ロン\n\u{3000}\u{3000}数量：1\n",
+            ),
+            ..Default::default()
+        });
+
+        let snapshot = snapshot_email(&registry, &settings, &parse_eml(eml.as_bytes()));
+        assert_eq!(snapshot.results.len(), 1);
+        let entry = &snapshot.results[0];
+        assert_eq!(entry.parser_type, "kotobukiya_send");
+        assert!(entry.error.is_none());
+        assert_eq!(
+            entry.order.as_ref().unwrap().order_number,
+            "0434429495".to_string()
+        );
+    }
+
+    #[test]
+    fn test_snapshot_email_without_candidates() {
+        let registry = build_registry();
+        let settings = default_shop_settings(&registry);
+        let eml = ParsedEml {
+            from_address: Some("unknown@example.com".to_string()),
+            body_plain: Some("hello".to_string()),
+            ..Default::default()
+        };
+        assert!(snapshot_email(&registry, &settings, &eml)
+            .results
+            .is_empty());
+    }
+
+    #[test]
+    fn test_diff_lines() {
+        assert!(diff_lines("a\nb\nc", "a\nb\nc").is_empty());
+        assert_eq!(
+            diff_lines("a\nb\nc", "a\nx\nc\nd"),
+            vec!["- b", "+ x", "+ d"]
+        );
+    }
+}
@@ -0,0 +1,104 @@
+//! メール本文から抽出した消費税情報（全店舗共通）
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+/// `消費税：¥1,000` / `（うち消費税 1,000円）` / `消費税額(10%) 1,000円` などの消費税額表記
+static TAX_AMOUNT_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?:うち|内)?消費税(?:額|等)?\s*(?:[（(]\s*\d+\s*[%％]\s*[）)])?\s*[：:]?\s*[¥￥]?\s*([\d,]+)")
+        .expect("Invalid TAX_AMOUNT_RE")
+});
+
+/// 税抜表示を示す表記
+static TAX_EXCLUDED_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"税抜|税別|外税").expect("Invalid TAX_EXCLUDED_RE"));
+
+/// 税込表示を示す表記
+static TAX_INCLUDED_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"税込|内税|消費税総額表示").expect("Invalid TAX_INCLUDED_RE"));
+
+/// メール本文から抽出した消費税情報
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TaxInfo {
+    /// 消費税額（記載がない場合は None）
+    pub tax_amount: Option<i64>,
+    /// 金額が税込表示か
+    pub tax_included: bool,
+}
+
+/// 本文から消費税額と税込/税抜表示を抽出する
+///
+/// 税抜表記（税抜・税別・外税）があり、税込表記が一切ない場合のみ税抜表示とみなす。
+/// 「消費税10%対象」のような税率表記は消費税額として扱わない。
+pub fn extract_tax_info(body: &str) -> TaxInfo {
+    let tax_amount = TAX_AMOUNT_RE.captures_iter(body).find_map(|caps| {
+        let m = caps.get(1)?;
+        let rest = body[m.end()..].trim_start();
+        if rest.starts_with('%') || rest.starts_with('％') {
+            return None;
+        }
+        m.as_str().replace(',', "").parse::<i64>().ok()
+    });
+    let tax_included = !(TAX_EXCLUDED_RE.is_match(body) && !TAX_INCLUDED_RE.is_match(body));
+
+    TaxInfo {
+        tax_amount,
+        tax_included,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_tax_info_included_with_amount() {
+        let info = extract_tax_info("合計：¥11,000（税込）\n（うち消費税 ¥1,000）");
+        assert_eq!(
+            info,
+            TaxInfo {
+                tax_amount: Some(1000),
+                tax_included: true,
+            }
+        );
+    }
+
+    #[test]
+    fn test_extract_tax_info_excluded() {
+        let info = extract_tax_info("商品代金 10,000円（税抜）\n消費税：1,000円");
+        assert_eq!(info.tax_amount, Some(1000));
+        assert!(!info.tax_included);
+    }
+
+    #[test]
+    fn test_extract_tax_info_with_rate() {
+        let info = extract_tax_info("消費税額(10%) 500円");
+        assert_eq!(info.tax_amount, Some(500));
+    }
+
+    #[test]
+    fn test_extract_tax_info_ignores_rate_only() {
+        let info = extract_tax_info("消費税10%対象 ¥5,000");
+        assert_eq!(info.tax_amount, None);
+        assert!(info.tax_included);
+    }
+
+    #[test]
+    fn test_extract_tax_info_total_display_notice() {
+        let info = extract_tax_info("（表示の価格は、すべて消費税総額表示です）");
+        assert_eq!(info.tax_amount, None);
+        assert!(info.tax_included);
+    }
+
+    #[test]
+    fn test_extract_tax_info_no_tax_text() {
+        let info = extract_tax_info("ご注文ありがとうございます。");
+        assert_eq!(
+            info,
+            TaxInfo {
+                tax_amount: None,
+                tax_included: true,
+            }
+        );
+    }
+}
@@ -41,6 +41,8 @@ pub fn parse_order_detail_html(html: &str, order_number: &str) -> Result<OrderIn
         subtotal,
         shipping_fee,
         total_amount,
+        tax_amount: None,
+        tax_included: true,
     })
 }
 
@@ -86,6 +88,8 @@ fn try_extract_yohtmlc_items(document: &Html) -> Vec<OrderItem> {
             quantity,
             subtotal: unit_price * quantity,
             image_url: None,
+            item_url: None,
+            release_date: None,
         });
     }
 
@@ -127,6 +131,8 @@ fn try_extract_grid_items(document: &Html) -> Vec<OrderItem> {
             quantity,
             subtotal: unit_price * quantity,
             image_url: None,
+            item_url: None,
+            release_date: None,
         });
     }
 
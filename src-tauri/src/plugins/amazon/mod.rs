@@ -7,6 +7,14 @@
 //! - 旧フォーマット・単一注文（件名: `Amazon.co.jp ご注文の確認`）
 //! - 旧フォーマット・複数注文（件名: `Amazon.co.jpでのご注文`）
 //!
+//! # 対応フォーマット（発送通知）
+//! - 件名: `発送済み:` / `発送済み：`
+//! - 1 通に複数の荷物（分割発送）が含まれる場合は荷物ごとに deliveries を登録し、商品を紐づける
+//!
+//! # 対応フォーマット（キャンセル確認）
+//! - 件名: `キャンセル済み:` / `キャンセル済み：` / `ご注文のキャンセル`
+//! - 記載された商品だけを apply_cancel で減算する
+//!
 //! # 対応フォーマット（配達完了）
 //! - 件名: `ご注文商品はお住まいの建物内の宅配ボックスに配達しました。`
 //! - 件名: `配達完了:` / `配達完了：`
@@ -18,8 +26,8 @@ pub mod parsers;
 use async_trait::async_trait;
 
 use super::{
-    apply_internal_date, derive_shop_domain, DefaultShopSetting, DispatchError, DispatchOutcome,
-    PluginRegistration, VendorPlugin,
+    apply_internal_date, apply_tax_info, derive_shop_domain, DefaultShopSetting, DispatchError,
+    DispatchOutcome, PluginRegistration, VendorPlugin,
 };
 use crate::parsers::EmailParser;
 use crate::repository::SqliteOrderRepository;
@@ -29,7 +37,12 @@ pub struct AmazonPlugin;
 #[async_trait]
 impl VendorPlugin for AmazonPlugin {
     fn parser_types(&self) -> &[&str] {
-        &["amazon_confirm", "amazon_delivery_complete"]
+        &[
+            "amazon_confirm",
+            "amazon_send",
+            "amazon_cancel",
+            "amazon_delivery_complete",
+        ]
     }
 
     fn priority(&self) -> i32 {
@@ -63,6 +76,22 @@ impl VendorPlugin for AmazonPlugin {
                     "注文済み:".to_string(),
                 ]),
             },
+            DefaultShopSetting {
+                shop_name: "Amazon.co.jp".to_string(),
+                sender_address: "shipment-tracking@amazon.co.jp".to_string(),
+                parser_type: "amazon_send".to_string(),
+                subject_filters: Some(vec!["発送済み:".to_string(), "発送済み：".to_string()]),
+            },
+            DefaultShopSetting {
+                shop_name: "Amazon.co.jp".to_string(),
+                sender_address: "order-update@amazon.co.jp".to_string(),
+                parser_type: "amazon_cancel".to_string(),
+                subject_filters: Some(vec![
+                    "キャンセル済み:".to_string(),
+                    "キャンセル済み：".to_string(),
+                    "ご注文のキャンセル".to_string(),
+                ]),
+            },
             DefaultShopSetting {
                 shop_name: "Amazon.co.jp".to_string(),
                 sender_address: "order-update@amazon.co.jp".to_string(),
@@ -92,6 +121,14 @@ impl VendorPlugin for AmazonPlugin {
             return dispatch_delivery_complete(email_id, body, tx).await;
         }
 
+        if parser_type == "amazon_cancel" {
+            return dispatch_cancel(email_id, from_address, body, tx).await;
+        }
+
+        if parser_type == "amazon_send" {
+            return dispatch_send(email_id, from_address, shop_name, body, tx).await;
+        }
+
         if parser_type != "amazon_confirm" {
             return Err(DispatchError::ParseFailed(format!(
                 "amazon: 未対応の parser_type '{parser_type}'"
@@ -143,6 +180,7 @@ impl VendorPlugin for AmazonPlugin {
         // 単一注文
         let mut order_info = parser.parse(body).map_err(DispatchError::ParseFailed)?;
         apply_internal_date(&mut order_info, internal_date);
+        apply_tax_info(&mut order_info, body);
 
         let order_id = SqliteOrderRepository::save_order_in_tx(
             tx,
@@ -164,6 +202,87 @@ impl VendorPlugin for AmazonPlugin {
     }
 }
 
+/// Amazon 発送通知メールを処理する
+///
+/// 分割発送で荷物が複数ある場合は、荷物ごとに同じ注文へ deliveries を登録し商品を紐づける。
+/// 発送日を注文日にしないよう、内部日付による注文日の補完は行わない。
+async fn dispatch_send(
+    email_id: i64,
+    from_address: Option<&str>,
+    shop_name: &str,
+    body: &str,
+    tx: &mut sqlx::Transaction<'_, sqlx::Sqlite>,
+) -> Result<DispatchOutcome, DispatchError> {
+    let parser = parsers::send::AmazonSendParser;
+    let shop_domain = derive_shop_domain(from_address);
+
+    let orders = match parser.parse_multi(body) {
+        Some(result) => result.map_err(DispatchError::ParseFailed)?,
+        None => vec![parser.parse(body).map_err(DispatchError::ParseFailed)?],
+    };
+
+    for order_info in &orders {
+        SqliteOrderRepository::save_order_in_tx(
+            tx,
+            order_info,
+            Some(email_id),
+            shop_domain.clone(),
+            Some(shop_name.to_string()),
+        )
+        .await
+        .map_err(DispatchError::SaveFailed)?;
+    }
+
+    log::debug!(
+        "[amazon_send] email_id={} order_number={} shipments={}",
+        email_id,
+        orders[0].order_number,
+        orders.len(),
+    );
+
+    // 荷物が 1 つなら通常の注文保存と同じ扱いにする
+    if orders.len() == 1 {
+        let order_info = orders.into_iter().next().expect("orders is not empty");
+        return Ok(DispatchOutcome::OrderSaved(Box::new(order_info)));
+    }
+    Ok(DispatchOutcome::MultiOrderSaved(orders))
+}
+
+/// Amazon キャンセル確認メールを処理する（記載された商品を減算）
+async fn dispatch_cancel(
+    email_id: i64,
+    from_address: Option<&str>,
+    body: &str,
+    tx: &mut sqlx::Transaction<'_, sqlx::Sqlite>,
+) -> Result<DispatchOutcome, DispatchError> {
+    let cancels = parsers::cancel::AmazonCancelParser
+        .parse_cancel(body)
+        .map_err(DispatchError::ParseFailed)?;
+    let order_number = cancels[0].order_number.clone();
+    let shop_domain = derive_shop_domain(from_address);
+
+    log::debug!(
+        "[amazon_cancel] email_id={} order_number={} items={}",
+        email_id,
+        order_number,
+        cancels.len()
+    );
+
+    for cancel_info in &cancels {
+        SqliteOrderRepository::apply_cancel_in_tx(
+            tx,
+            cancel_info,
+            email_id,
+            shop_domain.clone(),
+            None,
+        )
+        .await
+        .map_err(DispatchError::SaveFailed)?;
+    }
+
+    Ok(DispatchOutcome::CancelApplied { order_number })
+}
+
 /// Amazon 配達完了メールを処理する
 async fn dispatch_delivery_complete(
     email_id: i64,
@@ -179,12 +298,13 @@ async fn dispatch_delivery_complete(
     );
 
     // 注文番号で orders を検索
-    let order: Option<(i64,)> =
-        sqlx::query_as("SELECT id FROM orders WHERE order_number = ? LIMIT 1")
-            .bind(&info.order_number)
-            .fetch_optional(tx.as_mut())
-            .await
-            .map_err(|e| DispatchError::SaveFailed(format!("DB error: {e}")))?;
+    let order: Option<(i64,)> = sqlx::query_as(
+        "SELECT id FROM orders WHERE order_number = ? AND deleted_at IS NULL LIMIT 1",
+    )
+    .bind(&info.order_number)
+    .fetch_optional(tx.as_mut())
+    .await
+    .map_err(|e| DispatchError::SaveFailed(format!("DB error: {e}")))?;
 
     let Some((order_id,)) = order else {
         log::warn!(
@@ -275,6 +395,8 @@ mod tests {
     fn test_amazon_plugin_parser_types() {
         let plugin = AmazonPlugin;
         assert!(plugin.parser_types().contains(&"amazon_confirm"));
+        assert!(plugin.parser_types().contains(&"amazon_send"));
+        assert!(plugin.parser_types().contains(&"amazon_cancel"));
         assert!(plugin.parser_types().contains(&"amazon_delivery_complete"));
     }
 
@@ -286,7 +408,7 @@ mod tests {
     #[test]
     fn test_amazon_default_shop_settings() {
         let settings = AmazonPlugin.default_shop_settings();
-        assert_eq!(settings.len(), 2);
+        assert_eq!(settings.len(), 4);
 
         let confirm = &settings[0];
         assert_eq!(confirm.sender_address, "auto-confirm@amazon.co.jp");
@@ -296,7 +418,25 @@ mod tests {
         assert!(filters.contains(&"Amazon.co.jpでのご注文".to_string()));
         assert!(filters.contains(&"注文済み:".to_string()));
 
-        let delivery = &settings[1];
+        let send = &settings[1];
+        assert_eq!(send.sender_address, "shipment-tracking@amazon.co.jp");
+        assert_eq!(send.parser_type, "amazon_send");
+        assert!(send
+            .subject_filters
+            .as_ref()
+            .unwrap()
+            .contains(&"発送済み:".to_string()));
+
+        let cancel = &settings[2];
+        assert_eq!(cancel.sender_address, "order-update@amazon.co.jp");
+        assert_eq!(cancel.parser_type, "amazon_cancel");
+        assert!(cancel
+            .subject_filters
+            .as_ref()
+            .unwrap()
+            .contains(&"キャンセル済み:".to_string()));
+
+        let delivery = &settings[3];
         assert_eq!(delivery.sender_address, "order-update@amazon.co.jp");
         assert_eq!(delivery.parser_type, "amazon_delivery_complete");
         let df = delivery.subject_filters.as_ref().unwrap();
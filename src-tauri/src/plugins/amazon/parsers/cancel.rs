@@ -0,0 +1,147 @@
+//! Amazon.co.jp キャンセル確認メール用パーサー
+//!
+//! 送信元: order-update@amazon.co.jp
+//!
+//! 件名: `キャンセル済み:` / `ご注文のキャンセル`
+//!
+//! ```text
+//! 注文がキャンセルされました
+//! お客様のご依頼により、以下の商品のご注文をキャンセルしました。
+//!
+//! 注文番号
+//! 250-XXXXXXX-XXXXXXX
+//!
+//! * 商品A
+//!   数量: 1
+//! ```
+//!
+//! 記載された商品だけを減算する。商品行がない場合は注文全体を消さないようエラーとする。
+
+use super::{ITEM_NAME_RE, ORDER_NUMBER_RE, QUANTITY_RE};
+use crate::parsers::cancel_info::{classify_cancel_reason, CancelInfo};
+
+/// Amazon キャンセル確認メールパーサー
+pub struct AmazonCancelParser;
+
+impl AmazonCancelParser {
+    /// メール本文から商品ごとのキャンセル情報を抽出する
+    pub fn parse_cancel(&self, email_body: &str) -> Result<Vec<CancelInfo>, String> {
+        let order_number = ORDER_NUMBER_RE
+            .captures(email_body)
+            .map(|c| c[1].to_string())
+            .ok_or_else(|| "注文番号が見つかりません".to_string())?;
+
+        // 「お客様のご依頼により、…キャンセルしました」等の理由文（記載がなければ None）
+        let reason_detail = email_body
+            .lines()
+            .map(str::trim)
+            .find(|line| {
+                line.contains("キャンセル") && (line.contains("により") || line.contains("ため"))
+            })
+            .map(str::to_string);
+        let reason = reason_detail.as_deref().map(classify_cancel_reason);
+
+        let mut infos: Vec<CancelInfo> = Vec::new();
+        let mut pending_name: Option<String> = None;
+        let push = |name: String, quantity: i64, infos: &mut Vec<CancelInfo>| {
+            if let Some(existing) = infos.iter_mut().find(|i| i.product_name == name) {
+                existing.cancel_quantity += quantity;
+                return;
+            }
+            infos.push(CancelInfo {
+                order_number: order_number.clone(),
+                product_name: name,
+                cancel_quantity: quantity,
+                reason,
+                reason_detail: reason_detail.clone(),
+            });
+        };
+
+        for line in email_body.lines() {
+            if let Some(cap) = ITEM_NAME_RE.captures(line) {
+                if let Some(name) = pending_name.take() {
+                    push(name, 1, &mut infos);
+                }
+                pending_name = Some(cap[1].to_string());
+            } else if let Some(cap) = QUANTITY_RE.captures(line) {
+                if let Some(name) = pending_name.take() {
+                    push(name, cap[1].parse::<i64>().unwrap_or(1), &mut infos);
+                }
+            }
+        }
+        if let Some(name) = pending_name.take() {
+            push(name, 1, &mut infos);
+        }
+
+        if infos.is_empty() {
+            return Err("キャンセル対象の商品が見つかりません".to_string());
+        }
+        Ok(infos)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parsers::cancel_info::CancelReason;
+
+    const SAMPLE_CANCEL: &str = "\
+Amazon.co.jp
+注文がキャンセルされました
+お客様のご依頼により、以下の商品のご注文をキャンセルしました。
+
+注文番号
+250-1234567-7654321
+
+* HG 1/144 ガンダムエアリアル
+  数量: 2
+* ニッパー
+  数量: 1
+";
+
+    #[test]
+    fn test_parse_cancel_items_and_quantities() {
+        let infos = AmazonCancelParser.parse_cancel(SAMPLE_CANCEL).unwrap();
+        assert_eq!(infos.len(), 2);
+        assert!(infos
+            .iter()
+            .all(|i| i.order_number == "250-1234567-7654321"));
+        assert_eq!(infos[0].product_name, "HG 1/144 ガンダムエアリアル");
+        assert_eq!(infos[0].cancel_quantity, 2);
+        assert_eq!(infos[1].product_name, "ニッパー");
+        assert_eq!(infos[1].cancel_quantity, 1);
+    }
+
+    #[test]
+    fn test_parse_cancel_reason_user_request() {
+        let infos = AmazonCancelParser.parse_cancel(SAMPLE_CANCEL).unwrap();
+        assert_eq!(infos[0].reason, Some(CancelReason::UserRequest));
+        assert_eq!(
+            infos[0].reason_detail.as_deref(),
+            Some("お客様のご依頼により、以下の商品のご注文をキャンセルしました。")
+        );
+    }
+
+    #[test]
+    fn test_parse_cancel_without_reason_and_quantity() {
+        // 理由文・数量行がなければ理由なし・数量 1 とする
+        let body = "注文番号： 250-1234567-7654321\n* 商品A\n";
+        let infos = AmazonCancelParser.parse_cancel(body).unwrap();
+        assert_eq!(infos[0].cancel_quantity, 1);
+        assert_eq!(infos[0].reason, None);
+        assert_eq!(infos[0].reason_detail, None);
+    }
+
+    #[test]
+    fn test_parse_cancel_without_items_is_error() {
+        let body = "注文がキャンセルされました\n注文番号\n250-1234567-7654321\n";
+        assert!(AmazonCancelParser.parse_cancel(body).is_err());
+    }
+
+    #[test]
+    fn test_parse_cancel_without_order_number_is_error() {
+        assert!(AmazonCancelParser
+            .parse_cancel("* 商品A\n  数量: 1\n")
+            .is_err());
+    }
+}
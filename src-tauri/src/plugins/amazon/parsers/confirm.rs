@@ -154,6 +154,8 @@ fn parse_new_format(body: &str) -> Result<OrderInfo, String> {
         subtotal: None,
         shipping_fee: None,
         total_amount,
+        tax_amount: None,
+        tax_included: true,
     })
 }
 
@@ -189,6 +191,8 @@ fn extract_new_items(body: &str) -> Vec<OrderItem> {
                 quantity,
                 subtotal: unit_price * quantity,
                 image_url: None,
+                item_url: None,
+                release_date: None,
             }
         })
         .collect()
@@ -253,6 +257,8 @@ fn parse_very_old_format(body: &str) -> Result<OrderInfo, String> {
         subtotal,
         shipping_fee,
         total_amount,
+        tax_amount: None,
+        tax_included: true,
     })
 }
 
@@ -298,6 +304,8 @@ fn extract_very_old_items(body: &str) -> Vec<OrderItem> {
             quantity,
             subtotal: unit_price * quantity,
             image_url: None,
+            item_url: None,
+            release_date: None,
         });
     }
 
@@ -391,6 +399,8 @@ fn parse_legacy_all_orders(body: &str) -> Result<Vec<OrderInfo>, String> {
             subtotal,
             shipping_fee,
             total_amount,
+            tax_amount: None,
+            tax_included: true,
         });
     }
 
@@ -470,6 +480,8 @@ fn extract_legacy_items(section: &str) -> Vec<OrderItem> {
                             quantity: 1,
                             subtotal: unit_price,
                             image_url: None,
+                            item_url: None,
+                            release_date: None,
                         });
                         i = j + 1;
                         continue;
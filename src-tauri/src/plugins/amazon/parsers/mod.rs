@@ -1,2 +1,17 @@
+pub mod cancel;
 pub mod confirm;
 pub mod delivery_complete;
+pub mod send;
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+/// 商品名行（`* 商品名`）。発送通知・キャンセルメール共通
+pub(super) static ITEM_NAME_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^\s*\* (.+?)\s*$").unwrap());
+/// 商品名行に続く数量行（`  数量: N`）
+pub(super) static QUANTITY_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^\s*数量[：:]\s*(\d+)").unwrap());
+/// 注文番号（`注文番号` の直後の行、または `注文番号： ` に続く）
+pub(super) static ORDER_NUMBER_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"注文番号[：:]?\s*(\d{3}-\d{7}-\d{7})").unwrap());
@@ -0,0 +1,285 @@
+//! Amazon.co.jp 発送通知メール用パーサー
+//!
+//! 送信元: shipment-tracking@amazon.co.jp
+//!
+//! Amazon は 1 注文を複数の荷物に分けて発送することがあり、1 通の発送通知に
+//! 複数の荷物（配送業者・追跡番号・商品）が並ぶ場合がある。
+//!
+//! ```text
+//! 注文番号
+//! 250-XXXXXXX-XXXXXXX
+//!
+//! 配送業者: ヤマト運輸
+//! 追跡番号: 1234-5678-9012
+//! * 商品A
+//!   数量: 1
+//!
+//! 配送業者: 日本郵便
+//! 追跡番号: 123456789012
+//! * 商品B
+//!   数量: 2
+//! ```
+//!
+//! 荷物は `配送業者` 行で区切る。最初の `配送業者` 行より前に並ぶ商品は最初の荷物に含める。
+//! 追跡番号のない荷物（Amazon 自社配送など）は `tracking_number` を空文字列とし、shipped で登録する。
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+use super::{ITEM_NAME_RE, ORDER_NUMBER_RE, QUANTITY_RE};
+use crate::parsers::{DeliveryInfo, EmailParser, OrderInfo, OrderItem};
+use crate::plugins::tracking_url_for_carrier;
+
+/// Amazon 発送通知メールパーサー
+pub struct AmazonSendParser;
+
+/// 配送業者の記載がない場合の配送業者名
+const DEFAULT_AMAZON_CARRIER: &str = "Amazon";
+
+static CARRIER_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^\s*配送業者\s*[：:]\s*(.+?)\s*$").unwrap());
+static TRACKING_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^\s*(?:追跡番号|お問い合わせ伝票番号)\s*[：:]\s*([0-9A-Za-z][0-9A-Za-z-]*)")
+        .unwrap()
+});
+
+/// 1 つの荷物
+#[derive(Debug, Default)]
+struct Shipment {
+    carrier: Option<String>,
+    tracking_number: Option<String>,
+    items: Vec<OrderItem>,
+}
+
+impl Shipment {
+    fn into_delivery_info(self) -> (DeliveryInfo, Vec<OrderItem>) {
+        let carrier = self
+            .carrier
+            .unwrap_or_else(|| DEFAULT_AMAZON_CARRIER.to_string());
+        let tracking_number = self.tracking_number.unwrap_or_default();
+        let carrier_url = if tracking_number.is_empty() {
+            None
+        } else {
+            tracking_url_for_carrier(&carrier)
+        };
+        let info = DeliveryInfo {
+            carrier,
+            tracking_number,
+            delivery_date: None,
+            delivery_time: None,
+            carrier_url,
+            delivery_status: Some("shipped".to_string()),
+        };
+        (info, self.items)
+    }
+}
+
+impl EmailParser for AmazonSendParser {
+    /// 荷物が 1 つの発送通知をパースする
+    fn parse(&self, email_body: &str) -> Result<OrderInfo, String> {
+        let mut orders = parse_shipments(email_body)?;
+        if orders.len() > 1 {
+            log::warn!(
+                "[amazon_send] parse() called for {} shipments, using the first one",
+                orders.len()
+            );
+        }
+        Ok(orders.remove(0))
+    }
+
+    /// 複数の荷物を含む発送通知をパースする
+    ///
+    /// 荷物ごとに同じ注文番号の `OrderInfo` を返し、保存時に荷物（deliveries）と商品を紐づける。
+    /// 荷物が 1 つの場合やパースできない場合は `None` を返し、`parse()` に委譲する。
+    fn parse_multi(&self, email_body: &str) -> Option<Result<Vec<OrderInfo>, String>> {
+        parse_shipments(email_body)
+            .ok()
+            .filter(|orders| orders.len() > 1)
+            .map(Ok)
+    }
+}
+
+/// 本文を荷物ごとの `OrderInfo` に分割する
+fn parse_shipments(body: &str) -> Result<Vec<OrderInfo>, String> {
+    let order_number = ORDER_NUMBER_RE
+        .captures(body)
+        .map(|c| c[1].to_string())
+        .ok_or_else(|| "注文番号が見つかりません".to_string())?;
+
+    let shipments = split_shipments(body);
+    if shipments.iter().all(|s| s.items.is_empty()) {
+        return Err("発送商品が見つかりません".to_string());
+    }
+
+    Ok(shipments
+        .into_iter()
+        .filter(|s| !s.items.is_empty())
+        .map(|shipment| {
+            let (delivery_info, items) = shipment.into_delivery_info();
+            OrderInfo {
+                order_number: order_number.clone(),
+                order_date: None,
+                delivery_address: None,
+                delivery_info: Some(delivery_info),
+                items,
+                // 荷物ごとの金額は記載されないため None とする
+                subtotal: None,
+                shipping_fee: None,
+                total_amount: None,
+                tax_amount: None,
+                tax_included: true,
+            }
+        })
+        .collect())
+}
+
+/// `配送業者` 行を区切りとして荷物を抽出する
+fn split_shipments(body: &str) -> Vec<Shipment> {
+    let mut shipments: Vec<Shipment> = vec![Shipment::default()];
+    let mut seen_carrier = false;
+    let mut pending_item: Option<String> = None;
+
+    for line in body.lines() {
+        if let Some(cap) = CARRIER_RE.captures(line) {
+            flush_item(&mut shipments, &mut pending_item, 1);
+            // 最初の配送業者行より前の商品は最初の荷物に含める
+            if seen_carrier {
+                shipments.push(Shipment::default());
+            }
+            seen_carrier = true;
+            if let Some(current) = shipments.last_mut() {
+                current.carrier = Some(cap[1].to_string());
+            }
+        } else if let Some(cap) = TRACKING_RE.captures(line) {
+            if let Some(current) = shipments.last_mut() {
+                // 追跡 API・URL に合わせてハイフンは除去する
+                current.tracking_number = Some(cap[1].replace('-', ""));
+            }
+        } else if let Some(cap) = ITEM_NAME_RE.captures(line) {
+            flush_item(&mut shipments, &mut pending_item, 1);
+            pending_item = Some(cap[1].to_string());
+        } else if let Some(cap) = QUANTITY_RE.captures(line) {
+            let quantity = cap[1].parse::<i64>().unwrap_or(1);
+            flush_item(&mut shipments, &mut pending_item, quantity);
+        }
+    }
+    flush_item(&mut shipments, &mut pending_item, 1);
+
+    shipments
+}
+
+/// 読み取り中の商品を現在の荷物に追加する
+fn flush_item(shipments: &mut [Shipment], pending_item: &mut Option<String>, quantity: i64) {
+    let (Some(name), Some(current)) = (pending_item.take(), shipments.last_mut()) else {
+        return;
+    };
+    current.items.push(OrderItem {
+        name,
+        manufacturer: None,
+        model_number: None,
+        unit_price: 0,
+        quantity,
+        subtotal: 0,
+        image_url: None,
+        item_url: None,
+        release_date: None,
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE_SPLIT: &str = "\
+Amazon.co.jp
+ご注文の商品を発送しました
+
+注文番号
+250-1234567-7654321
+
+配送業者: ヤマト運輸
+追跡番号: 1234-5678-9012
+* 30MS オプションボディパーツ タイプS01
+  数量: 1
+
+配送業者: 日本郵便
+追跡番号: 123456789012
+* HG 1/144 ガンダムエアリアル
+  数量: 2
+* ニッパー
+  数量: 1
+";
+
+    const SAMPLE_SINGLE: &str = "\
+Amazon.co.jp
+ご注文の商品を発送しました
+
+注文番号： 250-1234567-7654321
+
+* HG 1/144 ガンダムエアリアル
+  数量: 1
+
+配送業者: Amazon
+";
+
+    #[test]
+    fn test_parse_multi_split_shipments() {
+        let orders = AmazonSendParser.parse_multi(SAMPLE_SPLIT).unwrap().unwrap();
+        assert_eq!(orders.len(), 2);
+        assert!(orders
+            .iter()
+            .all(|o| o.order_number == "250-1234567-7654321"));
+
+        let first = orders[0].delivery_info.as_ref().unwrap();
+        assert_eq!(first.carrier, "ヤマト運輸");
+        assert_eq!(first.tracking_number, "123456789012");
+        assert!(first.carrier_url.is_some());
+        assert_eq!(orders[0].items.len(), 1);
+        assert_eq!(
+            orders[0].items[0].name,
+            "30MS オプションボディパーツ タイプS01"
+        );
+
+        let second = orders[1].delivery_info.as_ref().unwrap();
+        assert_eq!(second.carrier, "日本郵便");
+        assert_eq!(second.tracking_number, "123456789012");
+        let names: Vec<(&str, i64)> = orders[1]
+            .items
+            .iter()
+            .map(|i| (i.name.as_str(), i.quantity))
+            .collect();
+        assert_eq!(
+            names,
+            vec![("HG 1/144 ガンダムエアリアル", 2), ("ニッパー", 1)]
+        );
+    }
+
+    #[test]
+    fn test_single_shipment_delegates_to_parse() {
+        assert!(AmazonSendParser.parse_multi(SAMPLE_SINGLE).is_none());
+
+        // 配送業者行より前の商品は最初の荷物に含め、追跡番号なしでも shipped で登録する
+        let order = AmazonSendParser.parse(SAMPLE_SINGLE).unwrap();
+        assert_eq!(order.order_number, "250-1234567-7654321");
+        assert_eq!(order.items.len(), 1);
+        let info = order.delivery_info.unwrap();
+        assert_eq!(info.carrier, "Amazon");
+        assert_eq!(info.tracking_number, "");
+        assert_eq!(info.carrier_url, None);
+        assert_eq!(info.delivery_status.as_deref(), Some("shipped"));
+    }
+
+    #[test]
+    fn test_parse_without_items_is_error() {
+        let body = "注文番号\n250-1234567-7654321\n配送業者: ヤマト運輸\n追跡番号: 123456789012\n";
+        assert!(AmazonSendParser.parse(body).is_err());
+        // parse_multi は parse() に委譲し、エラーは parse() 側で返す
+        assert!(AmazonSendParser.parse_multi(body).is_none());
+    }
+
+    #[test]
+    fn test_parse_without_order_number_is_error() {
+        let body = "配送業者: ヤマト運輸\n* 商品A\n  数量: 1\n";
+        assert!(AmazonSendParser.parse(body).is_err());
+    }
+}
@@ -8,19 +8,23 @@
 //! | amiami_rakuten_confirm   | amiami@shop.rakuten.co.jp        | 楽天 注文確認      |
 //! | amiami_rakuten_send      | amiami_2@shop.rakuten.co.jp      | 楽天 発送案内      |
 //! | amiami_confirm           | order@amiami.com                 | 直販 注文確認      |
-//! | amiami_send              | shop@amiami.com                  | 直販 発送案内      |
+//! | amiami_confirm_yoyaku    | order@amiami.com                 | 直販 予約内容確認  |
+//! | amiami_en_confirm        | order@amiami.com                 | 英語版（海外向け） 注文確認 |
+//! | amiami_send              | shop@amiami.com                  | 直販 発送案内・商品発送のご案内 |
 //! | amiami_cancel            | order@amiami.com / shop@amiami.com | キャンセル通知   |
+//! | amiami_delay             | shop@amiami.com                  | 発売延期のお知らせ |
 
 pub mod parsers;
 
 use async_trait::async_trait;
 
+use crate::logic::language::EmailLanguage;
 use crate::parsers::EmailParser;
 use crate::repository::SqliteOrderRepository;
 
 use super::{
-    apply_internal_date, derive_shop_domain, DefaultShopSetting, DispatchError, DispatchOutcome,
-    PluginRegistration, VendorPlugin,
+    apply_internal_date, apply_tax_info, derive_shop_domain, DefaultShopSetting, DispatchError,
+    DispatchOutcome, PluginRegistration, VendorPlugin,
 };
 
 pub struct AmiamiPlugin;
@@ -32,8 +36,11 @@ impl VendorPlugin for AmiamiPlugin {
             "amiami_rakuten_confirm",
             "amiami_rakuten_send",
             "amiami_confirm",
+            "amiami_confirm_yoyaku",
+            "amiami_en_confirm",
             "amiami_send",
             "amiami_cancel",
+            "amiami_delay",
         ]
     }
 
@@ -48,8 +55,12 @@ impl VendorPlugin for AmiamiPlugin {
             )),
             "amiami_rakuten_send" => Some(Box::new(parsers::rakuten_send::AmiamiRakutenSendParser)),
             "amiami_confirm" => Some(Box::new(parsers::confirm::AmiamiConfirmParser)),
+            "amiami_confirm_yoyaku" => {
+                Some(Box::new(parsers::confirm_yoyaku::AmiamiConfirmYoyakuParser))
+            }
+            "amiami_en_confirm" => Some(Box::new(parsers::en_confirm::AmiamiEnConfirmParser)),
             "amiami_send" => Some(Box::new(parsers::send::AmiamiSendParser)),
-            // cancel は dispatch() 内で直接処理するため get_parser は None を返す
+            // cancel / delay は dispatch() 内で直接処理するため get_parser は None を返す
             _ => None,
         }
     }
@@ -78,11 +89,23 @@ impl VendorPlugin for AmiamiPlugin {
                 parser_type: "amiami_confirm".to_string(),
                 subject_filters: Some(vec!["内容確認".to_string()]),
             },
+            DefaultShopSetting {
+                shop_name: "あみあみ".to_string(),
+                sender_address: "order@amiami.com".to_string(),
+                parser_type: "amiami_confirm_yoyaku".to_string(),
+                subject_filters: Some(vec!["ご予約内容の確認".to_string()]),
+            },
+            DefaultShopSetting {
+                shop_name: "あみあみ".to_string(),
+                sender_address: "order@amiami.com".to_string(),
+                parser_type: "amiami_en_confirm".to_string(),
+                subject_filters: Some(vec!["Order Confirmation".to_string()]),
+            },
             DefaultShopSetting {
                 shop_name: "あみあみ".to_string(),
                 sender_address: "shop@amiami.com".to_string(),
                 parser_type: "amiami_send".to_string(),
-                subject_filters: Some(vec!["発送案内".to_string()]),
+                subject_filters: Some(vec!["発送案内".to_string(), "発送のご案内".to_string()]),
             },
             // キャンセル: order@amiami.com からの「キャンセルご依頼の内容確認」
             DefaultShopSetting {
@@ -98,9 +121,23 @@ impl VendorPlugin for AmiamiPlugin {
                 parser_type: "amiami_cancel".to_string(),
                 subject_filters: Some(vec!["キャンセル".to_string()]),
             },
+            // 発売延期: shop@amiami.com からの「発売延期のお知らせ」
+            DefaultShopSetting {
+                shop_name: "あみあみ".to_string(),
+                sender_address: "shop@amiami.com".to_string(),
+                parser_type: "amiami_delay".to_string(),
+                subject_filters: Some(vec!["発売延期".to_string()]),
+            },
         ]
     }
 
+    fn parser_language(&self, parser_type: &str) -> EmailLanguage {
+        match parser_type {
+            "amiami_en_confirm" => EmailLanguage::English,
+            _ => EmailLanguage::Japanese,
+        }
+    }
+
     #[allow(clippy::too_many_arguments)]
     async fn dispatch(
         &self,
@@ -116,19 +153,53 @@ impl VendorPlugin for AmiamiPlugin {
 
         // ── キャンセル ──────────────────────────────────────────────────────────
         if parser_type == "amiami_cancel" {
-            let cancel_info = parsers::cancel::AmiamiCancelParser
-                .parse_cancel(body)
+            let cancels = parsers::cancel::AmiamiCancelParser
+                .parse_cancels(body)
+                .map_err(DispatchError::ParseFailed)?;
+            let order_number = cancels[0].order_number.clone();
+
+            log::debug!(
+                "[amiami_cancel] email_id={} order_number={} items={}",
+                email_id,
+                order_number,
+                cancels.len()
+            );
+
+            for cancel_info in &cancels {
+                SqliteOrderRepository::apply_cancel_in_tx(
+                    tx,
+                    cancel_info,
+                    email_id,
+                    shop_domain.clone(),
+                    None,
+                )
+                .await
+                .map_err(DispatchError::SaveFailed)?;
+            }
+
+            return Ok(DispatchOutcome::CancelApplied { order_number });
+        }
+
+        // ── 発売延期 ──────────────────────────────────────────────────────────
+        if parser_type == "amiami_delay" {
+            // 年の記載がない発売予定（`2月下旬`）はメール受信日（JST）を基準に年を補う
+            let reference = internal_date
+                .and_then(chrono::DateTime::from_timestamp_millis)
+                .map(|dt| dt.with_timezone(&chrono_tz::Asia::Tokyo).date_naive());
+            let delay_info = parsers::delay::AmiamiDelayParser
+                .parse_delay(body, reference)
                 .map_err(DispatchError::ParseFailed)?;
 
             log::debug!(
-                "[amiami_cancel] email_id={} order_number={}",
+                "[amiami_delay] email_id={} order_number={} items={}",
                 email_id,
-                cancel_info.order_number
+                delay_info.order_number,
+                delay_info.items.len()
             );
 
-            SqliteOrderRepository::apply_cancel_in_tx(
+            SqliteOrderRepository::apply_release_delay_in_tx(
                 tx,
-                &cancel_info,
+                &delay_info,
                 email_id,
                 shop_domain,
                 None,
@@ -136,8 +207,8 @@ impl VendorPlugin for AmiamiPlugin {
             .await
             .map_err(DispatchError::SaveFailed)?;
 
-            return Ok(DispatchOutcome::CancelApplied {
-                order_number: cancel_info.order_number,
+            return Ok(DispatchOutcome::ReleaseDateChanged {
+                order_number: delay_info.order_number,
             });
         }
 
@@ -150,7 +221,15 @@ impl VendorPlugin for AmiamiPlugin {
         };
 
         // 注文確認メールは注文日が本文に含まれないため internal_date で補完する
-        if matches!(parser_type, "amiami_rakuten_confirm" | "amiami_confirm") {
+        if matches!(
+            parser_type,
+            "amiami_rakuten_confirm" | "amiami_confirm" | "amiami_confirm_yoyaku"
+        ) {
+            apply_internal_date(&mut order_info, internal_date);
+            apply_tax_info(&mut order_info, body);
+        }
+        // 英語版は日本語の消費税表記がないため apply_tax_info は使わない
+        if parser_type == "amiami_en_confirm" {
             apply_internal_date(&mut order_info, internal_date);
         }
 
@@ -196,6 +275,31 @@ mod tests {
         assert!(plugin.get_parser("amiami_cancel").is_none());
     }
 
+    #[test]
+    fn test_amiami_plugin_delay_is_dispatch_only() {
+        let plugin = AmiamiPlugin;
+        assert!(plugin.parser_types().contains(&"amiami_delay"));
+        assert!(plugin.get_parser("amiami_delay").is_none());
+        assert!(plugin
+            .default_shop_settings()
+            .iter()
+            .any(|s| s.parser_type == "amiami_delay" && s.sender_address == "shop@amiami.com"));
+    }
+
+    #[test]
+    fn test_amiami_plugin_en_confirm_is_english() {
+        let plugin = AmiamiPlugin;
+        assert!(plugin.get_parser("amiami_en_confirm").is_some());
+        assert_eq!(
+            plugin.parser_language("amiami_en_confirm"),
+            EmailLanguage::English
+        );
+        assert_eq!(
+            plugin.parser_language("amiami_confirm"),
+            EmailLanguage::Japanese
+        );
+    }
+
     #[test]
     fn test_amiami_plugin_default_shop_settings_includes_cancel() {
         let settings = AmiamiPlugin.default_shop_settings();
@@ -14,7 +14,8 @@ use regex::Regex;
 /// 件名：`あみあみ　キャンセルを承りました`
 /// 注文番号形式：`ご注文226512861以下商品につきまして`
 ///
-/// いずれも `product_name = ""` で全件キャンセルとして処理する。
+/// 「キャンセルを承りました」に `[商品名] x 数量` の商品行があれば商品ごとの部分キャンセル、
+/// それ以外（「全てキャンセル」等）は `product_name = ""` で全件キャンセルとして処理する。
 pub struct AmiamiCancelParser;
 
 /// 注文番号を抽出するパターン（9桁以上）
@@ -26,6 +27,10 @@ static ORDER_NUMBER_RE: Lazy<Regex> = Lazy::new(|| {
     Regex::new(r"(?:受注番号\s*[：:]\s*|ご注文)(\d{9,})").expect("Invalid ORDER_NUMBER_RE")
 });
 
+/// キャンセル対象の商品行（`[商品名] x 1`。商品名自体が `[コトブキヤ]` 等の角括弧を含む）
+static CANCEL_ITEM_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^\[(.+)\]\s*[xX×]\s*(\d+)\s*$").expect("Invalid CANCEL_ITEM_RE"));
+
 impl AmiamiCancelParser {
     /// メール本文からキャンセル情報を抽出する
     pub fn parse_cancel(&self, email_body: &str) -> Result<CancelInfo, String> {
@@ -40,8 +45,42 @@ impl AmiamiCancelParser {
             // product_name を空にして全件削除として処理する
             product_name: String::new(),
             cancel_quantity: 0,
+            reason: None,
+            reason_detail: None,
         })
     }
+
+    /// メール本文から商品ごとのキャンセル情報を抽出する
+    ///
+    /// 商品行がない・「全てキャンセル」の場合は全件キャンセル 1 件を返す。
+    pub fn parse_cancels(&self, email_body: &str) -> Result<Vec<CancelInfo>, String> {
+        let whole = self.parse_cancel(email_body)?;
+        if email_body.contains("全てキャンセル") {
+            return Ok(vec![whole]);
+        }
+
+        let cancels: Vec<CancelInfo> = email_body
+            .lines()
+            .filter_map(|line| CANCEL_ITEM_RE.captures(line.trim()))
+            .filter_map(|caps| {
+                let quantity: i64 = caps[2].parse().ok()?;
+                Some(CancelInfo {
+                    order_number: whole.order_number.clone(),
+                    product_name: caps[1].trim().to_string(),
+                    cancel_quantity: quantity,
+                    reason: None,
+                    reason_detail: None,
+                })
+            })
+            .filter(|c| !c.product_name.is_empty() && c.cancel_quantity > 0)
+            .collect();
+
+        if cancels.is_empty() {
+            Ok(vec![whole])
+        } else {
+            Ok(cancels)
+        }
+    }
 }
 
 #[cfg(test)]
@@ -107,6 +146,38 @@ mod tests {
         assert!(result.product_name.is_empty());
     }
 
+    #[test]
+    fn test_parse_cancels_confirmed_lists_items() {
+        let body = r#"　ご注文226512861以下商品につきまして、キャンセルとさせて頂きましたことご連絡さしあげます。
+
+[メガミデバイス PUNI☆MOFU トゥ 1/1 プラモデル[コトブキヤ]《１１月予約》] x 1
+[HG 1/144 ガンダムエアリアル[BANDAI SPIRITS]] x 2
+"#;
+        let cancels = AmiamiCancelParser.parse_cancels(body).unwrap();
+        assert_eq!(cancels.len(), 2);
+        assert_eq!(cancels[0].order_number, "226512861");
+        assert_eq!(
+            cancels[0].product_name,
+            "メガミデバイス PUNI☆MOFU トゥ 1/1 プラモデル[コトブキヤ]《１１月予約》"
+        );
+        assert_eq!(cancels[0].cancel_quantity, 1);
+        assert_eq!(
+            cancels[1].product_name,
+            "HG 1/144 ガンダムエアリアル[BANDAI SPIRITS]"
+        );
+        assert_eq!(cancels[1].cancel_quantity, 2);
+    }
+
+    #[test]
+    fn test_parse_cancels_request_is_whole_order() {
+        // 「全てキャンセル」は商品行（`[コード] : 商品名`）があっても全件キャンセル
+        let cancels = AmiamiCancelParser
+            .parse_cancels(sample_cancel_request())
+            .unwrap();
+        assert_eq!(cancels.len(), 1);
+        assert!(cancels[0].product_name.is_empty());
+    }
+
     #[test]
     fn test_parse_cancel_no_order_number_returns_error() {
         let body = "キャンセルリクエストを承りました。\n全てキャンセル";
@@ -0,0 +1,134 @@
+use super::{
+    body_to_lines, extract_direct_items, extract_direct_shipping_fee, extract_direct_subtotal,
+    extract_direct_total, extract_order_number, extract_order_number_quoted,
+};
+use crate::parsers::{EmailParser, OrderInfo};
+
+/// あみあみ直販 予約受付メール用パーサー
+///
+/// 件名：`あみあみ ご予約内容の確認[219908570]`
+/// 送信元：`order@amiami.com`
+///
+/// 商品ブロックは注文確認メールと同一形式。予約商品は商品名の `【25年8月予約】` や
+/// ブロック内の `発売予定：2025年8月発売予定` から発売予定を抽出し、`OrderItem::release_date` に設定する。
+/// 注文番号は `受注番号 "219908570"` 形式を優先し、なければ `受注番号：219908570` 形式を使う。
+/// 注文日は本文に含まれないため、`dispatch()` 側で `apply_internal_date()` を使用する。
+pub struct AmiamiConfirmYoyakuParser;
+
+impl EmailParser for AmiamiConfirmYoyakuParser {
+    fn parse(&self, email_body: &str) -> Result<OrderInfo, String> {
+        let body_lines = body_to_lines(email_body);
+        let lines: Vec<&str> = body_lines.iter().map(|s| s.as_str()).collect();
+
+        let order_number = extract_order_number_quoted(&lines)
+            .or_else(|| extract_order_number(&lines))
+            .ok_or_else(|| "Order number not found".to_string())?;
+
+        let items = extract_direct_items(&lines);
+        if items.is_empty() {
+            return Err("No items found".to_string());
+        }
+
+        let subtotal = extract_direct_subtotal(&lines);
+        let shipping_fee = extract_direct_shipping_fee(&lines);
+        let total_amount = extract_direct_total(&lines);
+
+        Ok(OrderInfo {
+            order_number,
+            order_date: None, // internal_date で補完
+            delivery_address: None,
+            delivery_info: None,
+            items,
+            subtotal,
+            shipping_fee,
+            total_amount,
+            tax_amount: None,
+            tax_included: true,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parsers::release_date::{FuzzyReleaseDate, ReleaseDatePrecision};
+
+    fn sample_confirm_yoyaku() -> &'static str {
+        r#"「あみあみ」をご利用頂き、誠にありがとうございます。
+
+お客様のご予約は受注番号 "219912345"にて承りました。
+商品の発売後、順次発送いたします。
+
+◆受注番号　　　：219912345
+◆お支払い方法　：クレジットカード
+商品名：ねんどろいど 初音ミク 16th ver.[グッドスマイルカンパニー]【25年8月予約】
+単価：\5,980
+個数：1
+小計：\5,980
+
+商品名：HG 1/144 ガンダムエアリアル改修型 プラモデル[BANDAI SPIRITS]
+発売予定：2025年10月発売予定
+単価：\1,980
+個数：2
+小計：\3,960
+
+
+●小計　　　　　：\9,940
+●送料　　　　　：\0
+●合計　　　　　：9,940円
+"#
+    }
+
+    #[test]
+    fn test_parse_confirm_yoyaku_order_number() {
+        let order = AmiamiConfirmYoyakuParser
+            .parse(sample_confirm_yoyaku())
+            .unwrap();
+        assert_eq!(order.order_number, "219912345");
+    }
+
+    #[test]
+    fn test_parse_confirm_yoyaku_release_dates() {
+        let order = AmiamiConfirmYoyakuParser
+            .parse(sample_confirm_yoyaku())
+            .unwrap();
+        assert_eq!(order.items.len(), 2);
+        assert_eq!(
+            order.items[0].release_date,
+            FuzzyReleaseDate::month(2025, 8, ReleaseDatePrecision::Month)
+        );
+        assert_eq!(
+            order.items[1].release_date,
+            FuzzyReleaseDate::month(2025, 10, ReleaseDatePrecision::Month)
+        );
+        assert_eq!(order.items[1].quantity, 2);
+        assert_eq!(order.items[1].subtotal, 3960);
+    }
+
+    #[test]
+    fn test_parse_confirm_yoyaku_amounts() {
+        let order = AmiamiConfirmYoyakuParser
+            .parse(sample_confirm_yoyaku())
+            .unwrap();
+        assert_eq!(order.subtotal, Some(9940));
+        assert_eq!(order.shipping_fee, Some(0));
+        assert_eq!(order.total_amount, Some(9940));
+    }
+
+    #[test]
+    fn test_parse_confirm_yoyaku_colon_order_number() {
+        let body = "受注番号：219912345\n商品名：商品A【25年9月予約】\n単価：\\500\n個数：1\n小計：\\500\n";
+        let order = AmiamiConfirmYoyakuParser.parse(body).unwrap();
+        assert_eq!(order.order_number, "219912345");
+        assert_eq!(
+            order.items[0].release_date,
+            FuzzyReleaseDate::month(2025, 9, ReleaseDatePrecision::Month)
+        );
+    }
+
+    #[test]
+    fn test_parse_confirm_yoyaku_no_items_returns_error() {
+        let body = r#"お客様のご予約は受注番号 "219912345"にて承りました。"#;
+        assert!(AmiamiConfirmYoyakuParser.parse(body).is_err());
+    }
+}
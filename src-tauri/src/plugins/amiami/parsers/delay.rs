@@ -0,0 +1,178 @@
+use crate::parsers::delay_info::{DelayInfo, DelayedItem};
+use crate::parsers::release_date::parse_fuzzy_release_date;
+use chrono::NaiveDate;
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+/// あみあみ 発売延期のお知らせメール用パーサー
+///
+/// 件名：`あみあみ　発売延期のお知らせ`
+/// 送信元：`shop@amiami.com`
+///
+/// `[商品名] x 数量` の商品行に続く `旧発売予定：2024年11月` / `新発売予定：2025年2月下旬` から
+/// 商品ごとの発売予定の変更を抽出する。商品行がなく新発売予定のみの場合は注文内の全商品を対象とする。
+/// 発売予定は「2月下旬」「2025年春」等のあいまいな表記も `parse_fuzzy_release_date` で正規化する
+/// （年の記載がなければメール受信日を基準に補う）。
+pub struct AmiamiDelayParser;
+
+/// 注文番号（`受注番号 : 226512861` / `ご注文226512861の下記商品`）
+static ORDER_NUMBER_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?:受注番号\s*[：:]\s*|ご注文)(\d{9,})").expect("Invalid ORDER_NUMBER_RE")
+});
+
+/// 延期対象の商品行（`[商品名] x 1`。商品名自体が `[コトブキヤ]` 等の角括弧を含む）
+static DELAY_ITEM_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^\[(.+)\]\s*[xX×]\s*\d+\s*$").expect("Invalid DELAY_ITEM_RE"));
+
+/// `旧発売予定：2024年11月` / `変更前発売時期：2024年11月`
+static OLD_RELEASE_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^(?:旧|変更前の?)発売(?:予定|時期|日)?\s*[：:]\s*(.+)$")
+        .expect("Invalid OLD_RELEASE_RE")
+});
+
+/// `新発売予定：2025年2月下旬` / `変更後発売時期：2025年春`
+static NEW_RELEASE_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^(?:新|変更後の?)発売(?:予定|時期|日)?\s*[：:]\s*(.+)$")
+        .expect("Invalid NEW_RELEASE_RE")
+});
+
+impl AmiamiDelayParser {
+    /// メール本文から発売延期情報を抽出する
+    ///
+    /// `reference` は年の記載がない発売予定（`2月下旬`）の年を補うための基準日（メール受信日）。
+    /// 注文番号または新しい発売予定が 1 件も見つからない場合はエラーを返す。
+    pub fn parse_delay(
+        &self,
+        email_body: &str,
+        reference: Option<NaiveDate>,
+    ) -> Result<DelayInfo, String> {
+        let order_number = ORDER_NUMBER_RE
+            .captures(email_body)
+            .map(|c| c[1].to_string())
+            .ok_or_else(|| "Order number not found".to_string())?;
+
+        let mut items = Vec::new();
+        let mut current_name: Option<String> = None;
+        let mut current_old = None;
+
+        for line in email_body.lines() {
+            let trimmed = line.trim();
+
+            if let Some(caps) = DELAY_ITEM_RE.captures(trimmed) {
+                current_name = Some(caps[1].trim().to_string());
+                current_old = None;
+                continue;
+            }
+
+            if let Some(caps) = OLD_RELEASE_RE.captures(trimmed) {
+                current_old = parse_fuzzy_release_date(&caps[1], reference);
+                continue;
+            }
+
+            if let Some(caps) = NEW_RELEASE_RE.captures(trimmed) {
+                if let Some(new_release_date) = parse_fuzzy_release_date(&caps[1], reference) {
+                    items.push(DelayedItem {
+                        product_name: current_name.take().unwrap_or_default(),
+                        old_release_date: current_old.take(),
+                        new_release_date,
+                    });
+                }
+            }
+        }
+
+        if items.is_empty() {
+            return Err("New release date not found".to_string());
+        }
+
+        Ok(DelayInfo {
+            order_number,
+            items,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parsers::release_date::{FuzzyReleaseDate, ReleaseDatePrecision};
+
+    fn sample_delay() -> &'static str {
+        r#"お客様へ
+
+　いつもあみあみをご利用いただきありがとうございます。
+
+　ご注文226512861の下記商品につきまして、メーカーより発売延期の連絡がございましたのでお知らせいたします。
+
+[メガミデバイス PUNI☆MOFU トゥ 1/1 プラモデル[コトブキヤ]《１１月予約》] x 1
+旧発売予定：2024年11月
+新発売予定：2月下旬
+
+[ねんどろいど 初音ミク[グッドスマイルカンパニー]] x 2
+旧発売予定：2024年12月
+新発売予定：2025年春
+
+　ご迷惑をおかけいたしますが、何卒よろしくお願い申し上げます。
+
+2024.10.20
+"#
+    }
+
+    fn reference() -> Option<NaiveDate> {
+        NaiveDate::from_ymd_opt(2024, 10, 20)
+    }
+
+    #[test]
+    fn test_parse_delay_items() {
+        let info = AmiamiDelayParser
+            .parse_delay(sample_delay(), reference())
+            .unwrap();
+        assert_eq!(info.order_number, "226512861");
+        assert_eq!(info.items.len(), 2);
+
+        assert_eq!(
+            info.items[0].product_name,
+            "メガミデバイス PUNI☆MOFU トゥ 1/1 プラモデル[コトブキヤ]《１１月予約》"
+        );
+        assert_eq!(
+            info.items[0].old_release_date,
+            FuzzyReleaseDate::month(2024, 11, ReleaseDatePrecision::Month)
+        );
+        // 年の記載がない場合は受信日以降で最も近い年とみなす
+        assert_eq!(
+            info.items[0].new_release_date,
+            FuzzyReleaseDate::month(2025, 2, ReleaseDatePrecision::LateMonth).unwrap()
+        );
+
+        assert_eq!(
+            info.items[1].product_name,
+            "ねんどろいど 初音ミク[グッドスマイルカンパニー]"
+        );
+        assert_eq!(
+            info.items[1].new_release_date,
+            FuzzyReleaseDate::period(2025, ReleaseDatePrecision::Spring)
+        );
+    }
+
+    #[test]
+    fn test_parse_delay_without_item_lines_targets_whole_order() {
+        let body = "受注番号 : 226512861\n変更後発売時期：2025年8月\n";
+        let info = AmiamiDelayParser.parse_delay(body, None).unwrap();
+        assert_eq!(info.items.len(), 1);
+        assert_eq!(info.items[0].product_name, "");
+        assert_eq!(info.items[0].old_release_date, None);
+        assert_eq!(
+            info.items[0].new_release_date.to_release_date(),
+            "2025-08".to_string()
+        );
+    }
+
+    #[test]
+    fn test_parse_delay_missing_fields_returns_error() {
+        assert!(AmiamiDelayParser
+            .parse_delay("新発売予定：2025年8月\n", None)
+            .is_err());
+        assert!(AmiamiDelayParser
+            .parse_delay("ご注文226512861の下記商品につきまして\n", None)
+            .is_err());
+    }
+}
@@ -0,0 +1,255 @@
+use crate::logic::language::EmailLanguage;
+use crate::parsers::release_date::{FuzzyReleaseDate, ReleaseDatePrecision};
+use crate::parsers::{normalize_item_url, parse_amount, EmailParser, Label, OrderInfo, OrderItem};
+use chrono::{NaiveDate, NaiveDateTime};
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+/// `Order Number: 1234567890`
+static ORDER_NUMBER: Lazy<Label> =
+    Lazy::new(|| Label::new("Order Number").colon().value(r"\d{6,}").build());
+
+/// `Order Date: 2025/03/10 21:15`
+static ORDER_DATE: Lazy<Label> = Lazy::new(|| {
+    Label::new("Order Date")
+        .colon()
+        .value(r"\d{4}/\d{1,2}/\d{1,2}(?: \d{1,2}:\d{2})?")
+        .build()
+});
+
+/// 商品行: `[Pre-order] Nendoroid Hatsune Miku (FIGURE-171345)`（先頭の在庫区分は任意）
+static ITEM_HEADER_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^(?:\[[^\]]+\]\s*)?(.+?)\s*\(([A-Z]+-\d+(?:-[A-Z0-9]+)?)\)$")
+        .expect("Invalid ITEM_HEADER_RE")
+});
+
+/// `Price: 5,280 JPY x 1`
+static PRICE_LINE_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^Price\s*:\s*(.+?)\s*[x×]\s*(\d+)$").expect("Invalid PRICE_LINE_RE"));
+
+/// `Release Date: Late Aug 2025` / `Release Date: Aug 2025`
+static RELEASE_DATE_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^Release Date\s*:\s*(?:(Early|Mid|Late)\s+)?([A-Za-z]{3,})\.?\s+(\d{4})$")
+        .expect("Invalid RELEASE_DATE_RE")
+});
+
+static ITEM_URL: Lazy<Label> = Lazy::new(|| Label::new("URL").colon().build());
+static SUBTOTAL: Lazy<Label> = Lazy::new(|| Label::new("Item Total").colon().build());
+static SHIPPING_FEE: Lazy<Label> = Lazy::new(|| Label::new("Shipping Fee").colon().build());
+static TOTAL_AMOUNT: Lazy<Label> = Lazy::new(|| Label::new("Order Total").colon().build());
+
+/// あみあみ英語版（海外向け）注文確認メール用パーサー
+///
+/// 件名：`AmiAmi English - Order Confirmation [1234567890]`
+/// 送信元：`order@amiami.com`
+///
+/// 日本語版と異なり、商品は `商品名 (gcode)` の行から始まるブロックで並び、
+/// 単価と個数は `Price: 5,280 JPY x 1` の 1 行にまとまっている。
+/// 予約商品は `Release Date: Late Aug 2025` のように英語で発売予定が入る。
+/// 送料は発送時に確定するため、金額でない場合（`To be determined at shipment`）は None とする。
+pub struct AmiamiEnConfirmParser;
+
+fn extract_order_date(body: &str) -> Option<String> {
+    let value = ORDER_DATE.find(body)?;
+    if let Ok(dt) = NaiveDateTime::parse_from_str(&value, "%Y/%m/%d %H:%M") {
+        return Some(dt.format("%Y-%m-%d %H:%M").to_string());
+    }
+    NaiveDate::parse_from_str(&value, "%Y/%m/%d")
+        .ok()
+        .map(|d| d.format("%Y-%m-%d").to_string())
+}
+
+/// `Aug` / `August` → 8
+fn parse_english_month(name: &str) -> Option<u32> {
+    let prefix = name.get(..3)?.to_ascii_lowercase();
+    [
+        "jan", "feb", "mar", "apr", "may", "jun", "jul", "aug", "sep", "oct", "nov", "dec",
+    ]
+    .iter()
+    .position(|m| *m == prefix)
+    .map(|i| i as u32 + 1)
+}
+
+fn parse_release_date(line: &str) -> Option<FuzzyReleaseDate> {
+    let caps = RELEASE_DATE_RE.captures(line)?;
+    let precision = match caps.get(1).map(|m| m.as_str()) {
+        Some("Early") => ReleaseDatePrecision::EarlyMonth,
+        Some("Mid") => ReleaseDatePrecision::MidMonth,
+        Some("Late") => ReleaseDatePrecision::LateMonth,
+        _ => ReleaseDatePrecision::Month,
+    };
+    let month = parse_english_month(&caps[2])?;
+    let year = caps[3].parse().ok()?;
+    FuzzyReleaseDate::month(year, month, precision)
+}
+
+fn extract_items(lines: &[&str]) -> Vec<OrderItem> {
+    let mut items: Vec<OrderItem> = Vec::new();
+
+    for line in lines {
+        if SUBTOTAL.match_line(line).is_some() {
+            break;
+        }
+        if let Some(caps) = ITEM_HEADER_RE.captures(line) {
+            items.push(OrderItem {
+                name: caps[1].to_string(),
+                manufacturer: None,
+                model_number: Some(caps[2].to_string()),
+                unit_price: 0,
+                quantity: 1,
+                subtotal: 0,
+                image_url: None,
+                item_url: None,
+                release_date: None,
+            });
+            continue;
+        }
+        let Some(item) = items.last_mut() else {
+            continue;
+        };
+
+        if let Some(caps) = PRICE_LINE_RE.captures(line) {
+            item.unit_price = parse_amount(&caps[1], EmailLanguage::English).unwrap_or(0);
+            item.quantity = caps[2].parse().unwrap_or(1);
+        } else if let Some(release_date) = parse_release_date(line) {
+            item.release_date = Some(release_date);
+        } else if let Some(url) = ITEM_URL.match_line(line) {
+            item.item_url = normalize_item_url(&url);
+        }
+    }
+
+    for item in &mut items {
+        item.subtotal = item.unit_price * item.quantity;
+    }
+    items
+}
+
+impl EmailParser for AmiamiEnConfirmParser {
+    fn parse(&self, email_body: &str) -> Result<OrderInfo, String> {
+        let lines: Vec<&str> = email_body.lines().map(str::trim).collect();
+
+        let order_number = ORDER_NUMBER
+            .find_in_lines(&lines)
+            .ok_or_else(|| "Order number not found".to_string())?;
+
+        let items = extract_items(&lines);
+        if items.is_empty() {
+            return Err("No items found".to_string());
+        }
+
+        Ok(OrderInfo {
+            order_number,
+            order_date: extract_order_date(email_body),
+            delivery_address: None,
+            delivery_info: None,
+            items,
+            subtotal: SUBTOTAL.find_amount(email_body, EmailLanguage::English),
+            shipping_fee: SHIPPING_FEE.find_amount(email_body, EmailLanguage::English),
+            total_amount: TOTAL_AMOUNT.find_amount(email_body, EmailLanguage::English),
+            tax_amount: None,
+            tax_included: true,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_confirm() -> &'static str {
+        r#"Dear Taro Yamada,
+
+Thank you for shopping at AmiAmi English.
+Your order has been received as follows.
+
+Order Number: 1234567890
+Order Date: 2025/03/10 21:15
+
+------------------------------------------------------------
+[Pre-order] Nendoroid Hatsune Miku 2.0 (FIGURE-171345)
+URL: https://www.amiami.com/eng/detail/?gcode=FIGURE-171345&utm_source=mail
+Release Date: Late Aug 2025
+Price: 5,280 JPY x 1
+------------------------------------------------------------
+[In Stock] HG 1/144 Gundam Aerial (GUNPLA-012345)
+URL: https://www.amiami.com/eng/detail/?gcode=GUNPLA-012345
+Price: 1,320 JPY x 2
+------------------------------------------------------------
+
+Item Total: 7,920 JPY
+Shipping Fee: To be determined at shipment
+Order Total: 7,920 JPY
+"#
+    }
+
+    #[test]
+    fn test_parse_en_confirm_order_number_and_date() {
+        let order = AmiamiEnConfirmParser.parse(sample_confirm()).unwrap();
+        assert_eq!(order.order_number, "1234567890");
+        assert_eq!(order.order_date.as_deref(), Some("2025-03-10 21:15"));
+    }
+
+    #[test]
+    fn test_parse_en_confirm_items() {
+        let order = AmiamiEnConfirmParser.parse(sample_confirm()).unwrap();
+        assert_eq!(order.items.len(), 2);
+
+        let first = &order.items[0];
+        assert_eq!(first.name, "Nendoroid Hatsune Miku 2.0");
+        assert_eq!(first.model_number.as_deref(), Some("FIGURE-171345"));
+        assert_eq!(
+            first.item_url.as_deref(),
+            Some("https://www.amiami.com/eng/detail/?gcode=FIGURE-171345")
+        );
+        assert_eq!(first.unit_price, 5280);
+        assert_eq!(first.quantity, 1);
+        assert_eq!(
+            first.release_date,
+            FuzzyReleaseDate::month(2025, 8, ReleaseDatePrecision::LateMonth)
+        );
+
+        let second = &order.items[1];
+        assert_eq!(second.name, "HG 1/144 Gundam Aerial");
+        assert_eq!(second.unit_price, 1320);
+        assert_eq!(second.quantity, 2);
+        assert_eq!(second.subtotal, 2640);
+        assert_eq!(second.release_date, None);
+    }
+
+    #[test]
+    fn test_parse_en_confirm_amounts() {
+        let order = AmiamiEnConfirmParser.parse(sample_confirm()).unwrap();
+        assert_eq!(order.subtotal, Some(7920));
+        assert_eq!(order.shipping_fee, None);
+        assert_eq!(order.total_amount, Some(7920));
+        assert!(order.tax_included);
+    }
+
+    #[test]
+    fn test_parse_release_date() {
+        assert_eq!(
+            parse_release_date("Release Date: September 2025"),
+            FuzzyReleaseDate::month(2025, 9, ReleaseDatePrecision::Month)
+        );
+        assert_eq!(
+            parse_release_date("Release Date: Early Jan 2026"),
+            FuzzyReleaseDate::month(2026, 1, ReleaseDatePrecision::EarlyMonth)
+        );
+        assert_eq!(parse_release_date("Release Date: TBA"), None);
+    }
+
+    #[test]
+    fn test_parse_en_confirm_rejects_japanese_template() {
+        // 日本語版のテンプレートは amiami_confirm の担当
+        let body =
+            "お客様のご注文は受注番号 \"219908570\"にて承りました。\n商品名：テスト\n単価：\\1,690";
+        assert!(AmiamiEnConfirmParser.parse(body).is_err());
+    }
+
+    #[test]
+    fn test_parse_en_confirm_no_items() {
+        assert!(AmiamiEnConfirmParser
+            .parse("Order Number: 1234567890\nOrder Total: 0 JPY")
+            .is_err());
+    }
+}
@@ -1,9 +1,13 @@
+use crate::parsers::release_date::{extract_fuzzy_release_date, FuzzyReleaseDate};
 use crate::parsers::OrderItem;
 use once_cell::sync::Lazy;
 use regex::Regex;
 
 pub mod cancel;
 pub mod confirm;
+pub mod confirm_yoyaku;
+pub mod delay;
+pub mod en_confirm;
 pub mod rakuten_confirm;
 pub mod rakuten_send;
 pub mod send;
@@ -40,6 +44,23 @@ static TOTAL_RAKUTEN_RE: Lazy<Regex> =
 static TRACKING_RE: Lazy<Regex> =
     Lazy::new(|| Regex::new(r"荷物[^：:]*[：:]\s*(\d{12})").expect("Invalid TRACKING_RE"));
 
+/// 直販「商品発送のご案内」: `代表注文番号：219908570` パターン
+static REPRESENTATIVE_ORDER_NUMBER_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"代表(?:注文|受注)番号[：:]\s*(\d{6,})")
+        .expect("Invalid REPRESENTATIVE_ORDER_NUMBER_RE")
+});
+
+/// 直販「商品発送のご案内」: `お問い合わせ番号：5155-9648-8142` パターン（ハイフン区切りも許容）
+static INQUIRY_NUMBER_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"お問い?合わ?せ(?:伝票)?番号[：:]\s*(\d[\d-]{9,}\d)")
+        .expect("Invalid INQUIRY_NUMBER_RE")
+});
+
+/// 直販「商品発送のご案内」: `運送会社：佐川急便` パターン
+static CARRIER_NAME_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?:運送会社|配送会社|配送業者)[：:]\s*(.+)").expect("Invalid CARRIER_NAME_RE")
+});
+
 /// 直販 confirm 商品名: `商品名：xxx`
 static DIRECT_ITEM_NAME_RE: Lazy<Regex> =
     Lazy::new(|| Regex::new(r"^商品名[：:](.+)").expect("Invalid DIRECT_ITEM_NAME_RE"));
@@ -88,6 +109,17 @@ pub fn extract_order_number(lines: &[&str]) -> Option<String> {
         .find_map(|line| ORDER_NUMBER_RE.captures(line).map(|c| c[1].to_string()))
 }
 
+/// `代表注文番号：219908570` 形式の注文番号を抽出する（直販「商品発送のご案内」）
+///
+/// 複数注文をまとめて発送した場合も、代表となる注文番号が1つ記載される。
+pub fn extract_representative_order_number(lines: &[&str]) -> Option<String> {
+    lines.iter().find_map(|line| {
+        REPRESENTATIVE_ORDER_NUMBER_RE
+            .captures(line)
+            .map(|c| c[1].to_string())
+    })
+}
+
 /// `受注番号 "219908570"` 形式の注文番号を抽出する（直販 confirm 専用）
 pub fn extract_order_number_quoted(lines: &[&str]) -> Option<String> {
     lines.iter().find_map(|line| {
@@ -104,6 +136,39 @@ pub fn extract_tracking_number(lines: &[&str]) -> Option<String> {
         .find_map(|line| TRACKING_RE.captures(line).map(|c| c[1].to_string()))
 }
 
+/// `お問い合わせ番号：5155-9648-8142` から送り状番号を抽出する（ハイフンは除去）
+pub fn extract_inquiry_number(lines: &[&str]) -> Option<String> {
+    lines.iter().find_map(|line| {
+        INQUIRY_NUMBER_RE
+            .captures(line)
+            .map(|c| c[1].replace('-', ""))
+    })
+}
+
+/// `運送会社：佐川急便` 行から配送会社名を抽出する
+///
+/// 既知の配送会社は表記を揃える（`ヤマト運輸株式会社` → `ヤマト運輸`、`日本郵便` → `ゆうパック`）。
+pub fn extract_carrier_name(lines: &[&str]) -> Option<String> {
+    let name = lines.iter().find_map(|line| {
+        CARRIER_NAME_RE
+            .captures(line)
+            .map(|c| c[1].trim().to_string())
+    })?;
+    if name.is_empty() {
+        return None;
+    }
+    let normalized = if name.contains("佐川") {
+        "佐川急便".to_string()
+    } else if name.contains("ヤマト") || name.contains("クロネコ") {
+        "ヤマト運輸".to_string()
+    } else if name.contains("ゆうパック") || name.contains("日本郵便") {
+        "ゆうパック".to_string()
+    } else {
+        name
+    };
+    Some(normalized)
+}
+
 /// 楽天テーブル形式の商品行を抽出する
 ///
 /// フォーマット:
@@ -154,6 +219,8 @@ pub fn extract_rakuten_items(lines: &[&str]) -> Vec<OrderItem> {
                 quantity,
                 subtotal,
                 image_url: None,
+                item_url: None,
+                release_date: None,
             });
         }
     }
@@ -191,23 +258,35 @@ pub fn extract_total_amount(lines: &[&str]) -> Option<i64> {
 /// 商品名：商品B
 /// ...
 /// ```
+///
+/// 予約商品は商品名（`【25年8月予約】` 等）またはブロック内の `発売予定：2025年8月` 行から発売予定を抽出する。
 pub fn extract_direct_items(lines: &[&str]) -> Vec<OrderItem> {
     let mut items = Vec::new();
     let mut current_name: Option<String> = None;
     let mut current_unit_price: i64 = 0;
     let mut current_quantity: i64 = 1;
+    let mut current_release_date: Option<FuzzyReleaseDate> = None;
 
     for line in lines {
         let trimmed = line.trim();
 
         if let Some(caps) = DIRECT_ITEM_NAME_RE.captures(trimmed) {
             // 前のブロックが未確定なら破棄（不完全ブロック）
-            current_name = Some(caps[1].trim().to_string());
+            let name = caps[1].trim().to_string();
+            current_release_date = extract_fuzzy_release_date(&name, None);
+            current_name = Some(name);
             current_unit_price = 0;
             current_quantity = 1;
             continue;
         }
 
+        if current_name.is_some() {
+            if let Some(release_date) = extract_fuzzy_release_date(trimmed, None) {
+                current_release_date = Some(release_date);
+                continue;
+            }
+        }
+
         if let Some(caps) = DIRECT_UNIT_PRICE_RE.captures(trimmed) {
             current_unit_price = caps[1].replace(',', "").parse().unwrap_or(0);
             continue;
@@ -229,6 +308,8 @@ pub fn extract_direct_items(lines: &[&str]) -> Vec<OrderItem> {
                     quantity: current_quantity,
                     subtotal,
                     image_url: None,
+                    item_url: None,
+                    release_date: current_release_date.take(),
                 });
             }
             current_unit_price = 0;
@@ -392,6 +473,50 @@ mod tests {
         assert_eq!(extract_tracking_number(&lines), None);
     }
 
+    // ─── extract_representative_order_number / extract_inquiry_number / extract_carrier_name ───
+
+    #[test]
+    fn test_extract_representative_order_number() {
+        let lines = vec!["代表注文番号：219908570"];
+        assert_eq!(
+            extract_representative_order_number(&lines),
+            Some("219908570".to_string())
+        );
+        let lines = vec!["受注番号：219908570"];
+        assert_eq!(extract_representative_order_number(&lines), None);
+    }
+
+    #[test]
+    fn test_extract_inquiry_number_strips_hyphens() {
+        let lines = vec!["お問い合わせ番号：5155-9648-8142"];
+        assert_eq!(
+            extract_inquiry_number(&lines),
+            Some("515596488142".to_string())
+        );
+        let lines = vec!["お問合せ番号: 397404561713"];
+        assert_eq!(
+            extract_inquiry_number(&lines),
+            Some("397404561713".to_string())
+        );
+    }
+
+    #[test]
+    fn test_extract_carrier_name_normalizes() {
+        assert_eq!(
+            extract_carrier_name(&["運送会社：ヤマト運輸株式会社"]),
+            Some("ヤマト運輸".to_string())
+        );
+        assert_eq!(
+            extract_carrier_name(&["運送会社：日本郵便（ゆうパック）"]),
+            Some("ゆうパック".to_string())
+        );
+        assert_eq!(
+            extract_carrier_name(&["運送会社：西濃運輸"]),
+            Some("西濃運輸".to_string())
+        );
+        assert_eq!(extract_carrier_name(&["受注番号：219908570"]), None);
+    }
+
     // ─── extract_rakuten_items ───
 
     #[test]
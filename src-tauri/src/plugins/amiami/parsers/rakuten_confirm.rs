@@ -37,6 +37,8 @@ impl EmailParser for AmiamiRakutenConfirmParser {
             subtotal: None,
             shipping_fee,
             total_amount,
+            tax_amount: None,
+            tax_included: true,
         })
     }
 }
@@ -49,6 +49,8 @@ impl EmailParser for AmiamiRakutenSendParser {
             subtotal: None,
             shipping_fee: None,
             total_amount: None,
+            tax_amount: None,
+            tax_included: true,
         })
     }
 }
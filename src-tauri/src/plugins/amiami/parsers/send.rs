@@ -1,5 +1,6 @@
 use super::{
-    body_to_lines, detect_carrier, extract_order_number, extract_rakuten_items,
+    body_to_lines, detect_carrier, extract_carrier_name, extract_inquiry_number,
+    extract_order_number, extract_rakuten_items, extract_representative_order_number,
     extract_tracking_number,
 };
 use crate::parsers::{DeliveryInfo, EmailParser, OrderInfo};
@@ -13,6 +14,13 @@ use crate::parsers::{DeliveryInfo, EmailParser, OrderInfo};
 /// 商品テーブル形式は楽天と同一（パイプ区切り）。
 /// 配送会社はメール本文中の追跡 URL から判定する（sagawa-exp.co.jp → 佐川急便）。
 /// 金額情報は含まれないため、confirm 側で登録済みの値を保持する。
+///
+/// # 「商品発送のご案内」形式
+/// 件名：`商品発送のご案内`
+///
+/// 複数注文をまとめて発送するメールでは注文番号が `代表注文番号：219908570`、
+/// 配送会社が `運送会社：佐川急便`、送り状番号が `お問い合わせ番号：5155-9648-8142` の形式で記載される。
+/// 商品一覧を含まない場合は `items` を空で返す（confirm で登録済みの商品とマージ済み）。
 pub struct AmiamiSendParser;
 
 impl EmailParser for AmiamiSendParser {
@@ -20,18 +28,19 @@ impl EmailParser for AmiamiSendParser {
         let body_lines = body_to_lines(email_body);
         let lines: Vec<&str> = body_lines.iter().map(|s| s.as_str()).collect();
 
-        let order_number =
-            extract_order_number(&lines).ok_or_else(|| "Order number not found".to_string())?;
+        let order_number = extract_order_number(&lines)
+            .or_else(|| extract_representative_order_number(&lines))
+            .ok_or_else(|| "Order number not found".to_string())?;
 
         let tracking_number = extract_tracking_number(&lines)
+            .or_else(|| extract_inquiry_number(&lines))
             .ok_or_else(|| "Tracking number not found".to_string())?;
 
-        let carrier = detect_carrier(email_body).ok_or_else(|| "Carrier not found".to_string())?;
+        let carrier = extract_carrier_name(&lines)
+            .or_else(|| detect_carrier(email_body))
+            .ok_or_else(|| "Carrier not found".to_string())?;
 
         let items = extract_rakuten_items(&lines);
-        if items.is_empty() {
-            return Err("No items found".to_string());
-        }
 
         let delivery_info = DeliveryInfo {
             carrier,
@@ -51,6 +60,8 @@ impl EmailParser for AmiamiSendParser {
             subtotal: None,
             shipping_fee: None,
             total_amount: None,
+            tax_amount: None,
+            tax_included: true,
         })
     }
 }
@@ -89,6 +100,18 @@ mod tests {
 "#
     }
 
+    fn sample_shipping_notice() -> &'static str {
+        r#"この度はあみあみをご利用いただき、誠にありがとうございます。
+ご注文の商品を本日発送いたしましたのでお知らせいたします。
+
+代表注文番号：219908570
+運送会社：ヤマト運輸
+お問い合わせ番号：3974-0456-1713
+
+※お荷物の配送状況は運送会社のホームページよりご確認ください。
+"#
+    }
+
     #[test]
     fn test_parse_send_order_number() {
         let order = AmiamiSendParser.parse(sample_send()).unwrap();
@@ -157,4 +180,25 @@ mod tests {
         let body = format!("受注番号：219908570\n荷物お問合せ番号：515596488142\n{sep}\n商品A | 500円 | 1 | 500円\n{sep}");
         assert!(AmiamiSendParser.parse(&body).is_err());
     }
+
+    #[test]
+    fn test_parse_shipping_notice_representative_order_number() {
+        let order = AmiamiSendParser.parse(sample_shipping_notice()).unwrap();
+        assert_eq!(order.order_number, "219908570");
+    }
+
+    #[test]
+    fn test_parse_shipping_notice_delivery_info() {
+        let order = AmiamiSendParser.parse(sample_shipping_notice()).unwrap();
+        let delivery = order.delivery_info.unwrap();
+        assert_eq!(delivery.carrier, "ヤマト運輸");
+        assert_eq!(delivery.tracking_number, "397404561713");
+        assert!(delivery.delivery_status.is_none());
+    }
+
+    #[test]
+    fn test_parse_shipping_notice_without_items() {
+        let order = AmiamiSendParser.parse(sample_shipping_notice()).unwrap();
+        assert!(order.items.is_empty());
+    }
 }
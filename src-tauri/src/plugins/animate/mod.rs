@@ -11,8 +11,8 @@ use crate::parsers::EmailParser;
 use crate::repository::SqliteOrderRepository;
 
 use super::{
-    apply_internal_date, derive_shop_domain, DefaultShopSetting, DispatchError, DispatchOutcome,
-    PluginRegistration, VendorPlugin,
+    apply_internal_date, apply_tax_info, derive_shop_domain, DefaultShopSetting, DispatchError,
+    DispatchOutcome, PluginRegistration, VendorPlugin,
 };
 
 pub struct AnimatePlugin;
@@ -81,6 +81,7 @@ impl VendorPlugin for AnimatePlugin {
         // 注文確認メールは注文日が本文に含まれないため internal_date で補完する
         if parser_type == "animate_confirm" {
             apply_internal_date(&mut order_info, internal_date);
+            apply_tax_info(&mut order_info, body);
         }
 
         log::debug!(
@@ -39,6 +39,8 @@ impl EmailParser for AnimateConfirmParser {
             subtotal,
             shipping_fee,
             total_amount,
+            tax_amount: None,
+            tax_included: true,
         })
     }
 }
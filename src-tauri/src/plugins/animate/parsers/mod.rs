@@ -1,3 +1,4 @@
+use crate::parsers::release_date::{extract_fuzzy_release_date, FuzzyReleaseDate};
 use crate::parsers::OrderItem;
 use once_cell::sync::Lazy;
 use regex::Regex;
@@ -84,6 +85,7 @@ pub fn extract_tracking_number(lines: &[&str]) -> Option<String> {
 /// `●ご注文内容` セクションから商品リストを抽出する
 ///
 /// `商品名:` 行で商品ブロック開始、`商品合計額:` 行で商品を確定する。
+/// `発売日:2022年02月 中 発売予定` 行があれば発売予定月を release_date に入れる。
 /// `=============` はブロック区切り、`支払方法：` または `●合計` でセクション終了。
 pub fn extract_items(lines: &[&str]) -> Vec<OrderItem> {
     let mut items: Vec<OrderItem> = Vec::new();
@@ -91,6 +93,7 @@ pub fn extract_items(lines: &[&str]) -> Vec<OrderItem> {
     let mut current_name: Option<String> = None;
     let mut current_quantity: i64 = 1;
     let mut current_unit_price: i64 = 0;
+    let mut current_release_date: Option<FuzzyReleaseDate> = None;
 
     for line in lines {
         let trimmed = line.trim();
@@ -116,6 +119,8 @@ pub fn extract_items(lines: &[&str]) -> Vec<OrderItem> {
                     quantity: current_quantity,
                     subtotal: current_unit_price * current_quantity,
                     image_url: None,
+                    item_url: None,
+                    release_date: current_release_date.take(),
                 });
             }
             break;
@@ -126,6 +131,12 @@ pub fn extract_items(lines: &[&str]) -> Vec<OrderItem> {
             current_name = Some(caps[1].trim().to_string());
             current_quantity = 1;
             current_unit_price = 0;
+            current_release_date = None;
+            continue;
+        }
+
+        if trimmed.starts_with("発売日") {
+            current_release_date = extract_fuzzy_release_date(trimmed, None);
             continue;
         }
 
@@ -157,6 +168,8 @@ pub fn extract_items(lines: &[&str]) -> Vec<OrderItem> {
                     quantity: current_quantity,
                     subtotal,
                     image_url: None,
+                    item_url: None,
+                    release_date: current_release_date.take(),
                 });
                 current_quantity = 1;
                 current_unit_price = 0;
@@ -315,6 +328,13 @@ mod tests {
         assert_eq!(items[0].quantity, 1);
         assert_eq!(items[0].unit_price, 3000);
         assert_eq!(items[0].subtotal, 3000);
+        assert_eq!(
+            items[0]
+                .release_date
+                .as_ref()
+                .map(FuzzyReleaseDate::to_release_date),
+            Some("2022-02".to_string())
+        );
     }
 
     #[test]
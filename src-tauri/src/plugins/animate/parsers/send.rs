@@ -47,6 +47,8 @@ impl EmailParser for AnimateSendParser {
             subtotal: None,
             shipping_fee: None,
             total_amount: None,
+            tax_amount: None,
+            tax_included: true,
         })
     }
 }
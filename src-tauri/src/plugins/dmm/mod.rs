@@ -4,7 +4,7 @@
 //!
 //! # alternate_domains
 //! DMM の注文確認メールは `mail.dmm.com` / `mono.dmm.com` のどちらかから届く。
-//! キャンセル・注文番号変更メールは `mail.dmm.com` から届くが、注文検索では両方を試す。
+//! キャンセル・注文番号変更・予約商品入荷メールは `mail.dmm.com` から届くが、注文検索では両方を試す。
 
 pub mod parsers;
 
@@ -14,8 +14,8 @@ use crate::parsers::EmailParser;
 use crate::repository::SqliteOrderRepository;
 
 use super::{
-    apply_internal_date, derive_shop_domain, DefaultShopSetting, DispatchError, DispatchOutcome,
-    PluginRegistration, VendorPlugin,
+    apply_internal_date, apply_tax_info, derive_shop_domain, DefaultShopSetting, DispatchError,
+    DispatchOutcome, PluginRegistration, VendorPlugin,
 };
 
 pub struct DmmPlugin;
@@ -27,9 +27,11 @@ impl VendorPlugin for DmmPlugin {
             "dmm_confirm",
             "dmm_send",
             "dmm_cancel",
+            "dmm_auto_cancel",
             "dmm_order_number_change",
             "dmm_split_complete",
             "dmm_merge_complete",
+            "dmm_stock_arrival",
         ]
     }
 
@@ -38,7 +40,7 @@ impl VendorPlugin for DmmPlugin {
     }
 
     /// `OrderInfo` を返すパーサーのみ。
-    /// cancel / order_number_change / merge_complete は `dispatch()` 内で直接処理する。
+    /// cancel / auto_cancel / order_number_change / merge_complete / stock_arrival は `dispatch()` 内で直接処理する。
     fn get_parser(&self, parser_type: &str) -> Option<Box<dyn EmailParser>> {
         match parser_type {
             "dmm_confirm" => Some(Box::new(parsers::confirm::DmmConfirmParser)),
@@ -88,6 +90,14 @@ impl VendorPlugin for DmmPlugin {
                 parser_type: "dmm_cancel".to_string(),
                 subject_filters: Some(vec!["DMM通販：ご注文キャンセルのお知らせ".to_string()]),
             },
+            DefaultShopSetting {
+                shop_name: "DMM通販".to_string(),
+                sender_address: "info@mail.dmm.com".to_string(),
+                parser_type: "dmm_auto_cancel".to_string(),
+                subject_filters: Some(vec![
+                    "DMM通販：ご注文商品の自動キャンセルのお知らせ".to_string()
+                ]),
+            },
             DefaultShopSetting {
                 shop_name: "DMM通販".to_string(),
                 sender_address: "info@mail.dmm.com".to_string(),
@@ -108,6 +118,15 @@ impl VendorPlugin for DmmPlugin {
                 parser_type: "dmm_merge_complete".to_string(),
                 subject_filters: Some(vec!["DMM通販：ご注文まとめ完了のお知らせ".to_string()]),
             },
+            DefaultShopSetting {
+                shop_name: "DMM通販".to_string(),
+                sender_address: "info@mail.dmm.com".to_string(),
+                parser_type: "dmm_stock_arrival".to_string(),
+                subject_filters: Some(vec![
+                    "DMM通販：ご予約商品の発送準備が整いました".to_string(),
+                    "ご予約商品の発送準備が整いました".to_string(),
+                ]),
+            },
             DefaultShopSetting {
                 shop_name: "DMM通販".to_string(),
                 sender_address: "info@mail.dmm.com".to_string(),
@@ -172,6 +191,37 @@ impl VendorPlugin for DmmPlugin {
                 })
             }
 
+            // ── 入荷待ちキャンセル（自動キャンセル）────────────────────────────
+            "dmm_auto_cancel" => {
+                let cancel_infos = parsers::auto_cancel::DmmAutoCancelParser
+                    .parse_cancel(body)
+                    .map_err(DispatchError::ParseFailed)?;
+
+                let order_number = cancel_infos[0].order_number.clone();
+
+                log::debug!(
+                    "[dmm_auto_cancel] email_id={} order_number={} items={} reason={:?}",
+                    email_id,
+                    order_number,
+                    cancel_infos.len(),
+                    cancel_infos[0].reason_detail
+                );
+
+                for cancel_info in &cancel_infos {
+                    SqliteOrderRepository::apply_cancel_in_tx(
+                        tx,
+                        cancel_info,
+                        email_id,
+                        shop_domain.clone(),
+                        alt_domains.clone(),
+                    )
+                    .await
+                    .map_err(DispatchError::SaveFailed)?;
+                }
+
+                Ok(DispatchOutcome::CancelApplied { order_number })
+            }
+
             // ── 注文番号変更 ────────────────────────────────────────────────────
             "dmm_order_number_change" => {
                 let change_info = parsers::order_number_change::DmmOrderNumberChangeParser
@@ -305,6 +355,48 @@ impl VendorPlugin for DmmPlugin {
                 Ok(DispatchOutcome::MultiOrderSaved(saved_orders))
             }
 
+            // ── 予約商品入荷（発送準備中）──────────────────────────────────────
+            "dmm_stock_arrival" => {
+                let order_numbers = parsers::stock_arrival::DmmStockArrivalParser
+                    .parse_order_numbers(body)
+                    .map_err(DispatchError::ParseFailed)?;
+
+                log::debug!(
+                    "[dmm_stock_arrival] email_id={} order_numbers={:?}",
+                    email_id,
+                    order_numbers
+                );
+
+                // まとめて入荷した注文の一部が未取り込みでも、1件でも適用できれば成功とする
+                let mut applied: Option<String> = None;
+                let mut last_error = None;
+                for order_number in &order_numbers {
+                    match SqliteOrderRepository::apply_preparing_in_tx(
+                        tx,
+                        order_number,
+                        email_id,
+                        shop_domain.clone(),
+                        alt_domains.clone(),
+                    )
+                    .await
+                    {
+                        Ok(_) => {
+                            applied.get_or_insert_with(|| order_number.clone());
+                        }
+                        Err(e) => last_error = Some(e),
+                    }
+                }
+
+                match applied {
+                    Some(order_number) => Ok(DispatchOutcome::PreparingApplied { order_number }),
+                    None => {
+                        Err(DispatchError::SaveFailed(last_error.unwrap_or_else(|| {
+                            "No order to apply preparing".to_string()
+                        })))
+                    }
+                }
+            }
+
             // ── 通常注文（confirm / send）──────────────────────────────────────
             _ => {
                 // parser は同期処理のみ。await をまたがないようブロックで即 drop する。
@@ -319,6 +411,7 @@ impl VendorPlugin for DmmPlugin {
                 if parser_type == "dmm_confirm" {
                     apply_internal_date(&mut order_info, internal_date);
                 }
+                apply_tax_info(&mut order_info, body);
 
                 let save_result = if parser_type == "dmm_send" {
                     // 発送完了: 発送メール時点の items + 金額で元注文を更新しつつ delivery を shipped に変更
@@ -361,9 +454,11 @@ mod tests {
         assert!(types.contains(&"dmm_confirm"));
         assert!(types.contains(&"dmm_send"));
         assert!(types.contains(&"dmm_cancel"));
+        assert!(types.contains(&"dmm_auto_cancel"));
         assert!(types.contains(&"dmm_order_number_change"));
         assert!(types.contains(&"dmm_split_complete"));
         assert!(types.contains(&"dmm_merge_complete"));
+        assert!(types.contains(&"dmm_stock_arrival"));
     }
 
     #[test]
@@ -391,11 +486,13 @@ mod tests {
 
     #[test]
     fn test_dmm_plugin_get_parser_cancel_returns_none() {
-        // cancel / order_number_change / merge_complete は dispatch() 内で直接処理
+        // cancel / auto_cancel / order_number_change / merge_complete / stock_arrival は dispatch() 内で直接処理
         let plugin = DmmPlugin;
         assert!(plugin.get_parser("dmm_cancel").is_none());
+        assert!(plugin.get_parser("dmm_auto_cancel").is_none());
         assert!(plugin.get_parser("dmm_order_number_change").is_none());
         assert!(plugin.get_parser("dmm_merge_complete").is_none());
+        assert!(plugin.get_parser("dmm_stock_arrival").is_none());
     }
 
     #[test]
@@ -430,7 +527,7 @@ mod tests {
 
     #[test]
     fn test_dmm_default_shop_settings_count() {
-        assert_eq!(DmmPlugin.default_shop_settings().len(), 8);
+        assert_eq!(DmmPlugin.default_shop_settings().len(), 10);
     }
 
     #[test]
@@ -443,6 +540,7 @@ mod tests {
         assert!(parser_types.contains(&"dmm_split_complete"));
         assert!(parser_types.contains(&"dmm_merge_complete"));
         assert!(parser_types.contains(&"dmm_send"));
+        assert!(parser_types.contains(&"dmm_stock_arrival"));
     }
 }
 
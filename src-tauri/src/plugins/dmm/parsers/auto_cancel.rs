@@ -0,0 +1,206 @@
+//! DMM通販「入荷待ちキャンセル（自動キャンセル）」メール用パーサー
+//!
+//! 送信元: info@mail.dmm.com
+//! 件名: DMM通販：ご注文商品の自動キャンセルのお知らせ
+//!
+//! 入荷未定のまま入荷待ち期限を過ぎた商品の自動キャンセル通知。
+//! 通常のキャンセルメール（`dmm_cancel`）と異なり、1通に複数商品・数量・キャンセル理由が記載される。
+//! 理由行がない場合は本文の定型文から「入荷未定」を理由として記録する。
+//! 理由の文言は `classify_cancel_reason` で分類する（発売中止の場合は `Discontinued`）。
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+use crate::parsers::cancel_info::{classify_cancel_reason, CancelInfo, CancelReason};
+
+/// DMM通販 自動キャンセルメール用パーサー
+pub struct DmmAutoCancelParser;
+
+/// 理由行がない場合に記録するキャンセル理由
+pub const DEFAULT_AUTO_CANCEL_REASON: &str = "入荷未定のため自動キャンセル";
+
+/// `ご注文番号：KC-25278366`
+static ORDER_NUMBER_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"ご注文番号\s*[：:]\s*([A-Za-z]{2}-\d+)").expect("Invalid ORDER_NUMBER_RE")
+});
+
+/// `商品名　　：【再販】HG 1/144 ...`
+static PRODUCT_NAME_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"商品名\s*[：:]\s*(.+)").expect("Invalid PRODUCT_NAME_RE"));
+
+/// `数量　　　：2`
+static QUANTITY_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"数量\s*[：:]\s*(\d+)").expect("Invalid QUANTITY_RE"));
+
+/// `キャンセル理由：メーカーからの入荷の見込みが立たないため`
+static REASON_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"キャンセル理由\s*[：:]\s*(.+)").expect("Invalid REASON_RE"));
+
+impl DmmAutoCancelParser {
+    /// メール本文から商品ごとのキャンセル情報を抽出する
+    ///
+    /// `ご注文番号` の後に続く `商品名` / `数量` を1商品として扱う（数量の記載がなければ 1）。
+    /// 商品名が1件もない場合は注文全体のキャンセル（`product_name` 空）として1件返す。
+    pub fn parse_cancel(&self, email_body: &str) -> Result<Vec<CancelInfo>, String> {
+        let reason_detail = REASON_RE
+            .captures(email_body)
+            .map(|c| c[1].trim().to_string())
+            .filter(|r| !r.is_empty())
+            .unwrap_or_else(|| DEFAULT_AUTO_CANCEL_REASON.to_string());
+        let reason = classify_cancel_reason(&reason_detail);
+
+        let mut infos: Vec<CancelInfo> = Vec::new();
+        let mut current_order_number: Option<String> = None;
+        let mut order_has_items = false;
+
+        for line in email_body.lines() {
+            let line = line.trim();
+
+            if let Some(caps) = ORDER_NUMBER_RE.captures(line) {
+                // 商品名のない注文は注文全体のキャンセル
+                if let (Some(order_number), false) = (current_order_number.take(), order_has_items)
+                {
+                    infos.push(whole_order_cancel(order_number, reason, &reason_detail));
+                }
+                current_order_number = Some(caps[1].to_string());
+                order_has_items = false;
+                continue;
+            }
+
+            let Some(order_number) = current_order_number.as_ref() else {
+                continue;
+            };
+
+            if let Some(caps) = PRODUCT_NAME_RE.captures(line) {
+                let product_name = caps[1].trim().to_string();
+                if !product_name.is_empty() {
+                    infos.push(CancelInfo {
+                        order_number: order_number.clone(),
+                        product_name,
+                        cancel_quantity: 1,
+                        reason: Some(reason),
+                        reason_detail: Some(reason_detail.clone()),
+                    });
+                    order_has_items = true;
+                }
+                continue;
+            }
+
+            if let Some(caps) = QUANTITY_RE.captures(line) {
+                if let (Some(last), true) = (infos.last_mut(), order_has_items) {
+                    last.cancel_quantity = caps[1].parse().unwrap_or(1);
+                }
+            }
+        }
+
+        if let (Some(order_number), false) = (current_order_number, order_has_items) {
+            infos.push(whole_order_cancel(order_number, reason, &reason_detail));
+        }
+
+        if infos.is_empty() {
+            return Err("Order number with prefix (KC-, BS-, etc.) not found".to_string());
+        }
+        Ok(infos)
+    }
+}
+
+fn whole_order_cancel(
+    order_number: String,
+    reason: CancelReason,
+    reason_detail: &str,
+) -> CancelInfo {
+    CancelInfo {
+        order_number,
+        product_name: String::new(),
+        cancel_quantity: 1,
+        reason: Some(reason),
+        reason_detail: Some(reason_detail.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_auto_cancel() -> &'static str {
+        r#"山田 太郎 様
+
+DMM通販をご利用いただき、ありがとうございます。
+
+誠に申し訳ございませんが、下記のご注文商品につきましては
+メーカーからの入荷の見込みが立たないため、ご注文を自動キャンセルさせていただきました。
+
+━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+■　キャンセル対象商品
+━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+ご注文日　：2024/06/14
+ご注文番号：KC-25278366
+商品名　　：【再販】HG 1/144 ガンダムエアリアル
+数量　　　：2
+商品名　　：30 MINUTES MISSIONS eEXM-21 ラビオット
+数量　　　：1
+キャンセル理由：メーカーからの入荷の見込みが立たないため
+
+お支払いは発生しておりません。
+"#
+    }
+
+    #[test]
+    fn test_parse_auto_cancel_multiple_items() {
+        let infos = DmmAutoCancelParser
+            .parse_cancel(sample_auto_cancel())
+            .unwrap();
+        assert_eq!(infos.len(), 2);
+        assert!(infos.iter().all(|i| i.order_number == "KC-25278366"));
+        assert_eq!(infos[0].product_name, "【再販】HG 1/144 ガンダムエアリアル");
+        assert_eq!(infos[0].cancel_quantity, 2);
+        assert_eq!(
+            infos[1].product_name,
+            "30 MINUTES MISSIONS eEXM-21 ラビオット"
+        );
+        assert_eq!(infos[1].cancel_quantity, 1);
+    }
+
+    #[test]
+    fn test_parse_auto_cancel_reason() {
+        let infos = DmmAutoCancelParser
+            .parse_cancel(sample_auto_cancel())
+            .unwrap();
+        assert!(infos.iter().all(
+            |i| i.reason_detail.as_deref() == Some("メーカーからの入荷の見込みが立たないため")
+        ));
+        assert!(infos
+            .iter()
+            .all(|i| i.reason == Some(CancelReason::OutOfStock)));
+    }
+
+    #[test]
+    fn test_parse_auto_cancel_without_reason_line_uses_default() {
+        let email = "ご注文番号：KC-11111111\n商品名：サンプル商品\n";
+        let infos = DmmAutoCancelParser.parse_cancel(email).unwrap();
+        assert_eq!(infos.len(), 1);
+        assert_eq!(
+            infos[0].reason_detail.as_deref(),
+            Some(DEFAULT_AUTO_CANCEL_REASON)
+        );
+        assert_eq!(infos[0].reason, Some(CancelReason::OutOfStock));
+    }
+
+    #[test]
+    fn test_parse_auto_cancel_without_product_is_whole_order() {
+        let email = "ご注文番号：KC-11111111\nご注文番号：BS-22222222\n商品名：サンプル商品\n";
+        let infos = DmmAutoCancelParser.parse_cancel(email).unwrap();
+        assert_eq!(infos.len(), 2);
+        assert_eq!(infos[0].order_number, "KC-11111111");
+        assert!(infos[0].product_name.is_empty());
+        assert_eq!(infos[1].order_number, "BS-22222222");
+        assert_eq!(infos[1].product_name, "サンプル商品");
+    }
+
+    #[test]
+    fn test_parse_auto_cancel_no_order_number() {
+        assert!(DmmAutoCancelParser
+            .parse_cancel("商品名：サンプル商品")
+            .is_err());
+    }
+}
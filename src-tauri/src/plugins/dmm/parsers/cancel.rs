@@ -25,6 +25,8 @@ impl DmmCancelParser {
             order_number,
             product_name: product_name.trim().to_string(),
             cancel_quantity,
+            reason: None,
+            reason_detail: None,
         })
     }
 }
@@ -5,7 +5,8 @@
 //!
 //! HTML を優先してパースし、フォールバックでテキストをパースする。
 
-use crate::parsers::{DeliveryAddress, EmailParser, OrderInfo, OrderItem};
+use crate::parsers::release_date::extract_fuzzy_release_date;
+use crate::parsers::{normalize_item_url, DeliveryAddress, EmailParser, OrderInfo, OrderItem};
 use regex::Regex;
 use scraper::{Element, Html, Selector};
 
@@ -73,6 +74,8 @@ fn parse_from_html(html: &str) -> Result<OrderInfo, String> {
         subtotal,
         shipping_fee,
         total_amount,
+        tax_amount: None,
+        tax_included: true,
     })
 }
 
@@ -234,6 +237,8 @@ pub(crate) fn extract_items_from_html(document: &Html) -> Result<Vec<OrderItem>,
             if name.is_empty() {
                 continue;
             }
+            // 商品リンク自体が商品詳細ページ（dmmref は除去する）
+            let item_url = el.value().attr("href").and_then(normalize_item_url);
             // 同じ行ブロック内の価格・数量を探す（親の tr の兄弟をたどる）
             if let Some((unit_price, quantity)) = find_price_quantity_near_element(document, el) {
                 if unit_price > 0 {
@@ -245,6 +250,8 @@ pub(crate) fn extract_items_from_html(document: &Html) -> Result<Vec<OrderItem>,
                         quantity,
                         subtotal: unit_price * quantity,
                         image_url,
+                        item_url,
+                        release_date: None,
                     });
                 }
             }
@@ -299,6 +306,8 @@ pub(crate) fn extract_items_from_html(document: &Html) -> Result<Vec<OrderItem>,
                                 quantity,
                                 subtotal: unit_price * quantity,
                                 image_url,
+                                item_url: None,
+                                release_date: None,
                             });
                         }
                     }
@@ -319,6 +328,9 @@ pub(crate) fn extract_items_from_html(document: &Html) -> Result<Vec<OrderItem>,
             if item.image_url.is_some() && existing.image_url.is_none() {
                 existing.image_url = item.image_url;
             }
+            if item.item_url.is_some() && existing.item_url.is_none() {
+                existing.item_url = item.item_url;
+            }
             if item.manufacturer.is_some() && existing.manufacturer.is_none() {
                 existing.manufacturer = item.manufacturer;
             }
@@ -445,6 +457,8 @@ fn parse_from_text(body: &str) -> Result<OrderInfo, String> {
         subtotal,
         shipping_fee,
         total_amount,
+        tax_amount: None,
+        tax_included: true,
     })
 }
 
@@ -564,6 +578,8 @@ fn extract_order_items(lines: &[&str]) -> Result<Vec<OrderItem>, String> {
                                 quantity: q,
                                 subtotal: p * q,
                                 image_url: None,
+                                item_url: None,
+                                release_date: None,
                             });
                         }
                     }
@@ -588,6 +604,8 @@ fn extract_order_items(lines: &[&str]) -> Result<Vec<OrderItem>, String> {
                                 quantity: q,
                                 subtotal: p * q,
                                 image_url: None,
+                                item_url: None,
+                                release_date: extract_fuzzy_release_date(line, None),
                             });
                         }
                     }
@@ -612,6 +630,8 @@ fn extract_order_items(lines: &[&str]) -> Result<Vec<OrderItem>, String> {
                                 quantity: q,
                                 subtotal: p * q,
                                 image_url: None,
+                                item_url: None,
+                                release_date: None,
                             });
                         }
                     }
@@ -638,6 +658,8 @@ fn extract_order_items(lines: &[&str]) -> Result<Vec<OrderItem>, String> {
                                     quantity: q,
                                     subtotal: p * q,
                                     image_url: None,
+                                    item_url: None,
+                                    release_date: None,
                                 });
                             }
                         }
@@ -789,6 +811,13 @@ BS-28156389　発送元：千葉配送センター　発送：配送業者は発
         );
         assert_eq!(order_info.items[0].unit_price, 979);
         assert_eq!(order_info.items[0].quantity, 1);
+        assert_eq!(
+            order_info.items[0]
+                .release_date
+                .as_ref()
+                .map(|d| d.to_release_date()),
+            Some("2026-03".to_string())
+        );
         assert_eq!(order_info.subtotal, Some(979));
         assert_eq!(order_info.shipping_fee, Some(530));
         assert_eq!(order_info.total_amount, Some(1509));
@@ -873,6 +902,10 @@ KC-24167237　発送元：石川配送センター　発送：日本郵便
         assert_eq!(order_info.items[0].name, "BUSTER DOLL ガンナー");
         assert_eq!(order_info.items[0].unit_price, 5643);
         assert_eq!(order_info.items[0].quantity, 1);
+        assert_eq!(
+            order_info.items[0].item_url.as_deref(),
+            Some("https://www.dmm.com/mono/hobby/-/detail/=/cid=cha_2308211721081/")
+        );
         assert_eq!(order_info.subtotal, Some(5643));
         assert_eq!(order_info.shipping_fee, Some(0));
         assert_eq!(order_info.total_amount, Some(5643));
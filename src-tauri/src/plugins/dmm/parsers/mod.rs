@@ -2,9 +2,11 @@
 //!
 //! 各パーサーは parsers/ サブモジュールとして配置されています。
 
+pub mod auto_cancel;
 pub mod cancel;
 pub mod confirm;
 pub mod merge_complete;
 pub mod order_number_change;
 pub mod send;
 pub mod split_complete;
+pub mod stock_arrival;
@@ -49,6 +49,8 @@ impl EmailParser for DmmSendParser {
                 subtotal,
                 shipping_fee,
                 total_amount,
+                tax_amount: None,
+                tax_included: true,
             })
         } else {
             // プレーンテキストのみの場合は、配送情報だけを抽出（商品・金額は空）
@@ -67,6 +69,8 @@ impl EmailParser for DmmSendParser {
                 subtotal: None,
                 shipping_fee: None,
                 total_amount: None,
+                tax_amount: None,
+                tax_included: true,
             })
         }
     }
@@ -122,11 +126,13 @@ fn extract_delivery_address(lines: &[&str]) -> Option<DeliveryAddress> {
 /// - 配送業者：佐川急便
 /// - お問い合わせ番号：364631890991
 /// - お問い合わせ伝票番号：364629550353
+/// - 配送会社：ヤマト運輸 / 伝票番号：1234-5678-9012（一部のテンプレート）
 fn extract_delivery_info(lines: &[&str]) -> Option<DeliveryInfo> {
-    let carrier_re = Regex::new(r"配送業者\s*[：:]\s*(.+)").ok()?;
-    let tracking_re =
-        Regex::new(r"(お問い合わせ伝票番号|お問い合わせ番号|お問合せ番号)\s*[：:]\s*([\d\-]+)")
-            .ok()?;
+    let carrier_re = Regex::new(r"(?:配送業者|配送会社)\s*[：:]\s*(.+)").ok()?;
+    let tracking_re = Regex::new(
+        r"(お問い合わせ伝票番号|お問い合わせ番号|お問合せ番号|伝票番号)\s*[：:]\s*([\d\-]+)",
+    )
+    .ok()?;
 
     let mut carrier: Option<String> = None;
     let mut tracking: Option<String> = None;
@@ -203,6 +209,21 @@ DMM通販をご利用いただき、ありがとうございます。
         assert_eq!(order.items.len(), 0);
     }
 
+    #[test]
+    fn test_parse_dmm_send_slip_number_label() {
+        let body = r#"ご注文商品を発送いたしました。
+
+ご注文番号：KC-12345678
+配送会社：ヤマト運輸
+伝票番号：1234-5678-9012
+"#;
+        let order = DmmSendParser.parse(body).unwrap();
+        assert_eq!(order.order_number, "KC-12345678");
+        let info = order.delivery_info.unwrap();
+        assert_eq!(info.carrier, "ヤマト運輸");
+        assert_eq!(info.tracking_number, "1234-5678-9012");
+    }
+
     #[test]
     fn test_parse_dmm_send_html() {
         // HTML メールでは dmm_confirm と同じロジックで注文番号・商品・金額をパースし、
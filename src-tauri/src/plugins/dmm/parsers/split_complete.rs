@@ -122,6 +122,8 @@ fn parse_split_orders(body: &str) -> Result<Vec<OrderInfo>, String> {
                                 quantity: q,
                                 subtotal: p * q,
                                 image_url: None,
+                                item_url: None,
+                                release_date: None,
                             });
                         }
                     }
@@ -141,6 +143,8 @@ fn parse_split_orders(body: &str) -> Result<Vec<OrderInfo>, String> {
                 subtotal: Some(subtotal),
                 shipping_fee,
                 total_amount: Some(total),
+                tax_amount: None,
+                tax_included: true,
             });
         }
     }
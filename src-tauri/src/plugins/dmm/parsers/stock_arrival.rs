@@ -0,0 +1,80 @@
+//! DMM通販「ご予約商品の発送準備が整いました」メール用パーサー
+//!
+//! 送信元: info@mail.dmm.com
+//! 件名: DMM通販：ご予約商品の発送準備が整いました
+//!
+//! 予約商品が入荷して発送準備に入ったことを通知するメール。
+//! 配送ステータスを `preparing` にするため、メール内のご注文番号のみを抽出する。
+//! 複数の予約注文がまとめて入荷した場合はご注文番号が複数記載されるため、すべて返す。
+
+use crate::parsers::Label;
+use once_cell::sync::Lazy;
+
+/// DMM通販 予約商品入荷案内メール用パーサー
+pub struct DmmStockArrivalParser;
+
+/// `ご注文番号：KC-25278366`（接頭辞の大文字・小文字はそのまま使う）
+static ORDER_NUMBER: Lazy<Label> = Lazy::new(|| {
+    Label::new("ご注文番号")
+        .alias("注文番号")
+        .colon()
+        .value(r"[A-Za-z]{2}-\d+")
+        .build()
+});
+
+impl DmmStockArrivalParser {
+    /// メール本文からご注文番号を出現順（重複なし）で抽出する
+    pub fn parse_order_numbers(&self, email_body: &str) -> Result<Vec<String>, String> {
+        let order_numbers = ORDER_NUMBER.find_all(email_body);
+        if order_numbers.is_empty() {
+            return Err("Order number with prefix (KC-, BS-, etc.) not found".to_string());
+        }
+        Ok(order_numbers)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_order_numbers_single() {
+        let body = r#"テスト 太郎 様
+
+DMM通販をご利用いただき、ありがとうございます。
+ご予約いただいておりました商品が入荷し、発送準備が整いました。
+発送完了後、あらためて発送完了のお知らせをお送りいたします。
+
+ご注文番号：KC-25278366
+商品名：ねんどろいど 初音ミク
+"#;
+        assert_eq!(
+            DmmStockArrivalParser.parse_order_numbers(body).unwrap(),
+            vec!["KC-25278366".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_parse_order_numbers_multiple_orders_deduplicated() {
+        let body = r#"ご注文番号：KC-25278366
+商品名：商品A
+
+ご注文番号 : bs-27322313
+商品名：商品B
+
+ご注文番号：KC-25278366
+商品名：商品C
+"#;
+        assert_eq!(
+            DmmStockArrivalParser.parse_order_numbers(body).unwrap(),
+            vec!["KC-25278366".to_string(), "bs-27322313".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_parse_order_numbers_not_found() {
+        assert!(DmmStockArrivalParser
+            .parse_order_numbers("ご予約商品の発送準備が整いました。")
+            .is_err());
+    }
+}
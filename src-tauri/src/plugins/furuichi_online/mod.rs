@@ -11,8 +11,8 @@ use crate::parsers::EmailParser;
 use crate::repository::SqliteOrderRepository;
 
 use super::{
-    apply_internal_date, derive_shop_domain, DefaultShopSetting, DispatchError, DispatchOutcome,
-    PluginRegistration, VendorPlugin,
+    apply_internal_date, apply_tax_info, derive_shop_domain, DefaultShopSetting, DispatchError,
+    DispatchOutcome, PluginRegistration, VendorPlugin,
 };
 
 pub struct FuruichiOnlinePlugin;
@@ -82,6 +82,7 @@ impl VendorPlugin for FuruichiOnlinePlugin {
         // apply_internal_date は order_date が Some の場合は何もしない
         if parser_type == "furuichi_confirm" {
             apply_internal_date(&mut order_info, internal_date);
+            apply_tax_info(&mut order_info, body);
         }
 
         log::debug!(
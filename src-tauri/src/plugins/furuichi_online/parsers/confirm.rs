@@ -41,6 +41,8 @@ impl EmailParser for FuruichiConfirmParser {
             subtotal,
             shipping_fee,
             total_amount,
+            tax_amount: None,
+            tax_included: true,
         })
     }
 }
@@ -111,6 +111,8 @@ pub fn extract_items(lines: &[&str]) -> Vec<OrderItem> {
                 quantity,
                 subtotal: 0,
                 image_url: None,
+                item_url: None,
+                release_date: None,
             });
         }
     }
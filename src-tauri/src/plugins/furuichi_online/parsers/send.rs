@@ -59,6 +59,8 @@ impl EmailParser for FuruichiSendParser {
             subtotal: None,
             shipping_fee: None,
             total_amount: None,
+            tax_amount: None,
+            tax_included: true,
         })
     }
 }
@@ -1,7 +1,7 @@
 //! グッドスマイルカンパニー公式ショップ プラグイン
 //!
 //! SendGrid 経由（`em1807.goodsmile.jp`）で配信されるメールをパースする。
-//! 送信元アドレス `shop@goodsmile.jp` から届く注文確認・発送通知に対応する。
+//! 送信元アドレス `shop@goodsmile.jp` から届く注文確認・発送通知・キャンセル／返金通知に対応する。
 
 pub mod parsers;
 
@@ -11,8 +11,8 @@ use crate::parsers::EmailParser;
 use crate::repository::SqliteOrderRepository;
 
 use super::{
-    derive_shop_domain, DefaultShopSetting, DispatchError, DispatchOutcome, PluginRegistration,
-    VendorPlugin,
+    apply_tax_info, derive_shop_domain, DefaultShopSetting, DispatchError, DispatchOutcome,
+    PluginRegistration, VendorPlugin,
 };
 
 pub struct GoodSmilePlugin;
@@ -20,7 +20,7 @@ pub struct GoodSmilePlugin;
 #[async_trait]
 impl VendorPlugin for GoodSmilePlugin {
     fn parser_types(&self) -> &[&str] {
-        &["goodsmile_confirm", "goodsmile_send"]
+        &["goodsmile_confirm", "goodsmile_send", "goodsmile_cancel"]
     }
 
     fn priority(&self) -> i32 {
@@ -31,6 +31,7 @@ impl VendorPlugin for GoodSmilePlugin {
         match parser_type {
             "goodsmile_confirm" => Some(Box::new(parsers::confirm::GoodSmileConfirmParser)),
             "goodsmile_send" => Some(Box::new(parsers::send::GoodSmileSendParser)),
+            // cancel は dispatch() 内で直接処理するため get_parser は None を返す
             _ => None,
         }
     }
@@ -51,7 +52,13 @@ impl VendorPlugin for GoodSmilePlugin {
                 shop_name: "グッドスマイルカンパニー".to_string(),
                 sender_address: "shop@goodsmile.jp".to_string(),
                 parser_type: "goodsmile_send".to_string(),
-                subject_filters: Some(vec!["ご注文商品発送のお知らせ".to_string()]),
+                subject_filters: Some(vec!["商品発送のお知らせ".to_string()]),
+            },
+            DefaultShopSetting {
+                shop_name: "グッドスマイルカンパニー".to_string(),
+                sender_address: "shop@goodsmile.jp".to_string(),
+                parser_type: "goodsmile_cancel".to_string(),
+                subject_filters: Some(vec!["キャンセル".to_string(), "返金".to_string()]),
             },
         ]
     }
@@ -69,13 +76,58 @@ impl VendorPlugin for GoodSmilePlugin {
     ) -> Result<DispatchOutcome, DispatchError> {
         let shop_domain = derive_shop_domain(from_address);
 
+        if parser_type == "goodsmile_cancel" {
+            let result = parsers::cancel::GoodSmileCancelParser
+                .parse_cancel(body)
+                .map_err(DispatchError::ParseFailed)?;
+
+            log::debug!(
+                "[goodsmile_cancel] email_id={} order_number={} items={} refund_amount={:?}",
+                email_id,
+                result.order_number,
+                result.cancels.len(),
+                result.refund_amount
+            );
+
+            // 返金完了メールは cancels が空（キャンセルは確定メールで適用済み）
+            for cancel_info in &result.cancels {
+                SqliteOrderRepository::apply_cancel_in_tx(
+                    tx,
+                    cancel_info,
+                    email_id,
+                    shop_domain.clone(),
+                    None,
+                )
+                .await
+                .map_err(DispatchError::SaveFailed)?;
+            }
+
+            if let Some(refund_amount) = result.refund_amount {
+                SqliteOrderRepository::apply_refund_in_tx(
+                    tx,
+                    &result.order_number,
+                    refund_amount,
+                    email_id,
+                    shop_domain,
+                    None,
+                )
+                .await
+                .map_err(DispatchError::SaveFailed)?;
+            }
+
+            return Ok(DispatchOutcome::CancelApplied {
+                order_number: result.order_number,
+            });
+        }
+
         // parser は同期処理のみ。await をまたがないようブロックで即 drop する。
-        let order_info = {
+        let mut order_info = {
             let parser = self.get_parser(parser_type).ok_or_else(|| {
                 DispatchError::ParseFailed(format!("No parser for type: {}", parser_type))
             })?;
             parser.parse(body).map_err(DispatchError::ParseFailed)?
         };
+        apply_tax_info(&mut order_info, body);
 
         log::debug!(
             "[{}] email_id={} order_number={}",
@@ -0,0 +1,182 @@
+use super::{body_to_lines, extract_items, extract_order_number};
+use crate::parsers::cancel_info::{classify_cancel_reason, CancelInfo};
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+/// `返金額：￥5,900` / `返金予定額：￥5,900` / `ご返金金額 ￥5,900` パターン
+static REFUND_AMOUNT_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?:ご返金金額|返金予定額|返金額)[：:]?\s*[¥￥]\s*([\d,]+)")
+        .expect("Invalid REFUND_AMOUNT_RE")
+});
+
+/// `キャンセル理由：メーカー都合により発売中止となったため` パターン
+static CANCEL_REASON_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"キャンセル理由[：:]\s*(.+)").expect("Invalid CANCEL_REASON_RE"));
+
+/// 返金完了を示す表記（キャンセル自体は確定メールで適用済みのため返金額の記録のみ行う）
+const REFUND_COMPLETED_KEYWORDS: &[&str] = &["返金手続きが完了", "ご返金が完了", "返金が完了"];
+
+/// グッドスマイルカンパニー キャンセル・返金メールの解析結果
+#[derive(Debug, Clone)]
+pub struct GoodSmileCancel {
+    pub order_number: String,
+    /// キャンセル対象（返金完了メールでは空）
+    pub cancels: Vec<CancelInfo>,
+    /// メールに記載された返金額（記載がなければ None）
+    pub refund_amount: Option<i64>,
+}
+
+/// グッドスマイルカンパニー キャンセル確定／返金完了メール用パーサー
+///
+/// 件名：`ご注文キャンセルのお知らせ` / `ご返金完了のお知らせ`
+/// 送信元：`shop@goodsmile.jp`（SendGrid 経由）
+///
+/// キャンセル確定メールは注文確認メールと同じ `商品:` ブロックで対象商品が並ぶ。
+/// 商品ブロックがなければ注文全体のキャンセル（`product_name = ""`）として扱う。
+/// 返金完了メールはキャンセルを再適用せず、返金額だけを返す。
+pub struct GoodSmileCancelParser;
+
+impl GoodSmileCancelParser {
+    /// メール本文からキャンセル対象と返金額を抽出する
+    pub fn parse_cancel(&self, email_body: &str) -> Result<GoodSmileCancel, String> {
+        let body_lines = body_to_lines(email_body);
+        let lines: Vec<&str> = body_lines.iter().map(|s| s.as_str()).collect();
+
+        let order_number =
+            extract_order_number(&lines).ok_or_else(|| "Order number not found".to_string())?;
+
+        let refund_amount = lines.iter().find_map(|line| {
+            REFUND_AMOUNT_RE
+                .captures(line)
+                .and_then(|c| c[1].replace(',', "").parse().ok())
+        });
+
+        let is_refund_notice = REFUND_COMPLETED_KEYWORDS
+            .iter()
+            .any(|k| email_body.contains(k));
+        if is_refund_notice {
+            if refund_amount.is_none() {
+                return Err("Refund amount not found".to_string());
+            }
+            return Ok(GoodSmileCancel {
+                order_number,
+                cancels: Vec::new(),
+                refund_amount,
+            });
+        }
+
+        let reason_detail = lines.iter().find_map(|line| {
+            CANCEL_REASON_RE
+                .captures(line)
+                .map(|c| c[1].trim().to_string())
+                .filter(|s| !s.is_empty())
+        });
+        let reason = reason_detail.as_deref().map(classify_cancel_reason);
+
+        let mut cancels: Vec<CancelInfo> = extract_items(&lines)
+            .into_iter()
+            .map(|item| CancelInfo {
+                order_number: order_number.clone(),
+                product_name: item.name,
+                cancel_quantity: item.quantity,
+                reason,
+                reason_detail: reason_detail.clone(),
+            })
+            .collect();
+        if cancels.is_empty() {
+            cancels.push(CancelInfo {
+                order_number: order_number.clone(),
+                product_name: String::new(),
+                cancel_quantity: 0,
+                reason,
+                reason_detail,
+            });
+        }
+
+        Ok(GoodSmileCancel {
+            order_number,
+            cancels,
+            refund_amount,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parsers::cancel_info::CancelReason;
+
+    fn sample_cancel_plain() -> &'static str {
+        r#"※このメールはシステムより自動送信されています。
+山田 太郎 様
+グッドスマイルカンパニー公式ショップをご利用いただき誠にありがとうございます。
+下記ご注文のキャンセル手続きが完了いたしました。
+------------------------------------
+注文番号: CpBk4quaORPw
+キャンセル理由：メーカー都合により発売中止となったため
+商品:MODEROID バーンドラゴン
+数量：1
+小計：￥5,900
+返金予定額：￥5,900
+------------------------------------
+"#
+    }
+
+    fn sample_refund_html() -> &'static str {
+        r#"<html><body>
+山田 太郎 様<br>
+下記ご注文のご返金が完了いたしましたのでお知らせいたします。<br>
+------------------------------------<br>
+注文番号: CpBk4quaORPw<br>
+ご返金金額 ￥5,900<br>
+------------------------------------<br>
+</body></html>"#
+    }
+
+    #[test]
+    fn test_parse_cancel_items_and_refund() {
+        let result = GoodSmileCancelParser
+            .parse_cancel(sample_cancel_plain())
+            .unwrap();
+        assert_eq!(result.order_number, "CpBk4quaORPw");
+        assert_eq!(result.refund_amount, Some(5900));
+        assert_eq!(result.cancels.len(), 1);
+        let cancel = &result.cancels[0];
+        assert_eq!(cancel.product_name, "MODEROID バーンドラゴン");
+        assert_eq!(cancel.cancel_quantity, 1);
+        assert_eq!(cancel.reason, Some(CancelReason::Discontinued));
+    }
+
+    #[test]
+    fn test_parse_cancel_without_items_cancels_whole_order() {
+        let body = "注文番号: CpBk4quaORPw\nご注文をキャンセルいたしました。";
+        let result = GoodSmileCancelParser.parse_cancel(body).unwrap();
+        assert_eq!(result.refund_amount, None);
+        assert_eq!(result.cancels.len(), 1);
+        assert!(result.cancels[0].product_name.is_empty());
+        assert_eq!(result.cancels[0].reason, None);
+    }
+
+    #[test]
+    fn test_parse_refund_notice_has_no_cancels() {
+        let result = GoodSmileCancelParser
+            .parse_cancel(sample_refund_html())
+            .unwrap();
+        assert_eq!(result.order_number, "CpBk4quaORPw");
+        assert!(result.cancels.is_empty());
+        assert_eq!(result.refund_amount, Some(5900));
+    }
+
+    #[test]
+    fn test_parse_refund_notice_without_amount_returns_error() {
+        let body = "注文番号: CpBk4quaORPw\n返金手続きが完了いたしました。";
+        assert!(GoodSmileCancelParser.parse_cancel(body).is_err());
+    }
+
+    #[test]
+    fn test_parse_cancel_no_order_number_returns_error() {
+        assert!(GoodSmileCancelParser
+            .parse_cancel("ご注文をキャンセルいたしました。")
+            .is_err());
+    }
+}
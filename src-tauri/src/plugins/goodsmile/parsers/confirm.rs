@@ -45,6 +45,8 @@ impl EmailParser for GoodSmileConfirmParser {
             subtotal,
             shipping_fee,
             total_amount,
+            tax_amount: None,
+            tax_included: true,
         })
     }
 }
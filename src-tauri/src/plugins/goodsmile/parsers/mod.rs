@@ -1,7 +1,9 @@
+use crate::parsers::release_date::{extract_fuzzy_release_date, FuzzyReleaseDate};
 use crate::parsers::OrderItem;
 use once_cell::sync::Lazy;
 use regex::Regex;
 
+pub mod cancel;
 pub mod confirm;
 pub mod send;
 
@@ -39,9 +41,15 @@ static SHIPPING_RE: Lazy<Regex> =
 static TOTAL_RE: Lazy<Regex> =
     Lazy::new(|| Regex::new(r"^合計\s+[¥￥]([\d,]+)").expect("Invalid TOTAL_RE"));
 
-/// `配送番号：564841939476` パターン
+/// `配送番号：564841939476` / `配送番号：1234-5678-9012` パターン（ハイフンは抽出後に除去）
 static TRACKING_NUMBER_RE: Lazy<Regex> =
-    Lazy::new(|| Regex::new(r"配送番号[：:]\s*(\d+)").expect("Invalid TRACKING_NUMBER_RE"));
+    Lazy::new(|| Regex::new(r"配送番号[：:]\s*(\d[\d-]*\d)").expect("Invalid TRACKING_NUMBER_RE"));
+
+/// `追跡番号：http://...?okurijoNo=564841939476` パターン（URL のクエリ値から番号を取る）
+static TRACKING_URL_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"追跡番号[：:]\s*https?://\S*?[?&][A-Za-z_]+=(\d{8,})")
+        .expect("Invalid TRACKING_URL_RE")
+});
 
 /// `4580590207912 1` 形式の JAN コード（13 桁）+ 数量行パターン
 static JAN_QUANTITY_RE: Lazy<Regex> =
@@ -114,12 +122,13 @@ fn parse_english_datetime(s: &str) -> Option<String> {
 
 /// `商品:` 行を起点に注文商品リストを抽出する
 ///
-/// `商品:` マーカーの直後の非空行が商品名、その後の `数量：` / `小計：` 行で
-/// quantity / subtotal を取得する。複数商品にも対応する。
+/// `商品:` マーカーの直後の非空行が商品名、その後の `発売時期：` / `数量：` / `小計：` 行で
+/// release_date / quantity / subtotal を取得する。複数商品にも対応する。
 pub fn extract_items(lines: &[&str]) -> Vec<OrderItem> {
     let mut items: Vec<OrderItem> = Vec::new();
     let mut current_name: Option<String> = None;
     let mut current_quantity: Option<i64> = None;
+    let mut current_release_date: Option<FuzzyReleaseDate> = None;
     let mut after_product_marker = false;
 
     for line in lines {
@@ -137,9 +146,12 @@ pub fn extract_items(lines: &[&str]) -> Vec<OrderItem> {
                     quantity,
                     subtotal: 0,
                     image_url: None,
+                    item_url: None,
+                    release_date: current_release_date.take(),
                 });
             }
             current_quantity = None;
+            current_release_date = None;
             after_product_marker = true;
 
             // `商品:商品名` のように同一行に商品名が含まれる場合
@@ -158,6 +170,12 @@ pub fn extract_items(lines: &[&str]) -> Vec<OrderItem> {
             continue;
         }
 
+        // 発売時期行（`発売時期：2025/9`）
+        if trimmed.starts_with("発売時期") {
+            current_release_date = extract_fuzzy_release_date(trimmed, None);
+            continue;
+        }
+
         // 数量行
         if let Some(caps) = QUANTITY_RE.captures(trimmed) {
             current_quantity = caps[1].parse().ok();
@@ -182,6 +200,8 @@ pub fn extract_items(lines: &[&str]) -> Vec<OrderItem> {
                     quantity,
                     subtotal,
                     image_url: None,
+                    item_url: None,
+                    release_date: current_release_date.take(),
                 });
                 current_quantity = None;
             }
@@ -211,10 +231,21 @@ pub fn extract_total_amount(lines: &[&str]) -> Option<i64> {
 }
 
 /// `配送番号：564841939476` 行から追跡番号を抽出する
+///
+/// `配送番号：` 行がないメールでは `追跡番号：` の問い合わせ URL のクエリ値にフォールバックする。
 pub fn extract_tracking_number(lines: &[&str]) -> Option<String> {
     lines
         .iter()
-        .find_map(|line| TRACKING_NUMBER_RE.captures(line).map(|c| c[1].to_string()))
+        .find_map(|line| {
+            TRACKING_NUMBER_RE
+                .captures(line)
+                .map(|c| c[1].replace('-', ""))
+        })
+        .or_else(|| {
+            lines
+                .iter()
+                .find_map(|line| TRACKING_URL_RE.captures(line).map(|c| c[1].to_string()))
+        })
 }
 
 /// 配送情報セクション内の商品リストを抽出する（send メール用）
@@ -264,6 +295,8 @@ pub fn extract_send_items(lines: &[&str]) -> Vec<OrderItem> {
                     quantity,
                     subtotal: 0,
                     image_url: None,
+                    item_url: None,
+                    release_date: None,
                 });
             }
             continue;
@@ -376,6 +409,26 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_extract_tracking_number_strips_hyphens() {
+        let lines = vec!["配送番号：1234-5678-9012"];
+        assert_eq!(
+            extract_tracking_number(&lines),
+            Some("123456789012".to_string())
+        );
+    }
+
+    #[test]
+    fn test_extract_tracking_number_falls_back_to_url() {
+        let lines = vec![
+            "配送元：佐川急便(送料無料) 配送時間：指定なし 追跡番号：http://k2k.sagawa-exp.co.jp/p/web/okurijosearch.do?okurijoNo=564841939476",
+        ];
+        assert_eq!(
+            extract_tracking_number(&lines),
+            Some("564841939476".to_string())
+        );
+    }
+
     #[test]
     fn test_extract_carrier_strips_parentheses() {
         let lines = vec!["配送元：佐川急便(送料無料)"];
@@ -460,6 +513,13 @@ mod tests {
         assert_eq!(items[0].quantity, 1);
         assert_eq!(items[0].subtotal, 5900);
         assert_eq!(items[0].unit_price, 5900);
+        assert_eq!(
+            items[0]
+                .release_date
+                .as_ref()
+                .map(FuzzyReleaseDate::to_release_date),
+            Some("2025-09".to_string())
+        );
     }
 
     #[test]
@@ -13,7 +13,7 @@ use crate::parsers::{DeliveryInfo, EmailParser, OrderInfo};
 /// HTML メールでは `配送元・配送時間・追跡番号` が 1 行に並ぶため、
 /// `body_to_lines()` で `<br>` を改行に変換後に各フィールドを抽出する。
 /// 金額情報は発送通知メールに含まれないため、subtotal / shipping_fee / total_amount は None。
-/// 追跡番号は `追跡番号：http://...` の URL からではなく `配送番号：` 行から取得する。
+/// 追跡番号は `配送番号：` 行から取得し、行がない場合のみ `追跡番号：http://...` の URL から取る。
 pub struct GoodSmileSendParser;
 
 impl EmailParser for GoodSmileSendParser {
@@ -54,6 +54,8 @@ impl EmailParser for GoodSmileSendParser {
             subtotal: None,
             shipping_fee: None,
             total_amount: None,
+            tax_amount: None,
+            tax_included: true,
         })
     }
 }
@@ -213,6 +215,17 @@ MODEROID バーンドラゴン
         assert!(delivery.delivery_time.is_none());
     }
 
+    /// `配送番号：` 行がなくても追跡 URL から追跡番号を取れる
+    #[test]
+    fn test_parse_send_tracking_number_from_url() {
+        let body = sample_send_plain().replace("配送番号：564841939476\n", "");
+        let order = GoodSmileSendParser.parse(&body).unwrap();
+        assert_eq!(order.items.len(), 1);
+        let delivery = order.delivery_info.unwrap();
+        assert_eq!(delivery.tracking_number, "564841939476");
+        assert_eq!(delivery.carrier, "佐川急便");
+    }
+
     // ─── エラーケース ───
 
     #[test]
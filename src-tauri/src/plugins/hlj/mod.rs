@@ -0,0 +1,101 @@
+//! HobbyLink Japan（HLJ）プラグイン
+//!
+//! `@hlj.com` から配信される英語の注文確認メール（Order Confirmation）を取り込む。
+//! 金額は `¥` / `JPY` の英語表記のため、[`crate::parsers::parse_amount`] の英語ロケールで読む。
+
+pub mod parsers;
+
+use async_trait::async_trait;
+
+use crate::logic::language::EmailLanguage;
+use crate::parsers::EmailParser;
+use crate::repository::SqliteOrderRepository;
+
+use super::{
+    apply_internal_date, derive_shop_domain, DefaultShopSetting, DispatchError, DispatchOutcome,
+    PluginRegistration, VendorPlugin,
+};
+
+pub struct HljPlugin;
+
+#[async_trait]
+impl VendorPlugin for HljPlugin {
+    fn parser_types(&self) -> &[&str] {
+        &["hlj_confirm"]
+    }
+
+    fn priority(&self) -> i32 {
+        10
+    }
+
+    fn get_parser(&self, parser_type: &str) -> Option<Box<dyn EmailParser>> {
+        match parser_type {
+            "hlj_confirm" => Some(Box::new(parsers::confirm::HljConfirmParser)),
+            _ => None,
+        }
+    }
+
+    fn shop_name(&self) -> &str {
+        "HobbyLink Japan"
+    }
+
+    fn default_shop_settings(&self) -> Vec<DefaultShopSetting> {
+        vec![DefaultShopSetting {
+            shop_name: "HobbyLink Japan".to_string(),
+            sender_address: "orders@hlj.com".to_string(),
+            parser_type: "hlj_confirm".to_string(),
+            subject_filters: Some(vec!["Order Confirmation".to_string()]),
+        }]
+    }
+
+    fn parser_language(&self, _parser_type: &str) -> EmailLanguage {
+        EmailLanguage::English
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    async fn dispatch(
+        &self,
+        parser_type: &str,
+        email_id: i64,
+        from_address: Option<&str>,
+        shop_name: &str,
+        internal_date: Option<i64>,
+        body: &str,
+        tx: &mut sqlx::Transaction<'_, sqlx::Sqlite>,
+    ) -> Result<DispatchOutcome, DispatchError> {
+        let shop_domain = derive_shop_domain(from_address);
+
+        let mut order_info = {
+            let parser = self.get_parser(parser_type).ok_or_else(|| {
+                DispatchError::ParseFailed(format!("No parser for type: {}", parser_type))
+            })?;
+            parser.parse(body).map_err(DispatchError::ParseFailed)?
+        };
+
+        // 海外発送の免税価格で、日本語の消費税表記もないため apply_tax_info は使わない
+        apply_internal_date(&mut order_info, internal_date);
+
+        log::debug!(
+            "[{}] email_id={} order_number={}",
+            parser_type,
+            email_id,
+            order_info.order_number
+        );
+
+        SqliteOrderRepository::save_order_in_tx(
+            tx,
+            &order_info,
+            Some(email_id),
+            shop_domain,
+            Some(shop_name.to_string()),
+        )
+        .await
+        .map_err(DispatchError::SaveFailed)?;
+
+        Ok(DispatchOutcome::OrderSaved(Box::new(order_info)))
+    }
+}
+
+inventory::submit!(PluginRegistration {
+    factory: || Box::new(HljPlugin),
+});
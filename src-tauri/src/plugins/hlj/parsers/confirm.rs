@@ -0,0 +1,230 @@
+use crate::logic::language::EmailLanguage;
+use crate::parsers::{normalize_item_url, parse_amount, EmailParser, Label, OrderInfo, OrderItem};
+use chrono::NaiveDate;
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+/// `Order Number: 1234567` / `Order #1234567` パターン
+static ORDER_NUMBER_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"Order\s*(?:Number|No\.?|#)\s*[:#]?\s*(\d{5,})").expect("Invalid ORDER_NUMBER_RE")
+});
+
+/// `Order Date: March 10, 2025` / `Order Date: 2025-03-10`
+static ORDER_DATE: Lazy<Label> = Lazy::new(|| {
+    Label::new("Order Date")
+        .colon()
+        .value(r"[A-Za-z]+\.? \d{1,2}, \d{4}|\d{4}[-/]\d{1,2}[-/]\d{1,2}")
+        .build()
+});
+
+static ITEM_NAME: Lazy<Label> = Lazy::new(|| Label::new("Item").alias("Product").colon().build());
+static ITEM_CODE: Lazy<Label> = Lazy::new(|| Label::new("Item Code").colon().build());
+static ITEM_URL: Lazy<Label> = Lazy::new(|| Label::new("URL").colon().build());
+static UNIT_PRICE: Lazy<Label> = Lazy::new(|| Label::new("Price").colon().build());
+static QUANTITY: Lazy<Label> = Lazy::new(|| {
+    Label::new("Quantity")
+        .alias("Qty")
+        .colon()
+        .value(r"\d+")
+        .build()
+});
+
+static SUBTOTAL: Lazy<Label> = Lazy::new(|| Label::new("Subtotal").colon().build());
+static SHIPPING_FEE: Lazy<Label> = Lazy::new(|| Label::new("Shipping").colon().build());
+/// `Total:` / `Grand Total:` / `Order Total:`（大文字始まりのため `Subtotal:` には一致しない）
+static TOTAL_AMOUNT: Lazy<Label> = Lazy::new(|| Label::new("Total").colon().build());
+
+/// 注文日の書式（`March 10, 2025` / `Mar 10, 2025` / `Mar. 10, 2025` / `2025-03-10`）
+const ORDER_DATE_FORMATS: &[&str] = &["%B %d, %Y", "%b %d, %Y", "%Y-%m-%d", "%Y/%m/%d"];
+
+/// HobbyLink Japan 注文確認メール用パーサー（英語）
+///
+/// 件名：`HobbyLink Japan Order Confirmation (Order #1234567)`
+/// 送信元：`orders@hlj.com`
+///
+/// 商品は `Item:` 行から始まるブロックで並び、`Item Code:`・`URL:`・`Price:`・`Quantity:` が続く。
+/// 金額は `¥4,180` / `JPY 23,980.00` のような英語表記で、[`parse_amount`] の英語ロケールで読む。
+/// 送料は発送時に確定するため、`Shipping:` が金額でない場合（`TBD` 等）は None とする。
+pub struct HljConfirmParser;
+
+fn extract_order_date(body: &str) -> Option<String> {
+    let value = ORDER_DATE.find(body)?.replace('.', "");
+    ORDER_DATE_FORMATS
+        .iter()
+        .find_map(|format| NaiveDate::parse_from_str(&value, format).ok())
+        .map(|date| date.format("%Y-%m-%d").to_string())
+}
+
+fn extract_items(lines: &[&str]) -> Vec<OrderItem> {
+    let mut items: Vec<OrderItem> = Vec::new();
+
+    for line in lines {
+        if SUBTOTAL.match_line(line).is_some() {
+            // 合計欄に入ったら商品ブロックは終わり
+            break;
+        }
+        if let Some(code) = ITEM_CODE.match_line(line) {
+            if let Some(item) = items.last_mut() {
+                item.model_number = Some(code);
+            }
+            continue;
+        }
+        if let Some(name) = ITEM_NAME.match_line(line) {
+            items.push(OrderItem {
+                name,
+                manufacturer: None,
+                model_number: None,
+                unit_price: 0,
+                quantity: 1,
+                subtotal: 0,
+                image_url: None,
+                item_url: None,
+                release_date: None,
+            });
+            continue;
+        }
+        let Some(item) = items.last_mut() else {
+            continue;
+        };
+
+        if let Some(url) = ITEM_URL.match_line(line) {
+            item.item_url = normalize_item_url(&url);
+        } else if let Some(quantity) = QUANTITY.match_line(line) {
+            item.quantity = quantity.parse().unwrap_or(1);
+        } else if let Some(price) = UNIT_PRICE.match_line(line) {
+            item.unit_price = parse_amount(&price, EmailLanguage::English).unwrap_or(0);
+        }
+    }
+
+    for item in &mut items {
+        item.subtotal = item.unit_price * item.quantity;
+    }
+    items
+}
+
+impl EmailParser for HljConfirmParser {
+    fn parse(&self, email_body: &str) -> Result<OrderInfo, String> {
+        let lines: Vec<&str> = email_body.lines().map(str::trim).collect();
+
+        let order_number = ORDER_NUMBER_RE
+            .captures(email_body)
+            .map(|caps| caps[1].to_string())
+            .ok_or_else(|| "Order number not found".to_string())?;
+
+        let items = extract_items(&lines);
+        if items.is_empty() {
+            return Err("No items found".to_string());
+        }
+
+        Ok(OrderInfo {
+            order_number,
+            order_date: extract_order_date(email_body),
+            delivery_address: None,
+            delivery_info: None,
+            items,
+            subtotal: SUBTOTAL.find_amount(email_body, EmailLanguage::English),
+            shipping_fee: SHIPPING_FEE.find_amount(email_body, EmailLanguage::English),
+            total_amount: TOTAL_AMOUNT.find_amount(email_body, EmailLanguage::English),
+            tax_amount: None,
+            tax_included: true,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_confirm() -> &'static str {
+        r#"Dear Taro Yamada,
+
+Thank you for shopping at HobbyLink Japan!
+This email confirms that we have received your order.
+
+Order Number: 1234567
+Order Date: March 10, 2025
+
+----------------------------------------
+Item: 1/100 MG Gundam Exia
+Item Code: BAN2345678
+URL: https://www.hlj.com/1-100-mg-gundam-exia-ban2345678?utm_source=email
+Price: ¥4,180
+Quantity: 1
+----------------------------------------
+Item: figma Link Tears of the Kingdom Ver.
+Item Code: GSC12345
+Price: JPY 9,900
+Quantity: 2
+----------------------------------------
+
+Subtotal: ¥23,980
+Shipping: TBD (calculated when your order ships)
+Total: JPY 23,980.00
+"#
+    }
+
+    #[test]
+    fn test_parse_confirm_order_number_and_date() {
+        let order = HljConfirmParser.parse(sample_confirm()).unwrap();
+        assert_eq!(order.order_number, "1234567");
+        assert_eq!(order.order_date.as_deref(), Some("2025-03-10"));
+    }
+
+    #[test]
+    fn test_parse_confirm_order_number_in_header() {
+        let body = sample_confirm().replace("Order Number: 1234567", "Order #7654321");
+        let order = HljConfirmParser.parse(&body).unwrap();
+        assert_eq!(order.order_number, "7654321");
+    }
+
+    #[test]
+    fn test_parse_confirm_items() {
+        let order = HljConfirmParser.parse(sample_confirm()).unwrap();
+        assert_eq!(order.items.len(), 2);
+
+        let first = &order.items[0];
+        assert_eq!(first.name, "1/100 MG Gundam Exia");
+        assert_eq!(first.model_number.as_deref(), Some("BAN2345678"));
+        assert_eq!(
+            first.item_url.as_deref(),
+            Some("https://www.hlj.com/1-100-mg-gundam-exia-ban2345678")
+        );
+        assert_eq!(first.unit_price, 4180);
+        assert_eq!(first.quantity, 1);
+
+        let second = &order.items[1];
+        assert_eq!(second.name, "figma Link Tears of the Kingdom Ver.");
+        assert_eq!(second.unit_price, 9900);
+        assert_eq!(second.quantity, 2);
+        assert_eq!(second.subtotal, 19800);
+    }
+
+    #[test]
+    fn test_parse_confirm_amounts() {
+        let order = HljConfirmParser.parse(sample_confirm()).unwrap();
+        assert_eq!(order.subtotal, Some(23980));
+        assert_eq!(order.shipping_fee, None);
+        assert_eq!(order.total_amount, Some(23980));
+    }
+
+    #[test]
+    fn test_parse_confirm_short_month_date() {
+        let body = sample_confirm().replace("March 10, 2025", "Mar. 9, 2025");
+        let order = HljConfirmParser.parse(&body).unwrap();
+        assert_eq!(order.order_date.as_deref(), Some("2025-03-09"));
+    }
+
+    #[test]
+    fn test_parse_confirm_missing_order_number() {
+        assert!(HljConfirmParser
+            .parse("Item: Test\nPrice: ¥100\nQuantity: 1")
+            .is_err());
+    }
+
+    #[test]
+    fn test_parse_confirm_no_items() {
+        assert!(HljConfirmParser
+            .parse("Order Number: 1234567\nTotal: ¥0")
+            .is_err());
+    }
+}
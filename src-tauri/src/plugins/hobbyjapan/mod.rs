@@ -11,8 +11,8 @@ use crate::parsers::EmailParser;
 use crate::repository::SqliteOrderRepository;
 
 use super::{
-    apply_internal_date, derive_shop_domain, DefaultShopSetting, DispatchError, DispatchOutcome,
-    PluginRegistration, VendorPlugin,
+    apply_internal_date, apply_tax_info, derive_shop_domain, DefaultShopSetting, DispatchError,
+    DispatchOutcome, PluginRegistration, VendorPlugin,
 };
 
 pub struct HjPlugin;
@@ -68,6 +68,7 @@ impl VendorPlugin for HjPlugin {
         };
 
         apply_internal_date(&mut order_info, internal_date);
+        apply_tax_info(&mut order_info, body);
 
         log::debug!(
             "[{}] email_id={} order_number={}",
@@ -102,6 +102,8 @@ pub fn extract_items(lines: &[&str]) -> Vec<OrderItem> {
                     quantity,
                     subtotal,
                     image_url: None,
+                    item_url: None,
+                    release_date: None,
                 });
                 pending_name = None;
                 continue;
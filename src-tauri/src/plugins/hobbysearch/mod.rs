@@ -11,8 +11,8 @@ use crate::parsers::EmailParser;
 use crate::repository::SqliteOrderRepository;
 
 use super::{
-    apply_internal_date, derive_shop_domain, DefaultShopSetting, DispatchError, DispatchOutcome,
-    PluginRegistration, VendorPlugin,
+    apply_internal_date, apply_tax_info, derive_shop_domain, DefaultShopSetting, DispatchError,
+    DispatchOutcome, PluginRegistration, VendorPlugin,
 };
 
 pub struct HobbySearchPlugin;
@@ -27,6 +27,8 @@ impl VendorPlugin for HobbySearchPlugin {
             "hobbysearch_change_yoyaku",
             "hobbysearch_send",
             "hobbysearch_cancel",
+            "hobbysearch_preparing",
+            "hobbysearch_delay",
         ]
     }
 
@@ -35,7 +37,7 @@ impl VendorPlugin for HobbySearchPlugin {
     }
 
     /// `OrderInfo` を返すパーサーのみ。
-    /// cancel は `dispatch()` 内で直接処理する。
+    /// cancel / preparing / delay は `dispatch()` 内で直接処理する。
     fn get_parser(&self, parser_type: &str) -> Option<Box<dyn EmailParser>> {
         match parser_type {
             "hobbysearch_confirm" => Some(Box::new(parsers::confirm::HobbySearchConfirmParser)),
@@ -73,6 +75,18 @@ impl VendorPlugin for HobbySearchPlugin {
                     "【ホビーサーチ】ご注文の発送が完了しました".to_string()
                 ]),
             },
+            DefaultShopSetting {
+                shop_name: "ホビーサーチ".to_string(),
+                sender_address: "hs-support@1999.co.jp".to_string(),
+                parser_type: "hobbysearch_preparing".to_string(),
+                subject_filters: Some(vec!["【ホビーサーチ】出荷準備中のお知らせ".to_string()]),
+            },
+            DefaultShopSetting {
+                shop_name: "ホビーサーチ".to_string(),
+                sender_address: "hs-support@1999.co.jp".to_string(),
+                parser_type: "hobbysearch_delay".to_string(),
+                subject_filters: Some(vec!["【ホビーサーチ】入荷予定変更のお知らせ".to_string()]),
+            },
             DefaultShopSetting {
                 shop_name: "ホビーサーチ".to_string(),
                 sender_address: "hs-support@1999.co.jp".to_string(),
@@ -161,6 +175,94 @@ impl VendorPlugin for HobbySearchPlugin {
                 })
             }
 
+            // ── 出荷準備中 ─────────────────────────────────────────────────────
+            "hobbysearch_preparing" => {
+                let order_numbers = parsers::preparing::HobbySearchPreparingParser
+                    .parse_order_numbers(body)
+                    .map_err(DispatchError::ParseFailed)?;
+
+                log::debug!(
+                    "[hobbysearch_preparing] email_id={} order_numbers={:?}",
+                    email_id,
+                    order_numbers
+                );
+
+                // 代表注文番号が DB 上の注文と一致しない場合があるため、1件でも適用できれば成功とする
+                let mut applied: Option<String> = None;
+                let mut last_error = None;
+                for order_number in &order_numbers {
+                    match SqliteOrderRepository::apply_preparing_in_tx(
+                        tx,
+                        order_number,
+                        email_id,
+                        shop_domain.clone(),
+                        None,
+                    )
+                    .await
+                    {
+                        Ok(_) => {
+                            applied.get_or_insert_with(|| order_number.clone());
+                        }
+                        Err(e) => last_error = Some(e),
+                    }
+                }
+
+                match applied {
+                    Some(order_number) => Ok(DispatchOutcome::PreparingApplied { order_number }),
+                    None => {
+                        Err(DispatchError::SaveFailed(last_error.unwrap_or_else(|| {
+                            "No order to apply preparing".to_string()
+                        })))
+                    }
+                }
+            }
+
+            // ── 入荷予定変更 ───────────────────────────────────────────────────
+            "hobbysearch_delay" => {
+                // 年の記載がない入荷予定（`5月下旬`）はメール受信日（JST）を基準に年を補う
+                let reference = internal_date
+                    .and_then(DateTime::from_timestamp_millis)
+                    .map(|dt| dt.with_timezone(&chrono_tz::Asia::Tokyo).date_naive());
+                let delays = parsers::delay::HobbySearchDelayParser
+                    .parse_delays(body, reference)
+                    .map_err(DispatchError::ParseFailed)?;
+
+                log::debug!(
+                    "[hobbysearch_delay] email_id={} order_numbers={:?}",
+                    email_id,
+                    delays.iter().map(|d| &d.order_number).collect::<Vec<_>>()
+                );
+
+                // 一部の注文が DB にない場合もあるため、1件でも適用できれば成功とする
+                let mut applied: Option<String> = None;
+                let mut last_error = None;
+                for delay_info in &delays {
+                    match SqliteOrderRepository::apply_release_delay_in_tx(
+                        tx,
+                        delay_info,
+                        email_id,
+                        shop_domain.clone(),
+                        None,
+                    )
+                    .await
+                    {
+                        Ok(_) => {
+                            applied.get_or_insert_with(|| delay_info.order_number.clone());
+                        }
+                        Err(e) => last_error = Some(e),
+                    }
+                }
+
+                match applied {
+                    Some(order_number) => Ok(DispatchOutcome::ReleaseDateChanged { order_number }),
+                    None => {
+                        Err(DispatchError::SaveFailed(last_error.unwrap_or_else(|| {
+                            "No order to apply release delay".to_string()
+                        })))
+                    }
+                }
+            }
+
             // ── 組み換え（変更・予約変更）──────────────────────────────────────
             "hobbysearch_change" | "hobbysearch_change_yoyaku" => {
                 // parser は同期処理のみ。await をまたがないようブロックで即 drop する。
@@ -173,6 +275,7 @@ impl VendorPlugin for HobbySearchPlugin {
 
                 // hobbysearch_change / change_yoyaku: internal_date を order_date に使用
                 apply_internal_date(&mut order_info, internal_date);
+                apply_tax_info(&mut order_info, body);
 
                 // internal_date が無効値の場合、apply_change_items_in_tx をスキップして
                 // save_order_in_tx にフォールバックする（データ欠損よりは安全）
@@ -296,6 +399,7 @@ impl VendorPlugin for HobbySearchPlugin {
                 ) {
                     apply_internal_date(&mut order_info, internal_date);
                 }
+                apply_tax_info(&mut order_info, body);
 
                 SqliteOrderRepository::save_order_in_tx(
                     tx,
@@ -327,6 +431,8 @@ mod tests {
         assert!(types.contains(&"hobbysearch_change_yoyaku"));
         assert!(types.contains(&"hobbysearch_send"));
         assert!(types.contains(&"hobbysearch_cancel"));
+        assert!(types.contains(&"hobbysearch_preparing"));
+        assert!(types.contains(&"hobbysearch_delay"));
     }
 
     #[test]
@@ -371,6 +477,19 @@ mod tests {
         assert!(plugin.get_parser("hobbysearch_cancel").is_none());
     }
 
+    #[test]
+    fn test_hobbysearch_plugin_get_parser_preparing_returns_none() {
+        // preparing も dispatch() 内で直接処理する
+        let plugin = HobbySearchPlugin;
+        assert!(plugin.get_parser("hobbysearch_preparing").is_none());
+    }
+
+    #[test]
+    fn test_hobbysearch_plugin_get_parser_delay_returns_none() {
+        // delay も dispatch() 内で直接処理する
+        assert!(HobbySearchPlugin.get_parser("hobbysearch_delay").is_none());
+    }
+
     #[test]
     fn test_hobbysearch_no_alternate_domains() {
         let plugin = HobbySearchPlugin;
@@ -385,7 +504,7 @@ mod tests {
 
     #[test]
     fn test_hobbysearch_default_shop_settings_count() {
-        assert_eq!(HobbySearchPlugin.default_shop_settings().len(), 8);
+        assert_eq!(HobbySearchPlugin.default_shop_settings().len(), 10);
     }
 
     #[test]
@@ -398,6 +517,8 @@ mod tests {
         assert!(parser_types.contains(&"hobbysearch_change_yoyaku"));
         assert!(parser_types.contains(&"hobbysearch_confirm_yoyaku"));
         assert!(parser_types.contains(&"hobbysearch_confirm"));
+        assert!(parser_types.contains(&"hobbysearch_preparing"));
+        assert!(parser_types.contains(&"hobbysearch_delay"));
     }
 }
 
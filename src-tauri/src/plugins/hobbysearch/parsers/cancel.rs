@@ -2,8 +2,10 @@
 //!
 //! [キャンセル] セクションから注文番号・商品名・キャンセル個数を抽出する。
 
+use super::ORDER_NUMBER_VALUE;
 use crate::parsers::cancel_info::CancelInfo;
-use regex::Regex;
+use crate::parsers::Label;
+use once_cell::sync::Lazy;
 
 /// キャンセルメール用パーサー
 pub struct HobbySearchCancelParser;
@@ -11,71 +13,39 @@ pub struct HobbySearchCancelParser;
 impl HobbySearchCancelParser {
     /// メール本文からキャンセル情報を抽出する
     pub fn parse_cancel(&self, email_body: &str) -> Result<CancelInfo, String> {
-        let lines: Vec<&str> = email_body.lines().collect();
-
-        let order_number = extract_order_number(&lines)?;
-        let product_name = extract_product_name(&lines)?;
-        let cancel_quantity = extract_cancel_quantity(&lines)?;
+        let order_number = ORDER_NUMBER_LABEL
+            .find(email_body)
+            .ok_or_else(|| "Order number not found".to_string())?;
+        let product_name = PRODUCT_NAME_LABEL
+            .find(email_body)
+            .ok_or_else(|| "Product name not found".to_string())?;
+        // キャンセル個数が明示されていない場合は 1 とする（形式違いのメールに対応）
+        let cancel_quantity = CANCEL_QUANTITY_LABEL.find_i64(email_body).unwrap_or(1);
 
         Ok(CancelInfo {
             order_number,
-            product_name: product_name.trim().to_string(),
+            product_name,
             cancel_quantity,
+            reason: None,
+            reason_detail: None,
         })
     }
 }
 
-/// 注文番号を抽出（注文番号 ： XX-XXXX-XXXX 形式）
-fn extract_order_number(lines: &[&str]) -> Result<String, String> {
-    let re =
-        Regex::new(r"注文番号\s*[：:]\s*(\d+-\d+-\d+)").map_err(|e| format!("Regex error: {e}"))?;
-
-    for line in lines {
-        if let Some(captures) = re.captures(line) {
-            if let Some(m) = captures.get(1) {
-                return Ok(m.as_str().to_string());
-            }
-        }
-    }
+/// `注文番号 ： XX-XXXX-XXXX`
+static ORDER_NUMBER_LABEL: Lazy<Label> = Lazy::new(|| {
+    Label::new("注文番号")
+        .colon()
+        .value(ORDER_NUMBER_VALUE)
+        .build()
+});
 
-    Err("Order number not found".to_string())
-}
-
-/// 商品名を抽出（商品名 ： ... 形式）
-fn extract_product_name(lines: &[&str]) -> Result<String, String> {
-    let re = Regex::new(r"商品名\s*[：:]\s*(.+)").map_err(|e| format!("Regex error: {e}"))?;
-
-    for line in lines {
-        if let Some(captures) = re.captures(line) {
-            if let Some(m) = captures.get(1) {
-                return Ok(m.as_str().to_string());
-            }
-        }
-    }
+/// `商品名 ： ...`
+static PRODUCT_NAME_LABEL: Lazy<Label> = Lazy::new(|| Label::new("商品名").colon().build());
 
-    Err("Product name not found".to_string())
-}
-
-/// キャンセル個数を抽出（キャンセル個数 ： N 形式）
-/// 見つからない場合は 1 をデフォルトとする（形式違いのメールに対応）
-fn extract_cancel_quantity(lines: &[&str]) -> Result<i64, String> {
-    let re = Regex::new(r"キャンセル個数\s*[：:＝=]\s*(\d+)")
-        .map_err(|e| format!("Regex error: {e}"))?;
-
-    for line in lines {
-        if let Some(captures) = re.captures(line) {
-            if let Some(m) = captures.get(1) {
-                return m
-                    .as_str()
-                    .parse::<i64>()
-                    .map_err(|e| format!("Invalid cancel quantity: {e}"));
-            }
-        }
-    }
-
-    // キャンセル個数が明示されていない場合は 1 とする
-    Ok(1)
-}
+/// `キャンセル個数 ： N`
+static CANCEL_QUANTITY_LABEL: Lazy<Label> =
+    Lazy::new(|| Label::new("キャンセル個数").colon().value(r"\d+").build());
 
 #[cfg(test)]
 mod tests {
@@ -1,4 +1,4 @@
-use super::{extract_amounts, extract_delivery_address, parse_item_line};
+use super::{extract_amounts, extract_delivery_address, extract_order_number, parse_item_line};
 use crate::parsers::{EmailParser, OrderInfo, OrderItem};
 use regex::Regex;
 
@@ -32,26 +32,12 @@ impl EmailParser for HobbySearchChangeParser {
             subtotal,
             shipping_fee,
             total_amount,
+            tax_amount: None,
+            tax_included: true,
         })
     }
 }
 
-/// 注文番号を抽出（[注文番号] XX-XXXX-XXXX 形式）
-fn extract_order_number(lines: &[&str]) -> Result<String, String> {
-    let order_number_pattern =
-        Regex::new(r"\[注文番号\]\s*(\d+-\d+-\d+)").map_err(|e| format!("Regex error: {e}"))?;
-
-    for line in lines {
-        if let Some(captures) = order_number_pattern.captures(line) {
-            if let Some(order_number) = captures.get(1) {
-                return Ok(order_number.as_str().to_string());
-            }
-        }
-    }
-
-    Err("Order number not found".to_string())
-}
-
 /// 商品情報を抽出（[ご購入内容]セクション）
 fn extract_purchase_items(lines: &[&str]) -> Result<Vec<OrderItem>, String> {
     let mut items = Vec::new();
@@ -108,6 +94,8 @@ fn extract_purchase_items(lines: &[&str]) -> Result<Vec<OrderItem>, String> {
                         quantity,
                         subtotal,
                         image_url: None,
+                        item_url: None,
+                        release_date: None,
                     });
 
                     // 価格情報の行をスキップ
@@ -1,4 +1,6 @@
-use super::{extract_delivery_address, extract_yoyaku_total, parse_item_line};
+use super::{
+    extract_delivery_address, extract_order_number, extract_yoyaku_total, parse_item_line,
+};
 use crate::parsers::{EmailParser, OrderInfo, OrderItem};
 use regex::Regex;
 
@@ -30,26 +32,12 @@ impl EmailParser for HobbySearchConfirmYoyakuParser {
             subtotal,
             shipping_fee: None, // 予約時は送料別計算
             total_amount: None,
+            tax_amount: None,
+            tax_included: true,
         })
     }
 }
 
-/// 注文番号を抽出（[注文番号] XX-XXXX-XXXX 形式）
-fn extract_order_number(lines: &[&str]) -> Result<String, String> {
-    let order_number_pattern =
-        Regex::new(r"\[注文番号\]\s*(\d+-\d+-\d+)").map_err(|e| format!("Regex error: {e}"))?;
-
-    for line in lines {
-        if let Some(captures) = order_number_pattern.captures(line) {
-            if let Some(order_number) = captures.get(1) {
-                return Ok(order_number.as_str().to_string());
-            }
-        }
-    }
-
-    Err("Order number not found".to_string())
-}
-
 /// 予約商品情報を抽出（[ご予約内容]セクション）
 fn extract_yoyaku_items(lines: &[&str]) -> Result<Vec<OrderItem>, String> {
     let mut items = Vec::new();
@@ -108,6 +96,8 @@ fn extract_yoyaku_items(lines: &[&str]) -> Result<Vec<OrderItem>, String> {
                         quantity,
                         subtotal,
                         image_url: None,
+                        item_url: None,
+                        release_date: None,
                     });
 
                     // 価格情報の行をスキップ
@@ -0,0 +1,185 @@
+//! ホビーサーチ 入荷予定変更メール用パーサー
+//!
+//! 件名：`【ホビーサーチ】入荷予定変更のお知らせ`
+//!
+//! メーカーの発売延期などで予約商品の入荷予定が変わったときに届く。
+//! `[注文番号]` ごとに `[商品名]` と `[変更前入荷予定]` / `[変更後入荷予定]` が続くため、
+//! 注文ごとに商品の入荷予定の変更を抽出する。商品名がなく変更後入荷予定のみの場合は注文内の全商品を対象とする。
+//! 入荷予定は「5月下旬」「2025年夏」等のあいまいな表記も `parse_fuzzy_release_date` で正規化する
+//! （年の記載がなければメール受信日を基準に補う）。
+
+use super::ORDER_NUMBER_LABEL;
+use crate::parsers::delay_info::{DelayInfo, DelayedItem};
+use crate::parsers::release_date::parse_fuzzy_release_date;
+use crate::parsers::Label;
+use chrono::NaiveDate;
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+/// 入荷予定変更メール用パーサー
+pub struct HobbySearchDelayParser;
+
+/// `[商品名] コトブキヤ FG195 フレームアームズ・ガール ...`
+static ITEM_NAME_LABEL: Lazy<Label> = Lazy::new(|| Label::new("商品名").bracket().colon().build());
+
+/// `[変更前入荷予定] 2025年3月` / `旧入荷予定：2025年3月`
+static OLD_ARRIVAL_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^\[?(?:変更前|旧)の?(?:入荷|発売)予定月?\]?\s*[：:]?\s*(.+)$")
+        .expect("Invalid OLD_ARRIVAL_RE")
+});
+
+/// `[変更後入荷予定] 2025年5月下旬` / `新入荷予定：2025年夏`
+static NEW_ARRIVAL_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^\[?(?:変更後|新)の?(?:入荷|発売)予定月?\]?\s*[：:]?\s*(.+)$")
+        .expect("Invalid NEW_ARRIVAL_RE")
+});
+
+impl HobbySearchDelayParser {
+    /// メール本文から注文ごとの入荷予定変更を出現順で抽出する
+    ///
+    /// `reference` は年の記載がない入荷予定（`5月下旬`）の年を補うための基準日（メール受信日）。
+    /// 変更後入荷予定のない注文は結果に含めない。1 件も抽出できない場合はエラーを返す。
+    pub fn parse_delays(
+        &self,
+        email_body: &str,
+        reference: Option<NaiveDate>,
+    ) -> Result<Vec<DelayInfo>, String> {
+        let mut delays: Vec<DelayInfo> = Vec::new();
+        let mut current: Option<DelayInfo> = None;
+        let mut current_name: Option<String> = None;
+        let mut current_old = None;
+
+        for line in email_body.lines() {
+            let trimmed = line.trim();
+
+            if let Some(order_number) = ORDER_NUMBER_LABEL.match_line(trimmed) {
+                delays.extend(current.take().filter(|d| !d.items.is_empty()));
+                current = Some(DelayInfo {
+                    order_number,
+                    items: Vec::new(),
+                });
+                current_name = None;
+                current_old = None;
+                continue;
+            }
+            let Some(delay) = current.as_mut() else {
+                continue;
+            };
+
+            if let Some(name) = ITEM_NAME_LABEL.match_line(trimmed) {
+                current_name = Some(name);
+                current_old = None;
+                continue;
+            }
+
+            if let Some(caps) = OLD_ARRIVAL_RE.captures(trimmed) {
+                current_old = parse_fuzzy_release_date(&caps[1], reference);
+                continue;
+            }
+
+            if let Some(caps) = NEW_ARRIVAL_RE.captures(trimmed) {
+                if let Some(new_release_date) = parse_fuzzy_release_date(&caps[1], reference) {
+                    delay.items.push(DelayedItem {
+                        product_name: current_name.take().unwrap_or_default(),
+                        old_release_date: current_old.take(),
+                        new_release_date,
+                    });
+                }
+            }
+        }
+        delays.extend(current.filter(|d| !d.items.is_empty()));
+
+        if delays.is_empty() {
+            return Err("Order number or new arrival date not found".to_string());
+        }
+        Ok(delays)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parsers::release_date::{FuzzyReleaseDate, ReleaseDatePrecision};
+
+    fn reference() -> Option<NaiveDate> {
+        NaiveDate::from_ymd_opt(2025, 2, 10)
+    }
+
+    #[test]
+    fn test_parse_delays_single_order() {
+        let body = r#"このたびはホビーサーチをご利用いただき、誠にありがとうございます。
+ご予約いただいております下記商品につきまして、メーカーより発売延期の連絡がございました。
+入荷予定を以下の通り変更させていただきます。
+
+[注文番号] 25-1021-1156
+
+[商品名] コトブキヤ FG195 フレームアームズ・ガール ドゥルガーII 〈ノワールVer.〉
+[変更前入荷予定] 2025年3月
+[変更後入荷予定] 5月下旬
+
+[商品名] バンダイスピリッツ 5065432 HG 1/144 ガンダムエアリアル
+[変更前入荷予定] 2025年3月
+[変更後入荷予定] 2025年夏
+"#;
+        let delays = HobbySearchDelayParser
+            .parse_delays(body, reference())
+            .unwrap();
+        assert_eq!(delays.len(), 1);
+        assert_eq!(delays[0].order_number, "25-1021-1156");
+        assert_eq!(delays[0].items.len(), 2);
+
+        let first = &delays[0].items[0];
+        assert_eq!(
+            first.product_name,
+            "コトブキヤ FG195 フレームアームズ・ガール ドゥルガーII 〈ノワールVer.〉"
+        );
+        assert_eq!(
+            first.old_release_date,
+            FuzzyReleaseDate::month(2025, 3, ReleaseDatePrecision::Month)
+        );
+        // 年の記載がない場合は受信日以降で最も近い年とみなす
+        assert_eq!(
+            first.new_release_date,
+            FuzzyReleaseDate::month(2025, 5, ReleaseDatePrecision::LateMonth).unwrap()
+        );
+        assert_eq!(
+            delays[0].items[1].new_release_date,
+            FuzzyReleaseDate::period(2025, ReleaseDatePrecision::Summer)
+        );
+    }
+
+    #[test]
+    fn test_parse_delays_multiple_orders_and_whole_order() {
+        let body = r#"[注文番号] 25-1021-1156
+[商品名] 商品A
+[変更後入荷予定] 2025年6月
+
+[注文番号] 25-1101-0002
+注文内の全商品の入荷予定が変更となりました。
+[変更後入荷予定] 2025年7月
+
+[注文番号] 25-1101-0003
+[商品名] 予定未定の商品
+"#;
+        let delays = HobbySearchDelayParser.parse_delays(body, None).unwrap();
+        assert_eq!(delays.len(), 2);
+        assert_eq!(delays[0].order_number, "25-1021-1156");
+        assert_eq!(delays[0].items[0].product_name, "商品A");
+        assert_eq!(delays[1].order_number, "25-1101-0002");
+        assert_eq!(delays[1].items[0].product_name, "");
+        assert_eq!(
+            delays[1].items[0].new_release_date.to_release_date(),
+            "2025-07".to_string()
+        );
+    }
+
+    #[test]
+    fn test_parse_delays_missing_fields_returns_error() {
+        assert!(HobbySearchDelayParser
+            .parse_delays("[変更後入荷予定] 2025年6月\n", None)
+            .is_err());
+        assert!(HobbySearchDelayParser
+            .parse_delays("[注文番号] 25-1021-1156\n[商品名] 商品A\n", None)
+            .is_err());
+    }
+}
@@ -3,9 +3,11 @@ pub mod change;
 pub mod change_yoyaku;
 pub mod confirm;
 pub mod confirm_yoyaku;
+pub mod delay;
+pub mod preparing;
 pub mod send;
 
-use crate::parsers::{DeliveryAddress, DeliveryInfo};
+use crate::parsers::{DeliveryAddress, DeliveryInfo, Label};
 use once_cell::sync::Lazy;
 use regex::Regex;
 
@@ -17,6 +19,24 @@ static AMOUNT_PATTERN: Lazy<Regex> =
 static YOYAKU_TOTAL_PATTERN: Lazy<Regex> =
     Lazy::new(|| Regex::new(r"予約商品合計\s*([\d,]+)円").expect("Invalid regex pattern"));
 
+/// ホビーサーチの注文番号の形式（XX-XXXX-XXXX）
+pub(super) const ORDER_NUMBER_VALUE: &str = r"\d+-\d+-\d+";
+
+/// `[注文番号] XX-XXXX-XXXX` 形式の注文番号ラベル
+pub(super) static ORDER_NUMBER_LABEL: Lazy<Label> = Lazy::new(|| {
+    Label::new("注文番号")
+        .bracket()
+        .value(ORDER_NUMBER_VALUE)
+        .build()
+});
+
+/// 注文番号を抽出（[注文番号] XX-XXXX-XXXX 形式）
+pub fn extract_order_number(lines: &[&str]) -> Result<String, String> {
+    ORDER_NUMBER_LABEL
+        .find_in_lines(lines)
+        .ok_or_else(|| "Order number not found".to_string())
+}
+
 /// 配送先情報を抽出
 ///
 /// [商品お届け先] セクションから名前、郵便番号、住所を抽出する。
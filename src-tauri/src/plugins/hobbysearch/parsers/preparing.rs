@@ -0,0 +1,93 @@
+//! ホビーサーチ 出荷準備中メール用パーサー
+//!
+//! 件名：`【ホビーサーチ】出荷準備中のお知らせ`
+//!
+//! 発送前の「出荷準備中」段階を記録するため、メール内の注文番号のみを抽出する。
+//! 複数注文をまとめて出荷する場合は `[注文番号]` が複数記載されるため、すべて返す。
+
+use super::ORDER_NUMBER_VALUE;
+use crate::parsers::Label;
+use once_cell::sync::Lazy;
+
+/// 出荷準備中メール用パーサー
+pub struct HobbySearchPreparingParser;
+
+/// `[代表注文番号] 25-0807-1624` / `[注文番号] 25-0807-1624` / `注文番号 ： 25-0807-1624`
+static ORDER_NUMBER_LABEL: Lazy<Label> = Lazy::new(|| {
+    Label::new("注文番号")
+        .alias("代表注文番号")
+        .bracket()
+        .colon()
+        .value(ORDER_NUMBER_VALUE)
+        .build()
+});
+
+impl HobbySearchPreparingParser {
+    /// メール本文から注文番号を出現順（重複なし）で抽出する
+    pub fn parse_order_numbers(&self, email_body: &str) -> Result<Vec<String>, String> {
+        let order_numbers = ORDER_NUMBER_LABEL.find_all(email_body);
+        if order_numbers.is_empty() {
+            return Err("Order number not found".to_string());
+        }
+        Ok(order_numbers)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_order_numbers_single() {
+        let body = r#"このたびはホビーサーチをご利用いただき、誠にありがとうございます。
+ご注文の商品の出荷準備を開始いたしました。
+
+[代表注文番号] 25-0807-1624
+
+[商品お届け先]
+テスト 太郎 様
+"#;
+        assert_eq!(
+            HobbySearchPreparingParser
+                .parse_order_numbers(body)
+                .unwrap(),
+            vec!["25-0807-1624".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_parse_order_numbers_multiple_orders_deduplicated() {
+        let body = r#"[代表注文番号] 25-0807-1624
+
+[注文番号] 25-0807-1624
+メーカー1 0001 商品1 (プラモデル)
+
+[注文番号] 25-0810-0001
+メーカー2 0002 商品2 (プラモデル)
+"#;
+        assert_eq!(
+            HobbySearchPreparingParser
+                .parse_order_numbers(body)
+                .unwrap(),
+            vec!["25-0807-1624".to_string(), "25-0810-0001".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_parse_order_numbers_colon_format() {
+        let body = "注文番号 ： 25-0807-1624\n";
+        assert_eq!(
+            HobbySearchPreparingParser
+                .parse_order_numbers(body)
+                .unwrap(),
+            vec!["25-0807-1624".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_parse_order_numbers_not_found() {
+        assert!(HobbySearchPreparingParser
+            .parse_order_numbers("出荷準備を開始いたしました。")
+            .is_err());
+    }
+}
@@ -1,5 +1,9 @@
-use super::{extract_amounts, extract_delivery_address, extract_delivery_info, parse_item_line};
-use crate::parsers::{EmailParser, OrderInfo, OrderItem};
+use super::{
+    extract_amounts, extract_delivery_address, extract_delivery_info, parse_item_line,
+    ORDER_NUMBER_LABEL, ORDER_NUMBER_VALUE,
+};
+use crate::parsers::{EmailParser, Label, OrderInfo, OrderItem};
+use once_cell::sync::Lazy;
 use regex::Regex;
 
 /// 発送通知メール用パーサー
@@ -33,6 +37,8 @@ impl EmailParser for HobbySearchSendParser {
             subtotal,
             shipping_fee,
             total_amount,
+            tax_amount: None,
+            tax_included: true,
         })
     }
 
@@ -66,6 +72,8 @@ impl EmailParser for HobbySearchSendParser {
                 subtotal: None,
                 shipping_fee: None,
                 total_amount: None,
+                tax_amount: None,
+                tax_included: true,
             });
         }
 
@@ -77,30 +85,25 @@ impl EmailParser for HobbySearchSendParser {
     }
 }
 
+/// `[代表注文番号] XX-XXXX-XXXX` 形式の代表注文番号ラベル
+static REPRESENTATIVE_ORDER_NUMBER_LABEL: Lazy<Label> = Lazy::new(|| {
+    Label::new("代表注文番号")
+        .bracket()
+        .value(ORDER_NUMBER_VALUE)
+        .build()
+});
+
 /// 代表注文番号を抽出（[代表注文番号] 形式）
 fn extract_representative_order_number(lines: &[&str]) -> Result<String, String> {
-    let order_number_pattern =
-        Regex::new(r"\[代表注文番号\]\s*(\d+-\d+-\d+)").map_err(|e| format!("Regex error: {e}"))?;
-
-    for line in lines {
-        if let Some(captures) = order_number_pattern.captures(line) {
-            if let Some(order_number) = captures.get(1) {
-                return Ok(order_number.as_str().to_string());
-            }
-        }
-    }
-
-    Err("Representative order number not found".to_string())
+    REPRESENTATIVE_ORDER_NUMBER_LABEL
+        .find_in_lines(lines)
+        .ok_or_else(|| "Representative order number not found".to_string())
 }
 
 /// [ご購入内容]セクション内の[注文番号]ごとに商品を分割して返す。
 /// 戻り値: Vec<(注文番号, 商品リスト)>
 /// [注文番号]行が1つも見つからない場合は空 Vec を返す。
 fn extract_order_sections(lines: &[&str]) -> Vec<(String, Vec<OrderItem>)> {
-    let order_number_pattern = match Regex::new(r"\[注文番号\]\s*(\d+-\d+-\d+)") {
-        Ok(p) => p,
-        Err(_) => return Vec::new(),
-    };
     let price_pattern = match Regex::new(r"単価：([\d,]+)円\s*×\s*個数：(\d+)\s*=\s*([\d,]+)円")
     {
         Ok(p) => p,
@@ -137,12 +140,12 @@ fn extract_order_sections(lines: &[&str]) -> Vec<(String, Vec<OrderItem>)> {
         }
 
         // [注文番号]行の検出
-        if let Some(caps) = order_number_pattern.captures(line) {
+        if let Some(order_number) = ORDER_NUMBER_LABEL.match_line(line) {
             // 前のセクションを保存
             if let Some(num) = current_order_number.take() {
                 sections.push((num, std::mem::take(&mut current_items)));
             }
-            current_order_number = Some(caps.get(1).unwrap().as_str().to_string());
+            current_order_number = Some(order_number);
             i += 1;
             continue;
         }
@@ -177,6 +180,8 @@ fn extract_order_sections(lines: &[&str]) -> Vec<(String, Vec<OrderItem>)> {
                     quantity,
                     subtotal,
                     image_url: None,
+                    item_url: None,
+                    release_date: None,
                 });
 
                 i += 2;
@@ -258,6 +263,8 @@ fn extract_purchase_items(lines: &[&str]) -> Result<Vec<OrderItem>, String> {
                             quantity,
                             subtotal,
                             image_url: None,
+                            item_url: None,
+                            release_date: None,
                         });
 
                         // 価格情報の行をスキップ
@@ -0,0 +1,244 @@
+use crate::parsers::release_date::parse_fuzzy_release_date;
+use crate::parsers::{normalize_item_url, EmailParser, OrderInfo, OrderItem};
+use chrono::NaiveDate;
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+/// `ご注文番号：HS2503100001` パターン（全角・半角コロン両対応）
+static ORDER_NUMBER_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"ご注文番号\s*[：:]\s*([A-Za-z0-9\-]+)").expect("Invalid ORDER_NUMBER_RE")
+});
+
+/// `ご注文日：2025年03月10日 21:15` / `ご注文日時：2025/03/10 21:15` パターン
+static ORDER_DATE_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"ご注文日時?\s*[：:]\s*(\d{4})[年/](\d{1,2})[月/](\d{1,2})日?(?:\s+(\d{1,2}:\d{2}))?",
+    )
+    .expect("Invalid ORDER_DATE_RE")
+});
+
+/// `価格：5,280円（税込） × 1個` パターン
+static PRICE_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^価格\s*[：:]\s*([\d,]+)\s*円[^×xX]*[×xX]\s*(\d+)").expect("Invalid PRICE_RE")
+});
+
+/// `商品合計：10,560円` / `送料：660円` / `合計金額：11,220円` パターン
+static AMOUNT_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^(商品合計|送料|合計金額)\s*[：:]\s*([\d,]+)\s*円").expect("Invalid AMOUNT_RE")
+});
+
+/// ホビーストック 注文確認メール用パーサー
+///
+/// 件名：`【ホビーストック】ご注文ありがとうございます`
+/// 送信元：`info@hobbystock.jp`
+///
+/// 商品は `商品名：` 行から始まるブロックで並び、`商品コード：`（JAN）・`商品URL：`・`発売予定日：`・
+/// `価格：N円（税込） × 数量個` が続く。予約商品の `発売予定日：2025年8月下旬` は
+/// 精度付きの発売予定として取り込み、`発売中` 等の日付でない表記は None とする。
+pub struct HobbystockConfirmParser;
+
+/// 行頭のラベル（`商品名：`）を取り除いた値を返す
+fn label_value<'a>(line: &'a str, label: &str) -> Option<&'a str> {
+    let rest = line.strip_prefix(label)?.trim_start();
+    let rest = rest.strip_prefix('：').or_else(|| rest.strip_prefix(':'))?;
+    Some(rest.trim())
+}
+
+fn extract_order_date(lines: &[&str]) -> Option<(String, NaiveDate)> {
+    lines.iter().find_map(|line| {
+        let caps = ORDER_DATE_RE.captures(line)?;
+        let date = NaiveDate::from_ymd_opt(
+            caps[1].parse().ok()?,
+            caps[2].parse().ok()?,
+            caps[3].parse().ok()?,
+        )?;
+        let formatted = match caps.get(4) {
+            Some(time) => format!("{} {}", date.format("%Y-%m-%d"), time.as_str()),
+            None => date.format("%Y-%m-%d").to_string(),
+        };
+        Some((formatted, date))
+    })
+}
+
+fn extract_items(lines: &[&str], order_date: Option<NaiveDate>) -> Vec<OrderItem> {
+    let mut items: Vec<OrderItem> = Vec::new();
+    let mut current: Option<OrderItem> = None;
+
+    for line in lines {
+        if let Some(name) = label_value(line, "商品名") {
+            items.extend(current.take());
+            current = Some(OrderItem {
+                name: name.to_string(),
+                manufacturer: None,
+                model_number: None,
+                unit_price: 0,
+                quantity: 1,
+                subtotal: 0,
+                image_url: None,
+                item_url: None,
+                release_date: None,
+            });
+            continue;
+        }
+        let Some(item) = current.as_mut() else {
+            continue;
+        };
+
+        if let Some(code) = label_value(line, "商品コード") {
+            item.model_number = Some(code.to_string()).filter(|c| !c.is_empty());
+        } else if let Some(url) = label_value(line, "商品URL") {
+            item.item_url = normalize_item_url(url);
+        } else if let Some(release) = label_value(line, "発売予定日") {
+            item.release_date = parse_fuzzy_release_date(release, order_date);
+        } else if let Some(caps) = PRICE_RE.captures(line) {
+            item.unit_price = caps[1].replace(',', "").parse().unwrap_or(0);
+            item.quantity = caps[2].parse().unwrap_or(1);
+            item.subtotal = item.unit_price * item.quantity;
+        } else if AMOUNT_RE.is_match(line) {
+            // 合計欄に入ったら商品ブロックは終わり
+            items.extend(current.take());
+        }
+    }
+    items.extend(current);
+
+    items
+}
+
+impl EmailParser for HobbystockConfirmParser {
+    fn parse(&self, email_body: &str) -> Result<OrderInfo, String> {
+        let lines: Vec<&str> = email_body.lines().map(str::trim).collect();
+
+        let order_number = lines
+            .iter()
+            .find_map(|line| ORDER_NUMBER_RE.captures(line).map(|c| c[1].to_string()))
+            .ok_or_else(|| "Order number not found".to_string())?;
+
+        let (order_date, order_day) = match extract_order_date(&lines) {
+            Some((formatted, day)) => (Some(formatted), Some(day)),
+            None => (None, None),
+        };
+
+        let items = extract_items(&lines, order_day);
+        if items.is_empty() {
+            return Err("No items found".to_string());
+        }
+
+        let mut subtotal = None;
+        let mut shipping_fee = None;
+        let mut total_amount = None;
+        for caps in lines.iter().filter_map(|line| AMOUNT_RE.captures(line)) {
+            let amount: Option<i64> = caps[2].replace(',', "").parse().ok();
+            match &caps[1] {
+                "商品合計" => subtotal = amount,
+                "送料" => shipping_fee = amount,
+                _ => total_amount = amount,
+            }
+        }
+
+        Ok(OrderInfo {
+            order_number,
+            order_date,
+            delivery_address: None,
+            delivery_info: None,
+            items,
+            subtotal,
+            shipping_fee,
+            total_amount,
+            tax_amount: None,
+            tax_included: true,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parsers::release_date::ReleaseDatePrecision;
+
+    fn sample_confirm() -> &'static str {
+        r#"山田 太郎 様
+この度はホビーストックをご利用いただき、誠にありがとうございます。
+以下の内容でご注文を承りました。
+
+ご注文番号：HS2503100001
+ご注文日：2025年03月10日 21:15
+
+【ご注文商品】
+――――――――――――――――――――
+商品名：ねんどろいど 初音ミク 10th Anniversary Ver.
+商品コード：4580590123456
+商品URL：https://www.hobbystock.jp/item/view/hso-ccg-12345?utm_source=mail
+発売予定日：2025年8月下旬
+価格：5,280円（税込） × 1個
+――――――――――――――――――――
+商品名：figma 鏡音リン
+商品コード：4580590654321
+発売予定日：発売中
+価格：7,700円（税込） × 2個
+――――――――――――――――――――
+商品合計：20,680円
+送料：660円
+合計金額：21,340円
+"#
+    }
+
+    #[test]
+    fn test_parse_confirm_order_number_and_date() {
+        let order = HobbystockConfirmParser.parse(sample_confirm()).unwrap();
+        assert_eq!(order.order_number, "HS2503100001");
+        assert_eq!(order.order_date.as_deref(), Some("2025-03-10 21:15"));
+    }
+
+    #[test]
+    fn test_parse_confirm_items() {
+        let order = HobbystockConfirmParser.parse(sample_confirm()).unwrap();
+        assert_eq!(order.items.len(), 2);
+
+        let first = &order.items[0];
+        assert_eq!(first.name, "ねんどろいど 初音ミク 10th Anniversary Ver.");
+        assert_eq!(first.model_number.as_deref(), Some("4580590123456"));
+        assert_eq!(
+            first.item_url.as_deref(),
+            Some("https://www.hobbystock.jp/item/view/hso-ccg-12345")
+        );
+        assert_eq!(first.unit_price, 5280);
+        assert_eq!(first.quantity, 1);
+
+        let second = &order.items[1];
+        assert_eq!(second.unit_price, 7700);
+        assert_eq!(second.quantity, 2);
+        assert_eq!(second.subtotal, 15400);
+        assert!(second.item_url.is_none());
+    }
+
+    #[test]
+    fn test_parse_confirm_release_date() {
+        let order = HobbystockConfirmParser.parse(sample_confirm()).unwrap();
+        let release = order.items[0].release_date.as_ref().unwrap();
+        assert_eq!(release.year, 2025);
+        assert_eq!(release.month, Some(8));
+        assert_eq!(release.precision, ReleaseDatePrecision::LateMonth);
+        // 発売中の商品は発売予定なし
+        assert!(order.items[1].release_date.is_none());
+    }
+
+    #[test]
+    fn test_parse_confirm_amounts() {
+        let order = HobbystockConfirmParser.parse(sample_confirm()).unwrap();
+        assert_eq!(order.subtotal, Some(20680));
+        assert_eq!(order.shipping_fee, Some(660));
+        assert_eq!(order.total_amount, Some(21340));
+    }
+
+    #[test]
+    fn test_parse_confirm_missing_order_number() {
+        assert!(HobbystockConfirmParser.parse("商品名：テスト").is_err());
+    }
+
+    #[test]
+    fn test_parse_confirm_no_items() {
+        assert!(HobbystockConfirmParser
+            .parse("ご注文番号：HS2503100001\n合計金額：0円")
+            .is_err());
+    }
+}
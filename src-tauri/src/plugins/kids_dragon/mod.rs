@@ -11,8 +11,8 @@ use crate::parsers::EmailParser;
 use crate::repository::SqliteOrderRepository;
 
 use super::{
-    derive_shop_domain, DefaultShopSetting, DispatchError, DispatchOutcome, PluginRegistration,
-    VendorPlugin,
+    apply_tax_info, derive_shop_domain, DefaultShopSetting, DispatchError, DispatchOutcome,
+    PluginRegistration, VendorPlugin,
 };
 
 pub struct KidsDragonPlugin;
@@ -72,12 +72,13 @@ impl VendorPlugin for KidsDragonPlugin {
         let shop_domain = derive_shop_domain(from_address);
 
         // parser は同期処理のみ。await をまたがないようブロックで即 drop する。
-        let order_info = {
+        let mut order_info = {
             let parser = self.get_parser(parser_type).ok_or_else(|| {
                 DispatchError::ParseFailed(format!("No parser for type: {}", parser_type))
             })?;
             parser.parse(body).map_err(DispatchError::ParseFailed)?
         };
+        apply_tax_info(&mut order_info, body);
 
         log::debug!(
             "[{}] email_id={} order_number={}",
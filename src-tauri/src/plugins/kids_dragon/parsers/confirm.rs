@@ -34,6 +34,8 @@ impl EmailParser for KidsDragonConfirmParser {
             subtotal,
             shipping_fee,
             total_amount,
+            tax_amount: None,
+            tax_included: true,
         })
     }
 }
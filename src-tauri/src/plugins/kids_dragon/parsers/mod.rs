@@ -49,6 +49,8 @@ pub fn parse_item_line(line: &str) -> Option<OrderItem> {
         quantity,
         subtotal,
         image_url: None,
+        item_url: None,
+        release_date: None,
     })
 }
 
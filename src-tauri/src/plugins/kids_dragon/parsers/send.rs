@@ -47,6 +47,8 @@ impl EmailParser for KidsDragonSendParser {
             subtotal,
             shipping_fee,
             total_amount,
+            tax_amount: None,
+            tax_included: true,
         })
     }
 }
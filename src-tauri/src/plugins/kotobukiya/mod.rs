@@ -1,8 +1,7 @@
 //! コトブキヤオンラインショップ プラグイン
 //!
-//! `onlineshop@kotobukiya-ec.com` から配信される注文確認メールを取り込む。
-//!
-//! TODO: 実際のメール本文フォーマットを確認後、パーサーを実装する。
+//! `onlineshop@kotobukiya-ec.com` から配信される注文確認・出荷案内メールを取り込む。
+//! 出荷案内メールは `【オーダーID】` で既存注文に紐付け、伝票番号と配送業者を登録する。
 
 pub mod parsers;
 
@@ -12,8 +11,8 @@ use crate::parsers::EmailParser;
 use crate::repository::SqliteOrderRepository;
 
 use super::{
-    apply_internal_date, derive_shop_domain, DefaultShopSetting, DispatchError, DispatchOutcome,
-    PluginRegistration, VendorPlugin,
+    apply_internal_date, apply_tax_info, derive_shop_domain, DefaultShopSetting, DispatchError,
+    DispatchOutcome, PluginRegistration, VendorPlugin,
 };
 
 pub struct KotobukiyaPlugin;
@@ -21,7 +20,7 @@ pub struct KotobukiyaPlugin;
 #[async_trait]
 impl VendorPlugin for KotobukiyaPlugin {
     fn parser_types(&self) -> &[&str] {
-        &["kotobukiya_confirm"]
+        &["kotobukiya_confirm", "kotobukiya_send"]
     }
 
     fn priority(&self) -> i32 {
@@ -31,6 +30,7 @@ impl VendorPlugin for KotobukiyaPlugin {
     fn get_parser(&self, parser_type: &str) -> Option<Box<dyn EmailParser>> {
         match parser_type {
             "kotobukiya_confirm" => Some(Box::new(parsers::confirm::KotobukiyaConfirmParser)),
+            "kotobukiya_send" => Some(Box::new(parsers::send::KotobukiyaSendParser)),
             _ => None,
         }
     }
@@ -40,14 +40,22 @@ impl VendorPlugin for KotobukiyaPlugin {
     }
 
     fn default_shop_settings(&self) -> Vec<DefaultShopSetting> {
-        vec![DefaultShopSetting {
-            shop_name: "コトブキヤオンラインショップ".to_string(),
-            sender_address: "onlineshop@kotobukiya-ec.com".to_string(),
-            parser_type: "kotobukiya_confirm".to_string(),
-            subject_filters: Some(vec![
-                "ご注文確認のお知らせ［コトブキヤオンラインショップ］".to_string()
-            ]),
-        }]
+        vec![
+            DefaultShopSetting {
+                shop_name: "コトブキヤオンラインショップ".to_string(),
+                sender_address: "onlineshop@kotobukiya-ec.com".to_string(),
+                parser_type: "kotobukiya_confirm".to_string(),
+                subject_filters: Some(vec![
+                    "ご注文確認のお知らせ［コトブキヤオンラインショップ］".to_string()
+                ]),
+            },
+            DefaultShopSetting {
+                shop_name: "コトブキヤオンラインショップ".to_string(),
+                sender_address: "onlineshop@kotobukiya-ec.com".to_string(),
+                parser_type: "kotobukiya_send".to_string(),
+                subject_filters: Some(vec!["商品出荷のお知らせ".to_string()]),
+            },
+        ]
     }
 
     fn prefer_plain_text(&self) -> bool {
@@ -74,7 +82,11 @@ impl VendorPlugin for KotobukiyaPlugin {
             parser.parse(body).map_err(DispatchError::ParseFailed)?
         };
 
-        apply_internal_date(&mut order_info, internal_date);
+        // 出荷案内の受信日時を注文日にしないよう、補完は注文確認メールだけに行う
+        if parser_type == "kotobukiya_confirm" {
+            apply_internal_date(&mut order_info, internal_date);
+            apply_tax_info(&mut order_info, body);
+        }
 
         log::debug!(
             "[{}] email_id={} order_number={}",
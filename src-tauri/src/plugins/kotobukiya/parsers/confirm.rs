@@ -9,33 +9,22 @@
 use once_cell::sync::Lazy;
 use regex::Regex;
 
+use super::{extract_order_number, strip_name_suffix, ITEM_LINE_RE, QUANTITY_ONLY_RE};
 use crate::parsers::{EmailParser, OrderInfo, OrderItem};
 
 pub struct KotobukiyaConfirmParser;
 
 // ─── 正規表現 ────────────────────────────────────────────────────────────────
 
-/// `【オーダーID】0434429495`
-static ORDER_NUMBER_RE: Lazy<Regex> =
-    Lazy::new(|| Regex::new(r"【オーダーID】(\d+)").expect("ORDER_NUMBER_RE"));
-
 /// `【ご注文日】2026年04月15日`
 static ORDER_DATE_RE: Lazy<Regex> =
     Lazy::new(|| Regex::new(r"【ご注文日】(\d{4})年(\d{2})月(\d{2})日").expect("ORDER_DATE_RE"));
 
-/// `　1.商品名 （商品名）` — 全角スペース + 番号 + ドット + 商品名
-static ITEM_LINE_RE: Lazy<Regex> =
-    Lazy::new(|| Regex::new(r"^　\d+\.(.+)$").expect("ITEM_LINE_RE"));
-
 /// `　　価格：￥6,930 x 数量：1 = 合計：￥6,930`
 static PRICE_LINE_RE: Lazy<Regex> = Lazy::new(|| {
     Regex::new(r"価格：￥([\d,]+)\s*x\s*数量：(\d+)\s*=\s*合計：￥([\d,]+)").expect("PRICE_LINE_RE")
 });
 
-/// `　　数量：1` — 特典など価格なし商品
-static QUANTITY_ONLY_RE: Lazy<Regex> =
-    Lazy::new(|| Regex::new(r"^　　数量：(\d+)").expect("QUANTITY_ONLY_RE"));
-
 /// `　商品金額合計：￥6,930`
 static SUBTOTAL_RE: Lazy<Regex> =
     Lazy::new(|| Regex::new(r"商品金額合計：￥([\d,]+)").expect("SUBTOTAL_RE"));
@@ -54,25 +43,8 @@ fn parse_amount(s: &str) -> i64 {
     s.replace(',', "").parse().unwrap_or(0)
 }
 
-/// 商品名末尾の全角括弧 `（...）` を除去する
-///
-/// 例: `PUNI☆MOFU ロン （PUNI☆MOFU ロン）` → `PUNI☆MOFU ロン`
-fn strip_name_suffix(name: &str) -> String {
-    if let Some(pos) = name.rfind('（') {
-        let candidate = name[..pos].trim_end();
-        if !candidate.is_empty() {
-            return candidate.to_string();
-        }
-    }
-    name.trim().to_string()
-}
-
 // ─── 各フィールドの抽出 ───────────────────────────────────────────────────────
 
-fn extract_order_number(body: &str) -> Option<String> {
-    ORDER_NUMBER_RE.captures(body).map(|c| c[1].to_string())
-}
-
 fn extract_order_date(body: &str) -> Option<String> {
     ORDER_DATE_RE
         .captures(body)
@@ -120,6 +92,8 @@ fn extract_items(body: &str) -> Vec<OrderItem> {
                     quantity: current_quantity,
                     subtotal: current_subtotal,
                     image_url: None,
+                    item_url: None,
+                    release_date: None,
                 });
             }
             break;
@@ -139,6 +113,8 @@ fn extract_items(body: &str) -> Vec<OrderItem> {
                     quantity: current_quantity,
                     subtotal: current_subtotal,
                     image_url: None,
+                    item_url: None,
+                    release_date: None,
                 });
             }
             current_name = Some(strip_name_suffix(caps[1].trim()));
@@ -172,6 +148,8 @@ fn extract_items(body: &str) -> Vec<OrderItem> {
             quantity: current_quantity,
             subtotal: current_subtotal,
             image_url: None,
+            item_url: None,
+            release_date: None,
         });
     }
 
@@ -216,6 +194,8 @@ impl EmailParser for KotobukiyaConfirmParser {
             subtotal,
             shipping_fee,
             total_amount,
+            tax_amount: None,
+            tax_included: true,
         })
     }
 }
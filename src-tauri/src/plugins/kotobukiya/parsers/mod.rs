@@ -1 +1,35 @@
 pub mod confirm;
+pub mod send;
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+/// `【オーダーID】0434429495`
+static ORDER_NUMBER_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"【オーダーID】(\d+)").expect("ORDER_NUMBER_RE"));
+
+/// `　1.商品名 （商品名）` — 全角スペース + 番号 + ドット + 商品名
+pub(super) static ITEM_LINE_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^　\d+\.(.+)$").expect("ITEM_LINE_RE"));
+
+/// `　　数量：1` — 特典など価格なし商品
+pub(super) static QUANTITY_ONLY_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^　　数量：(\d+)").expect("QUANTITY_ONLY_RE"));
+
+/// 注文確認・出荷案内メール共通の `【オーダーID】` を抽出する
+pub fn extract_order_number(body: &str) -> Option<String> {
+    ORDER_NUMBER_RE.captures(body).map(|c| c[1].to_string())
+}
+
+/// 商品名末尾の全角括弧 `（...）` を除去する
+///
+/// 例: `PUNI☆MOFU ロン （PUNI☆MOFU ロン）` → `PUNI☆MOFU ロン`
+pub fn strip_name_suffix(name: &str) -> String {
+    if let Some(pos) = name.rfind('（') {
+        let candidate = name[..pos].trim_end();
+        if !candidate.is_empty() {
+            return candidate.to_string();
+        }
+    }
+    name.trim().to_string()
+}
@@ -0,0 +1,177 @@
+//! コトブキヤオンラインショップ 出荷案内メール用パーサー
+//!
+//! 件名：`商品出荷のお知らせ［コトブキヤオンラインショップ］`
+//! 送信元：`onlineshop@kotobukiya-ec.com`
+//!
+//! プレーンテキスト形式。注文確認メールと同じ `【オーダーID】` で既存注文に紐付ける。
+//! 出荷案内には金額が記載されないため、subtotal / shipping_fee / total_amount は None。
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+use super::{extract_order_number, strip_name_suffix, ITEM_LINE_RE, QUANTITY_ONLY_RE};
+use crate::parsers::{DeliveryInfo, EmailParser, OrderInfo, OrderItem};
+
+pub struct KotobukiyaSendParser;
+
+// ─── 正規表現 ────────────────────────────────────────────────────────────────
+
+/// `【配送業者】ヤマト運輸`
+static CARRIER_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"【配送業者】\s*(.+)").expect("CARRIER_RE"));
+
+/// `【お問い合わせ伝票番号】1234-5678-9012` / `【伝票番号】123456789012`
+static TRACKING_NUMBER_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"【(?:お問い合わせ)?伝票番号】\s*([0-9][0-9\-]*)").expect("TRACKING_NUMBER_RE")
+});
+
+// ─── 各フィールドの抽出 ───────────────────────────────────────────────────────
+
+fn extract_carrier(body: &str) -> Option<String> {
+    CARRIER_RE
+        .captures(body)
+        .map(|c| c[1].trim().to_string())
+        .filter(|s| !s.is_empty())
+}
+
+/// 伝票番号を抽出する（ハイフン区切りの表記はハイフンを除去する）
+fn extract_tracking_number(body: &str) -> Option<String> {
+    TRACKING_NUMBER_RE
+        .captures(body)
+        .map(|c| c[1].replace('-', ""))
+        .filter(|s| !s.is_empty())
+}
+
+/// `【出荷明細】` セクションから商品名と数量を抽出する
+///
+/// ```text
+/// 　N.商品名 （商品名）
+/// 　　数量：N
+/// ```
+///
+/// 区切り線（`---`）でセクション終了。価格は記載されないため 0 とし、
+/// 既存注文の商品（注文確認メールで登録済みの価格）は `save_order_in_tx` で上書きされない。
+fn extract_items(body: &str) -> Vec<OrderItem> {
+    let mut items: Vec<OrderItem> = Vec::new();
+    let mut in_section = false;
+
+    for line in body.lines() {
+        if line.contains("【出荷明細】") {
+            in_section = true;
+            continue;
+        }
+        if !in_section {
+            continue;
+        }
+        if line.trim_start().starts_with("---") {
+            break;
+        }
+        if let Some(caps) = ITEM_LINE_RE.captures(line) {
+            items.push(OrderItem {
+                name: strip_name_suffix(caps[1].trim()),
+                manufacturer: None,
+                model_number: None,
+                unit_price: 0,
+                quantity: 1,
+                subtotal: 0,
+                image_url: None,
+                item_url: None,
+                release_date: None,
+            });
+            continue;
+        }
+        if let Some(caps) = QUANTITY_ONLY_RE.captures(line) {
+            if let Some(item) = items.last_mut() {
+                item.quantity = caps[1].parse().unwrap_or(1);
+            }
+        }
+    }
+
+    items
+}
+
+// ─── EmailParser ─────────────────────────────────────────────────────────────
+
+impl EmailParser for KotobukiyaSendParser {
+    fn parse(&self, email_body: &str) -> Result<OrderInfo, String> {
+        let order_number = extract_order_number(email_body)
+            .ok_or_else(|| "注文番号（オーダーID）が見つかりません".to_string())?;
+
+        let tracking_number = extract_tracking_number(email_body)
+            .ok_or_else(|| "伝票番号が見つかりません".to_string())?;
+        let carrier =
+            extract_carrier(email_body).ok_or_else(|| "配送業者が見つかりません".to_string())?;
+
+        let items = extract_items(email_body);
+        if items.is_empty() {
+            return Err("商品情報が見つかりません".to_string());
+        }
+
+        Ok(OrderInfo {
+            order_number,
+            order_date: None,
+            delivery_address: None,
+            delivery_info: Some(DeliveryInfo {
+                carrier,
+                tracking_number,
+                delivery_date: None,
+                delivery_time: None,
+                carrier_url: None,
+                delivery_status: None,
+            }),
+            items,
+            subtotal: None,
+            shipping_fee: None,
+            total_amount: None,
+            tax_amount: None,
+            tax_included: true,
+        })
+    }
+}
+
+// ─── テスト ──────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_send() -> &'static str {
+        "山田　太郎　様\r\nコトブキヤオンラインショップをご利用いただき誠にありがとうございます。\r\nご注文いただきました商品を本日出荷いたしました。\r\n--------------------------------------------------\r\n【オーダーID】0434429495\r\n【出荷日】2026年05月20日\r\n【配送業者】ヤマト運輸\r\n【お問い合わせ伝票番号】1234-5678-9012\r\n【出荷明細】\r\n\u{3000}1.PUNI☆MOFU ロン （PUNI☆MOFU ロン）\r\n\u{3000}\u{3000}数量：1\r\n\u{3000}2.【特典】特別カラー髪パーツ\r\n\u{3000}\u{3000}数量：2\r\n--------------------------------------------------\r\n配送状況は配送業者のサイトにてご確認ください。\r\n"
+    }
+
+    #[test]
+    fn test_parse_send_order_number() {
+        let order = KotobukiyaSendParser.parse(sample_send()).unwrap();
+        assert_eq!(order.order_number, "0434429495");
+    }
+
+    #[test]
+    fn test_parse_send_delivery_info() {
+        let order = KotobukiyaSendParser.parse(sample_send()).unwrap();
+        let delivery = order.delivery_info.unwrap();
+        assert_eq!(delivery.carrier, "ヤマト運輸");
+        assert_eq!(delivery.tracking_number, "123456789012");
+    }
+
+    #[test]
+    fn test_parse_send_items() {
+        let order = KotobukiyaSendParser.parse(sample_send()).unwrap();
+        assert_eq!(order.items.len(), 2);
+        assert_eq!(order.items[0].name, "PUNI☆MOFU ロン");
+        assert_eq!(order.items[0].quantity, 1);
+        assert_eq!(order.items[1].name, "【特典】特別カラー髪パーツ");
+        assert_eq!(order.items[1].quantity, 2);
+        assert_eq!(order.total_amount, None);
+    }
+
+    #[test]
+    fn test_parse_send_without_tracking_number_returns_error() {
+        let body = sample_send().replace("【お問い合わせ伝票番号】1234-5678-9012\r\n", "");
+        assert!(KotobukiyaSendParser.parse(&body).is_err());
+    }
+
+    #[test]
+    fn test_parse_send_no_order_number_returns_error() {
+        assert!(KotobukiyaSendParser.parse("本日出荷いたしました").is_err());
+    }
+}
@@ -0,0 +1,185 @@
+use crate::logic::language::EmailLanguage;
+use crate::parsers::{EmailParser, Label, OrderInfo, OrderItem};
+use chrono::NaiveDateTime;
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+/// `取引ID：m12345678901`
+static TRANSACTION_ID: Lazy<Label> = Lazy::new(|| {
+    Label::new("取引ID")
+        .alias("商品ID")
+        .colon()
+        .value(r"m\d{8,}")
+        .build()
+});
+
+/// 取引画面のリンク: `https://jp.mercari.com/transaction/m12345678901`
+static TRANSACTION_URL_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"mercari\.com/transaction/(m\d{8,})").expect("Invalid TRANSACTION_URL_RE")
+});
+
+/// `購入日時：2025/03/10 21:15`
+static PURCHASED_AT: Lazy<Label> = Lazy::new(|| {
+    Label::new("購入日時")
+        .colon()
+        .value(r"\d{4}/\d{1,2}/\d{1,2} \d{1,2}:\d{2}")
+        .build()
+});
+
+static ITEM_NAME: Lazy<Label> = Lazy::new(|| Label::new("商品名").colon().build());
+static ITEM_PRICE: Lazy<Label> =
+    Lazy::new(|| Label::new("商品価格").alias("商品代金").colon().build());
+/// `配送料の負担：送料込み(出品者負担)` / `配送料の負担：着払い(購入者負担)`
+static SHIPPING_PAYER: Lazy<Label> = Lazy::new(|| Label::new("配送料の負担").colon().build());
+static PAYMENT_FEE: Lazy<Label> = Lazy::new(|| Label::new("支払い手数料").colon().build());
+static TOTAL_AMOUNT: Lazy<Label> = Lazy::new(|| {
+    Label::new("支払い金額")
+        .alias("お支払い金額")
+        .colon()
+        .build()
+});
+
+/// メルカリ 購入完了メール用パーサー
+///
+/// 件名：`「ねんどろいど 初音ミク」の購入を完了しました`
+/// 送信元：`no-reply@mercari.jp`
+///
+/// フリマには注文番号の概念がないため、取引 ID（= 商品 ID）を `OrderInfo::order_number` にする。
+/// 1 取引 1 商品なので商品は常に 1 件で、商品ページ（`jp.mercari.com/item/<取引 ID>`）を `item_url` に入れる。
+/// 送料込み（出品者負担）の取引は送料 0、着払いは送料不明（None）とし、
+/// 支払い手数料（コンビニ・ATM 払い）は合計との差額として調整額に回る。
+pub struct MercariPurchaseParser;
+
+fn extract_transaction_id(email_body: &str) -> Option<String> {
+    TRANSACTION_ID.find(email_body).or_else(|| {
+        TRANSACTION_URL_RE
+            .captures(email_body)
+            .map(|caps| caps[1].to_string())
+    })
+}
+
+fn extract_purchased_at(email_body: &str) -> Option<String> {
+    let value = PURCHASED_AT.find(email_body)?;
+    NaiveDateTime::parse_from_str(&value, "%Y/%m/%d %H:%M")
+        .ok()
+        .map(|dt| dt.format("%Y-%m-%d %H:%M").to_string())
+}
+
+impl EmailParser for MercariPurchaseParser {
+    fn parse(&self, email_body: &str) -> Result<OrderInfo, String> {
+        let ja = EmailLanguage::Japanese;
+
+        let transaction_id = extract_transaction_id(email_body)
+            .ok_or_else(|| "Transaction ID not found".to_string())?;
+        let name = ITEM_NAME
+            .find(email_body)
+            .ok_or_else(|| "Item name not found".to_string())?;
+        let price = ITEM_PRICE
+            .find_amount(email_body, ja)
+            .ok_or_else(|| "Item price not found".to_string())?;
+
+        let shipping_fee = SHIPPING_PAYER
+            .find(email_body)
+            .filter(|payer| payer.contains("送料込み") || payer.contains("出品者負担"))
+            .map(|_| 0);
+        let total_amount = TOTAL_AMOUNT.find_amount(email_body, ja).or_else(|| {
+            PAYMENT_FEE
+                .find_amount(email_body, ja)
+                .map(|fee| price + fee)
+        });
+
+        Ok(OrderInfo {
+            order_date: extract_purchased_at(email_body),
+            delivery_address: None,
+            delivery_info: None,
+            items: vec![OrderItem {
+                name,
+                manufacturer: None,
+                model_number: None,
+                unit_price: price,
+                quantity: 1,
+                subtotal: price,
+                image_url: None,
+                item_url: Some(format!("https://jp.mercari.com/item/{transaction_id}")),
+                release_date: None,
+            }],
+            order_number: transaction_id,
+            subtotal: Some(price),
+            shipping_fee,
+            total_amount,
+            tax_amount: None,
+            tax_included: true,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_purchase() -> &'static str {
+        r#"山田 太郎 様
+
+以下の商品の購入が完了しました。
+出品者が商品を発送するまでお待ちください。
+
+━━━━━━━━━━━━━━━━━━━━
+商品名：ねんどろいど 初音ミク 未開封
+商品価格：¥4,500
+配送料の負担：送料込み(出品者負担)
+支払い方法：コンビニ払い
+支払い手数料：¥100
+支払い金額：¥4,600
+取引ID：m12345678901
+購入日時：2025/03/10 21:15
+━━━━━━━━━━━━━━━━━━━━
+
+取引画面はこちら
+https://jp.mercari.com/transaction/m12345678901
+"#
+    }
+
+    #[test]
+    fn test_parse_purchase_transaction_id_as_order_number() {
+        let order = MercariPurchaseParser.parse(sample_purchase()).unwrap();
+        assert_eq!(order.order_number, "m12345678901");
+        assert_eq!(order.order_date.as_deref(), Some("2025-03-10 21:15"));
+    }
+
+    #[test]
+    fn test_parse_purchase_item_and_amounts() {
+        let order = MercariPurchaseParser.parse(sample_purchase()).unwrap();
+        assert_eq!(order.items.len(), 1);
+        let item = &order.items[0];
+        assert_eq!(item.name, "ねんどろいど 初音ミク 未開封");
+        assert_eq!(item.unit_price, 4500);
+        assert_eq!(item.quantity, 1);
+        assert_eq!(
+            item.item_url.as_deref(),
+            Some("https://jp.mercari.com/item/m12345678901")
+        );
+
+        assert_eq!(order.subtotal, Some(4500));
+        assert_eq!(order.shipping_fee, Some(0));
+        assert_eq!(order.total_amount, Some(4600));
+    }
+
+    #[test]
+    fn test_parse_purchase_transaction_id_from_url_and_cash_on_delivery() {
+        let body = sample_purchase()
+            .replace("取引ID：m12345678901\n", "")
+            .replace("支払い金額：¥4,600\n", "")
+            .replace("送料込み(出品者負担)", "着払い(購入者負担)");
+        let order = MercariPurchaseParser.parse(&body).unwrap();
+        assert_eq!(order.order_number, "m12345678901");
+        assert_eq!(order.shipping_fee, None);
+        // 支払い金額がなければ商品価格 + 支払い手数料
+        assert_eq!(order.total_amount, Some(4600));
+    }
+
+    #[test]
+    fn test_parse_purchase_missing_transaction_id() {
+        let body = "商品名：テスト\n商品価格：¥1,000";
+        assert!(MercariPurchaseParser.parse(body).is_err());
+    }
+}
@@ -15,14 +15,21 @@ pub mod animate;
 pub mod dmm;
 pub mod furuichi_online;
 pub mod goodsmile;
+pub mod hlj;
 pub mod hobbyjapan;
 pub mod hobbysearch;
+pub mod hobbystock;
 pub mod kids_dragon;
 pub mod kotobukiya;
+pub mod mercari;
 pub mod premium_bandai;
+pub mod rakuten;
 pub mod sagawa;
 pub mod surugaya;
 pub mod surugaya_mp;
+pub mod volks;
+pub mod yahoo_auction;
+pub mod yahoo_shopping;
 pub mod yodobashi;
 
 // ─────────────────────────────────────────────────────────────────────────────
@@ -66,6 +73,7 @@ use chrono::DateTime;
 use std::path::PathBuf;
 use std::sync::Arc;
 
+use crate::logic::language::EmailLanguage;
 use crate::parsers::{EmailParser, OrderInfo};
 use crate::repository::ShopSettingsRepository;
 
@@ -120,6 +128,12 @@ pub enum DispatchOutcome {
     MultiOrderSaved(Vec<OrderInfo>),
     /// 配達完了メールを処理した（tracking_check_logs + deliveries を更新済み）
     DeliveryCompleted { tracking_number: String },
+    /// お届け予定日変更を適用した（deliveries.estimated_delivery を更新済み）
+    DeliveryDateChanged { order_number: String },
+    /// 発売延期を適用した（items.release_date を更新済み）
+    ReleaseDateChanged { order_number: String },
+    /// 出荷準備中を適用した（deliveries.delivery_status を preparing に更新済み）
+    PreparingApplied { order_number: String },
 }
 
 /// ディスパッチ失敗時のエラー種別
@@ -215,6 +229,15 @@ pub trait VendorPlugin: Send + Sync {
     fn prefer_plain_text(&self) -> bool {
         false
     }
+
+    /// `parser_type` が対象とするメールの言語
+    ///
+    /// デフォルトは日本語。英語メール用のパーサーを持つプラグインはオーバーライドする。
+    /// 本文の言語判定（`detect_language`）と合わせて、候補パーサーの絞り込みに使われる。
+    fn parser_language(&self, parser_type: &str) -> EmailLanguage {
+        let _ = parser_type;
+        EmailLanguage::Japanese
+    }
 }
 
 // ─────────────────────────────────────────────────────────────────────────────
@@ -233,6 +256,49 @@ pub(crate) const JAPANPOST_TRACKING_URL_DIRECT: &str =
 // プラグイン共通ヘルパー（pub(crate) で各プラグインから参照）
 // ─────────────────────────────────────────────────────────────────────────────
 
+/// 配送会社名から追跡URLを返す
+pub(crate) fn tracking_url_for_carrier(carrier: &str) -> Option<String> {
+    if carrier.contains("ヤマト") || carrier.contains("クロネコ") {
+        // ヤマト運輸は追跡番号を直接URLに含める形式
+        Some("https://jizen.kuronekoyamato.co.jp/jizen/servlet/crjz.b.NQ0010".to_string())
+    } else if carrier.contains("日本郵便")
+        || carrier.contains("ゆうパック")
+        || carrier.contains("ゆうパケット")
+    {
+        Some(JAPANPOST_TRACKING_URL.to_string())
+    } else if carrier.contains("佐川") {
+        Some("https://k2k.sagawa-exp.co.jp/p/web/okurijosearch.do".to_string())
+    } else {
+        None
+    }
+}
+
+/// 配送業者名の表記ゆれを吸収して正式名称を返す（判定できない場合は None）
+///
+/// `クロネコヤマト` / `宅急便` / `ネコポス` → ヤマト運輸、`佐川` / `飛脚` → 佐川急便、
+/// `ゆうパック` / `ゆうパケット` / `郵便局` → 日本郵便。追跡 URL のドメインでも判定する。
+pub(crate) fn canonical_carrier_name(text: &str) -> Option<&'static str> {
+    let lower = text.to_lowercase();
+    let has = |keywords: &[&str]| keywords.iter().any(|k| lower.contains(k));
+    if has(&["ヤマト", "クロネコ", "宅急便", "ネコポス", "yamato"]) {
+        Some("ヤマト運輸")
+    } else if has(&["佐川", "飛脚", "sagawa"]) {
+        Some("佐川急便")
+    } else if has(&[
+        "日本郵便",
+        "郵便局",
+        "ゆうパック",
+        "ゆうパケット",
+        "ゆうメール",
+        "japanpost",
+        "japan post",
+    ]) {
+        Some("日本郵便")
+    } else {
+        None
+    }
+}
+
 /// from_address から shop_domain（ドメイン文字列）を抽出する
 pub(crate) fn derive_shop_domain(from_address: Option<&str>) -> Option<String> {
     use crate::logic::email_parser::extract_domain;
@@ -264,6 +330,19 @@ pub(crate) fn apply_internal_date(order_info: &mut OrderInfo, internal_date: Opt
     }
 }
 
+/// 本文から消費税額・税込/税抜表示を抽出して `OrderInfo` に補完する
+///
+/// パーサーが既に設定している値（`tax_amount` が Some、`tax_included` が false）は上書きしない。
+pub(crate) fn apply_tax_info(order_info: &mut OrderInfo, body: &str) {
+    let tax = crate::parsers::tax_info::extract_tax_info(body);
+    if order_info.tax_amount.is_none() {
+        order_info.tax_amount = tax.tax_amount;
+    }
+    if !tax.tax_included {
+        order_info.tax_included = false;
+    }
+}
+
 /// `OrderInfo` に含まれる商品画像 URL を `images` テーブルに登録する
 ///
 /// `image_save_ctx` が `None` の場合は何もしない。
@@ -373,6 +452,44 @@ mod tests {
         assert!(plugin.is_some());
     }
 
+    #[test]
+    fn test_find_plugin_amazon_send() {
+        let registry = build_registry();
+        let plugin = find_plugin(&registry, "amazon_send");
+        assert!(plugin.is_some());
+    }
+
+    #[test]
+    fn test_find_plugin_rakuten_send() {
+        let registry = build_registry();
+        let plugin = find_plugin(&registry, "rakuten_send");
+        assert!(plugin.is_some());
+    }
+
+    #[test]
+    fn test_canonical_carrier_name() {
+        assert_eq!(canonical_carrier_name("クロネコヤマト"), Some("ヤマト運輸"));
+        assert_eq!(
+            canonical_carrier_name("宅急便コンパクト"),
+            Some("ヤマト運輸")
+        );
+        assert_eq!(canonical_carrier_name("佐川急便株式会社"), Some("佐川急便"));
+        assert_eq!(canonical_carrier_name("飛脚宅配便"), Some("佐川急便"));
+        assert_eq!(canonical_carrier_name("ゆうパケット"), Some("日本郵便"));
+        assert_eq!(
+            canonical_carrier_name("https://trackings.post.JAPANPOST.jp/"),
+            Some("日本郵便")
+        );
+        assert_eq!(canonical_carrier_name("西濃運輸"), None);
+    }
+
+    #[test]
+    fn test_find_plugin_amazon_cancel() {
+        let registry = build_registry();
+        let plugin = find_plugin(&registry, "amazon_cancel");
+        assert!(plugin.is_some());
+    }
+
     #[test]
     fn test_find_plugin_yodobashi_confirm() {
         let registry = build_registry();
@@ -422,6 +539,7 @@ mod tests {
             "dmm_order_number_change",
             "dmm_split_complete",
             "dmm_merge_complete",
+            "dmm_stock_arrival",
         ];
         for pt in &dmm_types {
             assert!(find_plugin(&registry, pt).is_some(), "No plugin for {}", pt);
@@ -438,6 +556,7 @@ mod tests {
             "hobbysearch_change_yoyaku",
             "hobbysearch_send",
             "hobbysearch_cancel",
+            "hobbysearch_delay",
         ];
         for pt in &hs_types {
             assert!(find_plugin(&registry, pt).is_some(), "No plugin for {}", pt);
@@ -450,6 +569,7 @@ mod tests {
         let pb_types = [
             "premium_bandai_confirm",
             "premium_bandai_omatome",
+            "pbandai_merge_complete",
             "premium_bandai_send",
         ];
         for pt in &pb_types {
@@ -478,7 +598,7 @@ mod tests {
     #[test]
     fn test_all_kotobukiya_parser_types_have_plugin() {
         let registry = build_registry();
-        let kotobukiya_types = ["kotobukiya_confirm"];
+        let kotobukiya_types = ["kotobukiya_confirm", "kotobukiya_send"];
         for pt in &kotobukiya_types {
             assert!(find_plugin(&registry, pt).is_some(), "No plugin for {}", pt);
         }
@@ -491,8 +611,11 @@ mod tests {
             "amiami_rakuten_confirm",
             "amiami_rakuten_send",
             "amiami_confirm",
+            "amiami_confirm_yoyaku",
+            "amiami_en_confirm",
             "amiami_send",
             "amiami_cancel",
+            "amiami_delay",
         ];
         for pt in &amiami_types {
             assert!(find_plugin(&registry, pt).is_some(), "No plugin for {}", pt);
@@ -502,12 +625,48 @@ mod tests {
     #[test]
     fn test_all_surugaya_parser_types_have_plugin() {
         let registry = build_registry();
-        let surugaya_types = ["surugaya_confirm", "surugaya_send"];
+        let surugaya_types = ["surugaya_confirm", "surugaya_send", "surugaya_cancel"];
         for pt in &surugaya_types {
             assert!(find_plugin(&registry, pt).is_some(), "No plugin for {}", pt);
         }
     }
 
+    #[test]
+    fn test_all_hobbystock_parser_types_have_plugin() {
+        let registry = build_registry();
+        assert!(find_plugin(&registry, "hobbystock_confirm").is_some());
+    }
+
+    #[test]
+    fn test_all_volks_parser_types_have_plugin() {
+        let registry = build_registry();
+        assert!(find_plugin(&registry, "volks_confirm").is_some());
+    }
+
+    #[test]
+    fn test_all_yahoo_shopping_parser_types_have_plugin() {
+        let registry = build_registry();
+        assert!(find_plugin(&registry, "yahoo_shopping_confirm").is_some());
+    }
+
+    #[test]
+    fn test_all_hlj_parser_types_have_plugin() {
+        let registry = build_registry();
+        assert!(find_plugin(&registry, "hlj_confirm").is_some());
+    }
+
+    #[test]
+    fn test_all_mercari_parser_types_have_plugin() {
+        let registry = build_registry();
+        assert!(find_plugin(&registry, "mercari_purchase").is_some());
+    }
+
+    #[test]
+    fn test_all_yahoo_auction_parser_types_have_plugin() {
+        let registry = build_registry();
+        assert!(find_plugin(&registry, "yahoo_auction_won").is_some());
+    }
+
     #[test]
     fn test_all_surugaya_mp_parser_types_have_plugin() {
         let registry = build_registry();
@@ -2,6 +2,9 @@
 //!
 //! 送信元アドレス `evidence_bc@p-bandai.jp` / `evidence_info@p-bandai.jp` から届く
 //! 注文確認・おまとめ完了・発送通知メールに対応する。
+//!
+//! おまとめ完了メールは、おまとめ前の注文番号が記載されていれば `pbandai_merge_complete` が
+//! 元注文を統合し、記載がなければ `premium_bandai_omatome` が商品名マッチングで処理する。
 
 pub mod parsers;
 
@@ -12,8 +15,8 @@ use crate::parsers::EmailParser;
 use crate::repository::SqliteOrderRepository;
 
 use super::{
-    apply_internal_date, derive_shop_domain, DefaultShopSetting, DispatchError, DispatchOutcome,
-    PluginRegistration, VendorPlugin,
+    apply_internal_date, apply_tax_info, derive_shop_domain, DefaultShopSetting, DispatchError,
+    DispatchOutcome, PluginRegistration, VendorPlugin,
 };
 
 pub struct PremiumBandaiPlugin;
@@ -24,6 +27,7 @@ impl VendorPlugin for PremiumBandaiPlugin {
         &[
             "premium_bandai_confirm",
             "premium_bandai_omatome",
+            "pbandai_merge_complete",
             "premium_bandai_send",
         ]
     }
@@ -32,6 +36,7 @@ impl VendorPlugin for PremiumBandaiPlugin {
         10
     }
 
+    /// `OrderInfo` を返すパーサーのみ。pbandai_merge_complete は `dispatch()` 内で直接処理する。
     fn get_parser(&self, parser_type: &str) -> Option<Box<dyn EmailParser>> {
         match parser_type {
             "premium_bandai_confirm" => {
@@ -55,7 +60,10 @@ impl VendorPlugin for PremiumBandaiPlugin {
                 shop_name: "プレミアムバンダイ".to_string(),
                 sender_address: "evidence_bc@p-bandai.jp".to_string(),
                 parser_type: "premium_bandai_confirm".to_string(),
-                subject_filters: Some(vec!["ご注文完了のお知らせ".to_string()]),
+                subject_filters: Some(vec![
+                    "ご注文完了のお知らせ".to_string(),
+                    "ご注文ありがとうございます".to_string(),
+                ]),
             },
             DefaultShopSetting {
                 shop_name: "プレミアムバンダイ".to_string(),
@@ -63,6 +71,12 @@ impl VendorPlugin for PremiumBandaiPlugin {
                 parser_type: "premium_bandai_omatome".to_string(),
                 subject_filters: Some(vec!["ご注文おまとめ完了のお知らせ".to_string()]),
             },
+            DefaultShopSetting {
+                shop_name: "プレミアムバンダイ".to_string(),
+                sender_address: "evidence_bc@p-bandai.jp".to_string(),
+                parser_type: "pbandai_merge_complete".to_string(),
+                subject_filters: Some(vec!["ご注文おまとめ完了のお知らせ".to_string()]),
+            },
             DefaultShopSetting {
                 shop_name: "プレミアムバンダイ".to_string(),
                 sender_address: "evidence_bc@p-bandai.jp".to_string(),
@@ -74,7 +88,10 @@ impl VendorPlugin for PremiumBandaiPlugin {
                 shop_name: "プレミアムバンダイ".to_string(),
                 sender_address: "evidence_info@p-bandai.jp".to_string(),
                 parser_type: "premium_bandai_confirm".to_string(),
-                subject_filters: Some(vec!["ご注文完了のお知らせ".to_string()]),
+                subject_filters: Some(vec![
+                    "ご注文完了のお知らせ".to_string(),
+                    "ご注文ありがとうございます".to_string(),
+                ]),
             },
             DefaultShopSetting {
                 shop_name: "プレミアムバンダイ".to_string(),
@@ -82,6 +99,12 @@ impl VendorPlugin for PremiumBandaiPlugin {
                 parser_type: "premium_bandai_omatome".to_string(),
                 subject_filters: Some(vec!["ご注文おまとめ完了のお知らせ".to_string()]),
             },
+            DefaultShopSetting {
+                shop_name: "プレミアムバンダイ".to_string(),
+                sender_address: "evidence_info@p-bandai.jp".to_string(),
+                parser_type: "pbandai_merge_complete".to_string(),
+                subject_filters: Some(vec!["ご注文おまとめ完了のお知らせ".to_string()]),
+            },
             DefaultShopSetting {
                 shop_name: "プレミアムバンダイ".to_string(),
                 sender_address: "evidence_info@p-bandai.jp".to_string(),
@@ -105,8 +128,43 @@ impl VendorPlugin for PremiumBandaiPlugin {
         let shop_domain = derive_shop_domain(from_address);
 
         match parser_type {
+            // ── おまとめ完了（おまとめ前の注文番号あり）：元注文を注文番号で統合 ──
+            "pbandai_merge_complete" => {
+                let consolidation_info = parsers::merge_complete::PremiumBandaiMergeCompleteParser
+                    .parse_consolidation(body)
+                    .map_err(DispatchError::ParseFailed)?;
+
+                log::debug!(
+                    "[pbandai_merge_complete] email_id={} {:?} -> {}",
+                    email_id,
+                    consolidation_info.old_order_numbers,
+                    consolidation_info.new_order_number
+                );
+
+                SqliteOrderRepository::apply_consolidation_in_tx(
+                    tx,
+                    &consolidation_info,
+                    email_id,
+                    shop_domain,
+                    None,
+                )
+                .await
+                .map_err(DispatchError::SaveFailed)?;
+
+                Ok(DispatchOutcome::ConsolidationApplied {
+                    new_order_number: consolidation_info.new_order_number,
+                })
+            }
+
             // ── おまとめ完了：元注文を商品名マッチングで無効化し、新注文を保存 ──
             "premium_bandai_omatome" => {
+                // おまとめ前の注文番号があるメールは pbandai_merge_complete に任せる
+                if parsers::merge_complete::has_old_order_numbers(body) {
+                    return Err(DispatchError::ParseFailed(
+                        "Old order numbers found; handled by pbandai_merge_complete".to_string(),
+                    ));
+                }
+
                 let mut order_info = {
                     let parser = self.get_parser(parser_type).ok_or_else(|| {
                         DispatchError::ParseFailed(format!("No parser for type: {}", parser_type))
@@ -115,6 +173,7 @@ impl VendorPlugin for PremiumBandaiPlugin {
                 };
 
                 apply_internal_date(&mut order_info, internal_date);
+                apply_tax_info(&mut order_info, body);
 
                 // `DateTime` は生成後に捨てており、`from_timestamp_millis` が
                 // `Some` を返すかどうかでタイムスタンプの有効性のみを検証している。
@@ -184,12 +243,13 @@ impl VendorPlugin for PremiumBandaiPlugin {
 
             // ── confirm / send：通常保存 ──
             _ => {
-                let order_info = {
+                let mut order_info = {
                     let parser = self.get_parser(parser_type).ok_or_else(|| {
                         DispatchError::ParseFailed(format!("No parser for type: {}", parser_type))
                     })?;
                     parser.parse(body).map_err(DispatchError::ParseFailed)?
                 };
+                apply_tax_info(&mut order_info, body);
 
                 log::debug!(
                     "[{}] email_id={} order_number={}",
@@ -235,6 +295,7 @@ async fn cleanup_phantom_omatome_items_in_tx(
         JOIN emails e ON e.id = oe.email_id
         WHERE o.order_number COLLATE NOCASE != ?
         AND o.shop_domain = ?
+        AND o.deleted_at IS NULL
         AND o.id NOT IN (
             SELECT d.order_id FROM deliveries d
             WHERE d.delivery_status IN ('shipped', 'in_transit', 'out_for_delivery', 'delivered')
@@ -319,9 +380,10 @@ mod tests {
     fn test_premium_bandai_plugin_parser_types() {
         let plugin = PremiumBandaiPlugin;
         let types = plugin.parser_types();
-        assert_eq!(types.len(), 3);
+        assert_eq!(types.len(), 4);
         assert!(types.contains(&"premium_bandai_confirm"));
         assert!(types.contains(&"premium_bandai_omatome"));
+        assert!(types.contains(&"pbandai_merge_complete"));
         assert!(types.contains(&"premium_bandai_send"));
     }
 
@@ -351,6 +413,14 @@ mod tests {
             .is_some());
     }
 
+    #[test]
+    fn test_premium_bandai_plugin_get_parser_merge_complete_returns_none() {
+        // pbandai_merge_complete は dispatch() 内で直接処理
+        assert!(PremiumBandaiPlugin
+            .get_parser("pbandai_merge_complete")
+            .is_none());
+    }
+
     #[test]
     fn test_premium_bandai_plugin_get_parser_unknown_returns_none() {
         assert!(PremiumBandaiPlugin.get_parser("unknown").is_none());
@@ -364,7 +434,7 @@ mod tests {
 
     #[test]
     fn test_premium_bandai_default_shop_settings_count() {
-        assert_eq!(PremiumBandaiPlugin.default_shop_settings().len(), 6);
+        assert_eq!(PremiumBandaiPlugin.default_shop_settings().len(), 8);
     }
 
     #[test]
@@ -373,9 +443,130 @@ mod tests {
         let parser_types: Vec<&str> = settings.iter().map(|s| s.parser_type.as_str()).collect();
         assert!(parser_types.contains(&"premium_bandai_confirm"));
         assert!(parser_types.contains(&"premium_bandai_omatome"));
+        assert!(parser_types.contains(&"pbandai_merge_complete"));
         assert!(parser_types.contains(&"premium_bandai_send"));
     }
 
+    const MERGE_COMPLETE_BODY: &str = "【おまとめ前のご注文No.】\n\
+         1: 00125\n\
+         2: 00127\n\
+         【ご注文No.】\u{3000} 00130\n\
+         【ご注文明細】\n\
+         商品A\n\
+         3,630円×1＝3,630円\n\
+         商品B\n\
+         2,640円×1＝2,640円\n";
+
+    async fn create_pool() -> sqlx::SqlitePool {
+        let pool = sqlx::sqlite::SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .unwrap();
+        sqlx::raw_sql(
+            r#"
+            CREATE TABLE orders (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                order_number TEXT,
+                order_number_normalized TEXT,
+                shop_domain TEXT,
+                deleted_at DATETIME
+            );
+            CREATE TABLE items (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                order_id INTEGER NOT NULL,
+                item_name TEXT NOT NULL
+            );
+            CREATE TABLE deliveries (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                order_id INTEGER NOT NULL,
+                delivery_status TEXT NOT NULL DEFAULT 'not_shipped'
+            );
+            CREATE TABLE order_emails (
+                order_id INTEGER NOT NULL,
+                email_id INTEGER NOT NULL
+            );
+            INSERT INTO orders (id, order_number, order_number_normalized, shop_domain) VALUES
+                (1, '00125', '00125', 'p-bandai.jp'),
+                (2, '00127', '00127', 'p-bandai.jp');
+            INSERT INTO items (order_id, item_name) VALUES (1, '商品A'), (2, '商品B');
+            INSERT INTO deliveries (order_id) VALUES (1), (2);
+            "#,
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+        pool
+    }
+
+    #[tokio::test]
+    async fn test_dispatch_merge_complete_consolidates_old_orders() {
+        let pool = create_pool().await;
+        let mut tx = pool.begin().await.unwrap();
+        let outcome = PremiumBandaiPlugin
+            .dispatch(
+                "pbandai_merge_complete",
+                10,
+                Some("evidence_bc@p-bandai.jp"),
+                "プレミアムバンダイ",
+                None,
+                MERGE_COMPLETE_BODY,
+                &mut tx,
+            )
+            .await;
+        tx.commit().await.unwrap();
+
+        match outcome {
+            Ok(DispatchOutcome::ConsolidationApplied { new_order_number }) => {
+                assert_eq!(new_order_number, "00130")
+            }
+            Ok(_) => panic!("unexpected outcome"),
+            Err(e) => panic!("dispatch failed: {e:?}"),
+        }
+
+        // 先頭の元注文が新しい注文番号になり、残りの元注文の商品・配送は削除される
+        let orders: Vec<(i64, String)> =
+            sqlx::query_as("SELECT id, order_number FROM orders ORDER BY id")
+                .fetch_all(&pool)
+                .await
+                .unwrap();
+        assert_eq!(orders[0], (1, "00130".to_string()));
+        let items: Vec<(i64,)> = sqlx::query_as("SELECT order_id FROM items ORDER BY id")
+            .fetch_all(&pool)
+            .await
+            .unwrap();
+        assert_eq!(items, vec![(1,)]);
+        let deliveries: Vec<(i64,)> = sqlx::query_as("SELECT order_id FROM deliveries")
+            .fetch_all(&pool)
+            .await
+            .unwrap();
+        assert_eq!(deliveries, vec![(1,)]);
+        let links: Vec<(i64, i64)> = sqlx::query_as("SELECT order_id, email_id FROM order_emails")
+            .fetch_all(&pool)
+            .await
+            .unwrap();
+        assert_eq!(links, vec![(1, 10)]);
+    }
+
+    #[tokio::test]
+    async fn test_dispatch_omatome_defers_to_merge_complete() {
+        // おまとめ前の注文番号があるメールは premium_bandai_omatome では処理しない
+        let pool = create_pool().await;
+        let mut tx = pool.begin().await.unwrap();
+        let outcome = PremiumBandaiPlugin
+            .dispatch(
+                "premium_bandai_omatome",
+                10,
+                Some("evidence_bc@p-bandai.jp"),
+                "プレミアムバンダイ",
+                None,
+                MERGE_COMPLETE_BODY,
+                &mut tx,
+            )
+            .await;
+        assert!(matches!(outcome, Err(DispatchError::ParseFailed(_))));
+    }
+
     #[test]
     fn test_premium_bandai_default_shop_settings_sender_addresses() {
         let settings = PremiumBandaiPlugin.default_shop_settings();
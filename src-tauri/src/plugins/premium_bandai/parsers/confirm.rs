@@ -6,8 +6,13 @@
 //! フォーマット: multipart/alternative（テキスト + HTML）
 //! 文字コード: ISO-2022-JP（Gmail API により UTF-8 に変換済み）
 //!
+//! 件名：`ご注文ありがとうございます`（受注時に届く旧形式の件名。本文は同一）
+//!
 //! 商品画像 URL は HTML パートから取得する。
 //! 「おすすめ商品」セクション以降は注文商品に含めない。
+//!
+//! お届け予定時期（`【４次：２０２３年９月発送】` / `お届け予定時期：2025年06月発送予定`）は
+//! 商品の発売予定（`release_date`）として保存する。
 
 use once_cell::sync::Lazy;
 use regex::Regex;
@@ -18,6 +23,7 @@ use super::{
     find_recommend_section_line, normalize_product_name, parse_item_subtotal, parse_price,
     parse_quantity,
 };
+use crate::parsers::release_date::{parse_fuzzy_release_date, FuzzyReleaseDate};
 use crate::parsers::{EmailParser, OrderInfo, OrderItem};
 use scraper::{ElementRef, Html, Selector};
 
@@ -56,6 +62,12 @@ static ITEM_PRICE_QTY_RE: Lazy<Regex> = Lazy::new(|| {
     Regex::new(r"^([\d,]+)円(?:×|&times;)(\d+)[＝=]([\d,]+)円").expect("Invalid ITEM_PRICE_QTY_RE")
 });
 
+/// お届け予定時期（`2025年6月発送予定` / `【２次：２０２３年９月発送】` / `2025年6月下旬発送`）
+static SHIPPING_SCHEDULE_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"[0-9０-９]{4}\s*年\s*[0-9０-９]{1,2}\s*月(?:上旬|初旬|中旬|下旬|末)?\s*頃?\s*発送")
+        .expect("Invalid SHIPPING_SCHEDULE_RE")
+});
+
 /// プレミアムバンダイ 注文確認メール用パーサー
 pub struct PremiumBandaiConfirmParser;
 
@@ -86,6 +98,7 @@ impl EmailParser for PremiumBandaiConfirmParser {
         if items.is_empty() {
             return Err("No items found".to_string());
         }
+        let items = apply_order_shipping_schedule(items, item_lines);
 
         let subtotal: i64 = items.iter().map(|i| i.subtotal).sum();
         let subtotal = if subtotal > 0 { Some(subtotal) } else { None };
@@ -112,10 +125,40 @@ impl EmailParser for PremiumBandaiConfirmParser {
             subtotal,
             shipping_fee: combined_fee,
             total_amount,
+            tax_amount: None,
+            tax_included: true,
         })
     }
 }
 
+/// 行に含まれるお届け予定時期を発売予定として返す
+fn parse_shipping_schedule(line: &str) -> Option<FuzzyReleaseDate> {
+    let m = SHIPPING_SCHEDULE_RE.find(line)?;
+    parse_fuzzy_release_date(m.as_str(), None)
+}
+
+/// 商品ごとのお届け予定時期がない場合、注文全体のお届け予定時期を全商品に割り当てる
+///
+/// プレミアムバンダイは発送月ごとに注文が分かれるため、注文内で予定時期が 1 種類であれば
+/// 全商品の発売予定とみなす。複数種類ある場合はどの商品のものか判別できないため割り当てない。
+fn apply_order_shipping_schedule(mut items: Vec<OrderItem>, lines: &[&str]) -> Vec<OrderItem> {
+    if items.iter().any(|i| i.release_date.is_some()) {
+        return items;
+    }
+    let mut schedules: Vec<FuzzyReleaseDate> = Vec::new();
+    for date in lines.iter().filter_map(|l| parse_shipping_schedule(l)) {
+        if !schedules.contains(&date) {
+            schedules.push(date);
+        }
+    }
+    if let [schedule] = schedules.as_slice() {
+        for item in &mut items {
+            item.release_date = Some(schedule.clone());
+        }
+    }
+    items
+}
+
 /// `N円×N＝N円` / `N円&times;N＝N円` 形式の価格行を解析する
 ///
 /// 戻り値: `(unit_price, quantity, subtotal)`
@@ -218,6 +261,8 @@ fn extract_items_from_confirm_html(html: &str, image_urls: &[String]) -> Vec<Ord
             quantity,
             subtotal,
             image_url,
+            item_url: None,
+            release_date: parse_shipping_schedule(&raw_name),
         });
     }
 
@@ -229,6 +274,7 @@ fn extract_items_from_confirm_html(html: &str, image_urls: &[String]) -> Vec<Ord
 /// 商品名行 → `単価：￥N（税込）` → `個数：N個` → `小計：￥N` のパターンを繰り返しパースする。
 /// HTML テーブル形式では `N円×N＝N円` のパターンも対応する。
 /// `image_urls` が提供された場合は商品の順序に対応する URL を割り当てる。
+/// 商品名の `【…発送】` または直後の `お届け予定時期` 行を発売予定として割り当てる。
 fn extract_confirm_items(lines: &[&str], image_urls: &[String]) -> Vec<OrderItem> {
     let mut items: Vec<OrderItem> = Vec::new();
 
@@ -237,6 +283,7 @@ fn extract_confirm_items(lines: &[&str], image_urls: &[String]) -> Vec<OrderItem
         name: Option<String>,
         unit_price: Option<i64>,
         quantity: Option<i64>,
+        release_date: Option<FuzzyReleaseDate>,
     }
 
     let mut pending = Pending::default();
@@ -256,10 +303,13 @@ fn extract_confirm_items(lines: &[&str], image_urls: &[String]) -> Vec<OrderItem
                 quantity,
                 subtotal,
                 image_url,
+                item_url: None,
+                release_date: p.release_date.take(),
             });
         }
         p.unit_price = None;
         p.quantity = None;
+        p.release_date = None;
     };
 
     for line in lines {
@@ -299,6 +349,8 @@ fn extract_confirm_items(lines: &[&str], image_urls: &[String]) -> Vec<OrderItem
                     quantity,
                     subtotal,
                     image_url,
+                    item_url: None,
+                    release_date: pending.release_date.take(),
                 });
                 pending.name = None;
                 pending.unit_price = None;
@@ -327,6 +379,8 @@ fn extract_confirm_items(lines: &[&str], image_urls: &[String]) -> Vec<OrderItem
                     quantity,
                     subtotal,
                     image_url,
+                    item_url: None,
+                    release_date: pending.release_date.take(),
                 });
                 pending.name = None;
                 pending.unit_price = None;
@@ -364,6 +418,18 @@ fn extract_confirm_items(lines: &[&str], image_urls: &[String]) -> Vec<OrderItem
             continue;
         }
 
+        // お届け予定時期行は確定前の商品、なければ直前の商品の発売予定とする
+        if trimmed.starts_with("お届け予定") || trimmed.contains("発送予定") {
+            if let Some(date) = parse_shipping_schedule(trimmed) {
+                if pending.name.is_some() {
+                    pending.release_date = Some(date);
+                } else if let Some(last) = items.last_mut().filter(|i| i.release_date.is_none()) {
+                    last.release_date = Some(date);
+                }
+            }
+            continue;
+        }
+
         // 注文番号・注文日・お支払方法などのヘッダー行はスキップ
         if trimmed.starts_with("ご注文番号")
             || trimmed.starts_with("注文番号")
@@ -389,6 +455,7 @@ fn extract_confirm_items(lines: &[&str], image_urls: &[String]) -> Vec<OrderItem
 
         if !trimmed.is_empty() {
             pending.name = Some(normalize_product_name(trimmed));
+            pending.release_date = parse_shipping_schedule(trimmed);
         }
     }
 
@@ -566,6 +633,62 @@ figma テスト【再販】
         assert_eq!(order.total_amount, Some(8990));
     }
 
+    // ─── お届け予定時期テスト ───
+
+    #[test]
+    fn test_parse_confirm_release_date_from_item_name() {
+        let order = PremiumBandaiConfirmParser
+            .parse(sample_confirm_multiple_items())
+            .unwrap();
+        assert_eq!(
+            order.items[0]
+                .release_date
+                .as_ref()
+                .map(|d| d.to_release_date()),
+            Some("2025-04".to_string())
+        );
+        // 予定時期の記載がない商品には割り当てない
+        assert!(order.items[1].release_date.is_none());
+    }
+
+    #[test]
+    fn test_parse_confirm_release_date_from_schedule_line() {
+        let body = "■ご注文番号：12345\n■ご注文内容\n商品A\nお届け予定時期：2025年06月発送予定\n単価：￥5,000（税込）\n個数：1個\n小計：￥5,000\n商品B\n単価：￥3,000（税込）\n個数：1個\n小計：￥3,000\nお届け予定時期：2025年08月下旬発送予定\n合計：￥8,000";
+        let order = PremiumBandaiConfirmParser.parse(body).unwrap();
+        let dates: Vec<Option<String>> = order
+            .items
+            .iter()
+            .map(|i| i.release_date.as_ref().map(|d| d.to_release_date()))
+            .collect();
+        assert_eq!(
+            dates,
+            vec![Some("2025-06".to_string()), Some("2025-08-31".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_parse_confirm_order_level_schedule_applies_to_all_items() {
+        // HTML テーブル形式で予定時期が注文全体に 1 つだけ記載される場合
+        let body = "【注文No.】\n00037\n【注文明細】\n商品A\n2,420円&times;1＝2,420円\n商品B\n1,100円&times;2＝2,200円\n【お届け予定時期】\n２０２５年１１月発送予定\n【お支払方法】\nペイディ";
+        let order = PremiumBandaiConfirmParser.parse(body).unwrap();
+        assert_eq!(order.items.len(), 2);
+        assert!(order
+            .items
+            .iter()
+            .all(|i| i.release_date.as_ref().map(|d| d.to_release_date())
+                == Some("2025-11".to_string())));
+    }
+
+    #[test]
+    fn test_extract_items_from_confirm_html_release_date() {
+        let html = r#"<table><tr><th rowspan="2">【注文明細】</th><td>ＨＧ 1/144 テスト商品【４次：２０２３年９月発送】</td></tr><tr><td>2,420円×1＝2,420円</td></tr></table>"#;
+        let items = extract_items_from_confirm_html(html, &[]);
+        assert_eq!(
+            items[0].release_date.as_ref().map(|d| d.to_release_date()),
+            Some("2023-09".to_string())
+        );
+    }
+
     // ─── エラーケース ───
 
     #[test]
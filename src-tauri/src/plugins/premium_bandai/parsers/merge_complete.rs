@@ -0,0 +1,157 @@
+//! プレミアムバンダイ ご注文おまとめ完了メール（おまとめ前の注文番号つき）用パーサー
+//!
+//! 件名：`ご注文おまとめ完了のお知らせ`
+//! 送信元：`evidence_bc@p-bandai.jp` / `evidence_info@p-bandai.jp`
+//!
+//! 本文に `【おまとめ前のご注文No.】` ブロックがある場合のみ対象とし、
+//! まとめる前の注文番号リストとまとめた後の注文番号（`【ご注文No.】`）を抽出する。
+//! ブロックがないメールは従来どおり `premium_bandai_omatome` が商品名マッチングで処理する。
+
+use super::{body_to_lines, extract_order_number};
+use crate::parsers::consolidation_info::ConsolidationInfo;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::collections::HashSet;
+
+/// おまとめ前の注文番号ブロックの見出し: `【おまとめ前のご注文No.】` / `おまとめ前のご注文番号：`
+static OLD_ORDER_LABEL_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"おまとめ前の?ご?注文(?:No\.|番号)】?[：:]?").expect("Invalid OLD_ORDER_LABEL_RE")
+});
+
+/// 数字列（長さは呼び出し側で 5 桁か検証する）
+static DIGITS_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"\d+").expect("Invalid DIGITS_RE"));
+
+/// プレミアムバンダイ おまとめ完了メール用パーサー（元注文の統合用）
+pub struct PremiumBandaiMergeCompleteParser;
+
+impl PremiumBandaiMergeCompleteParser {
+    /// メール本文からおまとめ完了情報を抽出する
+    pub fn parse_consolidation(&self, email_body: &str) -> Result<ConsolidationInfo, String> {
+        let body_lines = body_to_lines(email_body);
+        let lines: Vec<&str> = body_lines.iter().map(|s| s.as_str()).collect();
+
+        let old_numbers = extract_old_order_numbers(&lines);
+        if old_numbers.is_empty() {
+            return Err("No old order numbers found".to_string());
+        }
+        let new_number = extract_order_number(&lines)
+            .ok_or_else(|| "New order number (ご注文No.) not found".to_string())?;
+
+        Ok(ConsolidationInfo {
+            old_order_numbers: old_numbers,
+            new_order_number: new_number,
+        })
+    }
+}
+
+/// 本文におまとめ前の注文番号ブロックがあるかどうか
+pub fn has_old_order_numbers(email_body: &str) -> bool {
+    let body_lines = body_to_lines(email_body);
+    let lines: Vec<&str> = body_lines.iter().map(|s| s.as_str()).collect();
+    !extract_old_order_numbers(&lines).is_empty()
+}
+
+/// `【おまとめ前のご注文No.】` 以降の 5 桁の注文番号を抽出する。
+///
+/// 番号は見出しと同じ行（`00125、00127`）にも、続く行（`1: 00125`）にも書かれうる。
+/// 次の `【…】` 見出しか区切り線でブロックを終える。重複は除去し出現順を保つ。
+fn extract_old_order_numbers(lines: &[&str]) -> Vec<String> {
+    let mut numbers = Vec::new();
+    let mut seen = HashSet::new();
+    let mut in_block = false;
+
+    for line in lines {
+        let trimmed = line.trim();
+        let text = if let Some(m) = OLD_ORDER_LABEL_RE.find(trimmed) {
+            in_block = true;
+            &trimmed[m.end()..]
+        } else if !in_block {
+            continue;
+        } else if trimmed.is_empty() {
+            // HTML テーブル形式ではセルの間に空行が入る
+            continue;
+        } else if trimmed.starts_with('【')
+            || trimmed.starts_with("---")
+            || trimmed.starts_with("－－")
+        {
+            break;
+        } else {
+            trimmed
+        };
+
+        for m in DIGITS_RE.find_iter(text) {
+            let num = m.as_str();
+            if num.len() == 5 && seen.insert(num.to_string()) {
+                numbers.push(num.to_string());
+            }
+        }
+    }
+
+    numbers
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// おまとめ前の注文番号が記載されたおまとめ完了メール
+    fn sample_merge_complete() -> &'static str {
+        "＜！このメールには返信できません。ご注意ください！＞\n\
+         このたびは「プレミアムバンダイ」をご利用いただき、まことにありがとうございます。\n\
+         以下、お客様のご注文おまとめ内容の確認をお願い申しあげます。\n\
+         ---------------------------------------------------------------------\n\
+         【おまとめ前のご注文No.】\n\
+         1: 00125\n\
+         2: 00127\n\
+         3: 00125\n\
+         【ご注文No.】\u{3000} 00130\n\
+         【ご注文日】\u{3000}\u{3000}2025-05-14 12:04:59\n\
+         【ご注文明細】\n\
+         ＨＧ 1/144 ジーライン・ライトアーマー【３次：２０２５年８月発送】\n\
+         3,630円×1＝3,630円\n\
+         ＨＧ 1/144 ガンダムプルトーネブラック【２次：２０２５年８月発送】\n\
+         2,640円×1＝2,640円\n\
+         【お買上金額】\n\
+         商品金額：\u{3000}6,270円\n\
+         お支払い合計金額：\u{3000}6,930円\n"
+    }
+
+    #[test]
+    fn test_parse_merge_complete() {
+        let info = PremiumBandaiMergeCompleteParser
+            .parse_consolidation(sample_merge_complete())
+            .unwrap();
+        assert_eq!(info.new_order_number, "00130");
+        // 重複除去後は 2 件、出現順を保つ
+        assert_eq!(info.old_order_numbers, vec!["00125", "00127"]);
+    }
+
+    #[test]
+    fn test_parse_merge_complete_same_line_numbers() {
+        let body = "【おまとめ前のご注文No.】\u{3000}00125、00127\n【ご注文No.】\u{3000}00130\n";
+        let info = PremiumBandaiMergeCompleteParser
+            .parse_consolidation(body)
+            .unwrap();
+        assert_eq!(info.new_order_number, "00130");
+        assert_eq!(info.old_order_numbers, vec!["00125", "00127"]);
+    }
+
+    #[test]
+    fn test_parse_merge_complete_without_old_numbers_returns_error() {
+        // おまとめ前の注文番号がない従来形式は premium_bandai_omatome が処理する
+        let body = "【ご注文No.】\u{3000}00130\n【ご注文明細】\n商品A\n3,630円×1＝3,630円\n";
+        assert!(PremiumBandaiMergeCompleteParser
+            .parse_consolidation(body)
+            .is_err());
+        assert!(!has_old_order_numbers(body));
+        assert!(has_old_order_numbers(sample_merge_complete()));
+    }
+
+    #[test]
+    fn test_parse_merge_complete_without_new_number_returns_error() {
+        let body = "【おまとめ前のご注文No.】\n00125\n00127\n";
+        assert!(PremiumBandaiMergeCompleteParser
+            .parse_consolidation(body)
+            .is_err());
+    }
+}
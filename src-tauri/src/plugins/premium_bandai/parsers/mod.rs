@@ -4,6 +4,7 @@ use once_cell::sync::Lazy;
 use regex::Regex;
 
 pub mod confirm;
+pub mod merge_complete;
 pub mod omatome;
 pub mod send;
 
@@ -898,6 +899,8 @@ mod tests {
             image_url: None,
             manufacturer: None,
             model_number: None,
+            item_url: None,
+            release_date: None,
         }
     }
 
@@ -8,6 +8,10 @@
 //! 結果は 1 つの `OrderInfo` に全商品をまとめて返す。
 //!
 //! ディスパッチ時に `apply_change_items_in_tx` で元注文の商品を商品名マッチングで削除する。
+//!
+//! 本文に新しい注文番号しか記載されず、まとめる前の注文番号が分からないメールが対象。
+//! おまとめ前の注文番号が記載されたメールは `pbandai_merge_complete`
+//! （`ConsolidationInfo` / `apply_consolidation_in_tx` で元注文を統合する）が処理する。
 
 use super::{
     body_to_lines, dedup_items, extract_order_date, extract_order_number, extract_payment_fee,
@@ -73,6 +77,8 @@ impl EmailParser for PremiumBandaiOmatomeParser {
             subtotal,
             shipping_fee: combined_fee,
             total_amount,
+            tax_amount: None,
+            tax_included: true,
         })
     }
 }
@@ -148,6 +154,8 @@ fn extract_omatome_items(lines: &[&str]) -> Vec<OrderItem> {
                     quantity,
                     subtotal,
                     image_url: None,
+                    item_url: None,
+                    release_date: None,
                 });
             }
             continue;
@@ -167,6 +175,8 @@ fn extract_omatome_items(lines: &[&str]) -> Vec<OrderItem> {
                     quantity,
                     subtotal,
                     image_url: None,
+                    item_url: None,
+                    release_date: None,
                 });
             }
             continue;
@@ -196,6 +206,8 @@ fn extract_omatome_items(lines: &[&str]) -> Vec<OrderItem> {
                     quantity: 1,
                     subtotal: unit_price,
                     image_url: None,
+                    item_url: None,
+                    release_date: None,
                 });
             }
             continue;
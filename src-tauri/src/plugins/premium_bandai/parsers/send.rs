@@ -110,6 +110,8 @@ impl EmailParser for PremiumBandaiSendParser {
             subtotal: None,
             shipping_fee: None,
             total_amount: None,
+            tax_amount: None,
+            tax_included: true,
         })
     }
 }
@@ -154,6 +156,8 @@ fn extract_send_items(lines: &[&str]) -> Vec<OrderItem> {
                     quantity: 1,
                     subtotal: 0,
                     image_url: None,
+                    item_url: None,
+                    release_date: None,
                 });
             }
             break;
@@ -182,6 +186,8 @@ fn extract_send_items(lines: &[&str]) -> Vec<OrderItem> {
                     quantity: qty,
                     subtotal: 0,
                     image_url: None,
+                    item_url: None,
+                    release_date: None,
                 });
             }
             continue;
@@ -208,6 +214,8 @@ fn extract_send_items(lines: &[&str]) -> Vec<OrderItem> {
                 quantity: 1,
                 subtotal: 0,
                 image_url: None,
+                item_url: None,
+                release_date: None,
             });
         }
 
@@ -227,6 +235,8 @@ fn extract_send_items(lines: &[&str]) -> Vec<OrderItem> {
             quantity: 1,
             subtotal: 0,
             image_url: None,
+            item_url: None,
+            release_date: None,
         });
     }
 
@@ -289,6 +299,8 @@ fn extract_items_from_send_html(html: &str) -> Vec<OrderItem> {
             quantity,
             subtotal: 0,
             image_url,
+            item_url: None,
+            release_date: None,
         });
     }
 
@@ -0,0 +1,120 @@
+//! 楽天市場 プラグイン
+//!
+//! 楽天市場の店舗から配信される「商品発送のお知らせ」メールをパースする。
+//! 送信元アドレスは店舗ごとに異なるため、既定では楽天市場の自動配信アドレスのみ登録し、
+//! 他の店舗は設定画面で送信元アドレスを追加して `rakuten_send` を割り当てる。
+//!
+//! あみあみ楽天市場店は専用パーサー（`amiami_rakuten_*`）で処理する。
+
+pub mod parsers;
+
+use async_trait::async_trait;
+
+use crate::parsers::EmailParser;
+use crate::repository::SqliteOrderRepository;
+
+use super::{
+    derive_shop_domain, DefaultShopSetting, DispatchError, DispatchOutcome, PluginRegistration,
+    VendorPlugin,
+};
+
+pub struct RakutenPlugin;
+
+#[async_trait]
+impl VendorPlugin for RakutenPlugin {
+    fn parser_types(&self) -> &[&str] {
+        &["rakuten_send"]
+    }
+
+    fn priority(&self) -> i32 {
+        10
+    }
+
+    fn get_parser(&self, parser_type: &str) -> Option<Box<dyn EmailParser>> {
+        match parser_type {
+            "rakuten_send" => Some(Box::new(parsers::send::RakutenSendParser)),
+            _ => None,
+        }
+    }
+
+    fn shop_name(&self) -> &str {
+        "楽天市場"
+    }
+
+    fn default_shop_settings(&self) -> Vec<DefaultShopSetting> {
+        vec![DefaultShopSetting {
+            shop_name: "楽天市場".to_string(),
+            sender_address: "order@rakuten.co.jp".to_string(),
+            parser_type: "rakuten_send".to_string(),
+            subject_filters: Some(vec![
+                "商品発送のお知らせ".to_string(),
+                "発送完了のお知らせ".to_string(),
+            ]),
+        }]
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    async fn dispatch(
+        &self,
+        parser_type: &str,
+        email_id: i64,
+        from_address: Option<&str>,
+        shop_name: &str,
+        _internal_date: Option<i64>,
+        body: &str,
+        tx: &mut sqlx::Transaction<'_, sqlx::Sqlite>,
+    ) -> Result<DispatchOutcome, DispatchError> {
+        let shop_domain = derive_shop_domain(from_address);
+
+        let order_info = {
+            let parser = self.get_parser(parser_type).ok_or_else(|| {
+                DispatchError::ParseFailed(format!("No parser for type: {}", parser_type))
+            })?;
+            parser.parse(body).map_err(DispatchError::ParseFailed)?
+        };
+
+        log::debug!(
+            "[{}] email_id={} order_number={}",
+            parser_type,
+            email_id,
+            order_info.order_number
+        );
+
+        SqliteOrderRepository::save_order_in_tx(
+            tx,
+            &order_info,
+            Some(email_id),
+            shop_domain,
+            Some(shop_name.to_string()),
+        )
+        .await
+        .map_err(DispatchError::SaveFailed)?;
+
+        Ok(DispatchOutcome::OrderSaved(Box::new(order_info)))
+    }
+}
+
+inventory::submit!(PluginRegistration {
+    factory: || Box::new(RakutenPlugin),
+});
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parser_types() {
+        let plugin = RakutenPlugin;
+        assert_eq!(plugin.parser_types(), &["rakuten_send"]);
+        assert!(plugin.get_parser("rakuten_send").is_some());
+        assert!(plugin.get_parser("amiami_rakuten_send").is_none());
+    }
+
+    #[test]
+    fn test_default_shop_settings() {
+        let settings = RakutenPlugin.default_shop_settings();
+        assert_eq!(settings.len(), 1);
+        assert_eq!(settings[0].parser_type, "rakuten_send");
+        assert_eq!(settings[0].sender_address, "order@rakuten.co.jp");
+    }
+}
@@ -0,0 +1,248 @@
+//! 楽天市場 商品発送のお知らせメール用パーサー
+//!
+//! 件名：`【楽天市場】商品発送のお知らせ`
+//!
+//! 楽天市場の発送通知は店舗ごとに文面が異なるが、受注番号・配送会社・お問い合わせ番号は
+//! `[受注番号] 123456-20240101-0000000001` / `配送会社：ヤマト運輸` のようなラベル行で記載される。
+//!
+//! ```text
+//! [受注番号] 123456-20240101-0000000001
+//! [配送会社] クロネコヤマト
+//! [お問い合わせ番号] 1234-5678-9012
+//!
+//! [商品]
+//! HG 1/144 ガンダムエアリアル
+//! 価格 1,650(円) x 1(個) = 1,650(円)
+//! ```
+//!
+//! 配送会社の表記ゆれ（クロネコヤマト / 宅急便 / 飛脚宅配便 / ゆうパック 等）は正式名称に揃える。
+//! 配送会社の記載がない場合は本文中の追跡 URL から判定する。
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+use crate::parsers::{DeliveryInfo, EmailParser, Label, OrderInfo, OrderItem};
+use crate::plugins::{canonical_carrier_name, tracking_url_for_carrier};
+
+/// 楽天市場 発送通知メールパーサー
+pub struct RakutenSendParser;
+
+/// `[受注番号] 123456-20240101-0000000001` / `注文番号：123456-20240101-0000000001`
+static ORDER_NUMBER_LABEL: Lazy<Label> = Lazy::new(|| {
+    Label::new("受注番号")
+        .alias("注文番号")
+        .value(r"\d+-\d+-\d+")
+        .build()
+});
+
+/// `[配送会社] ヤマト運輸` / `配送業者：佐川急便`
+static CARRIER_LABEL: Lazy<Label> = Lazy::new(|| {
+    Label::new("配送会社")
+        .alias("配送業者")
+        .alias("運送会社")
+        .build()
+});
+
+/// `[お問い合わせ番号] 1234-5678-9012` / `伝票番号：123456789012`
+static TRACKING_LABEL: Lazy<Label> = Lazy::new(|| {
+    Label::new("お問い合わせ番号")
+        .alias("お問合せ番号")
+        .alias("お問い合せ番号")
+        .alias("伝票番号")
+        .alias("送り状番号")
+        .alias("追跡番号")
+        .value(r"[0-9][0-9-]*[0-9]")
+        .build()
+});
+
+/// `価格 1,650(円) x 1(個) = 1,650(円)`
+static PRICE_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"価格\s*([\d,]+)\s*\(円\)\s*[x×]\s*(\d+)\s*\(個\)\s*=\s*([\d,]+)\s*\(円\)")
+        .expect("PRICE_RE")
+});
+
+impl EmailParser for RakutenSendParser {
+    fn parse(&self, email_body: &str) -> Result<OrderInfo, String> {
+        let order_number = ORDER_NUMBER_LABEL
+            .find(email_body)
+            .ok_or_else(|| "Order number not found".to_string())?;
+
+        // 追跡 API・URL に合わせてハイフンは除去する
+        let tracking_number = TRACKING_LABEL
+            .find(email_body)
+            .map(|n| n.replace('-', ""))
+            .ok_or_else(|| "Tracking number not found".to_string())?;
+
+        let carrier = extract_carrier(email_body).ok_or_else(|| "Carrier not found".to_string())?;
+        let carrier_url = tracking_url_for_carrier(&carrier);
+
+        Ok(OrderInfo {
+            order_number,
+            order_date: None,
+            delivery_address: None,
+            delivery_info: Some(DeliveryInfo {
+                carrier,
+                tracking_number,
+                delivery_date: None,
+                delivery_time: None,
+                carrier_url,
+                delivery_status: None,
+            }),
+            // 商品の記載がない店舗もあるため、空の場合は confirm 側で登録済みの商品を保持する
+            items: extract_items(email_body),
+            subtotal: None,
+            shipping_fee: None,
+            total_amount: None,
+            tax_amount: None,
+            tax_included: true,
+        })
+    }
+}
+
+/// 配送会社を抽出する（ラベル行 → 本文中の追跡 URL の順に判定）
+///
+/// ラベルの値が既知の配送業者でない場合（西濃運輸など）は記載どおりの名称を返す。
+fn extract_carrier(body: &str) -> Option<String> {
+    if let Some(raw) = CARRIER_LABEL.find(body) {
+        return Some(
+            canonical_carrier_name(&raw)
+                .map(str::to_string)
+                .unwrap_or(raw),
+        );
+    }
+    body.lines()
+        .filter(|line| line.contains("http"))
+        .find_map(canonical_carrier_name)
+        .map(str::to_string)
+}
+
+/// 価格行とその直前の商品名行から商品を抽出する
+fn extract_items(body: &str) -> Vec<OrderItem> {
+    let mut items = Vec::new();
+    let mut last_name: Option<&str> = None;
+
+    for line in body.lines().map(str::trim) {
+        if let Some(caps) = PRICE_RE.captures(line) {
+            let Some(name) = last_name.take() else {
+                continue;
+            };
+            let parse_amount = |s: &str| s.replace(',', "").parse::<i64>().unwrap_or(0);
+            items.push(OrderItem {
+                name: name.to_string(),
+                manufacturer: None,
+                model_number: None,
+                unit_price: parse_amount(&caps[1]),
+                quantity: caps[2].parse::<i64>().unwrap_or(1),
+                subtotal: parse_amount(&caps[3]),
+                image_url: None,
+                item_url: None,
+                release_date: None,
+            });
+        } else if line.is_empty() || line.starts_with('[') || line.starts_with('-') {
+            last_name = None;
+        } else {
+            last_name = Some(line);
+        }
+    }
+
+    items
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE_SEND: &str = r#"テスト 太郎 様
+
+この度は楽天市場店をご利用いただき、誠にありがとうございます。
+ご注文の商品を本日発送いたしました。
+
+[受注番号] 123456-20240101-0000000001
+[配送会社] クロネコヤマト
+[お問い合わせ番号] 1234-5678-9012
+
+---------------------------------------------------------------------
+[商品]
+HG 1/144 ガンダムエアリアル
+価格 1,650(円) x 2(個) = 3,300(円)
+ニッパー
+価格 800(円) x 1(個) = 800(円)
+---------------------------------------------------------------------
+"#;
+
+    #[test]
+    fn test_parse_rakuten_send() {
+        let order = RakutenSendParser.parse(SAMPLE_SEND).unwrap();
+        assert_eq!(order.order_number, "123456-20240101-0000000001");
+
+        let delivery = order.delivery_info.unwrap();
+        assert_eq!(delivery.carrier, "ヤマト運輸");
+        assert_eq!(delivery.tracking_number, "123456789012");
+        assert!(delivery.carrier_url.is_some());
+
+        assert_eq!(order.items.len(), 2);
+        assert_eq!(order.items[0].name, "HG 1/144 ガンダムエアリアル");
+        assert_eq!(order.items[0].unit_price, 1650);
+        assert_eq!(order.items[0].quantity, 2);
+        assert_eq!(order.items[0].subtotal, 3300);
+        assert_eq!(order.items[1].name, "ニッパー");
+        assert!(order.total_amount.is_none());
+    }
+
+    #[test]
+    fn test_parse_colon_labels_and_carrier_variants() {
+        let body =
+            "注文番号：123456-20240101-0000000002\n配送業者：飛脚宅配便\n伝票番号：123456789012\n";
+        let order = RakutenSendParser.parse(body).unwrap();
+        let delivery = order.delivery_info.unwrap();
+        assert_eq!(delivery.carrier, "佐川急便");
+        assert_eq!(delivery.tracking_number, "123456789012");
+        // 商品の記載がなければ空
+        assert!(order.items.is_empty());
+
+        let body = "[受注番号] 123456-20240101-0000000003\n[配送会社] ゆうパック\n[お問い合わせ番号] 1234-5678-9012\n";
+        let delivery = RakutenSendParser
+            .parse(body)
+            .unwrap()
+            .delivery_info
+            .unwrap();
+        assert_eq!(delivery.carrier, "日本郵便");
+    }
+
+    #[test]
+    fn test_parse_carrier_from_tracking_url() {
+        let body = "[受注番号] 123456-20240101-0000000004\n荷物お問合せ番号：397404561713\nhttp://k2k.sagawa-exp.co.jp/\n";
+        let delivery = RakutenSendParser
+            .parse(body)
+            .unwrap()
+            .delivery_info
+            .unwrap();
+        assert_eq!(delivery.carrier, "佐川急便");
+        assert_eq!(delivery.tracking_number, "397404561713");
+    }
+
+    #[test]
+    fn test_parse_unknown_carrier_keeps_label() {
+        let body = "[受注番号] 123456-20240101-0000000005\n[配送会社] 西濃運輸\n[お問い合わせ番号] 1234567890\n";
+        let delivery = RakutenSendParser
+            .parse(body)
+            .unwrap()
+            .delivery_info
+            .unwrap();
+        assert_eq!(delivery.carrier, "西濃運輸");
+        assert!(delivery.carrier_url.is_none());
+    }
+
+    #[test]
+    fn test_parse_missing_fields_is_error() {
+        assert!(RakutenSendParser
+            .parse("[配送会社] ヤマト運輸\n[お問い合わせ番号] 123456789012\n")
+            .is_err());
+        assert!(RakutenSendParser
+            .parse("[受注番号] 123456-20240101-0000000001\n[配送会社] ヤマト運輸\n")
+            .is_err());
+        assert!(RakutenSendParser
+            .parse("[受注番号] 123456-20240101-0000000001\n[お問い合わせ番号] 123456789012\n")
+            .is_err());
+    }
+}
@@ -1,12 +1,13 @@
 //! 駿河屋プラグイン
 //!
-//! 駿河屋の注文確認メール・発送案内メールのパース対応。
+//! 駿河屋の注文確認メール・発送案内メール・キャンセル（在庫切れ）通知のパース対応。
 //! 文字コードは ISO-2022-JP だが、Gmail API 同期時に UTF-8 にデコード済みであることを前提とする。
 //!
 //! | parser_type       | 送信元               | 種別       |
 //! |-------------------|----------------------|------------|
 //! | surugaya_confirm  | order@suruga-ya.jp   | 注文確認   |
 //! | surugaya_send     | order@suruga-ya.jp   | 発送案内   |
+//! | surugaya_cancel   | order@suruga-ya.jp   | キャンセル |
 
 pub mod parsers;
 
@@ -16,8 +17,8 @@ use crate::parsers::EmailParser;
 use crate::repository::SqliteOrderRepository;
 
 use super::{
-    apply_internal_date, derive_shop_domain, DefaultShopSetting, DispatchError, DispatchOutcome,
-    PluginRegistration, VendorPlugin,
+    apply_internal_date, apply_tax_info, derive_shop_domain, DefaultShopSetting, DispatchError,
+    DispatchOutcome, PluginRegistration, VendorPlugin,
 };
 
 pub struct SurugayaPlugin;
@@ -25,7 +26,7 @@ pub struct SurugayaPlugin;
 #[async_trait]
 impl VendorPlugin for SurugayaPlugin {
     fn parser_types(&self) -> &[&str] {
-        &["surugaya_confirm", "surugaya_send"]
+        &["surugaya_confirm", "surugaya_send", "surugaya_cancel"]
     }
 
     fn priority(&self) -> i32 {
@@ -36,6 +37,7 @@ impl VendorPlugin for SurugayaPlugin {
         match parser_type {
             "surugaya_confirm" => Some(Box::new(parsers::confirm::SurugayaConfirmParser)),
             "surugaya_send" => Some(Box::new(parsers::send::SurugayaSendParser)),
+            // cancel は dispatch() 内で直接処理するため get_parser は None を返す
             _ => None,
         }
     }
@@ -58,6 +60,12 @@ impl VendorPlugin for SurugayaPlugin {
                 parser_type: "surugaya_send".to_string(),
                 subject_filters: Some(vec!["発送のお知らせ".to_string()]),
             },
+            DefaultShopSetting {
+                shop_name: "駿河屋".to_string(),
+                sender_address: "order@suruga-ya.jp".to_string(),
+                parser_type: "surugaya_cancel".to_string(),
+                subject_filters: Some(vec!["キャンセル".to_string()]),
+            },
         ]
     }
 
@@ -74,6 +82,35 @@ impl VendorPlugin for SurugayaPlugin {
     ) -> Result<DispatchOutcome, DispatchError> {
         let shop_domain = derive_shop_domain(from_address);
 
+        // 在庫を確保できなかった商品だけの部分キャンセル
+        if parser_type == "surugaya_cancel" {
+            let cancels = parsers::cancel::SurugayaCancelParser
+                .parse_cancel(body)
+                .map_err(DispatchError::ParseFailed)?;
+            let order_number = cancels[0].order_number.clone();
+
+            log::debug!(
+                "[surugaya_cancel] email_id={} order_number={} items={}",
+                email_id,
+                order_number,
+                cancels.len()
+            );
+
+            for cancel_info in &cancels {
+                SqliteOrderRepository::apply_cancel_in_tx(
+                    tx,
+                    cancel_info,
+                    email_id,
+                    shop_domain.clone(),
+                    None,
+                )
+                .await
+                .map_err(DispatchError::SaveFailed)?;
+            }
+
+            return Ok(DispatchOutcome::CancelApplied { order_number });
+        }
+
         let mut order_info = {
             let parser = self.get_parser(parser_type).ok_or_else(|| {
                 DispatchError::ParseFailed(format!("No parser for type: {}", parser_type))
@@ -84,6 +121,7 @@ impl VendorPlugin for SurugayaPlugin {
         // 注文確認メールは注文日が本文に含まれないため internal_date で補完する
         if parser_type == "surugaya_confirm" {
             apply_internal_date(&mut order_info, internal_date);
+            apply_tax_info(&mut order_info, body);
         }
 
         log::debug!(
@@ -120,6 +158,13 @@ mod tests {
         let plugin = SurugayaPlugin;
         assert!(plugin.parser_types().contains(&"surugaya_confirm"));
         assert!(plugin.parser_types().contains(&"surugaya_send"));
+        assert!(plugin.parser_types().contains(&"surugaya_cancel"));
+    }
+
+    #[test]
+    fn test_surugaya_plugin_get_parser_cancel_returns_none() {
+        // cancel は dispatch() 内で直接処理するため get_parser は None を返す
+        assert!(SurugayaPlugin.get_parser("surugaya_cancel").is_none());
     }
 
     #[test]
@@ -143,7 +188,7 @@ mod tests {
     #[test]
     fn test_surugaya_plugin_default_shop_settings() {
         let settings = SurugayaPlugin.default_shop_settings();
-        assert_eq!(settings.len(), 2);
+        assert_eq!(settings.len(), 3);
 
         let confirm = settings
             .iter()
@@ -164,5 +209,12 @@ mod tests {
             send.subject_filters.as_deref(),
             Some(["発送のお知らせ".to_string()].as_slice())
         );
+        // 件名「商品発送のお知らせ」も部分一致で対象になる
+        assert!(send
+            .subject_filters
+            .as_ref()
+            .unwrap()
+            .iter()
+            .any(|f| "【駿河屋】商品発送のお知らせ".contains(f.as_str())));
     }
 }
@@ -0,0 +1,131 @@
+use super::{body_to_lines, extract_items, extract_order_number};
+use crate::parsers::cancel_info::{classify_cancel_reason, CancelInfo};
+
+/// 駿河屋 キャンセル（在庫切れ）通知メール用パーサー
+///
+/// 件名：`キャンセル` を含む
+/// 送信元：`order@suruga-ya.jp`
+///
+/// 在庫を確保できなかった商品だけが部分キャンセルされる。商品行は注文確認メールと同じ
+/// `1-3 \1,288 商品名 (603101318001)` 形式で、1 行が数量 1 に相当する（同名商品の行は数量を合算する）。
+/// 商品行がない場合は注文全体を消さないよう ParseFailed とする。
+pub struct SurugayaCancelParser;
+
+/// 本文からキャンセル理由が読み取れない場合に記録する理由
+pub const DEFAULT_SURUGAYA_CANCEL_REASON: &str = "在庫を確保できなかったため";
+
+impl SurugayaCancelParser {
+    /// メール本文から商品ごとのキャンセル情報を抽出する
+    pub fn parse_cancel(&self, email_body: &str) -> Result<Vec<CancelInfo>, String> {
+        let body_lines = body_to_lines(email_body);
+        let lines: Vec<&str> = body_lines.iter().map(|s| s.as_str()).collect();
+
+        let order_number =
+            extract_order_number(&lines).ok_or_else(|| "Order number not found".to_string())?;
+
+        // 「〜のため、キャンセルとさせていただきました」の理由文を優先し、なければ在庫切れとみなす
+        let reason_detail = lines
+            .iter()
+            .find(|line| line.contains("キャンセル") && line.contains("ため"))
+            .and_then(|line| line.split("ため").next())
+            .map(|s| format!("{}ため", s.trim()))
+            .filter(|s| s != "ため")
+            .unwrap_or_else(|| DEFAULT_SURUGAYA_CANCEL_REASON.to_string());
+        let reason = classify_cancel_reason(&reason_detail);
+
+        let mut infos: Vec<CancelInfo> = Vec::new();
+        for item in extract_items(&lines) {
+            if let Some(existing) = infos.iter_mut().find(|i| i.product_name == item.name) {
+                existing.cancel_quantity += item.quantity;
+                continue;
+            }
+            infos.push(CancelInfo {
+                order_number: order_number.clone(),
+                product_name: item.name,
+                cancel_quantity: item.quantity,
+                reason: Some(reason),
+                reason_detail: Some(reason_detail.clone()),
+            });
+        }
+
+        if infos.is_empty() {
+            return Err("No cancelled items found".to_string());
+        }
+        Ok(infos)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parsers::cancel_info::CancelReason;
+
+    fn sample_cancel() -> &'static str {
+        r#"山田太郎様 （取引番号：S2204166697）
+
+「駿河屋」をご利用いただき、誠にありがとうございます。
+
+大変申し訳ございませんが、ご注文いただいた商品のうち下記の商品は
+在庫を確保することができなかったため、キャンセルとさせていただきました。
+
+──────────────────────────────────
+1-3 \644 中古プラモデル 1/144 HGBC ボールデンアームアームズ 「ガンダムビルドファイターズトライ」 (603102455001)
+1-8 \1,288 中古プラモデル オーガンダム [5055732] (603101318001)
+1-9 \1,288 中古プラモデル オーガンダム [5055732] (603101318001)
+──────────────────────────────────
+
+その他の商品につきましては、引き続き発送の準備を進めさせていただきます。
+"#
+    }
+
+    #[test]
+    fn test_parse_cancel_items_and_quantities() {
+        let infos = SurugayaCancelParser.parse_cancel(sample_cancel()).unwrap();
+        assert_eq!(infos.len(), 2);
+        assert!(infos.iter().all(|i| i.order_number == "S2204166697"));
+        assert_eq!(
+            infos[0].product_name,
+            "中古プラモデル 1/144 HGBC ボールデンアームアームズ 「ガンダムビルドファイターズトライ」"
+        );
+        assert_eq!(infos[0].cancel_quantity, 1);
+        // 同名商品の行は数量を合算する
+        assert_eq!(infos[1].product_name, "中古プラモデル オーガンダム");
+        assert_eq!(infos[1].cancel_quantity, 2);
+    }
+
+    #[test]
+    fn test_parse_cancel_reason_out_of_stock() {
+        let infos = SurugayaCancelParser.parse_cancel(sample_cancel()).unwrap();
+        assert!(infos
+            .iter()
+            .all(|i| i.reason == Some(CancelReason::OutOfStock)));
+        assert_eq!(
+            infos[0].reason_detail.as_deref(),
+            Some("在庫を確保することができなかったため")
+        );
+    }
+
+    #[test]
+    fn test_parse_cancel_without_reason_uses_default() {
+        let body = "取引番号：S2204166697\n1-1 \\500 商品A (600000000001)\n";
+        let infos = SurugayaCancelParser.parse_cancel(body).unwrap();
+        assert_eq!(
+            infos[0].reason_detail.as_deref(),
+            Some(DEFAULT_SURUGAYA_CANCEL_REASON)
+        );
+        assert_eq!(infos[0].reason, Some(CancelReason::OutOfStock));
+    }
+
+    #[test]
+    fn test_parse_cancel_without_items_is_error() {
+        // 商品行がなければ注文全体を消さないようエラーにする
+        let body = "取引番号：S2204166697\nキャンセルとさせていただきました。";
+        assert!(SurugayaCancelParser.parse_cancel(body).is_err());
+    }
+
+    #[test]
+    fn test_parse_cancel_without_order_number_is_error() {
+        let body = "1-1 \\500 商品A (600000000001)";
+        assert!(SurugayaCancelParser.parse_cancel(body).is_err());
+    }
+}
@@ -33,6 +33,8 @@ impl EmailParser for SurugayaConfirmParser {
             subtotal: None,
             shipping_fee: None,
             total_amount: None,
+            tax_amount: None,
+            tax_included: true,
         })
     }
 }
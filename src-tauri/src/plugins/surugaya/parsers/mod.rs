@@ -2,6 +2,7 @@ use crate::parsers::OrderItem;
 use once_cell::sync::Lazy;
 use regex::Regex;
 
+pub mod cancel;
 pub mod confirm;
 pub mod send;
 
@@ -112,6 +113,8 @@ pub fn extract_items(lines: &[&str]) -> Vec<OrderItem> {
             quantity: 1,
             subtotal: unit_price,
             image_url: None,
+            item_url: None,
+            release_date: None,
         });
     }
 
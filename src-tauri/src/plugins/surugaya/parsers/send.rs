@@ -3,11 +3,11 @@ use super::{
     extract_subtotal, extract_total_amount, extract_tracking_number,
 };
 use crate::parsers::{DeliveryInfo, EmailParser, OrderInfo};
-use crate::plugins::JAPANPOST_TRACKING_URL;
+use crate::plugins::tracking_url_for_carrier;
 
 /// 駿河屋 発送案内メール用パーサー
 ///
-/// 件名：`発送のお知らせ` を含む
+/// 件名：`発送のお知らせ` を含む（`商品発送のお知らせ` 等）
 /// 送信元：`order@suruga-ya.jp`
 ///
 /// 取引番号は `（取引番号：S2204166697）` 形式。
@@ -16,6 +16,7 @@ use crate::plugins::JAPANPOST_TRACKING_URL;
 ///
 /// # 追跡番号について
 /// ゆうパック等は `お問い合わせ番号：764336939516` として12桁の番号が記載される。
+/// 追跡URLは配送会社（日本郵便・ヤマト運輸・佐川急便）に応じて切り替える。
 /// ゆうメール等の追跡不可配送は追跡番号フィールドが存在しない。
 /// この場合、発送通知メールの受信をもって配達完了とみなし、`delivery_status = "delivered"` を設定する。
 pub struct SurugayaSendParser;
@@ -41,11 +42,11 @@ impl EmailParser for SurugayaSendParser {
             Some(tracking) => {
                 // ゆうパック等: 追跡番号あり → "shipped" (デフォルト) で登録、以降追跡で更新
                 carrier.map(|c| DeliveryInfo {
+                    carrier_url: tracking_url_for_carrier(&c),
                     carrier: c,
                     tracking_number: tracking,
                     delivery_date,
                     delivery_time: None,
-                    carrier_url: Some(JAPANPOST_TRACKING_URL.to_string()),
                     delivery_status: None,
                 })
             }
@@ -71,6 +72,8 @@ impl EmailParser for SurugayaSendParser {
             subtotal,
             shipping_fee,
             total_amount,
+            tax_amount: None,
+            tax_included: true,
         })
     }
 }
@@ -152,7 +155,23 @@ http://tracking.post.japanpost.jp/services/srv/search/
     fn test_parse_send_carrier_url() {
         let order = SurugayaSendParser.parse(sample_send()).unwrap();
         let url = order.delivery_info.as_ref().unwrap().carrier_url.as_deref();
-        assert_eq!(url, Some(JAPANPOST_TRACKING_URL));
+        assert_eq!(url, Some(crate::plugins::JAPANPOST_TRACKING_URL));
+        // 追跡番号ありは "shipped"（delivery_status 未指定）で登録される
+        assert_eq!(order.delivery_info.as_ref().unwrap().delivery_status, None);
+    }
+
+    #[test]
+    fn test_parse_send_yamato_carrier_url() {
+        let body = "（取引番号：S2204166697）\nお届け方法　　　　　：宅急便（ヤマト運輸）\nお問い合わせ番号　　：123456789012";
+        let order = SurugayaSendParser.parse(body).unwrap();
+        let di = order.delivery_info.as_ref().unwrap();
+        assert_eq!(di.carrier, "宅急便（ヤマト運輸）");
+        assert_eq!(di.tracking_number, "123456789012");
+        assert!(di
+            .carrier_url
+            .as_deref()
+            .is_some_and(|u| u.contains("kuronekoyamato")));
+        assert_eq!(di.delivery_status, None);
     }
 
     #[test]
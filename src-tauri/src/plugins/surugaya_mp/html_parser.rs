@@ -60,6 +60,8 @@ pub fn parse_mypage_html(html: &str) -> Result<MypageOrderInfo, String> {
             subtotal,
             shipping_fee,
             total_amount,
+            tax_amount: None,
+            tax_included: true,
         },
     })
 }
@@ -139,7 +141,7 @@ fn build_delivery_info(
     delivery_date: Option<String>,
 ) -> Option<DeliveryInfo> {
     let carrier = carrier?;
-    let carrier_url = tracking_url_for_carrier(&carrier);
+    let carrier_url = crate::plugins::tracking_url_for_carrier(&carrier);
 
     match tracking_number {
         Some(tracking) if !tracking.is_empty() => Some(DeliveryInfo {
@@ -161,23 +163,6 @@ fn build_delivery_info(
     }
 }
 
-/// 配送会社名から追跡URLを返す
-fn tracking_url_for_carrier(carrier: &str) -> Option<String> {
-    if carrier.contains("ヤマト") || carrier.contains("クロネコ") {
-        // ヤマト運輸は追跡番号を直接URLに含める形式
-        Some("https://jizen.kuronekoyamato.co.jp/jizen/servlet/crjz.b.NQ0010".to_string())
-    } else if carrier.contains("日本郵便")
-        || carrier.contains("ゆうパック")
-        || carrier.contains("ゆうパケット")
-    {
-        Some(crate::plugins::JAPANPOST_TRACKING_URL.to_string())
-    } else if carrier.contains("佐川") {
-        Some("https://k2k.sagawa-exp.co.jp/p/web/okurijosearch.do".to_string())
-    } else {
-        None
-    }
-}
-
 /// 商品テーブル (`mgnT15 paddTbl`) から商品行を抽出する
 ///
 /// テーブルの構造:
@@ -238,6 +223,8 @@ fn extract_items(document: &Html) -> Vec<OrderItem> {
                 quantity,
                 subtotal,
                 image_url: None,
+                item_url: None,
+                release_date: None,
             });
         }
     }
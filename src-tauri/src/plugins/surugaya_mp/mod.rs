@@ -21,8 +21,8 @@ use crate::parsers::EmailParser;
 use crate::repository::SqliteOrderRepository;
 
 use super::{
-    apply_internal_date, derive_shop_domain, DefaultShopSetting, DispatchError, DispatchOutcome,
-    PluginRegistration, VendorPlugin,
+    apply_internal_date, apply_tax_info, derive_shop_domain, DefaultShopSetting, DispatchError,
+    DispatchOutcome, PluginRegistration, VendorPlugin,
 };
 
 pub struct SurugayaMpPlugin;
@@ -89,6 +89,7 @@ impl VendorPlugin for SurugayaMpPlugin {
 
         // 注文日は本文に含まれないため internal_date で補完
         apply_internal_date(&mut order_info, internal_date);
+        apply_tax_info(&mut order_info, body);
 
         log::debug!(
             "[{}] email_id={} order_number={}",
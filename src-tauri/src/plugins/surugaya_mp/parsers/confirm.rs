@@ -28,6 +28,8 @@ impl EmailParser for SurugayaMpConfirmParser {
             subtotal: None,
             shipping_fee: None,
             total_amount: None,
+            tax_amount: None,
+            tax_included: true,
         })
     }
 }
@@ -27,6 +27,8 @@ impl EmailParser for SurugayaMpSendParser {
             subtotal: None,
             shipping_fee: None,
             total_amount: None,
+            tax_amount: None,
+            tax_included: true,
         })
     }
 }
@@ -0,0 +1,241 @@
+use crate::parsers::release_date::parse_fuzzy_release_date;
+use crate::parsers::{normalize_item_url, EmailParser, Label, OrderInfo, OrderItem};
+use chrono::NaiveDate;
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+/// `ご注文番号：2503100123`（公式通販）/ `ご注文番号：HT-250310-0123`（ホビー天国オンラインストア）
+static ORDER_NUMBER: Lazy<Label> = Lazy::new(|| {
+    Label::new("注文番号")
+        .colon()
+        .value(r"(?:[A-Za-z]{2}-)?\d+(?:-\d+)*")
+        .build()
+});
+
+/// `ご注文日時：2025/03/10 21:15` / `ご注文日：2025年03月10日` パターン
+static ORDER_DATE_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"ご注文日時?\s*[：:]\s*(\d{4})[年/](\d{1,2})[月/](\d{1,2})日?(?:\s+(\d{1,2}:\d{2}))?",
+    )
+    .expect("Invalid ORDER_DATE_RE")
+});
+
+static ITEM_NAME: Lazy<Label> = Lazy::new(|| Label::new("商品名").bracket().build());
+static ITEM_CODE: Lazy<Label> = Lazy::new(|| Label::new("商品コード").bracket().build());
+static ITEM_URL: Lazy<Label> = Lazy::new(|| Label::new("商品URL").bracket().build());
+static RELEASE: Lazy<Label> = Lazy::new(|| Label::new("発売予定").bracket().build());
+static UNIT_PRICE: Lazy<Label> =
+    Lazy::new(|| Label::new("単価").bracket().value(r"[\d,]+").build());
+static QUANTITY: Lazy<Label> = Lazy::new(|| Label::new("数量").bracket().value(r"\d+").build());
+
+static SUBTOTAL: Lazy<Label> = Lazy::new(|| {
+    Label::new("商品小計")
+        .alias("小計")
+        .colon()
+        .value(r"[\d,]+")
+        .build()
+});
+static SHIPPING_FEE: Lazy<Label> =
+    Lazy::new(|| Label::new("送料").colon().value(r"[\d,]+").build());
+static TOTAL_AMOUNT: Lazy<Label> = Lazy::new(|| {
+    Label::new("お支払い合計")
+        .alias("合計金額")
+        .colon()
+        .value(r"[\d,]+")
+        .build()
+});
+
+/// ボークス 注文確認メール用パーサー
+///
+/// 件名：`【ボークス公式通販】ご注文ありがとうございます` /
+/// `【ホビー天国オンラインストア】ご注文ありがとうございます`
+/// 送信元：`shop@volks.co.jp`
+///
+/// 注文番号は公式通販が数字のみ、ホビー天国オンラインストアが `HT-` 始まりのハイフン区切りで、
+/// どちらも英字を大文字に揃えて注文番号とする。
+/// 商品は `[商品名]` 行から始まるブロックで並び、`[商品コード]`・`[商品URL]`・`[発売予定]`・`[単価]`・`[数量]` が続く。
+pub struct VolksConfirmParser;
+
+fn extract_order_date(lines: &[&str]) -> Option<(String, NaiveDate)> {
+    lines.iter().find_map(|line| {
+        let caps = ORDER_DATE_RE.captures(line)?;
+        let date = NaiveDate::from_ymd_opt(
+            caps[1].parse().ok()?,
+            caps[2].parse().ok()?,
+            caps[3].parse().ok()?,
+        )?;
+        let formatted = match caps.get(4) {
+            Some(time) => format!("{} {}", date.format("%Y-%m-%d"), time.as_str()),
+            None => date.format("%Y-%m-%d").to_string(),
+        };
+        Some((formatted, date))
+    })
+}
+
+fn extract_items(lines: &[&str], order_date: Option<NaiveDate>) -> Vec<OrderItem> {
+    let mut items: Vec<OrderItem> = Vec::new();
+
+    for line in lines {
+        if let Some(name) = ITEM_NAME.match_line(line) {
+            items.push(OrderItem {
+                name,
+                manufacturer: None,
+                model_number: None,
+                unit_price: 0,
+                quantity: 1,
+                subtotal: 0,
+                image_url: None,
+                item_url: None,
+                release_date: None,
+            });
+            continue;
+        }
+        let Some(item) = items.last_mut() else {
+            continue;
+        };
+
+        if let Some(code) = ITEM_CODE.match_line(line) {
+            item.model_number = Some(code);
+        } else if let Some(url) = ITEM_URL.match_line(line) {
+            item.item_url = normalize_item_url(&url);
+        } else if let Some(release) = RELEASE.match_line(line) {
+            item.release_date = parse_fuzzy_release_date(&release, order_date);
+        } else if let Some(price) = UNIT_PRICE.match_line(line) {
+            item.unit_price = price.replace(',', "").parse().unwrap_or(0);
+        } else if let Some(quantity) = QUANTITY.match_line(line) {
+            item.quantity = quantity.parse().unwrap_or(1);
+        }
+    }
+
+    for item in &mut items {
+        item.subtotal = item.unit_price * item.quantity;
+    }
+    items
+}
+
+impl EmailParser for VolksConfirmParser {
+    fn parse(&self, email_body: &str) -> Result<OrderInfo, String> {
+        let lines: Vec<&str> = email_body.lines().map(str::trim).collect();
+
+        let order_number = ORDER_NUMBER
+            .find_in_lines(&lines)
+            .map(|n| n.to_ascii_uppercase())
+            .ok_or_else(|| "Order number not found".to_string())?;
+
+        let (order_date, order_day) = match extract_order_date(&lines) {
+            Some((formatted, day)) => (Some(formatted), Some(day)),
+            None => (None, None),
+        };
+
+        let items = extract_items(&lines, order_day);
+        if items.is_empty() {
+            return Err("No items found".to_string());
+        }
+
+        Ok(OrderInfo {
+            order_number,
+            order_date,
+            delivery_address: None,
+            delivery_info: None,
+            items,
+            subtotal: SUBTOTAL.find_i64(email_body),
+            shipping_fee: SHIPPING_FEE.find_i64(email_body),
+            total_amount: TOTAL_AMOUNT.find_i64(email_body),
+            tax_amount: None,
+            tax_included: true,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parsers::release_date::ReleaseDatePrecision;
+
+    fn sample_confirm() -> &'static str {
+        r#"山田 太郎 様
+このたびはボークス公式通販サイトをご利用いただき、誠にありがとうございます。
+以下の内容でご注文を承りました。
+
+■ご注文番号：2503100123
+■ご注文日時：2025/03/10 21:15
+
+■ご注文内容
+----------------------------------------
+[商品名] ドルフィードリーム DD 初音ミク
+[商品コード] 4518992412345
+[商品URL] https://www.volks.co.jp/jp/item/vol_4518992412345.html
+[発売予定] 2025年9月下旬
+[単価] 88,000円(税込)
+[数量] 1
+----------------------------------------
+[商品名] 造形村 1/32 フォッケウルフ Ta152H-1
+[商品コード] 4518992400014
+[単価] 12,100円(税込)
+[数量] 2
+----------------------------------------
+商品小計：112,200円
+送料：1,100円
+お支払い合計：113,300円
+"#
+    }
+
+    #[test]
+    fn test_parse_confirm_order_number_and_date() {
+        let order = VolksConfirmParser.parse(sample_confirm()).unwrap();
+        assert_eq!(order.order_number, "2503100123");
+        assert_eq!(order.order_date.as_deref(), Some("2025-03-10 21:15"));
+    }
+
+    #[test]
+    fn test_parse_confirm_hobby_tengoku_order_number() {
+        let body = sample_confirm().replace("2503100123", "ht-250310-0123");
+        let order = VolksConfirmParser.parse(&body).unwrap();
+        assert_eq!(order.order_number, "HT-250310-0123");
+    }
+
+    #[test]
+    fn test_parse_confirm_items() {
+        let order = VolksConfirmParser.parse(sample_confirm()).unwrap();
+        assert_eq!(order.items.len(), 2);
+
+        let first = &order.items[0];
+        assert_eq!(first.name, "ドルフィードリーム DD 初音ミク");
+        assert_eq!(first.model_number.as_deref(), Some("4518992412345"));
+        assert_eq!(
+            first.item_url.as_deref(),
+            Some("https://www.volks.co.jp/jp/item/vol_4518992412345.html")
+        );
+        assert_eq!(first.unit_price, 88000);
+        assert_eq!(first.quantity, 1);
+        let release = first.release_date.as_ref().unwrap();
+        assert_eq!(release.month, Some(9));
+        assert_eq!(release.precision, ReleaseDatePrecision::LateMonth);
+
+        let second = &order.items[1];
+        assert_eq!(second.unit_price, 12100);
+        assert_eq!(second.quantity, 2);
+        assert_eq!(second.subtotal, 24200);
+        assert!(second.release_date.is_none());
+    }
+
+    #[test]
+    fn test_parse_confirm_amounts() {
+        let order = VolksConfirmParser.parse(sample_confirm()).unwrap();
+        assert_eq!(order.subtotal, Some(112200));
+        assert_eq!(order.shipping_fee, Some(1100));
+        assert_eq!(order.total_amount, Some(113300));
+    }
+
+    #[test]
+    fn test_parse_confirm_missing_order_number() {
+        assert!(VolksConfirmParser.parse("[商品名] テスト").is_err());
+    }
+
+    #[test]
+    fn test_parse_confirm_no_items() {
+        assert!(VolksConfirmParser
+            .parse("ご注文番号：2503100123\nお支払い合計：0円")
+            .is_err());
+    }
+}
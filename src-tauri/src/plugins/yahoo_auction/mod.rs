@@ -0,0 +1,96 @@
+//! ヤフオク! プラグイン
+//!
+//! `auction-master@mail.yahoo.co.jp` から配信される落札通知メールを取り込む。
+//! オークションには注文番号がないため、オークション ID（`x123456789` 等）を注文番号として扱う。
+
+pub mod parsers;
+
+use async_trait::async_trait;
+
+use crate::parsers::EmailParser;
+use crate::repository::SqliteOrderRepository;
+
+use super::{
+    apply_internal_date, derive_shop_domain, DefaultShopSetting, DispatchError, DispatchOutcome,
+    PluginRegistration, VendorPlugin,
+};
+
+pub struct YahooAuctionPlugin;
+
+#[async_trait]
+impl VendorPlugin for YahooAuctionPlugin {
+    fn parser_types(&self) -> &[&str] {
+        &["yahoo_auction_won"]
+    }
+
+    fn priority(&self) -> i32 {
+        10
+    }
+
+    fn get_parser(&self, parser_type: &str) -> Option<Box<dyn EmailParser>> {
+        match parser_type {
+            "yahoo_auction_won" => Some(Box::new(parsers::won::YahooAuctionWonParser)),
+            _ => None,
+        }
+    }
+
+    fn shop_name(&self) -> &str {
+        "ヤフオク!"
+    }
+
+    fn default_shop_settings(&self) -> Vec<DefaultShopSetting> {
+        vec![DefaultShopSetting {
+            shop_name: "ヤフオク!".to_string(),
+            sender_address: "auction-master@mail.yahoo.co.jp".to_string(),
+            parser_type: "yahoo_auction_won".to_string(),
+            subject_filters: Some(vec!["落札しました".to_string()]),
+        }]
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    async fn dispatch(
+        &self,
+        parser_type: &str,
+        email_id: i64,
+        from_address: Option<&str>,
+        shop_name: &str,
+        internal_date: Option<i64>,
+        body: &str,
+        tx: &mut sqlx::Transaction<'_, sqlx::Sqlite>,
+    ) -> Result<DispatchOutcome, DispatchError> {
+        let shop_domain = derive_shop_domain(from_address);
+
+        let mut order_info = {
+            let parser = self.get_parser(parser_type).ok_or_else(|| {
+                DispatchError::ParseFailed(format!("No parser for type: {}", parser_type))
+            })?;
+            parser.parse(body).map_err(DispatchError::ParseFailed)?
+        };
+
+        // 落札価格は消費税の内訳が書かれないため apply_tax_info は使わない
+        apply_internal_date(&mut order_info, internal_date);
+
+        log::debug!(
+            "[{}] email_id={} order_number={}",
+            parser_type,
+            email_id,
+            order_info.order_number
+        );
+
+        SqliteOrderRepository::save_order_in_tx(
+            tx,
+            &order_info,
+            Some(email_id),
+            shop_domain,
+            Some(shop_name.to_string()),
+        )
+        .await
+        .map_err(DispatchError::SaveFailed)?;
+
+        Ok(DispatchOutcome::OrderSaved(Box::new(order_info)))
+    }
+}
+
+inventory::submit!(PluginRegistration {
+    factory: || Box::new(YahooAuctionPlugin),
+});
@@ -0,0 +1,171 @@
+use crate::logic::language::EmailLanguage;
+use crate::parsers::{EmailParser, Label, OrderInfo, OrderItem};
+use chrono::NaiveDate;
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+/// `オークションID：x123456789`（旧形式は数字のみ `1234567890`）
+static AUCTION_ID: Lazy<Label> = Lazy::new(|| {
+    Label::new("オークションID")
+        .colon()
+        .value(r"[a-z]?\d{9,10}")
+        .build()
+});
+
+/// 商品ページのリンク: `https://page.auctions.yahoo.co.jp/jp/auction/x123456789`
+static AUCTION_URL_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"auctions\.yahoo\.co\.jp/jp/auction/([a-z]?\d{9,10})")
+        .expect("Invalid AUCTION_URL_RE")
+});
+
+/// `終了日時：2025年 3月 10日 21時 15分`
+static ENDED_AT_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"終了日時\s*[：:]\s*(\d{4})年\s*(\d{1,2})月\s*(\d{1,2})日\s*(\d{1,2})時\s*(\d{1,2})分",
+    )
+    .expect("Invalid ENDED_AT_RE")
+});
+
+static ITEM_NAME: Lazy<Label> =
+    Lazy::new(|| Label::new("商品名").alias("タイトル").colon().build());
+static WINNING_PRICE: Lazy<Label> =
+    Lazy::new(|| Label::new("落札価格").alias("落札額").colon().build());
+static QUANTITY: Lazy<Label> = Lazy::new(|| Label::new("落札数量").colon().value(r"\d+").build());
+
+/// ヤフオク! 落札通知メール用パーサー
+///
+/// 件名：`ヤフオク! - 落札しました：ねんどろいど 初音ミク`
+/// 送信元：`auction-master@mail.yahoo.co.jp`
+///
+/// オークションには注文番号の概念がないため、オークション ID を `OrderInfo::order_number` にする。
+/// 商品ページ（`page.auctions.yahoo.co.jp/jp/auction/<オークション ID>`）を `item_url` に入れる。
+/// 落札時点では送料・支払い金額が決まっていないため、送料と合計は None とする。
+pub struct YahooAuctionWonParser;
+
+fn extract_auction_id(email_body: &str) -> Option<String> {
+    AUCTION_ID.find(email_body).or_else(|| {
+        AUCTION_URL_RE
+            .captures(email_body)
+            .map(|caps| caps[1].to_string())
+    })
+}
+
+fn extract_ended_at(email_body: &str) -> Option<String> {
+    let caps = ENDED_AT_RE.captures(email_body)?;
+    let date = NaiveDate::from_ymd_opt(
+        caps[1].parse().ok()?,
+        caps[2].parse().ok()?,
+        caps[3].parse().ok()?,
+    )?;
+    let datetime = date.and_hms_opt(caps[4].parse().ok()?, caps[5].parse().ok()?, 0)?;
+    Some(datetime.format("%Y-%m-%d %H:%M").to_string())
+}
+
+impl EmailParser for YahooAuctionWonParser {
+    fn parse(&self, email_body: &str) -> Result<OrderInfo, String> {
+        let auction_id =
+            extract_auction_id(email_body).ok_or_else(|| "Auction ID not found".to_string())?;
+        let name = ITEM_NAME
+            .find(email_body)
+            .ok_or_else(|| "Item name not found".to_string())?;
+        let price = WINNING_PRICE
+            .find_amount(email_body, EmailLanguage::Japanese)
+            .ok_or_else(|| "Winning price not found".to_string())?;
+        let quantity = QUANTITY
+            .find_i64(email_body)
+            .filter(|q| *q > 0)
+            .unwrap_or(1);
+        let subtotal = price * quantity;
+
+        Ok(OrderInfo {
+            order_date: extract_ended_at(email_body),
+            delivery_address: None,
+            delivery_info: None,
+            items: vec![OrderItem {
+                name,
+                manufacturer: None,
+                model_number: None,
+                unit_price: price,
+                quantity,
+                subtotal,
+                image_url: None,
+                item_url: Some(format!(
+                    "https://page.auctions.yahoo.co.jp/jp/auction/{auction_id}"
+                )),
+                release_date: None,
+            }],
+            order_number: auction_id,
+            subtotal: Some(subtotal),
+            shipping_fee: None,
+            total_amount: None,
+            tax_amount: None,
+            tax_included: true,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_won() -> &'static str {
+        r#"Yahoo! JAPAN ID：taro_yamada さん
+
+落札おめでとうございます！
+あなたが入札していた以下のオークションを落札しました。
+
+■オークション情報
+オークションID：x123456789
+商品名：ねんどろいど 初音ミク 未開封
+落札価格：4,500 円
+落札数量：1
+終了日時：2025年 3月 10日 21時 15分
+出品者：seller_abc
+
+出品者と取引を進めてください。
+https://page.auctions.yahoo.co.jp/jp/auction/x123456789
+"#
+    }
+
+    #[test]
+    fn test_parse_won_auction_id_as_order_number() {
+        let order = YahooAuctionWonParser.parse(sample_won()).unwrap();
+        assert_eq!(order.order_number, "x123456789");
+        assert_eq!(order.order_date.as_deref(), Some("2025-03-10 21:15"));
+    }
+
+    #[test]
+    fn test_parse_won_item_and_amounts() {
+        let order = YahooAuctionWonParser.parse(sample_won()).unwrap();
+        assert_eq!(order.items.len(), 1);
+        let item = &order.items[0];
+        assert_eq!(item.name, "ねんどろいど 初音ミク 未開封");
+        assert_eq!(item.unit_price, 4500);
+        assert_eq!(item.quantity, 1);
+        assert_eq!(
+            item.item_url.as_deref(),
+            Some("https://page.auctions.yahoo.co.jp/jp/auction/x123456789")
+        );
+
+        assert_eq!(order.subtotal, Some(4500));
+        assert_eq!(order.shipping_fee, None);
+        assert_eq!(order.total_amount, None);
+    }
+
+    #[test]
+    fn test_parse_won_auction_id_from_url_and_quantity() {
+        let body = sample_won()
+            .replace("オークションID：x123456789\n", "")
+            .replace("落札数量：1", "落札数量：2");
+        let order = YahooAuctionWonParser.parse(&body).unwrap();
+        assert_eq!(order.order_number, "x123456789");
+        assert_eq!(order.items[0].quantity, 2);
+        assert_eq!(order.subtotal, Some(9000));
+    }
+
+    #[test]
+    fn test_parse_won_missing_auction_id() {
+        let body = "商品名：テスト\n落札価格：1,000 円";
+        assert!(YahooAuctionWonParser.parse(body).is_err());
+    }
+}
@@ -0,0 +1,107 @@
+//! Yahoo!ショッピング プラグイン
+//!
+//! Yahoo!ショッピングの注文確認メール（ストア共通フォーマット）をパースする。
+//! 1 つの送信元から全ストアの注文が届くため、注文の `shop_name` には本文の `ストア名：` を使い、
+//! 記載がない場合のみショップ設定のショップ名（`Yahoo!ショッピング`）にフォールバックする。
+
+pub mod parsers;
+
+use async_trait::async_trait;
+
+use crate::parsers::EmailParser;
+use crate::repository::SqliteOrderRepository;
+
+use super::{
+    apply_internal_date, apply_tax_info, derive_shop_domain, DefaultShopSetting, DispatchError,
+    DispatchOutcome, PluginRegistration, VendorPlugin,
+};
+
+pub struct YahooShoppingPlugin;
+
+#[async_trait]
+impl VendorPlugin for YahooShoppingPlugin {
+    fn parser_types(&self) -> &[&str] {
+        &["yahoo_shopping_confirm"]
+    }
+
+    fn priority(&self) -> i32 {
+        10
+    }
+
+    fn get_parser(&self, parser_type: &str) -> Option<Box<dyn EmailParser>> {
+        match parser_type {
+            "yahoo_shopping_confirm" => {
+                Some(Box::new(parsers::confirm::YahooShoppingConfirmParser))
+            }
+            _ => None,
+        }
+    }
+
+    fn shop_name(&self) -> &str {
+        "Yahoo!ショッピング"
+    }
+
+    fn default_shop_settings(&self) -> Vec<DefaultShopSetting> {
+        vec![DefaultShopSetting {
+            shop_name: "Yahoo!ショッピング".to_string(),
+            sender_address: "shopping-order-master@mail.yahoo.co.jp".to_string(),
+            parser_type: "yahoo_shopping_confirm".to_string(),
+            subject_filters: Some(vec!["ご注文確認".to_string()]),
+        }]
+    }
+
+    fn prefer_plain_text(&self) -> bool {
+        true
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    async fn dispatch(
+        &self,
+        parser_type: &str,
+        email_id: i64,
+        from_address: Option<&str>,
+        shop_name: &str,
+        internal_date: Option<i64>,
+        body: &str,
+        tx: &mut sqlx::Transaction<'_, sqlx::Sqlite>,
+    ) -> Result<DispatchOutcome, DispatchError> {
+        let shop_domain = derive_shop_domain(from_address);
+
+        let mut order_info = {
+            let parser = self.get_parser(parser_type).ok_or_else(|| {
+                DispatchError::ParseFailed(format!("No parser for type: {}", parser_type))
+            })?;
+            parser.parse(body).map_err(DispatchError::ParseFailed)?
+        };
+
+        apply_internal_date(&mut order_info, internal_date);
+        apply_tax_info(&mut order_info, body);
+
+        let store_name = parsers::extract_store_name(body);
+        let shop_name = store_name.as_deref().unwrap_or(shop_name);
+
+        log::debug!(
+            "[{}] email_id={} order_number={} store={}",
+            parser_type,
+            email_id,
+            order_info.order_number,
+            shop_name
+        );
+
+        SqliteOrderRepository::save_order_in_tx(
+            tx,
+            &order_info,
+            Some(email_id),
+            shop_domain,
+            Some(shop_name.to_string()),
+        )
+        .await
+        .map_err(DispatchError::SaveFailed)?;
+
+        Ok(DispatchOutcome::OrderSaved(Box::new(order_info)))
+    }
+}
+
+inventory::submit!(PluginRegistration {
+    factory: || Box::new(YahooShoppingPlugin),
+});
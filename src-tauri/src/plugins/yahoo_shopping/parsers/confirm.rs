@@ -0,0 +1,237 @@
+use crate::parsers::{normalize_item_url, EmailParser, Label, OrderInfo, OrderItem};
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+/// `注文番号：hobby-star-10000123`（ストアアカウント + 連番）
+static ORDER_NUMBER: Lazy<Label> = Lazy::new(|| {
+    Label::new("注文番号")
+        .alias("注文ID")
+        .colon()
+        .value(r"[A-Za-z0-9][A-Za-z0-9_\-]*-\d+")
+        .build()
+});
+
+/// `注文日時：2025年03月10日 21時15分` / `注文日時：2025/03/10 21:15` パターン
+static ORDER_DATE_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"注文日時?\s*[：:]\s*(\d{4})[年/](\d{1,2})[月/](\d{1,2})日?(?:\s*(\d{1,2})[時:](\d{2}))?",
+    )
+    .expect("Invalid ORDER_DATE_RE")
+});
+
+static ITEM_NAME: Lazy<Label> = Lazy::new(|| Label::new("商品名").colon().build());
+static ITEM_CODE: Lazy<Label> = Lazy::new(|| Label::new("商品コード").colon().build());
+static ITEM_URL: Lazy<Label> = Lazy::new(|| Label::new("商品URL").colon().build());
+static QUANTITY: Lazy<Label> = Lazy::new(|| Label::new("数量").colon().value(r"\d+").build());
+static UNIT_PRICE: Lazy<Label> = Lazy::new(|| {
+    Label::new("価格")
+        .alias("単価")
+        .colon()
+        .value(r"[\d,]+")
+        .build()
+});
+
+static SUBTOTAL: Lazy<Label> = Lazy::new(|| {
+    Label::new("商品小計")
+        .alias("商品合計")
+        .colon()
+        .value(r"[\d,]+")
+        .build()
+});
+static SHIPPING_FEE: Lazy<Label> =
+    Lazy::new(|| Label::new("送料").colon().value(r"[\d,]+").build());
+static TOTAL_AMOUNT: Lazy<Label> = Lazy::new(|| {
+    Label::new("合計金額")
+        .alias("お支払い金額")
+        .colon()
+        .value(r"[\d,]+")
+        .build()
+});
+
+/// Yahoo!ショッピング 注文確認メール用パーサー（ストア共通フォーマット）
+///
+/// 件名：`【Yahoo!ショッピング】ご注文確認（ホビーショップ星）`
+/// 送信元：`shopping-order-master@mail.yahoo.co.jp`
+///
+/// 注文番号は `ストアアカウント-連番` 形式で、ストアをまたいでも重複しない。
+/// 商品は `商品名：` 行から始まるブロックで並び、`商品コード：`・`商品URL：`・`数量：`・`価格：` が続く。
+/// `オプション：` 等のその他の行は読み飛ばす。ストア名は [`super::extract_store_name`] で別途抽出する。
+pub struct YahooShoppingConfirmParser;
+
+fn extract_order_date(lines: &[&str]) -> Option<String> {
+    lines.iter().find_map(|line| {
+        let caps = ORDER_DATE_RE.captures(line)?;
+        let date = chrono::NaiveDate::from_ymd_opt(
+            caps[1].parse().ok()?,
+            caps[2].parse().ok()?,
+            caps[3].parse().ok()?,
+        )?;
+        Some(match (caps.get(4), caps.get(5)) {
+            (Some(hour), Some(minute)) => format!(
+                "{} {:02}:{}",
+                date.format("%Y-%m-%d"),
+                hour.as_str().parse::<u32>().ok()?,
+                minute.as_str()
+            ),
+            _ => date.format("%Y-%m-%d").to_string(),
+        })
+    })
+}
+
+fn extract_items(lines: &[&str]) -> Vec<OrderItem> {
+    let mut items: Vec<OrderItem> = Vec::new();
+
+    for line in lines {
+        if let Some(name) = ITEM_NAME.match_line(line) {
+            items.push(OrderItem {
+                name,
+                manufacturer: None,
+                model_number: None,
+                unit_price: 0,
+                quantity: 1,
+                subtotal: 0,
+                image_url: None,
+                item_url: None,
+                release_date: None,
+            });
+            continue;
+        }
+        if SUBTOTAL.match_line(line).is_some() {
+            // 合計欄に入ったら商品ブロックは終わり
+            break;
+        }
+        let Some(item) = items.last_mut() else {
+            continue;
+        };
+
+        if let Some(code) = ITEM_CODE.match_line(line) {
+            item.model_number = Some(code);
+        } else if let Some(url) = ITEM_URL.match_line(line) {
+            item.item_url = normalize_item_url(&url);
+        } else if let Some(quantity) = QUANTITY.match_line(line) {
+            item.quantity = quantity.parse().unwrap_or(1);
+        } else if let Some(price) = UNIT_PRICE.match_line(line) {
+            item.unit_price = price.replace(',', "").parse().unwrap_or(0);
+        }
+    }
+
+    for item in &mut items {
+        item.subtotal = item.unit_price * item.quantity;
+    }
+    items
+}
+
+impl EmailParser for YahooShoppingConfirmParser {
+    fn parse(&self, email_body: &str) -> Result<OrderInfo, String> {
+        let lines: Vec<&str> = email_body.lines().map(str::trim).collect();
+
+        let order_number = ORDER_NUMBER
+            .find_in_lines(&lines)
+            .ok_or_else(|| "Order number not found".to_string())?;
+
+        let items = extract_items(&lines);
+        if items.is_empty() {
+            return Err("No items found".to_string());
+        }
+
+        Ok(OrderInfo {
+            order_number,
+            order_date: extract_order_date(&lines),
+            delivery_address: None,
+            delivery_info: None,
+            items,
+            subtotal: SUBTOTAL.find_i64(email_body),
+            shipping_fee: SHIPPING_FEE.find_i64(email_body),
+            total_amount: TOTAL_AMOUNT.find_i64(email_body),
+            tax_amount: None,
+            tax_included: true,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_confirm() -> &'static str {
+        r#"山田 太郎 様
+
+Yahoo!ショッピングをご利用いただき、ありがとうございます。
+以下の内容でご注文を受け付けました。
+
+ストア名：ホビーショップ星
+注文番号：hobby-star-10000123
+注文日時：2025年03月10日 9時05分
+
+【ご注文商品】
+------------------------------------------------------------
+商品名：ねんどろいど 初音ミク 10th Anniversary Ver.
+商品コード：4580590123456
+商品URL：https://store.shopping.yahoo.co.jp/hobby-star/4580590123456.html?utm_source=mail
+数量：1
+価格：5,280円
+------------------------------------------------------------
+商品名：figma 鏡音リン
+商品コード：4580590654321
+オプション：特典：あり
+数量：2
+価格：3,300円
+------------------------------------------------------------
+商品小計：11,880円
+送料：660円
+クーポン値引き：-500円
+合計金額：12,040円
+"#
+    }
+
+    #[test]
+    fn test_parse_confirm_order_number_and_date() {
+        let order = YahooShoppingConfirmParser.parse(sample_confirm()).unwrap();
+        assert_eq!(order.order_number, "hobby-star-10000123");
+        assert_eq!(order.order_date.as_deref(), Some("2025-03-10 09:05"));
+    }
+
+    #[test]
+    fn test_parse_confirm_items() {
+        let order = YahooShoppingConfirmParser.parse(sample_confirm()).unwrap();
+        assert_eq!(order.items.len(), 2);
+
+        let first = &order.items[0];
+        assert_eq!(first.name, "ねんどろいど 初音ミク 10th Anniversary Ver.");
+        assert_eq!(first.model_number.as_deref(), Some("4580590123456"));
+        assert_eq!(
+            first.item_url.as_deref(),
+            Some("https://store.shopping.yahoo.co.jp/hobby-star/4580590123456.html")
+        );
+        assert_eq!(first.unit_price, 5280);
+        assert_eq!(first.quantity, 1);
+
+        let second = &order.items[1];
+        assert_eq!(second.name, "figma 鏡音リン");
+        assert_eq!(second.unit_price, 3300);
+        assert_eq!(second.quantity, 2);
+        assert_eq!(second.subtotal, 6600);
+    }
+
+    #[test]
+    fn test_parse_confirm_amounts() {
+        let order = YahooShoppingConfirmParser.parse(sample_confirm()).unwrap();
+        assert_eq!(order.subtotal, Some(11880));
+        assert_eq!(order.shipping_fee, Some(660));
+        assert_eq!(order.total_amount, Some(12040));
+    }
+
+    #[test]
+    fn test_parse_confirm_missing_order_number() {
+        assert!(YahooShoppingConfirmParser
+            .parse("商品名：テスト\n数量：1\n価格：100円")
+            .is_err());
+    }
+
+    #[test]
+    fn test_parse_confirm_no_items() {
+        assert!(YahooShoppingConfirmParser
+            .parse("注文番号：hobby-star-10000123\n合計金額：0円")
+            .is_err());
+    }
+}
@@ -0,0 +1,27 @@
+pub mod confirm;
+
+use once_cell::sync::Lazy;
+
+use crate::parsers::Label;
+
+/// `ストア名：ホビーショップ○○` / `ストア名: ホビーショップ○○`
+static STORE_NAME: Lazy<Label> = Lazy::new(|| Label::new("ストア名").colon().build());
+
+/// 本文からストア名を抽出する（注文の shop_name に使う）
+pub fn extract_store_name(body: &str) -> Option<String> {
+    STORE_NAME.find(body)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_store_name() {
+        assert_eq!(
+            extract_store_name("ご注文ありがとうございます。\nストア名： ホビーショップ星 \n"),
+            Some("ホビーショップ星".to_string())
+        );
+        assert_eq!(extract_store_name("ストア名："), None);
+    }
+}
@@ -2,6 +2,7 @@
 //!
 //! - 注文確認: `thanks_gochuumon@yodobashi.com`
 //! - キャンセル: `cancel@yodobashi.com`
+//! - 発送・お届け予定日変更: `otodoke@yodobashi.com`
 
 pub mod parsers;
 
@@ -11,8 +12,8 @@ use crate::parsers::EmailParser;
 use crate::repository::SqliteOrderRepository;
 
 use super::{
-    derive_shop_domain, DefaultShopSetting, DispatchError, DispatchOutcome, PluginRegistration,
-    VendorPlugin,
+    apply_tax_info, derive_shop_domain, DefaultShopSetting, DispatchError, DispatchOutcome,
+    PluginRegistration, VendorPlugin,
 };
 
 pub struct YodobashiPlugin;
@@ -20,7 +21,12 @@ pub struct YodobashiPlugin;
 #[async_trait]
 impl VendorPlugin for YodobashiPlugin {
     fn parser_types(&self) -> &[&str] {
-        &["yodobashi_confirm", "yodobashi_cancel", "yodobashi_send"]
+        &[
+            "yodobashi_confirm",
+            "yodobashi_cancel",
+            "yodobashi_send",
+            "yodobashi_delivery_date_change",
+        ]
     }
 
     fn priority(&self) -> i32 {
@@ -69,6 +75,14 @@ impl VendorPlugin for YodobashiPlugin {
                     "ヨドバシ・ドット・コム：ご注文商品出荷のお知らせ".to_string()
                 ]),
             },
+            DefaultShopSetting {
+                shop_name: "ヨドバシ・ドット・コム".to_string(),
+                sender_address: "otodoke@yodobashi.com".to_string(),
+                parser_type: "yodobashi_delivery_date_change".to_string(),
+                subject_filters: Some(vec![
+                    "ヨドバシ・ドット・コム：お届け予定日変更のお知らせ".to_string()
+                ]),
+            },
         ]
     }
 
@@ -116,14 +130,42 @@ impl VendorPlugin for YodobashiPlugin {
                 Ok(DispatchOutcome::CancelApplied { order_number })
             }
 
+            "yodobashi_delivery_date_change" => {
+                let change_info = parsers::delivery_date_change::YodobashiDeliveryDateChangeParser
+                    .parse_delivery_date_change(body)
+                    .map_err(DispatchError::ParseFailed)?;
+
+                log::debug!(
+                    "[yodobashi_delivery_date_change] email_id={} order_number={} new_date={}",
+                    email_id,
+                    change_info.order_number,
+                    change_info.new_estimated_delivery
+                );
+
+                SqliteOrderRepository::apply_delivery_date_change_in_tx(
+                    tx,
+                    &change_info,
+                    email_id,
+                    shop_domain,
+                    None,
+                )
+                .await
+                .map_err(DispatchError::SaveFailed)?;
+
+                Ok(DispatchOutcome::DeliveryDateChanged {
+                    order_number: change_info.order_number,
+                })
+            }
+
             _ => {
                 // yodobashi_confirm およびその他
-                let order_info = {
+                let mut order_info = {
                     let parser = self.get_parser(parser_type).ok_or_else(|| {
                         DispatchError::ParseFailed(format!("No parser for type: {}", parser_type))
                     })?;
                     parser.parse(body).map_err(DispatchError::ParseFailed)?
                 };
+                apply_tax_info(&mut order_info, body);
 
                 log::debug!(
                     "[{}] email_id={} order_number={}",
@@ -9,7 +9,7 @@
 use once_cell::sync::Lazy;
 use regex::Regex;
 
-use crate::parsers::cancel_info::CancelInfo;
+use crate::parsers::cancel_info::{CancelInfo, CancelReason};
 
 pub struct YodobashiCancelParser;
 
@@ -119,6 +119,9 @@ impl YodobashiCancelParser {
                 order_number: order_number.clone(),
                 product_name,
                 cancel_quantity,
+                // 「変更ご依頼内容」に基づく購入者からの変更依頼
+                reason: Some(CancelReason::UserRequest),
+                reason_detail: None,
             })
             .collect();
 
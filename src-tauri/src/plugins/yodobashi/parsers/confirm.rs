@@ -151,6 +151,8 @@ fn extract_items(body: &str) -> Vec<OrderItem> {
                     quantity,
                     subtotal,
                     image_url: None,
+                    item_url: None,
+                    release_date: None,
                 });
             }
             continue;
@@ -188,6 +190,8 @@ impl EmailParser for YodobashiConfirmParser {
             subtotal: Some(subtotal),
             shipping_fee,
             total_amount,
+            tax_amount: None,
+            tax_included: true,
         })
     }
 }
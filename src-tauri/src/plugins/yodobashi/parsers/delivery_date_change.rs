@@ -0,0 +1,128 @@
+//! ヨドバシ・ドット・コム お届け予定日変更メール用パーサー
+//!
+//! 件名：`ヨドバシ・ドット・コム：お届け予定日変更のお知らせ`
+//! 送信元：`otodoke@yodobashi.com`
+//!
+//! `【ご注文番号】` と `【変更後】` / `【変更前】` のお届け予定日を抽出する。
+//! お届け予定日が期間（`2026年03月05日～2026年03月07日`）の場合は開始日を採用する。
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+use crate::parsers::delivery_date_change_info::DeliveryDateChangeInfo;
+
+pub struct YodobashiDeliveryDateChangeParser;
+
+// ─── 正規表現 ────────────────────────────────────────────────────────────────
+
+/// `【ご注文番号】 7538892732` / `【変更対象のご注文番号】 7538892732`
+static ORDER_NUMBER_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"【(?:変更対象の)?ご注文番号】\s*(\d+)").expect("ORDER_NUMBER_RE"));
+
+/// `　　【変更後】2026年03月05日`
+static NEW_DATE_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"【変更後】\s*(\d{4})年(\d{1,2})月(\d{1,2})日").expect("NEW_DATE_RE"));
+
+/// `　　【変更前】2026年02月24日`
+static OLD_DATE_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"【変更前】\s*(\d{4})年(\d{1,2})月(\d{1,2})日").expect("OLD_DATE_RE"));
+
+// ─── ヘルパー ─────────────────────────────────────────────────────────────────
+
+fn extract_order_number(body: &str) -> Option<String> {
+    ORDER_NUMBER_RE
+        .captures(body)
+        .map(|c| c[1].trim().to_string())
+}
+
+/// 年・月・日のキャプチャを `YYYY-MM-DD` に変換する
+fn extract_date(re: &Regex, body: &str) -> Option<String> {
+    re.captures(body).and_then(|c| {
+        let month: u32 = c[2].parse().ok()?;
+        let day: u32 = c[3].parse().ok()?;
+        Some(format!("{}-{:02}-{:02}", &c[1], month, day))
+    })
+}
+
+// ─── パブリック API ───────────────────────────────────────────────────────────
+
+impl YodobashiDeliveryDateChangeParser {
+    /// メール本文からお届け予定日変更情報を抽出する
+    ///
+    /// 注文番号または変更後のお届け予定日が見つからない場合はエラーを返す。
+    pub fn parse_delivery_date_change(
+        &self,
+        email_body: &str,
+    ) -> Result<DeliveryDateChangeInfo, String> {
+        let order_number = extract_order_number(email_body)
+            .ok_or_else(|| "注文番号が見つかりません".to_string())?;
+        let new_estimated_delivery = extract_date(&NEW_DATE_RE, email_body)
+            .ok_or_else(|| "変更後のお届け予定日が見つかりません".to_string())?;
+
+        Ok(DeliveryDateChangeInfo {
+            order_number,
+            old_estimated_delivery: extract_date(&OLD_DATE_RE, email_body),
+            new_estimated_delivery,
+        })
+    }
+}
+
+// ─── テスト ──────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_delivery_date_change() -> &'static str {
+        r#"ヨドバシ・ドット・コムをご利用いただき、誠にありがとうございます。
+ご注文いただいた商品のお届け予定日が変更となりましたので、お知らせいたします。
+
+【ご注文番号】 7538892732
+─────────────────────────────
+●お届け予定日
+　　【変更前】2026年02月24日
+　　【変更後】2026年03月05日～2026年03月07日
+
+●対象商品
+---------------------------------------------------------------
+・「データ用CD-R 700MB ひろびろワイドレーベル 10枚 エコパッケージ CD
+　　R700S.SWPS.10E」
+　　1 点
+─────────────────────────────
+"#
+    }
+
+    #[test]
+    fn test_parse_delivery_date_change() {
+        let info = YodobashiDeliveryDateChangeParser
+            .parse_delivery_date_change(sample_delivery_date_change())
+            .unwrap();
+        assert_eq!(info.order_number, "7538892732");
+        assert_eq!(info.old_estimated_delivery.as_deref(), Some("2026-02-24"));
+        // 期間指定の場合は開始日
+        assert_eq!(info.new_estimated_delivery, "2026-03-05");
+    }
+
+    #[test]
+    fn test_parse_delivery_date_change_pads_single_digit_date() {
+        let info = YodobashiDeliveryDateChangeParser
+            .parse_delivery_date_change("【ご注文番号】 7538892732\n【変更後】2026年3月5日")
+            .unwrap();
+        assert_eq!(info.new_estimated_delivery, "2026-03-05");
+        assert_eq!(info.old_estimated_delivery, None);
+    }
+
+    #[test]
+    fn test_parse_delivery_date_change_no_order_number_returns_error() {
+        let result = YodobashiDeliveryDateChangeParser
+            .parse_delivery_date_change("【変更後】2026年03月05日");
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_parse_delivery_date_change_no_new_date_returns_error() {
+        let result = YodobashiDeliveryDateChangeParser
+            .parse_delivery_date_change("【ご注文番号】 7538892732\n【変更前】2026年02月24日");
+        assert!(result.is_err());
+    }
+}
@@ -1,3 +1,4 @@
 pub mod cancel;
 pub mod confirm;
+pub mod delivery_date_change;
 pub mod send;
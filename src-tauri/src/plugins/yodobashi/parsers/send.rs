@@ -175,6 +175,8 @@ fn extract_shipped_items(body: &str) -> Vec<OrderItem> {
                     quantity,
                     subtotal,
                     image_url: None,
+                    item_url: None,
+                    release_date: None,
                 });
             }
             continue;
@@ -184,14 +186,21 @@ fn extract_shipped_items(body: &str) -> Vec<OrderItem> {
     items
 }
 
+/// ヨドバシの自社便（ヨドバシエクストリームサービス便・当社専用便）か
+fn is_in_house_carrier(carrier: &str) -> bool {
+    carrier.contains("ヨドバシ") || carrier.contains("当社専用便")
+}
+
 /// 配送情報を抽出する
 ///
 /// 追跡番号がない業者（ヨドバシエクストリームサービス便・当社専用便）では
-/// `tracking_number` を空文字列とする。
+/// `tracking_number` を空文字列とする。自社便は配送業者サイトで追跡できないため、
+/// 出荷通知の受信をもって `shipped` として登録する（配送状況確認バッチの対象外）。
 fn extract_delivery_info(body: &str) -> Option<DeliveryInfo> {
     let carrier = extract_carrier(body)?;
     let tracking_number = extract_tracking_number(body).unwrap_or_default();
     let carrier_url = extract_carrier_url(body);
+    let delivery_status = is_in_house_carrier(&carrier).then(|| "shipped".to_string());
 
     Some(DeliveryInfo {
         carrier,
@@ -199,7 +208,7 @@ fn extract_delivery_info(body: &str) -> Option<DeliveryInfo> {
         delivery_date: None,
         delivery_time: None,
         carrier_url,
-        delivery_status: None,
+        delivery_status,
     })
 }
 
@@ -226,6 +235,8 @@ impl EmailParser for YodobashiSendParser {
             subtotal: Some(subtotal),
             shipping_fee: extract_shipping_fee(email_body),
             total_amount: extract_total_amount(email_body),
+            tax_amount: None,
+            tax_included: true,
         })
     }
 }
@@ -407,6 +418,44 @@ mod tests {
         assert_eq!(order.items[0].unit_price, 649);
     }
 
+    #[test]
+    fn test_extreme_delivery_status_shipped() {
+        // 自社便は追跡番号なしでも shipped で登録する
+        let order = YodobashiSendParser.parse(sample_extreme()).unwrap();
+        let di = order.delivery_info.unwrap();
+        assert_eq!(di.delivery_status.as_deref(), Some("shipped"));
+    }
+
+    // ── 当社専用便 ────────────────────────────────────────────────────────────
+
+    #[test]
+    fn test_own_delivery_carrier() {
+        let body = r#"【ご注文番号】 7234682518
+・ご注文日　　　　　　　　　　　2019年10月14日
+
+【今回出荷の商品】
+---------------------------------------------------------------
+・「テスト商品」
+　 　 1 点　1,000 円
+・配達料金：　　0 円
+
+【配達について】今回の配達担当：当社専用便
+---------------------------------------------------------------
+"#;
+        let order = YodobashiSendParser.parse(body).unwrap();
+        let di = order.delivery_info.unwrap();
+        assert_eq!(di.carrier, "当社専用便");
+        assert_eq!(di.tracking_number, "");
+        assert_eq!(di.delivery_status.as_deref(), Some("shipped"));
+    }
+
+    #[test]
+    fn test_external_carrier_uses_default_status() {
+        // 外部配送業者はデフォルト（shipped）で登録し、以降は追跡で更新する
+        let order = YodobashiSendParser.parse(sample_yamato()).unwrap();
+        assert_eq!(order.delivery_info.unwrap().delivery_status, None);
+    }
+
     // ── エラーケース ──────────────────────────────────────────────────────────
 
     #[test]
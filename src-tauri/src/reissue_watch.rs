@@ -0,0 +1,130 @@
+//! 再販ウォッチ
+//!
+//! ウォッチ中の商品（所持済み商品から登録したもの・手動で登録した商品名）について、
+//! 再入荷・再販のメールやニュースクリップを `reissue_detections` に記録し、デスクトップ通知する。
+//! 常駐中は `run_reissue_watcher` が定期的に検出する。
+
+use sqlx::sqlite::SqlitePool;
+use std::time::Duration;
+use tauri::{Emitter, Manager};
+use tauri_plugin_notification::NotificationExt;
+
+use crate::repository::{ReissueDetection, SqliteReissueRepository};
+
+/// 再販を検出したときにフロントエンドへ送るイベント
+pub const REISSUE_DETECTED_EVENT: &str = "reissue-detected";
+
+/// 起動直後の確認を遅らせる時間（起動処理・初回同期と競合させない）
+const INITIAL_CHECK_DELAY: Duration = Duration::from_secs(150);
+
+/// 確認間隔（1時間）
+const CHECK_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+/// 通知本文に列挙する商品数の上限（超過分は件数のみ）
+const MAX_NOTIFIED_KEYWORDS: usize = 3;
+
+/// デスクトップ通知のタイトルと本文（検出がなければ None）
+///
+/// 所持済み商品の再販を優先して列挙する。同じ商品の複数のアナウンスは 1 件にまとめる。
+pub fn build_notification(detections: &[ReissueDetection]) -> Option<(String, String)> {
+    let mut keywords: Vec<(&str, bool)> = Vec::new();
+    for d in detections {
+        match keywords.iter_mut().find(|(k, _)| *k == d.keyword) {
+            Some(entry) => entry.1 |= d.owned,
+            None => keywords.push((&d.keyword, d.owned)),
+        }
+    }
+    if keywords.is_empty() {
+        return None;
+    }
+    keywords.sort_by_key(|(_, owned)| !owned);
+
+    let title = if keywords.iter().any(|(_, owned)| *owned) {
+        "所持済みの商品が再販されます".to_string()
+    } else {
+        "ウォッチ中の商品の再販情報".to_string()
+    };
+    let mut body = keywords
+        .iter()
+        .take(MAX_NOTIFIED_KEYWORDS)
+        .map(|(k, _)| *k)
+        .collect::<Vec<_>>()
+        .join("／");
+    if keywords.len() > MAX_NOTIFIED_KEYWORDS {
+        body.push_str(&format!(
+            " ほか {}件",
+            keywords.len() - MAX_NOTIFIED_KEYWORDS
+        ));
+    }
+    Some((title, body))
+}
+
+/// 再販を検出し、未通知のものを通知する。新たに検出した件数を返す。
+pub async fn check_and_notify(app: &tauri::AppHandle, pool: &SqlitePool) -> Result<usize, String> {
+    let repo = SqliteReissueRepository::new(pool.clone());
+    let detected = repo.detect().await?;
+
+    let unnotified = repo.list_unnotified().await?;
+    if let Some((title, body)) = build_notification(&unnotified) {
+        let _ = app.notification().builder().title(title).body(body).show();
+        let _ = app.emit(REISSUE_DETECTED_EVENT, &unnotified);
+        let ids: Vec<i64> = unnotified.iter().map(|d| d.id).collect();
+        repo.mark_notified(&ids).await?;
+    }
+    Ok(detected)
+}
+
+/// 常駐中に再販アナウンスを定期的に検出するループ。`setup()` から `tauri::async_runtime::spawn` で起動する。
+pub async fn run_reissue_watcher(app: tauri::AppHandle) {
+    tokio::time::sleep(INITIAL_CHECK_DELAY).await;
+    loop {
+        match app.try_state::<SqlitePool>() {
+            Some(pool) => match check_and_notify(&app, pool.inner()).await {
+                Ok(0) => {}
+                Ok(n) => log::info!("[ReissueWatch] Detected {n} reissue announcement(s)"),
+                Err(e) => log::warn!("[ReissueWatch] Failed to check reissues: {e}"),
+            },
+            None => log::warn!("[ReissueWatch] Database pool is not ready"),
+        }
+        tokio::time::sleep(CHECK_INTERVAL).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn detection(id: i64, keyword: &str, owned: bool) -> ReissueDetection {
+        ReissueDetection {
+            id,
+            watch_id: id,
+            keyword: keyword.to_string(),
+            owned,
+            source: "email".to_string(),
+            source_ref: id.to_string(),
+            title: None,
+            detected_at: "2026-10-16 10:00:00".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_build_notification() {
+        assert_eq!(build_notification(&[]), None);
+
+        let (title, body) = build_notification(&[
+            detection(1, "商品A", false),
+            detection(2, "商品B", true),
+            detection(3, "商品A", false),
+        ])
+        .unwrap();
+        assert_eq!(title, "所持済みの商品が再販されます");
+        assert_eq!(body, "商品B／商品A");
+
+        let detections: Vec<_> = (0..5)
+            .map(|i| detection(i, &format!("商品{i}"), false))
+            .collect();
+        let (title, body) = build_notification(&detections).unwrap();
+        assert_eq!(title, "ウォッチ中の商品の再販情報");
+        assert_eq!(body, "商品0／商品1／商品2 ほか 2件");
+    }
+}
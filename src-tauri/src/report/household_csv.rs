@@ -0,0 +1,338 @@
+//! 家計簿アプリ（Zaim / MoneyForward ME）向けの CSV エクスポート
+//!
+//! 注文 1 件を支出 1 件として、各サービスの CSV インポート形式（日付・金額・カテゴリ・メモ）で書き出す。
+//! カテゴリは既定で「趣味・ホビー」固定とし、対応表を指定した場合は注文内の商品の
+//! カテゴリ（items.category）→ 商品マスタのシリーズ → メーカーの順に照合して置き換える。
+//! 商品マスタ自体はカテゴリを持たないため、AI 解析で得たシリーズ・メーカーを照合に使う。
+//!
+//! 対応表の値は `大項目/中項目` のように `/` で区切ると、Zaim の「カテゴリ / カテゴリの内訳」、
+//! MoneyForward ME の「大項目 / 中項目」に分けて出力する。
+
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+use sqlx::sqlite::SqlitePool;
+use ts_rs::TS;
+
+use crate::repository::{HouseholdExpense, SqliteHouseholdExportRepository};
+
+/// 対応表に一致しない注文のカテゴリ（既定）
+pub const DEFAULT_HOUSEHOLD_CATEGORY: &str = "趣味・ホビー";
+
+/// 出力先の家計簿サービス
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(export)]
+pub enum HouseholdService {
+    /// Zaim（UTF-8）
+    Zaim,
+    /// MoneyForward ME（Shift_JIS）
+    MoneyForward,
+}
+
+/// カテゴリの対応表の 1 行
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct HouseholdCategoryMapping {
+    /// 商品のカテゴリ・シリーズ・メーカー（完全一致）
+    pub key: String,
+    /// 家計簿のカテゴリ（`大項目/中項目` 形式も可）
+    pub category: String,
+}
+
+/// エクスポートの条件
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct HouseholdCsvOptions {
+    pub service: HouseholdService,
+    /// 注文日の範囲（YYYY-MM-DD、省略時は無制限）
+    pub from: Option<String>,
+    pub to: Option<String>,
+    /// 対応表に一致しない注文のカテゴリ（省略時は「趣味・ホビー」）
+    pub default_category: Option<String>,
+    /// 空なら全注文を `default_category` にする
+    pub category_map: Vec<HouseholdCategoryMapping>,
+}
+
+/// 注文のカテゴリ。小計の大きい商品から順に、対応表に一致した最初のカテゴリを使う。
+pub fn resolve_category(expense: &HouseholdExpense, options: &HouseholdCsvOptions) -> String {
+    let mapped = expense.items.iter().find_map(|item| {
+        [&item.category, &item.series, &item.maker]
+            .into_iter()
+            .flatten()
+            .find_map(|key| {
+                options
+                    .category_map
+                    .iter()
+                    .find(|m| m.key.trim() == key.trim())
+                    .map(|m| m.category.trim().to_string())
+            })
+    });
+    mapped
+        .or_else(|| {
+            options
+                .default_category
+                .as_deref()
+                .map(str::trim)
+                .filter(|c| !c.is_empty())
+                .map(str::to_string)
+        })
+        .unwrap_or_else(|| DEFAULT_HOUSEHOLD_CATEGORY.to_string())
+}
+
+/// `大項目/中項目` を分割する（中項目がなければ空文字）
+fn split_category(category: &str) -> (&str, &str) {
+    match category.split_once('/') {
+        Some((main, sub)) => (main.trim(), sub.trim()),
+        None => (category.trim(), ""),
+    }
+}
+
+/// 品目（先頭の商品名、複数なら「ほか N 点」）
+fn item_summary(expense: &HouseholdExpense) -> String {
+    match expense.items.as_slice() {
+        [] => String::new(),
+        [only] => only.item_name.clone(),
+        [first, rest @ ..] => format!("{} ほか{}点", first.item_name, rest.len()),
+    }
+}
+
+fn memo(expense: &HouseholdExpense) -> String {
+    expense
+        .order_number
+        .as_deref()
+        .filter(|n| !n.is_empty())
+        .map(|n| format!("注文番号 {n}"))
+        .unwrap_or_default()
+}
+
+/// CSV のフィールド（カンマ・引用符・改行を含む場合のみ引用符で囲む）
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+fn csv_line(fields: &[String]) -> String {
+    let mut line = fields
+        .iter()
+        .map(|f| csv_field(f))
+        .collect::<Vec<_>>()
+        .join(",");
+    line.push_str("\r\n");
+    line
+}
+
+/// CSV の本文を組み立てる
+pub fn render_household_csv(
+    expenses: &[HouseholdExpense],
+    options: &HouseholdCsvOptions,
+) -> String {
+    let mut csv = String::new();
+    match options.service {
+        HouseholdService::Zaim => {
+            csv.push_str(
+                "日付,方法,カテゴリ,カテゴリの内訳,支払元,入金先,品目,メモ,お店,通貨,収入,支出\r\n",
+            );
+            for expense in expenses {
+                let category = resolve_category(expense, options);
+                let (main, sub) = split_category(&category);
+                csv.push_str(&csv_line(&[
+                    expense.order_date.clone(),
+                    "payment".to_string(),
+                    main.to_string(),
+                    sub.to_string(),
+                    String::new(),
+                    String::new(),
+                    item_summary(expense),
+                    memo(expense),
+                    expense.shop_name.clone().unwrap_or_default(),
+                    "JPY".to_string(),
+                    "0".to_string(),
+                    expense.amount.to_string(),
+                ]));
+            }
+        }
+        HouseholdService::MoneyForward => {
+            csv.push_str(
+                "計算対象,日付,内容,金額（円）,保有金融機関,大項目,中項目,メモ,振替,ID\r\n",
+            );
+            for expense in expenses {
+                let category = resolve_category(expense, options);
+                let (main, sub) = split_category(&category);
+                let content = match expense.shop_name.as_deref().filter(|s| !s.is_empty()) {
+                    Some(shop) => format!("{shop} {}", item_summary(expense)),
+                    None => item_summary(expense),
+                };
+                csv.push_str(&csv_line(&[
+                    "1".to_string(),
+                    expense.order_date.replace('-', "/"),
+                    content.trim().to_string(),
+                    // 支出は負の金額
+                    (-expense.amount).to_string(),
+                    String::new(),
+                    main.to_string(),
+                    sub.to_string(),
+                    memo(expense),
+                    "0".to_string(),
+                    String::new(),
+                ]));
+            }
+        }
+    }
+    csv
+}
+
+/// サービスのインポートが受け付ける文字コードにする（Zaim は BOM 付き UTF-8、MoneyForward ME は Shift_JIS）
+fn encode_csv(csv: &str, service: HouseholdService) -> Vec<u8> {
+    match service {
+        HouseholdService::Zaim => {
+            let mut bytes = "\u{feff}".as_bytes().to_vec();
+            bytes.extend_from_slice(csv.as_bytes());
+            bytes
+        }
+        HouseholdService::MoneyForward => encoding_rs::SHIFT_JIS.encode(csv).0.into_owned(),
+    }
+}
+
+/// 条件に合う注文を家計簿 CSV として `path` に書き出し、出力した支出の件数を返す
+pub async fn export_household_csv(
+    pool: &SqlitePool,
+    options: &HouseholdCsvOptions,
+    path: &Path,
+) -> Result<usize, String> {
+    let trimmed = |v: &Option<String>| {
+        v.as_deref()
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+            .map(str::to_string)
+    };
+    let (from, to) = (trimmed(&options.from), trimmed(&options.to));
+    for date in [&from, &to].into_iter().flatten() {
+        chrono::NaiveDate::parse_from_str(date, "%Y-%m-%d")
+            .map_err(|_| format!("Invalid date: {date} (expected YYYY-MM-DD)"))?;
+    }
+
+    let expenses = SqliteHouseholdExportRepository::new(pool.clone())
+        .list_expenses(from.as_deref(), to.as_deref())
+        .await?;
+    let bytes = encode_csv(&render_household_csv(&expenses, options), options.service);
+    tokio::fs::write(path, bytes)
+        .await
+        .map_err(|e| format!("Failed to write {}: {e}", path.display()))?;
+
+    log::info!(
+        "Exported {} household expense(s) to {}",
+        expenses.len(),
+        path.display()
+    );
+    Ok(expenses.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repository::HouseholdExpenseItem;
+
+    fn item(name: &str, category: Option<&str>, series: Option<&str>) -> HouseholdExpenseItem {
+        HouseholdExpenseItem {
+            item_name: name.to_string(),
+            subtotal: 1000,
+            category: category.map(str::to_string),
+            series: series.map(str::to_string),
+            maker: None,
+        }
+    }
+
+    fn expense(items: Vec<HouseholdExpenseItem>) -> HouseholdExpense {
+        HouseholdExpense {
+            order_id: 1,
+            order_date: "2025-03-10".to_string(),
+            shop_name: Some("ホビーショップ".to_string()),
+            order_number: Some("A-1".to_string()),
+            amount: 8660,
+            items,
+        }
+    }
+
+    fn options(service: HouseholdService, category_map: Vec<(&str, &str)>) -> HouseholdCsvOptions {
+        HouseholdCsvOptions {
+            service,
+            from: None,
+            to: None,
+            default_category: None,
+            category_map: category_map
+                .into_iter()
+                .map(|(key, category)| HouseholdCategoryMapping {
+                    key: key.to_string(),
+                    category: category.to_string(),
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_resolve_category() {
+        let e = expense(vec![
+            item("ねんどろいど 初音ミク", None, None),
+            item("HG ザク", None, Some("ガンプラ")),
+        ]);
+        assert_eq!(
+            resolve_category(&e, &options(HouseholdService::Zaim, vec![])),
+            DEFAULT_HOUSEHOLD_CATEGORY
+        );
+        assert_eq!(
+            resolve_category(
+                &e,
+                &options(
+                    HouseholdService::Zaim,
+                    vec![("ガンプラ", "趣味・娯楽/プラモデル")]
+                )
+            ),
+            "趣味・娯楽/プラモデル"
+        );
+
+        let mut opts = options(HouseholdService::Zaim, vec![("フィギュア", "フィギュア")]);
+        opts.default_category = Some(" 趣味・娯楽 ".to_string());
+        assert_eq!(resolve_category(&e, &opts), "趣味・娯楽");
+    }
+
+    #[test]
+    fn test_render_zaim_csv() {
+        let e = expense(vec![item("ねんどろいど 初音ミク, 特典付き", None, None)]);
+        let csv = render_household_csv(&[e], &options(HouseholdService::Zaim, vec![]));
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(
+            lines[1],
+            "2025-03-10,payment,趣味・ホビー,,,,\"ねんどろいど 初音ミク, 特典付き\",注文番号 A-1,ホビーショップ,JPY,0,8660"
+        );
+    }
+
+    #[test]
+    fn test_render_money_forward_csv() {
+        let e = expense(vec![
+            item("HG ザク", None, Some("ガンプラ")),
+            item("HG グフ", None, None),
+        ]);
+        let csv = render_household_csv(
+            &[e],
+            &options(
+                HouseholdService::MoneyForward,
+                vec![("ガンプラ", "趣味・娯楽/ホビー")],
+            ),
+        );
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(
+            lines[1],
+            "1,2025/03/10,ホビーショップ HG ザク ほか1点,-8660,,趣味・娯楽,ホビー,注文番号 A-1,0,"
+        );
+
+        let bytes = encode_csv(&csv, HouseholdService::MoneyForward);
+        let (decoded, _, had_errors) = encoding_rs::SHIFT_JIS.decode(&bytes);
+        assert!(!had_errors);
+        assert_eq!(decoded, csv);
+    }
+}
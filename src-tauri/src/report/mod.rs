@@ -0,0 +1,6 @@
+//! レポート出力（フロントエンドを介さずに生成する画像など）
+
+pub mod household_csv;
+pub mod monthly_summary;
+pub mod order_document;
+pub mod spending_chart;
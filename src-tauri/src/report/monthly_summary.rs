@@ -0,0 +1,324 @@
+//! 月初の「先月のまとめ」レポート
+//!
+//! 先月の支出・到着した商品と、今月お届け予定の商品をまとめ、Markdown として
+//! `monthly_reports` に保存する。常駐中は `run_monthly_report_scheduler` が
+//! 月が変わったことを検知して先月分を自動生成し、デスクトップ通知する。
+//!
+//! 発売日を保持していないため、今月の予定は配送のお届け予定日（`estimated_delivery`）から求める。
+
+use chrono::{Datelike, NaiveDate};
+use serde::{Deserialize, Serialize};
+use sqlx::sqlite::SqlitePool;
+use std::time::Duration;
+use tauri::{Emitter, Manager};
+use tauri_plugin_notification::NotificationExt;
+use ts_rs::TS;
+
+use crate::report::spending_chart::format_amount;
+use crate::repository::{
+    SpendingStatsRepository, SqliteMonthlyReportRepository, SqliteSpendingStatsRepository,
+    UpcomingItem,
+};
+
+/// レポート生成時にフロントエンドへ送るイベント
+pub const MONTHLY_REPORT_GENERATED_EVENT: &str = "monthly-report-generated";
+
+/// 起動直後の確認を遅らせる時間（起動処理・初回同期と競合させない）
+const INITIAL_CHECK_DELAY: Duration = Duration::from_secs(120);
+
+/// 月替わりの確認間隔（1時間）
+const CHECK_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+/// Markdown に列挙する商品数の上限（超過分は件数のみ）
+const MAX_LISTED_ITEMS: usize = 30;
+
+/// 「先月のまとめ」の内容
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct MonthlySummary {
+    /// 対象月（YYYY-MM）
+    pub month: String,
+    /// 対象月の支出（税込）
+    pub total_amount: i64,
+    pub order_count: i64,
+    /// 対象月に到着した商品名
+    pub delivered_items: Vec<String>,
+    /// 翌月（レポート生成月, YYYY-MM）
+    pub upcoming_month: String,
+    /// 翌月お届け予定の商品
+    pub upcoming_items: Vec<UpcomingItem>,
+}
+
+/// 生成したレポート
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct MonthlyReport {
+    pub summary: MonthlySummary,
+    pub markdown: String,
+}
+
+fn first_day_of_month(date: NaiveDate) -> NaiveDate {
+    date.with_day(1).unwrap_or(date)
+}
+
+fn next_month(first_day: NaiveDate) -> NaiveDate {
+    first_day
+        .checked_add_months(chrono::Months::new(1))
+        .unwrap_or(first_day)
+}
+
+/// `today` の前月の 1 日
+pub fn previous_month(today: NaiveDate) -> NaiveDate {
+    let first = first_day_of_month(today);
+    first
+        .checked_sub_months(chrono::Months::new(1))
+        .unwrap_or(first)
+}
+
+/// `YYYY-MM` をその月の 1 日に変換する
+pub fn parse_month(month: &str) -> Result<NaiveDate, String> {
+    NaiveDate::parse_from_str(&format!("{}-01", month.trim()), "%Y-%m-%d")
+        .map_err(|_| format!("Invalid month: {month} (expected YYYY-MM)"))
+}
+
+fn format_month(first_day: NaiveDate) -> String {
+    first_day.format("%Y-%m").to_string()
+}
+
+fn format_date(date: NaiveDate) -> String {
+    date.format("%Y-%m-%d").to_string()
+}
+
+/// デスクトップ通知のタイトルと本文
+pub fn build_notification(summary: &MonthlySummary) -> (String, String) {
+    let title = format!("{} のまとめ", summary.month);
+    let body = format!(
+        "支出 ¥{}（{}件）／到着 {}点／{} のお届け予定 {}点",
+        format_amount(summary.total_amount),
+        summary.order_count,
+        summary.delivered_items.len(),
+        summary.upcoming_month,
+        summary.upcoming_items.len()
+    );
+    (title, body)
+}
+
+fn push_list(markdown: &mut String, lines: Vec<String>) {
+    if lines.is_empty() {
+        markdown.push_str("- なし\n");
+        return;
+    }
+    let total = lines.len();
+    for line in lines.into_iter().take(MAX_LISTED_ITEMS) {
+        markdown.push_str(&format!("- {line}\n"));
+    }
+    if total > MAX_LISTED_ITEMS {
+        markdown.push_str(&format!("- ほか {}点\n", total - MAX_LISTED_ITEMS));
+    }
+}
+
+/// レポート本文（Markdown）を組み立てる
+pub fn render_markdown(summary: &MonthlySummary) -> String {
+    let mut markdown = format!("# {} のまとめ\n\n", summary.month);
+
+    markdown.push_str("## 支出\n\n");
+    markdown.push_str(&format!(
+        "- 合計: ¥{}\n- 注文数: {}件\n\n",
+        format_amount(summary.total_amount),
+        summary.order_count
+    ));
+
+    markdown.push_str(&format!(
+        "## 到着した商品（{}点）\n\n",
+        summary.delivered_items.len()
+    ));
+    push_list(&mut markdown, summary.delivered_items.clone());
+
+    markdown.push_str(&format!(
+        "\n## {} のお届け予定（{}点）\n\n",
+        summary.upcoming_month,
+        summary.upcoming_items.len()
+    ));
+    push_list(
+        &mut markdown,
+        summary
+            .upcoming_items
+            .iter()
+            .map(|item| match &item.shop_name {
+                Some(shop) => format!("{} {}（{}）", item.estimated_delivery, item.item_name, shop),
+                None => format!("{} {}", item.estimated_delivery, item.item_name),
+            })
+            .collect(),
+    );
+
+    markdown
+}
+
+/// `month_first`（対象月の 1 日）のまとめを DB から集計する
+pub async fn build_monthly_summary(
+    pool: &SqlitePool,
+    month_first: NaiveDate,
+) -> Result<MonthlySummary, String> {
+    let upcoming_first = next_month(month_first);
+    let upcoming_end = next_month(upcoming_first);
+    let (from, to) = (format_date(month_first), format_date(upcoming_first));
+
+    let spending = SqliteSpendingStatsRepository::new(pool.clone())
+        .get_monthly_spending(Some(from.clone()), Some(to.clone()))
+        .await?;
+    let (total_amount, order_count) = spending.iter().fold((0, 0), |(amount, count), m| {
+        (amount + m.total_amount, count + m.order_count)
+    });
+
+    let repo = SqliteMonthlyReportRepository::new(pool.clone());
+    let delivered_items = repo.get_delivered_item_names(&from, &to).await?;
+    let upcoming_items = repo
+        .get_upcoming_items(&to, &format_date(upcoming_end))
+        .await?;
+
+    Ok(MonthlySummary {
+        month: format_month(month_first),
+        total_amount,
+        order_count,
+        delivered_items,
+        upcoming_month: format_month(upcoming_first),
+        upcoming_items,
+    })
+}
+
+/// 対象月のレポートを生成して保存する（同じ月は上書き）
+pub async fn generate_monthly_report(
+    pool: &SqlitePool,
+    month_first: NaiveDate,
+) -> Result<MonthlyReport, String> {
+    let summary = build_monthly_summary(pool, month_first).await?;
+    let markdown = render_markdown(&summary);
+    let summary_json = serde_json::to_string(&summary)
+        .map_err(|e| format!("Failed to serialize monthly summary: {e}"))?;
+    SqliteMonthlyReportRepository::new(pool.clone())
+        .save(&summary.month, &summary_json, &markdown)
+        .await?;
+    Ok(MonthlyReport { summary, markdown })
+}
+
+/// 日本時間の今日
+pub fn today_jst() -> NaiveDate {
+    chrono::Utc::now()
+        .with_timezone(&chrono_tz::Asia::Tokyo)
+        .date_naive()
+}
+
+/// 自動生成が有効か（設定読み込みに失敗した場合は有効扱い）
+fn auto_report_enabled(app: &tauri::AppHandle) -> bool {
+    app.path()
+        .app_config_dir()
+        .map_err(|e| format!("Failed to get app config dir: {e}"))
+        .and_then(|dir| crate::config::load(&dir))
+        .map(|c| c.monthly_report.enabled)
+        .unwrap_or(true)
+}
+
+/// 先月分が未生成なら生成して通知する
+async fn generate_if_due(app: &tauri::AppHandle, pool: &SqlitePool) -> Result<(), String> {
+    let month_first = previous_month(today_jst());
+    let month = format_month(month_first);
+    if SqliteMonthlyReportRepository::new(pool.clone())
+        .exists(&month)
+        .await?
+    {
+        return Ok(());
+    }
+
+    let report = generate_monthly_report(pool, month_first).await?;
+    log::info!("[MonthlyReport] Generated report for {month}");
+    let (title, body) = build_notification(&report.summary);
+    let _ = app.notification().builder().title(title).body(body).show();
+    let _ = app.emit(MONTHLY_REPORT_GENERATED_EVENT, report);
+    Ok(())
+}
+
+/// 常駐中に月替わりを検知して先月のまとめを生成するループ。`setup()` から `tauri::async_runtime::spawn` で起動する。
+///
+/// 1日にアプリが起動していなかった場合も、その月の最初の確認時に生成する。
+pub async fn run_monthly_report_scheduler(app: tauri::AppHandle) {
+    tokio::time::sleep(INITIAL_CHECK_DELAY).await;
+    loop {
+        if auto_report_enabled(&app) {
+            match app.try_state::<SqlitePool>() {
+                Some(pool) => {
+                    if let Err(e) = generate_if_due(&app, pool.inner()).await {
+                        log::warn!("[MonthlyReport] Failed to generate monthly report: {e}");
+                    }
+                }
+                None => log::warn!("[MonthlyReport] Database pool is not ready"),
+            }
+        }
+        tokio::time::sleep(CHECK_INTERVAL).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    fn summary() -> MonthlySummary {
+        MonthlySummary {
+            month: "2026-09".to_string(),
+            total_amount: 123_456,
+            order_count: 4,
+            delivered_items: vec!["商品A".to_string(), "商品B".to_string()],
+            upcoming_month: "2026-10".to_string(),
+            upcoming_items: vec![UpcomingItem {
+                item_name: "商品C".to_string(),
+                shop_name: Some("ショップX".to_string()),
+                estimated_delivery: "2026-10-20".to_string(),
+            }],
+        }
+    }
+
+    #[test]
+    fn test_previous_month() {
+        assert_eq!(previous_month(date(2026, 10, 1)), date(2026, 9, 1));
+        assert_eq!(previous_month(date(2026, 1, 31)), date(2025, 12, 1));
+        assert_eq!(next_month(date(2025, 12, 1)), date(2026, 1, 1));
+    }
+
+    #[test]
+    fn test_parse_month() {
+        assert_eq!(parse_month("2026-09").unwrap(), date(2026, 9, 1));
+        assert!(parse_month("2026-13").is_err());
+        assert!(parse_month("202609").is_err());
+    }
+
+    #[test]
+    fn test_build_notification() {
+        let (title, body) = build_notification(&summary());
+        assert_eq!(title, "2026-09 のまとめ");
+        assert_eq!(
+            body,
+            "支出 ¥123,456（4件）／到着 2点／2026-10 のお届け予定 1点"
+        );
+    }
+
+    #[test]
+    fn test_render_markdown() {
+        let markdown = render_markdown(&summary());
+        assert!(markdown.starts_with("# 2026-09 のまとめ\n"));
+        assert!(markdown.contains("- 合計: ¥123,456\n"));
+        assert!(markdown.contains("## 到着した商品（2点）\n\n- 商品A\n- 商品B\n"));
+        assert!(markdown.contains("- 2026-10-20 商品C（ショップX）\n"));
+
+        let mut empty = summary();
+        empty.delivered_items = (0..MAX_LISTED_ITEMS + 2)
+            .map(|i| format!("商品{i}"))
+            .collect();
+        empty.upcoming_items.clear();
+        let markdown = render_markdown(&empty);
+        assert!(markdown.contains("- ほか 2点\n"));
+        assert!(markdown.ends_with("- なし\n"));
+    }
+}
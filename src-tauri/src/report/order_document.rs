@@ -0,0 +1,454 @@
+//! 注文情報の印刷用書類（HTML / PDF）
+//!
+//! 問い合わせや保証申請に添付するため、注文の商品・金額・関連メールの受信日・追跡番号を
+//! 1 つの書類にまとめる。出力形式は保存先の拡張子で決める（`.pdf` なら PDF、それ以外は HTML）。
+//!
+//! PDF は外部クレートを使わずに生成する。フォントは PDF ビューア標準の日本語フォント
+//! （HeiseiKakuGo-W5 / UniJIS-UCS2-H）を参照し、埋め込まない。
+
+use std::path::Path;
+
+use chrono::DateTime;
+
+use crate::report::spending_chart::format_amount;
+use crate::repository::OrderDocument;
+
+/// A4 縦（pt）
+const PAGE_WIDTH: f64 = 595.0;
+const PAGE_HEIGHT: f64 = 842.0;
+const MARGIN: f64 = 50.0;
+const FONT_SIZE: f64 = 10.5;
+const HEADING_SIZE: f64 = 14.0;
+const LINE_HEIGHT: f64 = 16.0;
+/// 1 行に収める全角文字数（半角は 0.5 文字として数える）
+const MAX_LINE_WIDTH: f64 = (PAGE_WIDTH - MARGIN * 2.0) / FONT_SIZE;
+
+/// 配送状況の表示名
+fn delivery_status_label(status: &str) -> &str {
+    match status {
+        "not_shipped" => "未発送",
+        "preparing" => "発送準備中",
+        "shipped" => "発送済み",
+        "in_transit" => "配送中",
+        "out_for_delivery" => "配達中",
+        "delivered" => "配達完了",
+        "failed" => "配達失敗",
+        "returned" => "返送",
+        "cancelled" => "キャンセル",
+        other => other,
+    }
+}
+
+/// メール受信日時（日本時間）
+fn format_received_at(internal_date: Option<i64>) -> String {
+    internal_date
+        .and_then(DateTime::from_timestamp_millis)
+        .map(|dt| {
+            dt.with_timezone(&chrono_tz::Asia::Tokyo)
+                .format("%Y-%m-%d %H:%M")
+                .to_string()
+        })
+        .unwrap_or_else(|| "-".to_string())
+}
+
+fn or_dash(value: Option<&str>) -> &str {
+    value.filter(|v| !v.is_empty()).unwrap_or("-")
+}
+
+fn yen(value: i64) -> String {
+    format!("{}円", format_amount(value))
+}
+
+/// PDF に書き出す見出し・本文行
+enum Line {
+    Heading(String),
+    Text(String),
+    Blank,
+}
+
+fn document_lines(doc: &OrderDocument) -> Vec<Line> {
+    let mut lines = vec![
+        Line::Heading("注文情報".to_string()),
+        Line::Text(format!("ショップ: {}", or_dash(doc.shop_name.as_deref()))),
+        Line::Text(format!(
+            "注文番号: {}",
+            or_dash(doc.order_number.as_deref())
+        )),
+        Line::Text(format!("注文日: {}", or_dash(doc.order_date.as_deref()))),
+        Line::Blank,
+        Line::Heading("商品".to_string()),
+    ];
+    for item in &doc.items {
+        lines.push(Line::Text(format!(
+            "{}  {} × {} = {}",
+            item.name,
+            yen(item.price),
+            item.quantity,
+            yen(item.price * item.quantity)
+        )));
+    }
+    lines.push(Line::Blank);
+    lines.push(Line::Heading("金額".to_string()));
+    lines.extend(
+        amount_rows(doc)
+            .into_iter()
+            .map(|(label, value)| Line::Text(format!("{label}: {value}"))),
+    );
+    lines.push(Line::Blank);
+    lines.push(Line::Heading("配送".to_string()));
+    if doc.deliveries.is_empty() {
+        lines.push(Line::Text("-".to_string()));
+    }
+    for d in &doc.deliveries {
+        lines.push(Line::Text(format!(
+            "{}  追跡番号: {}  状況: {}",
+            or_dash(d.carrier.as_deref()),
+            or_dash(d.tracking_number.as_deref()),
+            delivery_status_label(&d.delivery_status)
+        )));
+    }
+    lines.push(Line::Blank);
+    lines.push(Line::Heading("関連メール".to_string()));
+    if doc.emails.is_empty() {
+        lines.push(Line::Text("-".to_string()));
+    }
+    for e in &doc.emails {
+        lines.push(Line::Text(format!(
+            "{}  {}",
+            format_received_at(e.internal_date),
+            or_dash(e.subject.as_deref())
+        )));
+    }
+    lines
+}
+
+/// 金額欄（ラベル, 表示値）
+fn amount_rows(doc: &OrderDocument) -> Vec<(&'static str, String)> {
+    let mut rows = vec![("商品合計", yen(doc.items_amount()))];
+    if !doc.tax_included {
+        rows.push(("消費税", yen(doc.added_tax())));
+    }
+    if doc.amount_adjustment != 0 {
+        rows.push(("送料・割引等", yen(doc.amount_adjustment)));
+    }
+    rows.push(("合計", yen(doc.total_amount())));
+    rows
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// 印刷向けの HTML を生成する
+pub fn render_order_html(doc: &OrderDocument) -> String {
+    let mut html =
+        String::from("<!DOCTYPE html>\n<html lang=\"ja\">\n<head>\n<meta charset=\"utf-8\">\n");
+    html.push_str(&format!(
+        "<title>注文情報 {}</title>\n",
+        escape_html(or_dash(doc.order_number.as_deref()))
+    ));
+    html.push_str(
+        "<style>\n\
+         body { font-family: sans-serif; font-size: 10.5pt; margin: 20mm; }\n\
+         h1 { font-size: 16pt; } h2 { font-size: 12pt; margin-top: 1.5em; }\n\
+         table { border-collapse: collapse; width: 100%; }\n\
+         th, td { border: 1px solid #999; padding: 4px 6px; text-align: left; }\n\
+         td.num { text-align: right; white-space: nowrap; }\n\
+         @page { size: A4; margin: 15mm; }\n\
+         </style>\n</head>\n<body>\n",
+    );
+    html.push_str("<h1>注文情報</h1>\n<table>\n");
+    for (label, value) in [
+        ("ショップ", doc.shop_name.as_deref()),
+        ("注文番号", doc.order_number.as_deref()),
+        ("注文日", doc.order_date.as_deref()),
+    ] {
+        html.push_str(&format!(
+            "<tr><th>{label}</th><td>{}</td></tr>\n",
+            escape_html(or_dash(value))
+        ));
+    }
+    html.push_str("</table>\n");
+
+    html.push_str(
+        "<h2>商品</h2>\n<table>\n<tr><th>商品名</th><th>単価</th><th>数量</th><th>小計</th></tr>\n",
+    );
+    for item in &doc.items {
+        html.push_str(&format!(
+            "<tr><td>{}</td><td class=\"num\">{}</td><td class=\"num\">{}</td><td class=\"num\">{}</td></tr>\n",
+            escape_html(&item.name),
+            yen(item.price),
+            item.quantity,
+            yen(item.price * item.quantity)
+        ));
+    }
+    html.push_str("</table>\n");
+
+    html.push_str("<h2>金額</h2>\n<table>\n");
+    for (label, value) in amount_rows(doc) {
+        html.push_str(&format!(
+            "<tr><th>{label}</th><td class=\"num\">{value}</td></tr>\n"
+        ));
+    }
+    html.push_str("</table>\n");
+
+    html.push_str(
+        "<h2>配送</h2>\n<table>\n<tr><th>配送業者</th><th>追跡番号</th><th>状況</th></tr>\n",
+    );
+    for d in &doc.deliveries {
+        html.push_str(&format!(
+            "<tr><td>{}</td><td>{}</td><td>{}</td></tr>\n",
+            escape_html(or_dash(d.carrier.as_deref())),
+            escape_html(or_dash(d.tracking_number.as_deref())),
+            escape_html(delivery_status_label(&d.delivery_status))
+        ));
+    }
+    html.push_str("</table>\n");
+
+    html.push_str("<h2>関連メール</h2>\n<table>\n<tr><th>受信日時</th><th>件名</th></tr>\n");
+    for e in &doc.emails {
+        html.push_str(&format!(
+            "<tr><td>{}</td><td>{}</td></tr>\n",
+            format_received_at(e.internal_date),
+            escape_html(or_dash(e.subject.as_deref()))
+        ));
+    }
+    html.push_str("</table>\n</body>\n</html>\n");
+    html
+}
+
+/// 文字の表示幅（全角 = 1.0、半角 = 0.5）
+fn char_width(c: char) -> f64 {
+    if c.is_ascii() || ('\u{FF61}'..='\u{FF9F}').contains(&c) {
+        0.5
+    } else {
+        1.0
+    }
+}
+
+/// 1 行の幅に収まるよう折り返す
+fn wrap_line(text: &str, max_width: f64) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut current = String::new();
+    let mut width = 0.0;
+    for c in text.chars() {
+        let w = char_width(c);
+        if width + w > max_width && !current.is_empty() {
+            lines.push(std::mem::take(&mut current));
+            width = 0.0;
+        }
+        current.push(c);
+        width += w;
+    }
+    lines.push(current);
+    lines
+}
+
+/// UniJIS-UCS2-H 用の 16 進文字列（UCS-2 で表せない文字は `?` にする）
+fn pdf_hex_string(text: &str) -> String {
+    let mut hex = String::from("<");
+    for c in text.chars() {
+        let code = if (c as u32) <= 0xFFFF {
+            c as u32
+        } else {
+            '?' as u32
+        };
+        hex.push_str(&format!("{code:04X}"));
+    }
+    hex.push('>');
+    hex
+}
+
+/// 書類をページごとの PDF コンテンツストリームに変換する
+fn pdf_page_streams(doc: &OrderDocument) -> Vec<String> {
+    let mut pages: Vec<String> = Vec::new();
+    let mut stream = String::new();
+    let mut y = PAGE_HEIGHT - MARGIN;
+
+    for line in document_lines(doc) {
+        let (size, texts) = match line {
+            Line::Heading(text) => (HEADING_SIZE, vec![text]),
+            Line::Text(text) => (FONT_SIZE, wrap_line(&text, MAX_LINE_WIDTH)),
+            Line::Blank => {
+                y -= LINE_HEIGHT / 2.0;
+                continue;
+            }
+        };
+        for text in texts {
+            if y < MARGIN + LINE_HEIGHT {
+                pages.push(std::mem::take(&mut stream));
+                y = PAGE_HEIGHT - MARGIN;
+            }
+            y -= LINE_HEIGHT;
+            stream.push_str(&format!(
+                "BT /F1 {size} Tf {MARGIN} {y:.1} Td {} Tj ET\n",
+                pdf_hex_string(&text)
+            ));
+        }
+    }
+    pages.push(stream);
+    pages
+}
+
+/// PDF を生成する
+pub fn render_order_pdf(doc: &OrderDocument) -> Vec<u8> {
+    let streams = pdf_page_streams(doc);
+    // 1: Catalog, 2: Pages, 3-5: フォント, 以降はページごとに Page + Contents
+    let first_page_obj = 6;
+    let kids: Vec<String> = (0..streams.len())
+        .map(|i| format!("{} 0 R", first_page_obj + i * 2))
+        .collect();
+
+    let mut objects: Vec<String> = vec![
+        "<< /Type /Catalog /Pages 2 0 R >>".to_string(),
+        format!(
+            "<< /Type /Pages /Kids [{}] /Count {} >>",
+            kids.join(" "),
+            streams.len()
+        ),
+        "<< /Type /Font /Subtype /Type0 /BaseFont /HeiseiKakuGo-W5 /Encoding /UniJIS-UCS2-H /DescendantFonts [4 0 R] >>".to_string(),
+        "<< /Type /Font /Subtype /CIDFontType0 /BaseFont /HeiseiKakuGo-W5 /CIDSystemInfo << /Registry (Adobe) /Ordering (Japan1) /Supplement 2 >> /FontDescriptor 5 0 R /DW 1000 /W [1 95 500] >>".to_string(),
+        "<< /Type /FontDescriptor /FontName /HeiseiKakuGo-W5 /Flags 4 /FontBBox [-92 -250 1010 922] /ItalicAngle 0 /Ascent 880 /Descent -120 /CapHeight 737 /StemV 69 >>".to_string(),
+    ];
+    for (i, stream) in streams.iter().enumerate() {
+        objects.push(format!(
+            "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 {PAGE_WIDTH} {PAGE_HEIGHT}] /Resources << /Font << /F1 3 0 R >> >> /Contents {} 0 R >>",
+            first_page_obj + i * 2 + 1
+        ));
+        objects.push(format!(
+            "<< /Length {} >>\nstream\n{stream}endstream",
+            stream.len()
+        ));
+    }
+
+    let mut pdf = String::from("%PDF-1.4\n");
+    let mut offsets = Vec::with_capacity(objects.len());
+    for (i, object) in objects.iter().enumerate() {
+        offsets.push(pdf.len());
+        pdf.push_str(&format!("{} 0 obj\n{object}\nendobj\n", i + 1));
+    }
+    let xref_offset = pdf.len();
+    pdf.push_str(&format!(
+        "xref\n0 {}\n0000000000 65535 f \n",
+        objects.len() + 1
+    ));
+    for offset in offsets {
+        pdf.push_str(&format!("{offset:010} 00000 n \n"));
+    }
+    pdf.push_str(&format!(
+        "trailer\n<< /Size {} /Root 1 0 R >>\nstartxref\n{xref_offset}\n%%EOF\n",
+        objects.len() + 1
+    ));
+    pdf.into_bytes()
+}
+
+/// 拡張子に応じて HTML または PDF を `path` に保存する
+pub fn write_order_document(doc: &OrderDocument, path: &Path) -> Result<(), String> {
+    let is_pdf = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|e| e.eq_ignore_ascii_case("pdf"));
+    let bytes = if is_pdf {
+        render_order_pdf(doc)
+    } else {
+        render_order_html(doc).into_bytes()
+    };
+    std::fs::write(path, bytes).map_err(|e| format!("Failed to write {}: {e}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repository::{OrderDocumentDelivery, OrderDocumentEmail, OrderDocumentItem};
+
+    fn sample_document() -> OrderDocument {
+        OrderDocument {
+            order_id: 1,
+            shop_name: Some("テスト<ショップ>".to_string()),
+            shop_domain: Some("example.com".to_string()),
+            order_number: Some("ORD-1".to_string()),
+            order_date: Some("2024-05-01".to_string()),
+            tax_amount: None,
+            tax_included: true,
+            amount_adjustment: 660,
+            items: vec![OrderDocumentItem {
+                name: "HG 1/144 ガンダム".to_string(),
+                price: 1650,
+                quantity: 2,
+            }],
+            deliveries: vec![OrderDocumentDelivery {
+                carrier: Some("ヤマト運輸".to_string()),
+                tracking_number: Some("123456789012".to_string()),
+                delivery_status: "delivered".to_string(),
+            }],
+            emails: vec![OrderDocumentEmail {
+                subject: Some("ご注文確認".to_string()),
+                // 2024-05-01 00:00 JST
+                internal_date: Some(1714489200000),
+            }],
+        }
+    }
+
+    #[test]
+    fn test_render_order_html() {
+        let html = render_order_html(&sample_document());
+        assert!(html.contains("テスト&lt;ショップ&gt;"));
+        assert!(html.contains("HG 1/144 ガンダム"));
+        assert!(html.contains("3,300円"));
+        // 合計は商品合計 + 調整額
+        assert!(html.contains("3,960円"));
+        assert!(html.contains("123456789012"));
+        assert!(html.contains("配達完了"));
+        assert!(html.contains("2024-05-01 00:00"));
+    }
+
+    #[test]
+    fn test_render_order_pdf_structure() {
+        let pdf = String::from_utf8(render_order_pdf(&sample_document())).unwrap();
+        assert!(pdf.starts_with("%PDF-1.4"));
+        assert!(pdf.ends_with("%%EOF\n"));
+        assert!(pdf.contains("/Count 1"));
+        // 追跡番号が UCS-2 の 16 進文字列で含まれること
+        let hex = pdf_hex_string("123456789012");
+        assert!(pdf.contains(hex.trim_start_matches('<').trim_end_matches('>')));
+
+        // xref のオフセットが各オブジェクトの位置を指していること
+        let xref_at: usize = pdf
+            .rsplit("startxref\n")
+            .next()
+            .and_then(|s| s.lines().next())
+            .and_then(|s| s.parse().ok())
+            .unwrap();
+        assert!(pdf[xref_at..].starts_with("xref"));
+        let first_offset: usize = pdf[xref_at..].lines().nth(3).unwrap()[..10]
+            .parse()
+            .unwrap();
+        assert!(pdf[first_offset..].starts_with("1 0 obj"));
+    }
+
+    #[test]
+    fn test_render_order_pdf_paginates_long_orders() {
+        let mut doc = sample_document();
+        doc.items = (0..100)
+            .map(|i| OrderDocumentItem {
+                name: format!("商品{i}"),
+                price: 100,
+                quantity: 1,
+            })
+            .collect();
+        let pdf = String::from_utf8(render_order_pdf(&doc)).unwrap();
+        assert!(pdf.contains("/Count 3"));
+    }
+
+    #[test]
+    fn test_wrap_line_counts_half_width_as_half() {
+        assert_eq!(wrap_line("abcd", 2.0), vec!["abcd".to_string()]);
+        assert_eq!(
+            wrap_line("あいうえ", 2.0),
+            vec!["あい".to_string(), "うえ".to_string()]
+        );
+    }
+}
@@ -0,0 +1,263 @@
+//! 月次支出グラフの PNG 生成（plotters）
+//!
+//! レポート ZIP に同梱するため、フロントエンドを介さずにバックエンドだけで描画する。
+//! フォント依存を避けるため、ラベルは ASCII のみとする。
+
+use chrono::{Datelike, NaiveDate};
+use plotters::prelude::*;
+use std::path::Path;
+
+use crate::repository::MonthlySpending;
+
+const CHART_WIDTH: u32 = 1200;
+const CHART_HEIGHT: u32 = 600;
+
+/// 期間指定文字列を `(from, to)` の日付範囲（`YYYY-MM-DD`、to は含まない）に変換する。
+///
+/// - `all` または空文字: 全期間
+/// - `YYYY`: その年の 1/1 〜 12/31
+/// - `YYYY-MM..YYYY-MM`: 開始月の 1 日 〜 終了月の末日
+pub fn parse_spending_period(period: &str) -> Result<(Option<String>, Option<String>), String> {
+    let period = period.trim();
+    if period.is_empty() || period.eq_ignore_ascii_case("all") {
+        return Ok((None, None));
+    }
+
+    if let Some((start, end)) = period.split_once("..") {
+        let start = parse_month(start.trim())?;
+        let end = parse_month(end.trim())?;
+        if start > end {
+            return Err(format!("Invalid period (start is after end): {period}"));
+        }
+        return Ok((Some(format_date(start)), Some(format_date(next_month(end)))));
+    }
+
+    if period.len() == 4 && period.chars().all(|c| c.is_ascii_digit()) {
+        let year: i32 = period
+            .parse()
+            .map_err(|e| format!("Invalid year '{period}': {e}"))?;
+        let start =
+            NaiveDate::from_ymd_opt(year, 1, 1).ok_or_else(|| format!("Invalid year: {period}"))?;
+        let end = NaiveDate::from_ymd_opt(year + 1, 1, 1)
+            .ok_or_else(|| format!("Invalid year: {period}"))?;
+        return Ok((Some(format_date(start)), Some(format_date(end))));
+    }
+
+    Err(format!(
+        "Invalid period: {period} (expected 'all', 'YYYY' or 'YYYY-MM..YYYY-MM')"
+    ))
+}
+
+/// `YYYY-MM` をその月の 1 日に変換する
+fn parse_month(s: &str) -> Result<NaiveDate, String> {
+    NaiveDate::parse_from_str(&format!("{s}-01"), "%Y-%m-%d")
+        .map_err(|e| format!("Invalid month '{s}': {e}"))
+}
+
+fn next_month(date: NaiveDate) -> NaiveDate {
+    let (year, month) = if date.month() == 12 {
+        (date.year() + 1, 1)
+    } else {
+        (date.year(), date.month() + 1)
+    };
+    NaiveDate::from_ymd_opt(year, month, 1).unwrap_or(date)
+}
+
+fn format_date(date: NaiveDate) -> String {
+    date.format("%Y-%m-%d").to_string()
+}
+
+/// 注文のない月を 0 円で補完し、先頭月〜末尾月を連続させる
+pub fn fill_missing_months(data: &[MonthlySpending]) -> Vec<MonthlySpending> {
+    let (Some(first), Some(last)) = (data.first(), data.last()) else {
+        return Vec::new();
+    };
+    let (Ok(mut current), Ok(last)) = (parse_month(&first.month), parse_month(&last.month)) else {
+        return data.to_vec();
+    };
+
+    let mut filled = Vec::new();
+    while current <= last {
+        let month = current.format("%Y-%m").to_string();
+        match data.iter().find(|m| m.month == month) {
+            Some(m) => filled.push(m.clone()),
+            None => filled.push(MonthlySpending {
+                month,
+                total_amount: 0,
+                order_count: 0,
+            }),
+        }
+        current = next_month(current);
+    }
+    filled
+}
+
+/// 3 桁区切りの金額表記
+pub(crate) fn format_amount(value: i64) -> String {
+    let digits = value.unsigned_abs().to_string();
+    let mut out = String::new();
+    for (i, c) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i) % 3 == 0 {
+            out.push(',');
+        }
+        out.push(c);
+    }
+    if value < 0 {
+        format!("-{out}")
+    } else {
+        out
+    }
+}
+
+/// 月別支出の棒グラフを PNG として `path` に保存する
+pub fn render_spending_chart_png(data: &[MonthlySpending], path: &Path) -> Result<(), String> {
+    let data = fill_missing_months(data);
+    if data.is_empty() {
+        return Err("No spending data for the specified period".to_string());
+    }
+
+    let max_amount = data
+        .iter()
+        .map(|m| m.total_amount)
+        .max()
+        .unwrap_or(0)
+        .max(1);
+    let labels: Vec<String> = data.iter().map(|m| m.month.clone()).collect();
+
+    let root = BitMapBackend::new(path, (CHART_WIDTH, CHART_HEIGHT)).into_drawing_area();
+    root.fill(&WHITE)
+        .map_err(|e| format!("Failed to draw chart: {e}"))?;
+
+    let mut chart = ChartBuilder::on(&root)
+        .caption("Monthly spending (JPY)", ("sans-serif", 28))
+        .margin(20)
+        .x_label_area_size(70)
+        .y_label_area_size(100)
+        .build_cartesian_2d(
+            (0usize..data.len()).into_segmented(),
+            0i64..(max_amount + max_amount / 10),
+        )
+        .map_err(|e| format!("Failed to build chart: {e}"))?;
+
+    chart
+        .configure_mesh()
+        .disable_x_mesh()
+        .x_labels(data.len().min(24))
+        .x_label_formatter(&|v| match v {
+            SegmentValue::CenterOf(i) => labels.get(*i).cloned().unwrap_or_default(),
+            _ => String::new(),
+        })
+        .x_label_style(
+            ("sans-serif", 14)
+                .into_font()
+                .transform(FontTransform::Rotate90),
+        )
+        .y_label_formatter(&|v| format_amount(*v))
+        .draw()
+        .map_err(|e| format!("Failed to draw chart mesh: {e}"))?;
+
+    chart
+        .draw_series(data.iter().enumerate().map(|(i, m)| {
+            let mut bar = Rectangle::new(
+                [
+                    (SegmentValue::Exact(i), 0),
+                    (SegmentValue::Exact(i + 1), m.total_amount),
+                ],
+                BLUE.mix(0.7).filled(),
+            );
+            bar.set_margin(0, 0, 4, 4);
+            bar
+        }))
+        .map_err(|e| format!("Failed to draw chart bars: {e}"))?;
+
+    root.present()
+        .map_err(|e| format!("Failed to save chart: {e}"))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn spending(month: &str, total_amount: i64) -> MonthlySpending {
+        MonthlySpending {
+            month: month.to_string(),
+            total_amount,
+            order_count: 1,
+        }
+    }
+
+    #[test]
+    fn test_parse_spending_period_all() {
+        assert_eq!(parse_spending_period("all").unwrap(), (None, None));
+        assert_eq!(parse_spending_period("").unwrap(), (None, None));
+    }
+
+    #[test]
+    fn test_parse_spending_period_year() {
+        assert_eq!(
+            parse_spending_period("2024").unwrap(),
+            (
+                Some("2024-01-01".to_string()),
+                Some("2025-01-01".to_string())
+            )
+        );
+    }
+
+    #[test]
+    fn test_parse_spending_period_month_range() {
+        assert_eq!(
+            parse_spending_period("2024-11..2025-02").unwrap(),
+            (
+                Some("2024-11-01".to_string()),
+                Some("2025-03-01".to_string())
+            )
+        );
+        assert_eq!(
+            parse_spending_period("2024-12..2024-12").unwrap(),
+            (
+                Some("2024-12-01".to_string()),
+                Some("2025-01-01".to_string())
+            )
+        );
+    }
+
+    #[test]
+    fn test_parse_spending_period_invalid() {
+        assert!(parse_spending_period("last year").is_err());
+        assert!(parse_spending_period("2024-13..2025-01").is_err());
+        assert!(parse_spending_period("2025-02..2024-11").is_err());
+    }
+
+    #[test]
+    fn test_fill_missing_months() {
+        let data = vec![spending("2024-11", 1000), spending("2025-02", 3000)];
+        let filled = fill_missing_months(&data);
+        let months: Vec<&str> = filled.iter().map(|m| m.month.as_str()).collect();
+        assert_eq!(months, vec!["2024-11", "2024-12", "2025-01", "2025-02"]);
+        assert_eq!(filled[1].total_amount, 0);
+        assert_eq!(filled[3].total_amount, 3000);
+    }
+
+    #[test]
+    fn test_fill_missing_months_empty() {
+        assert!(fill_missing_months(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_format_amount() {
+        assert_eq!(format_amount(0), "0");
+        assert_eq!(format_amount(999), "999");
+        assert_eq!(format_amount(1000), "1,000");
+        assert_eq!(format_amount(1234567), "1,234,567");
+        assert_eq!(format_amount(-5000), "-5,000");
+    }
+
+    #[test]
+    fn test_render_spending_chart_png_empty_data() {
+        let path = std::env::temp_dir().join("paa_spending_chart_empty.png");
+        let result = render_spending_chart_png(&[], &path);
+        assert!(result.is_err());
+        assert!(!path.exists());
+    }
+}
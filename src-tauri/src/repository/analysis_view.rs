@@ -0,0 +1,216 @@
+use serde::{Deserialize, Serialize};
+use sqlx::sqlite::SqlitePool;
+use ts_rs::TS;
+
+/// 外部ツール向け分析用ビューの定義バージョン
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct AnalysisViewVersion {
+    pub view_name: String,
+    /// ビュー定義のバージョン（列の追加・変更のたびに上がる）
+    pub version: i64,
+    pub description: String,
+    pub updated_at: String,
+}
+
+/// 分析用ビューのDB操作
+pub struct SqliteAnalysisViewRepository {
+    pool: SqlitePool,
+}
+
+impl SqliteAnalysisViewRepository {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    /// 作成済みの分析用ビューとその定義バージョンを返す（DB に存在しないビューは含めない）
+    pub async fn list_views(&self) -> Result<Vec<AnalysisViewVersion>, String> {
+        let rows: Vec<(String, i64, String, String)> = sqlx::query_as(
+            r#"
+            SELECT v.view_name, v.version, v.description, v.updated_at
+            FROM analysis_view_versions v
+            INNER JOIN sqlite_master m ON m.type = 'view' AND m.name = v.view_name
+            ORDER BY v.view_name
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| format!("Failed to fetch analysis views: {e}"))?;
+
+        Ok(rows
+            .into_iter()
+            .map(
+                |(view_name, version, description, updated_at)| AnalysisViewVersion {
+                    view_name,
+                    version,
+                    description,
+                    updated_at,
+                },
+            )
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sqlx::sqlite::SqlitePoolOptions;
+
+    async fn setup_test_db() -> SqlitePool {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .expect("Failed to create test database");
+
+        sqlx::query(
+            r#"
+            CREATE TABLE orders (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                shop_domain TEXT,
+                shop_name TEXT,
+                order_number TEXT,
+                order_date DATETIME,
+                tax_amount INTEGER,
+                tax_included INTEGER NOT NULL DEFAULT 1,
+                reservation_status TEXT,
+                cancel_reason TEXT,
+                deleted_at DATETIME,
+                created_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+                updated_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP
+            );
+            CREATE TABLE items (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                order_id INTEGER NOT NULL,
+                item_name TEXT NOT NULL,
+                price INTEGER NOT NULL DEFAULT 0,
+                quantity INTEGER NOT NULL DEFAULT 1,
+                category TEXT,
+                brand TEXT,
+                deleted_at DATETIME,
+                created_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP
+            );
+            CREATE TABLE deliveries (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                order_id INTEGER NOT NULL,
+                tracking_number TEXT,
+                carrier TEXT,
+                delivery_status TEXT NOT NULL DEFAULT 'not_shipped',
+                estimated_delivery DATETIME,
+                actual_delivery DATETIME,
+                created_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+                updated_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP
+            );
+            CREATE TABLE product_master (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                raw_name TEXT UNIQUE NOT NULL,
+                normalized_name TEXT NOT NULL,
+                maker TEXT,
+                series TEXT,
+                product_name TEXT,
+                scale TEXT,
+                is_reissue INTEGER NOT NULL DEFAULT 0
+            );
+            "#,
+        )
+        .execute(&pool)
+        .await
+        .expect("Failed to create tables");
+        sqlx::query(include_str!("../../migrations/019_analysis_views.sql"))
+            .execute(&pool)
+            .await
+            .expect("Failed to create analysis views");
+        sqlx::query(include_str!(
+            "../../migrations/024_order_amount_adjustment.sql"
+        ))
+        .execute(&pool)
+        .await
+        .expect("Failed to add amount adjustment to analysis views");
+
+        pool
+    }
+
+    #[tokio::test]
+    async fn test_analysis_views() {
+        let pool = setup_test_db().await;
+        sqlx::query(
+            r#"
+            INSERT INTO orders (id, shop_name, order_number, order_date, tax_included, deleted_at) VALUES
+                (1, 'A', 'A-1', '2026-09-01 10:00:00', 1, NULL),
+                (2, 'B', 'B-1', '2026-09-10 10:00:00', 0, NULL),
+                (3, 'C', 'C-1', '2026-09-11 10:00:00', 1, '2026-09-12 10:00:00');
+            INSERT INTO items (order_id, item_name, price, quantity) VALUES
+                (1, '商品A', 1000, 2), (2, '商品B', 3000, 1), (3, '商品C', 500, 1);
+            INSERT INTO product_master (raw_name, normalized_name, maker, series) VALUES
+                ('商品A', '商品a', 'メーカーA', 'シリーズA');
+            INSERT INTO deliveries (order_id, delivery_status, actual_delivery, updated_at) VALUES
+                (1, 'shipped', NULL, '2026-09-02 10:00:00'),
+                (1, 'delivered', '2026-09-04 10:00:00', '2026-09-04 10:00:00');
+            "#,
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let orders: Vec<(i64, i64, i64, Option<String>)> = sqlx::query_as(
+            "SELECT order_id, items_amount, total_amount_tax_included, delivery_status FROM analysis_orders ORDER BY order_id",
+        )
+        .fetch_all(&pool)
+        .await
+        .unwrap();
+        assert_eq!(
+            orders,
+            vec![
+                (1, 2000, 2000, Some("delivered".to_string())),
+                (2, 3000, 3300, None),
+            ]
+        );
+
+        // 調整額（送料・割引など）は税込合計に含める
+        sqlx::query("UPDATE orders SET amount_adjustment = 500 WHERE id = 1")
+            .execute(&pool)
+            .await
+            .unwrap();
+        let total: i64 = sqlx::query_scalar(
+            "SELECT total_amount_tax_included FROM analysis_orders WHERE order_id = 1",
+        )
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        assert_eq!(total, 2500);
+
+        let items: Vec<(String, Option<String>, i64)> = sqlx::query_as(
+            "SELECT item_name, maker, subtotal FROM analysis_items ORDER BY item_id",
+        )
+        .fetch_all(&pool)
+        .await
+        .unwrap();
+        assert_eq!(
+            items,
+            vec![
+                ("商品A".to_string(), Some("メーカーA".to_string()), 2000),
+                ("商品B".to_string(), None, 3000),
+            ]
+        );
+
+        let days: Vec<Option<i64>> = sqlx::query_scalar(
+            "SELECT days_to_delivery FROM analysis_deliveries ORDER BY delivery_id",
+        )
+        .fetch_all(&pool)
+        .await
+        .unwrap();
+        assert_eq!(days, vec![None, Some(3)]);
+
+        let views = SqliteAnalysisViewRepository::new(pool)
+            .list_views()
+            .await
+            .unwrap();
+        let names: Vec<&str> = views.iter().map(|v| v.view_name.as_str()).collect();
+        assert_eq!(
+            names,
+            vec!["analysis_deliveries", "analysis_items", "analysis_orders"]
+        );
+        let versions: Vec<i64> = views.iter().map(|v| v.version).collect();
+        assert_eq!(versions, vec![1, 1, 2]);
+    }
+}
@@ -0,0 +1,325 @@
+use crate::parsers::release_date::FuzzyReleaseDate;
+use serde::{Deserialize, Serialize};
+use sqlx::sqlite::SqlitePool;
+use std::collections::BTreeMap;
+use ts_rs::TS;
+
+/// カレンダーに載せる予定の種類
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(export)]
+pub enum ArrivalEventKind {
+    /// 配送のお届け予定日（deliveries.estimated_delivery）
+    EstimatedDelivery,
+    /// 商品の発売予定日（items.release_date）
+    Release,
+}
+
+/// カレンダーの 1 件（注文ごと・種類ごとに商品をまとめる）
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct ArrivalCalendarEvent {
+    pub kind: ArrivalEventKind,
+    pub order_id: i64,
+    pub shop_name: Option<String>,
+    pub order_number: Option<String>,
+    pub item_names: Vec<String>,
+    /// 最新の配送状況（配送情報がなければ None）
+    pub delivery_status: Option<String>,
+    /// 表示用の発売予定（「2025年8月下旬」等。お届け予定の場合は None）
+    pub release_date_label: Option<String>,
+}
+
+/// 日付ごとの予定
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct ArrivalCalendarDay {
+    /// YYYY-MM-DD
+    pub date: String,
+    pub events: Vec<ArrivalCalendarEvent>,
+}
+
+/// 月カレンダー形式の到着予定
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct ArrivalCalendar {
+    pub year: i32,
+    pub month: u32,
+    /// 予定のある日だけを日付順に並べる
+    pub days: Vec<ArrivalCalendarDay>,
+    /// 日付まで決まっていない今月発売予定（「8月下旬」「8月」など）
+    pub undated_releases: Vec<ArrivalCalendarEvent>,
+}
+
+/// (日付 or None, 種類, order_id, shop_name, order_number, item_name, delivery_status, release_date_label)
+type CalendarRow = (
+    Option<String>,
+    ArrivalEventKind,
+    i64,
+    Option<String>,
+    Option<String>,
+    String,
+    Option<String>,
+    Option<String>,
+);
+
+/// 行を日付・種類・注文ごとにまとめてカレンダーを組み立てる
+fn build_arrival_calendar(year: i32, month: u32, rows: Vec<CalendarRow>) -> ArrivalCalendar {
+    let mut days: BTreeMap<String, BTreeMap<(ArrivalEventKind, i64), ArrivalCalendarEvent>> =
+        BTreeMap::new();
+    let mut undated: BTreeMap<(ArrivalEventKind, i64), ArrivalCalendarEvent> = BTreeMap::new();
+
+    for (date, kind, order_id, shop_name, order_number, item_name, status, label) in rows {
+        let events = match date {
+            Some(date) => days.entry(date).or_default(),
+            None => &mut undated,
+        };
+        let event = events
+            .entry((kind, order_id))
+            .or_insert_with(|| ArrivalCalendarEvent {
+                kind,
+                order_id,
+                shop_name,
+                order_number,
+                item_names: Vec::new(),
+                delivery_status: status,
+                release_date_label: label,
+            });
+        if !event.item_names.contains(&item_name) {
+            event.item_names.push(item_name);
+        }
+    }
+
+    ArrivalCalendar {
+        year,
+        month,
+        days: days
+            .into_iter()
+            .map(|(date, events)| ArrivalCalendarDay {
+                date,
+                events: events.into_values().collect(),
+            })
+            .collect(),
+        undated_releases: undated.into_values().collect(),
+    }
+}
+
+/// 到着予定カレンダーの読み込み
+pub struct SqliteArrivalCalendarRepository {
+    pool: SqlitePool,
+}
+
+impl SqliteArrivalCalendarRepository {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    /// 指定月のお届け予定日と発売予定日を取得する（削除済みの注文・商品は除く）
+    pub async fn get_month(&self, year: i32, month: u32) -> Result<ArrivalCalendar, String> {
+        if !(1..=12).contains(&month) {
+            return Err(format!("Invalid month: {month}"));
+        }
+        let year_month = format!("{year:04}-{month:02}");
+
+        let deliveries: Vec<(String, i64, Option<String>, Option<String>, String, String)> =
+            sqlx::query_as(
+                r#"
+                SELECT date(d.estimated_delivery), o.id, o.shop_name, o.order_number,
+                       i.item_name, d.delivery_status
+                FROM deliveries d
+                INNER JOIN orders o ON o.id = d.order_id
+                INNER JOIN items i ON i.order_id = o.id AND i.deleted_at IS NULL
+                WHERE d.estimated_delivery IS NOT NULL
+                  AND strftime('%Y-%m', d.estimated_delivery) = ?
+                  AND o.deleted_at IS NULL
+                ORDER BY d.estimated_delivery, o.id, i.id
+                "#,
+            )
+            .bind(&year_month)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| format!("Failed to fetch estimated deliveries: {e}"))?;
+
+        let releases: Vec<(
+            String,
+            Option<String>,
+            i64,
+            Option<String>,
+            Option<String>,
+            String,
+            Option<String>,
+        )> = sqlx::query_as(
+            r#"
+            WITH latest_delivery AS (
+                SELECT order_id, delivery_status
+                FROM (
+                    SELECT order_id, delivery_status,
+                           ROW_NUMBER() OVER (PARTITION BY order_id ORDER BY updated_at DESC) AS rn
+                    FROM deliveries
+                ) t
+                WHERE rn = 1
+            )
+            SELECT i.release_date, i.release_date_precision, o.id, o.shop_name, o.order_number,
+                   i.item_name, ld.delivery_status
+            FROM items i
+            INNER JOIN orders o ON o.id = i.order_id
+            LEFT JOIN latest_delivery ld ON ld.order_id = o.id
+            WHERE substr(i.release_date, 1, 7) = ?
+              AND i.deleted_at IS NULL
+              AND o.deleted_at IS NULL
+            ORDER BY i.release_date, o.id, i.id
+            "#,
+        )
+        .bind(&year_month)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| format!("Failed to fetch release dates: {e}"))?;
+
+        let mut rows: Vec<CalendarRow> = deliveries
+            .into_iter()
+            .map(
+                |(date, order_id, shop_name, order_number, item_name, status)| {
+                    (
+                        Some(date),
+                        ArrivalEventKind::EstimatedDelivery,
+                        order_id,
+                        shop_name,
+                        order_number,
+                        item_name,
+                        Some(status),
+                        None,
+                    )
+                },
+            )
+            .collect();
+        rows.extend(releases.into_iter().map(
+            |(release_date, precision, order_id, shop_name, order_number, item_name, status)| {
+                let fuzzy = FuzzyReleaseDate::from_stored(&release_date, precision.as_deref());
+                // 下旬・月のみ等は並び替え用に末日へ寄せて保存しているため、日付の確定したものだけ日に載せる
+                let date = fuzzy
+                    .as_ref()
+                    .and_then(|d| d.day)
+                    .map(|_| release_date.clone());
+                let label = fuzzy.map(|d| d.label()).unwrap_or(release_date);
+                (
+                    date,
+                    ArrivalEventKind::Release,
+                    order_id,
+                    shop_name,
+                    order_number,
+                    item_name,
+                    status,
+                    Some(label),
+                )
+            },
+        ));
+
+        Ok(build_arrival_calendar(year, month, rows))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sqlx::sqlite::SqlitePoolOptions;
+
+    async fn setup_test_db() -> SqlitePool {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .expect("Failed to create test database");
+
+        sqlx::query(
+            r#"
+            CREATE TABLE orders (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                shop_name TEXT,
+                order_number TEXT,
+                deleted_at DATETIME
+            );
+            CREATE TABLE items (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                order_id INTEGER NOT NULL,
+                item_name TEXT NOT NULL,
+                release_date TEXT,
+                release_date_precision TEXT,
+                deleted_at DATETIME
+            );
+            CREATE TABLE deliveries (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                order_id INTEGER NOT NULL,
+                delivery_status TEXT NOT NULL DEFAULT 'not_shipped',
+                estimated_delivery DATETIME,
+                updated_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP
+            );
+            "#,
+        )
+        .execute(&pool)
+        .await
+        .expect("Failed to create tables");
+
+        pool
+    }
+
+    #[tokio::test]
+    async fn test_get_month() {
+        let pool = setup_test_db().await;
+        sqlx::query(
+            r#"
+            INSERT INTO orders (id, shop_name, order_number, deleted_at) VALUES
+                (1, 'ショップA', 'A-1', NULL),
+                (2, 'ショップB', 'B-1', NULL),
+                (3, 'ショップC', 'C-1', '2025-07-01');
+            INSERT INTO items (order_id, item_name, release_date, release_date_precision) VALUES
+                (1, '商品1', NULL, NULL),
+                (1, '商品2', NULL, NULL),
+                (2, '発売日確定', '2025-08-15', 'day'),
+                (2, '下旬発売', '2025-08-31', 'late_month'),
+                (2, '翌月発売', '2025-09-10', 'day'),
+                (3, '削除済み注文', '2025-08-15', 'day');
+            INSERT INTO deliveries (order_id, delivery_status, estimated_delivery) VALUES
+                (1, 'shipped', '2025-08-15 00:00:00'),
+                (2, 'not_shipped', NULL),
+                (3, 'shipped', '2025-08-15');
+            "#,
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let repo = SqliteArrivalCalendarRepository::new(pool);
+        let calendar = repo.get_month(2025, 8).await.unwrap();
+
+        assert_eq!(calendar.days.len(), 1);
+        let day = &calendar.days[0];
+        assert_eq!(day.date, "2025-08-15");
+        assert_eq!(day.events.len(), 2);
+        assert_eq!(day.events[0].kind, ArrivalEventKind::EstimatedDelivery);
+        assert_eq!(day.events[0].order_id, 1);
+        assert_eq!(day.events[0].item_names, vec!["商品1", "商品2"]);
+        assert_eq!(day.events[0].delivery_status.as_deref(), Some("shipped"));
+        assert_eq!(day.events[1].kind, ArrivalEventKind::Release);
+        assert_eq!(day.events[1].item_names, vec!["発売日確定"]);
+        assert_eq!(
+            day.events[1].release_date_label.as_deref(),
+            Some("2025年8月15日")
+        );
+
+        // 下旬など日付の決まっていないものは日に載せない
+        assert_eq!(calendar.undated_releases.len(), 1);
+        assert_eq!(calendar.undated_releases[0].item_names, vec!["下旬発売"]);
+        assert_eq!(
+            calendar.undated_releases[0].release_date_label.as_deref(),
+            Some("2025年8月下旬")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_get_month_rejects_invalid_month() {
+        let pool = setup_test_db().await;
+        let repo = SqliteArrivalCalendarRepository::new(pool);
+        assert!(repo.get_month(2025, 13).await.is_err());
+        assert!(repo.get_month(2025, 0).await.is_err());
+    }
+}
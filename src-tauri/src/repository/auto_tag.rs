@@ -0,0 +1,452 @@
+use serde::{Deserialize, Serialize};
+use sqlx::sqlite::SqlitePool;
+use ts_rs::TS;
+
+/// 自動タグ付けルールレコード
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct AutoTagRule {
+    pub id: i64,
+    pub tag: String,
+    /// 照合対象: "item_name" / "shop_domain" / "shop_name"
+    pub target: String,
+    pub keyword: String,
+    /// 照合方法: "contains" / "starts_with" / "exact"
+    pub match_type: String,
+    pub created_at: String,
+}
+
+type AutoTagRuleDbRow = (i64, String, String, String, String, String);
+
+/// タグ付け対象アイテム（アイテム ID・商品名・注文のショップ情報）
+type TagTargetRow = (i64, String, Option<String>, Option<String>);
+
+/// ルールが対象に一致するか（大文字小文字を区別しない）
+pub fn matches_auto_tag_rule(
+    rule: &AutoTagRule,
+    item_name: &str,
+    shop_domain: Option<&str>,
+    shop_name: Option<&str>,
+) -> bool {
+    let value = match rule.target.as_str() {
+        "shop_domain" => shop_domain,
+        "shop_name" => shop_name,
+        _ => Some(item_name), // "item_name" (default)
+    };
+    let Some(value) = value else {
+        return false;
+    };
+    let value_lower = value.to_lowercase();
+    let keyword_lower = rule.keyword.to_lowercase();
+    match rule.match_type.as_str() {
+        "starts_with" => value_lower.starts_with(&keyword_lower),
+        "exact" => value_lower == keyword_lower,
+        _ => value_lower.contains(&keyword_lower), // "contains" (default)
+    }
+}
+
+/// アイテムに一致する全ルールのタグを返す（重複なし・ルール順）
+pub fn tags_for_item(
+    rules: &[AutoTagRule],
+    item_name: &str,
+    shop_domain: Option<&str>,
+    shop_name: Option<&str>,
+) -> Vec<String> {
+    let mut tags: Vec<String> = Vec::new();
+    for rule in rules {
+        if matches_auto_tag_rule(rule, item_name, shop_domain, shop_name)
+            && !tags.contains(&rule.tag)
+        {
+            tags.push(rule.tag.clone());
+        }
+    }
+    tags
+}
+
+/// 自動タグ付けルールのDB操作
+pub struct SqliteAutoTagRuleRepository {
+    pool: SqlitePool,
+}
+
+impl SqliteAutoTagRuleRepository {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    pub async fn get_all(&self) -> Result<Vec<AutoTagRule>, String> {
+        let rows: Vec<AutoTagRuleDbRow> = sqlx::query_as(
+            r#"
+            SELECT id, tag, target, keyword, match_type, created_at
+            FROM auto_tag_rules
+            ORDER BY id ASC
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| format!("Failed to fetch auto tag rules: {e}"))?;
+
+        Ok(rows.into_iter().map(row_to_rule).collect())
+    }
+
+    pub async fn add(
+        &self,
+        tag: String,
+        target: String,
+        keyword: String,
+        match_type: String,
+    ) -> Result<i64, String> {
+        let id: i64 = sqlx::query_scalar(
+            r#"
+            INSERT INTO auto_tag_rules (tag, target, keyword, match_type)
+            VALUES (?, ?, ?, ?)
+            RETURNING id
+            "#,
+        )
+        .bind(tag)
+        .bind(target)
+        .bind(keyword)
+        .bind(match_type)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| format!("Failed to add auto tag rule: {e}"))?;
+
+        Ok(id)
+    }
+
+    pub async fn update(
+        &self,
+        id: i64,
+        tag: String,
+        target: String,
+        keyword: String,
+        match_type: String,
+    ) -> Result<(), String> {
+        let result = sqlx::query(
+            r#"
+            UPDATE auto_tag_rules
+            SET tag = ?, target = ?, keyword = ?, match_type = ?
+            WHERE id = ?
+            "#,
+        )
+        .bind(tag)
+        .bind(target)
+        .bind(keyword)
+        .bind(match_type)
+        .bind(id)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| format!("Failed to update auto tag rule: {e}"))?;
+
+        if result.rows_affected() == 0 {
+            return Err(format!("Auto tag rule not found: {id}"));
+        }
+        Ok(())
+    }
+
+    pub async fn delete(&self, id: i64) -> Result<(), String> {
+        sqlx::query("DELETE FROM auto_tag_rules WHERE id = ?")
+            .bind(id)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| format!("Failed to delete auto tag rule: {e}"))?;
+        Ok(())
+    }
+
+    /// 既存の全アイテムにルールを適用する（ルール追加後の遡及適用用）。付与したタグ数を返す。
+    pub async fn apply_to_all_items(&self) -> Result<usize, String> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| format!("Failed to start transaction: {e}"))?;
+        let rules = load_all_rules_in_tx(&mut tx).await?;
+        let targets: Vec<TagTargetRow> = sqlx::query_as(
+            r#"
+            SELECT i.id, i.item_name, o.shop_domain, o.shop_name
+            FROM items i
+            INNER JOIN orders o ON o.id = i.order_id
+            WHERE i.deleted_at IS NULL
+            "#,
+        )
+        .fetch_all(tx.as_mut())
+        .await
+        .map_err(|e| format!("Failed to fetch items for tagging: {e}"))?;
+
+        let added = insert_tags_in_tx(&mut tx, &rules, targets).await?;
+        tx.commit()
+            .await
+            .map_err(|e| format!("Failed to commit transaction: {e}"))?;
+        Ok(added)
+    }
+
+    /// アイテムに付与されたタグを取得する
+    pub async fn get_item_tags(&self, item_id: i64) -> Result<Vec<String>, String> {
+        sqlx::query_scalar("SELECT tag FROM item_tags WHERE item_id = ? ORDER BY tag")
+            .bind(item_id)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| format!("Failed to fetch item tags: {e}"))
+    }
+}
+
+/// トランザクション内で全ルールを取得する
+pub async fn load_all_rules_in_tx(
+    tx: &mut sqlx::Transaction<'_, sqlx::Sqlite>,
+) -> Result<Vec<AutoTagRule>, String> {
+    let rows: Vec<AutoTagRuleDbRow> = sqlx::query_as(
+        r#"
+        SELECT id, tag, target, keyword, match_type, created_at
+        FROM auto_tag_rules
+        ORDER BY id ASC
+        "#,
+    )
+    .fetch_all(tx.as_mut())
+    .await
+    .map_err(|e| format!("Failed to load auto tag rules in tx: {e}"))?;
+
+    Ok(rows.into_iter().map(row_to_rule).collect())
+}
+
+/// 注文内の全アイテムにルールを適用する（`save_order_in_tx` から使用）。付与したタグ数を返す。
+pub async fn apply_auto_tags_for_order_in_tx(
+    tx: &mut sqlx::Transaction<'_, sqlx::Sqlite>,
+    order_id: i64,
+) -> Result<usize, String> {
+    let rules = load_all_rules_in_tx(tx).await?;
+    if rules.is_empty() {
+        return Ok(0);
+    }
+    let targets: Vec<TagTargetRow> = sqlx::query_as(
+        r#"
+        SELECT i.id, i.item_name, o.shop_domain, o.shop_name
+        FROM items i
+        INNER JOIN orders o ON o.id = i.order_id
+        WHERE i.order_id = ?
+        "#,
+    )
+    .bind(order_id)
+    .fetch_all(tx.as_mut())
+    .await
+    .map_err(|e| format!("Failed to fetch items for tagging: {e}"))?;
+
+    insert_tags_in_tx(tx, &rules, targets).await
+}
+
+async fn insert_tags_in_tx(
+    tx: &mut sqlx::Transaction<'_, sqlx::Sqlite>,
+    rules: &[AutoTagRule],
+    targets: Vec<TagTargetRow>,
+) -> Result<usize, String> {
+    let mut added = 0;
+    for (item_id, item_name, shop_domain, shop_name) in targets {
+        for tag in tags_for_item(
+            rules,
+            &item_name,
+            shop_domain.as_deref(),
+            shop_name.as_deref(),
+        ) {
+            let result =
+                sqlx::query("INSERT OR IGNORE INTO item_tags (item_id, tag) VALUES (?, ?)")
+                    .bind(item_id)
+                    .bind(&tag)
+                    .execute(tx.as_mut())
+                    .await
+                    .map_err(|e| format!("Failed to insert item tag: {e}"))?;
+            added += result.rows_affected() as usize;
+        }
+    }
+    Ok(added)
+}
+
+fn row_to_rule(r: AutoTagRuleDbRow) -> AutoTagRule {
+    AutoTagRule {
+        id: r.0,
+        tag: r.1,
+        target: r.2,
+        keyword: r.3,
+        match_type: r.4,
+        created_at: r.5,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sqlx::sqlite::SqlitePoolOptions;
+
+    async fn setup_test_db() -> SqlitePool {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .expect("Failed to create test database");
+
+        sqlx::query(
+            r#"
+            CREATE TABLE orders (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                shop_domain TEXT,
+                shop_name TEXT
+            );
+            CREATE TABLE items (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                order_id INTEGER NOT NULL,
+                item_name TEXT NOT NULL,
+                deleted_at DATETIME
+            );
+            CREATE TABLE auto_tag_rules (
+                id          INTEGER PRIMARY KEY AUTOINCREMENT,
+                tag         TEXT    NOT NULL,
+                target      TEXT    NOT NULL DEFAULT 'item_name'
+                                    CHECK(target IN ('item_name', 'shop_domain', 'shop_name')),
+                keyword     TEXT    NOT NULL,
+                match_type  TEXT    NOT NULL DEFAULT 'contains'
+                                    CHECK(match_type IN ('contains', 'starts_with', 'exact')),
+                created_at  TEXT    NOT NULL DEFAULT (datetime('now'))
+            );
+            CREATE TABLE item_tags (
+                id          INTEGER PRIMARY KEY AUTOINCREMENT,
+                item_id     INTEGER NOT NULL,
+                tag         TEXT    NOT NULL,
+                created_at  TEXT    NOT NULL DEFAULT (datetime('now')),
+                UNIQUE (item_id, tag)
+            );
+            "#,
+        )
+        .execute(&pool)
+        .await
+        .expect("Failed to create tables");
+
+        pool
+    }
+
+    fn rule(tag: &str, target: &str, keyword: &str, match_type: &str) -> AutoTagRule {
+        AutoTagRule {
+            id: 0,
+            tag: tag.to_string(),
+            target: target.to_string(),
+            keyword: keyword.to_string(),
+            match_type: match_type.to_string(),
+            created_at: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_matches_auto_tag_rule_item_name() {
+        let r = rule("ねんどろ", "item_name", "ねんどろいど", "contains");
+        assert!(matches_auto_tag_rule(
+            &r,
+            "ねんどろいど 初音ミク",
+            None,
+            None
+        ));
+        assert!(!matches_auto_tag_rule(&r, "figma 初音ミク", None, None));
+    }
+
+    #[test]
+    fn test_matches_auto_tag_rule_shop() {
+        let r = rule("DMM", "shop_domain", "mail.dmm.com", "exact");
+        assert!(matches_auto_tag_rule(
+            &r,
+            "商品",
+            Some("MAIL.DMM.COM"),
+            None
+        ));
+        assert!(!matches_auto_tag_rule(&r, "商品", None, None));
+
+        let r = rule("ホビサ", "shop_name", "ホビー", "starts_with");
+        assert!(matches_auto_tag_rule(
+            &r,
+            "商品",
+            None,
+            Some("ホビーサーチ")
+        ));
+    }
+
+    #[test]
+    fn test_tags_for_item_dedup() {
+        let rules = vec![
+            rule("ねんどろ", "item_name", "ねんどろいど", "contains"),
+            rule("ねんどろ", "item_name", "ねんどろ", "starts_with"),
+            rule("DMM", "shop_domain", "dmm.com", "contains"),
+        ];
+        assert_eq!(
+            tags_for_item(&rules, "ねんどろいど 初音ミク", Some("mail.dmm.com"), None),
+            vec!["ねんどろ".to_string(), "DMM".to_string()]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_crud_and_apply() {
+        let pool = setup_test_db().await;
+        sqlx::query(
+            r#"
+            INSERT INTO orders (id, shop_domain, shop_name) VALUES (1, 'mail.dmm.com', 'DMM通販');
+            INSERT INTO items (id, order_id, item_name) VALUES (1, 1, 'ねんどろいど 初音ミク'), (2, 1, 'figma 初音ミク');
+            "#,
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let repo = SqliteAutoTagRuleRepository::new(pool.clone());
+        let id = repo
+            .add(
+                "ねんどろ".to_string(),
+                "item_name".to_string(),
+                "ねんどろいど".to_string(),
+                "contains".to_string(),
+            )
+            .await
+            .unwrap();
+        repo.add(
+            "DMM".to_string(),
+            "shop_domain".to_string(),
+            "dmm.com".to_string(),
+            "contains".to_string(),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(repo.apply_to_all_items().await.unwrap(), 3);
+        // 再適用しても重複しない
+        assert_eq!(repo.apply_to_all_items().await.unwrap(), 0);
+        assert_eq!(
+            repo.get_item_tags(1).await.unwrap(),
+            vec!["DMM".to_string(), "ねんどろ".to_string()]
+        );
+
+        repo.update(
+            id,
+            "figma".to_string(),
+            "item_name".to_string(),
+            "figma".to_string(),
+            "starts_with".to_string(),
+        )
+        .await
+        .unwrap();
+        let mut tx = pool.begin().await.unwrap();
+        assert_eq!(
+            apply_auto_tags_for_order_in_tx(&mut tx, 1).await.unwrap(),
+            1
+        );
+        tx.commit().await.unwrap();
+        assert_eq!(
+            repo.get_item_tags(2).await.unwrap(),
+            vec!["DMM".to_string(), "figma".to_string()]
+        );
+
+        repo.delete(id).await.unwrap();
+        assert_eq!(repo.get_all().await.unwrap().len(), 1);
+        assert!(repo
+            .update(
+                id,
+                "x".to_string(),
+                "item_name".to_string(),
+                "x".to_string(),
+                "contains".to_string()
+            )
+            .await
+            .is_err());
+    }
+}
@@ -81,4 +81,263 @@ impl SqliteDeliveryRepository {
             })
             .collect())
     }
+
+    /// 発送メールのない注文に追跡番号を手動登録し、deliveries.id を返す。
+    ///
+    /// 同じ追跡番号が登録済みなら配送業者のみ更新する。追跡番号のない配送レコード
+    /// （未発送・発送準備中）があればそれに追跡番号を設定し、なければ新規作成する。
+    /// いずれも delivery_status は 'shipped' となり、配送状況確認バッチの対象になる。
+    pub async fn add_tracking_number(
+        &self,
+        order_id: i64,
+        carrier: &str,
+        tracking_number: &str,
+    ) -> Result<i64, String> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| format!("Failed to begin transaction: {e}"))?;
+
+        let order_exists: Option<i64> =
+            sqlx::query_scalar("SELECT id FROM orders WHERE id = ? AND deleted_at IS NULL")
+                .bind(order_id)
+                .fetch_optional(&mut *tx)
+                .await
+                .map_err(|e| format!("Failed to fetch order: {e}"))?;
+        if order_exists.is_none() {
+            return Err(format!("Order {order_id} not found"));
+        }
+
+        let existing: Option<i64> = sqlx::query_scalar(
+            "SELECT id FROM deliveries WHERE order_id = ? AND tracking_number = ? LIMIT 1",
+        )
+        .bind(order_id)
+        .bind(tracking_number)
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(|e| format!("Failed to check existing delivery: {e}"))?;
+
+        let delivery_id = if let Some(id) = existing {
+            sqlx::query(
+                "UPDATE deliveries SET carrier = ?, updated_at = CURRENT_TIMESTAMP WHERE id = ?",
+            )
+            .bind(carrier)
+            .bind(id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| format!("Failed to update delivery: {e}"))?;
+            id
+        } else {
+            let untracked: Option<i64> = sqlx::query_scalar(
+                r#"
+                SELECT id FROM deliveries
+                WHERE order_id = ?
+                  AND (tracking_number IS NULL OR TRIM(tracking_number) = '')
+                  AND delivery_status IN ('not_shipped', 'preparing')
+                ORDER BY id
+                LIMIT 1
+                "#,
+            )
+            .bind(order_id)
+            .fetch_optional(&mut *tx)
+            .await
+            .map_err(|e| format!("Failed to fetch untracked delivery: {e}"))?;
+
+            match untracked {
+                Some(id) => {
+                    sqlx::query(
+                        r#"
+                        UPDATE deliveries
+                        SET tracking_number = ?, carrier = ?, delivery_status = 'shipped',
+                            updated_at = CURRENT_TIMESTAMP
+                        WHERE id = ?
+                        "#,
+                    )
+                    .bind(tracking_number)
+                    .bind(carrier)
+                    .bind(id)
+                    .execute(&mut *tx)
+                    .await
+                    .map_err(|e| format!("Failed to update delivery: {e}"))?;
+                    id
+                }
+                None => sqlx::query(
+                    r#"
+                    INSERT INTO deliveries (order_id, tracking_number, carrier, delivery_status)
+                    VALUES (?, ?, ?, 'shipped')
+                    "#,
+                )
+                .bind(order_id)
+                .bind(tracking_number)
+                .bind(carrier)
+                .execute(&mut *tx)
+                .await
+                .map_err(|e| format!("Failed to insert delivery: {e}"))?
+                .last_insert_rowid(),
+            }
+        };
+
+        tx.commit()
+            .await
+            .map_err(|e| format!("Failed to commit transaction: {e}"))?;
+        log::info!(
+            "Tracking number added manually: order_id={} delivery_id={} carrier={}",
+            order_id,
+            delivery_id,
+            carrier
+        );
+        Ok(delivery_id)
+    }
+
+    /// 注文の配送のうち追跡番号が登録された最新のもの（配送業者・追跡番号）を返す
+    pub async fn get_latest_tracked_delivery(
+        &self,
+        order_id: i64,
+    ) -> Result<Option<PendingDelivery>, String> {
+        let row: Option<(i64, String, String)> = sqlx::query_as(
+            r#"
+            SELECT d.id, d.tracking_number, COALESCE(d.carrier, '')
+            FROM deliveries d
+            JOIN orders o ON o.id = d.order_id
+            WHERE d.order_id = ?
+              AND o.deleted_at IS NULL
+              AND d.tracking_number IS NOT NULL
+              AND TRIM(d.tracking_number) NOT IN ('', '-')
+            ORDER BY d.updated_at DESC, d.id DESC
+            LIMIT 1
+            "#,
+        )
+        .bind(order_id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| format!("Failed to fetch delivery: {e}"))?;
+
+        Ok(row.map(|(id, tracking_number, carrier)| PendingDelivery {
+            id,
+            tracking_number,
+            carrier,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sqlx::sqlite::SqlitePoolOptions;
+
+    async fn setup_test_db() -> SqlitePool {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .expect("Failed to create test database");
+
+        sqlx::query(
+            r#"
+            CREATE TABLE orders (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                deleted_at DATETIME
+            );
+            CREATE TABLE deliveries (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                order_id INTEGER NOT NULL,
+                tracking_number TEXT,
+                carrier TEXT,
+                delivery_status TEXT NOT NULL DEFAULT 'not_shipped',
+                last_checked_at DATETIME,
+                created_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+                updated_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP
+            );
+            CREATE TABLE tracking_check_logs (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                tracking_number TEXT NOT NULL UNIQUE,
+                checked_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+                delivery_status TEXT
+            );
+            INSERT INTO orders (id, deleted_at) VALUES (1, NULL), (2, NULL), (3, '2025-01-01');
+            INSERT INTO deliveries (order_id, delivery_status) VALUES (2, 'preparing');
+            "#,
+        )
+        .execute(&pool)
+        .await
+        .expect("Failed to create tables");
+
+        pool
+    }
+
+    #[tokio::test]
+    async fn test_add_tracking_number_creates_pending_delivery() {
+        let pool = setup_test_db().await;
+        let repo = SqliteDeliveryRepository::new(pool.clone());
+
+        let id = repo
+            .add_tracking_number(1, "佐川急便", "364631890991")
+            .await
+            .unwrap();
+        // 再登録は同じレコードを更新する
+        let again = repo
+            .add_tracking_number(1, "佐川急便", "364631890991")
+            .await
+            .unwrap();
+        assert_eq!(id, again);
+
+        let pending = repo.get_pending_deliveries().await.unwrap();
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].id, id);
+        assert_eq!(pending[0].tracking_number, "364631890991");
+        assert_eq!(pending[0].carrier, "佐川急便");
+    }
+
+    #[tokio::test]
+    async fn test_add_tracking_number_fills_untracked_delivery() {
+        let pool = setup_test_db().await;
+        let repo = SqliteDeliveryRepository::new(pool.clone());
+
+        repo.add_tracking_number(2, "ヤマト運輸", "504160758231")
+            .await
+            .unwrap();
+
+        let rows: Vec<(String, String)> = sqlx::query_as(
+            "SELECT tracking_number, delivery_status FROM deliveries WHERE order_id = 2",
+        )
+        .fetch_all(&pool)
+        .await
+        .unwrap();
+        assert_eq!(
+            rows,
+            vec![("504160758231".to_string(), "shipped".to_string())]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_add_tracking_number_unknown_or_deleted_order() {
+        let pool = setup_test_db().await;
+        let repo = SqliteDeliveryRepository::new(pool);
+
+        assert!(repo.add_tracking_number(99, "佐川急便", "1").await.is_err());
+        assert!(repo.add_tracking_number(3, "佐川急便", "1").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_get_latest_tracked_delivery_skips_untracked() {
+        let pool = setup_test_db().await;
+        let repo = SqliteDeliveryRepository::new(pool.clone());
+
+        // 追跡番号のない配送しかない注文は None
+        assert!(repo.get_latest_tracked_delivery(2).await.unwrap().is_none());
+
+        repo.add_tracking_number(2, "ヤマト運輸", "504160758231")
+            .await
+            .unwrap();
+        let delivery = repo.get_latest_tracked_delivery(2).await.unwrap().unwrap();
+        assert_eq!(delivery.carrier, "ヤマト運輸");
+        assert_eq!(delivery.tracking_number, "504160758231");
+
+        assert!(repo
+            .get_latest_tracked_delivery(99)
+            .await
+            .unwrap()
+            .is_none());
+    }
 }
@@ -4,9 +4,11 @@ use async_trait::async_trait;
 use mockall::automock;
 use serde::{Deserialize, Serialize};
 use sqlx::sqlite::SqlitePool;
+use ts_rs::TS;
 
 /// メール統計情報
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
 pub struct EmailStats {
     pub total_emails: i64,
     pub with_body_plain: i64,
@@ -45,6 +47,9 @@ pub trait EmailRepository: Send + Sync {
     /// DB内のメールの最新 internal_date（ミリ秒Unix時刻）を取得する。
     /// メールが存在しない場合は None を返す。
     async fn get_latest_internal_date(&self) -> Result<Option<i64>, String>;
+
+    /// メッセージの原本（RFC 822 形式）を email_raws に保存する。既存の場合は上書き。
+    async fn save_raw_message(&self, message_id: &str, raw: &[u8]) -> Result<(), String>;
 }
 
 /// メール統計関連のDB操作を抽象化するトレイト
@@ -55,6 +60,20 @@ pub trait EmailStatsRepository: Send + Sync {
     async fn get_email_stats(&self) -> Result<EmailStats, String>;
 }
 
+/// .eml エクスポート用のメール情報
+///
+/// `raw` は同期時に原本を保存していた場合のみ Some。
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct EmailExportSource {
+    pub message_id: String,
+    pub subject: Option<String>,
+    pub from_address: Option<String>,
+    pub internal_date: Option<i64>,
+    pub body_plain: Option<String>,
+    pub body_html: Option<String>,
+    pub raw: Option<Vec<u8>>,
+}
+
 /// SQLiteを使用したEmailRepositoryの実装
 pub struct SqliteEmailRepository {
     pool: SqlitePool,
@@ -64,6 +83,26 @@ impl SqliteEmailRepository {
     pub fn new(pool: SqlitePool) -> Self {
         Self { pool }
     }
+
+    /// .eml エクスポート用にメール本文と保存済み原本を取得する
+    pub async fn get_export_source(
+        &self,
+        email_id: i64,
+    ) -> Result<Option<EmailExportSource>, String> {
+        sqlx::query_as(
+            r#"
+            SELECT e.message_id, e.subject, e.from_address, e.internal_date,
+                   e.body_plain, e.body_html, r.raw
+            FROM emails e
+            LEFT JOIN email_raws r ON r.message_id = e.message_id
+            WHERE e.id = ?
+            "#,
+        )
+        .bind(email_id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| format!("Failed to fetch email {email_id}: {e}"))
+    }
 }
 
 /// SQLiteを使用したEmailStatsRepositoryの実装
@@ -265,6 +304,25 @@ impl EmailRepository for SqliteEmailRepository {
 
         Ok(row.0)
     }
+
+    async fn save_raw_message(&self, message_id: &str, raw: &[u8]) -> Result<(), String> {
+        sqlx::query(
+            r#"
+            INSERT INTO email_raws (message_id, raw)
+            VALUES (?, ?)
+            ON CONFLICT(message_id) DO UPDATE SET
+                raw = excluded.raw,
+                created_at = datetime('now')
+            "#,
+        )
+        .bind(message_id)
+        .bind(raw)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| format!("Failed to save raw message {message_id}: {e}"))?;
+
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -299,9 +357,60 @@ mod tests {
         .await
         .expect("Failed to create emails table");
 
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS email_raws (
+                message_id TEXT PRIMARY KEY NOT NULL,
+                raw BLOB NOT NULL,
+                created_at TEXT NOT NULL DEFAULT (datetime('now'))
+            )
+            "#,
+        )
+        .execute(&pool)
+        .await
+        .expect("Failed to create email_raws table");
+
         pool
     }
 
+    #[tokio::test]
+    async fn test_save_raw_message_and_get_export_source() {
+        let pool = setup_test_db().await;
+        let repo = SqliteEmailRepository::new(pool.clone());
+
+        let messages = vec![GmailMessage {
+            message_id: "raw1".to_string(),
+            snippet: String::new(),
+            subject: Some("件名".to_string()),
+            body_plain: Some("本文".to_string()),
+            body_html: None,
+            internal_date: 1704067200000,
+            from_address: Some("shop@example.com".to_string()),
+        }];
+        repo.save_messages(&messages).await.unwrap();
+        let (email_id,): (i64,) = sqlx::query_as("SELECT id FROM emails WHERE message_id = 'raw1'")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+
+        // 原本未保存: raw は None
+        let source = repo.get_export_source(email_id).await.unwrap().unwrap();
+        assert_eq!(source.message_id, "raw1");
+        assert_eq!(source.body_plain.as_deref(), Some("本文"));
+        assert!(source.raw.is_none());
+
+        // 保存後は raw が取得でき、再保存で上書きされる
+        repo.save_raw_message("raw1", b"old").await.unwrap();
+        repo.save_raw_message("raw1", b"Subject: x\r\n\r\nbody")
+            .await
+            .unwrap();
+        let source = repo.get_export_source(email_id).await.unwrap().unwrap();
+        assert_eq!(source.raw.as_deref(), Some(&b"Subject: x\r\n\r\nbody"[..]));
+
+        // 存在しない email_id
+        assert!(repo.get_export_source(9999).await.unwrap().is_none());
+    }
+
     #[tokio::test]
     async fn test_email_repository_save_and_get() {
         let pool = setup_test_db().await;
@@ -1,8 +1,10 @@
 use serde::{Deserialize, Serialize};
 use sqlx::sqlite::SqlitePool;
+use ts_rs::TS;
 
 /// 除外パターンレコード
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
 pub struct ExclusionPattern {
     pub id: i64,
     pub shop_domain: Option<String>,
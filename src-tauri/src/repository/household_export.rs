@@ -0,0 +1,242 @@
+use std::collections::HashMap;
+
+use sqlx::sqlite::SqlitePool;
+
+/// 家計簿エクスポートの 1 注文（支出 1 件）
+#[derive(Debug, Clone, PartialEq)]
+pub struct HouseholdExpense {
+    pub order_id: i64,
+    /// 注文日（YYYY-MM-DD）
+    pub order_date: String,
+    pub shop_name: Option<String>,
+    pub order_number: Option<String>,
+    /// 支払額（税込・調整額込み、返金額を差し引いた額）
+    pub amount: i64,
+    /// 小計の大きい順
+    pub items: Vec<HouseholdExpenseItem>,
+}
+
+/// 支出に含まれる商品（カテゴリの判定に使う）
+#[derive(Debug, Clone, PartialEq)]
+pub struct HouseholdExpenseItem {
+    pub item_name: String,
+    pub subtotal: i64,
+    /// items.category
+    pub category: Option<String>,
+    /// 商品マスタ（AI 解析結果）のシリーズ・メーカー
+    pub series: Option<String>,
+    pub maker: Option<String>,
+}
+
+type ExpenseRow = (i64, String, Option<String>, Option<String>, i64);
+type ExpenseItemRow = (
+    i64,
+    String,
+    i64,
+    Option<String>,
+    Option<String>,
+    Option<String>,
+);
+
+/// 家計簿エクスポート用のDB操作
+pub struct SqliteHouseholdExportRepository {
+    pool: SqlitePool,
+}
+
+impl SqliteHouseholdExportRepository {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    /// 注文日が `from`〜`to`（YYYY-MM-DD、None は無制限）の注文を支出として注文日順に返す
+    ///
+    /// 支払額は分析ビュー（analysis_orders）の税込合計と同じ計算に返金額を差し引いたもの。
+    /// 注文日のない注文・支払額が 0 以下の注文（全額キャンセル・返金済み）は含めない。
+    pub async fn list_expenses(
+        &self,
+        from: Option<&str>,
+        to: Option<&str>,
+    ) -> Result<Vec<HouseholdExpense>, String> {
+        let orders: Vec<ExpenseRow> = sqlx::query_as(
+            r#"
+            WITH item_totals AS (
+                SELECT order_id, SUM(price * quantity) AS items_amount
+                FROM items
+                WHERE deleted_at IS NULL
+                GROUP BY order_id
+            )
+            SELECT id, order_day, shop_name, order_number, amount
+            FROM (
+                SELECT o.id,
+                       date(o.order_date) AS order_day,
+                       o.shop_name,
+                       o.order_number,
+                       CASE
+                           WHEN o.tax_included = 1 THEN COALESCE(it.items_amount, 0)
+                           ELSE COALESCE(it.items_amount, 0)
+                                + COALESCE(o.tax_amount, CAST(COALESCE(it.items_amount, 0) * 0.1 AS INTEGER))
+                       END + o.amount_adjustment - COALESCE(o.refund_amount, 0) AS amount
+                FROM orders o
+                LEFT JOIN item_totals it ON it.order_id = o.id
+                WHERE o.deleted_at IS NULL
+                  AND o.order_date IS NOT NULL
+                  AND (?1 IS NULL OR date(o.order_date) >= ?1)
+                  AND (?2 IS NULL OR date(o.order_date) <= ?2)
+            )
+            WHERE amount > 0
+            ORDER BY order_day, id
+            "#,
+        )
+        .bind(from)
+        .bind(to)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| format!("Failed to fetch household expenses: {e}"))?;
+
+        let items: Vec<ExpenseItemRow> = sqlx::query_as(
+            r#"
+            SELECT i.order_id, i.item_name, i.price * i.quantity, i.category, pm.series, pm.maker
+            FROM items i
+            INNER JOIN orders o ON o.id = i.order_id
+            LEFT JOIN product_master pm ON TRIM(i.item_name) = pm.raw_name
+            WHERE i.deleted_at IS NULL
+              AND o.deleted_at IS NULL
+              AND o.order_date IS NOT NULL
+              AND (?1 IS NULL OR date(o.order_date) >= ?1)
+              AND (?2 IS NULL OR date(o.order_date) <= ?2)
+            ORDER BY i.order_id, i.price * i.quantity DESC, i.id
+            "#,
+        )
+        .bind(from)
+        .bind(to)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| format!("Failed to fetch household expense items: {e}"))?;
+
+        let mut items_by_order: HashMap<i64, Vec<HouseholdExpenseItem>> = HashMap::new();
+        for (order_id, item_name, subtotal, category, series, maker) in items {
+            items_by_order
+                .entry(order_id)
+                .or_default()
+                .push(HouseholdExpenseItem {
+                    item_name,
+                    subtotal,
+                    category,
+                    series,
+                    maker,
+                });
+        }
+
+        Ok(orders
+            .into_iter()
+            .map(
+                |(order_id, order_date, shop_name, order_number, amount)| HouseholdExpense {
+                    order_id,
+                    order_date,
+                    shop_name,
+                    order_number,
+                    amount,
+                    items: items_by_order.remove(&order_id).unwrap_or_default(),
+                },
+            )
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sqlx::sqlite::SqlitePoolOptions;
+
+    async fn setup_test_db() -> SqlitePool {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .expect("Failed to create test database");
+
+        sqlx::raw_sql(
+            r#"
+            CREATE TABLE orders (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                shop_name TEXT,
+                order_number TEXT,
+                order_date DATETIME,
+                tax_amount INTEGER,
+                tax_included INTEGER NOT NULL DEFAULT 1,
+                amount_adjustment INTEGER NOT NULL DEFAULT 0,
+                refund_amount INTEGER,
+                deleted_at DATETIME
+            );
+            CREATE TABLE items (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                order_id INTEGER NOT NULL,
+                item_name TEXT NOT NULL,
+                price INTEGER NOT NULL DEFAULT 0,
+                quantity INTEGER NOT NULL DEFAULT 1,
+                category TEXT,
+                deleted_at DATETIME
+            );
+            CREATE TABLE product_master (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                raw_name TEXT UNIQUE NOT NULL,
+                maker TEXT,
+                series TEXT
+            );
+            "#,
+        )
+        .execute(&pool)
+        .await
+        .expect("Failed to create tables");
+
+        pool
+    }
+
+    #[tokio::test]
+    async fn test_list_expenses() {
+        let pool = setup_test_db().await;
+        sqlx::raw_sql(
+            r#"
+            INSERT INTO orders (id, shop_name, order_number, order_date, tax_amount, tax_included, amount_adjustment, refund_amount, deleted_at) VALUES
+                (1, 'ホビーショップA', 'A-1', '2025-03-10 21:15:00', NULL, 1, 660, NULL, NULL),
+                (2, 'ホビーショップB', 'B-1', '2025-03-20', 300, 0, 0, 1000, NULL),
+                (3, 'ホビーショップC', 'C-1', '2025-04-01', NULL, 1, 0, NULL, NULL),
+                (4, 'ホビーショップA', 'A-2', '2025-03-11', NULL, 1, 0, NULL, '2025-03-12'),
+                (5, 'ホビーショップA', 'A-3', '2025-03-12', NULL, 1, 0, 2000, NULL);
+            INSERT INTO items (order_id, item_name, price, quantity, category) VALUES
+                (1, 'ねんどろいど 初音ミク', 5000, 1, 'フィギュア'),
+                (1, 'HG ザク', 1500, 2, NULL),
+                (2, 'figma 鏡音リン', 3000, 1, NULL),
+                (3, 'MG ガンダム', 5000, 1, NULL),
+                (4, 'HG グフ', 1500, 1, NULL),
+                (5, 'HG ドム', 2000, 1, NULL);
+            INSERT INTO product_master (raw_name, maker, series) VALUES
+                ('HG ザク', 'バンダイ', 'ガンプラ');
+            "#,
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let repo = SqliteHouseholdExportRepository::new(pool);
+        let expenses = repo
+            .list_expenses(Some("2025-03-01"), Some("2025-03-31"))
+            .await
+            .unwrap();
+        // ゴミ箱の注文・全額返金の注文・期間外の注文は含めない
+        assert_eq!(
+            expenses.iter().map(|e| e.order_id).collect::<Vec<_>>(),
+            vec![1, 2]
+        );
+
+        assert_eq!(expenses[0].order_date, "2025-03-10");
+        assert_eq!(expenses[0].amount, 8660);
+        assert_eq!(expenses[0].items.len(), 2);
+        assert_eq!(expenses[0].items[0].item_name, "ねんどろいど 初音ミク");
+        assert_eq!(expenses[0].items[1].series.as_deref(), Some("ガンプラ"));
+        // 税抜表示は税額を加算し、返金額を差し引く
+        assert_eq!(expenses[1].amount, 2300);
+
+        assert_eq!(repo.list_expenses(None, None).await.unwrap().len(), 3);
+    }
+}
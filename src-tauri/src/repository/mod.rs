@@ -2,19 +2,43 @@
 //!
 //! このモジュールはデータベース操作を抽象化し、テスト時にモック可能にします。
 
+pub mod analysis_view;
+pub mod arrival_calendar;
+pub mod auto_tag;
 pub mod delivery;
 pub mod email;
 pub mod exclusion_patterns;
+pub mod household_export;
+pub mod monthly_report;
+pub mod notification_rule;
 pub mod order;
+pub mod order_document;
+pub mod order_restructure;
+pub mod order_search;
 pub mod overrides;
 pub mod parse;
+pub mod parse_undo;
+pub mod parser_metrics;
+pub mod payment;
+pub mod point;
+pub mod price_anomaly;
 pub mod product_master;
+pub mod receipt_review;
+pub mod reissue;
+pub mod reservation;
+pub mod series_master;
+pub mod shop_setting_stats;
 pub mod shop_settings;
+pub mod shop_suggestion;
 pub mod stats;
+pub mod storage;
+pub mod storage_location;
+pub mod trash;
+pub mod wishlist;
 
 // email
 pub use email::{
-    EmailRepository, EmailStats, EmailStatsRepository, SqliteEmailRepository,
+    EmailExportSource, EmailRepository, EmailStats, EmailStatsRepository, SqliteEmailRepository,
     SqliteEmailStatsRepository,
 };
 #[cfg(test)]
@@ -22,15 +46,22 @@ pub use email::{MockEmailRepository, MockEmailStatsRepository};
 
 // stats
 pub use stats::{
-    DeliveryStats, DeliveryStatsRepository, MiscStats, MiscStatsRepository, OrderStats,
-    OrderStatsRepository, ProductMasterStats, ProductMasterStatsRepository,
-    SqliteDeliveryStatsRepository, SqliteMiscStatsRepository, SqliteOrderStatsRepository,
-    SqliteProductMasterStatsRepository,
+    summarize_latencies, CancelMakerCount, CancelReasonCount, CancelReasonStats,
+    CancelStatsRepository, DeliveryStats, DeliveryStatsRepository, IngestionLatencyMetrics,
+    IngestionLatencySamples, LatencyMetricsRepository, LatencyStats, MiscStats,
+    MiscStatsRepository, MonthlySpending, OrderStats, OrderStatsRepository, OverviewRepository,
+    ProductMasterStats, ProductMasterStatsRepository, SpendingStatsRepository,
+    SqliteCancelStatsRepository, SqliteDeliveryStatsRepository, SqliteLatencyMetricsRepository,
+    SqliteMiscStatsRepository, SqliteOrderStatsRepository, SqliteOverviewRepository,
+    SqliteProductMasterStatsRepository, SqliteSpendingStatsRepository,
+    SqliteStatsSnapshotRepository, StatsSnapshot, StatsSnapshotRepository, TodayDelivery,
+    TodayOverview,
 };
 #[cfg(test)]
 pub use stats::{
-    MockDeliveryStatsRepository, MockMiscStatsRepository, MockOrderStatsRepository,
-    MockProductMasterStatsRepository,
+    MockCancelStatsRepository, MockDeliveryStatsRepository, MockLatencyMetricsRepository,
+    MockMiscStatsRepository, MockOrderStatsRepository, MockOverviewRepository,
+    MockProductMasterStatsRepository, MockSpendingStatsRepository, MockStatsSnapshotRepository,
 };
 
 // order
@@ -38,11 +69,19 @@ pub use stats::{
 pub use order::MockOrderRepository;
 pub use order::{OrderRepository, SqliteOrderRepository};
 
+// order_search
+pub use order_search::{
+    OrderQuery, OrderSearchResult, OrderSearchSort, SmartFilter, SqliteOrderSearchRepository,
+};
+
 // parse
 #[cfg(test)]
 pub use parse::MockParseRepository;
 pub use parse::{ParseRepository, SqliteParseRepository};
 
+// parse_undo
+pub use parse_undo::SqliteParseUndoRepository;
+
 // shop_settings
 #[cfg(test)]
 pub use shop_settings::MockShopSettingsRepository;
@@ -69,3 +108,107 @@ pub use overrides::{
     ExcludeItemParams, ExcludeOrderParams, ExcludedItem, ExcludedOrder, ItemOverride,
     OrderOverride, SaveItemOverride, SaveOrderOverride, SqliteOverrideRepository,
 };
+
+// price_anomaly
+pub use price_anomaly::{
+    is_price_anomaly, PriceAnomaly, SqlitePriceAnomalyRepository, DEFAULT_PRICE_ANOMALY_RATIO,
+};
+
+// reissue
+pub use reissue::{
+    is_reissue_announcement, ReissueDetection, ReissueWatch, SqliteReissueRepository,
+};
+
+// arrival_calendar
+pub use arrival_calendar::{
+    ArrivalCalendar, ArrivalCalendarDay, ArrivalCalendarEvent, ArrivalEventKind,
+    SqliteArrivalCalendarRepository,
+};
+
+// reservation
+pub use reservation::{
+    detect_reservation_status, detect_reservation_status_in_email, group_duplicate_preorders,
+    is_released, DuplicatePreorder, DuplicatePreorderEntry, ReleaseScheduleItem, ReservationOrder,
+    ReservationStatus, SqliteReservationRepository,
+};
+
+// monthly_report
+pub use monthly_report::{MonthlyReportRecord, SqliteMonthlyReportRepository, UpcomingItem};
+
+// payment
+pub use payment::{
+    detect_payment_notice, Payment, PaymentNotice, PaymentStatus, SqlitePaymentRepository,
+};
+
+// point
+pub use point::{
+    detect_point_notice, PointKind, PointNotice, PointStatus, ShopPointBalance,
+    SqlitePointRepository,
+};
+
+// order_document
+pub use order_document::{
+    OrderDocument, OrderDocumentDelivery, OrderDocumentEmail, OrderDocumentItem,
+    SqliteOrderDocumentRepository,
+};
+
+// order_restructure
+pub use order_restructure::{
+    diff_order_snapshots, snapshot_orders_in_tx, OrderRestructureReport, OrderSnapshot,
+    OrderSnapshotChange, OrderSnapshotItem,
+};
+
+// receipt_review
+pub use receipt_review::{ItemReceiptReview, ShopDamageStats, SqliteReceiptReviewRepository};
+
+// auto_tag
+pub use auto_tag::{
+    apply_auto_tags_for_order_in_tx, load_all_rules_in_tx, matches_auto_tag_rule, tags_for_item,
+    AutoTagRule, SqliteAutoTagRuleRepository,
+};
+
+// notification_rule
+pub use notification_rule::{
+    matches_notification_rule, NotificationRule, NotificationRuleInput, NotificationRuleMatch,
+    NotificationRuleOrder, SqliteNotificationRuleRepository,
+};
+
+// series_master
+pub use series_master::{SeriesAlias, SeriesMaster, SeriesStats, SqliteSeriesMasterRepository};
+
+// trash
+pub use trash::{SqliteTrashRepository, Trash, TrashedItem, TrashedOrder};
+
+// shop_setting_stats
+pub use shop_setting_stats::{
+    ShopSettingMatchStats, SqliteShopSettingStatsRepository,
+    DEFAULT_DISABLE_SUGGESTION_INACTIVE_DAYS,
+};
+
+// shop_suggestion
+pub use shop_suggestion::{
+    email_domain, is_registered_domain, ShopCandidate, SqliteShopSuggestionRepository,
+    DEFAULT_SHOP_SUGGESTION_LIMIT, DEFAULT_SHOP_SUGGESTION_MIN_COUNT,
+};
+
+// analysis_view
+pub use analysis_view::{AnalysisViewVersion, SqliteAnalysisViewRepository};
+
+// storage
+pub use storage::{DbPageStats, SqliteStorageStatsRepository, TableStorage};
+
+// parser_metrics
+pub use parser_metrics::{ParserPerformance, ParserTiming, SqliteParserMetricsRepository};
+
+// storage_location
+pub use storage_location::{
+    ItemStorageLocation, SqliteStorageLocationRepository, StorageLocationSummary, StoredItem,
+};
+
+// wishlist
+pub use wishlist::{SqliteWishlistRepository, WishlistItem, WishlistItemInput, WishlistPurchase};
+
+// household_export
+pub use household_export::{
+    HouseholdExpense, HouseholdExpenseItem, SqliteHouseholdExportRepository,
+};
@@ -0,0 +1,261 @@
+use serde::{Deserialize, Serialize};
+use sqlx::sqlite::SqlitePool;
+use ts_rs::TS;
+
+/// 今月お届け予定の商品
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct UpcomingItem {
+    pub item_name: String,
+    pub shop_name: Option<String>,
+    /// お届け予定日（YYYY-MM-DD）
+    pub estimated_delivery: String,
+}
+
+/// 保存済みの月次レポート
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct MonthlyReportRecord {
+    /// 対象月（YYYY-MM）
+    pub month: String,
+    /// `MonthlySummary` の JSON
+    pub summary_json: String,
+    pub markdown: String,
+    pub created_at: String,
+}
+
+/// 月次レポートのDB操作
+pub struct SqliteMonthlyReportRepository {
+    pool: SqlitePool,
+}
+
+impl SqliteMonthlyReportRepository {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    /// 期間内（from 以上 to 未満、YYYY-MM-DD）に配達完了した注文の商品名を返す
+    pub async fn get_delivered_item_names(
+        &self,
+        from: &str,
+        to: &str,
+    ) -> Result<Vec<String>, String> {
+        sqlx::query_scalar(
+            r#"
+            SELECT i.item_name
+            FROM items i
+            INNER JOIN orders o ON o.id = i.order_id
+            WHERE i.deleted_at IS NULL
+              AND o.deleted_at IS NULL
+              AND EXISTS (
+                  SELECT 1 FROM deliveries d
+                  WHERE d.order_id = o.id
+                    AND d.delivery_status = 'delivered'
+                    AND date(COALESCE(d.actual_delivery, d.updated_at)) >= ?1
+                    AND date(COALESCE(d.actual_delivery, d.updated_at)) < ?2
+              )
+            ORDER BY i.id
+            "#,
+        )
+        .bind(from)
+        .bind(to)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| format!("Failed to fetch delivered items: {e}"))
+    }
+
+    /// お届け予定日が期間内（from 以上 to 未満）で、まだ配達されていない商品を予定日順に返す
+    pub async fn get_upcoming_items(
+        &self,
+        from: &str,
+        to: &str,
+    ) -> Result<Vec<UpcomingItem>, String> {
+        let rows: Vec<(String, Option<String>, String)> = sqlx::query_as(
+            r#"
+            SELECT i.item_name, o.shop_name, MIN(date(d.estimated_delivery)) AS estimated
+            FROM deliveries d
+            INNER JOIN orders o ON o.id = d.order_id
+            INNER JOIN items i ON i.order_id = o.id
+            WHERE i.deleted_at IS NULL
+              AND o.deleted_at IS NULL
+              AND d.delivery_status NOT IN ('delivered', 'cancelled', 'returned')
+              AND date(d.estimated_delivery) >= ?1
+              AND date(d.estimated_delivery) < ?2
+            GROUP BY i.id
+            ORDER BY estimated, i.id
+            "#,
+        )
+        .bind(from)
+        .bind(to)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| format!("Failed to fetch upcoming items: {e}"))?;
+
+        Ok(rows
+            .into_iter()
+            .map(|(item_name, shop_name, estimated_delivery)| UpcomingItem {
+                item_name,
+                shop_name,
+                estimated_delivery,
+            })
+            .collect())
+    }
+
+    pub async fn exists(&self, month: &str) -> Result<bool, String> {
+        let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM monthly_reports WHERE month = ?")
+            .bind(month)
+            .fetch_one(&self.pool)
+            .await
+            .map_err(|e| format!("Failed to check monthly report: {e}"))?;
+        Ok(count > 0)
+    }
+
+    /// レポートを保存する（同じ月は上書き）
+    pub async fn save(
+        &self,
+        month: &str,
+        summary_json: &str,
+        markdown: &str,
+    ) -> Result<(), String> {
+        sqlx::query(
+            r#"
+            INSERT INTO monthly_reports (month, summary_json, markdown)
+            VALUES (?, ?, ?)
+            ON CONFLICT(month) DO UPDATE SET
+                summary_json = excluded.summary_json,
+                markdown = excluded.markdown,
+                created_at = CURRENT_TIMESTAMP
+            "#,
+        )
+        .bind(month)
+        .bind(summary_json)
+        .bind(markdown)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| format!("Failed to save monthly report: {e}"))?;
+        Ok(())
+    }
+
+    /// 保存済みレポートを新しい月から返す
+    pub async fn list(&self) -> Result<Vec<MonthlyReportRecord>, String> {
+        let rows: Vec<(String, String, String, String)> = sqlx::query_as(
+            "SELECT month, summary_json, markdown, created_at FROM monthly_reports ORDER BY month DESC",
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| format!("Failed to fetch monthly reports: {e}"))?;
+
+        Ok(rows
+            .into_iter()
+            .map(
+                |(month, summary_json, markdown, created_at)| MonthlyReportRecord {
+                    month,
+                    summary_json,
+                    markdown,
+                    created_at,
+                },
+            )
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sqlx::sqlite::SqlitePoolOptions;
+
+    async fn setup_test_db() -> SqlitePool {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .expect("Failed to create test database");
+
+        sqlx::query(
+            r#"
+            CREATE TABLE orders (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                shop_name TEXT,
+                deleted_at DATETIME
+            );
+            CREATE TABLE items (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                order_id INTEGER NOT NULL,
+                item_name TEXT NOT NULL,
+                deleted_at DATETIME
+            );
+            CREATE TABLE deliveries (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                order_id INTEGER NOT NULL,
+                delivery_status TEXT NOT NULL DEFAULT 'not_shipped',
+                estimated_delivery DATETIME,
+                actual_delivery DATETIME,
+                updated_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP
+            );
+            "#,
+        )
+        .execute(&pool)
+        .await
+        .expect("Failed to create tables");
+        sqlx::query(include_str!("../../migrations/016_monthly_reports.sql"))
+            .execute(&pool)
+            .await
+            .expect("Failed to create monthly_reports table");
+
+        pool
+    }
+
+    #[tokio::test]
+    async fn test_get_delivered_and_upcoming_items() {
+        let pool = setup_test_db().await;
+        sqlx::query(
+            r#"
+            INSERT INTO orders (id, shop_name) VALUES (1, 'A'), (2, 'B'), (3, 'C');
+            INSERT INTO items (order_id, item_name) VALUES
+                (1, '先月届いた商品'), (2, '今月届く商品'), (3, '先々月届いた商品');
+            INSERT INTO deliveries (order_id, delivery_status, estimated_delivery, actual_delivery, updated_at) VALUES
+                (1, 'delivered', NULL, '2026-09-15 10:00:00', '2026-09-15 10:00:00'),
+                (2, 'shipped', '2026-10-20', NULL, '2026-09-30 10:00:00'),
+                (3, 'delivered', NULL, NULL, '2026-08-31 23:00:00');
+            "#,
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let repo = SqliteMonthlyReportRepository::new(pool);
+        assert_eq!(
+            repo.get_delivered_item_names("2026-09-01", "2026-10-01")
+                .await
+                .unwrap(),
+            vec!["先月届いた商品".to_string()]
+        );
+        assert_eq!(
+            repo.get_upcoming_items("2026-10-01", "2026-11-01")
+                .await
+                .unwrap(),
+            vec![UpcomingItem {
+                item_name: "今月届く商品".to_string(),
+                shop_name: Some("B".to_string()),
+                estimated_delivery: "2026-10-20".to_string(),
+            }]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_save_overwrites_and_lists() {
+        let pool = setup_test_db().await;
+        let repo = SqliteMonthlyReportRepository::new(pool);
+
+        assert!(!repo.exists("2026-09").await.unwrap());
+        repo.save("2026-09", "{}", "old").await.unwrap();
+        repo.save("2026-09", "{}", "new").await.unwrap();
+        repo.save("2026-08", "{}", "august").await.unwrap();
+        assert!(repo.exists("2026-09").await.unwrap());
+
+        let reports = repo.list().await.unwrap();
+        assert_eq!(reports.len(), 2);
+        assert_eq!(reports[0].month, "2026-09");
+        assert_eq!(reports[0].markdown, "new");
+    }
+}
@@ -0,0 +1,716 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use sqlx::sqlite::SqlitePool;
+use ts_rs::TS;
+
+/// 配送状況の値（deliveries.delivery_status の CHECK 制約と同じ）
+const DELIVERY_STATUSES: &[&str] = &[
+    "not_shipped",
+    "preparing",
+    "shipped",
+    "in_transit",
+    "out_for_delivery",
+    "delivered",
+    "failed",
+    "returned",
+    "cancelled",
+];
+
+/// スマート通知ルール（条件は None なら不問、すべての条件を満たす注文に一致する）
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS, sqlx::FromRow)]
+#[ts(export)]
+pub struct NotificationRule {
+    pub id: i64,
+    pub name: String,
+    pub is_enabled: bool,
+    /// 注文合計（税込・調整額込み）の下限
+    pub min_amount: Option<i64>,
+    /// ショップ名またはショップドメイン（大文字小文字を区別しない完全一致）
+    pub shop: Option<String>,
+    /// 注文内のいずれかの商品に付いたタグ
+    pub tag: Option<String>,
+    /// 最新の配送状況（例: "shipped"）
+    pub delivery_status: Option<String>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+/// 通知ルールの作成・更新内容
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct NotificationRuleInput {
+    pub name: String,
+    pub is_enabled: bool,
+    pub min_amount: Option<i64>,
+    pub shop: Option<String>,
+    pub tag: Option<String>,
+    pub delivery_status: Option<String>,
+}
+
+impl NotificationRuleInput {
+    /// 前後の空白を除き、空文字の条件は None に揃えて検証する
+    fn normalized(&self) -> Result<Self, String> {
+        let trimmed = |v: &Option<String>| {
+            v.as_deref()
+                .map(str::trim)
+                .filter(|s| !s.is_empty())
+                .map(str::to_string)
+        };
+        let input = Self {
+            name: self.name.trim().to_string(),
+            is_enabled: self.is_enabled,
+            min_amount: self.min_amount,
+            shop: trimmed(&self.shop),
+            tag: trimmed(&self.tag),
+            delivery_status: trimmed(&self.delivery_status),
+        };
+
+        if input.name.is_empty() {
+            return Err("Notification rule name is required".to_string());
+        }
+        if input.min_amount.is_some_and(|amount| amount < 0) {
+            return Err("min_amount must not be negative".to_string());
+        }
+        if let Some(status) = &input.delivery_status {
+            if !DELIVERY_STATUSES.contains(&status.as_str()) {
+                return Err(format!("Unknown delivery status: {status}"));
+            }
+        }
+        if input.min_amount.is_none()
+            && input.shop.is_none()
+            && input.tag.is_none()
+            && input.delivery_status.is_none()
+        {
+            return Err("Notification rule needs at least one condition".to_string());
+        }
+        Ok(input)
+    }
+}
+
+/// ルール評価の対象となる注文
+#[derive(Debug, Clone, PartialEq)]
+pub struct NotificationRuleOrder {
+    pub order_id: i64,
+    pub shop_domain: Option<String>,
+    pub shop_name: Option<String>,
+    pub order_number: Option<String>,
+    /// 注文合計（税込・調整額込み）
+    pub total_amount: i64,
+    /// 最新の配送状況（配送情報がなければ None）
+    pub delivery_status: Option<String>,
+    /// 注文内の商品に付いたタグ
+    pub tags: Vec<String>,
+}
+
+impl NotificationRuleOrder {
+    /// 一致済みの記録に使う注文のキー
+    ///
+    /// 全件再パースで orders.id が振り直されても同じ注文を指すよう、ショップドメインと注文番号から作る。
+    /// 注文番号がない注文のみ orders.id を使う。
+    pub fn order_key(&self) -> String {
+        match self.order_number.as_deref().filter(|n| !n.is_empty()) {
+            Some(number) => format!("{}:{}", self.shop_domain.as_deref().unwrap_or(""), number),
+            None => format!("id:{}", self.order_id),
+        }
+    }
+}
+
+/// ルールに新たに一致した注文（通知内容）
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct NotificationRuleMatch {
+    pub rule_id: i64,
+    pub rule_name: String,
+    pub order_id: i64,
+    pub shop_name: Option<String>,
+    pub order_number: Option<String>,
+    pub total_amount: i64,
+    pub delivery_status: Option<String>,
+}
+
+/// 注文がルールのすべての条件を満たすか
+pub fn matches_notification_rule(rule: &NotificationRule, order: &NotificationRuleOrder) -> bool {
+    if rule.min_amount.is_some_and(|min| order.total_amount < min) {
+        return false;
+    }
+    if let Some(shop) = &rule.shop {
+        let shop = shop.to_lowercase();
+        let matches_shop = [&order.shop_name, &order.shop_domain]
+            .into_iter()
+            .flatten()
+            .any(|value| value.to_lowercase() == shop);
+        if !matches_shop {
+            return false;
+        }
+    }
+    if let Some(tag) = &rule.tag {
+        if !order.tags.iter().any(|t| t == tag) {
+            return false;
+        }
+    }
+    if let Some(status) = &rule.delivery_status {
+        if order.delivery_status.as_deref() != Some(status.as_str()) {
+            return false;
+        }
+    }
+    true
+}
+
+type OrderFactRow = (
+    i64,
+    Option<String>,
+    Option<String>,
+    Option<String>,
+    i64,
+    Option<String>,
+);
+
+const RULE_SELECT: &str = r#"
+    SELECT id, name, is_enabled, min_amount, shop, tag, delivery_status, created_at, updated_at
+    FROM notification_rules
+"#;
+
+/// スマート通知ルールのDB操作
+pub struct SqliteNotificationRuleRepository {
+    pool: SqlitePool,
+}
+
+impl SqliteNotificationRuleRepository {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    pub async fn list(&self) -> Result<Vec<NotificationRule>, String> {
+        sqlx::query_as(&format!("{RULE_SELECT} ORDER BY id ASC"))
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| format!("Failed to fetch notification rules: {e}"))
+    }
+
+    /// ルールを追加し、notification_rules.id を返す
+    ///
+    /// 追加時点で既に条件を満たしている注文は通知せず一致済みとして記録し、以降に一致した注文だけを通知する。
+    pub async fn add(&self, input: &NotificationRuleInput) -> Result<i64, String> {
+        let input = input.normalized()?;
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| format!("Failed to begin transaction: {e}"))?;
+
+        let id: i64 = sqlx::query_scalar(
+            r#"
+            INSERT INTO notification_rules (name, is_enabled, min_amount, shop, tag, delivery_status)
+            VALUES (?, ?, ?, ?, ?, ?)
+            RETURNING id
+            "#,
+        )
+        .bind(&input.name)
+        .bind(input.is_enabled)
+        .bind(input.min_amount)
+        .bind(&input.shop)
+        .bind(&input.tag)
+        .bind(&input.delivery_status)
+        .fetch_one(tx.as_mut())
+        .await
+        .map_err(|e| format!("Failed to add notification rule: {e}"))?;
+
+        record_baseline_in_tx(&mut tx, id).await?;
+        tx.commit()
+            .await
+            .map_err(|e| format!("Failed to commit transaction: {e}"))?;
+        Ok(id)
+    }
+
+    /// ルールを更新する。条件が変わるため一致済みの記録は作り直す（追加時と同じく現時点の一致は通知しない）
+    pub async fn update(&self, id: i64, input: &NotificationRuleInput) -> Result<(), String> {
+        let input = input.normalized()?;
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| format!("Failed to begin transaction: {e}"))?;
+
+        let result = sqlx::query(
+            r#"
+            UPDATE notification_rules
+            SET name = ?, is_enabled = ?, min_amount = ?, shop = ?, tag = ?, delivery_status = ?
+            WHERE id = ?
+            "#,
+        )
+        .bind(&input.name)
+        .bind(input.is_enabled)
+        .bind(input.min_amount)
+        .bind(&input.shop)
+        .bind(&input.tag)
+        .bind(&input.delivery_status)
+        .bind(id)
+        .execute(tx.as_mut())
+        .await
+        .map_err(|e| format!("Failed to update notification rule: {e}"))?;
+        if result.rows_affected() == 0 {
+            return Err(format!("Notification rule not found: {id}"));
+        }
+
+        sqlx::query("DELETE FROM notification_rule_hits WHERE rule_id = ?")
+            .bind(id)
+            .execute(tx.as_mut())
+            .await
+            .map_err(|e| format!("Failed to clear notification rule hits: {e}"))?;
+        record_baseline_in_tx(&mut tx, id).await?;
+        tx.commit()
+            .await
+            .map_err(|e| format!("Failed to commit transaction: {e}"))?;
+        Ok(())
+    }
+
+    pub async fn delete(&self, id: i64) -> Result<(), String> {
+        sqlx::query("DELETE FROM notification_rules WHERE id = ?")
+            .bind(id)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| format!("Failed to delete notification rule: {e}"))?;
+        Ok(())
+    }
+
+    /// 有効なルールを評価し、新たに一致した注文を一致済みとして記録して返す（バッチ処理後に呼ぶ）
+    pub async fn evaluate(&self) -> Result<Vec<NotificationRuleMatch>, String> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| format!("Failed to begin transaction: {e}"))?;
+
+        let rules: Vec<NotificationRule> = sqlx::query_as(&format!(
+            "{RULE_SELECT} WHERE is_enabled = 1 ORDER BY id ASC"
+        ))
+        .fetch_all(tx.as_mut())
+        .await
+        .map_err(|e| format!("Failed to fetch notification rules: {e}"))?;
+        if rules.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let orders = fetch_orders_in_tx(&mut tx).await?;
+        let mut matches = Vec::new();
+        for rule in &rules {
+            for order in orders.iter().filter(|o| matches_notification_rule(rule, o)) {
+                if insert_hit_in_tx(&mut tx, rule.id, &order.order_key(), true).await? {
+                    matches.push(NotificationRuleMatch {
+                        rule_id: rule.id,
+                        rule_name: rule.name.clone(),
+                        order_id: order.order_id,
+                        shop_name: order.shop_name.clone(),
+                        order_number: order.order_number.clone(),
+                        total_amount: order.total_amount,
+                        delivery_status: order.delivery_status.clone(),
+                    });
+                }
+            }
+        }
+
+        tx.commit()
+            .await
+            .map_err(|e| format!("Failed to commit transaction: {e}"))?;
+        Ok(matches)
+    }
+}
+
+/// ルールに現時点で一致している注文を、通知せず一致済みとして記録する
+async fn record_baseline_in_tx(
+    tx: &mut sqlx::Transaction<'_, sqlx::Sqlite>,
+    rule_id: i64,
+) -> Result<(), String> {
+    let rule: NotificationRule = sqlx::query_as(&format!("{RULE_SELECT} WHERE id = ?"))
+        .bind(rule_id)
+        .fetch_one(tx.as_mut())
+        .await
+        .map_err(|e| format!("Failed to fetch notification rule: {e}"))?;
+
+    for order in fetch_orders_in_tx(tx).await? {
+        if matches_notification_rule(&rule, &order) {
+            insert_hit_in_tx(tx, rule_id, &order.order_key(), false).await?;
+        }
+    }
+    Ok(())
+}
+
+/// 一致済みとして記録する。未記録だった（新たに一致した）場合は true
+async fn insert_hit_in_tx(
+    tx: &mut sqlx::Transaction<'_, sqlx::Sqlite>,
+    rule_id: i64,
+    order_key: &str,
+    notified: bool,
+) -> Result<bool, String> {
+    let result = sqlx::query(
+        "INSERT OR IGNORE INTO notification_rule_hits (rule_id, order_key, notified) VALUES (?, ?, ?)",
+    )
+    .bind(rule_id)
+    .bind(order_key)
+    .bind(notified)
+    .execute(tx.as_mut())
+    .await
+    .map_err(|e| format!("Failed to record notification rule hit: {e}"))?;
+    Ok(result.rows_affected() > 0)
+}
+
+/// ゴミ箱に入っていない全注文の合計金額・最新の配送状況・タグを取得する
+async fn fetch_orders_in_tx(
+    tx: &mut sqlx::Transaction<'_, sqlx::Sqlite>,
+) -> Result<Vec<NotificationRuleOrder>, String> {
+    // 税抜表示の注文は tax_amount（未記載なら商品合計の 10%）を加算して税込に揃え、調整額を加算する
+    let rows: Vec<OrderFactRow> = sqlx::query_as(
+        r#"
+        WITH order_amounts AS (
+            SELECT o.id, o.shop_domain, o.shop_name, o.order_number,
+                   o.tax_amount, o.tax_included, o.amount_adjustment,
+                   COALESCE(SUM(i.price * i.quantity), 0) AS items_amount
+            FROM orders o
+            LEFT JOIN items i ON i.order_id = o.id AND i.deleted_at IS NULL
+            WHERE o.deleted_at IS NULL
+            GROUP BY o.id
+        )
+        SELECT oa.id, oa.shop_domain, oa.shop_name, oa.order_number,
+               oa.items_amount
+               + CASE WHEN oa.tax_included = 0
+                      THEN COALESCE(oa.tax_amount, CAST(oa.items_amount * 0.1 AS INTEGER))
+                      ELSE 0 END
+               + oa.amount_adjustment AS total_amount,
+               (
+                   SELECT d.delivery_status FROM deliveries d
+                   WHERE d.order_id = oa.id
+                   ORDER BY d.updated_at DESC, d.id DESC
+                   LIMIT 1
+               ) AS delivery_status
+        FROM order_amounts oa
+        ORDER BY oa.id ASC
+        "#,
+    )
+    .fetch_all(tx.as_mut())
+    .await
+    .map_err(|e| format!("Failed to fetch orders for notification rules: {e}"))?;
+
+    let tag_rows: Vec<(i64, String)> = sqlx::query_as(
+        r#"
+        SELECT DISTINCT i.order_id, t.tag
+        FROM item_tags t
+        INNER JOIN items i ON i.id = t.item_id
+        WHERE i.deleted_at IS NULL
+        "#,
+    )
+    .fetch_all(tx.as_mut())
+    .await
+    .map_err(|e| format!("Failed to fetch item tags for notification rules: {e}"))?;
+    let mut tags: HashMap<i64, Vec<String>> = HashMap::new();
+    for (order_id, tag) in tag_rows {
+        tags.entry(order_id).or_default().push(tag);
+    }
+
+    Ok(rows
+        .into_iter()
+        .map(
+            |(order_id, shop_domain, shop_name, order_number, total_amount, delivery_status)| {
+                NotificationRuleOrder {
+                    order_id,
+                    shop_domain,
+                    shop_name,
+                    order_number,
+                    total_amount,
+                    delivery_status,
+                    tags: tags.remove(&order_id).unwrap_or_default(),
+                }
+            },
+        )
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sqlx::sqlite::SqlitePoolOptions;
+
+    async fn setup_test_db() -> SqlitePool {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .expect("Failed to create test database");
+
+        sqlx::raw_sql(
+            r#"
+            CREATE TABLE orders (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                shop_domain TEXT,
+                shop_name TEXT,
+                order_number TEXT,
+                tax_amount INTEGER,
+                tax_included INTEGER NOT NULL DEFAULT 1,
+                amount_adjustment INTEGER NOT NULL DEFAULT 0,
+                deleted_at DATETIME
+            );
+            CREATE TABLE items (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                order_id INTEGER NOT NULL,
+                item_name TEXT NOT NULL,
+                price INTEGER NOT NULL DEFAULT 0,
+                quantity INTEGER NOT NULL DEFAULT 1,
+                deleted_at DATETIME
+            );
+            CREATE TABLE deliveries (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                order_id INTEGER NOT NULL,
+                delivery_status TEXT NOT NULL DEFAULT 'not_shipped',
+                updated_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP
+            );
+            CREATE TABLE item_tags (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                item_id INTEGER NOT NULL,
+                tag TEXT NOT NULL,
+                UNIQUE (item_id, tag)
+            );
+            "#,
+        )
+        .execute(&pool)
+        .await
+        .expect("Failed to create tables");
+        sqlx::raw_sql(include_str!("../../migrations/030_notification_rules.sql"))
+            .execute(&pool)
+            .await
+            .expect("Failed to apply migration 030");
+        pool
+    }
+
+    /// 注文 1 件（商品 1 件）を追加して orders.id を返す
+    async fn insert_order(pool: &SqlitePool, shop: &str, number: &str, price: i64) -> i64 {
+        let order_id: i64 = sqlx::query_scalar(
+            "INSERT INTO orders (shop_domain, shop_name, order_number) VALUES (?, ?, ?) RETURNING id",
+        )
+        .bind(format!("{shop}.example.com"))
+        .bind(shop)
+        .bind(number)
+        .fetch_one(pool)
+        .await
+        .unwrap();
+        sqlx::query("INSERT INTO items (order_id, item_name, price) VALUES (?, 'item', ?)")
+            .bind(order_id)
+            .bind(price)
+            .execute(pool)
+            .await
+            .unwrap();
+        order_id
+    }
+
+    async fn set_status(pool: &SqlitePool, order_id: i64, status: &str) {
+        sqlx::query("INSERT INTO deliveries (order_id, delivery_status) VALUES (?, ?)")
+            .bind(order_id)
+            .bind(status)
+            .execute(pool)
+            .await
+            .unwrap();
+    }
+
+    fn shipped_over(min_amount: i64) -> NotificationRuleInput {
+        NotificationRuleInput {
+            name: "高額注文の発送".to_string(),
+            is_enabled: true,
+            min_amount: Some(min_amount),
+            shop: None,
+            tag: None,
+            delivery_status: Some("shipped".to_string()),
+        }
+    }
+
+    fn order(total_amount: i64, status: Option<&str>, tags: &[&str]) -> NotificationRuleOrder {
+        NotificationRuleOrder {
+            order_id: 1,
+            shop_domain: Some("shop.example.com".to_string()),
+            shop_name: Some("ホビーショップ".to_string()),
+            order_number: Some("A-1".to_string()),
+            total_amount,
+            delivery_status: status.map(str::to_string),
+            tags: tags.iter().map(|t| t.to_string()).collect(),
+        }
+    }
+
+    fn rule(min_amount: Option<i64>, shop: Option<&str>, tag: Option<&str>) -> NotificationRule {
+        NotificationRule {
+            id: 1,
+            name: "rule".to_string(),
+            is_enabled: true,
+            min_amount,
+            shop: shop.map(str::to_string),
+            tag: tag.map(str::to_string),
+            delivery_status: Some("shipped".to_string()),
+            created_at: String::new(),
+            updated_at: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_matches_notification_rule() {
+        let shipped = order(12000, Some("shipped"), &["フィギュア"]);
+        assert!(matches_notification_rule(
+            &rule(Some(10000), None, None),
+            &shipped
+        ));
+        assert!(!matches_notification_rule(
+            &rule(Some(15000), None, None),
+            &shipped
+        ));
+        assert!(!matches_notification_rule(
+            &rule(None, None, None),
+            &order(12000, Some("preparing"), &[])
+        ));
+        assert!(matches_notification_rule(
+            &rule(None, Some("SHOP.example.com"), Some("フィギュア")),
+            &shipped
+        ));
+        assert!(matches_notification_rule(
+            &rule(None, Some("ホビーショップ"), None),
+            &shipped
+        ));
+        assert!(!matches_notification_rule(
+            &rule(None, Some("other.example.com"), None),
+            &shipped
+        ));
+        assert!(!matches_notification_rule(
+            &rule(None, None, Some("プラモデル")),
+            &shipped
+        ));
+    }
+
+    #[test]
+    fn test_order_key() {
+        let mut o = order(0, None, &[]);
+        assert_eq!(o.order_key(), "shop.example.com:A-1");
+        o.order_number = None;
+        assert_eq!(o.order_key(), "id:1");
+    }
+
+    #[tokio::test]
+    async fn test_add_validates_input() {
+        let pool = setup_test_db().await;
+        let repo = SqliteNotificationRuleRepository::new(pool);
+
+        let mut input = shipped_over(10000);
+        input.name = "  ".to_string();
+        assert!(repo.add(&input).await.is_err());
+
+        let mut input = shipped_over(10000);
+        input.delivery_status = Some("lost".to_string());
+        assert!(repo.add(&input).await.is_err());
+
+        let input = NotificationRuleInput {
+            name: "条件なし".to_string(),
+            is_enabled: true,
+            min_amount: None,
+            shop: Some(" ".to_string()),
+            tag: None,
+            delivery_status: None,
+        };
+        assert!(repo.add(&input).await.is_err());
+        assert!(repo.list().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_evaluate_notifies_only_new_matches() {
+        let pool = setup_test_db().await;
+        let repo = SqliteNotificationRuleRepository::new(pool.clone());
+
+        // ルール作成前から発送済みの注文は通知しない
+        let already_shipped = insert_order(&pool, "shop", "A-1", 20000).await;
+        set_status(&pool, already_shipped, "shipped").await;
+        let expensive = insert_order(&pool, "shop", "A-2", 12000).await;
+        let cheap = insert_order(&pool, "shop", "A-3", 3000).await;
+
+        let rule_id = repo.add(&shipped_over(10000)).await.unwrap();
+        assert!(repo.evaluate().await.unwrap().is_empty());
+
+        set_status(&pool, expensive, "shipped").await;
+        set_status(&pool, cheap, "shipped").await;
+        let matches = repo.evaluate().await.unwrap();
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].rule_id, rule_id);
+        assert_eq!(matches[0].rule_name, "高額注文の発送");
+        assert_eq!(matches[0].order_number.as_deref(), Some("A-2"));
+        assert_eq!(matches[0].total_amount, 12000);
+
+        // 同じ注文は再通知しない（全件再パースで orders.id が変わっても同じ）
+        assert!(repo.evaluate().await.unwrap().is_empty());
+        sqlx::query("UPDATE orders SET id = 100 WHERE id = ?")
+            .bind(expensive)
+            .execute(&pool)
+            .await
+            .unwrap();
+        sqlx::query("UPDATE deliveries SET order_id = 100 WHERE order_id = ?")
+            .bind(expensive)
+            .execute(&pool)
+            .await
+            .unwrap();
+        assert!(repo.evaluate().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_evaluate_skips_disabled_rules_and_update_resets_baseline() {
+        let pool = setup_test_db().await;
+        let repo = SqliteNotificationRuleRepository::new(pool.clone());
+        let order_id = insert_order(&pool, "shop", "A-1", 12000).await;
+
+        let mut input = shipped_over(10000);
+        input.is_enabled = false;
+        let rule_id = repo.add(&input).await.unwrap();
+        set_status(&pool, order_id, "shipped").await;
+        assert!(repo.evaluate().await.unwrap().is_empty());
+
+        // 有効化時点で既に一致している注文は通知しない
+        input.is_enabled = true;
+        repo.update(rule_id, &input).await.unwrap();
+        assert!(repo.evaluate().await.unwrap().is_empty());
+
+        let rules = repo.list().await.unwrap();
+        assert_eq!(rules.len(), 1);
+        assert!(rules[0].is_enabled);
+        assert_eq!(rules[0].delivery_status.as_deref(), Some("shipped"));
+
+        assert!(repo.update(999, &input).await.is_err());
+        repo.delete(rule_id).await.unwrap();
+        assert!(repo.list().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_evaluate_matches_tag_and_tax_excluded_total() {
+        let pool = setup_test_db().await;
+        let repo = SqliteNotificationRuleRepository::new(pool.clone());
+        repo.add(&NotificationRuleInput {
+            name: "タグ付き高額注文".to_string(),
+            is_enabled: true,
+            min_amount: Some(11000),
+            shop: None,
+            tag: Some("限定版".to_string()),
+            delivery_status: None,
+        })
+        .await
+        .unwrap();
+
+        // 税抜 10,000 円（税額未記載は 10%）→ 税込 11,000 円
+        let order_id = insert_order(&pool, "shop", "A-1", 10000).await;
+        sqlx::query("UPDATE orders SET tax_included = 0 WHERE id = ?")
+            .bind(order_id)
+            .execute(&pool)
+            .await
+            .unwrap();
+        assert!(repo.evaluate().await.unwrap().is_empty());
+
+        sqlx::query(
+            "INSERT INTO item_tags (item_id, tag) SELECT id, '限定版' FROM items WHERE order_id = ?",
+        )
+        .bind(order_id)
+        .execute(&pool)
+        .await
+        .unwrap();
+        let matches = repo.evaluate().await.unwrap();
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].total_amount, 11000);
+    }
+}
@@ -1,8 +1,11 @@
 use crate::gemini::normalize_product_name;
-use crate::parsers::cancel_info::CancelInfo;
+use crate::parsers::cancel_info::{CancelInfo, CancelReason};
 use crate::parsers::consolidation_info::ConsolidationInfo;
+use crate::parsers::delay_info::DelayInfo;
+use crate::parsers::delivery_date_change_info::DeliveryDateChangeInfo;
 use crate::parsers::order_number_change_info::OrderNumberChangeInfo;
-use crate::parsers::OrderInfo;
+use crate::parsers::release_date::extract_fuzzy_release_date;
+use crate::parsers::{normalize_order_number, OrderInfo, OrderItem};
 use async_trait::async_trait;
 #[cfg(test)]
 use mockall::automock;
@@ -38,6 +41,20 @@ fn resolve_delivery_status(status: Option<&str>) -> Result<&str, String> {
     }
 }
 
+/// 商品の発売予定（パーサーが抽出していなければ商品名の `【25年8月予約】` 等から補う）
+///
+/// DB には並び替え用の `release_date` と精度（`release_date_precision`）の組で保存する。
+fn item_release_date(item: &OrderItem) -> (Option<String>, Option<&'static str>) {
+    match item
+        .release_date
+        .clone()
+        .or_else(|| extract_fuzzy_release_date(&item.name, None))
+    {
+        Some(date) => (Some(date.to_release_date()), Some(date.precision.as_str())),
+        None => (None, None),
+    }
+}
+
 /// 注文関連のDB操作を抽象化するトレイト
 #[cfg_attr(test, automock)]
 #[async_trait]
@@ -243,40 +260,143 @@ impl SqliteOrderRepository {
             }
         }
         for domain_opt in domains_to_try {
-            let row: Option<(i64,)> = match &domain_opt {
-                Some(domain) => {
-                    sqlx::query_as(
-                        r#"
-                        SELECT id FROM orders
-                        WHERE order_number COLLATE NOCASE = ? AND shop_domain = ?
-                        LIMIT 1
-                        "#,
-                    )
-                    .bind(order_number)
-                    .bind(domain)
-                    .fetch_optional(tx.as_mut())
-                    .await?
-                }
-                None => {
-                    sqlx::query_as(
-                        r#"
-                        SELECT id FROM orders
-                        WHERE order_number COLLATE NOCASE = ? AND (shop_domain IS NULL OR shop_domain = '')
-                        LIMIT 1
-                        "#,
-                    )
-                    .bind(order_number)
-                    .fetch_optional(tx.as_mut())
-                    .await?
-                }
-            };
-            if let Some((id,)) = row {
+            if let Some(id) =
+                Self::find_order_id_by_number_in_tx(tx, order_number, domain_opt.as_deref()).await?
+            {
                 return Ok(Some(id));
             }
         }
         Ok(None)
     }
 
+    /// 注文番号で注文IDを検索する（shop_domain が None の場合はドメイン未設定の注文が対象）。
+    ///
+    /// ゴミ箱内の注文（deleted_at が設定済み）は対象外。メールの内容で削除済みの注文を
+    /// 更新しないよう、save / apply 系の処理はすべてこの検索を経由する。
+    ///
+    /// まず大文字小文字を無視した完全一致で検索し、見つからなければ `normalize_order_number` で
+    /// 正規化した値を `order_number_normalized`（インデックスあり）で検索する（ハイフン有無等の吸収）。
+    /// 正規化済み注文番号が未設定の既存の注文は、検索前に `backfill_order_number_normalized_in_tx` で補完する。
+    pub(crate) async fn find_order_id_by_number_in_tx(
+        tx: &mut sqlx::Transaction<'_, Sqlite>,
+        order_number: &str,
+        shop_domain: Option<&str>,
+    ) -> Result<Option<i64>, sqlx::Error> {
+        let normalized = normalize_order_number(order_number);
+        match shop_domain {
+            Some(domain) => {
+                let row: Option<(i64,)> = sqlx::query_as(
+                    r#"
+                    SELECT id FROM orders
+                    WHERE order_number COLLATE NOCASE = ? AND shop_domain = ? AND deleted_at IS NULL
+                    LIMIT 1
+                    "#,
+                )
+                .bind(order_number)
+                .bind(domain)
+                .fetch_optional(tx.as_mut())
+                .await?;
+                if row.is_some() || normalized.is_empty() {
+                    return Ok(row.map(|(id,)| id));
+                }
+                Self::backfill_order_number_normalized_in_tx(tx).await?;
+                sqlx::query_scalar(
+                    r#"
+                    SELECT id FROM orders
+                    WHERE order_number_normalized = ? AND shop_domain = ? AND deleted_at IS NULL
+                    ORDER BY id
+                    LIMIT 1
+                    "#,
+                )
+                .bind(&normalized)
+                .bind(domain)
+                .fetch_optional(tx.as_mut())
+                .await
+            }
+            None => {
+                let row: Option<(i64,)> = sqlx::query_as(
+                    r#"
+                    SELECT id FROM orders
+                    WHERE order_number COLLATE NOCASE = ? AND (shop_domain IS NULL OR shop_domain = '')
+                      AND deleted_at IS NULL
+                    LIMIT 1
+                    "#,
+                )
+                .bind(order_number)
+                .fetch_optional(tx.as_mut())
+                .await?;
+                if row.is_some() || normalized.is_empty() {
+                    return Ok(row.map(|(id,)| id));
+                }
+                Self::backfill_order_number_normalized_in_tx(tx).await?;
+                sqlx::query_scalar(
+                    r#"
+                    SELECT id FROM orders
+                    WHERE order_number_normalized = ? AND (shop_domain IS NULL OR shop_domain = '')
+                      AND deleted_at IS NULL
+                    ORDER BY id
+                    LIMIT 1
+                    "#,
+                )
+                .bind(&normalized)
+                .fetch_optional(tx.as_mut())
+                .await
+            }
+        }
+    }
+
+    /// `order_number_normalized` が未設定の注文（マイグレーション前からある注文等）に
+    /// `normalize_order_number` の値を設定する。補完した件数を返す。
+    ///
+    /// 正規化（NFKC 等）は SQL で再現できないため、マイグレーションではなくここで補完する。
+    /// 未設定の注文はインデックスで探すため、補完済みであればほとんどコストはかからない。
+    pub(crate) async fn backfill_order_number_normalized_in_tx(
+        tx: &mut sqlx::Transaction<'_, Sqlite>,
+    ) -> Result<usize, sqlx::Error> {
+        let rows: Vec<(i64, String)> = sqlx::query_as(
+            r#"
+            SELECT id, order_number FROM orders
+            WHERE order_number_normalized IS NULL AND order_number IS NOT NULL
+            "#,
+        )
+        .fetch_all(tx.as_mut())
+        .await?;
+        for (id, order_number) in &rows {
+            sqlx::query("UPDATE orders SET order_number_normalized = ? WHERE id = ?")
+                .bind(normalize_order_number(order_number))
+                .bind(id)
+                .execute(tx.as_mut())
+                .await?;
+        }
+        if !rows.is_empty() {
+            log::info!(
+                "Backfilled order_number_normalized for {} orders",
+                rows.len()
+            );
+        }
+        Ok(rows.len())
+    }
+
+    /// ゴミ箱内の注文を注文番号＋ドメインで検索する（save_order_in_tx で削除済み注文への再登録を防ぐ）
+    async fn find_trashed_order_id_in_tx(
+        tx: &mut sqlx::Transaction<'_, Sqlite>,
+        order_number: &str,
+        shop_domain: &str,
+    ) -> Result<Option<i64>, sqlx::Error> {
+        sqlx::query_scalar(
+            r#"
+            SELECT id FROM orders
+            WHERE order_number COLLATE NOCASE = ? AND shop_domain = ? AND deleted_at IS NOT NULL
+            ORDER BY id
+            LIMIT 1
+            "#,
+        )
+        .bind(order_number)
+        .bind(shop_domain)
+        .fetch_optional(tx.as_mut())
+        .await
+    }
+
     /// apply_change_items のトランザクション内ロジック（tx は呼び出し元で commit）
     pub(crate) async fn apply_change_items_in_tx(
         tx: &mut sqlx::Transaction<'_, Sqlite>,
@@ -294,6 +414,7 @@ impl SqliteOrderRepository {
                     r#"
                     SELECT o.id FROM orders o
                     WHERE o.order_number COLLATE NOCASE != ?1
+                    AND o.deleted_at IS NULL
                     AND o.shop_domain = ?2
                     AND o.id NOT IN (
                         SELECT d.order_id FROM deliveries d
@@ -317,6 +438,7 @@ impl SqliteOrderRepository {
                     r#"
                     SELECT o.id FROM orders o
                     WHERE o.order_number COLLATE NOCASE != ?1
+                    AND o.deleted_at IS NULL
                     AND (o.shop_domain IS NULL OR o.shop_domain = '')
                     AND o.id NOT IN (
                         SELECT d.order_id FROM deliveries d
@@ -340,6 +462,7 @@ impl SqliteOrderRepository {
                 r#"
                 SELECT o.id FROM orders o
                 WHERE o.order_number COLLATE NOCASE != ?1
+                AND o.deleted_at IS NULL
                 AND (o.shop_domain IS NULL OR o.shop_domain = '')
                 AND o.id NOT IN (
                     SELECT d.order_id FROM deliveries d
@@ -555,34 +678,56 @@ impl SqliteOrderRepository {
         shop_domain: Option<String>,
         shop_name: Option<String>,
     ) -> Result<i64, String> {
-        // 注文番号は大文字小文字を区別せずマッチ（メールからそのまま保存するため表記が揺れる場合あり）
-        let existing_order: Option<(i64,)> = sqlx::query_as(
-            r#"
-            SELECT id FROM orders
-            WHERE order_number COLLATE NOCASE = ? AND shop_domain = ?
-            LIMIT 1
-            "#,
-        )
-        .bind(&order_info.order_number)
-        .bind(shop_domain.as_deref())
-        .fetch_optional(tx.as_mut())
-        .await
-        .map_err(|e| format!("Failed to check existing order: {e}"))?;
+        // 注文番号は正規化して比較（メールからそのまま保存するため大小文字・ハイフン・ゼロ埋めが揺れる場合あり）
+        let existing_order = match shop_domain.as_deref() {
+            Some(domain) => {
+                Self::find_order_id_by_number_in_tx(tx, &order_info.order_number, Some(domain))
+                    .await
+                    .map_err(|e| format!("Failed to check existing order: {e}"))?
+            }
+            None => None,
+        };
+
+        // ゴミ箱内の注文はメールの内容で更新しない（紐付けのみ行い、未パース扱いで繰り返し処理されないようにする）
+        if let (None, Some(domain)) = (existing_order, shop_domain.as_deref()) {
+            if let Some(trashed_id) =
+                Self::find_trashed_order_id_in_tx(tx, &order_info.order_number, domain)
+                    .await
+                    .map_err(|e| format!("Failed to check trashed order: {e}"))?
+            {
+                log::info!(
+                    "Order {} is in trash; skipping update (order_id={})",
+                    order_info.order_number,
+                    trashed_id
+                );
+                if let Some(email_id_val) = email_id {
+                    Self::link_order_email_in_tx(tx, trashed_id, email_id_val).await?;
+                }
+                return Ok(trashed_id);
+            }
+        }
 
-        let order_id = if let Some((existing_id,)) = existing_order {
+        let order_id = if let Some(existing_id) = existing_order {
             log::debug!("Found existing order with id: {}", existing_id);
             existing_id
         } else {
+            // 全件再パースで作り直す場合も、ゴミ箱に入れた注文は削除済みのまま登録する
             let new_order_id = sqlx::query(
                 r#"
-                INSERT INTO orders (order_number, order_date, shop_domain, shop_name)
-                VALUES (?, ?, ?, ?)
+                INSERT INTO orders (order_number, order_date, shop_domain, shop_name, tax_amount, tax_included, deleted_at, order_number_normalized)
+                VALUES (?1, ?2, ?3, ?4, ?5, ?6, (
+                    SELECT deleted_at FROM trashed_orders
+                    WHERE shop_domain = COALESCE(?3, '') AND order_number = ?1
+                ), ?7)
                 "#,
             )
             .bind(&order_info.order_number)
             .bind(&order_info.order_date)
             .bind(shop_domain.as_deref())
             .bind(shop_name.as_deref())
+            .bind(order_info.tax_amount)
+            .bind(order_info.tax_included)
+            .bind(normalize_order_number(&order_info.order_number))
             .execute(tx.as_mut())
             .await
             .map_err(|e| format!("Failed to insert order: {e}"))?
@@ -609,6 +754,24 @@ impl SqliteOrderRepository {
             log::debug!("Updated order {} with new date info", order_id);
         }
 
+        // 税情報はメールに記載がある場合のみ上書きする（発送通知などで既存の値を消さない）
+        if existing_order.is_some() && (order_info.tax_amount.is_some() || !order_info.tax_included)
+        {
+            sqlx::query(
+                r#"
+                UPDATE orders
+                SET tax_amount = COALESCE(?, tax_amount), tax_included = ?
+                WHERE id = ?
+                "#,
+            )
+            .bind(order_info.tax_amount)
+            .bind(order_info.tax_included)
+            .bind(order_id)
+            .execute(tx.as_mut())
+            .await
+            .map_err(|e| format!("Failed to update order tax info: {e}"))?;
+        }
+
         // 除外パターンを読み込んでアイテムをフィルタリング
         let exclusion_patterns = crate::repository::load_all_patterns_in_tx(tx)
             .await
@@ -642,39 +805,61 @@ impl SqliteOrderRepository {
             .await
             .map_err(|e| format!("Failed to check existing item: {e}"))?;
 
-            if existing_item.is_none() {
-                let item_name_normalized = {
-                    let n = normalize_product_name(&item.name);
-                    if n.is_empty() {
-                        None
-                    } else {
-                        Some(n)
-                    }
-                };
-                sqlx::query(
-                    r#"
-                    INSERT INTO items (order_id, item_name, item_name_normalized, brand, price, quantity)
-                    VALUES (?, ?, ?, ?, ?, ?)
-                    "#,
-                )
-                .bind(order_id)
-                .bind(&item.name)
-                .bind(item_name_normalized.as_deref())
-                .bind(&item.manufacturer)
-                .bind(item.unit_price)
-                .bind(item.quantity)
-                .execute(tx.as_mut())
-                .await
-                .map_err(|e| format!("Failed to insert item: {e}"))?;
-
-                log::debug!("Added new item '{}' to order {}", item.name, order_id);
-            } else {
+            let (release_date, release_date_precision) = item_release_date(item);
+            if let Some((item_id,)) = existing_item {
+                // 延期のお知らせ等で発売予定が変わることがあるため、新しいメールの値で更新する
+                if release_date.is_some() {
+                    sqlx::query(
+                        "UPDATE items SET release_date = ?, release_date_precision = ? WHERE id = ?",
+                    )
+                    .bind(&release_date)
+                    .bind(release_date_precision)
+                    .bind(item_id)
+                    .execute(tx.as_mut())
+                    .await
+                    .map_err(|e| format!("Failed to update item release date: {e}"))?;
+                }
+                // 発送メール等で初めてリンクが取れた場合のみ補完する（既存のURLは上書きしない）
+                if let Some(item_url) = &item.item_url {
+                    sqlx::query("UPDATE items SET item_url = ? WHERE id = ? AND item_url IS NULL")
+                        .bind(item_url)
+                        .bind(item_id)
+                        .execute(tx.as_mut())
+                        .await
+                        .map_err(|e| format!("Failed to update item url: {e}"))?;
+                }
                 log::debug!("Item '{}' already exists for order {}", item.name, order_id);
+            } else {
+                Self::insert_items_in_tx(tx, order_id, std::slice::from_ref(item)).await?;
+                log::debug!("Added new item '{}' to order {}", item.name, order_id);
             }
         }
 
         remove_zero_price_duplicates_in_tx(tx, order_id).await?;
 
+        // メール記載の合計と商品合計の差額（送料・割引など）を調整額として記録する。
+        // 同じ注文の別メールで再計算しても上書きのため二重計上にならない
+        let (tax_amount, tax_included): (Option<i64>, bool) =
+            sqlx::query_as("SELECT tax_amount, tax_included FROM orders WHERE id = ?")
+                .bind(order_id)
+                .fetch_one(tx.as_mut())
+                .await
+                .map_err(|e| format!("Failed to fetch order tax info: {e}"))?;
+        if let Some(adjustment) = crate::logic::amount_reconcile::compute_amount_adjustment(
+            order_info,
+            tax_amount,
+            tax_included,
+        ) {
+            sqlx::query("UPDATE orders SET amount_adjustment = ? WHERE id = ?")
+                .bind(adjustment)
+                .bind(order_id)
+                .execute(tx.as_mut())
+                .await
+                .map_err(|e| format!("Failed to update order amount adjustment: {e}"))?;
+        }
+
+        crate::repository::apply_auto_tags_for_order_in_tx(tx, order_id).await?;
+
         if let Some(delivery_info) = &order_info.delivery_info {
             let status = resolve_delivery_status(delivery_info.delivery_status.as_deref())?;
 
@@ -691,41 +876,45 @@ impl SqliteOrderRepository {
             .await
             .map_err(|e| format!("Failed to check existing delivery: {e}"))?;
 
-            if existing_delivery.is_none() {
+            let delivery_id = if let Some((delivery_id,)) = existing_delivery {
                 sqlx::query(
                     r#"
-                    INSERT INTO deliveries (order_id, tracking_number, carrier, delivery_status)
-                    VALUES (?, ?, ?, ?)
+                    UPDATE deliveries
+                    SET carrier = COALESCE(?, carrier),
+                        delivery_status = ?
+                    WHERE order_id = ? AND tracking_number = ?
                     "#,
                 )
-                .bind(order_id)
-                .bind(&delivery_info.tracking_number)
                 .bind(&delivery_info.carrier)
                 .bind(status)
+                .bind(order_id)
+                .bind(&delivery_info.tracking_number)
                 .execute(tx.as_mut())
                 .await
-                .map_err(|e| format!("Failed to insert delivery: {e}"))?;
+                .map_err(|e| format!("Failed to update delivery: {e}"))?;
 
-                log::debug!("Added new delivery info for order {}", order_id);
+                log::debug!("Updated delivery info for order {}", order_id);
+                delivery_id
             } else {
-                sqlx::query(
+                let result = sqlx::query(
                     r#"
-                    UPDATE deliveries
-                    SET carrier = COALESCE(?, carrier),
-                        delivery_status = ?
-                    WHERE order_id = ? AND tracking_number = ?
+                    INSERT INTO deliveries (order_id, tracking_number, carrier, delivery_status)
+                    VALUES (?, ?, ?, ?)
                     "#,
                 )
-                .bind(&delivery_info.carrier)
-                .bind(status)
                 .bind(order_id)
                 .bind(&delivery_info.tracking_number)
+                .bind(&delivery_info.carrier)
+                .bind(status)
                 .execute(tx.as_mut())
                 .await
-                .map_err(|e| format!("Failed to update delivery: {e}"))?;
+                .map_err(|e| format!("Failed to insert delivery: {e}"))?;
 
-                log::debug!("Updated delivery info for order {}", order_id);
-            }
+                log::debug!("Added new delivery info for order {}", order_id);
+                result.last_insert_rowid()
+            };
+
+            Self::link_delivery_items_in_tx(tx, order_id, delivery_id, &order_info.items).await?;
         }
 
         if let Some(email_id_val) = email_id {
@@ -818,7 +1007,16 @@ impl SqliteOrderRepository {
             .map_err(|e| format!("Failed to delete existing items: {e}"))?;
         log::debug!("Replaced items for order {} (split first order)", order_id);
 
-        for item in &order_info.items {
+        Self::insert_items_in_tx(tx, order_id, &order_info.items).await
+    }
+
+    async fn insert_items_in_tx(
+        tx: &mut sqlx::Transaction<'_, Sqlite>,
+        order_id: i64,
+        items: &[OrderItem],
+    ) -> Result<(), String> {
+        for item in items {
+            let (release_date, release_date_precision) = item_release_date(item);
             let item_name_normalized = {
                 let n = normalize_product_name(&item.name);
                 if n.is_empty() {
@@ -827,10 +1025,19 @@ impl SqliteOrderRepository {
                     Some(n)
                 }
             };
+            // ゴミ箱に入れた商品（注文ごと削除した場合は注文の削除日時）は削除済みのまま登録する
             sqlx::query(
                 r#"
-                INSERT INTO items (order_id, item_name, item_name_normalized, brand, price, quantity)
-                VALUES (?, ?, ?, ?, ?, ?)
+                INSERT INTO items (order_id, item_name, item_name_normalized, brand, price, quantity, release_date, release_date_precision, item_url, deleted_at)
+                SELECT ?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, COALESCE(
+                    (SELECT ti.deleted_at FROM trashed_items ti
+                     WHERE ti.shop_domain = COALESCE(o.shop_domain, '')
+                       AND ti.order_number = o.order_number
+                       AND ti.item_name = ?2),
+                    o.deleted_at
+                )
+                FROM orders o
+                WHERE o.id = ?1
                 "#,
             )
             .bind(order_id)
@@ -839,6 +1046,9 @@ impl SqliteOrderRepository {
             .bind(&item.manufacturer)
             .bind(item.unit_price)
             .bind(item.quantity)
+            .bind(release_date)
+            .bind(release_date_precision)
+            .bind(&item.item_url)
             .execute(tx.as_mut())
             .await
             .map_err(|e| format!("Failed to insert item: {e}"))?;
@@ -846,6 +1056,109 @@ impl SqliteOrderRepository {
         Ok(())
     }
 
+    /// 分割出荷用: 発送メールの商品で items を置き換える。
+    ///
+    /// 別の荷物（追跡番号が異なる deliveries）に紐づく商品は先行して発送済みのため削除せず残し、
+    /// それ以外の商品を発送メールの商品で置き換える。
+    async fn replace_unshipped_items_for_order_in_tx(
+        tx: &mut sqlx::Transaction<'_, Sqlite>,
+        order_id: i64,
+        order_info: &OrderInfo,
+    ) -> Result<(), String> {
+        let tracking_number = order_info
+            .delivery_info
+            .as_ref()
+            .map(|d| d.tracking_number.as_str());
+
+        let result = sqlx::query(
+            r#"
+            DELETE FROM items
+            WHERE order_id = ?
+              AND id NOT IN (
+                  SELECT di.item_id
+                  FROM delivery_items di
+                  JOIN deliveries d ON d.id = di.delivery_id
+                  WHERE d.order_id = ? AND d.tracking_number IS NOT ?
+              )
+            "#,
+        )
+        .bind(order_id)
+        .bind(order_id)
+        .bind(tracking_number)
+        .execute(tx.as_mut())
+        .await
+        .map_err(|e| format!("Failed to delete unshipped items: {e}"))?;
+        log::debug!(
+            "Removed {} unshipped items for order {} (send mail)",
+            result.rows_affected(),
+            order_id
+        );
+
+        Self::insert_items_in_tx(tx, order_id, &order_info.items).await
+    }
+
+    /// 発送メールに記載された商品を delivery_items に記録し、どの商品がどの荷物で届くかを紐づける。
+    ///
+    /// 商品は注文内の items から商品名・メーカーで探す。同名の商品が複数ある場合（同一商品の分割出荷）は
+    /// まだ他の荷物に紐づいていない商品を優先する。見つからない商品（除外パターン等）はスキップする。
+    pub(crate) async fn link_delivery_items_in_tx(
+        tx: &mut sqlx::Transaction<'_, Sqlite>,
+        order_id: i64,
+        delivery_id: i64,
+        items: &[OrderItem],
+    ) -> Result<(), String> {
+        let mut linked_item_ids: Vec<i64> = Vec::new();
+        for item in items {
+            let candidates: Vec<(i64,)> = sqlx::query_as(
+                r#"
+                SELECT i.id
+                FROM items i
+                WHERE i.order_id = ? AND i.item_name = ? AND COALESCE(i.brand, '') = COALESCE(?, '')
+                ORDER BY EXISTS (
+                    SELECT 1 FROM delivery_items di
+                    WHERE di.item_id = i.id AND di.delivery_id != ?
+                ), i.id
+                "#,
+            )
+            .bind(order_id)
+            .bind(&item.name)
+            .bind(&item.manufacturer)
+            .bind(delivery_id)
+            .fetch_all(tx.as_mut())
+            .await
+            .map_err(|e| format!("Failed to find item for delivery: {e}"))?;
+
+            let Some(item_id) = candidates
+                .into_iter()
+                .map(|(id,)| id)
+                .find(|id| !linked_item_ids.contains(id))
+            else {
+                log::debug!(
+                    "Item '{}' not found in order {}, skipping delivery link",
+                    item.name,
+                    order_id
+                );
+                continue;
+            };
+
+            sqlx::query(
+                r#"
+                INSERT INTO delivery_items (delivery_id, item_id, quantity)
+                VALUES (?, ?, ?)
+                ON CONFLICT(delivery_id, item_id) DO UPDATE SET quantity = excluded.quantity
+                "#,
+            )
+            .bind(delivery_id)
+            .bind(item_id)
+            .bind(item.quantity)
+            .execute(tx.as_mut())
+            .await
+            .map_err(|e| format!("Failed to link item to delivery: {e}"))?;
+            linked_item_ids.push(item_id);
+        }
+        Ok(())
+    }
+
     /// apply_cancel のトランザクション内ロジック（tx は呼び出し元で commit）
     pub(crate) async fn apply_cancel_in_tx(
         tx: &mut sqlx::Transaction<'_, Sqlite>,
@@ -912,7 +1225,10 @@ impl SqliteOrderRepository {
             None
         };
 
+        let reason = cancel_info.reason.map(|r| r.as_str());
+
         if product_name.is_empty() {
+            Self::record_cancelled_items_in_tx(tx, order_id, None, None, email_id, reason).await?;
             sqlx::query("DELETE FROM items WHERE order_id = ?")
                 .bind(order_id)
                 .execute(tx.as_mut())
@@ -956,6 +1272,15 @@ impl SqliteOrderRepository {
                     }
 
                     let new_qty = current_qty - cancel_info.cancel_quantity;
+                    Self::record_cancelled_items_in_tx(
+                        tx,
+                        order_id,
+                        Some(item_id),
+                        Some(cancel_info.cancel_quantity.min(current_qty)),
+                        email_id,
+                        reason,
+                    )
+                    .await?;
 
                     if new_qty <= 0 {
                         sqlx::query("DELETE FROM items WHERE id = ?")
@@ -994,33 +1319,25 @@ impl SqliteOrderRepository {
             }
         }
 
-        let existing_link: Option<(i64,)> = sqlx::query_as(
-            r#"
-            SELECT order_id FROM order_emails
-            WHERE order_id = ? AND email_id = ?
-            LIMIT 1
-            "#,
-        )
-        .bind(order_id)
-        .bind(email_id)
-        .fetch_optional(tx.as_mut())
-        .await
-        .map_err(|e| format!("Failed to check order_email link: {e}"))?;
-
-        if existing_link.is_none() {
+        if reason.is_some() || cancel_info.reason_detail.is_some() {
             sqlx::query(
                 r#"
-                INSERT INTO order_emails (order_id, email_id)
-                VALUES (?, ?)
+                UPDATE orders
+                SET cancel_reason = COALESCE(?, cancel_reason),
+                    cancel_reason_detail = COALESCE(?, cancel_reason_detail)
+                WHERE id = ?
                 "#,
             )
+            .bind(reason)
+            .bind(cancel_info.reason_detail.as_deref())
             .bind(order_id)
-            .bind(email_id)
             .execute(tx.as_mut())
             .await
-            .map_err(|e| format!("Failed to link order to email: {e}"))?;
+            .map_err(|e| format!("Failed to update cancel reason: {e}"))?;
         }
 
+        Self::link_order_email_in_tx(tx, order_id, email_id).await?;
+
         Ok(order_id)
     }
 
@@ -1061,14 +1378,15 @@ impl SqliteOrderRepository {
 
             let new_order_id = sqlx::query(
                 r#"
-                INSERT INTO orders (order_number, order_date, shop_domain, shop_name)
-                VALUES (?, ?, ?, ?)
+                INSERT INTO orders (order_number, order_date, shop_domain, shop_name, order_number_normalized)
+                VALUES (?, ?, ?, ?, ?)
                 "#,
             )
             .bind(&change_info.new_order_number)
             .bind(&order_date_str)
             .bind(shop_domain.as_deref())
             .bind(shop_name.as_deref())
+            .bind(normalize_order_number(&change_info.new_order_number))
             .execute(tx.as_mut())
             .await
             .map_err(|e| format!("Failed to insert order for number change fallback: {e}"))?
@@ -1082,8 +1400,9 @@ impl SqliteOrderRepository {
             new_order_id
         };
 
-        sqlx::query("UPDATE orders SET order_number = ? WHERE id = ?")
+        sqlx::query("UPDATE orders SET order_number = ?, order_number_normalized = ? WHERE id = ?")
             .bind(&change_info.new_order_number)
+            .bind(normalize_order_number(&change_info.new_order_number))
             .bind(order_id)
             .execute(tx.as_mut())
             .await
@@ -1158,8 +1477,9 @@ impl SqliteOrderRepository {
             }
         };
 
-        sqlx::query("UPDATE orders SET order_number = ? WHERE id = ?")
+        sqlx::query("UPDATE orders SET order_number = ?, order_number_normalized = ? WHERE id = ?")
             .bind(&consolidation_info.new_order_number)
+            .bind(normalize_order_number(&consolidation_info.new_order_number))
             .bind(first_order_id)
             .execute(tx.as_mut())
             .await
@@ -1238,7 +1558,7 @@ impl SqliteOrderRepository {
                 alternate_domains
             );
             if !order_info.items.is_empty() {
-                Self::replace_items_for_order_in_tx(tx, id, order_info).await?;
+                Self::replace_unshipped_items_for_order_in_tx(tx, id, order_info).await?;
                 log::info!(
                     "[dmm_send] replaced items for existing order {} with {} items from send mail",
                     id,
@@ -1286,39 +1606,43 @@ impl SqliteOrderRepository {
             .await
             .map_err(|e| format!("Failed to check existing delivery: {e}"))?;
 
-            if existing_delivery.is_none() {
+            let delivery_id = if let Some((delivery_id,)) = existing_delivery {
                 sqlx::query(
                     r#"
-                    INSERT INTO deliveries (order_id, tracking_number, carrier, delivery_status)
-                    VALUES (?, ?, ?, ?)
+                    UPDATE deliveries
+                    SET carrier = COALESCE(?, carrier),
+                        delivery_status = ?
+                    WHERE order_id = ? AND tracking_number = ?
                     "#,
                 )
-                .bind(order_id)
-                .bind(&delivery_info.tracking_number)
                 .bind(&delivery_info.carrier)
                 .bind(status)
+                .bind(order_id)
+                .bind(&delivery_info.tracking_number)
                 .execute(tx.as_mut())
                 .await
-                .map_err(|e| format!("Failed to insert delivery: {e}"))?;
-                log::debug!("Added new delivery info for order {} (send mail)", order_id);
+                .map_err(|e| format!("Failed to update delivery: {e}"))?;
+                log::debug!("Updated delivery info for order {} (send mail)", order_id);
+                delivery_id
             } else {
-                sqlx::query(
+                let result = sqlx::query(
                     r#"
-                    UPDATE deliveries
-                    SET carrier = COALESCE(?, carrier),
-                        delivery_status = ?
-                    WHERE order_id = ? AND tracking_number = ?
+                    INSERT INTO deliveries (order_id, tracking_number, carrier, delivery_status)
+                    VALUES (?, ?, ?, ?)
                     "#,
                 )
-                .bind(&delivery_info.carrier)
-                .bind(status)
                 .bind(order_id)
                 .bind(&delivery_info.tracking_number)
+                .bind(&delivery_info.carrier)
+                .bind(status)
                 .execute(tx.as_mut())
                 .await
-                .map_err(|e| format!("Failed to update delivery: {e}"))?;
-                log::debug!("Updated delivery info for order {} (send mail)", order_id);
-            }
+                .map_err(|e| format!("Failed to insert delivery: {e}"))?;
+                log::debug!("Added new delivery info for order {} (send mail)", order_id);
+                result.last_insert_rowid()
+            };
+
+            Self::link_delivery_items_in_tx(tx, order_id, delivery_id, &order_info.items).await?;
         }
 
         if let Some(email_id_val) = email_id {
@@ -1433,139 +1757,520 @@ impl SqliteOrderRepository {
 
         Ok(order_id)
     }
-}
-
-#[async_trait]
-impl OrderRepository for SqliteOrderRepository {
-    async fn save_order(
-        &self,
-        order_info: &OrderInfo,
-        email_id: Option<i64>,
-        shop_domain: Option<String>,
-        shop_name: Option<String>,
-    ) -> Result<i64, String> {
-        let mut tx = self
-            .pool
-            .begin()
-            .await
-            .map_err(|e| format!("Failed to start transaction: {e}"))?;
-        let order_id =
-            Self::save_order_in_tx(&mut tx, order_info, email_id, shop_domain, shop_name).await?;
-        tx.commit()
-            .await
-            .map_err(|e| format!("Failed to commit transaction: {e}"))?;
-        Ok(order_id)
-    }
 
-    async fn apply_cancel(
-        &self,
-        cancel_info: &CancelInfo,
+    /// お届け予定日変更を適用する（tx は呼び出し元で commit）
+    ///
+    /// 注文の最新の deliveries レコードの `estimated_delivery` を更新する。
+    /// 配送レコードがまだない場合（未発送）は `not_shipped` で作成する。
+    /// 配達完了済みの場合は古い通知とみなし、予定日は更新せずメールの紐付けのみ行う。
+    pub(crate) async fn apply_delivery_date_change_in_tx(
+        tx: &mut sqlx::Transaction<'_, Sqlite>,
+        change_info: &DeliveryDateChangeInfo,
         email_id: i64,
         shop_domain: Option<String>,
-        _shop_name: Option<String>,
         alternate_domains: Option<Vec<String>>,
     ) -> Result<i64, String> {
-        let mut tx = self
-            .pool
-            .begin()
-            .await
-            .map_err(|e| format!("Failed to start transaction: {e}"))?;
-        let result = Self::apply_cancel_in_tx(
-            &mut tx,
-            cancel_info,
-            email_id,
-            shop_domain,
-            alternate_domains,
+        let order_id = match Self::find_order_by_number_and_domain(
+            tx,
+            &change_info.order_number,
+            &shop_domain,
+            alternate_domains.as_deref(),
         )
-        .await;
-        match result {
-            Ok(order_id) => {
-                tx.commit()
+        .await
+        .map_err(|e| format!("Failed to find order: {e}"))?
+        {
+            Some(id) => id,
+            None => {
+                log::warn!(
+                    "Delivery date change mail: order {} not found (shop_domain={:?}, alternate_domains={:?})",
+                    change_info.order_number,
+                    shop_domain,
+                    alternate_domains
+                );
+                return Err(format!(
+                    "Order {} not found for delivery date change",
+                    change_info.order_number
+                ));
+            }
+        };
+
+        let latest_delivery: Option<(i64, String)> = sqlx::query_as(
+            r#"
+            SELECT id, delivery_status FROM deliveries
+            WHERE order_id = ?
+            ORDER BY updated_at DESC, id DESC
+            LIMIT 1
+            "#,
+        )
+        .bind(order_id)
+        .fetch_optional(tx.as_mut())
+        .await
+        .map_err(|e| format!("Failed to fetch delivery: {e}"))?;
+
+        match latest_delivery {
+            Some((_, status)) if status == "delivered" => {
+                log::info!(
+                    "Delivery date change ignored: order {} is already delivered",
+                    change_info.order_number
+                );
+            }
+            Some((delivery_id, _)) => {
+                sqlx::query("UPDATE deliveries SET estimated_delivery = ? WHERE id = ?")
+                    .bind(&change_info.new_estimated_delivery)
+                    .bind(delivery_id)
+                    .execute(tx.as_mut())
                     .await
-                    .map_err(|e| format!("Failed to commit transaction: {e}"))?;
-                Ok(order_id)
+                    .map_err(|e| format!("Failed to update estimated delivery: {e}"))?;
             }
-            Err(e) => {
-                let _ = tx.rollback().await;
-                Err(e)
+            None => {
+                sqlx::query(
+                    r#"
+                    INSERT INTO deliveries (order_id, delivery_status, estimated_delivery)
+                    VALUES (?, 'not_shipped', ?)
+                    "#,
+                )
+                .bind(order_id)
+                .bind(&change_info.new_estimated_delivery)
+                .execute(tx.as_mut())
+                .await
+                .map_err(|e| format!("Failed to insert delivery: {e}"))?;
             }
         }
+        log::info!(
+            "Estimated delivery changed: order {} {:?} -> {} (order_id={})",
+            change_info.order_number,
+            change_info.old_estimated_delivery,
+            change_info.new_estimated_delivery,
+            order_id
+        );
+
+        Self::link_order_email_in_tx(tx, order_id, email_id).await?;
+
+        Ok(order_id)
     }
 
-    async fn apply_order_number_change(
-        &self,
-        change_info: &OrderNumberChangeInfo,
+    /// 返金額を注文に記録する（tx は呼び出し元で commit）
+    ///
+    /// メールに記載された注文全体の返金額で `orders.refund_amount` を上書きし、メールを紐付ける。
+    pub(crate) async fn apply_refund_in_tx(
+        tx: &mut sqlx::Transaction<'_, Sqlite>,
+        order_number: &str,
+        refund_amount: i64,
         email_id: i64,
-        change_email_internal_date: Option<i64>,
         shop_domain: Option<String>,
-        shop_name: Option<String>,
         alternate_domains: Option<Vec<String>>,
     ) -> Result<i64, String> {
-        let mut tx = self
-            .pool
-            .begin()
-            .await
-            .map_err(|e| format!("Failed to start transaction: {e}"))?;
-        let result = Self::apply_order_number_change_in_tx(
-            &mut tx,
-            change_info,
-            email_id,
-            change_email_internal_date,
-            shop_domain,
-            shop_name,
-            alternate_domains,
+        let order_id = match Self::find_order_by_number_and_domain(
+            tx,
+            order_number,
+            &shop_domain,
+            alternate_domains.as_deref(),
         )
-        .await;
-        match result {
-            Ok(order_id) => {
-                tx.commit()
-                    .await
-                    .map_err(|e| format!("Failed to commit transaction: {e}"))?;
-                Ok(order_id)
-            }
-            Err(e) => {
-                let _ = tx.rollback().await;
-                Err(e)
+        .await
+        .map_err(|e| format!("Failed to find order: {e}"))?
+        {
+            Some(id) => id,
+            None => {
+                log::warn!(
+                    "Refund mail: order {} not found (shop_domain={:?}, alternate_domains={:?})",
+                    order_number,
+                    shop_domain,
+                    alternate_domains
+                );
+                return Err(format!("Order {} not found for refund", order_number));
             }
-        }
+        };
+
+        sqlx::query(
+            "UPDATE orders SET refund_amount = ?, updated_at = CURRENT_TIMESTAMP WHERE id = ?",
+        )
+        .bind(refund_amount)
+        .bind(order_id)
+        .execute(tx.as_mut())
+        .await
+        .map_err(|e| format!("Failed to update refund amount: {e}"))?;
+        log::info!(
+            "Refund recorded: order {} amount={} (order_id={})",
+            order_number,
+            refund_amount,
+            order_id
+        );
+
+        Self::link_order_email_in_tx(tx, order_id, email_id).await?;
+
+        Ok(order_id)
     }
 
-    async fn apply_consolidation(
-        &self,
-        consolidation_info: &ConsolidationInfo,
+    /// 発売延期を適用する（tx は呼び出し元で commit）
+    ///
+    /// 延期対象の商品（商品名が空なら注文内の全商品）の `release_date` / `release_date_precision` を
+    /// 新しい発売予定で更新する。商品名の照合はキャンセルと同じ `item_names_match` を使う。
+    /// 一致する商品がない場合も警告のみとし、メールの紐付けは行う。
+    pub(crate) async fn apply_release_delay_in_tx(
+        tx: &mut sqlx::Transaction<'_, Sqlite>,
+        delay_info: &DelayInfo,
         email_id: i64,
         shop_domain: Option<String>,
-        _shop_name: Option<String>,
         alternate_domains: Option<Vec<String>>,
     ) -> Result<i64, String> {
-        let mut tx = self
-            .pool
-            .begin()
-            .await
-            .map_err(|e| format!("Failed to start transaction: {e}"))?;
-        let result = Self::apply_consolidation_in_tx(
-            &mut tx,
-            consolidation_info,
-            email_id,
-            shop_domain,
-            alternate_domains,
+        let order_id = match Self::find_order_by_number_and_domain(
+            tx,
+            &delay_info.order_number,
+            &shop_domain,
+            alternate_domains.as_deref(),
         )
-        .await;
-        match result {
-            Ok(order_id) => {
-                tx.commit()
-                    .await
-                    .map_err(|e| format!("Failed to commit transaction: {e}"))?;
-                Ok(order_id)
+        .await
+        .map_err(|e| format!("Failed to find order: {e}"))?
+        {
+            Some(id) => id,
+            None => {
+                log::warn!(
+                    "Release delay mail: order {} not found (shop_domain={:?}, alternate_domains={:?})",
+                    delay_info.order_number,
+                    shop_domain,
+                    alternate_domains
+                );
+                return Err(format!(
+                    "Order {} not found for release delay",
+                    delay_info.order_number
+                ));
             }
-            Err(e) => {
-                let _ = tx.rollback().await;
-                Err(e)
+        };
+
+        type ItemRow = (i64, String, Option<String>, Option<String>);
+        let items: Vec<ItemRow> = sqlx::query_as(
+            r#"
+            SELECT i.id, i.item_name, i.item_name_normalized, pm.product_name
+            FROM items i
+            LEFT JOIN product_master pm ON TRIM(i.item_name) = pm.raw_name
+            WHERE i.order_id = ?
+            ORDER BY i.id
+            "#,
+        )
+        .bind(order_id)
+        .fetch_all(tx.as_mut())
+        .await
+        .map_err(|e| format!("Failed to fetch items: {e}"))?;
+
+        for delayed in &delay_info.items {
+            let product_name = delayed.product_name.trim();
+            let targets: Vec<i64> = items
+                .iter()
+                .filter(|(_, item_name, item_name_normalized, item_pm_name)| {
+                    product_name.is_empty()
+                        || item_names_match(
+                            product_name,
+                            None,
+                            item_name,
+                            item_name_normalized.as_deref(),
+                            item_pm_name.as_deref(),
+                        )
+                })
+                .map(|(id, _, _, _)| *id)
+                .collect();
+
+            if targets.is_empty() {
+                log::warn!(
+                    "Release delay: item '{}' not found in order {} (order_id={})",
+                    product_name,
+                    delay_info.order_number,
+                    order_id
+                );
+                continue;
+            }
+
+            let release_date = delayed.new_release_date.to_release_date();
+            for item_id in targets {
+                sqlx::query(
+                    "UPDATE items SET release_date = ?, release_date_precision = ? WHERE id = ?",
+                )
+                .bind(&release_date)
+                .bind(delayed.new_release_date.precision.as_str())
+                .bind(item_id)
+                .execute(tx.as_mut())
+                .await
+                .map_err(|e| format!("Failed to update item release date: {e}"))?;
             }
+            log::info!(
+                "Release date changed: order {} item '{}' {:?} -> {}",
+                delay_info.order_number,
+                product_name,
+                delayed.old_release_date.as_ref().map(|d| d.label()),
+                delayed.new_release_date.label()
+            );
         }
+
+        Self::link_order_email_in_tx(tx, order_id, email_id).await?;
+
+        Ok(order_id)
     }
 
-    async fn apply_send_and_replace_items(
+    /// 出荷準備中メールを適用する（tx は呼び出し元で commit）
+    ///
+    /// 最新の deliveries が `not_shipped` の場合のみ `preparing` に進める（発送済み以降は戻さない）。
+    /// 配送レコードがまだない場合は `preparing` で作成する。
+    pub(crate) async fn apply_preparing_in_tx(
+        tx: &mut sqlx::Transaction<'_, Sqlite>,
+        order_number: &str,
+        email_id: i64,
+        shop_domain: Option<String>,
+        alternate_domains: Option<Vec<String>>,
+    ) -> Result<i64, String> {
+        let order_id = match Self::find_order_by_number_and_domain(
+            tx,
+            order_number,
+            &shop_domain,
+            alternate_domains.as_deref(),
+        )
+        .await
+        .map_err(|e| format!("Failed to find order: {e}"))?
+        {
+            Some(id) => id,
+            None => {
+                log::warn!(
+                    "Preparing mail: order {} not found (shop_domain={:?}, alternate_domains={:?})",
+                    order_number,
+                    shop_domain,
+                    alternate_domains
+                );
+                return Err(format!("Order {} not found for preparing", order_number));
+            }
+        };
+
+        let latest_delivery: Option<(i64, String)> = sqlx::query_as(
+            r#"
+            SELECT id, delivery_status FROM deliveries
+            WHERE order_id = ?
+            ORDER BY updated_at DESC, id DESC
+            LIMIT 1
+            "#,
+        )
+        .bind(order_id)
+        .fetch_optional(tx.as_mut())
+        .await
+        .map_err(|e| format!("Failed to fetch delivery: {e}"))?;
+
+        match latest_delivery {
+            Some((delivery_id, status)) if status == "not_shipped" => {
+                sqlx::query("UPDATE deliveries SET delivery_status = 'preparing' WHERE id = ?")
+                    .bind(delivery_id)
+                    .execute(tx.as_mut())
+                    .await
+                    .map_err(|e| format!("Failed to update delivery status: {e}"))?;
+            }
+            Some((_, status)) => {
+                log::debug!(
+                    "Preparing mail ignored: order {} is already {}",
+                    order_number,
+                    status
+                );
+            }
+            None => {
+                sqlx::query(
+                    "INSERT INTO deliveries (order_id, delivery_status) VALUES (?, 'preparing')",
+                )
+                .bind(order_id)
+                .execute(tx.as_mut())
+                .await
+                .map_err(|e| format!("Failed to insert delivery: {e}"))?;
+            }
+        }
+
+        Self::link_order_email_in_tx(tx, order_id, email_id).await?;
+
+        Ok(order_id)
+    }
+
+    /// キャンセル対象の商品を削除前に cancelled_items へ記録する（キャンセル理由別統計用）
+    ///
+    /// `item_id` が None の場合は注文内の全商品を記録する。メーカーは product_master を優先し、
+    /// 未解析の場合は items.brand を使う。`quantity` が None の場合は商品の数量をそのまま記録する。
+    async fn record_cancelled_items_in_tx(
+        tx: &mut sqlx::Transaction<'_, Sqlite>,
+        order_id: i64,
+        item_id: Option<i64>,
+        quantity: Option<i64>,
+        email_id: i64,
+        reason: Option<&str>,
+    ) -> Result<(), String> {
+        sqlx::query(
+            r#"
+            INSERT INTO cancelled_items (order_id, email_id, item_name, maker, quantity, cancel_reason)
+            SELECT i.order_id, ?, i.item_name, COALESCE(pm.maker, i.brand), COALESCE(?, i.quantity), ?
+            FROM items i
+            LEFT JOIN product_master pm ON TRIM(i.item_name) = pm.raw_name
+            WHERE i.order_id = ? AND (? IS NULL OR i.id = ?)
+            "#,
+        )
+        .bind(email_id)
+        .bind(quantity)
+        .bind(reason)
+        .bind(order_id)
+        .bind(item_id)
+        .bind(item_id)
+        .execute(tx.as_mut())
+        .await
+        .map_err(|e| format!("Failed to record cancelled items: {e}"))?;
+        Ok(())
+    }
+
+    /// 注文とメールを order_emails で紐付ける（紐付け済みなら何もしない）
+    async fn link_order_email_in_tx(
+        tx: &mut sqlx::Transaction<'_, Sqlite>,
+        order_id: i64,
+        email_id: i64,
+    ) -> Result<(), String> {
+        let existing_link: Option<(i64,)> = sqlx::query_as(
+            r#"SELECT order_id FROM order_emails WHERE order_id = ? AND email_id = ? LIMIT 1"#,
+        )
+        .bind(order_id)
+        .bind(email_id)
+        .fetch_optional(tx.as_mut())
+        .await
+        .map_err(|e| format!("Failed to check order_email link: {e}"))?;
+        if existing_link.is_none() {
+            sqlx::query(r#"INSERT INTO order_emails (order_id, email_id) VALUES (?, ?)"#)
+                .bind(order_id)
+                .bind(email_id)
+                .execute(tx.as_mut())
+                .await
+                .map_err(|e| format!("Failed to link order to email: {e}"))?;
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl OrderRepository for SqliteOrderRepository {
+    async fn save_order(
+        &self,
+        order_info: &OrderInfo,
+        email_id: Option<i64>,
+        shop_domain: Option<String>,
+        shop_name: Option<String>,
+    ) -> Result<i64, String> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| format!("Failed to start transaction: {e}"))?;
+        let order_id =
+            Self::save_order_in_tx(&mut tx, order_info, email_id, shop_domain, shop_name).await?;
+        tx.commit()
+            .await
+            .map_err(|e| format!("Failed to commit transaction: {e}"))?;
+        Ok(order_id)
+    }
+
+    async fn apply_cancel(
+        &self,
+        cancel_info: &CancelInfo,
+        email_id: i64,
+        shop_domain: Option<String>,
+        _shop_name: Option<String>,
+        alternate_domains: Option<Vec<String>>,
+    ) -> Result<i64, String> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| format!("Failed to start transaction: {e}"))?;
+        let result = Self::apply_cancel_in_tx(
+            &mut tx,
+            cancel_info,
+            email_id,
+            shop_domain,
+            alternate_domains,
+        )
+        .await;
+        match result {
+            Ok(order_id) => {
+                tx.commit()
+                    .await
+                    .map_err(|e| format!("Failed to commit transaction: {e}"))?;
+                Ok(order_id)
+            }
+            Err(e) => {
+                let _ = tx.rollback().await;
+                Err(e)
+            }
+        }
+    }
+
+    async fn apply_order_number_change(
+        &self,
+        change_info: &OrderNumberChangeInfo,
+        email_id: i64,
+        change_email_internal_date: Option<i64>,
+        shop_domain: Option<String>,
+        shop_name: Option<String>,
+        alternate_domains: Option<Vec<String>>,
+    ) -> Result<i64, String> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| format!("Failed to start transaction: {e}"))?;
+        let result = Self::apply_order_number_change_in_tx(
+            &mut tx,
+            change_info,
+            email_id,
+            change_email_internal_date,
+            shop_domain,
+            shop_name,
+            alternate_domains,
+        )
+        .await;
+        match result {
+            Ok(order_id) => {
+                tx.commit()
+                    .await
+                    .map_err(|e| format!("Failed to commit transaction: {e}"))?;
+                Ok(order_id)
+            }
+            Err(e) => {
+                let _ = tx.rollback().await;
+                Err(e)
+            }
+        }
+    }
+
+    async fn apply_consolidation(
+        &self,
+        consolidation_info: &ConsolidationInfo,
+        email_id: i64,
+        shop_domain: Option<String>,
+        _shop_name: Option<String>,
+        alternate_domains: Option<Vec<String>>,
+    ) -> Result<i64, String> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| format!("Failed to start transaction: {e}"))?;
+        let result = Self::apply_consolidation_in_tx(
+            &mut tx,
+            consolidation_info,
+            email_id,
+            shop_domain,
+            alternate_domains,
+        )
+        .await;
+        match result {
+            Ok(order_id) => {
+                tx.commit()
+                    .await
+                    .map_err(|e| format!("Failed to commit transaction: {e}"))?;
+                Ok(order_id)
+            }
+            Err(e) => {
+                let _ = tx.rollback().await;
+                Err(e)
+            }
+        }
+    }
+
+    async fn apply_send_and_replace_items(
         &self,
         order_info: &OrderInfo,
         email_id: Option<i64>,
@@ -1759,6 +2464,8 @@ async fn remove_zero_price_duplicates_in_tx(
 mod tests {
     use super::*;
     use crate::parsers::cancel_info::CancelInfo;
+    use crate::parsers::delay_info::DelayedItem;
+    use crate::parsers::release_date::{FuzzyReleaseDate, ReleaseDatePrecision};
     use sqlx::sqlite::SqlitePoolOptions;
 
     async fn setup_test_db() -> SqlitePool {
@@ -1800,8 +2507,15 @@ mod tests {
                 shop_name TEXT,
                 order_number TEXT,
                 order_date DATETIME,
+                tax_amount INTEGER,
+                tax_included INTEGER NOT NULL DEFAULT 1,
+                amount_adjustment INTEGER NOT NULL DEFAULT 0,
+                cancel_reason TEXT,
+                cancel_reason_detail TEXT,
+                refund_amount INTEGER,
                 created_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
-                updated_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP
+                updated_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+                deleted_at DATETIME
             )
             "#,
         )
@@ -1821,8 +2535,12 @@ mod tests {
                 quantity INTEGER NOT NULL DEFAULT 1,
                 category TEXT,
                 brand TEXT,
+                release_date TEXT,
+                release_date_precision TEXT,
+                item_url TEXT,
                 created_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
                 updated_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+                deleted_at DATETIME,
                 FOREIGN KEY (order_id) REFERENCES orders(id) ON DELETE CASCADE
             )
             "#,
@@ -1893,14 +2611,73 @@ mod tests {
         .await
         .expect("Failed to create product_master table");
 
-        // 外部キー制約を有効化（ロールバックテストで使用）
-        sqlx::query("PRAGMA foreign_keys = ON")
+        // cancelled_items テーブル（apply_cancel で記録）
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS cancelled_items (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                order_id INTEGER NOT NULL,
+                email_id INTEGER,
+                item_name TEXT NOT NULL,
+                maker TEXT,
+                quantity INTEGER NOT NULL DEFAULT 1,
+                cancel_reason TEXT,
+                created_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+                FOREIGN KEY (order_id) REFERENCES orders(id) ON DELETE CASCADE
+            )
+            "#,
+        )
+        .execute(&pool)
+        .await
+        .expect("Failed to create cancelled_items table");
+
+        // delivery_items テーブル（発送メールの商品と荷物の紐づけ）
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS delivery_items (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                delivery_id INTEGER NOT NULL,
+                item_id INTEGER NOT NULL,
+                quantity INTEGER NOT NULL DEFAULT 1,
+                created_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+                FOREIGN KEY (delivery_id) REFERENCES deliveries(id) ON DELETE CASCADE,
+                FOREIGN KEY (item_id) REFERENCES items(id) ON DELETE CASCADE,
+                UNIQUE (delivery_id, item_id)
+            )
+            "#,
+        )
+        .execute(&pool)
+        .await
+        .expect("Failed to create delivery_items table");
+
+        // trashed_orders / trashed_items テーブル（ゴミ箱の状態をビジネスキーで保持）
+        sqlx::raw_sql(include_str!("../../migrations/034_trashed_keys.sql"))
             .execute(&pool)
             .await
-            .expect("Failed to enable foreign keys");
+            .expect("Failed to create trashed key tables");
 
-        pool
-    }
+        // auto_tag_rules / item_tags テーブル（save_order_in_tx で自動タグ付けに使用）
+        sqlx::raw_sql(include_str!("../../migrations/008_auto_tag_rules.sql"))
+            .execute(&pool)
+            .await
+            .expect("Failed to create auto tag tables");
+
+        // orders.order_number_normalized（正規化済み注文番号での突合用）
+        sqlx::raw_sql(include_str!(
+            "../../migrations/036_order_number_normalized.sql"
+        ))
+        .execute(&pool)
+        .await
+        .expect("Failed to add order_number_normalized");
+
+        // 外部キー制約を有効化（ロールバックテストで使用）
+        sqlx::query("PRAGMA foreign_keys = ON")
+            .execute(&pool)
+            .await
+            .expect("Failed to enable foreign keys");
+
+        pool
+    }
 
     #[tokio::test]
     async fn test_order_repository_save_new_order() {
@@ -1942,6 +2719,8 @@ mod tests {
                     quantity: 2,
                     subtotal: 2000,
                     image_url: None,
+                    item_url: None,
+                    release_date: None,
                 },
                 OrderItem {
                     name: "商品B".to_string(),
@@ -1951,11 +2730,15 @@ mod tests {
                     quantity: 1,
                     subtotal: 500,
                     image_url: None,
+                    item_url: None,
+                    release_date: None,
                 },
             ],
             subtotal: Some(2500),
             shipping_fee: Some(500),
             total_amount: Some(3000),
+            tax_amount: None,
+            tax_included: true,
         };
 
         // 注文を保存
@@ -2021,6 +2804,157 @@ mod tests {
         assert_eq!(link.1, email_id.0);
     }
 
+    #[tokio::test]
+    async fn test_save_order_matches_existing_order_by_normalized_number() {
+        let pool = setup_test_db().await;
+        let repo = SqliteOrderRepository::new(pool.clone());
+
+        let existing_id = sqlx::query(
+            r#"INSERT INTO orders (order_number, shop_domain, shop_name) VALUES ('KC-12345', 'mail.dmm.com', 'DMM')"#,
+        )
+        .execute(&pool)
+        .await
+        .unwrap()
+        .last_insert_rowid();
+
+        use crate::parsers::OrderInfo;
+        let order_info = OrderInfo {
+            order_number: "kc12345".to_string(),
+            order_date: None,
+            delivery_address: None,
+            delivery_info: None,
+            items: vec![],
+            subtotal: None,
+            shipping_fee: None,
+            total_amount: None,
+            tax_amount: None,
+            tax_included: true,
+        };
+        let order_id = repo
+            .save_order(
+                &order_info,
+                None,
+                Some("mail.dmm.com".to_string()),
+                Some("DMM".to_string()),
+            )
+            .await
+            .unwrap();
+        assert_eq!(order_id, existing_id);
+
+        let mut tx = pool.begin().await.unwrap();
+        let found = SqliteOrderRepository::find_order_by_number_and_domain(
+            &mut tx,
+            "KC 12345",
+            &Some("mail.dmm.com".to_string()),
+            None,
+        )
+        .await
+        .unwrap();
+        assert_eq!(found, Some(existing_id));
+        let other_domain = SqliteOrderRepository::find_order_by_number_and_domain(
+            &mut tx,
+            "KC-12345",
+            &Some("example.com".to_string()),
+            None,
+        )
+        .await
+        .unwrap();
+        assert_eq!(other_domain, None);
+    }
+
+    #[tokio::test]
+    async fn test_find_order_does_not_merge_different_zero_padding_per_segment() {
+        let pool = setup_test_db().await;
+
+        let existing_id = sqlx::query(
+            r#"INSERT INTO orders (order_number, shop_domain, shop_name) VALUES ('A-01-2', 'example.com', 'Example')"#,
+        )
+        .execute(&pool)
+        .await
+        .unwrap()
+        .last_insert_rowid();
+
+        let mut tx = pool.begin().await.unwrap();
+        let domain = Some("example.com".to_string());
+        let different = SqliteOrderRepository::find_order_by_number_and_domain(
+            &mut tx, "A-1-02", &domain, None,
+        )
+        .await
+        .unwrap();
+        assert_eq!(different, None);
+        let same =
+            SqliteOrderRepository::find_order_by_number_and_domain(&mut tx, "a 012", &domain, None)
+                .await
+                .unwrap();
+        assert_eq!(same, Some(existing_id));
+    }
+
+    #[tokio::test]
+    async fn test_find_order_backfills_normalized_number_of_existing_orders() {
+        let pool = setup_test_db().await;
+
+        // マイグレーション前からある注文（全角数字・ダッシュ違い）は正規化済み注文番号が未設定
+        let existing_id = sqlx::query(
+            r#"INSERT INTO orders (order_number, shop_domain, shop_name) VALUES ('２５–１０２１―１１５６', '1999.co.jp', 'ホビーサーチ')"#,
+        )
+        .execute(&pool)
+        .await
+        .unwrap()
+        .last_insert_rowid();
+
+        let mut tx = pool.begin().await.unwrap();
+        let found = SqliteOrderRepository::find_order_by_number_and_domain(
+            &mut tx,
+            "25-1021-1156",
+            &Some("1999.co.jp".to_string()),
+            None,
+        )
+        .await
+        .unwrap();
+        assert_eq!(found, Some(existing_id));
+        tx.commit().await.unwrap();
+
+        let normalized: Option<String> =
+            sqlx::query_scalar("SELECT order_number_normalized FROM orders WHERE id = ?")
+                .bind(existing_id)
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+        assert_eq!(normalized.as_deref(), Some("2510211156"));
+    }
+
+    #[tokio::test]
+    async fn test_save_order_sets_order_number_normalized() {
+        let pool = setup_test_db().await;
+        let repo = SqliteOrderRepository::new(pool.clone());
+
+        use crate::parsers::OrderInfo;
+        let order_info = OrderInfo {
+            order_number: "ｂｓ－００１２".to_string(),
+            order_date: None,
+            delivery_address: None,
+            delivery_info: None,
+            items: vec![],
+            subtotal: None,
+            shipping_fee: None,
+            total_amount: None,
+            tax_amount: None,
+            tax_included: true,
+        };
+        let order_id = repo
+            .save_order(&order_info, None, Some("example.com".to_string()), None)
+            .await
+            .unwrap();
+
+        let normalized: Option<String> =
+            sqlx::query_scalar("SELECT order_number_normalized FROM orders WHERE id = ?")
+                .bind(order_id)
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+        assert_eq!(normalized.as_deref(), Some("BS0012"));
+    }
+
     #[tokio::test]
     async fn test_save_order_delivery_status_delivered() {
         // delivery_status: Some("delivered") を指定した場合に delivered で登録されること
@@ -2058,10 +2992,14 @@ mod tests {
                 quantity: 1,
                 subtotal: 1000,
                 image_url: None,
+                item_url: None,
+                release_date: None,
             }],
             subtotal: Some(1000),
             shipping_fee: None,
             total_amount: Some(1000),
+            tax_amount: None,
+            tax_included: true,
         };
 
         let order_id = repo
@@ -2084,154 +3022,543 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn test_save_order_delivery_status_invalid_returns_error() {
-        // delivery_status に不正値を指定した場合にエラーが返ること
+    async fn test_save_order_applies_auto_tag_rules() {
         let pool = setup_test_db().await;
         let repo = SqliteOrderRepository::new(pool.clone());
+        sqlx::query(
+            "INSERT INTO auto_tag_rules (tag, target, keyword, match_type) VALUES ('ガンプラ', 'item_name', 'HG', 'contains')",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
 
-        sqlx::query("INSERT INTO emails (message_id, body_plain, from_address, subject) VALUES ('test-email-invalid', 'body', 'test@example.com', 'Subject')")
-            .execute(&pool)
-            .await
-            .expect("Failed to insert test email");
-        let email_id: (i64,) =
-            sqlx::query_as("SELECT id FROM emails WHERE message_id = 'test-email-invalid'")
-                .fetch_one(&pool)
-                .await
-                .expect("Failed to get email id");
-
-        use crate::parsers::{DeliveryInfo, OrderInfo, OrderItem};
+        use crate::parsers::{OrderInfo, OrderItem};
         let order_info = OrderInfo {
-            order_number: "ORD-INVALID".to_string(),
+            order_number: "ORD-TAG".to_string(),
             order_date: None,
             delivery_address: None,
-            delivery_info: Some(DeliveryInfo {
-                carrier: "ヤマト運輸".to_string(),
-                tracking_number: "9999".to_string(),
-                delivery_date: None,
-                delivery_time: None,
-                carrier_url: None,
-                delivery_status: Some("invalid_status".to_string()),
-            }),
+            delivery_info: None,
             items: vec![OrderItem {
-                name: "商品Y".to_string(),
+                name: "HG 1/144 ガンダム".to_string(),
                 manufacturer: None,
                 model_number: None,
-                unit_price: 500,
+                unit_price: 1000,
                 quantity: 1,
-                subtotal: 500,
+                subtotal: 1000,
                 image_url: None,
+                item_url: None,
+                release_date: None,
             }],
-            subtotal: Some(500),
+            subtotal: Some(1000),
             shipping_fee: None,
-            total_amount: Some(500),
+            total_amount: Some(1000),
+            tax_amount: None,
+            tax_included: true,
         };
+        repo.save_order(&order_info, None, Some("example.com".to_string()), None)
+            .await
+            .unwrap();
 
-        let result = repo
-            .save_order(
-                &order_info,
-                Some(email_id.0),
-                Some("example.com".to_string()),
-                None,
-            )
-            .await;
-
-        assert!(result.is_err());
-        assert!(result.unwrap_err().contains("Invalid delivery_status"));
+        let tags: Vec<String> = sqlx::query_scalar("SELECT tag FROM item_tags")
+            .fetch_all(&pool)
+            .await
+            .unwrap();
+        assert_eq!(tags, vec!["ガンプラ".to_string()]);
     }
 
     #[tokio::test]
-    async fn test_apply_cancel_quantity_decrease() {
+    async fn test_save_order_stores_tax_info() {
+        // 税抜表示の注文は tax_amount / tax_included が保存され、税情報のない再保存で消えないこと
         let pool = setup_test_db().await;
         let repo = SqliteOrderRepository::new(pool.clone());
 
-        // 注文と商品を直接挿入
-        sqlx::query(
-            r#"INSERT INTO orders (order_number, shop_domain, shop_name) VALUES ('99-1111-1111', '1999.co.jp', 'ホビーサーチ')"#,
-        )
-        .execute(&pool)
-        .await
-        .expect("insert order");
-        let order_id: (i64,) =
-            sqlx::query_as("SELECT id FROM orders WHERE order_number = '99-1111-1111'")
-                .fetch_one(&pool)
-                .await
-                .expect("get order id");
-        sqlx::query(r#"INSERT INTO items (order_id, item_name, quantity) VALUES (?, '商品A', 2)"#)
-            .bind(order_id.0)
-            .execute(&pool)
-            .await
-            .expect("insert item");
-        sqlx::query("INSERT INTO emails (message_id, body_plain) VALUES ('cancel-email-1', '')")
-            .execute(&pool)
+        use crate::parsers::{OrderInfo, OrderItem};
+        let mut order_info = OrderInfo {
+            order_number: "ORD-TAX".to_string(),
+            order_date: Some("2024-01-01".to_string()),
+            delivery_address: None,
+            delivery_info: None,
+            items: vec![OrderItem {
+                name: "商品T".to_string(),
+                manufacturer: None,
+                model_number: None,
+                unit_price: 1000,
+                quantity: 1,
+                subtotal: 1000,
+                image_url: None,
+                item_url: None,
+                release_date: None,
+            }],
+            subtotal: Some(1000),
+            shipping_fee: None,
+            total_amount: Some(1100),
+            tax_amount: Some(100),
+            tax_included: false,
+        };
+
+        let order_id = repo
+            .save_order(&order_info, None, Some("example.com".to_string()), None)
             .await
-            .expect("insert email");
-        let email_id: (i64,) =
-            sqlx::query_as("SELECT id FROM emails WHERE message_id = 'cancel-email-1'")
-                .fetch_one(&pool)
-                .await
-                .expect("get email id");
+            .unwrap();
 
-        let cancel_info = CancelInfo {
-            order_number: "99-1111-1111".to_string(),
-            product_name: "商品A".to_string(),
-            cancel_quantity: 1,
-        };
-        let result = repo
-            .apply_cancel(
-                &cancel_info,
-                email_id.0,
-                Some("1999.co.jp".to_string()),
-                None,
-                None,
-            )
-            .await;
-        assert!(result.is_ok());
-        assert_eq!(result.unwrap(), order_id.0);
+        order_info.tax_amount = None;
+        order_info.tax_included = true;
+        repo.save_order(&order_info, None, Some("example.com".to_string()), None)
+            .await
+            .unwrap();
 
-        let qty: (i64,) =
-            sqlx::query_as("SELECT quantity FROM items WHERE order_id = ? AND item_name = '商品A'")
-                .bind(order_id.0)
+        let tax: (Option<i64>, bool) =
+            sqlx::query_as("SELECT tax_amount, tax_included FROM orders WHERE id = ?")
+                .bind(order_id)
                 .fetch_one(&pool)
                 .await
-                .expect("get item");
-        assert_eq!(qty.0, 1);
+                .expect("Failed to fetch order tax info");
+        assert_eq!(tax, (Some(100), false));
 
-        // order_emails に (order_id, email_id) が1件挿入されること
-        let link_count: (i64,) =
-            sqlx::query_as("SELECT COUNT(*) FROM order_emails WHERE order_id = ? AND email_id = ?")
-                .bind(order_id.0)
-                .bind(email_id.0)
+        // 合計は商品 + 税額で一致するため調整額は 0（税情報のない再保存でも税額を二重に差し引かない）
+        let adjustment: i64 =
+            sqlx::query_scalar("SELECT amount_adjustment FROM orders WHERE id = ?")
+                .bind(order_id)
                 .fetch_one(&pool)
                 .await
-                .expect("count order_emails");
-        assert_eq!(link_count.0, 1, "order_emails should have 1 link");
+                .expect("Failed to fetch amount adjustment");
+        assert_eq!(adjustment, 0);
     }
 
     #[tokio::test]
-    async fn test_apply_cancel_order_emails_no_duplicate() {
+    async fn test_save_order_records_amount_adjustment() {
         let pool = setup_test_db().await;
         let repo = SqliteOrderRepository::new(pool.clone());
 
-        sqlx::query(
-            r#"INSERT INTO orders (order_number, shop_domain, shop_name) VALUES ('99-4444-4444', '1999.co.jp', 'ホビーサーチ')"#,
-        )
-        .execute(&pool)
-        .await
-        .expect("insert order");
-        let order_id: (i64,) =
-            sqlx::query_as("SELECT id FROM orders WHERE order_number = '99-4444-4444'")
-                .fetch_one(&pool)
-                .await
-                .expect("get order id");
-        sqlx::query(r#"INSERT INTO items (order_id, item_name, quantity) VALUES (?, '商品D', 2)"#)
-            .bind(order_id.0)
-            .execute(&pool)
+        use crate::parsers::{OrderInfo, OrderItem};
+        let order_info = OrderInfo {
+            order_number: "ORD-ADJ".to_string(),
+            order_date: Some("2024-01-01".to_string()),
+            delivery_address: None,
+            delivery_info: None,
+            items: vec![OrderItem {
+                name: "商品A".to_string(),
+                manufacturer: None,
+                model_number: None,
+                unit_price: 2000,
+                quantity: 1,
+                subtotal: 2000,
+                image_url: None,
+                item_url: None,
+                release_date: None,
+            }],
+            subtotal: Some(2000),
+            shipping_fee: Some(660),
+            total_amount: Some(2660),
+            tax_amount: None,
+            tax_included: true,
+        };
+
+        // 同じ注文を再保存しても加算されず上書きされる
+        let order_id = repo
+            .save_order(&order_info, None, Some("example.com".to_string()), None)
             .await
-            .expect("insert item");
-        sqlx::query("INSERT INTO emails (message_id, body_plain) VALUES ('cancel-email-5', '')")
-            .execute(&pool)
+            .unwrap();
+        repo.save_order(&order_info, None, Some("example.com".to_string()), None)
             .await
-            .expect("insert email");
+            .unwrap();
+
+        let adjustment: i64 =
+            sqlx::query_scalar("SELECT amount_adjustment FROM orders WHERE id = ?")
+                .bind(order_id)
+                .fetch_one(&pool)
+                .await
+                .expect("Failed to fetch amount adjustment");
+        assert_eq!(adjustment, 660);
+    }
+
+    #[tokio::test]
+    async fn test_save_order_send_without_amounts_keeps_confirm_values() {
+        // 発送通知（プレミアムバンダイ等）は金額を含まないため、confirm で登録した価格・調整額を保持すること
+        let pool = setup_test_db().await;
+        let repo = SqliteOrderRepository::new(pool.clone());
+
+        use crate::parsers::{DeliveryInfo, OrderInfo, OrderItem};
+        let item = |unit_price: i64| OrderItem {
+            name: "商品P".to_string(),
+            manufacturer: None,
+            model_number: None,
+            unit_price,
+            quantity: 1,
+            subtotal: unit_price,
+            image_url: None,
+            item_url: None,
+            release_date: None,
+        };
+        let confirm = OrderInfo {
+            order_number: "00123".to_string(),
+            order_date: Some("2024-01-01".to_string()),
+            delivery_address: None,
+            delivery_info: None,
+            items: vec![item(5000)],
+            subtotal: Some(5000),
+            shipping_fee: Some(990),
+            total_amount: Some(5990),
+            tax_amount: None,
+            tax_included: true,
+        };
+        let send = OrderInfo {
+            order_date: Some("2024-03-01".to_string()),
+            delivery_info: Some(DeliveryInfo {
+                carrier: "佐川急便".to_string(),
+                tracking_number: "123456789012".to_string(),
+                delivery_date: None,
+                delivery_time: None,
+                carrier_url: None,
+                delivery_status: None,
+            }),
+            items: vec![item(0)],
+            subtotal: None,
+            shipping_fee: None,
+            total_amount: None,
+            ..confirm.clone()
+        };
+
+        let order_id = repo
+            .save_order(&confirm, None, Some("p-bandai.jp".to_string()), None)
+            .await
+            .unwrap();
+        let send_order_id = repo
+            .save_order(&send, None, Some("p-bandai.jp".to_string()), None)
+            .await
+            .unwrap();
+        assert_eq!(send_order_id, order_id);
+
+        let items: Vec<(i64, i64)> =
+            sqlx::query_as("SELECT price, quantity FROM items WHERE order_id = ?")
+                .bind(order_id)
+                .fetch_all(&pool)
+                .await
+                .expect("Failed to fetch items");
+        assert_eq!(items, vec![(5000, 1)]);
+
+        let adjustment: i64 =
+            sqlx::query_scalar("SELECT amount_adjustment FROM orders WHERE id = ?")
+                .bind(order_id)
+                .fetch_one(&pool)
+                .await
+                .expect("Failed to fetch amount adjustment");
+        assert_eq!(adjustment, 990);
+
+        let tracking: String =
+            sqlx::query_scalar("SELECT tracking_number FROM deliveries WHERE order_id = ?")
+                .bind(order_id)
+                .fetch_one(&pool)
+                .await
+                .expect("Failed to fetch delivery");
+        assert_eq!(tracking, "123456789012");
+    }
+
+    #[tokio::test]
+    async fn test_save_order_stores_release_date() {
+        // パーサーの抽出値を保存し、なければ商品名から補う。再保存時は新しい値で更新する
+        let pool = setup_test_db().await;
+        let repo = SqliteOrderRepository::new(pool.clone());
+
+        use crate::parsers::{OrderInfo, OrderItem};
+        let item = |name: &str, release_date: Option<FuzzyReleaseDate>| OrderItem {
+            name: name.to_string(),
+            manufacturer: None,
+            model_number: None,
+            unit_price: 1000,
+            quantity: 1,
+            subtotal: 1000,
+            image_url: None,
+            item_url: None,
+            release_date,
+        };
+        let mut order_info = OrderInfo {
+            order_number: "ORD-RELEASE".to_string(),
+            order_date: Some("2025-01-01".to_string()),
+            delivery_address: None,
+            delivery_info: None,
+            items: vec![
+                item(
+                    "商品R",
+                    FuzzyReleaseDate::month(2025, 8, ReleaseDatePrecision::Month),
+                ),
+                item("商品S【25年10月下旬予約】", None),
+                item(
+                    "商品T",
+                    Some(FuzzyReleaseDate::period(2025, ReleaseDatePrecision::Summer)),
+                ),
+                item("商品U", None),
+            ],
+            subtotal: Some(3000),
+            shipping_fee: None,
+            total_amount: Some(3000),
+            tax_amount: None,
+            tax_included: true,
+        };
+
+        let order_id = repo
+            .save_order(&order_info, None, Some("example.com".to_string()), None)
+            .await
+            .unwrap();
+
+        // 延期で発売予定が変わったメール
+        order_info.items = vec![item("商品R", FuzzyReleaseDate::day(2025, 11, 20))];
+        repo.save_order(&order_info, None, Some("example.com".to_string()), None)
+            .await
+            .unwrap();
+
+        let rows: Vec<(String, Option<String>, Option<String>)> = sqlx::query_as(
+            "SELECT item_name, release_date, release_date_precision FROM items WHERE order_id = ? ORDER BY id",
+        )
+        .bind(order_id)
+        .fetch_all(&pool)
+        .await
+        .expect("Failed to fetch items");
+        assert_eq!(
+            rows,
+            vec![
+                (
+                    "商品R".to_string(),
+                    Some("2025-11-20".to_string()),
+                    Some("day".to_string())
+                ),
+                (
+                    "商品S【25年10月下旬予約】".to_string(),
+                    Some("2025-10-31".to_string()),
+                    Some("late_month".to_string())
+                ),
+                (
+                    "商品T".to_string(),
+                    Some("2025-08".to_string()),
+                    Some("summer".to_string())
+                ),
+                ("商品U".to_string(), None, None),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_save_order_stores_item_url() {
+        // 新規商品はURLを保存し、既存商品はURLが未設定の場合のみ補完する
+        let pool = setup_test_db().await;
+        let repo = SqliteOrderRepository::new(pool.clone());
+
+        use crate::parsers::{OrderInfo, OrderItem};
+        let item = |name: &str, item_url: Option<&str>| OrderItem {
+            name: name.to_string(),
+            manufacturer: None,
+            model_number: None,
+            unit_price: 1000,
+            quantity: 1,
+            subtotal: 1000,
+            image_url: None,
+            item_url: item_url.map(String::from),
+            release_date: None,
+        };
+        let mut order_info = OrderInfo {
+            order_number: "ORD-URL".to_string(),
+            order_date: Some("2025-01-01".to_string()),
+            delivery_address: None,
+            delivery_info: None,
+            items: vec![
+                item("商品A", Some("https://shop.example.com/item/a")),
+                item("商品B", None),
+            ],
+            subtotal: Some(2000),
+            shipping_fee: None,
+            total_amount: Some(2000),
+            tax_amount: None,
+            tax_included: true,
+        };
+        let order_id = repo
+            .save_order(&order_info, None, Some("example.com".to_string()), None)
+            .await
+            .unwrap();
+
+        order_info.items = vec![
+            item("商品A", Some("https://shop.example.com/item/a2")),
+            item("商品B", Some("https://shop.example.com/item/b")),
+        ];
+        repo.save_order(&order_info, None, Some("example.com".to_string()), None)
+            .await
+            .unwrap();
+
+        let rows: Vec<(String, Option<String>)> =
+            sqlx::query_as("SELECT item_name, item_url FROM items WHERE order_id = ? ORDER BY id")
+                .bind(order_id)
+                .fetch_all(&pool)
+                .await
+                .expect("Failed to fetch items");
+        assert_eq!(
+            rows,
+            vec![
+                (
+                    "商品A".to_string(),
+                    Some("https://shop.example.com/item/a".to_string())
+                ),
+                (
+                    "商品B".to_string(),
+                    Some("https://shop.example.com/item/b".to_string())
+                ),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_save_order_delivery_status_invalid_returns_error() {
+        // delivery_status に不正値を指定した場合にエラーが返ること
+        let pool = setup_test_db().await;
+        let repo = SqliteOrderRepository::new(pool.clone());
+
+        sqlx::query("INSERT INTO emails (message_id, body_plain, from_address, subject) VALUES ('test-email-invalid', 'body', 'test@example.com', 'Subject')")
+            .execute(&pool)
+            .await
+            .expect("Failed to insert test email");
+        let email_id: (i64,) =
+            sqlx::query_as("SELECT id FROM emails WHERE message_id = 'test-email-invalid'")
+                .fetch_one(&pool)
+                .await
+                .expect("Failed to get email id");
+
+        use crate::parsers::{DeliveryInfo, OrderInfo, OrderItem};
+        let order_info = OrderInfo {
+            order_number: "ORD-INVALID".to_string(),
+            order_date: None,
+            delivery_address: None,
+            delivery_info: Some(DeliveryInfo {
+                carrier: "ヤマト運輸".to_string(),
+                tracking_number: "9999".to_string(),
+                delivery_date: None,
+                delivery_time: None,
+                carrier_url: None,
+                delivery_status: Some("invalid_status".to_string()),
+            }),
+            items: vec![OrderItem {
+                name: "商品Y".to_string(),
+                manufacturer: None,
+                model_number: None,
+                unit_price: 500,
+                quantity: 1,
+                subtotal: 500,
+                image_url: None,
+                item_url: None,
+                release_date: None,
+            }],
+            subtotal: Some(500),
+            shipping_fee: None,
+            total_amount: Some(500),
+            tax_amount: None,
+            tax_included: true,
+        };
+
+        let result = repo
+            .save_order(
+                &order_info,
+                Some(email_id.0),
+                Some("example.com".to_string()),
+                None,
+            )
+            .await;
+
+        assert!(result.is_err());
+        assert!(result.unwrap_err().contains("Invalid delivery_status"));
+    }
+
+    #[tokio::test]
+    async fn test_apply_cancel_quantity_decrease() {
+        let pool = setup_test_db().await;
+        let repo = SqliteOrderRepository::new(pool.clone());
+
+        // 注文と商品を直接挿入
+        sqlx::query(
+            r#"INSERT INTO orders (order_number, shop_domain, shop_name) VALUES ('99-1111-1111', '1999.co.jp', 'ホビーサーチ')"#,
+        )
+        .execute(&pool)
+        .await
+        .expect("insert order");
+        let order_id: (i64,) =
+            sqlx::query_as("SELECT id FROM orders WHERE order_number = '99-1111-1111'")
+                .fetch_one(&pool)
+                .await
+                .expect("get order id");
+        sqlx::query(r#"INSERT INTO items (order_id, item_name, quantity) VALUES (?, '商品A', 2)"#)
+            .bind(order_id.0)
+            .execute(&pool)
+            .await
+            .expect("insert item");
+        sqlx::query("INSERT INTO emails (message_id, body_plain) VALUES ('cancel-email-1', '')")
+            .execute(&pool)
+            .await
+            .expect("insert email");
+        let email_id: (i64,) =
+            sqlx::query_as("SELECT id FROM emails WHERE message_id = 'cancel-email-1'")
+                .fetch_one(&pool)
+                .await
+                .expect("get email id");
+
+        let cancel_info = CancelInfo {
+            order_number: "99-1111-1111".to_string(),
+            product_name: "商品A".to_string(),
+            cancel_quantity: 1,
+            reason: None,
+            reason_detail: None,
+        };
+        let result = repo
+            .apply_cancel(
+                &cancel_info,
+                email_id.0,
+                Some("1999.co.jp".to_string()),
+                None,
+                None,
+            )
+            .await;
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap(), order_id.0);
+
+        let qty: (i64,) =
+            sqlx::query_as("SELECT quantity FROM items WHERE order_id = ? AND item_name = '商品A'")
+                .bind(order_id.0)
+                .fetch_one(&pool)
+                .await
+                .expect("get item");
+        assert_eq!(qty.0, 1);
+
+        // order_emails に (order_id, email_id) が1件挿入されること
+        let link_count: (i64,) =
+            sqlx::query_as("SELECT COUNT(*) FROM order_emails WHERE order_id = ? AND email_id = ?")
+                .bind(order_id.0)
+                .bind(email_id.0)
+                .fetch_one(&pool)
+                .await
+                .expect("count order_emails");
+        assert_eq!(link_count.0, 1, "order_emails should have 1 link");
+    }
+
+    #[tokio::test]
+    async fn test_apply_cancel_order_emails_no_duplicate() {
+        let pool = setup_test_db().await;
+        let repo = SqliteOrderRepository::new(pool.clone());
+
+        sqlx::query(
+            r#"INSERT INTO orders (order_number, shop_domain, shop_name) VALUES ('99-4444-4444', '1999.co.jp', 'ホビーサーチ')"#,
+        )
+        .execute(&pool)
+        .await
+        .expect("insert order");
+        let order_id: (i64,) =
+            sqlx::query_as("SELECT id FROM orders WHERE order_number = '99-4444-4444'")
+                .fetch_one(&pool)
+                .await
+                .expect("get order id");
+        sqlx::query(r#"INSERT INTO items (order_id, item_name, quantity) VALUES (?, '商品D', 2)"#)
+            .bind(order_id.0)
+            .execute(&pool)
+            .await
+            .expect("insert item");
+        sqlx::query("INSERT INTO emails (message_id, body_plain) VALUES ('cancel-email-5', '')")
+            .execute(&pool)
+            .await
+            .expect("insert email");
         let email_id: (i64,) =
             sqlx::query_as("SELECT id FROM emails WHERE message_id = 'cancel-email-5'")
                 .fetch_one(&pool)
@@ -2242,6 +3569,8 @@ mod tests {
             order_number: "99-4444-4444".to_string(),
             product_name: "商品D".to_string(),
             cancel_quantity: 1,
+            reason: None,
+            reason_detail: None,
         };
 
         // 1回目: 数量 2 -> 1
@@ -2310,6 +3639,8 @@ mod tests {
             order_number: "99-2222-2222".to_string(),
             product_name: "商品B".to_string(),
             cancel_quantity: 1,
+            reason: None,
+            reason_detail: None,
         };
         let result = repo
             .apply_cancel(
@@ -2349,6 +3680,8 @@ mod tests {
             order_number: "99-9999-9999".to_string(),
             product_name: "商品X".to_string(),
             cancel_quantity: 1,
+            reason: None,
+            reason_detail: None,
         };
         let result = repo
             .apply_cancel(
@@ -2398,6 +3731,8 @@ mod tests {
             order_number: "99-3333-3333".to_string(),
             product_name: "存在しない商品名".to_string(),
             cancel_quantity: 1,
+            reason: None,
+            reason_detail: None,
         };
         let result = repo
             .apply_cancel(
@@ -2443,6 +3778,8 @@ mod tests {
             order_number: "99-5555-5555".to_string(),
             product_name: "商品E".to_string(),
             cancel_quantity: 0,
+            reason: None,
+            reason_detail: None,
         };
         let result = repo
             .apply_cancel(
@@ -2497,6 +3834,8 @@ mod tests {
             order_number: "KC-99999".to_string(),
             product_name: "".to_string(),
             cancel_quantity: 1,
+            reason: None,
+            reason_detail: None,
         };
         let result = repo
             .apply_cancel(
@@ -2519,16 +3858,178 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn test_apply_order_number_change() {
+    async fn test_apply_cancel_records_reason_detail() {
         let pool = setup_test_db().await;
         let repo = SqliteOrderRepository::new(pool.clone());
 
-        sqlx::query(
-            r#"INSERT INTO orders (order_number, shop_domain, shop_name) VALUES ('KC-26407532', 'mail.dmm.com', 'DMM')"#,
+        let order_id = sqlx::query(
+            r#"INSERT INTO orders (order_number, shop_domain, shop_name) VALUES ('KC-88888', 'mail.dmm.com', 'DMM')"#,
         )
         .execute(&pool)
         .await
-        .expect("insert order");
+        .expect("insert order")
+        .last_insert_rowid();
+        sqlx::query(r#"INSERT INTO items (order_id, item_name, quantity) VALUES (?, '商品A', 1)"#)
+            .bind(order_id)
+            .execute(&pool)
+            .await
+            .expect("insert item");
+        let email_id = sqlx::query(
+            "INSERT INTO emails (message_id, body_plain) VALUES ('cancel-email-reason', '')",
+        )
+        .execute(&pool)
+        .await
+        .expect("insert email")
+        .last_insert_rowid();
+
+        let cancel_info = CancelInfo {
+            order_number: "KC-88888".to_string(),
+            product_name: "商品A".to_string(),
+            cancel_quantity: 1,
+            reason: Some(CancelReason::OutOfStock),
+            reason_detail: Some("メーカーからの入荷の見込みが立たないため".to_string()),
+        };
+        repo.apply_cancel(
+            &cancel_info,
+            email_id,
+            Some("mail.dmm.com".to_string()),
+            None,
+            None,
+        )
+        .await
+        .expect("apply_cancel");
+
+        let reason: Option<String> =
+            sqlx::query_scalar("SELECT cancel_reason_detail FROM orders WHERE id = ?")
+                .bind(order_id)
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+        assert_eq!(
+            reason.as_deref(),
+            Some("メーカーからの入荷の見込みが立たないため")
+        );
+
+        let category: Option<String> =
+            sqlx::query_scalar("SELECT cancel_reason FROM orders WHERE id = ?")
+                .bind(order_id)
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+        assert_eq!(category.as_deref(), Some("out_of_stock"));
+    }
+
+    #[tokio::test]
+    async fn test_apply_cancel_records_cancelled_items_with_maker() {
+        let pool = setup_test_db().await;
+        let repo = SqliteOrderRepository::new(pool.clone());
+
+        let order_id = sqlx::query(
+            r#"INSERT INTO orders (order_number, shop_domain, shop_name) VALUES ('KC-77777', 'mail.dmm.com', 'DMM')"#,
+        )
+        .execute(&pool)
+        .await
+        .expect("insert order")
+        .last_insert_rowid();
+        sqlx::query(
+            r#"INSERT INTO items (order_id, item_name, quantity, brand) VALUES (?, '商品A', 3, 'ブランドA'), (?, '商品B', 1, 'ブランドB')"#,
+        )
+        .bind(order_id)
+        .bind(order_id)
+        .execute(&pool)
+        .await
+        .expect("insert items");
+        sqlx::query(
+            r#"INSERT INTO product_master (raw_name, normalized_name, maker) VALUES ('商品A', '商品a', 'メーカーA')"#,
+        )
+        .execute(&pool)
+        .await
+        .expect("insert product_master");
+        let email_id = sqlx::query(
+            "INSERT INTO emails (message_id, body_plain) VALUES ('cancel-email-log', '')",
+        )
+        .execute(&pool)
+        .await
+        .expect("insert email")
+        .last_insert_rowid();
+
+        // 部分キャンセル: キャンセル数量のみ記録
+        let partial = CancelInfo {
+            order_number: "KC-77777".to_string(),
+            product_name: "商品A".to_string(),
+            cancel_quantity: 2,
+            reason: Some(CancelReason::Discontinued),
+            reason_detail: None,
+        };
+        repo.apply_cancel(
+            &partial,
+            email_id,
+            Some("mail.dmm.com".to_string()),
+            None,
+            None,
+        )
+        .await
+        .expect("apply partial cancel");
+
+        // 注文全体のキャンセル: 残りの全商品を記録
+        let whole = CancelInfo {
+            order_number: "KC-77777".to_string(),
+            product_name: String::new(),
+            cancel_quantity: 1,
+            reason: None,
+            reason_detail: None,
+        };
+        repo.apply_cancel(
+            &whole,
+            email_id,
+            Some("mail.dmm.com".to_string()),
+            None,
+            None,
+        )
+        .await
+        .expect("apply whole cancel");
+
+        let rows: Vec<(String, Option<String>, i64, Option<String>)> = sqlx::query_as(
+            "SELECT item_name, maker, quantity, cancel_reason FROM cancelled_items ORDER BY id",
+        )
+        .fetch_all(&pool)
+        .await
+        .unwrap();
+        assert_eq!(
+            rows,
+            vec![
+                (
+                    "商品A".to_string(),
+                    Some("メーカーA".to_string()),
+                    2,
+                    Some("discontinued".to_string())
+                ),
+                ("商品A".to_string(), Some("メーカーA".to_string()), 1, None),
+                ("商品B".to_string(), Some("ブランドB".to_string()), 1, None),
+            ]
+        );
+
+        // 理由不明のキャンセルでは注文の理由を上書きしない
+        let category: Option<String> =
+            sqlx::query_scalar("SELECT cancel_reason FROM orders WHERE id = ?")
+                .bind(order_id)
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+        assert_eq!(category.as_deref(), Some("discontinued"));
+    }
+
+    #[tokio::test]
+    async fn test_apply_order_number_change() {
+        let pool = setup_test_db().await;
+        let repo = SqliteOrderRepository::new(pool.clone());
+
+        sqlx::query(
+            r#"INSERT INTO orders (order_number, shop_domain, shop_name) VALUES ('KC-26407532', 'mail.dmm.com', 'DMM')"#,
+        )
+        .execute(&pool)
+        .await
+        .expect("insert order");
         let order_id: (i64,) =
             sqlx::query_as("SELECT id FROM orders WHERE order_number = 'KC-26407532'")
                 .fetch_one(&pool)
@@ -2570,12 +4071,432 @@ mod tests {
         );
         assert_eq!(result.unwrap(), order_id.0);
 
-        let row: (String,) = sqlx::query_as("SELECT order_number FROM orders WHERE id = ?")
-            .bind(order_id.0)
-            .fetch_one(&pool)
+        let row: (String,) = sqlx::query_as("SELECT order_number FROM orders WHERE id = ?")
+            .bind(order_id.0)
+            .fetch_one(&pool)
+            .await
+            .expect("get order");
+        assert_eq!(row.0, "BS-26888944");
+    }
+
+    async fn insert_order_with_email(pool: &SqlitePool) -> (i64, i64) {
+        let order_id = sqlx::query(
+            r#"INSERT INTO orders (order_number, shop_domain, shop_name) VALUES ('7538892732', 'yodobashi.com', 'ヨドバシ・ドット・コム')"#,
+        )
+        .execute(pool)
+        .await
+        .expect("insert order")
+        .last_insert_rowid();
+        let email_id = sqlx::query(
+            "INSERT INTO emails (message_id, body_plain) VALUES ('date-change-email-1', '')",
+        )
+        .execute(pool)
+        .await
+        .expect("insert email")
+        .last_insert_rowid();
+        (order_id, email_id)
+    }
+
+    fn delivery_date_change(order_number: &str) -> DeliveryDateChangeInfo {
+        DeliveryDateChangeInfo {
+            order_number: order_number.to_string(),
+            old_estimated_delivery: Some("2026-02-24".to_string()),
+            new_estimated_delivery: "2026-03-05".to_string(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_apply_delivery_date_change_updates_latest_delivery() {
+        let pool = setup_test_db().await;
+        let (order_id, email_id) = insert_order_with_email(&pool).await;
+        sqlx::query(
+            "INSERT INTO deliveries (order_id, delivery_status, estimated_delivery) VALUES (?, 'shipped', '2026-02-24')",
+        )
+        .bind(order_id)
+        .execute(&pool)
+        .await
+        .expect("insert delivery");
+
+        let mut tx = pool.begin().await.unwrap();
+        let result = SqliteOrderRepository::apply_delivery_date_change_in_tx(
+            &mut tx,
+            &delivery_date_change("7538892732"),
+            email_id,
+            Some("yodobashi.com".to_string()),
+            None,
+        )
+        .await;
+        tx.commit().await.unwrap();
+        assert_eq!(result, Ok(order_id));
+
+        let rows: Vec<(String, Option<String>)> = sqlx::query_as(
+            "SELECT delivery_status, estimated_delivery FROM deliveries WHERE order_id = ?",
+        )
+        .bind(order_id)
+        .fetch_all(&pool)
+        .await
+        .unwrap();
+        assert_eq!(
+            rows,
+            vec![("shipped".to_string(), Some("2026-03-05".to_string()))]
+        );
+
+        let linked: i64 = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM order_emails WHERE order_id = ? AND email_id = ?",
+        )
+        .bind(order_id)
+        .bind(email_id)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        assert_eq!(linked, 1);
+    }
+
+    #[tokio::test]
+    async fn test_apply_delivery_date_change_creates_delivery_when_missing() {
+        let pool = setup_test_db().await;
+        let (order_id, email_id) = insert_order_with_email(&pool).await;
+
+        let mut tx = pool.begin().await.unwrap();
+        SqliteOrderRepository::apply_delivery_date_change_in_tx(
+            &mut tx,
+            &delivery_date_change("7538892732"),
+            email_id,
+            Some("yodobashi.com".to_string()),
+            None,
+        )
+        .await
+        .unwrap();
+        tx.commit().await.unwrap();
+
+        let row: (String, Option<String>) = sqlx::query_as(
+            "SELECT delivery_status, estimated_delivery FROM deliveries WHERE order_id = ?",
+        )
+        .bind(order_id)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        assert_eq!(
+            row,
+            ("not_shipped".to_string(), Some("2026-03-05".to_string()))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_apply_delivery_date_change_keeps_delivered() {
+        let pool = setup_test_db().await;
+        let (order_id, email_id) = insert_order_with_email(&pool).await;
+        sqlx::query(
+            "INSERT INTO deliveries (order_id, delivery_status, estimated_delivery) VALUES (?, 'delivered', '2026-02-24')",
+        )
+        .bind(order_id)
+        .execute(&pool)
+        .await
+        .expect("insert delivery");
+
+        let mut tx = pool.begin().await.unwrap();
+        SqliteOrderRepository::apply_delivery_date_change_in_tx(
+            &mut tx,
+            &delivery_date_change("7538892732"),
+            email_id,
+            Some("yodobashi.com".to_string()),
+            None,
+        )
+        .await
+        .unwrap();
+        tx.commit().await.unwrap();
+
+        let estimated: Option<String> =
+            sqlx::query_scalar("SELECT estimated_delivery FROM deliveries WHERE order_id = ?")
+                .bind(order_id)
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+        assert_eq!(estimated.as_deref(), Some("2026-02-24"));
+    }
+
+    #[tokio::test]
+    async fn test_apply_delivery_date_change_order_not_found() {
+        let pool = setup_test_db().await;
+        let (_, email_id) = insert_order_with_email(&pool).await;
+
+        let mut tx = pool.begin().await.unwrap();
+        let result = SqliteOrderRepository::apply_delivery_date_change_in_tx(
+            &mut tx,
+            &delivery_date_change("9999999999"),
+            email_id,
+            Some("yodobashi.com".to_string()),
+            None,
+        )
+        .await;
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_apply_refund_overwrites_amount_and_links_email() {
+        let pool = setup_test_db().await;
+        let (order_id, email_id) = insert_order_with_email(&pool).await;
+
+        let mut tx = pool.begin().await.unwrap();
+        for amount in [5900, 3000] {
+            let result = SqliteOrderRepository::apply_refund_in_tx(
+                &mut tx,
+                "7538892732",
+                amount,
+                email_id,
+                Some("yodobashi.com".to_string()),
+                None,
+            )
+            .await;
+            assert_eq!(result, Ok(order_id));
+        }
+        let missing = SqliteOrderRepository::apply_refund_in_tx(
+            &mut tx,
+            "9999999999",
+            100,
+            email_id,
+            Some("yodobashi.com".to_string()),
+            None,
+        )
+        .await;
+        assert!(missing.is_err());
+        tx.commit().await.unwrap();
+
+        let refund: Option<i64> =
+            sqlx::query_scalar("SELECT refund_amount FROM orders WHERE id = ?")
+                .bind(order_id)
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+        assert_eq!(refund, Some(3000));
+
+        let linked: i64 = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM order_emails WHERE order_id = ? AND email_id = ?",
+        )
+        .bind(order_id)
+        .bind(email_id)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        assert_eq!(linked, 1);
+    }
+
+    fn delay_info(items: Vec<(&str, FuzzyReleaseDate)>) -> DelayInfo {
+        DelayInfo {
+            order_number: "7538892732".to_string(),
+            items: items
+                .into_iter()
+                .map(|(name, new_release_date)| DelayedItem {
+                    product_name: name.to_string(),
+                    old_release_date: None,
+                    new_release_date,
+                })
+                .collect(),
+        }
+    }
+
+    async fn fetch_release_dates(
+        pool: &SqlitePool,
+        order_id: i64,
+    ) -> Vec<(String, Option<String>, Option<String>)> {
+        sqlx::query_as(
+            "SELECT item_name, release_date, release_date_precision FROM items WHERE order_id = ? ORDER BY id",
+        )
+        .bind(order_id)
+        .fetch_all(pool)
+        .await
+        .expect("fetch items")
+    }
+
+    #[tokio::test]
+    async fn test_apply_release_delay_updates_matched_item() {
+        let pool = setup_test_db().await;
+        let (order_id, email_id) = insert_order_with_email(&pool).await;
+        sqlx::query(
+            r#"
+            INSERT INTO items (order_id, item_name, release_date) VALUES
+                (?1, 'メガミデバイス PUNI☆MOFU トゥ 1/1 プラモデル[コトブキヤ]《１１月予約》', '2024-11'),
+                (?1, 'ねんどろいど 初音ミク', '2024-12')
+            "#,
+        )
+        .bind(order_id)
+        .execute(&pool)
+        .await
+        .expect("insert items");
+
+        let mut tx = pool.begin().await.unwrap();
+        let result = SqliteOrderRepository::apply_release_delay_in_tx(
+            &mut tx,
+            &delay_info(vec![(
+                "メガミデバイス PUNI☆MOFU トゥ 1/1 プラモデル[コトブキヤ]《１１月予約》",
+                FuzzyReleaseDate::month(2025, 2, ReleaseDatePrecision::LateMonth).unwrap(),
+            )]),
+            email_id,
+            Some("yodobashi.com".to_string()),
+            None,
+        )
+        .await;
+        tx.commit().await.unwrap();
+        assert_eq!(result, Ok(order_id));
+
+        let rows = fetch_release_dates(&pool, order_id).await;
+        assert_eq!(rows[0].1.as_deref(), Some("2025-02-28"));
+        assert_eq!(rows[0].2.as_deref(), Some("late_month"));
+        assert_eq!(rows[1].1.as_deref(), Some("2024-12"));
+        assert_eq!(rows[1].2, None);
+
+        let linked: i64 = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM order_emails WHERE order_id = ? AND email_id = ?",
+        )
+        .bind(order_id)
+        .bind(email_id)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        assert_eq!(linked, 1);
+    }
+
+    #[tokio::test]
+    async fn test_apply_release_delay_whole_order() {
+        let pool = setup_test_db().await;
+        let (order_id, email_id) = insert_order_with_email(&pool).await;
+        sqlx::query("INSERT INTO items (order_id, item_name) VALUES (?1, '商品A'), (?1, '商品B')")
+            .bind(order_id)
+            .execute(&pool)
+            .await
+            .expect("insert items");
+
+        let mut tx = pool.begin().await.unwrap();
+        SqliteOrderRepository::apply_release_delay_in_tx(
+            &mut tx,
+            &delay_info(vec![(
+                "",
+                FuzzyReleaseDate::period(2025, ReleaseDatePrecision::Summer),
+            )]),
+            email_id,
+            Some("yodobashi.com".to_string()),
+            None,
+        )
+        .await
+        .unwrap();
+        tx.commit().await.unwrap();
+
+        let rows = fetch_release_dates(&pool, order_id).await;
+        assert!(rows
+            .iter()
+            .all(|(_, date, precision)| date.as_deref() == Some("2025-08")
+                && precision.as_deref() == Some("summer")));
+    }
+
+    #[tokio::test]
+    async fn test_apply_release_delay_order_not_found() {
+        let pool = setup_test_db().await;
+        let (_, email_id) = insert_order_with_email(&pool).await;
+
+        let mut info = delay_info(vec![(
+            "",
+            FuzzyReleaseDate::period(2025, ReleaseDatePrecision::Year),
+        )]);
+        info.order_number = "9999999999".to_string();
+
+        let mut tx = pool.begin().await.unwrap();
+        let result = SqliteOrderRepository::apply_release_delay_in_tx(
+            &mut tx,
+            &info,
+            email_id,
+            Some("yodobashi.com".to_string()),
+            None,
+        )
+        .await;
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_apply_preparing_promotes_not_shipped() {
+        let pool = setup_test_db().await;
+        let (order_id, email_id) = insert_order_with_email(&pool).await;
+        sqlx::query("INSERT INTO deliveries (order_id, delivery_status) VALUES (?, 'not_shipped')")
+            .bind(order_id)
+            .execute(&pool)
+            .await
+            .expect("insert delivery");
+
+        let mut tx = pool.begin().await.unwrap();
+        let result = SqliteOrderRepository::apply_preparing_in_tx(
+            &mut tx,
+            "7538892732",
+            email_id,
+            Some("yodobashi.com".to_string()),
+            None,
+        )
+        .await;
+        tx.commit().await.unwrap();
+        assert_eq!(result, Ok(order_id));
+
+        let status: String =
+            sqlx::query_scalar("SELECT delivery_status FROM deliveries WHERE order_id = ?")
+                .bind(order_id)
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+        assert_eq!(status, "preparing");
+    }
+
+    #[tokio::test]
+    async fn test_apply_preparing_creates_delivery_when_missing() {
+        let pool = setup_test_db().await;
+        let (order_id, email_id) = insert_order_with_email(&pool).await;
+
+        let mut tx = pool.begin().await.unwrap();
+        SqliteOrderRepository::apply_preparing_in_tx(
+            &mut tx,
+            "7538892732",
+            email_id,
+            Some("yodobashi.com".to_string()),
+            None,
+        )
+        .await
+        .unwrap();
+        tx.commit().await.unwrap();
+
+        let status: String =
+            sqlx::query_scalar("SELECT delivery_status FROM deliveries WHERE order_id = ?")
+                .bind(order_id)
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+        assert_eq!(status, "preparing");
+    }
+
+    #[tokio::test]
+    async fn test_apply_preparing_does_not_regress_shipped() {
+        let pool = setup_test_db().await;
+        let (order_id, email_id) = insert_order_with_email(&pool).await;
+        sqlx::query("INSERT INTO deliveries (order_id, delivery_status) VALUES (?, 'shipped')")
+            .bind(order_id)
+            .execute(&pool)
             .await
-            .expect("get order");
-        assert_eq!(row.0, "BS-26888944");
+            .expect("insert delivery");
+
+        let mut tx = pool.begin().await.unwrap();
+        SqliteOrderRepository::apply_preparing_in_tx(
+            &mut tx,
+            "7538892732",
+            email_id,
+            Some("yodobashi.com".to_string()),
+            None,
+        )
+        .await
+        .unwrap();
+        tx.commit().await.unwrap();
+
+        let status: String =
+            sqlx::query_scalar("SELECT delivery_status FROM deliveries WHERE order_id = ?")
+                .bind(order_id)
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+        assert_eq!(status, "shipped");
     }
 
     // --- apply_change_items 統合テスト ---
@@ -2617,10 +4538,14 @@ mod tests {
                 quantity: 1,
                 subtotal: 1000,
                 image_url: None,
+                item_url: None,
+                release_date: None,
             }],
             subtotal: Some(1000),
             shipping_fee: None,
             total_amount: Some(1000),
+            tax_amount: None,
+            tax_included: true,
         };
 
         let result = repo
@@ -2691,10 +4616,14 @@ mod tests {
                 quantity: 1,
                 subtotal: 500,
                 image_url: None,
+                item_url: None,
+                release_date: None,
             }],
             subtotal: Some(500),
             shipping_fee: None,
             total_amount: Some(500),
+            tax_amount: None,
+            tax_included: true,
         };
 
         let result = repo
@@ -2730,10 +4659,14 @@ mod tests {
                 quantity: 1,
                 subtotal: 100,
                 image_url: None,
+                item_url: None,
+                release_date: None,
             }],
             subtotal: Some(100),
             shipping_fee: None,
             total_amount: Some(100),
+            tax_amount: None,
+            tax_included: true,
         };
 
         // マッチする注文がなくても Err は返さない（フォールバック設計）
@@ -2780,10 +4713,14 @@ mod tests {
                 quantity: 1,
                 subtotal: 1000,
                 image_url: None,
+                item_url: None,
+                release_date: None,
             }],
             subtotal: Some(1000),
             shipping_fee: None,
             total_amount: Some(1000),
+            tax_amount: None,
+            tax_included: true,
         };
 
         let result = repo
@@ -2867,10 +4804,14 @@ mod tests {
                 quantity: 2,
                 subtotal: 2000,
                 image_url: None,
+                item_url: None,
+                release_date: None,
             }],
             subtotal: Some(2000),
             shipping_fee: None,
             total_amount: Some(2000),
+            tax_amount: None,
+            tax_included: true,
         };
 
         let result = repo
@@ -2949,10 +4890,14 @@ mod tests {
                 quantity: 2,
                 subtotal: 2000,
                 image_url: None,
+                item_url: None,
+                release_date: None,
             }],
             subtotal: Some(2000),
             shipping_fee: None,
             total_amount: Some(2000),
+            tax_amount: None,
+            tax_included: true,
         };
 
         let result = repo
@@ -3051,10 +4996,14 @@ mod tests {
                 quantity: 2,
                 subtotal: 1600,
                 image_url: None,
+                item_url: None,
+                release_date: None,
             }],
             subtotal: Some(1600),
             shipping_fee: None,
             total_amount: Some(1600),
+            tax_amount: None,
+            tax_included: true,
         };
 
         let result = repo
@@ -3150,10 +5099,14 @@ mod tests {
                 quantity: 1,
                 subtotal: 4950,
                 image_url: None,
+                item_url: None,
+                release_date: None,
             }],
             subtotal: Some(4950),
             shipping_fee: None,
             total_amount: Some(4950),
+            tax_amount: None,
+            tax_included: true,
         };
 
         let result = repo
@@ -3223,10 +5176,14 @@ mod tests {
                 quantity: 1,
                 subtotal: 1000,
                 image_url: None,
+                item_url: None,
+                release_date: None,
             }],
             subtotal: Some(1000),
             shipping_fee: None,
             total_amount: Some(1000),
+            tax_amount: None,
+            tax_included: true,
         };
 
         let result = repo
@@ -3294,10 +5251,14 @@ mod tests {
                 quantity: 1,
                 subtotal: 500,
                 image_url: None,
+                item_url: None,
+                release_date: None,
             }],
             subtotal: Some(500),
             shipping_fee: None,
             total_amount: Some(500),
+            tax_amount: None,
+            tax_included: true,
         };
 
         let result = repo
@@ -3387,10 +5348,14 @@ mod tests {
                 quantity: 1,
                 subtotal: 300,
                 image_url: None,
+                item_url: None,
+                release_date: None,
             }],
             subtotal: Some(300),
             shipping_fee: None,
             total_amount: Some(300),
+            tax_amount: None,
+            tax_included: true,
         };
 
         let result = repo
@@ -3466,10 +5431,14 @@ mod tests {
                 quantity: 1,
                 subtotal: 500,
                 image_url: None,
+                item_url: None,
+                release_date: None,
             }],
             subtotal: Some(500),
             shipping_fee: None,
             total_amount: Some(500),
+            tax_amount: None,
+            tax_included: true,
         };
 
         let result = repo
@@ -3537,10 +5506,14 @@ mod tests {
                 quantity: 1,
                 subtotal: 800,
                 image_url: None,
+                item_url: None,
+                release_date: None,
             }],
             subtotal: Some(800),
             shipping_fee: None,
             total_amount: Some(800),
+            tax_amount: None,
+            tax_included: true,
         };
 
         let result = repo
@@ -3737,10 +5710,14 @@ mod tests {
                 quantity: 1,
                 subtotal: 800,
                 image_url: None,
+                item_url: None,
+                release_date: None,
             }],
             subtotal: Some(800),
             shipping_fee: None,
             total_amount: Some(800),
+            tax_amount: None,
+            tax_included: true,
         };
 
         let result = repo
@@ -3826,10 +5803,14 @@ mod tests {
                 quantity: 1,
                 subtotal: 1200,
                 image_url: None,
+                item_url: None,
+                release_date: None,
             }],
             subtotal: Some(1200),
             shipping_fee: None,
             total_amount: Some(1200),
+            tax_amount: None,
+            tax_included: true,
         };
 
         let result = repo
@@ -3870,6 +5851,232 @@ mod tests {
         assert_eq!(deliveries.0, 1);
     }
 
+    fn send_order_info(
+        order_number: &str,
+        tracking_number: &str,
+        items: &[(&str, i64)],
+    ) -> crate::parsers::OrderInfo {
+        crate::parsers::OrderInfo {
+            order_number: order_number.to_string(),
+            order_date: None,
+            delivery_address: None,
+            delivery_info: Some(crate::parsers::DeliveryInfo {
+                carrier: "佐川急便".to_string(),
+                tracking_number: tracking_number.to_string(),
+                delivery_date: None,
+                delivery_time: None,
+                carrier_url: None,
+                delivery_status: None,
+            }),
+            items: items
+                .iter()
+                .map(|(name, quantity)| crate::parsers::OrderItem {
+                    name: name.to_string(),
+                    manufacturer: None,
+                    model_number: None,
+                    unit_price: 1000,
+                    quantity: *quantity,
+                    subtotal: 1000 * quantity,
+                    image_url: None,
+                    item_url: None,
+                    release_date: None,
+                })
+                .collect(),
+            subtotal: None,
+            shipping_fee: None,
+            total_amount: None,
+            tax_amount: None,
+            tax_included: true,
+        }
+    }
+
+    /// (tracking_number, item_name, quantity) を追跡番号・商品名順で返す
+    async fn fetch_delivery_items(pool: &SqlitePool, order_id: i64) -> Vec<(String, String, i64)> {
+        sqlx::query_as(
+            r#"
+            SELECT d.tracking_number, i.item_name, di.quantity
+            FROM delivery_items di
+            JOIN deliveries d ON d.id = di.delivery_id
+            JOIN items i ON i.id = di.item_id
+            WHERE d.order_id = ?
+            ORDER BY d.tracking_number, i.item_name
+            "#,
+        )
+        .bind(order_id)
+        .fetch_all(pool)
+        .await
+        .expect("fetch delivery_items")
+    }
+
+    #[tokio::test]
+    async fn test_apply_send_matches_order_on_alternate_dmm_domain() {
+        let pool = setup_test_db().await;
+        let repo = SqliteOrderRepository::new(pool.clone());
+
+        // 注文確認は mono.dmm.com、発送通知は mail.dmm.com から届いたケース
+        let order_id: i64 = sqlx::query_scalar(
+            r#"INSERT INTO orders (order_number, shop_domain, shop_name) VALUES ('KC-22222', 'mono.dmm.com', 'DMM通販') RETURNING id"#,
+        )
+        .fetch_one(&pool)
+        .await
+        .expect("insert order");
+
+        let result_id = repo
+            .apply_send_and_replace_items(
+                &send_order_info("KC-22222", "364631890991", &[]),
+                None,
+                Some("mail.dmm.com".to_string()),
+                Some("DMM通販".to_string()),
+                Some(vec!["mono.dmm.com".to_string()]),
+            )
+            .await
+            .expect("apply send");
+        assert_eq!(result_id, order_id);
+
+        let order_count: (i64,) =
+            sqlx::query_as("SELECT COUNT(*) FROM orders WHERE order_number = 'KC-22222'")
+                .fetch_one(&pool)
+                .await
+                .expect("count orders");
+        assert_eq!(order_count.0, 1);
+
+        let deliveries: Vec<(String, String)> = sqlx::query_as(
+            "SELECT tracking_number, delivery_status FROM deliveries WHERE order_id = ?",
+        )
+        .bind(order_id)
+        .fetch_all(&pool)
+        .await
+        .expect("fetch deliveries");
+        assert_eq!(
+            deliveries,
+            vec![("364631890991".to_string(), "shipped".to_string())]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_apply_send_split_shipment_links_items_per_delivery() {
+        let pool = setup_test_db().await;
+        let repo = SqliteOrderRepository::new(pool.clone());
+
+        sqlx::query(
+            r#"INSERT INTO orders (order_number, shop_domain) VALUES ('BS-22222', 'mail.dmm.com')"#,
+        )
+        .execute(&pool)
+        .await
+        .expect("insert order");
+        let order_id: i64 =
+            sqlx::query_scalar("SELECT id FROM orders WHERE order_number = 'BS-22222'")
+                .fetch_one(&pool)
+                .await
+                .expect("get order id");
+        sqlx::query(
+            "INSERT INTO items (order_id, item_name, quantity, price) VALUES (?, '商品A', 1, 1000), (?, '商品B', 2, 1000)",
+        )
+        .bind(order_id)
+        .bind(order_id)
+        .execute(&pool)
+        .await
+        .expect("insert items");
+
+        // 1回目の発送: 商品A
+        repo.apply_send_and_replace_items(
+            &send_order_info("BS-22222", "111", &[("商品A", 1)]),
+            None,
+            Some("mail.dmm.com".to_string()),
+            None,
+            None,
+        )
+        .await
+        .expect("first send");
+        // 2回目の発送: 商品B
+        repo.apply_send_and_replace_items(
+            &send_order_info("BS-22222", "222", &[("商品B", 2)]),
+            None,
+            Some("mail.dmm.com".to_string()),
+            None,
+            None,
+        )
+        .await
+        .expect("second send");
+
+        // 1回目で発送済みの商品Aは2回目の発送メールで消えない
+        let item_names: Vec<String> =
+            sqlx::query_scalar("SELECT item_name FROM items WHERE order_id = ? ORDER BY item_name")
+                .bind(order_id)
+                .fetch_all(&pool)
+                .await
+                .expect("fetch items");
+        assert_eq!(item_names, vec!["商品A", "商品B"]);
+
+        assert_eq!(
+            fetch_delivery_items(&pool, order_id).await,
+            vec![
+                ("111".to_string(), "商品A".to_string(), 1),
+                ("222".to_string(), "商品B".to_string(), 2),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_apply_send_split_shipment_same_item_and_reparse() {
+        let pool = setup_test_db().await;
+        let repo = SqliteOrderRepository::new(pool.clone());
+
+        // 新規注文として作成される発送メール（同一商品を 1 個ずつ分割出荷）
+        let first = send_order_info("BS-33333", "111", &[("商品A", 1)]);
+        let order_id = repo
+            .apply_send_and_replace_items(
+                &first,
+                None,
+                Some("mail.dmm.com".to_string()),
+                None,
+                None,
+            )
+            .await
+            .expect("first send");
+        let second = send_order_info("BS-33333", "222", &[("商品A", 1)]);
+        repo.apply_send_and_replace_items(
+            &second,
+            None,
+            Some("mail.dmm.com".to_string()),
+            None,
+            None,
+        )
+        .await
+        .expect("second send");
+        // 同じ発送メールの再パースで重複しない
+        repo.apply_send_and_replace_items(
+            &second,
+            None,
+            Some("mail.dmm.com".to_string()),
+            None,
+            None,
+        )
+        .await
+        .expect("reparse second send");
+
+        let item_count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM items WHERE order_id = ?")
+            .bind(order_id)
+            .fetch_one(&pool)
+            .await
+            .expect("count items");
+        assert_eq!(item_count, 2);
+
+        let linked_item_count: i64 =
+            sqlx::query_scalar("SELECT COUNT(DISTINCT item_id) FROM delivery_items")
+                .fetch_one(&pool)
+                .await
+                .expect("count linked items");
+        assert_eq!(linked_item_count, 2);
+        assert_eq!(
+            fetch_delivery_items(&pool, order_id).await,
+            vec![
+                ("111".to_string(), "商品A".to_string(), 1),
+                ("222".to_string(), "商品A".to_string(), 1),
+            ]
+        );
+    }
+
     // --- item_names_match 単体テスト ---
 
     #[test]
@@ -3986,10 +6193,14 @@ mod tests {
                 quantity: 1,
                 subtotal: 5049,
                 image_url: None,
+                item_url: None,
+                release_date: None,
             }],
             subtotal: Some(5049),
             shipping_fee: None,
             total_amount: Some(5049),
+            tax_amount: None,
+            tax_included: true,
         };
 
         let result = repo
@@ -4012,4 +6223,197 @@ mod tests {
             "item should be removed from old order via product_master match"
         );
     }
+
+    fn trash_test_order_info(order_number: &str, item_names: &[&str]) -> OrderInfo {
+        OrderInfo {
+            order_number: order_number.to_string(),
+            order_date: Some("2024-01-01".to_string()),
+            delivery_address: None,
+            delivery_info: None,
+            items: item_names
+                .iter()
+                .map(|name| OrderItem {
+                    name: name.to_string(),
+                    manufacturer: None,
+                    model_number: None,
+                    unit_price: 1000,
+                    quantity: 1,
+                    subtotal: 1000,
+                    image_url: None,
+                    item_url: None,
+                    release_date: None,
+                })
+                .collect(),
+            subtotal: None,
+            shipping_fee: None,
+            total_amount: None,
+            tax_amount: None,
+            tax_included: true,
+        }
+    }
+
+    async fn insert_test_email(pool: &SqlitePool, message_id: &str) -> i64 {
+        sqlx::query_scalar(
+            "INSERT INTO emails (message_id, body_plain) VALUES (?, '') RETURNING id",
+        )
+        .bind(message_id)
+        .fetch_one(pool)
+        .await
+        .expect("insert email")
+    }
+
+    #[tokio::test]
+    async fn test_mail_for_trashed_order_does_not_update_it() {
+        let pool = setup_test_db().await;
+        let repo = SqliteOrderRepository::new(pool.clone());
+        let domain = Some("1999.co.jp".to_string());
+
+        let order_id = repo
+            .save_order(
+                &trash_test_order_info("99-8000-0001", &["商品A"]),
+                None,
+                domain.clone(),
+                None,
+            )
+            .await
+            .unwrap();
+        crate::repository::SqliteTrashRepository::new(pool.clone())
+            .delete_order(order_id)
+            .await
+            .unwrap();
+
+        // 同じ注文の後続メール: 新しい注文は作られず、商品も追加されない（メールの紐付けのみ）
+        let email_id = insert_test_email(&pool, "trashed-followup").await;
+        let saved_id = repo
+            .save_order(
+                &trash_test_order_info("99-8000-0001", &["商品A", "商品B"]),
+                Some(email_id),
+                domain.clone(),
+                None,
+            )
+            .await
+            .unwrap();
+        assert_eq!(saved_id, order_id);
+
+        let order_count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM orders")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!(order_count, 1);
+        let item_names: Vec<String> =
+            sqlx::query_scalar("SELECT item_name FROM items WHERE order_id = ?")
+                .bind(order_id)
+                .fetch_all(&pool)
+                .await
+                .unwrap();
+        assert_eq!(item_names, vec!["商品A"]);
+        let deleted: Option<String> =
+            sqlx::query_scalar("SELECT deleted_at FROM orders WHERE id = ?")
+                .bind(order_id)
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+        assert!(deleted.is_some());
+        let link_count: i64 = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM order_emails WHERE order_id = ? AND email_id = ?",
+        )
+        .bind(order_id)
+        .bind(email_id)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        assert_eq!(link_count, 1);
+
+        // キャンセルメール等の apply 系もゴミ箱内の注文は対象外
+        let cancel_email_id = insert_test_email(&pool, "trashed-cancel").await;
+        let cancel_info = CancelInfo {
+            order_number: "99-8000-0001".to_string(),
+            product_name: "商品A".to_string(),
+            cancel_quantity: 1,
+            reason: None,
+            reason_detail: None,
+        };
+        assert!(repo
+            .apply_cancel(&cancel_info, cancel_email_id, domain, None, None)
+            .await
+            .is_err());
+        let item_count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM items WHERE order_id = ?")
+            .bind(order_id)
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!(item_count, 1);
+    }
+
+    #[tokio::test]
+    async fn test_full_reparse_keeps_trash_state() {
+        use crate::repository::{ParseRepository, SqliteParseRepository, SqliteTrashRepository};
+
+        let pool = setup_test_db().await;
+        let repo = SqliteOrderRepository::new(pool.clone());
+        let trash = SqliteTrashRepository::new(pool.clone());
+        let domain = Some("1999.co.jp".to_string());
+        let order_a = trash_test_order_info("99-8100-0001", &["商品A", "商品B"]);
+        let order_b = trash_test_order_info("99-8100-0002", &["商品C"]);
+
+        let a_id = repo
+            .save_order(&order_a, None, domain.clone(), None)
+            .await
+            .unwrap();
+        let b_id = repo
+            .save_order(&order_b, None, domain.clone(), None)
+            .await
+            .unwrap();
+        let item_b_id: i64 =
+            sqlx::query_scalar("SELECT id FROM items WHERE order_id = ? AND item_name = '商品B'")
+                .bind(a_id)
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+        trash.delete_item(item_b_id).await.unwrap();
+        trash.delete_order(b_id).await.unwrap();
+
+        // 全件再パース: 注文関連テーブルを消してから同じメールを保存し直す
+        SqliteParseRepository::new(pool.clone())
+            .clear_order_tables()
+            .await
+            .unwrap();
+        repo.save_order(&order_a, None, domain.clone(), None)
+            .await
+            .unwrap();
+        repo.save_order(&order_b, None, domain.clone(), None)
+            .await
+            .unwrap();
+
+        let live_items: Vec<String> = sqlx::query_scalar(
+            "SELECT item_name FROM items WHERE deleted_at IS NULL ORDER BY item_name",
+        )
+        .fetch_all(&pool)
+        .await
+        .unwrap();
+        assert_eq!(live_items, vec!["商品A"]);
+        let live_orders: Vec<String> =
+            sqlx::query_scalar("SELECT order_number FROM orders WHERE deleted_at IS NULL")
+                .fetch_all(&pool)
+                .await
+                .unwrap();
+        assert_eq!(live_orders, vec!["99-8100-0001"]);
+
+        // 再登録した注文もゴミ箱から復元できる
+        let trashed = trash.list().await.unwrap();
+        assert_eq!(trashed.orders.len(), 1);
+        assert_eq!(trashed.orders[0].item_count, 1);
+        assert_eq!(trashed.items.len(), 1);
+        trash
+            .restore_order(trashed.orders[0].order_id)
+            .await
+            .unwrap();
+        trash.restore_item(trashed.items[0].item_id).await.unwrap();
+        let live_count: i64 =
+            sqlx::query_scalar("SELECT COUNT(*) FROM items WHERE deleted_at IS NULL")
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+        assert_eq!(live_count, 3);
+    }
 }
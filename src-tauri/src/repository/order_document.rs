@@ -0,0 +1,303 @@
+use sqlx::sqlite::SqlitePool;
+
+/// 印刷用注文書類の商品行
+#[derive(Debug, Clone, PartialEq)]
+pub struct OrderDocumentItem {
+    pub name: String,
+    pub price: i64,
+    pub quantity: i64,
+}
+
+/// 印刷用注文書類の配送情報
+#[derive(Debug, Clone, PartialEq)]
+pub struct OrderDocumentDelivery {
+    pub carrier: Option<String>,
+    pub tracking_number: Option<String>,
+    pub delivery_status: String,
+}
+
+/// 印刷用注文書類の関連メール
+#[derive(Debug, Clone, PartialEq)]
+pub struct OrderDocumentEmail {
+    pub subject: Option<String>,
+    /// 受信日時（ミリ秒Unix時刻）
+    pub internal_date: Option<i64>,
+}
+
+/// 問い合わせ・保証用に印刷する注文情報
+#[derive(Debug, Clone, PartialEq)]
+pub struct OrderDocument {
+    pub order_id: i64,
+    pub shop_name: Option<String>,
+    pub shop_domain: Option<String>,
+    pub order_number: Option<String>,
+    pub order_date: Option<String>,
+    pub tax_amount: Option<i64>,
+    pub tax_included: bool,
+    /// 送料・割引などの調整額
+    pub amount_adjustment: i64,
+    pub items: Vec<OrderDocumentItem>,
+    pub deliveries: Vec<OrderDocumentDelivery>,
+    /// 受信日時の古い順
+    pub emails: Vec<OrderDocumentEmail>,
+}
+
+impl OrderDocument {
+    /// 商品合計（税抜表示の注文では税抜）
+    pub fn items_amount(&self) -> i64 {
+        self.items.iter().map(|i| i.price * i.quantity).sum()
+    }
+
+    /// 税抜表示の注文で加算する消費税（記載がなければ商品合計の 10%）
+    pub fn added_tax(&self) -> i64 {
+        if self.tax_included {
+            0
+        } else {
+            self.tax_amount
+                .unwrap_or((self.items_amount() as f64 * 0.1) as i64)
+        }
+    }
+
+    /// 支払総額（統計と同じく商品合計 + 税 + 調整額）
+    pub fn total_amount(&self) -> i64 {
+        self.items_amount() + self.added_tax() + self.amount_adjustment
+    }
+}
+
+type OrderRow = (
+    Option<String>,
+    Option<String>,
+    Option<String>,
+    Option<String>,
+    Option<i64>,
+    bool,
+    i64,
+);
+
+/// 印刷用注文書類の読み込み
+pub struct SqliteOrderDocumentRepository {
+    pool: SqlitePool,
+}
+
+impl SqliteOrderDocumentRepository {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    /// 注文・商品・配送・関連メールをまとめて取得する（削除済みの注文は None）
+    pub async fn get(&self, order_id: i64) -> Result<Option<OrderDocument>, String> {
+        let order: Option<OrderRow> = sqlx::query_as(
+            r#"
+            SELECT shop_name, shop_domain, order_number, order_date,
+                   tax_amount, tax_included, amount_adjustment
+            FROM orders
+            WHERE id = ? AND deleted_at IS NULL
+            "#,
+        )
+        .bind(order_id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| format!("Failed to fetch order: {e}"))?;
+
+        let Some((
+            shop_name,
+            shop_domain,
+            order_number,
+            order_date,
+            tax_amount,
+            tax_included,
+            amount_adjustment,
+        )) = order
+        else {
+            return Ok(None);
+        };
+
+        let items: Vec<(String, i64, i64)> = sqlx::query_as(
+            r#"
+            SELECT item_name, price, quantity
+            FROM items
+            WHERE order_id = ? AND deleted_at IS NULL
+            ORDER BY id
+            "#,
+        )
+        .bind(order_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| format!("Failed to fetch items: {e}"))?;
+
+        let deliveries: Vec<(Option<String>, Option<String>, String)> = sqlx::query_as(
+            r#"
+            SELECT carrier, tracking_number, delivery_status
+            FROM deliveries
+            WHERE order_id = ?
+            ORDER BY id
+            "#,
+        )
+        .bind(order_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| format!("Failed to fetch deliveries: {e}"))?;
+
+        let emails: Vec<(Option<String>, Option<i64>)> = sqlx::query_as(
+            r#"
+            SELECT e.subject, e.internal_date
+            FROM order_emails oe
+            INNER JOIN emails e ON e.id = oe.email_id
+            WHERE oe.order_id = ?
+            ORDER BY e.internal_date IS NULL, e.internal_date, e.id
+            "#,
+        )
+        .bind(order_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| format!("Failed to fetch order emails: {e}"))?;
+
+        Ok(Some(OrderDocument {
+            order_id,
+            shop_name,
+            shop_domain,
+            order_number,
+            order_date,
+            tax_amount,
+            tax_included,
+            amount_adjustment,
+            items: items
+                .into_iter()
+                .map(|(name, price, quantity)| OrderDocumentItem {
+                    name,
+                    price,
+                    quantity,
+                })
+                .collect(),
+            deliveries: deliveries
+                .into_iter()
+                .map(
+                    |(carrier, tracking_number, delivery_status)| OrderDocumentDelivery {
+                        carrier,
+                        tracking_number,
+                        delivery_status,
+                    },
+                )
+                .collect(),
+            emails: emails
+                .into_iter()
+                .map(|(subject, internal_date)| OrderDocumentEmail {
+                    subject,
+                    internal_date,
+                })
+                .collect(),
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sqlx::sqlite::SqlitePoolOptions;
+
+    async fn setup_test_db() -> SqlitePool {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .expect("Failed to create test database");
+
+        sqlx::query(
+            r#"
+            CREATE TABLE orders (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                shop_domain TEXT,
+                shop_name TEXT,
+                order_number TEXT,
+                order_date DATETIME,
+                tax_amount INTEGER,
+                tax_included INTEGER NOT NULL DEFAULT 1,
+                amount_adjustment INTEGER NOT NULL DEFAULT 0,
+                deleted_at DATETIME
+            );
+            CREATE TABLE items (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                order_id INTEGER NOT NULL,
+                item_name TEXT NOT NULL,
+                price INTEGER NOT NULL DEFAULT 0,
+                quantity INTEGER NOT NULL DEFAULT 1,
+                deleted_at DATETIME
+            );
+            CREATE TABLE deliveries (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                order_id INTEGER NOT NULL,
+                tracking_number TEXT,
+                carrier TEXT,
+                delivery_status TEXT NOT NULL DEFAULT 'not_shipped'
+            );
+            CREATE TABLE emails (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                subject TEXT,
+                internal_date INTEGER
+            );
+            CREATE TABLE order_emails (
+                order_id INTEGER NOT NULL,
+                email_id INTEGER NOT NULL
+            );
+            "#,
+        )
+        .execute(&pool)
+        .await
+        .expect("Failed to create tables");
+
+        pool
+    }
+
+    #[tokio::test]
+    async fn test_get_order_document() {
+        let pool = setup_test_db().await;
+        sqlx::query(
+            r#"
+            INSERT INTO orders (id, shop_domain, shop_name, order_number, order_date, tax_amount, tax_included, amount_adjustment)
+            VALUES (1, 'example.com', 'テストショップ', 'ORD-1', '2024-05-01', 300, 0, 660);
+            INSERT INTO items (order_id, item_name, price, quantity, deleted_at) VALUES
+                (1, '商品A', 1000, 2, NULL),
+                (1, '商品B', 1000, 1, NULL),
+                (1, '削除済み', 500, 1, '2024-05-02');
+            INSERT INTO deliveries (order_id, tracking_number, carrier, delivery_status)
+            VALUES (1, '123456789012', 'ヤマト運輸', 'delivered');
+            INSERT INTO emails (id, subject, internal_date) VALUES
+                (1, '発送のお知らせ', 1714800000000),
+                (2, 'ご注文確認', 1714500000000);
+            INSERT INTO order_emails (order_id, email_id) VALUES (1, 1), (1, 2);
+            "#,
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let repo = SqliteOrderDocumentRepository::new(pool);
+        let doc = repo.get(1).await.unwrap().unwrap();
+        assert_eq!(doc.order_number.as_deref(), Some("ORD-1"));
+        assert_eq!(doc.items.len(), 2);
+        assert_eq!(doc.items_amount(), 3000);
+        assert_eq!(doc.total_amount(), 3000 + 300 + 660);
+        assert_eq!(
+            doc.deliveries[0].tracking_number.as_deref(),
+            Some("123456789012")
+        );
+        // 受信日時の古い順
+        assert_eq!(doc.emails[0].subject.as_deref(), Some("ご注文確認"));
+        assert_eq!(doc.emails[1].subject.as_deref(), Some("発送のお知らせ"));
+    }
+
+    #[tokio::test]
+    async fn test_get_deleted_or_missing_order_returns_none() {
+        let pool = setup_test_db().await;
+        sqlx::query(
+            "INSERT INTO orders (id, order_number, deleted_at) VALUES (1, 'X', '2024-01-01')",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let repo = SqliteOrderDocumentRepository::new(pool);
+        assert_eq!(repo.get(1).await.unwrap(), None);
+        assert_eq!(repo.get(2).await.unwrap(), None);
+    }
+}
@@ -0,0 +1,327 @@
+//! 注文の分割・統合（DMM のまとめ完了・分割完了など）の適用前後差分
+//!
+//! 適用前後に同じショップの注文・商品をスナップショットし、注文 ID 単位で比較する。
+//! 統合は先頭の旧注文の ID を引き継いで注文番号を書き換えるため、注文番号ではなく ID で突き合わせる。
+
+use serde::{Deserialize, Serialize};
+use sqlx::Sqlite;
+use std::collections::BTreeMap;
+use ts_rs::TS;
+
+/// スナップショット時点の商品
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct OrderSnapshotItem {
+    pub item_name: String,
+    pub price: i64,
+    pub quantity: i64,
+}
+
+/// スナップショット時点の注文
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct OrderSnapshot {
+    pub order_id: i64,
+    pub order_number: Option<String>,
+    pub order_date: Option<String>,
+    pub items: Vec<OrderSnapshotItem>,
+}
+
+impl OrderSnapshot {
+    fn total_quantity(&self) -> i64 {
+        self.items.iter().map(|i| i.quantity).sum()
+    }
+}
+
+/// 適用前後で内容が変わった注文
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct OrderSnapshotChange {
+    pub before: OrderSnapshot,
+    pub after: OrderSnapshot,
+}
+
+/// 分割・統合の適用前後差分レポート
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct OrderRestructureReport {
+    pub email_id: i64,
+    /// 適用したパーサー種別（`dmm_merge_complete` / `dmm_split_complete` / `pbandai_merge_complete`）
+    pub parser_type: String,
+    /// true ならシミュレーション（DB には反映していない）
+    pub dry_run: bool,
+    pub added_orders: Vec<OrderSnapshot>,
+    pub removed_orders: Vec<OrderSnapshot>,
+    pub changed_orders: Vec<OrderSnapshotChange>,
+    /// 検証で見つかった問題（商品数の不一致など）
+    pub warnings: Vec<String>,
+}
+
+/// 指定ドメインの注文と商品をスナップショットする（削除済みの注文・商品は除く）
+pub async fn snapshot_orders_in_tx(
+    tx: &mut sqlx::Transaction<'_, Sqlite>,
+    shop_domains: &[String],
+) -> Result<BTreeMap<i64, OrderSnapshot>, String> {
+    let mut snapshots: BTreeMap<i64, OrderSnapshot> = BTreeMap::new();
+    if shop_domains.is_empty() {
+        return Ok(snapshots);
+    }
+    let placeholders = vec!["?"; shop_domains.len()].join(", ");
+
+    let order_sql = format!(
+        r#"
+        SELECT id, order_number, order_date FROM orders
+        WHERE shop_domain IN ({placeholders}) AND deleted_at IS NULL
+        ORDER BY id
+        "#
+    );
+    let mut query = sqlx::query_as::<_, (i64, Option<String>, Option<String>)>(&order_sql);
+    for domain in shop_domains {
+        query = query.bind(domain);
+    }
+    let orders = query
+        .fetch_all(tx.as_mut())
+        .await
+        .map_err(|e| format!("Failed to snapshot orders: {e}"))?;
+    for (order_id, order_number, order_date) in orders {
+        snapshots.insert(
+            order_id,
+            OrderSnapshot {
+                order_id,
+                order_number,
+                order_date,
+                items: Vec::new(),
+            },
+        );
+    }
+
+    let item_sql = format!(
+        r#"
+        SELECT i.order_id, i.item_name, i.price, i.quantity
+        FROM items i
+        INNER JOIN orders o ON o.id = i.order_id
+        WHERE o.shop_domain IN ({placeholders})
+          AND o.deleted_at IS NULL
+          AND i.deleted_at IS NULL
+        ORDER BY i.order_id, i.id
+        "#
+    );
+    let mut query = sqlx::query_as::<_, (i64, String, i64, i64)>(&item_sql);
+    for domain in shop_domains {
+        query = query.bind(domain);
+    }
+    let items = query
+        .fetch_all(tx.as_mut())
+        .await
+        .map_err(|e| format!("Failed to snapshot items: {e}"))?;
+    for (order_id, item_name, price, quantity) in items {
+        if let Some(snapshot) = snapshots.get_mut(&order_id) {
+            snapshot.items.push(OrderSnapshotItem {
+                item_name,
+                price,
+                quantity,
+            });
+        }
+    }
+
+    Ok(snapshots)
+}
+
+/// 適用前後のスナップショットを比較してレポートを作る
+///
+/// 分割・統合は商品の付け替えなので、関係した注文の商品数の合計は前後で一致するはず。
+/// 一致しない場合や、商品のない注文が残った場合は `warnings` に記録する。
+pub fn diff_order_snapshots(
+    email_id: i64,
+    parser_type: &str,
+    dry_run: bool,
+    before: &BTreeMap<i64, OrderSnapshot>,
+    after: &BTreeMap<i64, OrderSnapshot>,
+) -> OrderRestructureReport {
+    let added_orders: Vec<OrderSnapshot> = after
+        .iter()
+        .filter(|(id, _)| !before.contains_key(id))
+        .map(|(_, o)| o.clone())
+        .collect();
+    let removed_orders: Vec<OrderSnapshot> = before
+        .iter()
+        .filter(|(id, _)| !after.contains_key(id))
+        .map(|(_, o)| o.clone())
+        .collect();
+    let changed_orders: Vec<OrderSnapshotChange> = before
+        .iter()
+        .filter_map(|(id, b)| {
+            let a = after.get(id)?;
+            (a != b).then(|| OrderSnapshotChange {
+                before: b.clone(),
+                after: a.clone(),
+            })
+        })
+        .collect();
+
+    let mut warnings = Vec::new();
+    let quantity_before: i64 = removed_orders
+        .iter()
+        .chain(changed_orders.iter().map(|c| &c.before))
+        .map(OrderSnapshot::total_quantity)
+        .sum();
+    let quantity_after: i64 = added_orders
+        .iter()
+        .chain(changed_orders.iter().map(|c| &c.after))
+        .map(OrderSnapshot::total_quantity)
+        .sum();
+    if quantity_before != quantity_after {
+        warnings.push(format!(
+            "商品数の合計が一致しません（適用前 {quantity_before} / 適用後 {quantity_after}）"
+        ));
+    }
+    for order in added_orders
+        .iter()
+        .chain(changed_orders.iter().map(|c| &c.after))
+        .filter(|o| o.items.is_empty())
+    {
+        warnings.push(format!(
+            "商品のない注文が残ります: {}",
+            order.order_number.as_deref().unwrap_or("(注文番号なし)")
+        ));
+    }
+    if added_orders.is_empty() && removed_orders.is_empty() && changed_orders.is_empty() {
+        warnings.push(
+            "注文・商品に変化がありません（対象の注文が見つからない可能性があります）".to_string(),
+        );
+    }
+
+    OrderRestructureReport {
+        email_id,
+        parser_type: parser_type.to_string(),
+        dry_run,
+        added_orders,
+        removed_orders,
+        changed_orders,
+        warnings,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sqlx::sqlite::{SqlitePool, SqlitePoolOptions};
+
+    fn snapshot(order_id: i64, order_number: &str, items: &[(&str, i64)]) -> OrderSnapshot {
+        OrderSnapshot {
+            order_id,
+            order_number: Some(order_number.to_string()),
+            order_date: None,
+            items: items
+                .iter()
+                .map(|(name, quantity)| OrderSnapshotItem {
+                    item_name: name.to_string(),
+                    price: 1000,
+                    quantity: *quantity,
+                })
+                .collect(),
+        }
+    }
+
+    fn to_map(snapshots: Vec<OrderSnapshot>) -> BTreeMap<i64, OrderSnapshot> {
+        snapshots.into_iter().map(|s| (s.order_id, s)).collect()
+    }
+
+    #[test]
+    fn test_diff_merge() {
+        let before = to_map(vec![
+            snapshot(1, "KC-1", &[("商品A", 1)]),
+            snapshot(2, "KC-2", &[("商品B", 2)]),
+            snapshot(3, "KC-3", &[("無関係", 1)]),
+        ]);
+        let after = to_map(vec![
+            snapshot(1, "KC-9", &[("商品A", 1), ("商品B", 2)]),
+            snapshot(3, "KC-3", &[("無関係", 1)]),
+        ]);
+
+        let report = diff_order_snapshots(10, "dmm_merge_complete", true, &before, &after);
+        assert!(report.added_orders.is_empty());
+        assert_eq!(report.removed_orders.len(), 1);
+        assert_eq!(
+            report.removed_orders[0].order_number.as_deref(),
+            Some("KC-2")
+        );
+        assert_eq!(report.changed_orders.len(), 1);
+        assert_eq!(
+            report.changed_orders[0].after.order_number.as_deref(),
+            Some("KC-9")
+        );
+        assert!(report.warnings.is_empty());
+    }
+
+    #[test]
+    fn test_diff_split_warns_on_quantity_mismatch() {
+        let before = to_map(vec![snapshot(1, "KS-1", &[("商品A", 1), ("商品B", 1)])]);
+        let after = to_map(vec![
+            snapshot(1, "KS-1", &[("商品A", 1)]),
+            snapshot(2, "KS-2", &[]),
+        ]);
+
+        let report = diff_order_snapshots(10, "dmm_split_complete", false, &before, &after);
+        assert_eq!(report.added_orders.len(), 1);
+        assert_eq!(report.changed_orders.len(), 1);
+        assert_eq!(report.warnings.len(), 2);
+        assert!(report.warnings[0].contains("適用前 2 / 適用後 1"));
+        assert!(report.warnings[1].contains("KS-2"));
+    }
+
+    #[test]
+    fn test_diff_without_changes_warns() {
+        let before = to_map(vec![snapshot(1, "KC-1", &[("商品A", 1)])]);
+        let report = diff_order_snapshots(10, "dmm_merge_complete", true, &before, &before);
+        assert_eq!(report.warnings.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_snapshot_orders_in_tx() {
+        let pool: SqlitePool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .unwrap();
+        sqlx::query(
+            r#"
+            CREATE TABLE orders (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                shop_domain TEXT,
+                order_number TEXT,
+                order_date DATETIME,
+                deleted_at DATETIME
+            );
+            CREATE TABLE items (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                order_id INTEGER NOT NULL,
+                item_name TEXT NOT NULL,
+                price INTEGER NOT NULL DEFAULT 0,
+                quantity INTEGER NOT NULL DEFAULT 1,
+                deleted_at DATETIME
+            );
+            INSERT INTO orders (id, shop_domain, order_number, deleted_at) VALUES
+                (1, 'mail.dmm.com', 'KC-1', NULL),
+                (2, 'mono.dmm.com', 'KC-2', NULL),
+                (3, 'mail.dmm.com', 'KC-3', '2025-01-01'),
+                (4, 'example.com', 'EX-1', NULL);
+            INSERT INTO items (order_id, item_name, price, quantity, deleted_at) VALUES
+                (1, '商品A', 1000, 1, NULL),
+                (1, '削除済み', 1000, 1, '2025-01-01'),
+                (2, '商品B', 2000, 2, NULL),
+                (4, '他店', 500, 1, NULL);
+            "#,
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let mut tx = pool.begin().await.unwrap();
+        let domains = vec!["mail.dmm.com".to_string(), "mono.dmm.com".to_string()];
+        let snapshots = snapshot_orders_in_tx(&mut tx, &domains).await.unwrap();
+        assert_eq!(snapshots.keys().copied().collect::<Vec<_>>(), vec![1, 2]);
+        assert_eq!(snapshots[&1].items.len(), 1);
+        assert_eq!(snapshots[&2].items[0].quantity, 2);
+    }
+}
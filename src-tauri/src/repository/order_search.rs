@@ -0,0 +1,669 @@
+//! 複合条件による注文検索とスマートフィルタ
+//!
+//! 「2024年に買った・バンダイ製・1/144・未発送・1万円以上」のような条件を `OrderQuery`（JSON）で受け取り、
+//! 値はすべてバインドパラメータとして SQL を組み立てる。
+//! 商品に関する条件（メーカー・スケール・キーワード）は同じ商品に対して同時に満たす必要がある。
+//! 検索条件には名前を付けてスマートフィルタとして保存できる。
+
+use serde::{Deserialize, Serialize};
+use sqlx::sqlite::{Sqlite, SqlitePool};
+use sqlx::QueryBuilder;
+use ts_rs::TS;
+
+/// 検索結果の取得件数（省略時）
+pub const DEFAULT_ORDER_SEARCH_LIMIT: i64 = 500;
+
+/// 検索結果の取得件数の上限
+const MAX_ORDER_SEARCH_LIMIT: i64 = 5000;
+
+/// 配送状況の条件に指定できる値（deliveries.delivery_status の値域）
+const DELIVERY_STATUSES: &[&str] = &[
+    "not_shipped",
+    "preparing",
+    "shipped",
+    "in_transit",
+    "out_for_delivery",
+    "delivered",
+    "failed",
+    "returned",
+    "cancelled",
+];
+
+/// 検索結果の並び順
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(export)]
+pub enum OrderSearchSort {
+    /// 注文日の新しい順
+    #[default]
+    OrderDateDesc,
+    /// 注文日の古い順
+    OrderDateAsc,
+    /// 合計金額の高い順
+    TotalDesc,
+    /// 合計金額の安い順
+    TotalAsc,
+}
+
+impl OrderSearchSort {
+    fn order_by(&self) -> &'static str {
+        match self {
+            Self::OrderDateDesc => "COALESCE(o.order_date, o.created_at) DESC, o.id DESC",
+            Self::OrderDateAsc => "COALESCE(o.order_date, o.created_at) ASC, o.id ASC",
+            Self::TotalDesc => "total_amount DESC, o.id DESC",
+            Self::TotalAsc => "total_amount ASC, o.id ASC",
+        }
+    }
+}
+
+/// 注文検索の条件（指定したものをすべて満たす注文を返す。未指定の条件は無視する）
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(default)]
+#[ts(export)]
+pub struct OrderQuery {
+    /// 注文年（例: 2024）
+    pub year: Option<i32>,
+    /// 注文日の下限（YYYY-MM-DD、当日を含む）
+    pub order_date_from: Option<String>,
+    /// 注文日の上限（YYYY-MM-DD、当日を含む）
+    pub order_date_to: Option<String>,
+    /// ショップ名（いずれかに一致）
+    pub shop_names: Vec<String>,
+    /// メーカー（product_master.maker または items.brand の部分一致）
+    pub maker: Option<String>,
+    /// スケール（例: "1/144"。product_master.scale の一致または商品名の部分一致）
+    pub scale: Option<String>,
+    /// 商品名の部分一致
+    pub keyword: Option<String>,
+    /// 最新の配送状況（いずれかに一致。"not_shipped" は配送情報のない注文も含む）
+    pub delivery_statuses: Vec<String>,
+    /// 合計金額の下限（円、含む）
+    pub min_total: Option<i64>,
+    /// 合計金額の上限（円、含む）
+    pub max_total: Option<i64>,
+    pub sort: OrderSearchSort,
+    /// 取得件数（省略時は 500 件）
+    pub limit: Option<i64>,
+}
+
+fn non_empty(value: &Option<String>) -> Option<&str> {
+    value.as_deref().map(str::trim).filter(|v| !v.is_empty())
+}
+
+fn validate_date(label: &str, value: &str) -> Result<(), String> {
+    chrono::NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .map(|_| ())
+        .map_err(|_| format!("Invalid {label} (expected YYYY-MM-DD): {value}"))
+}
+
+impl OrderQuery {
+    /// 条件の値を検証する
+    pub fn validate(&self) -> Result<(), String> {
+        if let Some(year) = self.year {
+            if !(1900..=9999).contains(&year) {
+                return Err(format!("Invalid year: {year}"));
+            }
+        }
+        if let Some(from) = non_empty(&self.order_date_from) {
+            validate_date("order_date_from", from)?;
+        }
+        if let Some(to) = non_empty(&self.order_date_to) {
+            validate_date("order_date_to", to)?;
+        }
+        if let Some(status) = self
+            .delivery_statuses
+            .iter()
+            .find(|s| !DELIVERY_STATUSES.contains(&s.as_str()))
+        {
+            return Err(format!("Invalid delivery status: {status}"));
+        }
+        if let (Some(min), Some(max)) = (self.min_total, self.max_total) {
+            if min > max {
+                return Err(format!(
+                    "min_total must not exceed max_total: {min} > {max}"
+                ));
+            }
+        }
+        if let Some(limit) = self.limit {
+            if !(1..=MAX_ORDER_SEARCH_LIMIT).contains(&limit) {
+                return Err(format!(
+                    "limit must be between 1 and {MAX_ORDER_SEARCH_LIMIT}: {limit}"
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    fn has_item_conditions(&self) -> bool {
+        non_empty(&self.maker).is_some()
+            || non_empty(&self.scale).is_some()
+            || non_empty(&self.keyword).is_some()
+    }
+
+    /// 検索用の SQL を組み立てる（値はすべてバインドパラメータ）
+    fn build_sql(&self) -> QueryBuilder<'_, Sqlite> {
+        // 合計金額は月別集計（stats）と同じく、税抜注文の税額と送料・割引などの調整額を含めて算出する
+        let mut qb = QueryBuilder::new(
+            r#"
+            WITH latest_delivery AS (
+                SELECT order_id, delivery_status
+                FROM (
+                    SELECT order_id, delivery_status,
+                           ROW_NUMBER() OVER (PARTITION BY order_id ORDER BY updated_at DESC, id DESC) AS rn
+                    FROM deliveries
+                ) t
+                WHERE rn = 1
+            ),
+            order_totals AS (
+                SELECT
+                    order_id,
+                    items_amount
+                        + CASE WHEN tax_included = 0
+                               THEN COALESCE(tax_amount, CAST(items_amount * 0.1 AS INTEGER))
+                               ELSE 0 END
+                        + amount_adjustment AS total_amount,
+                    item_count
+                FROM (
+                    SELECT
+                        o.id AS order_id,
+                        o.tax_amount,
+                        o.tax_included,
+                        o.amount_adjustment,
+                        COALESCE(SUM(i.price * i.quantity), 0) AS items_amount,
+                        COUNT(i.id) AS item_count
+                    FROM orders o
+                    LEFT JOIN items i ON i.order_id = o.id AND i.deleted_at IS NULL
+                    GROUP BY o.id
+                ) a
+            )
+            SELECT
+                o.id AS order_id,
+                COALESCE(o.shop_name, o.shop_domain) AS shop_name,
+                o.order_number,
+                o.order_date,
+                COALESCE(t.total_amount, 0) AS total_amount,
+                COALESCE(t.item_count, 0) AS item_count,
+                ld.delivery_status
+            FROM orders o
+            LEFT JOIN order_totals t ON t.order_id = o.id
+            LEFT JOIN latest_delivery ld ON ld.order_id = o.id
+            WHERE o.deleted_at IS NULL
+            "#,
+        );
+
+        if let Some(year) = self.year {
+            qb.push(" AND strftime('%Y', o.order_date) = ")
+                .push_bind(format!("{year:04}"));
+        }
+        if let Some(from) = non_empty(&self.order_date_from) {
+            qb.push(" AND date(o.order_date) >= ").push_bind(from);
+        }
+        if let Some(to) = non_empty(&self.order_date_to) {
+            qb.push(" AND date(o.order_date) <= ").push_bind(to);
+        }
+        if !self.shop_names.is_empty() {
+            qb.push(" AND COALESCE(o.shop_name, o.shop_domain) IN (");
+            let mut separated = qb.separated(", ");
+            for name in &self.shop_names {
+                separated.push_bind(name.as_str());
+            }
+            qb.push(")");
+        }
+        if self.has_item_conditions() {
+            qb.push(
+                " AND EXISTS (SELECT 1 FROM items i \
+                 LEFT JOIN product_master pm ON TRIM(i.item_name) = pm.raw_name \
+                 WHERE i.order_id = o.id AND i.deleted_at IS NULL",
+            );
+            if let Some(maker) = non_empty(&self.maker) {
+                qb.push(" AND (instr(lower(COALESCE(pm.maker, '')), lower(")
+                    .push_bind(maker)
+                    .push(")) > 0 OR instr(lower(COALESCE(i.brand, '')), lower(")
+                    .push_bind(maker)
+                    .push(")) > 0)");
+            }
+            if let Some(scale) = non_empty(&self.scale) {
+                qb.push(" AND (pm.scale = ")
+                    .push_bind(scale)
+                    .push(" OR instr(i.item_name, ")
+                    .push_bind(scale)
+                    .push(") > 0)");
+            }
+            if let Some(keyword) = non_empty(&self.keyword) {
+                qb.push(" AND instr(lower(i.item_name), lower(")
+                    .push_bind(keyword)
+                    .push(")) > 0");
+            }
+            qb.push(")");
+        }
+        if !self.delivery_statuses.is_empty() {
+            qb.push(" AND (");
+            if self.delivery_statuses.iter().any(|s| s == "not_shipped") {
+                qb.push("ld.delivery_status IS NULL OR ");
+            }
+            qb.push("ld.delivery_status IN (");
+            let mut separated = qb.separated(", ");
+            for status in &self.delivery_statuses {
+                separated.push_bind(status.as_str());
+            }
+            qb.push("))");
+        }
+        if let Some(min) = self.min_total {
+            qb.push(" AND COALESCE(t.total_amount, 0) >= ")
+                .push_bind(min);
+        }
+        if let Some(max) = self.max_total {
+            qb.push(" AND COALESCE(t.total_amount, 0) <= ")
+                .push_bind(max);
+        }
+
+        qb.push(" ORDER BY ").push(self.sort.order_by());
+        qb.push(" LIMIT ")
+            .push_bind(self.limit.unwrap_or(DEFAULT_ORDER_SEARCH_LIMIT));
+        qb
+    }
+}
+
+/// 注文検索の結果（1 注文 1 行）
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, sqlx::FromRow, TS)]
+#[ts(export)]
+pub struct OrderSearchResult {
+    pub order_id: i64,
+    pub shop_name: Option<String>,
+    pub order_number: Option<String>,
+    pub order_date: Option<String>,
+    /// 商品の合計金額（単価 × 数量）
+    pub total_amount: i64,
+    pub item_count: i64,
+    /// 最新の配送状況（配送情報がなければ None）
+    pub delivery_status: Option<String>,
+}
+
+/// 保存した検索条件
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct SmartFilter {
+    pub id: i64,
+    pub name: String,
+    pub query: OrderQuery,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+/// 注文検索・スマートフィルタのDB操作
+pub struct SqliteOrderSearchRepository {
+    pool: SqlitePool,
+}
+
+impl SqliteOrderSearchRepository {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    /// 条件に一致する注文を返す
+    pub async fn search(&self, query: &OrderQuery) -> Result<Vec<OrderSearchResult>, String> {
+        query.validate()?;
+        let mut qb = query.build_sql();
+        qb.build_query_as::<OrderSearchResult>()
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| format!("Failed to search orders: {e}"))
+    }
+
+    /// スマートフィルタの一覧を名前順に返す
+    pub async fn list_filters(&self) -> Result<Vec<SmartFilter>, String> {
+        let rows: Vec<(i64, String, String, String, String)> = sqlx::query_as(
+            "SELECT id, name, query_json, created_at, updated_at FROM smart_filters ORDER BY name",
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| format!("Failed to fetch smart filters: {e}"))?;
+
+        rows.into_iter()
+            .map(|(id, name, query_json, created_at, updated_at)| {
+                let query = serde_json::from_str(&query_json)
+                    .map_err(|e| format!("Invalid smart filter '{name}': {e}"))?;
+                Ok(SmartFilter {
+                    id,
+                    name,
+                    query,
+                    created_at,
+                    updated_at,
+                })
+            })
+            .collect()
+    }
+
+    /// 検索条件を名前を付けて保存し、smart_filters.id を返す（同名のフィルタは上書きする）
+    pub async fn save_filter(&self, name: &str, query: &OrderQuery) -> Result<i64, String> {
+        let name = name.trim();
+        if name.is_empty() {
+            return Err("Smart filter name must not be empty".to_string());
+        }
+        query.validate()?;
+        let query_json = serde_json::to_string(query)
+            .map_err(|e| format!("Failed to serialize order query: {e}"))?;
+
+        sqlx::query_scalar(
+            r#"
+            INSERT INTO smart_filters (name, query_json) VALUES (?, ?)
+            ON CONFLICT(name) DO UPDATE SET query_json = excluded.query_json
+            RETURNING id
+            "#,
+        )
+        .bind(name)
+        .bind(query_json)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| format!("Failed to save smart filter: {e}"))
+    }
+
+    /// スマートフィルタを削除する
+    pub async fn delete_filter(&self, id: i64) -> Result<(), String> {
+        let result = sqlx::query("DELETE FROM smart_filters WHERE id = ?")
+            .bind(id)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| format!("Failed to delete smart filter: {e}"))?;
+        if result.rows_affected() == 0 {
+            return Err(format!("Smart filter not found: {id}"));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sqlx::sqlite::SqlitePoolOptions;
+
+    async fn setup_test_db() -> SqlitePool {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .expect("Failed to create test database");
+
+        sqlx::query(
+            r#"
+            CREATE TABLE orders (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                shop_domain TEXT,
+                shop_name TEXT,
+                order_number TEXT,
+                order_date DATETIME,
+                tax_amount INTEGER,
+                tax_included INTEGER NOT NULL DEFAULT 1,
+                amount_adjustment INTEGER NOT NULL DEFAULT 0,
+                deleted_at DATETIME,
+                created_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP
+            );
+            CREATE TABLE items (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                order_id INTEGER NOT NULL,
+                item_name TEXT NOT NULL,
+                price INTEGER NOT NULL DEFAULT 0,
+                quantity INTEGER NOT NULL DEFAULT 1,
+                brand TEXT,
+                deleted_at DATETIME
+            );
+            CREATE TABLE deliveries (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                order_id INTEGER NOT NULL,
+                delivery_status TEXT NOT NULL DEFAULT 'not_shipped',
+                updated_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP
+            );
+            CREATE TABLE product_master (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                raw_name TEXT UNIQUE NOT NULL,
+                maker TEXT,
+                scale TEXT
+            );
+            CREATE TABLE smart_filters (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                name TEXT NOT NULL UNIQUE,
+                query_json TEXT NOT NULL,
+                created_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+                updated_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP
+            );
+
+            INSERT INTO orders (id, shop_name, order_number, order_date) VALUES
+                (1, 'あみあみ', 'A-1', '2024-03-10 10:00:00'),
+                (2, 'あみあみ', 'A-2', '2024-05-01 10:00:00'),
+                (3, 'ホビーサーチ', 'H-1', '2023-12-20 10:00:00'),
+                (4, 'あみあみ', 'A-3', '2024-06-01 10:00:00');
+            UPDATE orders SET deleted_at = '2024-07-01 00:00:00' WHERE id = 4;
+            INSERT INTO items (order_id, item_name, price, quantity, brand) VALUES
+                (1, 'HG 1/144 ガンダムエアリアル', 6000, 2, NULL),
+                (2, 'ねんどろいど 初音ミク', 5000, 1, 'グッドスマイルカンパニー'),
+                (2, 'MG 1/100 ザク', 4000, 1, 'BANDAI SPIRITS'),
+                (3, 'HG 1/144 ザク', 15000, 1, NULL),
+                (4, 'HG 1/144 ジム', 20000, 1, NULL);
+            INSERT INTO product_master (raw_name, maker, scale) VALUES
+                ('HG 1/144 ガンダムエアリアル', 'バンダイ', '1/144'),
+                ('HG 1/144 ザク', 'バンダイ', '1/144');
+            INSERT INTO deliveries (order_id, delivery_status, updated_at) VALUES
+                (1, 'not_shipped', '2024-03-10 10:00:00'),
+                (2, 'not_shipped', '2024-05-01 10:00:00'),
+                (2, 'delivered', '2024-05-10 10:00:00'),
+                (3, 'shipped', '2023-12-25 10:00:00');
+            "#,
+        )
+        .execute(&pool)
+        .await
+        .expect("Failed to create tables");
+
+        pool
+    }
+
+    fn order_ids(results: &[OrderSearchResult]) -> Vec<i64> {
+        results.iter().map(|r| r.order_id).collect()
+    }
+
+    #[tokio::test]
+    async fn test_search_compound_conditions() {
+        let repo = SqliteOrderSearchRepository::new(setup_test_db().await);
+
+        // 2024年・バンダイ製・1/144・未発送・1万円以上
+        let query = OrderQuery {
+            year: Some(2024),
+            maker: Some("バンダイ".to_string()),
+            scale: Some("1/144".to_string()),
+            delivery_statuses: vec!["not_shipped".to_string()],
+            min_total: Some(10000),
+            ..Default::default()
+        };
+        let results = repo.search(&query).await.unwrap();
+        assert_eq!(order_ids(&results), vec![1]);
+        assert_eq!(results[0].total_amount, 12000);
+        assert_eq!(results[0].item_count, 1);
+        assert_eq!(results[0].delivery_status.as_deref(), Some("not_shipped"));
+
+        // 条件なしは削除済みを除く全件（新しい順）
+        let results = repo.search(&OrderQuery::default()).await.unwrap();
+        assert_eq!(order_ids(&results), vec![2, 1, 3]);
+    }
+
+    #[tokio::test]
+    async fn test_search_item_conditions_apply_to_same_item() {
+        let repo = SqliteOrderSearchRepository::new(setup_test_db().await);
+
+        // 注文2はグッドスマイル製の商品と 1/100 の商品を含むが、同じ商品ではない
+        let query = OrderQuery {
+            maker: Some("グッドスマイル".to_string()),
+            scale: Some("1/100".to_string()),
+            ..Default::default()
+        };
+        assert!(repo.search(&query).await.unwrap().is_empty());
+
+        // brand の部分一致（大文字小文字を区別しない）
+        let query = OrderQuery {
+            maker: Some("bandai".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(order_ids(&repo.search(&query).await.unwrap()), vec![2]);
+    }
+
+    #[tokio::test]
+    async fn test_search_shop_date_range_and_sort() {
+        let repo = SqliteOrderSearchRepository::new(setup_test_db().await);
+
+        let query = OrderQuery {
+            shop_names: vec!["あみあみ".to_string(), "ホビーサーチ".to_string()],
+            order_date_from: Some("2023-12-20".to_string()),
+            order_date_to: Some("2024-03-10".to_string()),
+            sort: OrderSearchSort::TotalAsc,
+            ..Default::default()
+        };
+        assert_eq!(order_ids(&repo.search(&query).await.unwrap()), vec![1, 3]);
+
+        let query = OrderQuery {
+            delivery_statuses: vec!["delivered".to_string(), "shipped".to_string()],
+            sort: OrderSearchSort::OrderDateAsc,
+            ..Default::default()
+        };
+        assert_eq!(order_ids(&repo.search(&query).await.unwrap()), vec![3, 2]);
+    }
+
+    #[tokio::test]
+    async fn test_search_total_includes_tax_and_adjustment() {
+        let pool = setup_test_db().await;
+        // 注文1: 税抜 12000 円（税額未記載 → 10%）+ 送料 800 円 = 14000 円
+        // 注文3: 税込 15000 円 - 割引 2000 円 = 13000 円
+        sqlx::query(
+            r#"
+            UPDATE orders SET tax_included = 0, amount_adjustment = 800 WHERE id = 1;
+            UPDATE orders SET amount_adjustment = -2000 WHERE id = 3;
+            "#,
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+        let repo = SqliteOrderSearchRepository::new(pool);
+
+        let query = OrderQuery {
+            sort: OrderSearchSort::TotalDesc,
+            ..Default::default()
+        };
+        let results = repo.search(&query).await.unwrap();
+        assert_eq!(order_ids(&results), vec![1, 3, 2]);
+        assert_eq!(results[0].total_amount, 14000);
+        assert_eq!(results[1].total_amount, 13000);
+        assert_eq!(results[2].total_amount, 9000);
+
+        let query = OrderQuery {
+            min_total: Some(13500),
+            ..Default::default()
+        };
+        assert_eq!(order_ids(&repo.search(&query).await.unwrap()), vec![1]);
+
+        let query = OrderQuery {
+            max_total: Some(13000),
+            sort: OrderSearchSort::TotalAsc,
+            ..Default::default()
+        };
+        assert_eq!(order_ids(&repo.search(&query).await.unwrap()), vec![2, 3]);
+    }
+
+    #[tokio::test]
+    async fn test_search_latest_delivery_breaks_tie_by_id() {
+        let pool = setup_test_db().await;
+        // 同時刻に更新された配送は id が大きい方を最新とする
+        sqlx::query(
+            "INSERT INTO deliveries (order_id, delivery_status, updated_at) VALUES (1, 'shipped', '2024-03-10 10:00:00')",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+        let repo = SqliteOrderSearchRepository::new(pool);
+
+        let query = OrderQuery {
+            delivery_statuses: vec!["shipped".to_string()],
+            sort: OrderSearchSort::OrderDateAsc,
+            ..Default::default()
+        };
+        let results = repo.search(&query).await.unwrap();
+        assert_eq!(order_ids(&results), vec![3, 1]);
+        assert_eq!(results[1].delivery_status.as_deref(), Some("shipped"));
+    }
+
+    #[tokio::test]
+    async fn test_search_rejects_invalid_query() {
+        let repo = SqliteOrderSearchRepository::new(setup_test_db().await);
+        let invalid = [
+            OrderQuery {
+                delivery_statuses: vec!["shipped' OR 1=1 --".to_string()],
+                ..Default::default()
+            },
+            OrderQuery {
+                order_date_from: Some("2024/01/01".to_string()),
+                ..Default::default()
+            },
+            OrderQuery {
+                min_total: Some(10000),
+                max_total: Some(100),
+                ..Default::default()
+            },
+            OrderQuery {
+                limit: Some(0),
+                ..Default::default()
+            },
+        ];
+        for query in &invalid {
+            assert!(repo.search(query).await.is_err(), "{query:?}");
+        }
+
+        // 値はバインドされるため、SQL として解釈されない
+        let query = OrderQuery {
+            keyword: Some("' OR 1=1 --".to_string()),
+            ..Default::default()
+        };
+        assert!(repo.search(&query).await.unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_order_query_deserialize_partial_json() {
+        let query: OrderQuery =
+            serde_json::from_str(r#"{"year": 2024, "scale": "1/144", "sort": "total_desc"}"#)
+                .unwrap();
+        assert_eq!(query.year, Some(2024));
+        assert_eq!(query.scale.as_deref(), Some("1/144"));
+        assert_eq!(query.sort, OrderSearchSort::TotalDesc);
+        assert!(query.shop_names.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_smart_filter_save_list_delete() {
+        let repo = SqliteOrderSearchRepository::new(setup_test_db().await);
+        let query = OrderQuery {
+            year: Some(2024),
+            delivery_statuses: vec!["not_shipped".to_string()],
+            ..Default::default()
+        };
+
+        let id = repo.save_filter(" 2024年の未発送 ", &query).await.unwrap();
+        let filters = repo.list_filters().await.unwrap();
+        assert_eq!(filters.len(), 1);
+        assert_eq!(filters[0].name, "2024年の未発送");
+        assert_eq!(filters[0].query, query);
+
+        // 同名で保存すると上書き
+        let updated = OrderQuery {
+            year: Some(2023),
+            ..query.clone()
+        };
+        assert_eq!(
+            repo.save_filter("2024年の未発送", &updated).await.unwrap(),
+            id
+        );
+        let filters = repo.list_filters().await.unwrap();
+        assert_eq!(filters.len(), 1);
+        assert_eq!(filters[0].query.year, Some(2023));
+
+        assert!(repo.save_filter("  ", &query).await.is_err());
+
+        repo.delete_filter(id).await.unwrap();
+        assert!(repo.list_filters().await.unwrap().is_empty());
+        assert!(repo.delete_filter(id).await.is_err());
+    }
+}
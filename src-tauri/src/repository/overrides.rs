@@ -1,5 +1,6 @@
 use serde::{Deserialize, Serialize};
 use sqlx::sqlite::SqlitePool;
+use ts_rs::TS;
 
 // NOTE: Clippy (type_complexity) 対応
 // `sqlx::query_as` で使用する巨大タプル型を type alias にして可読性を保つ。
@@ -30,7 +31,8 @@ type OrderOverrideDbRow = (
 type ExcludedItemDbRow = (i64, String, String, String, String, Option<String>, String);
 
 /// アイテム上書き保存パラメータ
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
 pub struct SaveItemOverride {
     pub shop_domain: String,
     pub order_number: String,
@@ -44,7 +46,8 @@ pub struct SaveItemOverride {
 }
 
 /// アイテム上書きレコード
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
 pub struct ItemOverride {
     pub id: i64,
     pub shop_domain: String,
@@ -61,7 +64,8 @@ pub struct ItemOverride {
 }
 
 /// 注文上書き保存パラメータ
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
 pub struct SaveOrderOverride {
     pub shop_domain: String,
     pub order_number: String,
@@ -71,7 +75,8 @@ pub struct SaveOrderOverride {
 }
 
 /// 注文上書きレコード
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
 pub struct OrderOverride {
     pub id: i64,
     pub shop_domain: String,
@@ -84,7 +89,8 @@ pub struct OrderOverride {
 }
 
 /// アイテム除外パラメータ
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
 pub struct ExcludeItemParams {
     pub shop_domain: String,
     pub order_number: String,
@@ -94,7 +100,8 @@ pub struct ExcludeItemParams {
 }
 
 /// 除外アイテムレコード
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
 pub struct ExcludedItem {
     pub id: i64,
     pub shop_domain: String,
@@ -106,7 +113,8 @@ pub struct ExcludedItem {
 }
 
 /// 注文除外パラメータ
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
 pub struct ExcludeOrderParams {
     pub shop_domain: String,
     pub order_number: String,
@@ -114,7 +122,8 @@ pub struct ExcludeOrderParams {
 }
 
 /// 除外注文レコード
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
 pub struct ExcludedOrder {
     pub id: i64,
     pub shop_domain: String,
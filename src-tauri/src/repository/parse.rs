@@ -8,14 +8,66 @@ use sqlx::sqlite::SqlitePool;
 #[cfg_attr(test, automock)]
 #[async_trait]
 pub trait ParseRepository: Send + Sync {
-    /// 未パースのメールを取得（order_emails に存在しないメール）
+    /// 未パースのメールを取得（order_emails に存在せず、有効な shop_settings の送信元から届いたメール）
     async fn get_unparsed_emails(&self, batch_size: usize) -> Result<Vec<EmailRow>, String>;
 
+    /// 未パースのメールを受信順に `after` より後から最大 `limit` 件取得（ページ単位の読み込み用）
+    ///
+    /// `after` は直前のページ末尾の `(internal_date, email_id)`。パースした行は
+    /// 未パース条件から外れていくため、OFFSET ではなくこのカーソルで続きを読む。
+    async fn get_unparsed_emails_after(
+        &self,
+        after: Option<(i64, i64)>,
+        limit: usize,
+    ) -> Result<Vec<EmailRow>, String>;
+
+    /// 未パースのメール数を取得（進捗表示用）
+    async fn count_unparsed_emails(&self) -> Result<i64, String>;
+
     /// 注文関連テーブルをクリア（order_emails, deliveries, items, orders）
     async fn clear_order_tables(&self) -> Result<(), String>;
 
-    /// パース対象の全メール数を取得
+    /// パース対象の全メール数を取得（有効な shop_settings の送信元から届いたメールのみ）
     async fn get_total_email_count(&self) -> Result<i64, String>;
+
+    /// 注文に紐づくメールを受信順に取得（単体再パース用）
+    async fn get_order_emails(&self, order_id: i64) -> Result<Vec<EmailRow>, String>;
+
+    /// メールを 1 通取得（分割・統合の検証用）
+    async fn get_email(&self, email_id: i64) -> Result<Option<EmailRow>, String>;
+}
+
+/// from_address から送信元アドレスを取り出して小文字化する SQL 式
+///
+/// `extract_email_address()` と同じく山括弧内（なければ全体）を使う。
+/// マイグレーション 026 の式インデックス `idx_emails_from_email` と同じ式でなければならない。
+const FROM_EMAIL_EXPR: &str = r#"lower(trim(
+    CASE
+        WHEN instr(e.from_address, '<') > 0
+             AND instr(substr(e.from_address, instr(e.from_address, '<') + 1), '>') > 0
+        THEN substr(
+            e.from_address,
+            instr(e.from_address, '<') + 1,
+            instr(substr(e.from_address, instr(e.from_address, '<') + 1), '>') - 1
+        )
+        ELSE e.from_address
+    END
+))"#;
+
+/// パース対象メールの共通条件（有効な shop_settings の送信元から届いた本文ありのメール）
+///
+/// 件名フィルターは JSON のため SQL では見ず、候補パーサー選択（`get_candidate_parsers`）で判定する。
+fn parse_target_condition() -> String {
+    format!(
+        r#"e.from_address IS NOT NULL
+            AND {FROM_EMAIL_EXPR} IN (
+                SELECT lower(sender_address) FROM shop_settings WHERE is_enabled = 1
+            )
+            AND (
+                (e.body_plain IS NOT NULL AND LENGTH(TRIM(e.body_plain)) > 0)
+                OR (e.body_html IS NOT NULL AND LENGTH(TRIM(e.body_html)) > 0)
+            )"#
+    )
 }
 
 /// SQLiteを使用したParseRepositoryの実装
@@ -27,34 +79,119 @@ impl SqliteParseRepository {
     pub fn new(pool: SqlitePool) -> Self {
         Self { pool }
     }
+
+    /// 注文の商品・配送・キャンセル記録・メール紐づけを削除し、メールから作り直せる状態にする
+    ///
+    /// 注文行は残すため、注文 ID に紐づく支払い・補正などのユーザーデータは維持される。
+    /// 再パースで上書きされないメール由来の値（調整額・キャンセル理由・返金額）は初期値に戻す。
+    pub async fn reset_order_in_tx(
+        tx: &mut sqlx::Transaction<'_, sqlx::Sqlite>,
+        order_id: i64,
+    ) -> Result<(), String> {
+        for (table, sql) in [
+            ("deliveries", "DELETE FROM deliveries WHERE order_id = ?"),
+            (
+                "cancelled_items",
+                "DELETE FROM cancelled_items WHERE order_id = ?",
+            ),
+            ("items", "DELETE FROM items WHERE order_id = ?"),
+            (
+                "order_emails",
+                "DELETE FROM order_emails WHERE order_id = ?",
+            ),
+        ] {
+            sqlx::query(sql)
+                .bind(order_id)
+                .execute(tx.as_mut())
+                .await
+                .map_err(|e| format!("Failed to clear {table} for order {order_id}: {e}"))?;
+        }
+
+        sqlx::query(
+            "UPDATE orders SET amount_adjustment = 0, cancel_reason = NULL, refund_amount = NULL, updated_at = CURRENT_TIMESTAMP WHERE id = ?",
+        )
+        .bind(order_id)
+        .execute(tx.as_mut())
+        .await
+        .map_err(|e| format!("Failed to reset order {order_id}: {e}"))?;
+
+        Ok(())
+    }
 }
 
 #[async_trait]
 impl ParseRepository for SqliteParseRepository {
     async fn get_unparsed_emails(&self, batch_size: usize) -> Result<Vec<EmailRow>, String> {
-        let emails: Vec<EmailRow> = sqlx::query_as(
+        let sql = format!(
             r#"
             SELECT e.id, e.message_id, e.body_plain, e.body_html, e.from_address, e.subject, e.internal_date
             FROM emails e
             LEFT JOIN order_emails oe ON e.id = oe.email_id
-            WHERE e.from_address IS NOT NULL
-            AND oe.email_id IS NULL
-            AND (
-                (e.body_plain IS NOT NULL AND LENGTH(TRIM(e.body_plain)) > 0)
-                OR (e.body_html IS NOT NULL AND LENGTH(TRIM(e.body_html)) > 0)
-            )
+            WHERE oe.email_id IS NULL
+            AND {}
             ORDER BY e.internal_date ASC
             LIMIT ?
             "#,
-        )
-        .bind(batch_size as i64)
-        .fetch_all(&self.pool)
-        .await
-        .map_err(|e| format!("Failed to fetch unparsed emails: {e}"))?;
+            parse_target_condition()
+        );
+        let emails: Vec<EmailRow> = sqlx::query_as(&sql)
+            .bind(batch_size as i64)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| format!("Failed to fetch unparsed emails: {e}"))?;
+
+        Ok(emails)
+    }
+
+    async fn get_unparsed_emails_after(
+        &self,
+        after: Option<(i64, i64)>,
+        limit: usize,
+    ) -> Result<Vec<EmailRow>, String> {
+        let sql = format!(
+            r#"
+            SELECT e.id, e.message_id, e.body_plain, e.body_html, e.from_address, e.subject, e.internal_date
+            FROM emails e
+            LEFT JOIN order_emails oe ON e.id = oe.email_id
+            WHERE oe.email_id IS NULL
+            AND {}
+            AND (COALESCE(e.internal_date, 0), e.id) > (?, ?)
+            ORDER BY COALESCE(e.internal_date, 0) ASC, e.id ASC
+            LIMIT ?
+            "#,
+            parse_target_condition()
+        );
+        let (after_date, after_id) = after.unwrap_or((i64::MIN, i64::MIN));
+        let emails: Vec<EmailRow> = sqlx::query_as(&sql)
+            .bind(after_date)
+            .bind(after_id)
+            .bind(limit as i64)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| format!("Failed to fetch unparsed emails: {e}"))?;
 
         Ok(emails)
     }
 
+    async fn count_unparsed_emails(&self) -> Result<i64, String> {
+        let sql = format!(
+            r#"
+            SELECT COUNT(*)
+            FROM emails e
+            LEFT JOIN order_emails oe ON e.id = oe.email_id
+            WHERE oe.email_id IS NULL
+            AND {}
+            "#,
+            parse_target_condition()
+        );
+        let count: i64 = sqlx::query_scalar(&sql)
+            .fetch_one(&self.pool)
+            .await
+            .map_err(|e| format!("Failed to count unparsed emails: {e}"))?;
+
+        Ok(count)
+    }
+
     async fn clear_order_tables(&self) -> Result<(), String> {
         // トランザクション内で全てのDELETE操作を実行してアトミック性を確保
         // 外部キー制約により、order_emails -> deliveries -> items -> orders の順でクリア
@@ -92,22 +229,48 @@ impl ParseRepository for SqliteParseRepository {
     }
 
     async fn get_total_email_count(&self) -> Result<i64, String> {
-        let count: i64 = sqlx::query_scalar(
+        let sql = format!(
+            "SELECT COUNT(*) FROM emails e WHERE {}",
+            parse_target_condition()
+        );
+        let count: i64 = sqlx::query_scalar(&sql)
+            .fetch_one(&self.pool)
+            .await
+            .map_err(|e| format!("Failed to count emails: {e}"))?;
+
+        Ok(count)
+    }
+
+    async fn get_order_emails(&self, order_id: i64) -> Result<Vec<EmailRow>, String> {
+        let emails: Vec<EmailRow> = sqlx::query_as(
             r#"
-            SELECT COUNT(*)
-            FROM emails
-            WHERE from_address IS NOT NULL
-            AND (
-                (body_plain IS NOT NULL AND LENGTH(TRIM(body_plain)) > 0)
-                OR (body_html IS NOT NULL AND LENGTH(TRIM(body_html)) > 0)
-            )
+            SELECT e.id, e.message_id, e.body_plain, e.body_html, e.from_address, e.subject, e.internal_date
+            FROM emails e
+            JOIN order_emails oe ON e.id = oe.email_id
+            WHERE oe.order_id = ?
+            ORDER BY e.internal_date ASC, e.id ASC
             "#,
         )
-        .fetch_one(&self.pool)
+        .bind(order_id)
+        .fetch_all(&self.pool)
         .await
-        .map_err(|e| format!("Failed to count emails: {e}"))?;
+        .map_err(|e| format!("Failed to fetch order emails: {e}"))?;
 
-        Ok(count)
+        Ok(emails)
+    }
+
+    async fn get_email(&self, email_id: i64) -> Result<Option<EmailRow>, String> {
+        sqlx::query_as(
+            r#"
+            SELECT e.id, e.message_id, e.body_plain, e.body_html, e.from_address, e.subject, e.internal_date
+            FROM emails e
+            WHERE e.id = ?
+            "#,
+        )
+        .bind(email_id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| format!("Failed to fetch email: {e}"))
     }
 }
 
@@ -151,6 +314,11 @@ mod tests {
                 shop_name TEXT,
                 order_number TEXT,
                 order_date DATETIME,
+                tax_amount INTEGER,
+                tax_included INTEGER NOT NULL DEFAULT 1,
+                amount_adjustment INTEGER NOT NULL DEFAULT 0,
+                cancel_reason TEXT,
+                refund_amount INTEGER,
                 created_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
                 updated_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP
             )
@@ -219,13 +387,64 @@ mod tests {
         .await
         .expect("Failed to create order_emails table");
 
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS cancelled_items (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                order_id INTEGER NOT NULL,
+                email_id INTEGER,
+                item_name TEXT NOT NULL,
+                quantity INTEGER NOT NULL DEFAULT 1,
+                FOREIGN KEY (order_id) REFERENCES orders(id) ON DELETE CASCADE
+            )
+            "#,
+        )
+        .execute(&pool)
+        .await
+        .expect("Failed to create cancelled_items table");
+
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS shop_settings (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                shop_name TEXT NOT NULL,
+                sender_address TEXT NOT NULL,
+                parser_type TEXT NOT NULL,
+                is_enabled INTEGER NOT NULL DEFAULT 1,
+                subject_filters TEXT
+            )
+            "#,
+        )
+        .execute(&pool)
+        .await
+        .expect("Failed to create shop_settings table");
+
         pool
     }
 
+    async fn insert_shop_setting(pool: &SqlitePool, sender_address: &str, is_enabled: bool) {
+        sqlx::query(
+            "INSERT INTO shop_settings (shop_name, sender_address, parser_type, is_enabled) VALUES ('Shop', ?, 'test', ?)",
+        )
+        .bind(sender_address)
+        .bind(is_enabled)
+        .execute(pool)
+        .await
+        .expect("Failed to insert shop setting");
+    }
+
     #[tokio::test]
     async fn test_parse_repository_get_unparsed_emails_and_clear() {
         let pool = setup_test_db().await;
         let repo = SqliteParseRepository::new(pool.clone());
+        for sender in [
+            "test1@example.com",
+            "test2@example.com",
+            "test3@example.com",
+            "html@example.com",
+        ] {
+            insert_shop_setting(&pool, sender, true).await;
+        }
 
         // テスト用のメールを追加
         sqlx::query(
@@ -310,4 +529,187 @@ mod tests {
             .unwrap()
             .contains("注文番号:99999"));
     }
+
+    #[tokio::test]
+    async fn test_get_unparsed_emails_only_from_enabled_senders() {
+        let pool = setup_test_db().await;
+        let repo = SqliteParseRepository::new(pool.clone());
+        insert_shop_setting(&pool, "order@shop.example.com", true).await;
+        insert_shop_setting(&pool, "info@disabled.example.com", false).await;
+
+        sqlx::query(
+            r#"
+            INSERT INTO emails (message_id, body_plain, from_address, subject, internal_date)
+            VALUES
+                ('plain', 'body', 'order@shop.example.com', 'S', 1000),
+                ('display-name', 'body', '"Shop >" <Order@Shop.Example.com>', 'S', 2000),
+                ('padded', 'body', '  ORDER@shop.example.com ', 'S', 3000),
+                ('disabled', 'body', 'info@disabled.example.com', 'S', 4000),
+                ('unknown', 'body', 'newsletter@example.com', 'S', 5000),
+                ('prefix', 'body', 'xorder@shop.example.com', 'S', 6000)
+            "#,
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let ids: Vec<String> = repo
+            .get_unparsed_emails(10)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|e| e.message_id)
+            .collect();
+        assert_eq!(ids, vec!["plain", "display-name", "padded"]);
+        assert_eq!(repo.get_total_email_count().await.unwrap(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_get_unparsed_emails_after_pages_in_receive_order() {
+        let pool = setup_test_db().await;
+        let repo = SqliteParseRepository::new(pool.clone());
+        insert_shop_setting(&pool, "order@shop.example.com", true).await;
+
+        sqlx::query(
+            r#"
+            INSERT INTO emails (message_id, body_plain, from_address, subject, internal_date)
+            VALUES
+                ('c', 'body', 'order@shop.example.com', 'S', 3000),
+                ('a', 'body', 'order@shop.example.com', 'S', NULL),
+                ('b1', 'body', 'order@shop.example.com', 'S', 2000),
+                ('b2', 'body', 'order@shop.example.com', 'S', 2000),
+                ('other', 'body', 'newsletter@example.com', 'S', 1000)
+            "#,
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+        sqlx::query(
+            "INSERT INTO orders (id, order_number, shop_domain) VALUES (1, 'ORD-001', 'example.com')",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+        assert_eq!(repo.count_unparsed_emails().await.unwrap(), 4);
+
+        let mut pages: Vec<Vec<String>> = Vec::new();
+        let mut after = None;
+        loop {
+            let page = repo.get_unparsed_emails_after(after, 2).await.unwrap();
+            let Some(last) = page.last() else {
+                break;
+            };
+            after = Some((last.internal_date.unwrap_or(0), last.email_id));
+            pages.push(page.into_iter().map(|e| e.message_id).collect());
+
+            // 読み込んだメールがパース済みになっても続きのページは変わらない
+            sqlx::query("INSERT INTO order_emails (order_id, email_id) VALUES (1, ?)")
+                .bind(after.unwrap().1)
+                .execute(&pool)
+                .await
+                .unwrap();
+        }
+
+        assert_eq!(pages, vec![vec!["a", "b1"], vec!["b2", "c"]]);
+        assert_eq!(repo.count_unparsed_emails().await.unwrap(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_unparsed_emails_query_uses_sender_index() {
+        let pool = setup_test_db().await;
+        sqlx::raw_sql(include_str!("../../migrations/026_emails_sender_index.sql"))
+            .execute(&pool)
+            .await
+            .expect("Failed to apply migration 026");
+
+        let sql = format!(
+            "EXPLAIN QUERY PLAN SELECT e.id FROM emails e WHERE {}",
+            parse_target_condition()
+        );
+        let plan: Vec<(i64, i64, i64, String)> =
+            sqlx::query_as(&sql).fetch_all(&pool).await.unwrap();
+        // マイグレーションの式とクエリの式が一致していれば全件走査にならない
+        assert!(
+            plan.iter()
+                .any(|(_, _, _, detail)| detail.contains("idx_emails_from_email")),
+            "plan: {plan:?}"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_get_order_emails_and_reset_order() {
+        let pool = setup_test_db().await;
+        let repo = SqliteParseRepository::new(pool.clone());
+
+        sqlx::query(
+            r#"
+            INSERT INTO emails (id, message_id, body_plain, from_address, subject, internal_date)
+            VALUES
+                (1, 'send', 'body', 'shop@example.com', '発送', 2000),
+                (2, 'confirm', 'body', 'shop@example.com', '注文確認', 1000),
+                (3, 'other', 'body', 'shop@example.com', '別注文', 1500)
+            "#,
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+        sqlx::query(
+            r#"
+            INSERT INTO orders (id, order_number, shop_domain, amount_adjustment, cancel_reason, refund_amount)
+            VALUES (1, 'ORD-001', 'example.com', 500, 'other', 1000), (2, 'ORD-002', 'example.com', 0, NULL, NULL)
+            "#,
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+        sqlx::query(
+            r#"
+            INSERT INTO items (order_id, item_name) VALUES (1, '商品A'), (2, '商品B');
+            INSERT INTO deliveries (order_id, tracking_number) VALUES (1, '123');
+            INSERT INTO cancelled_items (order_id, item_name) VALUES (1, '商品C');
+            INSERT INTO order_emails (order_id, email_id) VALUES (1, 1), (1, 2), (2, 3);
+            "#,
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        // 受信順に注文のメールだけを返す
+        let emails = repo.get_order_emails(1).await.unwrap();
+        let ids: Vec<i64> = emails.iter().map(|e| e.email_id).collect();
+        assert_eq!(ids, vec![2, 1]);
+
+        let email = repo.get_email(3).await.unwrap().unwrap();
+        assert_eq!(email.message_id, "other");
+        assert!(repo.get_email(99).await.unwrap().is_none());
+
+        let mut tx = pool.begin().await.unwrap();
+        SqliteParseRepository::reset_order_in_tx(&mut tx, 1)
+            .await
+            .unwrap();
+        tx.commit().await.unwrap();
+
+        // 注文行は残り、紐づくデータだけが消える（他の注文には影響しない）
+        let (adjustment, reason, refund): (i64, Option<String>, Option<i64>) = sqlx::query_as(
+            "SELECT amount_adjustment, cancel_reason, refund_amount FROM orders WHERE id = 1",
+        )
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        assert_eq!((adjustment, reason, refund), (0, None, None));
+        for table in ["items", "deliveries", "cancelled_items", "order_emails"] {
+            let count: i64 =
+                sqlx::query_scalar(&format!("SELECT COUNT(*) FROM {table} WHERE order_id = 1"))
+                    .fetch_one(&pool)
+                    .await
+                    .unwrap();
+            assert_eq!(count, 0, "{table} should be cleared");
+        }
+        let other_items: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM items WHERE order_id = 2")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!(other_items, 1);
+        assert!(repo.get_order_emails(1).await.unwrap().is_empty());
+    }
 }
@@ -0,0 +1,329 @@
+//! バッチパースのアンドゥ用スナップショット
+//!
+//! バッチパースは注文関連テーブルを全削除してから作り直すため、パーサーの誤動作で
+//! 手入力の補正（支払い予定・タグ・分納の紐付け等）を含むデータが失われることがある。
+//! 実行前に注文関連テーブルを別ファイルの SQLite DB へコピーしておき、`restore_snapshot` で元に戻す。
+
+use sqlx::sqlite::{SqliteConnection, SqlitePool};
+use sqlx::Connection;
+use std::path::{Path, PathBuf};
+
+/// スナップショット対象のテーブル（外部キーの親 → 子の順。復元時はこの順に挿入し、逆順に削除する）
+const SNAPSHOT_TABLES: &[&str] = &[
+    "orders",
+    "items",
+    "deliveries",
+    "order_emails",
+    "order_htmls",
+    "delivery_items",
+    "cancelled_items",
+    "payments",
+    "point_transactions",
+    "item_tags",
+    "trashed_orders",
+    "trashed_items",
+];
+
+/// ATTACH 時のスキーマ名
+const SNAPSHOT_SCHEMA: &str = "parse_undo";
+
+/// バッチパースのアンドゥ用スナップショットのDB操作
+pub struct SqliteParseUndoRepository {
+    pool: SqlitePool,
+}
+
+impl SqliteParseUndoRepository {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    /// 注文関連テーブルを `path` のDBファイルへコピーする（既存のスナップショットは置き換える）
+    ///
+    /// 書き込み途中のファイルを復元に使わないよう、一時ファイルに書き出してからリネームする。
+    pub async fn create_snapshot(&self, path: &Path) -> Result<(), String> {
+        let tmp_path = tmp_snapshot_path(path);
+        remove_file_if_exists(&tmp_path)?;
+
+        let mut conn = self
+            .pool
+            .acquire()
+            .await
+            .map_err(|e| format!("Failed to acquire connection: {e}"))?;
+        attach(&mut conn, &tmp_path).await?;
+        let result = copy_tables_to_snapshot(&mut conn).await;
+        detach(&mut conn).await?;
+        result?;
+
+        std::fs::rename(&tmp_path, path)
+            .map_err(|e| format!("Failed to save parse snapshot: {e}"))?;
+        Ok(())
+    }
+
+    /// `path` のスナップショットで注文関連テーブルを置き換え、スナップショットを削除する
+    ///
+    /// 戻り値は復元後の注文数（ゴミ箱内の注文を除く）。
+    pub async fn restore_snapshot(&self, path: &Path) -> Result<i64, String> {
+        if !path.exists() {
+            return Err("No parse snapshot to undo".to_string());
+        }
+
+        let mut conn = self
+            .pool
+            .acquire()
+            .await
+            .map_err(|e| format!("Failed to acquire connection: {e}"))?;
+        attach(&mut conn, path).await?;
+        let result = restore_tables_from_snapshot(&mut conn).await;
+        detach(&mut conn).await?;
+        let order_count = result?;
+
+        remove_file_if_exists(path)?;
+        Ok(order_count)
+    }
+}
+
+fn tmp_snapshot_path(path: &Path) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(".tmp");
+    PathBuf::from(name)
+}
+
+fn remove_file_if_exists(path: &Path) -> Result<(), String> {
+    match std::fs::remove_file(path) {
+        Ok(()) => Ok(()),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+        Err(e) => Err(format!("Failed to remove {}: {e}", path.display())),
+    }
+}
+
+async fn attach(conn: &mut SqliteConnection, path: &Path) -> Result<(), String> {
+    sqlx::query(&format!("ATTACH DATABASE ? AS {SNAPSHOT_SCHEMA}"))
+        .bind(path.to_string_lossy().to_string())
+        .execute(&mut *conn)
+        .await
+        .map_err(|e| format!("Failed to attach parse snapshot: {e}"))?;
+    Ok(())
+}
+
+async fn detach(conn: &mut SqliteConnection) -> Result<(), String> {
+    sqlx::query(&format!("DETACH DATABASE {SNAPSHOT_SCHEMA}"))
+        .execute(&mut *conn)
+        .await
+        .map_err(|e| format!("Failed to detach parse snapshot: {e}"))?;
+    Ok(())
+}
+
+/// `schema` に存在するスナップショット対象テーブルを `SNAPSHOT_TABLES` の順で返す
+async fn existing_tables(
+    conn: &mut SqliteConnection,
+    schema: &str,
+) -> Result<Vec<&'static str>, String> {
+    let names: Vec<String> = sqlx::query_scalar(&format!(
+        "SELECT name FROM {schema}.sqlite_master WHERE type = 'table'"
+    ))
+    .fetch_all(&mut *conn)
+    .await
+    .map_err(|e| format!("Failed to list tables: {e}"))?;
+
+    Ok(SNAPSHOT_TABLES
+        .iter()
+        .copied()
+        .filter(|t| names.iter().any(|n| n == t))
+        .collect())
+}
+
+async fn copy_tables_to_snapshot(conn: &mut SqliteConnection) -> Result<(), String> {
+    let tables = existing_tables(conn, "main").await?;
+    let mut tx = conn
+        .begin()
+        .await
+        .map_err(|e| format!("Failed to begin transaction: {e}"))?;
+    for table in tables {
+        sqlx::query(&format!(
+            "CREATE TABLE {SNAPSHOT_SCHEMA}.{table} AS SELECT * FROM main.{table}"
+        ))
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| format!("Failed to snapshot {table}: {e}"))?;
+    }
+    tx.commit()
+        .await
+        .map_err(|e| format!("Failed to commit transaction: {e}"))
+}
+
+/// 両方に存在する列名（スナップショット後にマイグレーションで列が増減しても復元できるようにする）
+async fn common_columns(conn: &mut SqliteConnection, table: &str) -> Result<Vec<String>, String> {
+    sqlx::query_scalar(
+        r#"
+        SELECT s.name
+        FROM pragma_table_info(?1, ?2) s
+        INNER JOIN pragma_table_info(?1, 'main') m ON m.name = s.name
+        ORDER BY s.cid
+        "#,
+    )
+    .bind(table)
+    .bind(SNAPSHOT_SCHEMA)
+    .fetch_all(&mut *conn)
+    .await
+    .map_err(|e| format!("Failed to read columns of {table}: {e}"))
+}
+
+async fn restore_tables_from_snapshot(conn: &mut SqliteConnection) -> Result<i64, String> {
+    let main_tables = existing_tables(conn, "main").await?;
+    let snapshot_tables = existing_tables(conn, SNAPSHOT_SCHEMA).await?;
+
+    let mut restores = Vec::new();
+    for table in snapshot_tables
+        .into_iter()
+        .filter(|t| main_tables.contains(t))
+    {
+        let columns = common_columns(conn, table).await?.join(", ");
+        restores.push((table, columns));
+    }
+
+    let mut tx = conn
+        .begin()
+        .await
+        .map_err(|e| format!("Failed to begin transaction: {e}"))?;
+
+    for table in main_tables.iter().rev() {
+        sqlx::query(&format!("DELETE FROM main.{table}"))
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| format!("Failed to clear {table}: {e}"))?;
+    }
+    for (table, columns) in &restores {
+        sqlx::query(&format!(
+            "INSERT INTO main.{table} ({columns}) SELECT {columns} FROM {SNAPSHOT_SCHEMA}.{table}"
+        ))
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| format!("Failed to restore {table}: {e}"))?;
+    }
+
+    let order_count: i64 =
+        sqlx::query_scalar("SELECT COUNT(*) FROM main.orders WHERE deleted_at IS NULL")
+            .fetch_one(&mut *tx)
+            .await
+            .map_err(|e| format!("Failed to count orders: {e}"))?;
+
+    tx.commit()
+        .await
+        .map_err(|e| format!("Failed to commit transaction: {e}"))?;
+    Ok(order_count)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sqlx::sqlite::SqlitePoolOptions;
+
+    async fn setup_test_db() -> SqlitePool {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .expect("Failed to create test database");
+
+        sqlx::query(
+            r#"
+            PRAGMA foreign_keys = ON;
+            CREATE TABLE orders (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                order_number TEXT,
+                deleted_at DATETIME
+            );
+            CREATE TABLE items (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                order_id INTEGER NOT NULL,
+                item_name TEXT NOT NULL,
+                FOREIGN KEY (order_id) REFERENCES orders(id) ON DELETE CASCADE
+            );
+            CREATE TABLE item_tags (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                item_id INTEGER NOT NULL,
+                tag TEXT NOT NULL,
+                FOREIGN KEY (item_id) REFERENCES items(id) ON DELETE CASCADE
+            );
+            "#,
+        )
+        .execute(&pool)
+        .await
+        .expect("Failed to create tables");
+
+        pool
+    }
+
+    async fn count(pool: &SqlitePool, table: &str) -> i64 {
+        sqlx::query_scalar(&format!("SELECT COUNT(*) FROM {table}"))
+            .fetch_one(pool)
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_snapshot_and_restore() {
+        let pool = setup_test_db().await;
+        sqlx::query(
+            r#"
+            INSERT INTO orders (id, order_number) VALUES (1, 'A-1'), (2, 'A-2');
+            INSERT INTO items (id, order_id, item_name) VALUES (10, 1, '商品1'), (20, 2, '商品2');
+            INSERT INTO item_tags (item_id, tag) VALUES (10, 'お気に入り');
+            "#,
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("undo.db");
+        let repo = SqliteParseUndoRepository::new(pool.clone());
+        repo.create_snapshot(&path).await.unwrap();
+        assert!(path.exists());
+        assert!(!tmp_snapshot_path(&path).exists());
+
+        // パースのやり直しで別の内容に置き換わった状態
+        sqlx::query("DELETE FROM orders; INSERT INTO orders (id, order_number) VALUES (3, 'B-1');")
+            .execute(&pool)
+            .await
+            .unwrap();
+        assert_eq!(count(&pool, "item_tags").await, 0);
+
+        assert_eq!(repo.restore_snapshot(&path).await.unwrap(), 2);
+        assert_eq!(count(&pool, "items").await, 2);
+        assert_eq!(count(&pool, "item_tags").await, 1);
+        let numbers: Vec<String> =
+            sqlx::query_scalar("SELECT order_number FROM orders ORDER BY id")
+                .fetch_all(&pool)
+                .await
+                .unwrap();
+        assert_eq!(numbers, vec!["A-1", "A-2"]);
+
+        // アンドゥは1回限り
+        assert!(!path.exists());
+        assert!(repo.restore_snapshot(&path).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_restore_tolerates_added_columns() {
+        let pool = setup_test_db().await;
+        sqlx::query("INSERT INTO orders (id, order_number) VALUES (1, 'A-1')")
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("undo.db");
+        let repo = SqliteParseUndoRepository::new(pool.clone());
+        repo.create_snapshot(&path).await.unwrap();
+
+        // スナップショット後のマイグレーションで列が追加された場合
+        sqlx::query("ALTER TABLE orders ADD COLUMN memo TEXT")
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        assert_eq!(repo.restore_snapshot(&path).await.unwrap(), 1);
+        assert_eq!(count(&pool, "orders").await, 1);
+    }
+}
@@ -0,0 +1,174 @@
+use serde::{Deserialize, Serialize};
+use sqlx::sqlite::SqlitePool;
+use sqlx::FromRow;
+use std::collections::BTreeMap;
+use std::time::Duration;
+use ts_rs::TS;
+
+/// パーサー 1 回分（1 通 × 1 候補パーサー）の処理時間
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParserTiming {
+    pub parser_type: String,
+    pub elapsed: Duration,
+    /// dispatch に成功したか（パース失敗で次の候補に回った試行・保存失敗は false）
+    pub succeeded: bool,
+}
+
+/// パーサー種別ごとの処理時間の集計
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, FromRow, TS)]
+#[ts(export)]
+pub struct ParserPerformance {
+    pub parser_type: String,
+    /// 計測した試行の累計件数
+    pub sample_count: i64,
+    /// うち dispatch に失敗した件数
+    pub failure_count: i64,
+    /// 1 通あたりの平均処理時間（ミリ秒）
+    pub avg_ms: f64,
+    /// 1 通あたりの最大処理時間（ミリ秒）
+    pub max_ms: f64,
+    pub last_measured_at: Option<String>,
+}
+
+#[derive(Default)]
+struct TimingTotals {
+    samples: i64,
+    failures: i64,
+    total_micros: i64,
+    max_micros: i64,
+}
+
+/// パーサーの処理時間メトリクスのDB操作
+pub struct SqliteParserMetricsRepository {
+    pool: SqlitePool,
+}
+
+impl SqliteParserMetricsRepository {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    /// 計測結果を parser_type ごとに集計して parser_metrics に加算する
+    pub async fn record_timings(&self, timings: &[ParserTiming]) -> Result<(), String> {
+        if timings.is_empty() {
+            return Ok(());
+        }
+        let mut totals: BTreeMap<&str, TimingTotals> = BTreeMap::new();
+        for timing in timings {
+            let micros = i64::try_from(timing.elapsed.as_micros()).unwrap_or(i64::MAX);
+            let entry = totals.entry(timing.parser_type.as_str()).or_default();
+            entry.samples += 1;
+            entry.failures += i64::from(!timing.succeeded);
+            entry.total_micros = entry.total_micros.saturating_add(micros);
+            entry.max_micros = entry.max_micros.max(micros);
+        }
+
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| format!("Failed to begin transaction: {e}"))?;
+        for (parser_type, t) in totals {
+            sqlx::query(
+                r#"
+                INSERT INTO parser_metrics
+                    (parser_type, sample_count, failure_count, total_micros, max_micros, last_measured_at)
+                VALUES (?, ?, ?, ?, ?, datetime('now'))
+                ON CONFLICT(parser_type) DO UPDATE SET
+                    sample_count = sample_count + excluded.sample_count,
+                    failure_count = failure_count + excluded.failure_count,
+                    total_micros = total_micros + excluded.total_micros,
+                    max_micros = MAX(max_micros, excluded.max_micros),
+                    last_measured_at = excluded.last_measured_at
+                "#,
+            )
+            .bind(parser_type)
+            .bind(t.samples)
+            .bind(t.failures)
+            .bind(t.total_micros)
+            .bind(t.max_micros)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| format!("Failed to record parser metrics: {e}"))?;
+        }
+        tx.commit()
+            .await
+            .map_err(|e| format!("Failed to commit transaction: {e}"))?;
+
+        Ok(())
+    }
+
+    /// パーサー種別ごとの平均・最大処理時間を平均の遅い順に返す
+    pub async fn list_performance(&self) -> Result<Vec<ParserPerformance>, String> {
+        sqlx::query_as::<_, ParserPerformance>(
+            r#"
+            SELECT parser_type, sample_count, failure_count,
+                   CAST(total_micros AS REAL) / sample_count / 1000.0 AS avg_ms,
+                   CAST(max_micros AS REAL) / 1000.0 AS max_ms,
+                   last_measured_at
+            FROM parser_metrics
+            WHERE sample_count > 0
+            ORDER BY avg_ms DESC, parser_type
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| format!("Failed to get parser performance: {e}"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sqlx::sqlite::SqlitePoolOptions;
+
+    async fn setup_test_db() -> SqlitePool {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .expect("Failed to create test database");
+        sqlx::raw_sql(include_str!("../../migrations/033_parser_metrics.sql"))
+            .execute(&pool)
+            .await
+            .expect("Failed to create parser_metrics table");
+        pool
+    }
+
+    fn timing(parser_type: &str, millis: u64, succeeded: bool) -> ParserTiming {
+        ParserTiming {
+            parser_type: parser_type.to_string(),
+            elapsed: Duration::from_millis(millis),
+            succeeded,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_record_timings_and_list_performance() {
+        let pool = setup_test_db().await;
+        let repo = SqliteParserMetricsRepository::new(pool);
+
+        repo.record_timings(&[
+            timing("amazon_confirm", 4, true),
+            timing("amazon_confirm", 8, false),
+            timing("dmm_confirm", 1, true),
+        ])
+        .await
+        .unwrap();
+        repo.record_timings(&[timing("amazon_confirm", 3, true)])
+            .await
+            .unwrap();
+        repo.record_timings(&[]).await.unwrap();
+
+        let perf = repo.list_performance().await.unwrap();
+        assert_eq!(perf.len(), 2);
+        assert_eq!(perf[0].parser_type, "amazon_confirm");
+        assert_eq!(perf[0].sample_count, 3);
+        assert_eq!(perf[0].failure_count, 1);
+        assert!((perf[0].avg_ms - 5.0).abs() < 1e-9);
+        assert!((perf[0].max_ms - 8.0).abs() < 1e-9);
+        assert!(perf[0].last_measured_at.is_some());
+        assert_eq!(perf[1].parser_type, "dmm_confirm");
+        assert!((perf[1].avg_ms - 1.0).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,564 @@
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use sqlx::sqlite::SqlitePool;
+use std::collections::HashSet;
+use ts_rs::TS;
+
+/// 支払い予定の状態
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(export)]
+pub enum PaymentStatus {
+    /// 未払い
+    Pending,
+    /// 支払い済み
+    Paid,
+    /// 取り消し（注文キャンセル等で支払い不要になったもの）
+    Cancelled,
+}
+
+impl PaymentStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Pending => "pending",
+            Self::Paid => "paid",
+            Self::Cancelled => "cancelled",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "pending" => Some(Self::Pending),
+            "paid" => Some(Self::Paid),
+            "cancelled" => Some(Self::Cancelled),
+            _ => None,
+        }
+    }
+}
+
+/// 注文の支払い予定（予約時の内金・発売時の残金など、1注文に複数持てる）
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct Payment {
+    pub id: i64,
+    pub order_id: i64,
+    pub shop_name: Option<String>,
+    pub order_number: Option<String>,
+    /// 支払いの区分（「内金」「残金」など。任意）
+    pub label: Option<String>,
+    pub amount: i64,
+    /// 支払い期日（YYYY-MM-DD）
+    pub due_date: Option<String>,
+    pub status: PaymentStatus,
+    pub paid_at: Option<String>,
+    /// 消し込みに使った支払いメール
+    pub email_id: Option<i64>,
+}
+
+type PaymentRow = (
+    i64,
+    i64,
+    Option<String>,
+    Option<String>,
+    Option<String>,
+    i64,
+    Option<String>,
+    String,
+    Option<String>,
+    Option<i64>,
+);
+
+/// 支払い予定は (shop_domain, order_number) で注文に紐付ける（再パースで orders.id が変わっても残すため）
+const PAYMENT_SELECT: &str = r#"
+    SELECT p.id, o.id, o.shop_name, o.order_number, p.label, p.amount,
+           p.due_date, p.status, p.paid_at, p.email_id
+    FROM payments p
+    INNER JOIN orders o
+        ON p.shop_domain = COALESCE(o.shop_domain, '')
+       AND p.order_number = o.order_number
+"#;
+
+fn payment_from_row(row: PaymentRow) -> Payment {
+    let (id, order_id, shop_name, order_number, label, amount, due_date, status, paid_at, email_id) =
+        row;
+    Payment {
+        id,
+        order_id,
+        shop_name,
+        order_number,
+        label,
+        amount,
+        due_date,
+        status: PaymentStatus::parse(&status).unwrap_or(PaymentStatus::Pending),
+        paid_at,
+        email_id,
+    }
+}
+
+/// 支払い完了を示す表記
+const PAID_KEYWORDS: &[&str] = &[
+    "お支払いが完了",
+    "お支払い完了",
+    "お支払いを確認",
+    "ご入金を確認",
+    "ご入金確認",
+    "入金を確認",
+    "決済が完了",
+    "決済完了",
+];
+
+/// `お支払い金額：12,800円` / `ご入金額 ￥3,000` / `決済金額: 9,800円`
+static PAID_AMOUNT_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?:お支払い?|ご入金|入金|決済|ご請求)(?:金)?額\s*[：:]?\s*[¥￥]?\s*([\d,]+)")
+        .expect("PAID_AMOUNT_RE")
+});
+
+/// 支払いメールから読み取った内容
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PaymentNotice {
+    /// 支払い金額（記載がなければ None）
+    pub amount: Option<i64>,
+}
+
+/// メールの件名・本文が支払い完了通知なら金額とともに返す（該当表記がなければ None）
+pub fn detect_payment_notice(text: &str) -> Option<PaymentNotice> {
+    if !PAID_KEYWORDS.iter().any(|k| text.contains(k)) {
+        return None;
+    }
+    let amount = PAID_AMOUNT_RE
+        .captures(text)
+        .and_then(|c| c[1].replace(',', "").parse().ok());
+    Some(PaymentNotice { amount })
+}
+
+/// 未払いの支払い予定から消し込み対象を選ぶ
+///
+/// 金額の記載があれば同額の支払い予定、なければ期日の早いものを選ぶ（`pending` は期日順に並んでいる前提）。
+fn select_payment_to_settle(pending: &[(i64, i64)], notice: PaymentNotice) -> Option<i64> {
+    let payment = match notice.amount {
+        Some(amount) => pending.iter().find(|(_, a)| *a == amount),
+        None => pending.first(),
+    };
+    payment.map(|(id, _)| *id)
+}
+
+/// 支払い予定のDB操作
+pub struct SqlitePaymentRepository {
+    pool: SqlitePool,
+}
+
+impl SqlitePaymentRepository {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    /// 注文の支払い予定を期日順に取得する
+    pub async fn list_by_order(&self, order_id: i64) -> Result<Vec<Payment>, String> {
+        let sql = format!(
+            "{PAYMENT_SELECT} WHERE o.id = ? ORDER BY p.due_date IS NULL, p.due_date, p.id"
+        );
+        let rows: Vec<PaymentRow> = sqlx::query_as(&sql)
+            .bind(order_id)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| format!("Failed to fetch payments: {e}"))?;
+        Ok(rows.into_iter().map(payment_from_row).collect())
+    }
+
+    /// 未払いの支払い予定を期日の近い順に取得する（削除済み注文は除く）
+    pub async fn list_pending(&self) -> Result<Vec<Payment>, String> {
+        let sql = format!(
+            r#"{PAYMENT_SELECT}
+            WHERE p.status = 'pending' AND o.deleted_at IS NULL
+            ORDER BY p.due_date IS NULL, p.due_date, p.id
+            "#
+        );
+        let rows: Vec<PaymentRow> = sqlx::query_as(&sql)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| format!("Failed to fetch pending payments: {e}"))?;
+        Ok(rows.into_iter().map(payment_from_row).collect())
+    }
+
+    /// 支払い予定を追加し、payments.id を返す（注文番号のない注文には登録できない）
+    pub async fn add(
+        &self,
+        order_id: i64,
+        label: Option<&str>,
+        amount: i64,
+        due_date: Option<&str>,
+    ) -> Result<i64, String> {
+        let order_key: Option<(String, Option<String>)> = sqlx::query_as(
+            "SELECT COALESCE(shop_domain, ''), order_number FROM orders WHERE id = ? AND deleted_at IS NULL",
+        )
+        .bind(order_id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| format!("Failed to fetch order: {e}"))?;
+        let Some((shop_domain, order_number)) = order_key else {
+            return Err(format!("Order not found: {order_id}"));
+        };
+        let Some(order_number) = order_number else {
+            return Err(format!("Order {order_id} has no order number"));
+        };
+
+        let result = sqlx::query(
+            "INSERT INTO payments (shop_domain, order_number, label, amount, due_date) VALUES (?, ?, ?, ?, ?)",
+        )
+        .bind(&shop_domain)
+        .bind(&order_number)
+        .bind(label)
+        .bind(amount)
+        .bind(due_date)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| format!("Failed to insert payment: {e}"))?;
+        Ok(result.last_insert_rowid())
+    }
+
+    /// 支払い予定の状態を手動で更新する。paid にした場合は paid_at を現在時刻にする。
+    pub async fn set_status(&self, payment_id: i64, status: PaymentStatus) -> Result<(), String> {
+        let result = sqlx::query(
+            r#"
+            UPDATE payments
+            SET status = ?,
+                paid_at = CASE WHEN ? = 'paid' THEN COALESCE(paid_at, CURRENT_TIMESTAMP) ELSE NULL END,
+                updated_at = CURRENT_TIMESTAMP
+            WHERE id = ?
+            "#,
+        )
+        .bind(status.as_str())
+        .bind(status.as_str())
+        .bind(payment_id)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| format!("Failed to update payment status: {e}"))?;
+
+        if result.rows_affected() == 0 {
+            return Err(format!("Payment not found: {payment_id}"));
+        }
+        Ok(())
+    }
+
+    pub async fn delete(&self, payment_id: i64) -> Result<(), String> {
+        let result = sqlx::query("DELETE FROM payments WHERE id = ?")
+            .bind(payment_id)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| format!("Failed to delete payment: {e}"))?;
+
+        if result.rows_affected() == 0 {
+            return Err(format!("Payment not found: {payment_id}"));
+        }
+        Ok(())
+    }
+
+    /// 未払いの支払い予定がある注文について、注文番号を含む同一ショップの支払い完了メールを
+    /// 受信順に走査し、支払い予定を消し込む。消し込みに使ったメールは再利用しない。
+    ///
+    /// 消し込んだ件数を返す。
+    pub async fn reconcile_from_emails(&self) -> Result<usize, String> {
+        let rows: Vec<(i64, i64, String, Option<i64>)> = sqlx::query_as(
+            r#"
+            SELECT o.id, e.id,
+                   COALESCE(e.subject, '') || char(10) || COALESCE(e.body_plain, '') AS text,
+                   e.internal_date
+            FROM orders o
+            INNER JOIN emails e
+                ON e.body_plain LIKE '%' || o.order_number || '%'
+               AND e.from_address LIKE '%' || o.shop_domain || '%'
+            WHERE o.order_number IS NOT NULL
+              AND length(o.order_number) >= 5
+              AND o.shop_domain IS NOT NULL
+              AND o.deleted_at IS NULL
+              AND EXISTS (
+                  SELECT 1 FROM payments p
+                  WHERE p.shop_domain = o.shop_domain
+                    AND p.order_number = o.order_number
+                    AND p.status = 'pending'
+              )
+              AND NOT EXISTS (SELECT 1 FROM payments p WHERE p.email_id = e.id)
+            ORDER BY o.id, e.internal_date ASC
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| format!("Failed to fetch payment notices: {e}"))?;
+
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| format!("Failed to start transaction: {e}"))?;
+        let mut settled: HashSet<i64> = HashSet::new();
+        for (order_id, email_id, text, internal_date) in rows {
+            let Some(notice) = detect_payment_notice(&text) else {
+                continue;
+            };
+
+            let pending: Vec<(i64, i64)> = sqlx::query_as(
+                r#"
+                SELECT p.id, p.amount
+                FROM payments p
+                INNER JOIN orders o
+                    ON p.shop_domain = COALESCE(o.shop_domain, '')
+                   AND p.order_number = o.order_number
+                WHERE o.id = ? AND p.status = 'pending'
+                ORDER BY p.due_date IS NULL, p.due_date, p.id
+                "#,
+            )
+            .bind(order_id)
+            .fetch_all(&mut *tx)
+            .await
+            .map_err(|e| format!("Failed to fetch pending payments: {e}"))?;
+
+            let Some(payment_id) = select_payment_to_settle(&pending, notice) else {
+                log::debug!(
+                    "Payment notice (email_id={}) did not match pending payments of order {}",
+                    email_id,
+                    order_id
+                );
+                continue;
+            };
+
+            sqlx::query(
+                r#"
+                UPDATE payments
+                SET status = 'paid',
+                    paid_at = COALESCE(datetime(? / 1000, 'unixepoch'), CURRENT_TIMESTAMP),
+                    email_id = ?,
+                    updated_at = CURRENT_TIMESTAMP
+                WHERE id = ?
+                "#,
+            )
+            .bind(internal_date)
+            .bind(email_id)
+            .bind(payment_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| format!("Failed to settle payment: {e}"))?;
+            settled.insert(payment_id);
+        }
+        tx.commit()
+            .await
+            .map_err(|e| format!("Failed to commit transaction: {e}"))?;
+
+        Ok(settled.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sqlx::sqlite::SqlitePoolOptions;
+
+    async fn setup_test_db() -> SqlitePool {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .expect("Failed to create test database");
+
+        sqlx::query(
+            r#"
+            CREATE TABLE orders (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                shop_domain TEXT,
+                shop_name TEXT,
+                order_number TEXT,
+                deleted_at DATETIME
+            );
+            CREATE TABLE emails (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                subject TEXT,
+                body_plain TEXT,
+                from_address TEXT,
+                internal_date INTEGER
+            );
+            "#,
+        )
+        .execute(&pool)
+        .await
+        .expect("Failed to create tables");
+        sqlx::raw_sql(include_str!("../../migrations/015_payments.sql"))
+            .execute(&pool)
+            .await
+            .expect("Failed to create payments table");
+        sqlx::raw_sql(include_str!(
+            "../../migrations/035_payments_business_key.sql"
+        ))
+        .execute(&pool)
+        .await
+        .expect("Failed to migrate payments table");
+
+        pool
+    }
+
+    #[test]
+    fn test_detect_payment_notice() {
+        assert_eq!(
+            detect_payment_notice("内金のお支払いが完了しました\nお支払い金額：3,000円"),
+            Some(PaymentNotice { amount: Some(3000) })
+        );
+        assert_eq!(
+            detect_payment_notice("ご入金を確認いたしました。ご入金額 ￥12,800"),
+            Some(PaymentNotice {
+                amount: Some(12800)
+            })
+        );
+        assert_eq!(
+            detect_payment_notice("決済完了のお知らせ"),
+            Some(PaymentNotice { amount: None })
+        );
+        assert_eq!(detect_payment_notice("残金のお支払いのお願い"), None);
+    }
+
+    #[test]
+    fn test_select_payment_to_settle() {
+        let pending = [(1, 3000), (2, 12800)];
+        assert_eq!(
+            select_payment_to_settle(
+                &pending,
+                PaymentNotice {
+                    amount: Some(12800)
+                }
+            ),
+            Some(2)
+        );
+        assert_eq!(
+            select_payment_to_settle(&pending, PaymentNotice { amount: None }),
+            Some(1)
+        );
+        assert_eq!(
+            select_payment_to_settle(&pending, PaymentNotice { amount: Some(500) }),
+            None
+        );
+    }
+
+    #[tokio::test]
+    async fn test_reconcile_from_emails_settles_deposit_then_balance() {
+        let pool = setup_test_db().await;
+        sqlx::query(
+            r#"
+            INSERT INTO orders (id, shop_domain, shop_name, order_number) VALUES
+                (1, 'fnex.jp', 'F:NEX', 'FN-000123');
+            INSERT INTO emails (subject, body_plain, from_address, internal_date) VALUES
+                ('ご予約ありがとうございます', '注文番号 FN-000123', 'shop@fnex.jp', 1700000000000),
+                ('内金お支払い完了', '注文番号 FN-000123\nお支払い金額：3,000円', 'shop@fnex.jp', 1700000100000);
+            "#,
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let repo = SqlitePaymentRepository::new(pool.clone());
+        let deposit = repo
+            .add(1, Some("内金"), 3000, Some("2023-11-20"))
+            .await
+            .unwrap();
+        let balance = repo
+            .add(1, Some("残金"), 12800, Some("2024-05-31"))
+            .await
+            .unwrap();
+
+        assert_eq!(repo.reconcile_from_emails().await.unwrap(), 1);
+        let payments = repo.list_by_order(1).await.unwrap();
+        assert_eq!(payments[0].id, deposit);
+        assert_eq!(payments[0].status, PaymentStatus::Paid);
+        assert_eq!(payments[0].paid_at.as_deref(), Some("2023-11-14 22:15:00"));
+        assert_eq!(payments[0].email_id, Some(2));
+        assert_eq!(payments[1].status, PaymentStatus::Pending);
+
+        // 使用済みのメールでは再度消し込まない
+        assert_eq!(repo.reconcile_from_emails().await.unwrap(), 0);
+
+        sqlx::query(
+            "INSERT INTO emails (subject, body_plain, from_address, internal_date) VALUES ('残金決済完了', '注文番号 FN-000123', 'shop@fnex.jp', 1710000000000)",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+        assert_eq!(repo.reconcile_from_emails().await.unwrap(), 1);
+        let pending = repo.list_pending().await.unwrap();
+        assert!(pending.iter().all(|p| p.id != balance));
+    }
+
+    #[tokio::test]
+    async fn test_set_status_and_delete() {
+        let pool = setup_test_db().await;
+        sqlx::query(
+            "INSERT INTO orders (id, order_number, shop_name) VALUES (1, 'ORD-1', 'A'), (2, NULL, 'B')",
+        )
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        let repo = SqlitePaymentRepository::new(pool);
+        let id = repo.add(1, None, 5000, None).await.unwrap();
+        assert!(repo.add(99, None, 5000, None).await.is_err());
+        assert!(repo.add(2, None, 5000, None).await.is_err());
+
+        repo.set_status(id, PaymentStatus::Paid).await.unwrap();
+        let payments = repo.list_by_order(1).await.unwrap();
+        assert_eq!(payments[0].status, PaymentStatus::Paid);
+        assert!(payments[0].paid_at.is_some());
+        assert_eq!(payments[0].order_number.as_deref(), Some("ORD-1"));
+
+        repo.set_status(id, PaymentStatus::Pending).await.unwrap();
+        let pending = repo.list_pending().await.unwrap();
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].paid_at, None);
+
+        repo.delete(id).await.unwrap();
+        assert!(repo.list_by_order(1).await.unwrap().is_empty());
+        assert!(repo.delete(id).await.is_err());
+        assert!(repo.set_status(id, PaymentStatus::Paid).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_payments_survive_full_reparse() {
+        use crate::repository::{ParseRepository, SqliteParseRepository};
+
+        let pool = setup_test_db().await;
+        sqlx::raw_sql(
+            r#"
+            PRAGMA foreign_keys = ON;
+            CREATE TABLE items (id INTEGER PRIMARY KEY AUTOINCREMENT, order_id INTEGER NOT NULL);
+            CREATE TABLE deliveries (id INTEGER PRIMARY KEY AUTOINCREMENT, order_id INTEGER NOT NULL);
+            CREATE TABLE order_emails (order_id INTEGER NOT NULL, email_id INTEGER NOT NULL);
+            INSERT INTO orders (id, shop_domain, shop_name, order_number) VALUES
+                (1, 'fnex.jp', 'F:NEX', 'FN-000123');
+            "#,
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let repo = SqlitePaymentRepository::new(pool.clone());
+        let id = repo
+            .add(1, Some("残金"), 12800, Some("2024-05-31"))
+            .await
+            .unwrap();
+        repo.set_status(id, PaymentStatus::Paid).await.unwrap();
+
+        // 全件再パース: 注文関連テーブルを消して、同じ注文を別の id で作り直す
+        SqliteParseRepository::new(pool.clone())
+            .clear_order_tables()
+            .await
+            .unwrap();
+        sqlx::query(
+            "INSERT INTO orders (id, shop_domain, shop_name, order_number) VALUES (5, 'fnex.jp', 'F:NEX', 'fn-000123')",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let payments = repo.list_by_order(5).await.unwrap();
+        assert_eq!(payments.len(), 1);
+        assert_eq!(payments[0].id, id);
+        assert_eq!(payments[0].order_id, 5);
+        assert_eq!(payments[0].status, PaymentStatus::Paid);
+        assert_eq!(payments[0].label.as_deref(), Some("残金"));
+    }
+}
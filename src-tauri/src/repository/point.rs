@@ -0,0 +1,440 @@
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use sqlx::sqlite::SqlitePool;
+use std::collections::BTreeMap;
+use ts_rs::TS;
+
+/// ポイントの増減の種類
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(export)]
+pub enum PointKind {
+    /// 付与（獲得）
+    Earned,
+    /// 利用（値引き）
+    Used,
+}
+
+impl PointKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Earned => "earned",
+            Self::Used => "used",
+        }
+    }
+}
+
+/// ポイントの確定状態
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(export)]
+pub enum PointStatus {
+    /// 付与予定（発送後付与など、まだ残高に入っていないもの）
+    Pending,
+    /// 確定
+    Confirmed,
+}
+
+impl PointStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Pending => "pending",
+            Self::Confirmed => "confirmed",
+        }
+    }
+}
+
+/// ショップごとの推定ポイント残高
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct ShopPointBalance {
+    pub shop_domain: Option<String>,
+    pub shop_name: Option<String>,
+    /// 確定済みの付与ポイント合計
+    pub earned_points: i64,
+    /// 利用ポイント合計
+    pub used_points: i64,
+    /// 付与予定（未確定）のポイント合計
+    pub pending_points: i64,
+    /// 推定残高（確定付与 − 利用）
+    pub balance: i64,
+}
+
+/// `ポイント利用:500` / `ポイント利用「500」ポイント` / `ポイント値引き：-500ポイント` / `ご利用ポイント：500pt`
+static USED_POINTS_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?:ご?利用ポイント|ポイント(?:ご?利用|値引き?))数?\s*[：:「]?\s*[-－]?\s*([\d,]+)")
+        .expect("USED_POINTS_RE")
+});
+
+/// `獲得予定ポイント：52pt` / `ポイント付与予定：52` / `付与予定ポイント 52`
+static PENDING_EARNED_POINTS_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"(?:(?:獲得|付与|加算)予定ポイント|ポイント(?:獲得|付与|加算)予定)数?\s*[：:「]?\s*([\d,]+)",
+    )
+    .expect("PENDING_EARNED_POINTS_RE")
+});
+
+/// `獲得ポイント：52pt` / `付与ポイント：52` / `ポイント付与数 52`
+static EARNED_POINTS_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"(?:(?:獲得|付与|加算)ポイント|ポイント(?:獲得|付与|加算))数?\s*[：:「]?\s*([\d,]+)",
+    )
+    .expect("EARNED_POINTS_RE")
+});
+
+/// 付与が発送後であることを示す表記（`獲得ポイント` の記載でも付与予定として扱う）
+const PENDING_KEYWORDS: &[&str] = &["発送後に付与", "発送後付与", "出荷後に付与", "出荷後付与"];
+
+/// メールから読み取ったポイントの増減
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PointNotice {
+    /// 利用ポイント（記載がない・0 の場合は None）
+    pub used_points: Option<i64>,
+    /// 付与ポイントと確定状態（記載がない・0 の場合は None）
+    pub earned: Option<(i64, PointStatus)>,
+}
+
+fn capture_points(re: &Regex, text: &str) -> Option<i64> {
+    re.captures(text)
+        .and_then(|c| c[1].replace(',', "").parse().ok())
+        .filter(|points| *points > 0)
+}
+
+/// メールの件名・本文からポイントの利用・付与を読み取る（どちらも記載がなければ None）
+pub fn detect_point_notice(text: &str) -> Option<PointNotice> {
+    let used_points = capture_points(&USED_POINTS_RE, text);
+    let earned = match capture_points(&PENDING_EARNED_POINTS_RE, text) {
+        Some(points) => Some((points, PointStatus::Pending)),
+        None => capture_points(&EARNED_POINTS_RE, text).map(|points| {
+            if PENDING_KEYWORDS.iter().any(|k| text.contains(k)) {
+                (points, PointStatus::Pending)
+            } else {
+                (points, PointStatus::Confirmed)
+            }
+        }),
+    };
+
+    if used_points.is_none() && earned.is_none() {
+        return None;
+    }
+    Some(PointNotice {
+        used_points,
+        earned,
+    })
+}
+
+/// 注文ごとのポイント（注文確認・発送メールで同じ値が繰り返し記載されるため、最新のメールの値を採用する）
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct PointEntry {
+    points: i64,
+    status: PointStatus,
+    email_id: i64,
+}
+
+/// 受信順のメールから注文・種類ごとの最終的なポイントを決める。確定済みの付与は付与予定に戻さない。
+fn merge_point_notices(
+    notices: Vec<(i64, i64, PointNotice)>,
+) -> BTreeMap<(i64, PointKind), PointEntry> {
+    let mut entries: BTreeMap<(i64, PointKind), PointEntry> = BTreeMap::new();
+    for (order_id, email_id, notice) in notices {
+        let changes = notice
+            .used_points
+            .map(|points| (PointKind::Used, points, PointStatus::Confirmed))
+            .into_iter()
+            .chain(
+                notice
+                    .earned
+                    .map(|(points, status)| (PointKind::Earned, points, status)),
+            );
+        for (kind, points, status) in changes {
+            let status = match entries.get(&(order_id, kind)) {
+                Some(prev) if prev.status == PointStatus::Confirmed => PointStatus::Confirmed,
+                _ => status,
+            };
+            entries.insert(
+                (order_id, kind),
+                PointEntry {
+                    points,
+                    status,
+                    email_id,
+                },
+            );
+        }
+    }
+    entries
+}
+
+/// 会員ポイントのDB操作
+pub struct SqlitePointRepository {
+    pool: SqlitePool,
+}
+
+impl SqlitePointRepository {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    /// 注文番号を含む同一ショップのメールを受信順に走査し、注文ごとのポイント利用・付与を記録する
+    ///
+    /// 付与予定のポイントは、後続のメールで確定の記載（`獲得ポイント` 等）があれば確定に更新する。
+    /// 追加・更新した件数を返す。
+    pub async fn collect_from_emails(&self) -> Result<usize, String> {
+        let rows: Vec<(i64, i64, String)> = sqlx::query_as(
+            r#"
+            SELECT o.id, e.id,
+                   COALESCE(e.subject, '') || char(10) || COALESCE(e.body_plain, '') AS text
+            FROM orders o
+            INNER JOIN emails e
+                ON e.body_plain LIKE '%' || o.order_number || '%'
+               AND e.from_address LIKE '%' || o.shop_domain || '%'
+            WHERE o.order_number IS NOT NULL
+              AND length(o.order_number) >= 5
+              AND o.shop_domain IS NOT NULL
+              AND o.deleted_at IS NULL
+              AND e.body_plain LIKE '%ポイント%'
+            ORDER BY o.id, e.internal_date ASC, e.id ASC
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| format!("Failed to fetch point notices: {e}"))?;
+
+        let notices: Vec<(i64, i64, PointNotice)> = rows
+            .into_iter()
+            .filter_map(|(order_id, email_id, text)| {
+                detect_point_notice(&text).map(|notice| (order_id, email_id, notice))
+            })
+            .collect();
+        let entries = merge_point_notices(notices);
+
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| format!("Failed to start transaction: {e}"))?;
+        let mut changed = 0usize;
+        for ((order_id, kind), entry) in entries {
+            let result = sqlx::query(
+                r#"
+                INSERT INTO point_transactions (order_id, kind, points, status, email_id)
+                VALUES (?, ?, ?, ?, ?)
+                ON CONFLICT(order_id, kind) DO UPDATE SET
+                    points = excluded.points,
+                    status = CASE WHEN point_transactions.status = 'confirmed'
+                                  THEN 'confirmed' ELSE excluded.status END,
+                    email_id = excluded.email_id,
+                    updated_at = CURRENT_TIMESTAMP
+                WHERE point_transactions.points != excluded.points
+                   OR (point_transactions.status = 'pending' AND excluded.status = 'confirmed')
+                "#,
+            )
+            .bind(order_id)
+            .bind(kind.as_str())
+            .bind(entry.points)
+            .bind(entry.status.as_str())
+            .bind(entry.email_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| format!("Failed to save point transaction: {e}"))?;
+            changed += result.rows_affected() as usize;
+        }
+        tx.commit()
+            .await
+            .map_err(|e| format!("Failed to commit transaction: {e}"))?;
+
+        Ok(changed)
+    }
+
+    /// ショップごとの推定ポイント残高を取得する
+    ///
+    /// 削除済みの注文と、全商品がキャンセルされた注文（有効な商品が残っていないもの）は除く。
+    pub async fn list_balances(&self) -> Result<Vec<ShopPointBalance>, String> {
+        let rows: Vec<(Option<String>, Option<String>, i64, i64, i64)> = sqlx::query_as(
+            r#"
+            SELECT o.shop_domain, MAX(o.shop_name),
+                   COALESCE(SUM(CASE WHEN p.kind = 'earned' AND p.status = 'confirmed' THEN p.points END), 0),
+                   COALESCE(SUM(CASE WHEN p.kind = 'used' THEN p.points END), 0),
+                   COALESCE(SUM(CASE WHEN p.kind = 'earned' AND p.status = 'pending' THEN p.points END), 0)
+            FROM point_transactions p
+            INNER JOIN orders o ON o.id = p.order_id
+            WHERE o.deleted_at IS NULL
+              AND EXISTS (
+                  SELECT 1 FROM items i WHERE i.order_id = o.id AND i.deleted_at IS NULL
+              )
+            GROUP BY o.shop_domain
+            ORDER BY MAX(o.shop_name), o.shop_domain
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| format!("Failed to fetch point balances: {e}"))?;
+
+        Ok(rows
+            .into_iter()
+            .map(
+                |(shop_domain, shop_name, earned_points, used_points, pending_points)| {
+                    ShopPointBalance {
+                        shop_domain,
+                        shop_name,
+                        earned_points,
+                        used_points,
+                        pending_points,
+                        balance: earned_points - used_points,
+                    }
+                },
+            )
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sqlx::sqlite::SqlitePoolOptions;
+
+    async fn setup_test_db() -> SqlitePool {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .expect("Failed to create test database");
+
+        sqlx::query(
+            r#"
+            CREATE TABLE orders (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                shop_domain TEXT,
+                shop_name TEXT,
+                order_number TEXT,
+                deleted_at DATETIME
+            );
+            CREATE TABLE items (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                order_id INTEGER NOT NULL,
+                item_name TEXT NOT NULL,
+                deleted_at DATETIME
+            );
+            CREATE TABLE emails (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                subject TEXT,
+                body_plain TEXT,
+                from_address TEXT,
+                internal_date INTEGER
+            );
+            "#,
+        )
+        .execute(&pool)
+        .await
+        .expect("Failed to create tables");
+        sqlx::query(include_str!("../../migrations/028_point_transactions.sql"))
+            .execute(&pool)
+            .await
+            .expect("Failed to create point_transactions table");
+
+        pool
+    }
+
+    #[test]
+    fn test_detect_point_notice() {
+        assert_eq!(
+            detect_point_notice("ポイント利用「300」ポイント\n獲得ポイント：52pt"),
+            Some(PointNotice {
+                used_points: Some(300),
+                earned: Some((52, PointStatus::Confirmed)),
+            })
+        );
+        assert_eq!(
+            detect_point_notice("ポイント値引き：-1,200ポイント\n獲得予定ポイント：88pt"),
+            Some(PointNotice {
+                used_points: Some(1200),
+                earned: Some((88, PointStatus::Pending)),
+            })
+        );
+        // 獲得ポイントの記載でも発送後付与なら付与予定
+        assert_eq!(
+            detect_point_notice("獲得ポイント：52pt\n※ポイントは商品発送後に付与されます"),
+            Some(PointNotice {
+                used_points: None,
+                earned: Some((52, PointStatus::Pending)),
+            })
+        );
+        assert_eq!(detect_point_notice("ポイント利用:0"), None);
+        assert_eq!(detect_point_notice("ご注文ありがとうございます"), None);
+    }
+
+    #[test]
+    fn test_merge_point_notices_keeps_confirmed() {
+        let pending = PointNotice {
+            used_points: None,
+            earned: Some((52, PointStatus::Pending)),
+        };
+        let confirmed = PointNotice {
+            used_points: None,
+            earned: Some((52, PointStatus::Confirmed)),
+        };
+        let entries =
+            merge_point_notices(vec![(1, 10, pending), (1, 11, confirmed), (1, 12, pending)]);
+        let entry = entries[&(1, PointKind::Earned)];
+        assert_eq!(entry.status, PointStatus::Confirmed);
+        assert_eq!(entry.email_id, 12);
+    }
+
+    #[tokio::test]
+    async fn test_collect_from_emails_and_list_balances() {
+        let pool = setup_test_db().await;
+        sqlx::query(
+            r#"
+            INSERT INTO orders (id, shop_domain, shop_name, order_number, deleted_at) VALUES
+                (1, 'animate-onlineshop.jp', 'アニメイト', 'AN-000123', NULL),
+                (2, 'animate-onlineshop.jp', 'アニメイト', 'AN-000456', NULL),
+                (3, 'animate-onlineshop.jp', 'アニメイト', 'AN-000789', '2025-01-01');
+            INSERT INTO items (order_id, item_name) VALUES (1, '商品1'), (2, '商品2'), (3, '商品3');
+            INSERT INTO emails (subject, body_plain, from_address, internal_date) VALUES
+                ('ご注文確認', '注文番号 AN-000123\nポイント利用:200\n獲得予定ポイント：50pt', 'info@animate-onlineshop.jp', 1700000000000),
+                ('ご注文確認', '注文番号 AN-000456\n獲得予定ポイント：30pt', 'info@animate-onlineshop.jp', 1700000050000),
+                ('発送のお知らせ', '注文番号 AN-000123\nポイント利用:200\n獲得ポイント：50pt', 'info@animate-onlineshop.jp', 1700000100000),
+                ('ご注文確認', '注文番号 AN-000789\n獲得ポイント：999pt', 'info@animate-onlineshop.jp', 1700000200000);
+            "#,
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let repo = SqlitePointRepository::new(pool.clone());
+        // 注文1の利用・付与、注文2の付与予定（削除済みの注文3は対象外）
+        assert_eq!(repo.collect_from_emails().await.unwrap(), 3);
+        // 変化がなければ更新しない
+        assert_eq!(repo.collect_from_emails().await.unwrap(), 0);
+
+        let balances = repo.list_balances().await.unwrap();
+        assert_eq!(balances.len(), 1);
+        let balance = &balances[0];
+        assert_eq!(balance.shop_name.as_deref(), Some("アニメイト"));
+        assert_eq!(balance.earned_points, 50);
+        assert_eq!(balance.used_points, 200);
+        assert_eq!(balance.pending_points, 30);
+        assert_eq!(balance.balance, -150);
+
+        // 注文2の発送メールで付与が確定する
+        sqlx::query(
+            "INSERT INTO emails (subject, body_plain, from_address, internal_date) VALUES ('発送のお知らせ', '注文番号 AN-000456\n獲得ポイント：30pt', 'info@animate-onlineshop.jp', 1700000300000)",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+        assert_eq!(repo.collect_from_emails().await.unwrap(), 1);
+        let balance = &repo.list_balances().await.unwrap()[0];
+        assert_eq!(balance.earned_points, 80);
+        assert_eq!(balance.pending_points, 0);
+
+        // 全商品キャンセルの注文は残高に含めない
+        sqlx::query("UPDATE items SET deleted_at = '2025-01-01' WHERE order_id = 1")
+            .execute(&pool)
+            .await
+            .unwrap();
+        let balance = &repo.list_balances().await.unwrap()[0];
+        assert_eq!(balance.used_points, 0);
+        assert_eq!(balance.balance, 30);
+    }
+}
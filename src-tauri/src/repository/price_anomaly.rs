@@ -0,0 +1,245 @@
+use serde::{Deserialize, Serialize};
+use sqlx::sqlite::SqlitePool;
+use std::collections::BTreeSet;
+use ts_rs::TS;
+
+/// 過去平均価格に対してこの倍率以上の購入を価格異常とみなす（デフォルト）
+pub const DEFAULT_PRICE_ANOMALY_RATIO: f64 = 1.5;
+
+/// 価格異常として検出された商品
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct PriceAnomaly {
+    pub order_id: i64,
+    pub item_id: i64,
+    pub item_name: String,
+    pub item_name_normalized: String,
+    pub price: i64,
+    /// 同一 normalized_name の過去購入の平均単価
+    pub reference_price: f64,
+    /// price / reference_price
+    pub ratio: f64,
+}
+
+type PriceCandidateRow = (i64, i64, String, String, i64, Option<f64>);
+
+/// 単価が基準価格の `ratio` 倍以上なら価格異常とみなす
+pub fn is_price_anomaly(price: i64, reference_price: f64, ratio: f64) -> bool {
+    reference_price > 0.0 && price as f64 >= reference_price * ratio
+}
+
+/// 価格異常検知のDB操作
+pub struct SqlitePriceAnomalyRepository {
+    pool: SqlitePool,
+}
+
+impl SqlitePriceAnomalyRepository {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    /// 同一 normalized_name の過去購入（注文日がより前のもの）の平均単価と比較して価格異常を検出し、
+    /// 該当注文の `orders.price_warning` を 1、該当しなくなった注文を 0 に更新する。
+    pub async fn detect_and_flag(&self, ratio: f64) -> Result<Vec<PriceAnomaly>, String> {
+        let rows: Vec<PriceCandidateRow> = sqlx::query_as(
+            r#"
+            SELECT
+                i.id,
+                i.order_id,
+                i.item_name,
+                i.item_name_normalized,
+                i.price,
+                (
+                    SELECT AVG(p.price)
+                    FROM items p
+                    INNER JOIN orders po ON po.id = p.order_id
+                    WHERE p.item_name_normalized = i.item_name_normalized
+                      AND p.id != i.id
+                      AND p.price > 0
+                      AND p.deleted_at IS NULL
+                      AND COALESCE(po.order_date, po.created_at) < COALESCE(o.order_date, o.created_at)
+                ) AS reference_price
+            FROM items i
+            INNER JOIN orders o ON o.id = i.order_id
+            WHERE i.item_name_normalized IS NOT NULL
+              AND i.price > 0
+              AND i.deleted_at IS NULL
+            ORDER BY i.order_id, i.id
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| format!("Failed to fetch item prices: {e}"))?;
+
+        let anomalies: Vec<PriceAnomaly> = rows
+            .into_iter()
+            .filter_map(
+                |(item_id, order_id, item_name, item_name_normalized, price, reference_price)| {
+                    let reference_price = reference_price?;
+                    if !is_price_anomaly(price, reference_price, ratio) {
+                        return None;
+                    }
+                    Some(PriceAnomaly {
+                        order_id,
+                        item_id,
+                        item_name,
+                        item_name_normalized,
+                        price,
+                        reference_price,
+                        ratio: price as f64 / reference_price,
+                    })
+                },
+            )
+            .collect();
+
+        let flagged: BTreeSet<i64> = anomalies.iter().map(|a| a.order_id).collect();
+        let previously_flagged: Vec<i64> =
+            sqlx::query_scalar("SELECT id FROM orders WHERE price_warning = 1")
+                .fetch_all(&self.pool)
+                .await
+                .map_err(|e| format!("Failed to fetch flagged orders: {e}"))?;
+
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| format!("Failed to start transaction: {e}"))?;
+        for id in previously_flagged
+            .iter()
+            .filter(|id| !flagged.contains(*id))
+        {
+            sqlx::query("UPDATE orders SET price_warning = 0 WHERE id = ?")
+                .bind(id)
+                .execute(&mut *tx)
+                .await
+                .map_err(|e| format!("Failed to clear price warning: {e}"))?;
+        }
+        for id in flagged
+            .iter()
+            .filter(|id| !previously_flagged.contains(*id))
+        {
+            sqlx::query("UPDATE orders SET price_warning = 1 WHERE id = ?")
+                .bind(id)
+                .execute(&mut *tx)
+                .await
+                .map_err(|e| format!("Failed to set price warning: {e}"))?;
+        }
+        tx.commit()
+            .await
+            .map_err(|e| format!("Failed to commit transaction: {e}"))?;
+
+        Ok(anomalies)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sqlx::sqlite::SqlitePoolOptions;
+
+    async fn setup_test_db() -> SqlitePool {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .expect("Failed to create test database");
+
+        sqlx::query(
+            r#"
+            CREATE TABLE orders (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                order_date DATETIME,
+                created_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+                price_warning INTEGER NOT NULL DEFAULT 0,
+                deleted_at DATETIME
+            );
+            CREATE TABLE items (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                order_id INTEGER NOT NULL,
+                item_name TEXT NOT NULL,
+                item_name_normalized TEXT,
+                price INTEGER NOT NULL DEFAULT 0,
+                quantity INTEGER NOT NULL DEFAULT 1,
+                deleted_at DATETIME
+            );
+            "#,
+        )
+        .execute(&pool)
+        .await
+        .expect("Failed to create tables");
+
+        pool
+    }
+
+    #[test]
+    fn test_is_price_anomaly() {
+        assert!(is_price_anomaly(15000, 10000.0, 1.5));
+        assert!(!is_price_anomaly(14999, 10000.0, 1.5));
+        assert!(!is_price_anomaly(15000, 0.0, 1.5));
+    }
+
+    #[tokio::test]
+    async fn test_detect_and_flag_marks_overpriced_order() {
+        let pool = setup_test_db().await;
+        sqlx::query(
+            r#"
+            INSERT INTO orders (id, order_date) VALUES
+                (1, '2024-01-01'), (2, '2024-02-01'), (3, '2024-03-01'), (4, '2024-04-01');
+            INSERT INTO items (order_id, item_name, item_name_normalized, price) VALUES
+                (1, 'フィギュアA', 'figure_a', 10000),
+                (2, 'フィギュアA 再販', 'figure_a', 10000),
+                (3, 'フィギュアA 中古', 'figure_a', 25000),
+                (4, 'フィギュアB', 'figure_b', 99999);
+            "#,
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let repo = SqlitePriceAnomalyRepository::new(pool.clone());
+        let anomalies = repo
+            .detect_and_flag(DEFAULT_PRICE_ANOMALY_RATIO)
+            .await
+            .unwrap();
+
+        assert_eq!(anomalies.len(), 1);
+        assert_eq!(anomalies[0].order_id, 3);
+        assert_eq!(anomalies[0].reference_price, 10000.0);
+        assert_eq!(anomalies[0].ratio, 2.5);
+
+        let flagged: Vec<i64> =
+            sqlx::query_scalar("SELECT id FROM orders WHERE price_warning = 1 ORDER BY id")
+                .fetch_all(&pool)
+                .await
+                .unwrap();
+        assert_eq!(flagged, vec![3]);
+    }
+
+    #[tokio::test]
+    async fn test_detect_and_flag_clears_stale_flags() {
+        let pool = setup_test_db().await;
+        sqlx::query(
+            r#"
+            INSERT INTO orders (id, order_date, price_warning) VALUES
+                (1, '2024-01-01', 0), (2, '2024-02-01', 1);
+            INSERT INTO items (order_id, item_name, item_name_normalized, price) VALUES
+                (1, 'A', 'a', 10000),
+                (2, 'A', 'a', 11000);
+            "#,
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let repo = SqlitePriceAnomalyRepository::new(pool.clone());
+        let anomalies = repo.detect_and_flag(1.5).await.unwrap();
+        assert!(anomalies.is_empty());
+
+        let flagged: i64 =
+            sqlx::query_scalar("SELECT COUNT(*) FROM orders WHERE price_warning = 1")
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+        assert_eq!(flagged, 0);
+    }
+}
@@ -4,6 +4,7 @@ use async_trait::async_trait;
 use mockall::automock;
 use serde::{Deserialize, Serialize};
 use sqlx::sqlite::SqlitePool;
+use ts_rs::TS;
 
 /// 商品マスタ一覧・検索用フィルター
 #[derive(Debug, Default, Clone, Serialize, Deserialize)]
@@ -17,7 +18,8 @@ pub struct ProductMasterFilter {
 }
 
 /// ProductMaster エンティティ
-#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow, TS)]
+#[ts(export)]
 pub struct ProductMaster {
     pub id: i64,
     pub raw_name: String,
@@ -120,6 +122,7 @@ impl SqliteProductMasterRepository {
             WHERE i.item_name IS NOT NULL
               AND i.item_name != ''
               AND TRIM(i.item_name) != ''
+              AND i.deleted_at IS NULL
               AND pm.id IS NULL
             GROUP BY TRIM(i.item_name)
             "#,
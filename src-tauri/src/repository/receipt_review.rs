@@ -0,0 +1,331 @@
+use serde::{Deserialize, Serialize};
+use sqlx::sqlite::SqlitePool;
+use ts_rs::TS;
+
+/// 到着後アンケート（商品受け取り評価）
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct ItemReceiptReview {
+    pub item_id: i64,
+    /// 満足度（1〜5）
+    pub satisfaction: i64,
+    /// 破損・不良があったか
+    pub damaged: bool,
+    pub note: Option<String>,
+    pub updated_at: String,
+}
+
+/// ショップ別の受け取り評価の集計
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct ShopDamageStats {
+    pub shop_domain: String,
+    pub shop_name: Option<String>,
+    /// 評価済みの商品数
+    pub review_count: i64,
+    /// 破損・不良があった商品数
+    pub damaged_count: i64,
+    /// 破損率（0.0〜1.0）
+    pub damage_rate: f64,
+    /// 平均満足度
+    pub average_satisfaction: f64,
+}
+
+/// 評価対象の商品（items からビジネスキーを引く）
+type ReviewTargetRow = (String, String, String, Option<String>, bool);
+
+/// 到着後アンケートのDB操作
+pub struct SqliteReceiptReviewRepository {
+    pool: SqlitePool,
+}
+
+impl SqliteReceiptReviewRepository {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    /// 商品の評価を取得する（未評価なら None）
+    pub async fn get_by_item(&self, item_id: i64) -> Result<Option<ItemReceiptReview>, String> {
+        let row: Option<(i64, bool, Option<String>, String)> = sqlx::query_as(
+            r#"
+            SELECT r.satisfaction, r.damaged, r.note, r.updated_at
+            FROM items i
+            INNER JOIN orders o ON o.id = i.order_id
+            INNER JOIN item_receipt_reviews r
+                ON r.shop_domain = COALESCE(o.shop_domain, '')
+               AND r.order_number = o.order_number
+               AND r.item_name = i.item_name
+            WHERE i.id = ?
+            "#,
+        )
+        .bind(item_id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| format!("Failed to fetch receipt review: {e}"))?;
+
+        Ok(row.map(
+            |(satisfaction, damaged, note, updated_at)| ItemReceiptReview {
+                item_id,
+                satisfaction,
+                damaged,
+                note,
+                updated_at,
+            },
+        ))
+    }
+
+    /// 到着済みの商品に評価を記録する（記録済みなら上書き）
+    pub async fn upsert(
+        &self,
+        item_id: i64,
+        satisfaction: i64,
+        damaged: bool,
+        note: Option<&str>,
+    ) -> Result<(), String> {
+        let target: Option<ReviewTargetRow> = sqlx::query_as(
+            r#"
+            SELECT COALESCE(o.shop_domain, ''), o.order_number, i.item_name, o.shop_name,
+                   EXISTS (
+                       SELECT 1 FROM deliveries d
+                       WHERE d.order_id = o.id AND d.delivery_status = 'delivered'
+                   )
+            FROM items i
+            INNER JOIN orders o ON o.id = i.order_id
+            WHERE i.id = ? AND i.deleted_at IS NULL AND o.deleted_at IS NULL
+            "#,
+        )
+        .bind(item_id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| format!("Failed to fetch item: {e}"))?;
+
+        let Some((shop_domain, order_number, item_name, shop_name, delivered)) = target else {
+            return Err(format!("Item not found: {item_id}"));
+        };
+        if !delivered {
+            return Err("到着済みの商品のみ評価できます".to_string());
+        }
+
+        sqlx::query(
+            r#"
+            INSERT INTO item_receipt_reviews
+                (shop_domain, order_number, item_name, shop_name, satisfaction, damaged, note)
+            VALUES (?, ?, ?, ?, ?, ?, ?)
+            ON CONFLICT(shop_domain, order_number, item_name) DO UPDATE SET
+                shop_name = excluded.shop_name,
+                satisfaction = excluded.satisfaction,
+                damaged = excluded.damaged,
+                note = excluded.note,
+                updated_at = CURRENT_TIMESTAMP
+            "#,
+        )
+        .bind(shop_domain)
+        .bind(order_number)
+        .bind(item_name)
+        .bind(shop_name)
+        .bind(satisfaction)
+        .bind(damaged)
+        .bind(note)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| format!("Failed to save receipt review: {e}"))?;
+        Ok(())
+    }
+
+    /// 商品の評価を削除する
+    pub async fn delete_by_item(&self, item_id: i64) -> Result<(), String> {
+        let result = sqlx::query(
+            r#"
+            DELETE FROM item_receipt_reviews
+            WHERE id IN (
+                SELECT r.id
+                FROM items i
+                INNER JOIN orders o ON o.id = i.order_id
+                INNER JOIN item_receipt_reviews r
+                    ON r.shop_domain = COALESCE(o.shop_domain, '')
+                   AND r.order_number = o.order_number
+                   AND r.item_name = i.item_name
+                WHERE i.id = ?
+            )
+            "#,
+        )
+        .bind(item_id)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| format!("Failed to delete receipt review: {e}"))?;
+
+        if result.rows_affected() == 0 {
+            return Err(format!("Receipt review not found for item: {item_id}"));
+        }
+        Ok(())
+    }
+
+    /// ショップ別の破損率・平均満足度を破損率の高い順に集計する
+    pub async fn shop_damage_stats(&self) -> Result<Vec<ShopDamageStats>, String> {
+        let rows: Vec<(String, Option<String>, i64, i64, f64)> = sqlx::query_as(
+            r#"
+            SELECT shop_domain,
+                   MAX(shop_name),
+                   COUNT(*),
+                   SUM(damaged),
+                   AVG(satisfaction)
+            FROM item_receipt_reviews
+            GROUP BY shop_domain
+            ORDER BY CAST(SUM(damaged) AS REAL) / COUNT(*) DESC, COUNT(*) DESC, shop_domain
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| format!("Failed to fetch shop damage stats: {e}"))?;
+
+        Ok(rows
+            .into_iter()
+            .map(
+                |(shop_domain, shop_name, review_count, damaged_count, average_satisfaction)| {
+                    ShopDamageStats {
+                        shop_domain,
+                        shop_name,
+                        review_count,
+                        damaged_count,
+                        damage_rate: damaged_count as f64 / review_count as f64,
+                        average_satisfaction,
+                    }
+                },
+            )
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sqlx::sqlite::SqlitePoolOptions;
+
+    async fn setup_test_db() -> SqlitePool {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .expect("Failed to create test database");
+
+        sqlx::query(
+            r#"
+            CREATE TABLE orders (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                shop_domain TEXT,
+                shop_name TEXT,
+                order_number TEXT,
+                deleted_at DATETIME
+            );
+            CREATE TABLE items (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                order_id INTEGER NOT NULL,
+                item_name TEXT NOT NULL,
+                deleted_at DATETIME
+            );
+            CREATE TABLE deliveries (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                order_id INTEGER NOT NULL,
+                delivery_status TEXT NOT NULL DEFAULT 'not_shipped'
+            );
+            "#,
+        )
+        .execute(&pool)
+        .await
+        .expect("Failed to create tables");
+        sqlx::query(include_str!(
+            "../../migrations/025_item_receipt_reviews.sql"
+        ))
+        .execute(&pool)
+        .await
+        .expect("Failed to create item_receipt_reviews table");
+
+        sqlx::query(
+            r#"
+            INSERT INTO orders (id, shop_domain, shop_name, order_number) VALUES
+                (1, 'a.example.com', 'ショップA', 'A-1'),
+                (2, 'a.example.com', 'ショップA', 'A-2'),
+                (3, 'b.example.com', 'ショップB', 'B-1');
+            INSERT INTO items (id, order_id, item_name) VALUES
+                (1, 1, '商品1'), (2, 2, '商品2'), (3, 3, '商品3'), (4, 3, '商品4');
+            INSERT INTO deliveries (order_id, delivery_status) VALUES
+                (1, 'delivered'), (2, 'delivered'), (3, 'shipped');
+            "#,
+        )
+        .execute(&pool)
+        .await
+        .expect("Failed to insert test data");
+
+        pool
+    }
+
+    #[tokio::test]
+    async fn test_upsert_and_get_review() {
+        let pool = setup_test_db().await;
+        let repo = SqliteReceiptReviewRepository::new(pool);
+
+        assert_eq!(repo.get_by_item(1).await.unwrap(), None);
+        repo.upsert(1, 4, false, Some("箱潰れなし")).await.unwrap();
+        repo.upsert(1, 2, true, None).await.unwrap();
+
+        let review = repo.get_by_item(1).await.unwrap().unwrap();
+        assert_eq!(review.satisfaction, 2);
+        assert!(review.damaged);
+        assert_eq!(review.note, None);
+
+        repo.delete_by_item(1).await.unwrap();
+        assert_eq!(repo.get_by_item(1).await.unwrap(), None);
+        assert!(repo.delete_by_item(1).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_upsert_requires_delivered_item() {
+        let pool = setup_test_db().await;
+        let repo = SqliteReceiptReviewRepository::new(pool);
+
+        assert!(repo.upsert(3, 5, false, None).await.is_err());
+        assert!(repo.upsert(999, 5, false, None).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_review_survives_item_recreation() {
+        let pool = setup_test_db().await;
+        let repo = SqliteReceiptReviewRepository::new(pool.clone());
+        repo.upsert(1, 5, false, None).await.unwrap();
+
+        // 再パースで商品が作り直されても同じ注文・商品名なら評価を引き継ぐ
+        sqlx::query("DELETE FROM items WHERE id = 1")
+            .execute(&pool)
+            .await
+            .unwrap();
+        sqlx::query("INSERT INTO items (id, order_id, item_name) VALUES (10, 1, '商品1')")
+            .execute(&pool)
+            .await
+            .unwrap();
+        assert_eq!(repo.get_by_item(10).await.unwrap().unwrap().satisfaction, 5);
+    }
+
+    #[tokio::test]
+    async fn test_shop_damage_stats() {
+        let pool = setup_test_db().await;
+        sqlx::query("UPDATE deliveries SET delivery_status = 'delivered'")
+            .execute(&pool)
+            .await
+            .unwrap();
+        let repo = SqliteReceiptReviewRepository::new(pool);
+        repo.upsert(1, 5, false, None).await.unwrap();
+        repo.upsert(2, 2, true, None).await.unwrap();
+        repo.upsert(3, 4, false, None).await.unwrap();
+        repo.upsert(4, 4, false, None).await.unwrap();
+
+        let stats = repo.shop_damage_stats().await.unwrap();
+        assert_eq!(stats.len(), 2);
+        assert_eq!(stats[0].shop_domain, "a.example.com");
+        assert_eq!(stats[0].shop_name.as_deref(), Some("ショップA"));
+        assert_eq!((stats[0].review_count, stats[0].damaged_count), (2, 1));
+        assert_eq!(stats[0].damage_rate, 0.5);
+        assert_eq!(stats[0].average_satisfaction, 3.5);
+        assert_eq!(stats[1].shop_domain, "b.example.com");
+        assert_eq!(stats[1].damage_rate, 0.0);
+    }
+}
@@ -0,0 +1,454 @@
+use serde::{Deserialize, Serialize};
+use sqlx::sqlite::SqlitePool;
+use ts_rs::TS;
+
+use crate::gemini::product_parser::normalize_product_name;
+
+/// 再販・再入荷のアナウンスを示す表記
+const REISSUE_KEYWORDS: &[&str] = &[
+    "再販",
+    "再生産",
+    "再入荷",
+    "再出荷",
+    "再受注",
+    "受注再開",
+    "予約再開",
+    "販売再開",
+];
+
+/// 照合に使う正規化後のキーワードの最小文字数（短すぎると無関係なメールに一致するため）
+const MIN_NORMALIZED_KEYWORD_LEN: usize = 4;
+
+/// 再販ウォッチ
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct ReissueWatch {
+    pub id: i64,
+    pub keyword: String,
+    /// 所持済み商品から登録した場合の items.id
+    pub item_id: Option<i64>,
+    /// 所持済み（削除されていない商品に紐付いている）か
+    pub owned: bool,
+    pub detection_count: i64,
+    pub last_detected_at: Option<String>,
+    pub created_at: String,
+}
+
+/// 検出した再販アナウンス
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct ReissueDetection {
+    pub id: i64,
+    pub watch_id: i64,
+    pub keyword: String,
+    pub owned: bool,
+    /// `email` または `news`
+    pub source: String,
+    /// emails.id または記事URL
+    pub source_ref: String,
+    /// メール件名・記事タイトル
+    pub title: Option<String>,
+    pub detected_at: String,
+}
+
+type DetectionRow = (
+    i64,
+    i64,
+    String,
+    bool,
+    String,
+    String,
+    Option<String>,
+    String,
+);
+
+const DETECTION_SELECT: &str = r#"
+    SELECT d.id, d.watch_id, w.keyword,
+           EXISTS (SELECT 1 FROM items i WHERE i.id = w.item_id AND i.deleted_at IS NULL) AS owned,
+           d.source, d.source_ref, d.title, d.detected_at
+    FROM reissue_detections d
+    INNER JOIN reissue_watches w ON w.id = d.watch_id
+"#;
+
+fn detection_from_row(row: DetectionRow) -> ReissueDetection {
+    let (id, watch_id, keyword, owned, source, source_ref, title, detected_at) = row;
+    ReissueDetection {
+        id,
+        watch_id,
+        keyword,
+        owned,
+        source,
+        source_ref,
+        title,
+        detected_at,
+    }
+}
+
+/// テキスト（件名・本文）が再販・再入荷のアナウンスか
+pub fn is_reissue_announcement(text: &str) -> bool {
+    REISSUE_KEYWORDS.iter().any(|k| text.contains(k))
+}
+
+/// 再販アナウンスのテキストに、正規化済みのウォッチキーワードが含まれる ID を返す
+fn matching_watch_ids(text: &str, watches: &[(i64, String)]) -> Vec<i64> {
+    if !is_reissue_announcement(text) {
+        return Vec::new();
+    }
+    let normalized = normalize_product_name(text);
+    watches
+        .iter()
+        .filter(|(_, keyword)| normalized.contains(keyword.as_str()))
+        .map(|(id, _)| *id)
+        .collect()
+}
+
+/// 再販ウォッチ・検出結果のDB操作
+pub struct SqliteReissueRepository {
+    pool: SqlitePool,
+}
+
+impl SqliteReissueRepository {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    /// ウォッチを追加し、reissue_watches.id を返す（同じ商品名のウォッチがあればその ID を返す）
+    pub async fn add_watch(&self, keyword: &str, item_id: Option<i64>) -> Result<i64, String> {
+        let keyword = keyword.trim();
+        let normalized = normalize_product_name(keyword);
+        if normalized.chars().count() < MIN_NORMALIZED_KEYWORD_LEN {
+            return Err(format!(
+                "ウォッチする商品名は記号を除いて {MIN_NORMALIZED_KEYWORD_LEN} 文字以上にしてください: {keyword}"
+            ));
+        }
+
+        sqlx::query(
+            r#"
+            INSERT INTO reissue_watches (keyword, keyword_normalized, item_id)
+            VALUES (?, ?, ?)
+            ON CONFLICT(keyword_normalized) DO UPDATE SET
+                item_id = COALESCE(reissue_watches.item_id, excluded.item_id)
+            "#,
+        )
+        .bind(keyword)
+        .bind(&normalized)
+        .bind(item_id)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| format!("Failed to add reissue watch: {e}"))?;
+
+        sqlx::query_scalar("SELECT id FROM reissue_watches WHERE keyword_normalized = ?")
+            .bind(&normalized)
+            .fetch_one(&self.pool)
+            .await
+            .map_err(|e| format!("Failed to fetch reissue watch: {e}"))
+    }
+
+    /// 所持済みの商品をウォッチに追加する（商品名は削除されていない items から取得する）
+    pub async fn add_watch_for_item(&self, item_id: i64) -> Result<i64, String> {
+        let item_name: Option<String> =
+            sqlx::query_scalar("SELECT item_name FROM items WHERE id = ? AND deleted_at IS NULL")
+                .bind(item_id)
+                .fetch_optional(&self.pool)
+                .await
+                .map_err(|e| format!("Failed to fetch item: {e}"))?;
+        let item_name = item_name.ok_or_else(|| format!("Item not found: {item_id}"))?;
+        self.add_watch(&item_name, Some(item_id)).await
+    }
+
+    pub async fn delete_watch(&self, watch_id: i64) -> Result<(), String> {
+        let result = sqlx::query("DELETE FROM reissue_watches WHERE id = ?")
+            .bind(watch_id)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| format!("Failed to delete reissue watch: {e}"))?;
+        if result.rows_affected() == 0 {
+            return Err(format!("Reissue watch not found: {watch_id}"));
+        }
+        Ok(())
+    }
+
+    /// ウォッチ一覧を新しい順に返す
+    pub async fn list_watches(&self) -> Result<Vec<ReissueWatch>, String> {
+        let rows: Vec<(i64, String, Option<i64>, bool, i64, Option<String>, String)> =
+            sqlx::query_as(
+                r#"
+                SELECT w.id, w.keyword, w.item_id,
+                       EXISTS (SELECT 1 FROM items i WHERE i.id = w.item_id AND i.deleted_at IS NULL),
+                       (SELECT COUNT(*) FROM reissue_detections d WHERE d.watch_id = w.id),
+                       (SELECT MAX(d.detected_at) FROM reissue_detections d WHERE d.watch_id = w.id),
+                       w.created_at
+                FROM reissue_watches w
+                ORDER BY w.created_at DESC, w.id DESC
+                "#,
+            )
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| format!("Failed to fetch reissue watches: {e}"))?;
+
+        Ok(rows
+            .into_iter()
+            .map(
+                |(id, keyword, item_id, owned, detection_count, last_detected_at, created_at)| {
+                    ReissueWatch {
+                        id,
+                        keyword,
+                        item_id,
+                        owned,
+                        detection_count,
+                        last_detected_at,
+                        created_at,
+                    }
+                },
+            )
+            .collect())
+    }
+
+    /// 検出結果を新しい順に最大 `limit` 件返す
+    pub async fn list_detections(&self, limit: i64) -> Result<Vec<ReissueDetection>, String> {
+        let sql = format!("{DETECTION_SELECT} ORDER BY d.detected_at DESC, d.id DESC LIMIT ?");
+        let rows: Vec<DetectionRow> = sqlx::query_as(&sql)
+            .bind(limit)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| format!("Failed to fetch reissue detections: {e}"))?;
+        Ok(rows.into_iter().map(detection_from_row).collect())
+    }
+
+    /// メール・ニュースクリップから再販アナウンスを検出して記録し、新たに記録した件数を返す
+    ///
+    /// 件名・本文（ニュースはタイトル・要約）に再販を示す表記とウォッチ中の商品名の両方を含むものを対象とする。
+    pub async fn detect(&self) -> Result<usize, String> {
+        let watches: Vec<(i64, String)> =
+            sqlx::query_as("SELECT id, keyword_normalized FROM reissue_watches")
+                .fetch_all(&self.pool)
+                .await
+                .map_err(|e| format!("Failed to fetch reissue watches: {e}"))?;
+        if watches.is_empty() {
+            return Ok(0);
+        }
+
+        let keyword_filter = |columns: &[&str]| {
+            columns
+                .iter()
+                .flat_map(|c| {
+                    REISSUE_KEYWORDS
+                        .iter()
+                        .map(move |k| format!("{c} LIKE '%{k}%'"))
+                })
+                .collect::<Vec<_>>()
+                .join(" OR ")
+        };
+
+        let emails: Vec<(i64, Option<String>, String)> = sqlx::query_as(&format!(
+            r#"
+            SELECT id, subject, COALESCE(subject, '') || char(10) || COALESCE(body_plain, '')
+            FROM emails
+            WHERE {}
+            "#,
+            keyword_filter(&["subject", "body_plain"])
+        ))
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| format!("Failed to fetch reissue emails: {e}"))?;
+
+        let news: Vec<(String, String, String)> = sqlx::query_as(&format!(
+            r#"
+            SELECT url, title, title || char(10) || COALESCE(summary, '')
+            FROM news_clips
+            WHERE {}
+            "#,
+            keyword_filter(&["title", "summary"])
+        ))
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| format!("Failed to fetch reissue news: {e}"))?;
+
+        let candidates = emails
+            .into_iter()
+            .map(|(id, subject, text)| ("email", id.to_string(), subject, text))
+            .chain(
+                news.into_iter()
+                    .map(|(url, title, text)| ("news", url, Some(title), text)),
+            );
+
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| format!("Failed to start transaction: {e}"))?;
+        let mut inserted = 0;
+        for (source, source_ref, title, text) in candidates {
+            for watch_id in matching_watch_ids(&text, &watches) {
+                let result = sqlx::query(
+                    r#"
+                    INSERT OR IGNORE INTO reissue_detections (watch_id, source, source_ref, title)
+                    VALUES (?, ?, ?, ?)
+                    "#,
+                )
+                .bind(watch_id)
+                .bind(source)
+                .bind(&source_ref)
+                .bind(&title)
+                .execute(&mut *tx)
+                .await
+                .map_err(|e| format!("Failed to save reissue detection: {e}"))?;
+                inserted += result.rows_affected() as usize;
+            }
+        }
+        tx.commit()
+            .await
+            .map_err(|e| format!("Failed to commit transaction: {e}"))?;
+
+        Ok(inserted)
+    }
+
+    /// 未通知の検出結果を返す
+    pub async fn list_unnotified(&self) -> Result<Vec<ReissueDetection>, String> {
+        let sql = format!("{DETECTION_SELECT} WHERE d.notified = 0 ORDER BY d.id");
+        let rows: Vec<DetectionRow> = sqlx::query_as(&sql)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| format!("Failed to fetch unnotified reissue detections: {e}"))?;
+        Ok(rows.into_iter().map(detection_from_row).collect())
+    }
+
+    pub async fn mark_notified(&self, detection_ids: &[i64]) -> Result<(), String> {
+        if detection_ids.is_empty() {
+            return Ok(());
+        }
+        let placeholders = vec!["?"; detection_ids.len()].join(", ");
+        let sql =
+            format!("UPDATE reissue_detections SET notified = 1 WHERE id IN ({placeholders})");
+        let mut query = sqlx::query(&sql);
+        for id in detection_ids {
+            query = query.bind(id);
+        }
+        query
+            .execute(&self.pool)
+            .await
+            .map_err(|e| format!("Failed to mark reissue detections notified: {e}"))?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sqlx::sqlite::SqlitePoolOptions;
+
+    async fn setup_test_db() -> SqlitePool {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .expect("Failed to create test database");
+
+        sqlx::query(
+            r#"
+            CREATE TABLE emails (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                subject TEXT,
+                body_plain TEXT
+            );
+            CREATE TABLE items (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                item_name TEXT NOT NULL,
+                deleted_at DATETIME
+            );
+            CREATE TABLE news_clips (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                title TEXT NOT NULL,
+                url TEXT NOT NULL UNIQUE,
+                summary TEXT
+            );
+            "#,
+        )
+        .execute(&pool)
+        .await
+        .expect("Failed to create tables");
+        sqlx::query(include_str!("../../migrations/018_reissue_watches.sql"))
+            .execute(&pool)
+            .await
+            .expect("Failed to create reissue tables");
+
+        pool
+    }
+
+    #[test]
+    fn test_matching_watch_ids() {
+        let watches = vec![
+            (1, normalize_product_name("ねんどろいど 初音ミク")),
+            (2, normalize_product_name("HG 1/144 ガンダムエアリアル")),
+        ];
+        assert_eq!(
+            matching_watch_ids("【再販】ねんどろいど　初音ミク 予約受付中", &watches),
+            vec![1]
+        );
+        // 再販の表記がなければ一致しない
+        assert!(matching_watch_ids("ねんどろいど 初音ミク 発送のお知らせ", &watches).is_empty());
+        assert!(matching_watch_ids("再入荷のお知らせ: HGUC ザク", &watches).is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_add_watch_rejects_short_keyword_and_dedups() {
+        let pool = setup_test_db().await;
+        let repo = SqliteReissueRepository::new(pool);
+
+        assert!(repo.add_watch(" !? ", None).await.is_err());
+        let id = repo.add_watch("ねんどろいど 初音ミク", None).await.unwrap();
+        assert_eq!(
+            repo.add_watch("ねんどろいど　初音ミク", None)
+                .await
+                .unwrap(),
+            id
+        );
+        assert_eq!(repo.list_watches().await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_detect_from_emails_and_news() {
+        let pool = setup_test_db().await;
+        sqlx::query(
+            r#"
+            INSERT INTO items (id, item_name) VALUES (1, 'ねんどろいど 初音ミク');
+            INSERT INTO emails (id, subject, body_plain) VALUES
+                (1, '【再販】予約受付開始のお知らせ', 'ねんどろいど 初音ミク が再販決定！'),
+                (2, 'ご注文の確認', 'ねんどろいど 初音ミク 1点'),
+                (3, '再入荷のお知らせ', 'figma 鏡音リン');
+            INSERT INTO news_clips (title, url, summary) VALUES
+                ('「ねんどろいど 初音ミク」再生産決定', 'https://example.com/news/1', NULL);
+            "#,
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let repo = SqliteReissueRepository::new(pool);
+        let watch_id = repo.add_watch_for_item(1).await.unwrap();
+
+        assert_eq!(repo.detect().await.unwrap(), 2);
+        // 同じアナウンスは再検出しない
+        assert_eq!(repo.detect().await.unwrap(), 0);
+
+        let watches = repo.list_watches().await.unwrap();
+        assert_eq!(watches[0].id, watch_id);
+        assert!(watches[0].owned);
+        assert_eq!(watches[0].detection_count, 2);
+
+        let unnotified = repo.list_unnotified().await.unwrap();
+        assert_eq!(unnotified.len(), 2);
+        assert_eq!(unnotified[0].source, "email");
+        assert_eq!(unnotified[0].source_ref, "1");
+        assert_eq!(unnotified[1].source, "news");
+        assert!(unnotified.iter().all(|d| d.owned));
+
+        let ids: Vec<i64> = unnotified.iter().map(|d| d.id).collect();
+        repo.mark_notified(&ids).await.unwrap();
+        assert!(repo.list_unnotified().await.unwrap().is_empty());
+        assert_eq!(repo.list_detections(10).await.unwrap().len(), 2);
+
+        repo.delete_watch(watch_id).await.unwrap();
+        assert!(repo.delete_watch(watch_id).await.is_err());
+    }
+}
@@ -0,0 +1,868 @@
+use crate::parsers::release_date::FuzzyReleaseDate;
+use serde::{Deserialize, Serialize};
+use sqlx::sqlite::SqlitePool;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use ts_rs::TS;
+
+/// 予約注文の確保状況
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(export)]
+pub enum ReservationStatus {
+    /// 確保済み
+    Secured,
+    /// 在庫切れリスク（数量制限・抽選・入荷数不足など）
+    AtRisk,
+    /// 確保不可
+    Unavailable,
+}
+
+impl ReservationStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Secured => "secured",
+            Self::AtRisk => "at_risk",
+            Self::Unavailable => "unavailable",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "secured" => Some(Self::Secured),
+            "at_risk" => Some(Self::AtRisk),
+            "unavailable" => Some(Self::Unavailable),
+            _ => None,
+        }
+    }
+}
+
+/// 確保不可を示す表記（確保済み表記より先に判定する）
+const UNAVAILABLE_KEYWORDS: &[&str] = &[
+    "確保できませんでした",
+    "ご用意できませんでした",
+    "ご用意することができませんでした",
+    "お届けできなくなりました",
+];
+
+/// 在庫切れリスクを示す表記
+const AT_RISK_KEYWORDS: &[&str] = &[
+    "数量制限",
+    "抽選",
+    "入荷数が",
+    "入荷数量が",
+    "確保できない可能性",
+    "ご用意できない可能性",
+    "在庫が不足",
+];
+
+/// 確保済みを示す表記
+const SECURED_KEYWORDS: &[&str] = &[
+    "在庫を確保",
+    "在庫確保",
+    "確保いたしました",
+    "ご用意できました",
+];
+
+/// フッター（送信専用の案内・配信停止・署名など）の開始を示す表記。
+/// フッター以降にはメルマガや抽選販売の告知が入ることがあるため、判定対象から外す。
+/// 本文の冒頭に置かれる「このメールは…」等の注意書きはフッターとみなさない（`notice_section` 参照）。
+const FOOTER_MARKERS: &[&str] = &[
+    "このメールは",
+    "本メールは",
+    "送信専用",
+    "配信停止",
+    "メールマガジン",
+    "メルマガ",
+    "Copyright",
+    "©",
+];
+
+/// 区切り線がなくてもフッターとみなす末尾の行数
+const FOOTER_TAIL_LINES: usize = 10;
+
+fn is_footer_marker_line(line: &str) -> bool {
+    FOOTER_MARKERS.iter().any(|m| line.contains(m))
+}
+
+/// `────` `=====` 等の区切り線か
+fn is_separator_line(line: &str) -> bool {
+    let t = line.trim();
+    t.chars().count() >= 5
+        && t.chars().all(|c| {
+            matches!(
+                c,
+                '-' | '='
+                    | '*'
+                    | '_'
+                    | '~'
+                    | '─'
+                    | '━'
+                    | '＝'
+                    | '－'
+                    | '＊'
+                    | '～'
+                    | '■'
+                    | '□'
+                    | '◆'
+                    | '◇'
+            )
+        })
+}
+
+/// 本文のうちフッターより前の部分（確保状況の判定対象）
+///
+/// フッターの表記がある行は、本文の内容より後にある場合だけフッターの始まりとみなす。
+/// 具体的には、内容の後の区切り線より後にある場合か、末尾 `FOOTER_TAIL_LINES` 行かつ本文の後半にある場合。
+/// 冒頭の注意書き（内容より前の表記）は無視する。
+fn notice_section(body: &str) -> &str {
+    let lines: Vec<&str> = body.split_inclusive('\n').collect();
+    let tail_start = lines
+        .len()
+        .saturating_sub(FOOTER_TAIL_LINES)
+        .max(lines.len() / 2);
+    let mut has_content = false;
+    let mut separated = false;
+    let mut offset = 0;
+    for (i, line) in lines.iter().enumerate() {
+        if is_footer_marker_line(line) {
+            if has_content && (separated || i >= tail_start) {
+                return &body[..offset];
+            }
+        } else if is_separator_line(line) {
+            separated |= has_content;
+        } else if !line.trim().is_empty() {
+            has_content = true;
+        }
+        offset += line.len();
+    }
+    body
+}
+
+/// メールの件名と本文（フッターより前）から確保状況を判定する
+pub fn detect_reservation_status_in_email(subject: &str, body: &str) -> Option<ReservationStatus> {
+    detect_reservation_status(&format!("{subject}\n{}", notice_section(body)))
+}
+
+/// メールの件名・本文から確保状況を判定する（該当表記がなければ None）
+pub fn detect_reservation_status(text: &str) -> Option<ReservationStatus> {
+    if UNAVAILABLE_KEYWORDS.iter().any(|k| text.contains(k)) {
+        return Some(ReservationStatus::Unavailable);
+    }
+    if AT_RISK_KEYWORDS.iter().any(|k| text.contains(k)) {
+        return Some(ReservationStatus::AtRisk);
+    }
+    if SECURED_KEYWORDS.iter().any(|k| text.contains(k)) {
+        return Some(ReservationStatus::Secured);
+    }
+    None
+}
+
+/// 未発送の予約注文と確保状況
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct ReservationOrder {
+    pub order_id: i64,
+    pub shop_name: Option<String>,
+    pub order_number: Option<String>,
+    pub order_date: Option<String>,
+    pub reservation_status: Option<ReservationStatus>,
+    pub reservation_status_updated_at: Option<String>,
+}
+
+type ReservationOrderRow = (
+    i64,
+    Option<String>,
+    Option<String>,
+    Option<String>,
+    Option<String>,
+    Option<String>,
+);
+
+/// 重複予約の候補となる未発送注文の商品
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS, sqlx::FromRow)]
+#[ts(export)]
+pub struct DuplicatePreorderEntry {
+    pub order_id: i64,
+    pub item_id: i64,
+    pub shop_domain: Option<String>,
+    pub shop_name: Option<String>,
+    pub order_number: Option<String>,
+    pub order_date: Option<String>,
+    pub item_name: String,
+    pub item_name_normalized: String,
+    pub price: i64,
+    pub quantity: i64,
+}
+
+/// 同一商品（normalized_name）を複数ショップで予約している重複予約
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct DuplicatePreorder {
+    pub item_name_normalized: String,
+    /// 予約しているショップ数
+    pub shop_count: usize,
+    pub entries: Vec<DuplicatePreorderEntry>,
+}
+
+/// 注文のショップ識別キー（shop_domain 優先、なければ shop_name）
+fn shop_key(entry: &DuplicatePreorderEntry) -> Option<&str> {
+    entry
+        .shop_domain
+        .as_deref()
+        .or(entry.shop_name.as_deref())
+        .filter(|s| !s.is_empty())
+}
+
+/// normalized_name ごとにまとめ、2ショップ以上にまたがるものだけを重複予約として返す
+pub fn group_duplicate_preorders(entries: Vec<DuplicatePreorderEntry>) -> Vec<DuplicatePreorder> {
+    let mut groups: BTreeMap<String, Vec<DuplicatePreorderEntry>> = BTreeMap::new();
+    for entry in entries {
+        groups
+            .entry(entry.item_name_normalized.clone())
+            .or_default()
+            .push(entry);
+    }
+
+    groups
+        .into_iter()
+        .filter_map(|(item_name_normalized, entries)| {
+            let shop_count = entries
+                .iter()
+                .filter_map(shop_key)
+                .collect::<BTreeSet<_>>()
+                .len();
+            (shop_count >= 2).then_some(DuplicatePreorder {
+                item_name_normalized,
+                shop_count,
+                entries,
+            })
+        })
+        .collect()
+}
+
+/// 未発送（最新配送ステータスが not_shipped / preparing、または配送情報なし）の注文を抽出する条件
+const NOT_SHIPPED_CONDITION: &str = r#"
+    NOT EXISTS (
+        SELECT 1 FROM deliveries d
+        WHERE d.order_id = o.id
+          AND d.delivery_status NOT IN ('not_shipped', 'preparing')
+    )
+"#;
+
+/// 発売予定のある商品（発売日順の一覧用）
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS, sqlx::FromRow)]
+#[ts(export)]
+pub struct ReleaseScheduleItem {
+    pub item_id: i64,
+    pub order_id: i64,
+    pub shop_name: Option<String>,
+    pub order_number: Option<String>,
+    pub item_name: String,
+    pub price: i64,
+    pub quantity: i64,
+    /// 発売予定（並び替え用の YYYY-MM または YYYY-MM-DD）
+    pub release_date: String,
+    /// 発売予定の精度（`ReleaseDatePrecision` の文字列。既存データは None）
+    pub release_date_precision: Option<String>,
+    /// 表示用の発売予定（「2025年8月下旬」「2025年夏」等、メール上の表記に戻したもの）
+    #[sqlx(skip)]
+    pub release_date_label: String,
+    /// 発売済みか（月までの場合はその月が過ぎていれば発売済み）
+    #[sqlx(skip)]
+    pub is_released: bool,
+    /// 最新の配送状況（配送情報がなければ None）
+    pub delivery_status: Option<String>,
+}
+
+/// 発売予定 `YYYY-MM` / `YYYY-MM-DD` が `today` の時点で発売済みか
+pub fn is_released(release_date: &str, today: chrono::NaiveDate) -> bool {
+    if release_date.len() == 7 {
+        release_date < today.format("%Y-%m").to_string().as_str()
+    } else {
+        release_date <= today.format("%Y-%m-%d").to_string().as_str()
+    }
+}
+
+/// 予約確保状況のDB操作
+pub struct SqliteReservationRepository {
+    pool: SqlitePool,
+}
+
+impl SqliteReservationRepository {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    /// 確保状況を手動（またはマイページ連携）で更新する。None で未判定に戻す。
+    pub async fn set_status(
+        &self,
+        order_id: i64,
+        status: Option<ReservationStatus>,
+    ) -> Result<(), String> {
+        let result = sqlx::query(
+            r#"
+            UPDATE orders
+            SET reservation_status = ?, reservation_status_updated_at = CURRENT_TIMESTAMP
+            WHERE id = ?
+            "#,
+        )
+        .bind(status.map(|s| s.as_str()))
+        .bind(order_id)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| format!("Failed to update reservation status: {e}"))?;
+
+        if result.rows_affected() == 0 {
+            return Err(format!("Order not found: {order_id}"));
+        }
+        Ok(())
+    }
+
+    /// 未発送注文に紐づくメール（order_emails）を走査し、確保状況を更新する。
+    ///
+    /// 判定は件名と本文のフッターより前の部分で行う。同じ注文に複数の通知がある場合は
+    /// 受信日時が最も新しいメールの判定を採用する。更新した注文数を返す。
+    pub async fn update_from_emails(&self) -> Result<usize, String> {
+        let sql = format!(
+            r#"
+            SELECT o.id, COALESCE(e.subject, ''), COALESCE(e.body_plain, '')
+            FROM orders o
+            INNER JOIN order_emails oe ON oe.order_id = o.id
+            INNER JOIN emails e ON e.id = oe.email_id
+            WHERE o.deleted_at IS NULL
+              AND {NOT_SHIPPED_CONDITION}
+            ORDER BY o.id, e.internal_date ASC
+            "#
+        );
+        let rows: Vec<(i64, String, String)> = sqlx::query_as(&sql)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| format!("Failed to fetch reservation notices: {e}"))?;
+
+        let mut latest: HashMap<i64, ReservationStatus> = HashMap::new();
+        for (order_id, subject, body) in rows {
+            if let Some(status) = detect_reservation_status_in_email(&subject, &body) {
+                latest.insert(order_id, status);
+            }
+        }
+
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| format!("Failed to start transaction: {e}"))?;
+        let mut updated = 0;
+        for (order_id, status) in &latest {
+            let result = sqlx::query(
+                r#"
+                UPDATE orders
+                SET reservation_status = ?, reservation_status_updated_at = CURRENT_TIMESTAMP
+                WHERE id = ? AND COALESCE(reservation_status, '') != ?
+                "#,
+            )
+            .bind(status.as_str())
+            .bind(order_id)
+            .bind(status.as_str())
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| format!("Failed to update reservation status: {e}"))?;
+            updated += result.rows_affected() as usize;
+        }
+        tx.commit()
+            .await
+            .map_err(|e| format!("Failed to commit transaction: {e}"))?;
+
+        Ok(updated)
+    }
+
+    /// 未発送の注文を確保状況つきで取得する（確保済み以外を先頭に並べる）
+    pub async fn list_unshipped(&self) -> Result<Vec<ReservationOrder>, String> {
+        let sql = format!(
+            r#"
+            SELECT o.id, o.shop_name, o.order_number, o.order_date,
+                   o.reservation_status, o.reservation_status_updated_at
+            FROM orders o
+            WHERE o.deleted_at IS NULL
+              AND {NOT_SHIPPED_CONDITION}
+            ORDER BY CASE o.reservation_status
+                         WHEN 'unavailable' THEN 0
+                         WHEN 'at_risk' THEN 1
+                         WHEN 'secured' THEN 3
+                         ELSE 2
+                     END,
+                     COALESCE(o.order_date, o.created_at) ASC
+            "#
+        );
+        let rows: Vec<ReservationOrderRow> = sqlx::query_as(&sql)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| format!("Failed to fetch unshipped orders: {e}"))?;
+
+        Ok(rows
+            .into_iter()
+            .map(
+                |(order_id, shop_name, order_number, order_date, status, updated_at)| {
+                    ReservationOrder {
+                        order_id,
+                        shop_name,
+                        order_number,
+                        order_date,
+                        reservation_status: status.as_deref().and_then(ReservationStatus::parse),
+                        reservation_status_updated_at: updated_at,
+                    }
+                },
+            )
+            .collect())
+    }
+
+    /// 同一商品を複数ショップで予約している（未発送の注文が複数ショップにある）ものを検出する
+    ///
+    /// 確保不可（reservation_status = 'unavailable'）の注文は予約が成立していないため除外する。
+    pub async fn find_duplicate_preorders(&self) -> Result<Vec<DuplicatePreorder>, String> {
+        let sql = format!(
+            r#"
+            SELECT o.id AS order_id, i.id AS item_id, o.shop_domain, o.shop_name,
+                   o.order_number, o.order_date, i.item_name, i.item_name_normalized,
+                   i.price, i.quantity
+            FROM items i
+            INNER JOIN orders o ON o.id = i.order_id
+            WHERE i.item_name_normalized IS NOT NULL
+              AND i.item_name_normalized != ''
+              AND i.deleted_at IS NULL
+              AND o.deleted_at IS NULL
+              AND (o.reservation_status IS NULL OR o.reservation_status != 'unavailable')
+              AND {NOT_SHIPPED_CONDITION}
+            ORDER BY i.item_name_normalized, COALESCE(o.order_date, o.created_at) ASC, i.id
+            "#
+        );
+        let entries: Vec<DuplicatePreorderEntry> = sqlx::query_as(&sql)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| format!("Failed to fetch unshipped items: {e}"))?;
+
+        Ok(group_duplicate_preorders(entries))
+    }
+
+    /// 発売予定のある商品を発売日の早い順に取得する
+    ///
+    /// 月までの発売予定（季節・年を含む）はその月の末日として並べる。
+    /// `include_released` が false の場合は `today` 時点で発売済みの商品を除く。
+    pub async fn list_by_release_date(
+        &self,
+        include_released: bool,
+        today: chrono::NaiveDate,
+    ) -> Result<Vec<ReleaseScheduleItem>, String> {
+        let items: Vec<ReleaseScheduleItem> = sqlx::query_as(
+            r#"
+            WITH latest_delivery AS (
+                SELECT order_id, delivery_status
+                FROM (
+                    SELECT order_id, delivery_status,
+                           ROW_NUMBER() OVER (PARTITION BY order_id ORDER BY updated_at DESC) AS rn
+                    FROM deliveries
+                ) t
+                WHERE rn = 1
+            )
+            SELECT i.id AS item_id, o.id AS order_id, o.shop_name, o.order_number,
+                   i.item_name, i.price, i.quantity, i.release_date, i.release_date_precision,
+                   ld.delivery_status
+            FROM items i
+            INNER JOIN orders o ON o.id = i.order_id
+            LEFT JOIN latest_delivery ld ON ld.order_id = o.id
+            WHERE i.release_date IS NOT NULL
+              AND i.deleted_at IS NULL
+              AND o.deleted_at IS NULL
+            ORDER BY i.release_date || CASE WHEN length(i.release_date) = 7 THEN '-99' ELSE '' END ASC,
+                     i.id
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| format!("Failed to fetch release schedule: {e}"))?;
+
+        Ok(items
+            .into_iter()
+            .map(|item| ReleaseScheduleItem {
+                is_released: is_released(&item.release_date, today),
+                release_date_label: FuzzyReleaseDate::from_stored(
+                    &item.release_date,
+                    item.release_date_precision.as_deref(),
+                )
+                .map(|d| d.label())
+                .unwrap_or_else(|| item.release_date.clone()),
+                ..item
+            })
+            .filter(|item| include_released || !item.is_released)
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sqlx::sqlite::SqlitePoolOptions;
+
+    async fn setup_test_db() -> SqlitePool {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .expect("Failed to create test database");
+
+        sqlx::query(
+            r#"
+            CREATE TABLE orders (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                shop_domain TEXT,
+                shop_name TEXT,
+                order_number TEXT,
+                order_date DATETIME,
+                reservation_status TEXT,
+                reservation_status_updated_at DATETIME,
+                created_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+                deleted_at DATETIME
+            );
+            CREATE TABLE items (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                order_id INTEGER NOT NULL,
+                item_name TEXT NOT NULL,
+                item_name_normalized TEXT,
+                price INTEGER NOT NULL DEFAULT 0,
+                quantity INTEGER NOT NULL DEFAULT 1,
+                release_date TEXT,
+                release_date_precision TEXT,
+                deleted_at DATETIME
+            );
+            CREATE TABLE deliveries (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                order_id INTEGER NOT NULL,
+                delivery_status TEXT NOT NULL DEFAULT 'not_shipped',
+                updated_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP
+            );
+            CREATE TABLE emails (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                subject TEXT,
+                body_plain TEXT,
+                from_address TEXT,
+                internal_date INTEGER
+            );
+            CREATE TABLE order_emails (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                order_id INTEGER NOT NULL,
+                email_id INTEGER NOT NULL,
+                UNIQUE (order_id, email_id)
+            );
+            "#,
+        )
+        .execute(&pool)
+        .await
+        .expect("Failed to create tables");
+
+        pool
+    }
+
+    #[test]
+    fn test_detect_reservation_status() {
+        assert_eq!(
+            detect_reservation_status("ご予約商品の在庫を確保いたしました"),
+            Some(ReservationStatus::Secured)
+        );
+        assert_eq!(
+            detect_reservation_status("メーカーからの入荷数が予定を下回る見込みです"),
+            Some(ReservationStatus::AtRisk)
+        );
+        assert_eq!(
+            detect_reservation_status("誠に申し訳ございませんが、商品を確保できませんでした"),
+            Some(ReservationStatus::Unavailable)
+        );
+        assert_eq!(
+            detect_reservation_status("ご注文ありがとうございます"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_detect_reservation_status_ignores_footer() {
+        let body = "ご注文番号 ORD-00001\nご注文ありがとうございます。\n\n\
+                    ──────────\n\
+                    ※このメールは送信専用です。\n\
+                    【数量制限】人気商品の抽選販売を受付中！\n\
+                    メールマガジンで在庫確保の情報をお届けします";
+        assert_eq!(detect_reservation_status_in_email("ご注文確認", body), None);
+
+        let notice = "ご注文番号 ORD-00001\n入荷数が予定を下回る見込みです。\n\
+                      ※このメールは送信専用です。\n在庫を確保いたしました";
+        assert_eq!(
+            detect_reservation_status_in_email("ご予約商品について", notice),
+            Some(ReservationStatus::AtRisk)
+        );
+        assert_eq!(
+            detect_reservation_status_in_email(
+                "在庫確保のお知らせ",
+                "ご予約商品についてのご連絡です。\n─────\n※本メールは送信専用です。\n抽選販売のご案内"
+            ),
+            Some(ReservationStatus::Secured)
+        );
+    }
+
+    #[test]
+    fn test_detect_reservation_status_keeps_body_after_header_disclaimer() {
+        // 冒頭の「このメールは…」はフッターではない（グッドスマイル・ふるいちオンライン等）
+        let body = "※このメールはシステムより自動送信されています。\n\
+                    このメールは商品のお届けまで大切に保管してください。\n\
+                    \n\
+                    ご注文番号：1234567\n\
+                    ご予約いただいた商品の在庫を確保いたしました。\n\
+                    発売までいましばらくお待ちください。\n\
+                    \n\
+                    ──────────\n\
+                    株式会社サンプル\n\
+                    ※本メールは送信専用です。\n\
+                    メールマガジンで抽選販売の情報をお届けします";
+        assert_eq!(
+            detect_reservation_status_in_email("ご予約商品について", body),
+            Some(ReservationStatus::Secured)
+        );
+
+        let body = "本メールは送信専用のため、ご返信いただいてもお答えできません。\n\
+                    ご注文番号：1234567\n\
+                    メーカーからの入荷数が予定を下回る見込みです。";
+        assert_eq!(
+            detect_reservation_status_in_email("ご予約商品について", body),
+            Some(ReservationStatus::AtRisk)
+        );
+    }
+
+    #[test]
+    fn test_reservation_status_roundtrip() {
+        for status in [
+            ReservationStatus::Secured,
+            ReservationStatus::AtRisk,
+            ReservationStatus::Unavailable,
+        ] {
+            assert_eq!(ReservationStatus::parse(status.as_str()), Some(status));
+        }
+        assert_eq!(ReservationStatus::parse("unknown"), None);
+    }
+
+    #[tokio::test]
+    async fn test_update_from_emails_uses_latest_notice() {
+        let pool = setup_test_db().await;
+        sqlx::query(
+            r#"
+            INSERT INTO orders (id, shop_domain, order_number) VALUES
+                (1, 'example.com', 'ORD-00001'),
+                (2, 'example.com', 'ORD-00002');
+            INSERT INTO deliveries (order_id, delivery_status) VALUES (2, 'shipped');
+            INSERT INTO emails (id, subject, body_plain, from_address, internal_date) VALUES
+                (1, '数量制限のお知らせ', '注文番号 ORD-00001', 'shop@example.com', 1000),
+                (2, '在庫確保のお知らせ', '注文番号 ORD-00001 の在庫を確保いたしました', 'shop@example.com', 2000),
+                (3, '抽選のお知らせ', '注文番号 ORD-00002', 'shop@example.com', 3000),
+                (4, '抽選のお知らせ', '注文番号 ORD-00001', 'other@other.example', 4000);
+            INSERT INTO order_emails (order_id, email_id) VALUES (1, 1), (1, 2), (2, 3);
+            "#,
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let repo = SqliteReservationRepository::new(pool.clone());
+        let updated = repo.update_from_emails().await.unwrap();
+        assert_eq!(updated, 1);
+
+        let orders = repo.list_unshipped().await.unwrap();
+        assert_eq!(orders.len(), 1);
+        assert_eq!(orders[0].order_id, 1);
+        assert_eq!(
+            orders[0].reservation_status,
+            Some(ReservationStatus::Secured)
+        );
+
+        // 状況が変わらなければ再更新しない
+        assert_eq!(repo.update_from_emails().await.unwrap(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_update_from_emails_ignores_unlinked_and_footer_mentions() {
+        let pool = setup_test_db().await;
+        sqlx::query(
+            r#"
+            INSERT INTO orders (id, shop_domain, order_number) VALUES (1, 'example.com', 'ORD-00001');
+            INSERT INTO emails (id, subject, body_plain, from_address, internal_date) VALUES
+                (1, 'ご注文確認', 'ご注文番号 ORD-00001' || char(10) || '※このメールは送信専用です。' || char(10) || '抽選販売受付中', 'shop@example.com', 1000),
+                (2, 'メルマガ', '数量制限の商品 ORD-00001', 'shop@example.com', 2000);
+            INSERT INTO order_emails (order_id, email_id) VALUES (1, 1);
+            "#,
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let repo = SqliteReservationRepository::new(pool);
+        assert_eq!(repo.update_from_emails().await.unwrap(), 0);
+        let orders = repo.list_unshipped().await.unwrap();
+        assert_eq!(orders[0].reservation_status, None);
+    }
+
+    #[tokio::test]
+    async fn test_set_status() {
+        let pool = setup_test_db().await;
+        sqlx::query("INSERT INTO orders (id, order_number) VALUES (1, 'ORD-1')")
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        let repo = SqliteReservationRepository::new(pool);
+        repo.set_status(1, Some(ReservationStatus::AtRisk))
+            .await
+            .unwrap();
+        let orders = repo.list_unshipped().await.unwrap();
+        assert_eq!(
+            orders[0].reservation_status,
+            Some(ReservationStatus::AtRisk)
+        );
+
+        repo.set_status(1, None).await.unwrap();
+        let orders = repo.list_unshipped().await.unwrap();
+        assert_eq!(orders[0].reservation_status, None);
+
+        assert!(repo.set_status(99, None).await.is_err());
+    }
+
+    #[test]
+    fn test_is_released() {
+        let today = chrono::NaiveDate::from_ymd_opt(2025, 8, 15).unwrap();
+        assert!(is_released("2025-07", today));
+        assert!(!is_released("2025-08", today));
+        assert!(is_released("2025-08-15", today));
+        assert!(!is_released("2025-08-16", today));
+    }
+
+    #[tokio::test]
+    async fn test_list_by_release_date() {
+        let pool = setup_test_db().await;
+        sqlx::query(
+            r#"
+            INSERT INTO orders (id, shop_name, order_number) VALUES
+                (1, 'あみあみ', 'A-1'),
+                (2, 'ホビーサーチ', 'H-1');
+            INSERT INTO items (id, order_id, item_name, price, release_date, release_date_precision) VALUES
+                (10, 1, '商品A', 1000, '2025-09', NULL),
+                (11, 1, '商品B', 2000, '2025-07', NULL),
+                (12, 2, '商品C', 3000, '2025-08-20', 'mid_month'),
+                (13, 2, '商品D', 4000, NULL, NULL),
+                (14, 1, '商品E', 5000, '2025-08', 'summer'),
+                (15, 1, '商品F', 6000, '2025-08-31', 'late_month');
+            INSERT INTO deliveries (order_id, delivery_status) VALUES (2, 'delivered');
+            "#,
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let repo = SqliteReservationRepository::new(pool);
+        let today = chrono::NaiveDate::from_ymd_opt(2025, 8, 15).unwrap();
+
+        let all = repo.list_by_release_date(true, today).await.unwrap();
+        let ids: Vec<i64> = all.iter().map(|i| i.item_id).collect();
+        // 月までの発売予定（夏 = 8月）は同じ月の日付より後に並ぶ
+        assert_eq!(ids, vec![11, 12, 15, 14, 10]);
+        assert!(all[0].is_released);
+        assert!(!all[1].is_released);
+        assert_eq!(all[1].delivery_status.as_deref(), Some("delivered"));
+
+        let labels: Vec<&str> = all.iter().map(|i| i.release_date_label.as_str()).collect();
+        assert_eq!(
+            labels,
+            vec![
+                "2025年7月",
+                "2025年8月中旬",
+                "2025年8月下旬",
+                "2025年夏",
+                "2025年9月"
+            ]
+        );
+
+        let upcoming = repo.list_by_release_date(false, today).await.unwrap();
+        let ids: Vec<i64> = upcoming.iter().map(|i| i.item_id).collect();
+        assert_eq!(ids, vec![12, 15, 14, 10]);
+    }
+
+    fn entry(
+        item_id: i64,
+        shop_domain: Option<&str>,
+        shop_name: &str,
+        normalized: &str,
+    ) -> DuplicatePreorderEntry {
+        DuplicatePreorderEntry {
+            order_id: item_id,
+            item_id,
+            shop_domain: shop_domain.map(str::to_string),
+            shop_name: Some(shop_name.to_string()),
+            order_number: None,
+            order_date: None,
+            item_name: normalized.to_string(),
+            item_name_normalized: normalized.to_string(),
+            price: 1000,
+            quantity: 1,
+        }
+    }
+
+    #[test]
+    fn test_group_duplicate_preorders_requires_multiple_shops() {
+        let groups = group_duplicate_preorders(vec![
+            entry(1, Some("a.example.com"), "A", "figure-x"),
+            entry(2, Some("b.example.com"), "B", "figure-x"),
+            // 同一ショップで2件は重複予約とみなさない
+            entry(3, Some("a.example.com"), "A", "figure-y"),
+            entry(4, Some("a.example.com"), "A", "figure-y"),
+            // shop_domain がなければ shop_name で区別する
+            entry(5, None, "C", "figure-z"),
+            entry(6, None, "D", "figure-z"),
+        ]);
+        let names: Vec<&str> = groups
+            .iter()
+            .map(|g| g.item_name_normalized.as_str())
+            .collect();
+        assert_eq!(names, vec!["figure-x", "figure-z"]);
+        assert_eq!(groups[0].shop_count, 2);
+        assert_eq!(groups[0].entries.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_find_duplicate_preorders() {
+        let pool = setup_test_db().await;
+        sqlx::query(
+            r#"
+            INSERT INTO orders (id, shop_domain, shop_name, order_number, reservation_status) VALUES
+                (1, 'a.example.com', 'A', 'A-1', NULL),
+                (2, 'b.example.com', 'B', 'B-1', 'secured'),
+                (3, 'c.example.com', 'C', 'C-1', NULL),
+                (4, 'd.example.com', 'D', 'D-1', 'unavailable');
+            INSERT INTO items (order_id, item_name, item_name_normalized) VALUES
+                (1, 'フィギュアX', 'フィギュアx'),
+                (2, 'フィギュアX 予約', 'フィギュアx'),
+                (3, 'フィギュアY', 'フィギュアy'),
+                (4, 'フィギュアY', 'フィギュアy');
+            INSERT INTO deliveries (order_id, delivery_status) VALUES (2, 'preparing');
+            "#,
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let repo = SqliteReservationRepository::new(pool.clone());
+        let duplicates = repo.find_duplicate_preorders().await.unwrap();
+        assert_eq!(duplicates.len(), 1);
+        assert_eq!(duplicates[0].item_name_normalized, "フィギュアx");
+        let order_ids: Vec<i64> = duplicates[0].entries.iter().map(|e| e.order_id).collect();
+        assert_eq!(order_ids, vec![1, 2]);
+
+        // 片方が発送済みになれば重複予約ではなくなる
+        sqlx::query("INSERT INTO deliveries (order_id, delivery_status) VALUES (1, 'shipped')")
+            .execute(&pool)
+            .await
+            .unwrap();
+        assert!(repo.find_duplicate_preorders().await.unwrap().is_empty());
+    }
+}
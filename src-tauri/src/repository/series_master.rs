@@ -0,0 +1,658 @@
+use crate::gemini::normalize_product_name;
+use serde::{Deserialize, Serialize};
+use sqlx::sqlite::SqlitePool;
+use std::collections::HashMap;
+use ts_rs::TS;
+
+/// 照合キー生成時に除去する接頭辞（正規化後の表記）
+const SERIES_PREFIXES: &[&str] = &["機動戦士", "劇場版", "映画", "tvアニメ", "アニメ"];
+
+/// 正規名と既知の略称・別表記の辞書
+const SERIES_DICTIONARY: &[(&str, &[&str])] = &[
+    ("機動戦士ガンダム", &["ガンダム", "1stガンダム"]),
+    (
+        "新世紀エヴァンゲリオン",
+        &["エヴァンゲリオン", "エヴァ", "ヱヴァンゲリヲン"],
+    ),
+    ("Re:ゼロから始める異世界生活", &["リゼロ", "Re:ゼロ"]),
+    ("ウマ娘 プリティーダービー", &["ウマ娘"]),
+    ("魔法少女まどか☆マギカ", &["まどマギ", "まどか☆マギカ"]),
+    ("ラブライブ!", &["ラブライブ"]),
+];
+
+/// 類似度がこの値以上の既存シリーズに統合する
+pub const SERIES_SIMILARITY_THRESHOLD: f64 = 0.8;
+
+/// シリーズ名の照合キー（NFKC・小文字化・記号除去のうえ、既知の接頭辞を除去）
+pub fn series_key(name: &str) -> String {
+    let key = normalize_product_name(name);
+    for prefix in SERIES_PREFIXES {
+        if let Some(rest) = key.strip_prefix(prefix) {
+            if !rest.is_empty() {
+                return rest.to_string();
+            }
+        }
+    }
+    key
+}
+
+/// 辞書に登録された正規名を返す（正規名・別表記のいずれかとキーが一致する場合）
+pub fn dictionary_canonical(name: &str) -> Option<&'static str> {
+    let key = series_key(name);
+    SERIES_DICTIONARY
+        .iter()
+        .find(|(canonical, aliases)| {
+            series_key(canonical) == key || aliases.iter().any(|a| series_key(a) == key)
+        })
+        .map(|(canonical, _)| *canonical)
+}
+
+/// 文字単位のレーベンシュタイン距離
+fn levenshtein(a: &[char], b: &[char]) -> usize {
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.iter().enumerate() {
+        let mut cur = vec![i + 1; b.len() + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let cost = usize::from(ca != cb);
+            cur[j + 1] = (prev[j] + cost).min(prev[j + 1] + 1).min(cur[j] + 1);
+        }
+        prev = cur;
+    }
+    prev[b.len()]
+}
+
+/// 照合キー同士の類似度（0.0〜1.0、1.0 で一致）
+pub fn series_similarity(a: &str, b: &str) -> f64 {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let max_len = a.len().max(b.len());
+    if max_len == 0 {
+        return 1.0;
+    }
+    1.0 - levenshtein(&a, &b) as f64 / max_len as f64
+}
+
+/// 既存エイリアス `(series_id, alias_key)` の中から統合先シリーズを探す
+///
+/// キー完全一致を優先し、なければ類似度が閾値以上で最も高いものを返す。
+pub fn find_matching_series(key: &str, existing: &[(i64, String)]) -> Option<i64> {
+    if let Some((id, _)) = existing.iter().find(|(_, k)| k == key) {
+        return Some(*id);
+    }
+    existing
+        .iter()
+        .map(|(id, k)| (*id, series_similarity(key, k)))
+        .filter(|(_, score)| *score >= SERIES_SIMILARITY_THRESHOLD)
+        .max_by(|a, b| a.1.total_cmp(&b.1))
+        .map(|(id, _)| id)
+}
+
+/// シリーズ名のエイリアス
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct SeriesAlias {
+    pub id: i64,
+    pub alias: String,
+}
+
+/// 統合済みシリーズ（正規名と、束ねられた表記）
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct SeriesMaster {
+    pub id: i64,
+    pub canonical_name: String,
+    pub aliases: Vec<SeriesAlias>,
+    /// このシリーズに属する product_master の件数
+    pub product_count: i64,
+}
+
+/// シリーズ別の購入集計（統合後の正規名単位）
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct SeriesStats {
+    pub series_name: String,
+    pub item_count: i64,
+    pub total_amount: i64,
+}
+
+/// series_master / series_aliases のDB操作
+pub struct SqliteSeriesMasterRepository {
+    pool: SqlitePool,
+}
+
+impl SqliteSeriesMasterRepository {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    /// 統合済みシリーズ一覧（正規名順）
+    pub async fn list(&self) -> Result<Vec<SeriesMaster>, String> {
+        let masters: Vec<(i64, String, i64)> = sqlx::query_as(
+            r#"
+            SELECT sm.id, sm.canonical_name,
+                   (SELECT COUNT(*) FROM product_master pm
+                    INNER JOIN series_aliases sa ON sa.alias = pm.series
+                    WHERE sa.series_id = sm.id) AS product_count
+            FROM series_master sm
+            ORDER BY sm.canonical_name
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| format!("Failed to fetch series master: {e}"))?;
+
+        let aliases: Vec<(i64, i64, String)> =
+            sqlx::query_as("SELECT series_id, id, alias FROM series_aliases ORDER BY alias")
+                .fetch_all(&self.pool)
+                .await
+                .map_err(|e| format!("Failed to fetch series aliases: {e}"))?;
+        let mut alias_map: HashMap<i64, Vec<SeriesAlias>> = HashMap::new();
+        for (series_id, id, alias) in aliases {
+            alias_map
+                .entry(series_id)
+                .or_default()
+                .push(SeriesAlias { id, alias });
+        }
+
+        Ok(masters
+            .into_iter()
+            .map(|(id, canonical_name, product_count)| SeriesMaster {
+                id,
+                canonical_name,
+                aliases: alias_map.remove(&id).unwrap_or_default(),
+                product_count,
+            })
+            .collect())
+    }
+
+    /// product_master.series のうち未登録の表記を、辞書・類似度で既存シリーズに統合（なければ新規作成）する
+    ///
+    /// 戻り値は新たに登録したエイリアス数。
+    pub async fn sync_from_product_master(&self) -> Result<usize, String> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| format!("Failed to start transaction: {e}"))?;
+
+        let unregistered: Vec<String> = sqlx::query_scalar(
+            r#"
+            SELECT DISTINCT pm.series
+            FROM product_master pm
+            WHERE pm.series IS NOT NULL
+              AND TRIM(pm.series) != ''
+              AND NOT EXISTS (SELECT 1 FROM series_aliases sa WHERE sa.alias = pm.series)
+            ORDER BY pm.series
+            "#,
+        )
+        .fetch_all(&mut *tx)
+        .await
+        .map_err(|e| format!("Failed to fetch unregistered series: {e}"))?;
+
+        let mut existing: Vec<(i64, String)> =
+            sqlx::query_as("SELECT series_id, alias_key FROM series_aliases")
+                .fetch_all(&mut *tx)
+                .await
+                .map_err(|e| format!("Failed to fetch series aliases: {e}"))?;
+
+        let mut added = 0;
+        for series in unregistered {
+            let key = series_key(&series);
+            let series_id = match dictionary_canonical(&series) {
+                Some(canonical) => {
+                    find_or_create_series_in_tx(&mut tx, canonical, &mut existing).await?
+                }
+                None => match find_matching_series(&key, &existing) {
+                    Some(id) => id,
+                    None => find_or_create_series_in_tx(&mut tx, &series, &mut existing).await?,
+                },
+            };
+
+            sqlx::query(
+                "INSERT INTO series_aliases (series_id, alias, alias_key) VALUES (?, ?, ?)",
+            )
+            .bind(series_id)
+            .bind(&series)
+            .bind(&key)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| format!("Failed to add series alias: {e}"))?;
+            existing.push((series_id, key));
+            added += 1;
+        }
+
+        tx.commit()
+            .await
+            .map_err(|e| format!("Failed to commit transaction: {e}"))?;
+
+        Ok(added)
+    }
+
+    /// 表記を手動でシリーズに紐付ける（既に別シリーズに属している場合は付け替える）
+    pub async fn set_alias(&self, series_id: i64, alias: String) -> Result<(), String> {
+        let alias = alias.trim().to_string();
+        if alias.is_empty() {
+            return Err("Alias must not be empty".to_string());
+        }
+        let key = series_key(&alias);
+        sqlx::query(
+            r#"
+            INSERT INTO series_aliases (series_id, alias, alias_key)
+            VALUES (?, ?, ?)
+            ON CONFLICT(alias) DO UPDATE SET series_id = excluded.series_id
+            "#,
+        )
+        .bind(series_id)
+        .bind(&alias)
+        .bind(&key)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| format!("Failed to set series alias: {e}"))?;
+        Ok(())
+    }
+
+    pub async fn delete_alias(&self, alias_id: i64) -> Result<(), String> {
+        let result = sqlx::query("DELETE FROM series_aliases WHERE id = ?")
+            .bind(alias_id)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| format!("Failed to delete series alias: {e}"))?;
+
+        if result.rows_affected() == 0 {
+            return Err(format!("Series alias not found: id={alias_id}"));
+        }
+        Ok(())
+    }
+
+    /// 正規名を変更する
+    pub async fn rename(&self, series_id: i64, canonical_name: String) -> Result<(), String> {
+        let canonical_name = canonical_name.trim().to_string();
+        if canonical_name.is_empty() {
+            return Err("Series name must not be empty".to_string());
+        }
+        let result = sqlx::query("UPDATE series_master SET canonical_name = ? WHERE id = ?")
+            .bind(&canonical_name)
+            .bind(series_id)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| format!("Failed to rename series: {e}"))?;
+
+        if result.rows_affected() == 0 {
+            return Err(format!("Series not found: id={series_id}"));
+        }
+        Ok(())
+    }
+
+    /// `source_id` のエイリアスをすべて `target_id` に移し、`source_id` を削除する
+    pub async fn merge(&self, source_id: i64, target_id: i64) -> Result<(), String> {
+        if source_id == target_id {
+            return Err("Cannot merge a series into itself".to_string());
+        }
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| format!("Failed to start transaction: {e}"))?;
+
+        let target_exists: Option<i64> =
+            sqlx::query_scalar("SELECT id FROM series_master WHERE id = ?")
+                .bind(target_id)
+                .fetch_optional(&mut *tx)
+                .await
+                .map_err(|e| format!("Failed to fetch series: {e}"))?;
+        if target_exists.is_none() {
+            return Err(format!("Series not found: id={target_id}"));
+        }
+
+        sqlx::query("UPDATE series_aliases SET series_id = ? WHERE series_id = ?")
+            .bind(target_id)
+            .bind(source_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| format!("Failed to move series aliases: {e}"))?;
+        let result = sqlx::query("DELETE FROM series_master WHERE id = ?")
+            .bind(source_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| format!("Failed to delete series: {e}"))?;
+        if result.rows_affected() == 0 {
+            return Err(format!("Series not found: id={source_id}"));
+        }
+
+        tx.commit()
+            .await
+            .map_err(|e| format!("Failed to commit transaction: {e}"))
+    }
+
+    /// シリーズ別の購入集計（統合後の正規名単位。未統合の表記はそのまま集計）
+    ///
+    /// 金額は月別集計と同じ税込・調整額込みの注文合計を、注文内の商品金額の比率で按分する
+    pub async fn get_series_stats(&self) -> Result<Vec<SeriesStats>, String> {
+        let rows: Vec<(String, i64, i64)> = sqlx::query_as(
+            r#"
+            WITH order_amounts AS (
+                SELECT
+                    order_id,
+                    items_amount,
+                    items_amount
+                        + CASE WHEN tax_included = 0
+                               THEN COALESCE(tax_amount, CAST(items_amount * 0.1 AS INTEGER))
+                               ELSE 0 END
+                        + amount_adjustment AS total_amount
+                FROM (
+                    SELECT
+                        o.id AS order_id,
+                        o.tax_amount,
+                        o.tax_included,
+                        o.amount_adjustment,
+                        COALESCE(SUM(i.price * i.quantity), 0) AS items_amount
+                    FROM orders o
+                    LEFT JOIN items i ON i.order_id = o.id AND i.deleted_at IS NULL
+                    WHERE o.deleted_at IS NULL
+                    GROUP BY o.id
+                ) a
+            )
+            SELECT COALESCE(sm.canonical_name, pm.series) AS series_name,
+                   COUNT(i.id) AS item_count,
+                   CAST(ROUND(COALESCE(SUM(
+                       CASE WHEN oa.items_amount > 0
+                            THEN i.price * i.quantity * 1.0 * oa.total_amount / oa.items_amount
+                            ELSE 0 END
+                   ), 0)) AS INTEGER) AS total_amount
+            FROM items i
+            INNER JOIN order_amounts oa ON oa.order_id = i.order_id
+            INNER JOIN product_master pm ON TRIM(i.item_name) = pm.raw_name
+            LEFT JOIN series_aliases sa ON sa.alias = pm.series
+            LEFT JOIN series_master sm ON sm.id = sa.series_id
+            WHERE pm.series IS NOT NULL AND TRIM(pm.series) != ''
+              AND i.deleted_at IS NULL
+            GROUP BY series_name
+            ORDER BY total_amount DESC, series_name
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| format!("Failed to fetch series stats: {e}"))?;
+
+        Ok(rows
+            .into_iter()
+            .map(|(series_name, item_count, total_amount)| SeriesStats {
+                series_name,
+                item_count,
+                total_amount,
+            })
+            .collect())
+    }
+}
+
+/// 正規名のシリーズを取得（なければ作成）する。作成時は正規名自体もエイリアス照合対象に加える
+async fn find_or_create_series_in_tx(
+    tx: &mut sqlx::Transaction<'_, sqlx::Sqlite>,
+    canonical_name: &str,
+    existing: &mut Vec<(i64, String)>,
+) -> Result<i64, String> {
+    let found: Option<i64> =
+        sqlx::query_scalar("SELECT id FROM series_master WHERE canonical_name = ?")
+            .bind(canonical_name)
+            .fetch_optional(&mut **tx)
+            .await
+            .map_err(|e| format!("Failed to fetch series: {e}"))?;
+    if let Some(id) = found {
+        return Ok(id);
+    }
+
+    let id: i64 =
+        sqlx::query_scalar("INSERT INTO series_master (canonical_name) VALUES (?) RETURNING id")
+            .bind(canonical_name)
+            .fetch_one(&mut **tx)
+            .await
+            .map_err(|e| format!("Failed to create series: {e}"))?;
+    existing.push((id, series_key(canonical_name)));
+    Ok(id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sqlx::sqlite::SqlitePoolOptions;
+
+    async fn setup_test_db() -> SqlitePool {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .expect("Failed to create test database");
+
+        sqlx::query(
+            r#"
+            CREATE TABLE orders (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                tax_amount INTEGER,
+                tax_included INTEGER NOT NULL DEFAULT 1,
+                amount_adjustment INTEGER NOT NULL DEFAULT 0,
+                deleted_at DATETIME
+            );
+            CREATE TABLE items (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                order_id INTEGER NOT NULL,
+                item_name TEXT NOT NULL,
+                price INTEGER NOT NULL DEFAULT 0,
+                quantity INTEGER NOT NULL DEFAULT 1,
+                deleted_at DATETIME
+            );
+            CREATE TABLE product_master (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                raw_name TEXT UNIQUE NOT NULL,
+                normalized_name TEXT NOT NULL,
+                series TEXT
+            );
+            "#,
+        )
+        .execute(&pool)
+        .await
+        .expect("Failed to create tables");
+        sqlx::query(include_str!("../../migrations/010_series_master.sql"))
+            .execute(&pool)
+            .await
+            .expect("Failed to create series tables");
+
+        pool
+    }
+
+    #[test]
+    fn test_series_key_strips_prefix_and_symbols() {
+        assert_eq!(series_key("機動戦士ガンダム"), "ガンダム");
+        assert_eq!(series_key("劇場版 ガンダム"), "ガンダム");
+        assert_eq!(series_key("ラブライブ！"), "ラブライブ");
+        // 接頭辞のみの場合は除去しない
+        assert_eq!(series_key("アニメ"), "アニメ");
+    }
+
+    #[test]
+    fn test_dictionary_canonical() {
+        assert_eq!(
+            dictionary_canonical("エヴァ"),
+            Some("新世紀エヴァンゲリオン")
+        );
+        assert_eq!(dictionary_canonical("ガンダム"), Some("機動戦士ガンダム"));
+        assert_eq!(dictionary_canonical("ホロライブ"), None);
+    }
+
+    #[test]
+    fn test_series_similarity_and_matching() {
+        assert_eq!(series_similarity("abc", "abc"), 1.0);
+        assert!(
+            series_similarity(
+                "アイドルマスターシンデレラガールズ",
+                "アイドルマスターシンデレラガール"
+            ) >= SERIES_SIMILARITY_THRESHOLD
+        );
+
+        let existing = vec![
+            (1, "アイドルマスターシンデレラガールズ".to_string()),
+            (2, "ホロライブ".to_string()),
+        ];
+        assert_eq!(
+            find_matching_series("アイドルマスターシンデレラガール", &existing),
+            Some(1)
+        );
+        assert_eq!(find_matching_series("ホロライブ", &existing), Some(2));
+        assert_eq!(find_matching_series("ブルーアーカイブ", &existing), None);
+    }
+
+    #[tokio::test]
+    async fn test_sync_from_product_master_groups_variants() {
+        let pool = setup_test_db().await;
+        sqlx::query(
+            r#"
+            INSERT INTO product_master (raw_name, normalized_name, series) VALUES
+                ('HG ガンダム', 'hgガンダム', '機動戦士ガンダム'),
+                ('RG ガンダム', 'rgガンダム', 'ガンダム'),
+                ('ねんどろいど A', 'a', 'アイドルマスター シンデレラガールズ'),
+                ('ねんどろいど B', 'b', 'アイドルマスターシンデレラガール'),
+                ('figma C', 'c', 'ホロライブ'),
+                ('no series', 'd', NULL);
+            INSERT INTO orders (id) VALUES (1), (2);
+            INSERT INTO items (order_id, item_name, price, quantity) VALUES
+                (1, 'HG ガンダム', 2000, 1),
+                (1, 'RG ガンダム', 3000, 2),
+                (2, 'figma C', 5000, 1);
+            "#,
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let repo = SqliteSeriesMasterRepository::new(pool.clone());
+        assert_eq!(repo.sync_from_product_master().await.unwrap(), 5);
+        // 登録済みの表記は再登録しない
+        assert_eq!(repo.sync_from_product_master().await.unwrap(), 0);
+
+        let series = repo.list().await.unwrap();
+        let names: Vec<&str> = series.iter().map(|s| s.canonical_name.as_str()).collect();
+        assert_eq!(
+            names,
+            vec![
+                "アイドルマスター シンデレラガールズ",
+                "ホロライブ",
+                "機動戦士ガンダム"
+            ]
+        );
+        let gundam = series
+            .iter()
+            .find(|s| s.canonical_name == "機動戦士ガンダム")
+            .unwrap();
+        assert_eq!(gundam.aliases.len(), 2);
+        assert_eq!(gundam.product_count, 2);
+
+        let stats = repo.get_series_stats().await.unwrap();
+        assert_eq!(
+            stats[0],
+            SeriesStats {
+                series_name: "機動戦士ガンダム".to_string(),
+                item_count: 2,
+                total_amount: 8000,
+            }
+        );
+        assert_eq!(stats[1].series_name, "ホロライブ");
+    }
+
+    #[tokio::test]
+    async fn test_series_stats_include_tax_and_adjustment() {
+        let pool = setup_test_db().await;
+        sqlx::query(
+            r#"
+            INSERT INTO product_master (raw_name, normalized_name, series) VALUES
+                ('HG ガンダム', 'hgガンダム', '機動戦士ガンダム'),
+                ('figma C', 'c', 'ホロライブ'),
+                ('ねんどろいど D', 'd', 'ホロライブ');
+            -- 注文1: 税抜 10000 円（税額未記載 → 10%）+ 送料 1000 円 = 12000 円を 3:2 で按分
+            -- 注文2: 税込 5000 円 - 割引 500 円 = 4500 円
+            -- 注文3: 削除済みの注文は集計しない
+            INSERT INTO orders (id, tax_included, amount_adjustment, deleted_at) VALUES
+                (1, 0, 1000, NULL),
+                (2, 1, -500, NULL),
+                (3, 1, 0, '2024-01-01 00:00:00');
+            INSERT INTO items (order_id, item_name, price, quantity) VALUES
+                (1, 'HG ガンダム', 3000, 2),
+                (1, 'figma C', 4000, 1),
+                (2, 'ねんどろいど D', 5000, 1),
+                (3, 'HG ガンダム', 9000, 1);
+            "#,
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let repo = SqliteSeriesMasterRepository::new(pool);
+        let stats = repo.get_series_stats().await.unwrap();
+        assert_eq!(
+            stats,
+            vec![
+                SeriesStats {
+                    series_name: "ホロライブ".to_string(),
+                    item_count: 2,
+                    total_amount: 9300,
+                },
+                SeriesStats {
+                    series_name: "機動戦士ガンダム".to_string(),
+                    item_count: 1,
+                    total_amount: 7200,
+                },
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_merge_rename_and_alias_management() {
+        let pool = setup_test_db().await;
+        sqlx::query(
+            r#"
+            INSERT INTO product_master (raw_name, normalized_name, series) VALUES
+                ('A', 'a', 'ホロライブ'),
+                ('B', 'b', 'hololive');
+            "#,
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let repo = SqliteSeriesMasterRepository::new(pool.clone());
+        repo.sync_from_product_master().await.unwrap();
+        let series = repo.list().await.unwrap();
+        assert_eq!(series.len(), 2);
+        let hololive_en = series
+            .iter()
+            .find(|s| s.canonical_name == "hololive")
+            .unwrap()
+            .id;
+        let hololive_ja = series
+            .iter()
+            .find(|s| s.canonical_name == "ホロライブ")
+            .unwrap()
+            .id;
+
+        repo.merge(hololive_en, hololive_ja).await.unwrap();
+        repo.rename(hololive_ja, "ホロライブプロダクション".to_string())
+            .await
+            .unwrap();
+        let series = repo.list().await.unwrap();
+        assert_eq!(series.len(), 1);
+        assert_eq!(series[0].canonical_name, "ホロライブプロダクション");
+        assert_eq!(series[0].aliases.len(), 2);
+        assert_eq!(series[0].product_count, 2);
+
+        repo.set_alias(hololive_ja, "ホロライブ公式".to_string())
+            .await
+            .unwrap();
+        let alias_id = repo.list().await.unwrap()[0]
+            .aliases
+            .iter()
+            .find(|a| a.alias == "ホロライブ公式")
+            .unwrap()
+            .id;
+        repo.delete_alias(alias_id).await.unwrap();
+        assert!(repo.delete_alias(alias_id).await.is_err());
+        assert!(repo.merge(hololive_ja, hololive_ja).await.is_err());
+    }
+}
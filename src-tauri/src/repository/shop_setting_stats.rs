@@ -0,0 +1,221 @@
+use serde::{Deserialize, Serialize};
+use sqlx::sqlite::SqlitePool;
+use sqlx::FromRow;
+use std::collections::BTreeMap;
+use ts_rs::TS;
+
+/// 無効化候補とみなすヒットなし期間（日数、デフォルト）
+pub const DEFAULT_DISABLE_SUGGESTION_INACTIVE_DAYS: i64 = 365;
+
+/// shop_settings 1 件ごとのパース統計
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, FromRow, TS)]
+#[ts(export)]
+pub struct ShopSettingMatchStats {
+    pub id: i64,
+    pub shop_name: String,
+    pub sender_address: String,
+    pub parser_type: String,
+    pub is_enabled: bool,
+    /// パースに成功した累計件数
+    pub matched_count: i64,
+    /// パースに成功した最終日時（UTC）。一度もヒットしていなければ None
+    pub last_matched_at: Option<String>,
+    pub created_at: String,
+}
+
+/// shop_settings のパース統計のDB操作
+pub struct SqliteShopSettingStatsRepository {
+    pool: SqlitePool,
+}
+
+impl SqliteShopSettingStatsRepository {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    /// パースに成功した (sender_address, parser_type) ごとに matched_count を加算し、last_matched_at を更新する
+    ///
+    /// sender_address は候補パーサー選択と同じく大文字小文字を区別せずに照合する。
+    pub async fn record_matches(&self, matches: &[(String, String)]) -> Result<(), String> {
+        if matches.is_empty() {
+            return Ok(());
+        }
+        let mut counts: BTreeMap<(String, &str), i64> = BTreeMap::new();
+        for (sender_address, parser_type) in matches {
+            *counts
+                .entry((sender_address.to_lowercase(), parser_type.as_str()))
+                .or_default() += 1;
+        }
+
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| format!("Failed to begin transaction: {e}"))?;
+        for ((sender_address, parser_type), count) in counts {
+            sqlx::query(
+                r#"
+                UPDATE shop_settings
+                SET matched_count = matched_count + ?,
+                    last_matched_at = datetime('now')
+                WHERE sender_address = ? COLLATE NOCASE AND parser_type = ?
+                "#,
+            )
+            .bind(count)
+            .bind(&sender_address)
+            .bind(parser_type)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| format!("Failed to record shop setting matches: {e}"))?;
+        }
+        tx.commit()
+            .await
+            .map_err(|e| format!("Failed to commit transaction: {e}"))?;
+
+        Ok(())
+    }
+
+    /// 全ショップ設定のパース統計を取得（shop_name, id 順）
+    pub async fn list_match_stats(&self) -> Result<Vec<ShopSettingMatchStats>, String> {
+        sqlx::query_as::<_, ShopSettingMatchStats>(
+            r#"
+            SELECT id, shop_name, sender_address, parser_type, is_enabled,
+                   matched_count, last_matched_at, created_at
+            FROM shop_settings
+            ORDER BY shop_name, id
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| format!("Failed to get shop setting match stats: {e}"))
+    }
+
+    /// 有効な設定のうち `inactive_days` 日以上ヒットしていないものを無効化候補として返す
+    ///
+    /// 一度もヒットしていない設定は作成日時から数える（追加したばかりの設定は候補にしない）。
+    /// 最終ヒット（未ヒットなら作成日時）の古い順。
+    pub async fn suggest_disable_candidates(
+        &self,
+        inactive_days: i64,
+    ) -> Result<Vec<ShopSettingMatchStats>, String> {
+        sqlx::query_as::<_, ShopSettingMatchStats>(
+            r#"
+            SELECT id, shop_name, sender_address, parser_type, is_enabled,
+                   matched_count, last_matched_at, created_at
+            FROM shop_settings
+            WHERE is_enabled = 1
+              AND datetime(COALESCE(last_matched_at, created_at)) < datetime('now', ?)
+            ORDER BY COALESCE(last_matched_at, created_at), shop_name, id
+            "#,
+        )
+        .bind(format!("-{inactive_days} days"))
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| format!("Failed to get shop settings to disable: {e}"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sqlx::sqlite::SqlitePoolOptions;
+
+    async fn setup_test_db() -> SqlitePool {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .expect("Failed to create test database");
+
+        sqlx::query(
+            r#"
+            CREATE TABLE shop_settings (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                shop_name TEXT NOT NULL,
+                sender_address TEXT NOT NULL,
+                parser_type TEXT NOT NULL,
+                is_enabled INTEGER NOT NULL DEFAULT 1,
+                created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+                last_matched_at DATETIME,
+                matched_count INTEGER NOT NULL DEFAULT 0
+            );
+            INSERT INTO shop_settings (shop_name, sender_address, parser_type, is_enabled, created_at) VALUES
+                ('ホビーサーチ', 'hs-support@1999.co.jp', 'hobbysearch_confirm', 1, '2020-01-01 00:00:00'),
+                ('ホビーサーチ', 'hs-support@1999.co.jp', 'hobbysearch_send', 1, '2020-01-01 00:00:00'),
+                ('旧ショップ', 'old@shop.example', 'old_confirm', 0, '2020-01-01 00:00:00'),
+                ('新ショップ', 'new@shop.example', 'new_confirm', 1, datetime('now'));
+            "#,
+        )
+        .execute(&pool)
+        .await
+        .expect("Failed to create shop_settings table");
+
+        pool
+    }
+
+    #[tokio::test]
+    async fn test_record_matches_counts_per_setting() {
+        let pool = setup_test_db().await;
+        let repo = SqliteShopSettingStatsRepository::new(pool);
+
+        repo.record_matches(&[
+            (
+                "HS-Support@1999.co.jp".to_string(),
+                "hobbysearch_confirm".to_string(),
+            ),
+            (
+                "hs-support@1999.co.jp".to_string(),
+                "hobbysearch_confirm".to_string(),
+            ),
+            ("unknown@example.com".to_string(), "x_confirm".to_string()),
+        ])
+        .await
+        .unwrap();
+
+        let stats = repo.list_match_stats().await.unwrap();
+        let confirm = stats
+            .iter()
+            .find(|s| s.parser_type == "hobbysearch_confirm")
+            .unwrap();
+        assert_eq!(confirm.matched_count, 2);
+        assert!(confirm.last_matched_at.is_some());
+        let send = stats
+            .iter()
+            .find(|s| s.parser_type == "hobbysearch_send")
+            .unwrap();
+        assert_eq!(send.matched_count, 0);
+        assert_eq!(send.last_matched_at, None);
+    }
+
+    #[tokio::test]
+    async fn test_suggest_disable_candidates_skips_recent_and_disabled() {
+        let pool = setup_test_db().await;
+        let repo = SqliteShopSettingStatsRepository::new(pool.clone());
+
+        repo.record_matches(&[(
+            "hs-support@1999.co.jp".to_string(),
+            "hobbysearch_confirm".to_string(),
+        )])
+        .await
+        .unwrap();
+
+        // 最近ヒットした設定・無効な設定・作成したばかりの設定は候補にしない
+        let candidates = repo.suggest_disable_candidates(365).await.unwrap();
+        let parser_types: Vec<&str> = candidates.iter().map(|c| c.parser_type.as_str()).collect();
+        assert_eq!(parser_types, vec!["hobbysearch_send"]);
+
+        // 最終ヒットが古ければ候補になる
+        sqlx::query(
+            "UPDATE shop_settings SET last_matched_at = '2021-06-01 00:00:00' WHERE parser_type = 'hobbysearch_confirm'",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+        let candidates = repo.suggest_disable_candidates(365).await.unwrap();
+        let parser_types: Vec<&str> = candidates.iter().map(|c| c.parser_type.as_str()).collect();
+        assert_eq!(
+            parser_types,
+            vec!["hobbysearch_send", "hobbysearch_confirm"]
+        );
+    }
+}
@@ -0,0 +1,246 @@
+use serde::{Deserialize, Serialize};
+use sqlx::sqlite::SqlitePool;
+use std::collections::{BTreeSet, HashMap};
+use ts_rs::TS;
+
+use crate::logic::sync_logic::extract_email_address;
+
+/// 候補とみなす最小メール件数（デフォルト）
+pub const DEFAULT_SHOP_SUGGESTION_MIN_COUNT: i64 = 3;
+
+/// 返す候補数の上限（デフォルト）
+pub const DEFAULT_SHOP_SUGGESTION_LIMIT: i64 = 20;
+
+/// shop_settings 未登録の送信元ドメイン（新規パーサー作成の候補）
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct ShopCandidate {
+    pub domain: String,
+    pub email_count: i64,
+    /// このドメインから届いた送信元アドレス（重複なし・昇順）
+    pub sender_addresses: Vec<String>,
+    /// 最新メールの件名（どのような通知かの目安）
+    pub latest_subject: Option<String>,
+    pub latest_internal_date: Option<i64>,
+}
+
+type SenderRow = (String, i64, Option<i64>, Option<String>);
+
+/// メールアドレスのドメイン部（小文字）を返す
+pub fn email_domain(address: &str) -> Option<String> {
+    extract_email_address(address).and_then(|email| email.split('@').nth(1).map(String::from))
+}
+
+/// `domain` が登録済みドメイン（またはそのサブドメイン）に該当するか
+pub fn is_registered_domain(domain: &str, registered: &BTreeSet<String>) -> bool {
+    registered
+        .iter()
+        .any(|r| domain == r || domain.ends_with(&format!(".{r}")))
+}
+
+/// 送信元ドメイン集計のDB操作
+pub struct SqliteShopSuggestionRepository {
+    pool: SqlitePool,
+}
+
+impl SqliteShopSuggestionRepository {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    /// emails.from_address をドメイン単位で集計し、shop_settings（有効・無効を問わない）に
+    /// 未登録のドメインのうち `min_count` 件以上のものを件数の多い順に最大 `limit` 件返す。
+    pub async fn suggest_new_shops(
+        &self,
+        min_count: i64,
+        limit: i64,
+    ) -> Result<Vec<ShopCandidate>, String> {
+        let registered_addresses: Vec<String> =
+            sqlx::query_scalar("SELECT sender_address FROM shop_settings")
+                .fetch_all(&self.pool)
+                .await
+                .map_err(|e| format!("Failed to fetch shop settings: {e}"))?;
+        let registered: BTreeSet<String> = registered_addresses
+            .iter()
+            .filter_map(|a| email_domain(a))
+            .collect();
+
+        let rows: Vec<SenderRow> = sqlx::query_as(
+            r#"
+            SELECT
+                e.from_address,
+                COUNT(*) AS email_count,
+                MAX(e.internal_date) AS latest_internal_date,
+                (
+                    SELECT l.subject
+                    FROM emails l
+                    WHERE l.from_address = e.from_address
+                    ORDER BY l.internal_date DESC, l.id DESC
+                    LIMIT 1
+                ) AS latest_subject
+            FROM emails e
+            WHERE e.from_address IS NOT NULL
+            GROUP BY e.from_address
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| format!("Failed to aggregate sender addresses: {e}"))?;
+
+        let mut by_domain: HashMap<String, ShopCandidate> = HashMap::new();
+        for (from_address, count, latest_date, latest_subject) in rows {
+            let Some(email) = extract_email_address(&from_address) else {
+                continue;
+            };
+            let Some(domain) = email.split('@').nth(1).map(String::from) else {
+                continue;
+            };
+            if is_registered_domain(&domain, &registered) {
+                continue;
+            }
+
+            let candidate = by_domain
+                .entry(domain.clone())
+                .or_insert_with(|| ShopCandidate {
+                    domain,
+                    email_count: 0,
+                    sender_addresses: Vec::new(),
+                    latest_subject: None,
+                    latest_internal_date: None,
+                });
+            candidate.email_count += count;
+            if !candidate.sender_addresses.contains(&email) {
+                candidate.sender_addresses.push(email);
+            }
+            if latest_date > candidate.latest_internal_date {
+                candidate.latest_internal_date = latest_date;
+                candidate.latest_subject = latest_subject;
+            }
+        }
+
+        let mut candidates: Vec<ShopCandidate> = by_domain
+            .into_values()
+            .filter(|c| c.email_count >= min_count)
+            .map(|mut c| {
+                c.sender_addresses.sort();
+                c
+            })
+            .collect();
+        candidates.sort_by(|a, b| {
+            b.email_count
+                .cmp(&a.email_count)
+                .then_with(|| a.domain.cmp(&b.domain))
+        });
+        candidates.truncate(usize::try_from(limit.max(0)).unwrap_or(usize::MAX));
+        Ok(candidates)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sqlx::sqlite::SqlitePoolOptions;
+
+    async fn setup_test_db() -> SqlitePool {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .expect("Failed to create test database");
+
+        sqlx::query(
+            r#"
+            CREATE TABLE emails (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                message_id TEXT UNIQUE NOT NULL,
+                internal_date INTEGER,
+                from_address TEXT,
+                subject TEXT
+            );
+            CREATE TABLE shop_settings (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                shop_name TEXT NOT NULL,
+                sender_address TEXT NOT NULL,
+                parser_type TEXT NOT NULL,
+                is_enabled INTEGER NOT NULL DEFAULT 1
+            );
+            INSERT INTO shop_settings (shop_name, sender_address, parser_type) VALUES
+                ('ホビーサーチ', 'hs-support@1999.co.jp', 'hobbysearch_confirm');
+            INSERT INTO emails (message_id, internal_date, from_address, subject) VALUES
+                ('m1', 100, 'ホビーサーチ <hs-support@1999.co.jp>', '注文確認'),
+                ('m2', 110, 'info@mail.1999.co.jp', 'お知らせ'),
+                ('m3', 200, 'Shop X <order@shop-x.example>', 'ご注文ありがとうございます'),
+                ('m4', 300, 'SHIP@shop-x.example', '発送のお知らせ'),
+                ('m5', 250, 'order@shop-x.example', 'ご注文ありがとうございます'),
+                ('m6', 400, 'news@small.example', 'メルマガ'),
+                ('m7', 401, 'invalid-sender', '不明');
+            "#,
+        )
+        .execute(&pool)
+        .await
+        .expect("Failed to create tables");
+
+        pool
+    }
+
+    #[test]
+    fn test_email_domain() {
+        assert_eq!(
+            email_domain("Shop <Order@Shop.Example>"),
+            Some("shop.example".to_string())
+        );
+        assert_eq!(email_domain("invalid"), None);
+    }
+
+    #[test]
+    fn test_is_registered_domain_includes_subdomains() {
+        let registered: BTreeSet<String> = ["1999.co.jp".to_string()].into();
+        assert!(is_registered_domain("1999.co.jp", &registered));
+        assert!(is_registered_domain("mail.1999.co.jp", &registered));
+        assert!(!is_registered_domain("x1999.co.jp", &registered));
+    }
+
+    #[tokio::test]
+    async fn test_suggest_new_shops_excludes_registered_domains() {
+        let pool = setup_test_db().await;
+        let repo = SqliteShopSuggestionRepository::new(pool);
+
+        let candidates = repo.suggest_new_shops(1, 10).await.unwrap();
+        assert_eq!(
+            candidates,
+            vec![
+                ShopCandidate {
+                    domain: "shop-x.example".to_string(),
+                    email_count: 3,
+                    sender_addresses: vec![
+                        "order@shop-x.example".to_string(),
+                        "ship@shop-x.example".to_string(),
+                    ],
+                    latest_subject: Some("発送のお知らせ".to_string()),
+                    latest_internal_date: Some(300),
+                },
+                ShopCandidate {
+                    domain: "small.example".to_string(),
+                    email_count: 1,
+                    sender_addresses: vec!["news@small.example".to_string()],
+                    latest_subject: Some("メルマガ".to_string()),
+                    latest_internal_date: Some(400),
+                },
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_suggest_new_shops_applies_min_count_and_limit() {
+        let pool = setup_test_db().await;
+        let repo = SqliteShopSuggestionRepository::new(pool);
+
+        let candidates = repo.suggest_new_shops(2, 10).await.unwrap();
+        assert_eq!(candidates.len(), 1);
+        assert_eq!(candidates[0].domain, "shop-x.example");
+
+        let candidates = repo.suggest_new_shops(1, 1).await.unwrap();
+        assert_eq!(candidates.len(), 1);
+        assert_eq!(candidates[0].domain, "shop-x.example");
+    }
+}
@@ -3,14 +3,19 @@ use async_trait::async_trait;
 use mockall::automock;
 use serde::{Deserialize, Serialize};
 use sqlx::sqlite::SqlitePool;
+use ts_rs::TS;
+
+use crate::parsers::cancel_info::CancelReason;
 
 /// 注文・商品サマリ統計
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
 pub struct OrderStats {
     pub total_orders: i64,
     pub total_items: i64,
     /// 正規化名を持つユニーク商品数（商品名解析・商品画像と同一指標）
     pub distinct_items_with_normalized: i64,
+    /// 購入金額合計（税抜表示の注文も税込に換算）
     pub total_amount: i64,
 }
 
@@ -23,7 +28,8 @@ pub trait OrderStatsRepository: Send + Sync {
 }
 
 /// 配送状況サマリ（注文ごとの最新配送ステータス別件数）
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize, Default, TS)]
+#[ts(export)]
 pub struct DeliveryStats {
     pub not_shipped: i64,
     pub preparing: i64,
@@ -47,7 +53,8 @@ pub trait DeliveryStatsRepository: Send + Sync {
 }
 
 /// 商品名解析（product_master）進捗
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
 pub struct ProductMasterStats {
     /// product_master テーブルの件数
     pub product_master_count: i64,
@@ -66,7 +73,8 @@ pub trait ProductMasterStatsRepository: Send + Sync {
 }
 
 /// 店舗設定・画像サマリ
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
 pub struct MiscStats {
     pub shop_settings_count: i64,
     pub shop_settings_enabled_count: i64,
@@ -83,6 +91,382 @@ pub trait MiscStatsRepository: Send + Sync {
     async fn get_misc_stats(&self) -> Result<MiscStats, String>;
 }
 
+/// 月別支出（注文日の年月ごとの商品金額合計）
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct MonthlySpending {
+    /// 年月（YYYY-MM）
+    pub month: String,
+    pub total_amount: i64,
+    pub order_count: i64,
+}
+
+/// 月別支出のDB操作を抽象化するトレイト
+#[cfg_attr(test, automock)]
+#[async_trait]
+pub trait SpendingStatsRepository: Send + Sync {
+    /// 月別支出を年月の昇順で取得する。
+    ///
+    /// `from` / `to` は `YYYY-MM-DD` 形式で、`from` 以上 `to` 未満の注文日を対象とする（None は無制限）。
+    async fn get_monthly_spending(
+        &self,
+        from: Option<String>,
+        to: Option<String>,
+    ) -> Result<Vec<MonthlySpending>, String>;
+}
+
+/// SQLiteを使用したSpendingStatsRepositoryの実装
+pub struct SqliteSpendingStatsRepository {
+    pool: SqlitePool,
+}
+
+impl SqliteSpendingStatsRepository {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl SpendingStatsRepository for SqliteSpendingStatsRepository {
+    async fn get_monthly_spending(
+        &self,
+        from: Option<String>,
+        to: Option<String>,
+    ) -> Result<Vec<MonthlySpending>, String> {
+        // 税抜表示の注文は tax_amount（未記載なら商品合計の 10%）を加算して税込に揃え、
+        // 送料・割引などの調整額を加算する
+        let rows: Vec<(String, i64, i64)> = sqlx::query_as(
+            r#"
+            WITH order_amounts AS (
+                SELECT
+                    COALESCE(o.order_date, o.created_at) AS ordered_at,
+                    o.tax_amount,
+                    o.tax_included,
+                    o.amount_adjustment,
+                    COALESCE(SUM(i.price * i.quantity), 0) AS items_amount
+                FROM orders o
+                LEFT JOIN items i ON i.order_id = o.id AND i.deleted_at IS NULL
+                WHERE o.deleted_at IS NULL
+                GROUP BY o.id
+            )
+            SELECT
+                strftime('%Y-%m', ordered_at) AS month,
+                COALESCE(SUM(
+                    items_amount
+                    + CASE WHEN tax_included = 0
+                           THEN COALESCE(tax_amount, CAST(items_amount * 0.1 AS INTEGER))
+                           ELSE 0 END
+                    + amount_adjustment
+                ), 0) AS total_amount,
+                COUNT(*) AS order_count
+            FROM order_amounts
+            WHERE ordered_at IS NOT NULL
+              AND (?1 IS NULL OR ordered_at >= ?1)
+              AND (?2 IS NULL OR ordered_at < ?2)
+            GROUP BY month
+            ORDER BY month ASC
+            "#,
+        )
+        .bind(from)
+        .bind(to)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| format!("Failed to fetch monthly spending: {e}"))?;
+
+        Ok(rows
+            .into_iter()
+            .map(|(month, total_amount, order_count)| MonthlySpending {
+                month,
+                total_amount,
+                order_count,
+            })
+            .collect())
+    }
+}
+
+/// 本日配達予定の荷物（ホーム画面・トレイ通知用）
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct TodayDelivery {
+    pub order_id: i64,
+    pub shop_name: Option<String>,
+    pub order_number: Option<String>,
+    pub tracking_number: Option<String>,
+    pub carrier: Option<String>,
+    pub delivery_status: String,
+    /// 注文内の商品名
+    pub item_names: Vec<String>,
+}
+
+/// `get_today_overview` のレスポンス
+#[derive(Debug, Clone, Default, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct TodayOverview {
+    /// 基準日（JST, YYYY-MM-DD）
+    pub date: String,
+    /// 本日配達予定（最新ステータスが out_for_delivery、または配達予定日が本日）
+    pub deliveries_today: Vec<TodayDelivery>,
+    /// 今週（本日から 7 日以内）発売予定の商品名（発売日が日単位で分かっている商品のみ）
+    pub releasing_this_week: Vec<String>,
+    /// 未払いの支払い予定のうち、支払期限が 7 日以内（期限切れを含む）の注文番号
+    pub payment_due_soon: Vec<String>,
+}
+
+/// ホーム画面ウィジェット用データのDB操作を抽象化するトレイト
+#[cfg_attr(test, automock)]
+#[async_trait]
+pub trait OverviewRepository: Send + Sync {
+    /// `today`（JST, YYYY-MM-DD）時点の概要を取得
+    async fn get_today_overview(&self, today: String) -> Result<TodayOverview, String>;
+}
+
+/// SQLiteを使用したOverviewRepositoryの実装
+pub struct SqliteOverviewRepository {
+    pool: SqlitePool,
+}
+
+impl SqliteOverviewRepository {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl OverviewRepository for SqliteOverviewRepository {
+    async fn get_today_overview(&self, today: String) -> Result<TodayOverview, String> {
+        let rows: Vec<(
+            i64,
+            Option<String>,
+            Option<String>,
+            Option<String>,
+            Option<String>,
+            String,
+        )> = sqlx::query_as(
+            r#"
+            WITH latest_delivery AS (
+                SELECT order_id, tracking_number, carrier, delivery_status, estimated_delivery
+                FROM (
+                    SELECT order_id, tracking_number, carrier, delivery_status, estimated_delivery,
+                           ROW_NUMBER() OVER (PARTITION BY order_id ORDER BY updated_at DESC) AS rn
+                    FROM deliveries
+                ) t
+                WHERE rn = 1
+            )
+            SELECT o.id, o.shop_name, o.order_number, ld.tracking_number, ld.carrier, ld.delivery_status
+            FROM orders o
+            INNER JOIN latest_delivery ld ON ld.order_id = o.id
+            WHERE o.deleted_at IS NULL
+              AND (ld.delivery_status = 'out_for_delivery'
+                   OR (ld.delivery_status NOT IN ('delivered', 'cancelled', 'returned')
+                       AND date(ld.estimated_delivery) = ?1))
+            ORDER BY o.id ASC
+            "#,
+        )
+        .bind(&today)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| format!("Failed to fetch today's deliveries: {e}"))?;
+
+        let mut deliveries_today = Vec::with_capacity(rows.len());
+        for (order_id, shop_name, order_number, tracking_number, carrier, delivery_status) in rows {
+            let item_names: Vec<String> = sqlx::query_scalar(
+                "SELECT item_name FROM items WHERE order_id = ? AND deleted_at IS NULL ORDER BY id",
+            )
+            .bind(order_id)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| format!("Failed to fetch items for order {order_id}: {e}"))?;
+            deliveries_today.push(TodayDelivery {
+                order_id,
+                shop_name,
+                order_number,
+                tracking_number,
+                carrier,
+                delivery_status,
+                item_names,
+            });
+        }
+
+        let releasing_this_week: Vec<String> = sqlx::query_scalar(
+            r#"
+            SELECT i.item_name
+            FROM items i
+            INNER JOIN orders o ON o.id = i.order_id
+            WHERE i.deleted_at IS NULL
+              AND o.deleted_at IS NULL
+              AND length(i.release_date) = 10
+              AND i.release_date BETWEEN ?1 AND date(?1, '+6 days')
+            ORDER BY i.release_date ASC, i.id ASC
+            "#,
+        )
+        .bind(&today)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| format!("Failed to fetch releasing items: {e}"))?;
+
+        let payment_due_soon: Vec<String> = sqlx::query_scalar(
+            r#"
+            SELECT o.order_number
+            FROM payments p
+            INNER JOIN orders o
+                ON p.shop_domain = COALESCE(o.shop_domain, '') AND p.order_number = o.order_number
+            WHERE p.status = 'pending'
+              AND p.due_date IS NOT NULL
+              AND date(p.due_date) <= date(?1, '+7 days')
+              AND o.deleted_at IS NULL
+            GROUP BY o.id
+            ORDER BY MIN(date(p.due_date)) ASC, o.id ASC
+            "#,
+        )
+        .bind(&today)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| format!("Failed to fetch payments due soon: {e}"))?;
+
+        Ok(TodayOverview {
+            date: today,
+            deliveries_today,
+            releasing_this_week,
+            payment_due_soon,
+        })
+    }
+}
+
+/// レイテンシの要約統計（秒）
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct LatencyStats {
+    pub sample_count: i64,
+    pub avg_seconds: Option<f64>,
+    pub p50_seconds: Option<i64>,
+    pub p90_seconds: Option<i64>,
+    pub max_seconds: Option<i64>,
+}
+
+/// メール受信〜注文登録までのレイテンシ指標
+///
+/// 受信日時は `emails.internal_date`、同期日時は `emails.created_at`、
+/// パース（注文登録）日時は `order_emails.created_at` を用いる。
+#[derive(Debug, Clone, Default, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct IngestionLatencyMetrics {
+    /// 集計対象期間（受信日時が直近 N 日のメール）
+    pub days: i64,
+    /// 受信 → 同期
+    pub sync: LatencyStats,
+    /// 同期 → 注文登録
+    pub parse: LatencyStats,
+    /// 受信 → 注文登録
+    pub total: LatencyStats,
+    /// 期間内に受信し、まだパースされていないメール数
+    pub pending_parse_count: i64,
+}
+
+/// 秒単位のレイテンシ列から要約統計を計算する（負値は時計ずれとして 0 に丸める）
+pub fn summarize_latencies(mut values: Vec<i64>) -> LatencyStats {
+    if values.is_empty() {
+        return LatencyStats::default();
+    }
+    for v in values.iter_mut() {
+        *v = (*v).max(0);
+    }
+    values.sort_unstable();
+    let n = values.len();
+    let percentile = |p: f64| values[((n as f64 * p).ceil() as usize).clamp(1, n) - 1];
+    LatencyStats {
+        sample_count: n as i64,
+        avg_seconds: Some(values.iter().sum::<i64>() as f64 / n as f64),
+        p50_seconds: Some(percentile(0.5)),
+        p90_seconds: Some(percentile(0.9)),
+        max_seconds: values.last().copied(),
+    }
+}
+
+/// レイテンシの生データ（秒）
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct IngestionLatencySamples {
+    pub sync: Vec<i64>,
+    pub parse: Vec<i64>,
+    pub total: Vec<i64>,
+    pub pending_parse_count: i64,
+}
+
+/// レイテンシ指標のDB操作を抽象化するトレイト
+#[cfg_attr(test, automock)]
+#[async_trait]
+pub trait LatencyMetricsRepository: Send + Sync {
+    /// 受信日時が `since_ms`（UNIX ミリ秒）以降のメールについてレイテンシの生データを取得
+    async fn get_ingestion_latency(&self, since_ms: i64)
+        -> Result<IngestionLatencySamples, String>;
+}
+
+/// SQLiteを使用したLatencyMetricsRepositoryの実装
+pub struct SqliteLatencyMetricsRepository {
+    pool: SqlitePool,
+}
+
+impl SqliteLatencyMetricsRepository {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl LatencyMetricsRepository for SqliteLatencyMetricsRepository {
+    async fn get_ingestion_latency(
+        &self,
+        since_ms: i64,
+    ) -> Result<IngestionLatencySamples, String> {
+        let rows: Vec<(i64, Option<i64>)> = sqlx::query_as(
+            r#"
+            SELECT
+                CAST(strftime('%s', e.created_at) AS INTEGER) - e.internal_date / 1000 AS sync_latency,
+                (
+                    SELECT MIN(CAST(strftime('%s', oe.created_at) AS INTEGER))
+                    FROM order_emails oe
+                    WHERE oe.email_id = e.id
+                ) - CAST(strftime('%s', e.created_at) AS INTEGER) AS parse_latency
+            FROM emails e
+            WHERE e.internal_date IS NOT NULL
+              AND e.created_at IS NOT NULL
+              AND e.internal_date >= ?
+            "#,
+        )
+        .bind(since_ms)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| format!("Failed to fetch ingestion latency: {e}"))?;
+
+        let pending_parse_count: i64 = sqlx::query_scalar(
+            r#"
+            SELECT COUNT(*) FROM emails
+            WHERE internal_date >= ? AND analysis_status = 'pending'
+            "#,
+        )
+        .bind(since_ms)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| format!("Failed to count pending emails: {e}"))?;
+
+        let mut sync = Vec::with_capacity(rows.len());
+        let mut parse = Vec::new();
+        let mut total = Vec::new();
+        for (sync_latency, parse_latency) in rows {
+            sync.push(sync_latency);
+            if let Some(parse_latency) = parse_latency {
+                parse.push(parse_latency);
+                total.push(sync_latency.max(0) + parse_latency.max(0));
+            }
+        }
+        Ok(IngestionLatencySamples {
+            sync,
+            parse,
+            total,
+            pending_parse_count,
+        })
+    }
+}
+
 /// SQLiteを使用したMiscStatsRepositoryの実装
 pub struct SqliteMiscStatsRepository {
     pool: SqlitePool,
@@ -103,7 +487,7 @@ impl MiscStatsRepository for SqliteMiscStatsRepository {
                 (SELECT COUNT(*) FROM shop_settings) AS shop_settings_count,
                 (SELECT COUNT(*) FROM shop_settings WHERE is_enabled = 1) AS shop_settings_enabled_count,
                 (SELECT COUNT(*) FROM images) AS images_count,
-                (SELECT COUNT(DISTINCT item_name_normalized) FROM items WHERE item_name_normalized IS NOT NULL) AS distinct_items_with_normalized
+                (SELECT COUNT(DISTINCT item_name_normalized) FROM items WHERE item_name_normalized IS NOT NULL AND deleted_at IS NULL) AS distinct_items_with_normalized
             "#,
         )
         .fetch_one(&self.pool)
@@ -137,8 +521,8 @@ impl ProductMasterStatsRepository for SqliteProductMasterStatsRepository {
             r#"
             SELECT
                 (SELECT COUNT(*) FROM product_master) AS product_master_count,
-                (SELECT COUNT(DISTINCT item_name_normalized) FROM items WHERE item_name_normalized IS NOT NULL) AS distinct_items,
-                (SELECT COUNT(DISTINCT i.item_name_normalized) FROM items i INNER JOIN product_master pm ON i.item_name_normalized = pm.normalized_name) AS items_parsed
+                (SELECT COUNT(DISTINCT item_name_normalized) FROM items WHERE item_name_normalized IS NOT NULL AND deleted_at IS NULL) AS distinct_items,
+                (SELECT COUNT(DISTINCT i.item_name_normalized) FROM items i INNER JOIN product_master pm ON i.item_name_normalized = pm.normalized_name WHERE i.deleted_at IS NULL) AS items_parsed
             "#,
         )
         .fetch_one(&self.pool)
@@ -189,6 +573,7 @@ impl DeliveryStatsRepository for SqliteDeliveryStatsRepository {
                 END) AS over_1_year_cnt
             FROM orders o
             LEFT JOIN latest_delivery ld ON ld.order_id = o.id
+            WHERE o.deleted_at IS NULL
             GROUP BY status
             "#,
         )
@@ -235,10 +620,25 @@ impl OrderStatsRepository for SqliteOrderStatsRepository {
         let stats: (i64, i64, Option<i64>, Option<i64>) = sqlx::query_as(
             r#"
             SELECT
-                (SELECT COUNT(*) FROM orders) AS total_orders,
-                (SELECT COUNT(*) FROM items) AS total_items,
-                (SELECT COUNT(DISTINCT item_name_normalized) FROM items WHERE item_name_normalized IS NOT NULL) AS distinct_items_with_normalized,
-                (SELECT COALESCE(SUM(price * quantity), 0) FROM items) AS total_amount
+                (SELECT COUNT(*) FROM orders WHERE deleted_at IS NULL) AS total_orders,
+                (SELECT COUNT(*) FROM items WHERE deleted_at IS NULL) AS total_items,
+                (SELECT COUNT(DISTINCT item_name_normalized) FROM items WHERE item_name_normalized IS NOT NULL AND deleted_at IS NULL) AS distinct_items_with_normalized,
+                (
+                    SELECT COALESCE(SUM(
+                        oa.items_amount
+                        + CASE WHEN oa.tax_included = 0
+                               THEN COALESCE(oa.tax_amount, CAST(oa.items_amount * 0.1 AS INTEGER))
+                               ELSE 0 END
+                        + oa.amount_adjustment
+                    ), 0)
+                    FROM (
+                        SELECT o.tax_amount, o.tax_included, o.amount_adjustment, COALESCE(SUM(i.price * i.quantity), 0) AS items_amount
+                        FROM orders o
+                        LEFT JOIN items i ON i.order_id = o.id AND i.deleted_at IS NULL
+                        WHERE o.deleted_at IS NULL
+                        GROUP BY o.id
+                    ) oa
+                ) AS total_amount
             "#,
         )
         .fetch_one(&self.pool)
@@ -253,3 +653,739 @@ impl OrderStatsRepository for SqliteOrderStatsRepository {
         })
     }
 }
+
+/// キャンセル理由別の件数
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct CancelReasonCount {
+    /// キャンセル理由（メールから判別できなかったものは None）
+    pub reason: Option<CancelReason>,
+    /// キャンセルされた商品の延べ数量
+    pub quantity: i64,
+    pub order_count: i64,
+}
+
+/// メーカー別のキャンセル件数
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct CancelMakerCount {
+    pub maker: String,
+    pub quantity: i64,
+    /// キャンセルされた商品の種類数（商品名の重複なし）
+    pub item_count: i64,
+}
+
+/// `get_cancel_reason_stats` のレスポンス
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct CancelReasonStats {
+    /// 理由別（数量の多い順）
+    pub by_reason: Vec<CancelReasonCount>,
+    /// メーカー別（数量の多い順）
+    pub by_maker: Vec<CancelMakerCount>,
+}
+
+/// キャンセル理由別統計のDB操作を抽象化するトレイト
+#[cfg_attr(test, automock)]
+#[async_trait]
+pub trait CancelStatsRepository: Send + Sync {
+    /// cancelled_items を理由別・メーカー別に集計する。
+    ///
+    /// `by_maker` は `reason` で絞り込み（None は全理由）、上位 `maker_limit` 件を返す。
+    async fn get_cancel_reason_stats(
+        &self,
+        reason: Option<CancelReason>,
+        maker_limit: i64,
+    ) -> Result<CancelReasonStats, String>;
+}
+
+/// SQLiteを使用したCancelStatsRepositoryの実装
+pub struct SqliteCancelStatsRepository {
+    pool: SqlitePool,
+}
+
+impl SqliteCancelStatsRepository {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl CancelStatsRepository for SqliteCancelStatsRepository {
+    async fn get_cancel_reason_stats(
+        &self,
+        reason: Option<CancelReason>,
+        maker_limit: i64,
+    ) -> Result<CancelReasonStats, String> {
+        let reason_rows: Vec<(Option<String>, i64, i64)> = sqlx::query_as(
+            r#"
+            SELECT c.cancel_reason, SUM(c.quantity) AS quantity, COUNT(DISTINCT c.order_id)
+            FROM cancelled_items c
+            JOIN orders o ON o.id = c.order_id
+            WHERE o.deleted_at IS NULL
+            GROUP BY c.cancel_reason
+            ORDER BY quantity DESC, c.cancel_reason
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| format!("Failed to fetch cancel reason stats: {e}"))?;
+
+        let maker_rows: Vec<(String, i64, i64)> = sqlx::query_as(
+            r#"
+            SELECT TRIM(c.maker) AS maker, SUM(c.quantity) AS quantity, COUNT(DISTINCT c.item_name)
+            FROM cancelled_items c
+            JOIN orders o ON o.id = c.order_id
+            WHERE o.deleted_at IS NULL
+              AND c.maker IS NOT NULL AND TRIM(c.maker) != ''
+              AND (?1 IS NULL OR c.cancel_reason = ?1)
+            GROUP BY TRIM(c.maker)
+            ORDER BY quantity DESC, maker
+            LIMIT ?2
+            "#,
+        )
+        .bind(reason.map(|r| r.as_str()))
+        .bind(maker_limit)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| format!("Failed to fetch cancel maker stats: {e}"))?;
+
+        Ok(CancelReasonStats {
+            by_reason: reason_rows
+                .into_iter()
+                .map(|(reason, quantity, order_count)| CancelReasonCount {
+                    reason: reason.as_deref().and_then(CancelReason::parse),
+                    quantity,
+                    order_count,
+                })
+                .collect(),
+            by_maker: maker_rows
+                .into_iter()
+                .map(|(maker, quantity, item_count)| CancelMakerCount {
+                    maker,
+                    quantity,
+                    item_count,
+                })
+                .collect(),
+        })
+    }
+}
+
+/// 統計の日次スナップショット（推移グラフの1点）
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct StatsSnapshot {
+    /// 日付（JST, YYYY-MM-DD）
+    pub date: String,
+    pub total_orders: i64,
+    /// 商品数（積み数）
+    pub total_items: i64,
+    pub total_amount: i64,
+    /// 未着の注文数（最新配送ステータスが配達完了・返品・キャンセル以外）
+    pub undelivered: i64,
+    pub not_shipped: i64,
+    pub preparing: i64,
+    pub shipped: i64,
+    pub in_transit: i64,
+    pub out_for_delivery: i64,
+    pub delivered: i64,
+}
+
+type StatsSnapshotRow = (String, i64, i64, i64, i64, i64, i64, i64, i64, i64, i64);
+
+/// 統計スナップショットのDB操作を抽象化するトレイト
+#[cfg_attr(test, automock)]
+#[async_trait]
+pub trait StatsSnapshotRepository: Send + Sync {
+    /// `date`（JST, YYYY-MM-DD）のスナップショットを保存する（同じ日は上書き）
+    async fn save_snapshot(
+        &self,
+        date: String,
+        order_stats: OrderStats,
+        delivery_stats: DeliveryStats,
+    ) -> Result<(), String>;
+
+    /// `from`（YYYY-MM-DD, None は全期間）以降のスナップショットを日付の昇順で取得
+    async fn get_snapshots(&self, from: Option<String>) -> Result<Vec<StatsSnapshot>, String>;
+}
+
+/// SQLiteを使用したStatsSnapshotRepositoryの実装
+pub struct SqliteStatsSnapshotRepository {
+    pool: SqlitePool,
+}
+
+impl SqliteStatsSnapshotRepository {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl StatsSnapshotRepository for SqliteStatsSnapshotRepository {
+    async fn save_snapshot(
+        &self,
+        date: String,
+        order_stats: OrderStats,
+        delivery_stats: DeliveryStats,
+    ) -> Result<(), String> {
+        sqlx::query(
+            r#"
+            INSERT INTO stats_snapshots (
+                snapshot_date, total_orders, total_items, total_amount,
+                not_shipped, preparing, shipped, in_transit, out_for_delivery,
+                delivered, failed, returned, cancelled
+            )
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            ON CONFLICT(snapshot_date) DO UPDATE SET
+                total_orders = excluded.total_orders,
+                total_items = excluded.total_items,
+                total_amount = excluded.total_amount,
+                not_shipped = excluded.not_shipped,
+                preparing = excluded.preparing,
+                shipped = excluded.shipped,
+                in_transit = excluded.in_transit,
+                out_for_delivery = excluded.out_for_delivery,
+                delivered = excluded.delivered,
+                failed = excluded.failed,
+                returned = excluded.returned,
+                cancelled = excluded.cancelled,
+                updated_at = CURRENT_TIMESTAMP
+            "#,
+        )
+        .bind(&date)
+        .bind(order_stats.total_orders)
+        .bind(order_stats.total_items)
+        .bind(order_stats.total_amount)
+        .bind(delivery_stats.not_shipped)
+        .bind(delivery_stats.preparing)
+        .bind(delivery_stats.shipped)
+        .bind(delivery_stats.in_transit)
+        .bind(delivery_stats.out_for_delivery)
+        .bind(delivery_stats.delivered)
+        .bind(delivery_stats.failed)
+        .bind(delivery_stats.returned)
+        .bind(delivery_stats.cancelled)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| format!("Failed to save stats snapshot: {e}"))?;
+        Ok(())
+    }
+
+    async fn get_snapshots(&self, from: Option<String>) -> Result<Vec<StatsSnapshot>, String> {
+        let rows: Vec<StatsSnapshotRow> = sqlx::query_as(
+            r#"
+            SELECT snapshot_date, total_orders, total_items, total_amount,
+                   not_shipped + preparing + shipped + in_transit + out_for_delivery + failed AS undelivered,
+                   not_shipped, preparing, shipped, in_transit, out_for_delivery, delivered
+            FROM stats_snapshots
+            WHERE ?1 IS NULL OR snapshot_date >= ?1
+            ORDER BY snapshot_date ASC
+            "#,
+        )
+        .bind(from)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| format!("Failed to fetch stats snapshots: {e}"))?;
+
+        Ok(rows
+            .into_iter()
+            .map(
+                |(
+                    date,
+                    total_orders,
+                    total_items,
+                    total_amount,
+                    undelivered,
+                    not_shipped,
+                    preparing,
+                    shipped,
+                    in_transit,
+                    out_for_delivery,
+                    delivered,
+                )| StatsSnapshot {
+                    date,
+                    total_orders,
+                    total_items,
+                    total_amount,
+                    undelivered,
+                    not_shipped,
+                    preparing,
+                    shipped,
+                    in_transit,
+                    out_for_delivery,
+                    delivered,
+                },
+            )
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sqlx::sqlite::SqlitePoolOptions;
+
+    async fn setup_test_db() -> SqlitePool {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .expect("Failed to create test database");
+
+        sqlx::query(
+            r#"
+            CREATE TABLE orders (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                shop_domain TEXT,
+                shop_name TEXT,
+                order_number TEXT,
+                order_date DATETIME,
+                tax_amount INTEGER,
+                tax_included INTEGER NOT NULL DEFAULT 1,
+                amount_adjustment INTEGER NOT NULL DEFAULT 0,
+                created_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+                updated_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+                deleted_at DATETIME
+            );
+            CREATE TABLE items (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                order_id INTEGER NOT NULL,
+                item_name TEXT NOT NULL,
+                item_name_normalized TEXT,
+                price INTEGER NOT NULL DEFAULT 0,
+                quantity INTEGER NOT NULL DEFAULT 1,
+                release_date TEXT,
+                deleted_at DATETIME
+            );
+            CREATE TABLE deliveries (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                order_id INTEGER NOT NULL,
+                tracking_number TEXT,
+                carrier TEXT,
+                delivery_status TEXT NOT NULL DEFAULT 'not_shipped',
+                estimated_delivery DATETIME,
+                updated_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP
+            );
+            CREATE TABLE payments (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                shop_domain TEXT NOT NULL,
+                order_number TEXT NOT NULL COLLATE NOCASE,
+                amount INTEGER NOT NULL,
+                due_date DATE,
+                status TEXT NOT NULL DEFAULT 'pending'
+            );
+            "#,
+        )
+        .execute(&pool)
+        .await
+        .expect("Failed to create tables");
+
+        pool
+    }
+
+    #[tokio::test]
+    async fn test_get_monthly_spending_groups_by_month() {
+        let pool = setup_test_db().await;
+        sqlx::query(
+            r#"
+            INSERT INTO orders (id, order_date) VALUES (1, '2024-11-03 10:00:00'), (2, '2024-11-20 10:00:00'), (3, '2025-01-05 10:00:00');
+            INSERT INTO items (order_id, item_name, price, quantity) VALUES (1, 'A', 1000, 2), (2, 'B', 500, 1), (3, 'C', 3000, 1);
+            "#,
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let repo = SqliteSpendingStatsRepository::new(pool);
+        let all = repo.get_monthly_spending(None, None).await.unwrap();
+        assert_eq!(
+            all,
+            vec![
+                MonthlySpending {
+                    month: "2024-11".to_string(),
+                    total_amount: 2500,
+                    order_count: 2,
+                },
+                MonthlySpending {
+                    month: "2025-01".to_string(),
+                    total_amount: 3000,
+                    order_count: 1,
+                },
+            ]
+        );
+
+        let only_2025 = repo
+            .get_monthly_spending(
+                Some("2025-01-01".to_string()),
+                Some("2026-01-01".to_string()),
+            )
+            .await
+            .unwrap();
+        assert_eq!(only_2025.len(), 1);
+        assert_eq!(only_2025[0].month, "2025-01");
+    }
+
+    #[tokio::test]
+    async fn test_get_monthly_spending_adds_tax_for_tax_excluded_orders() {
+        let pool = setup_test_db().await;
+        sqlx::query(
+            r#"
+            INSERT INTO orders (id, order_date, tax_amount, tax_included) VALUES
+                (1, '2025-03-01', 300, 0),
+                (2, '2025-03-02', NULL, 0),
+                (3, '2025-03-03', 100, 1);
+            INSERT INTO items (order_id, item_name, price, quantity) VALUES
+                (1, 'A', 3000, 1), (2, 'B', 2000, 1), (3, 'C', 1100, 1);
+            "#,
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let spending = SqliteSpendingStatsRepository::new(pool.clone())
+            .get_monthly_spending(None, None)
+            .await
+            .unwrap();
+        // 3000 + 300 / 2000 + 200（10% 換算）/ 1100（税込）
+        assert_eq!(spending[0].total_amount, 6600);
+
+        let order_stats = SqliteOrderStatsRepository::new(pool)
+            .get_order_stats()
+            .await
+            .unwrap();
+        assert_eq!(order_stats.total_amount, 6600);
+    }
+
+    #[tokio::test]
+    async fn test_stats_add_amount_adjustment() {
+        let pool = setup_test_db().await;
+        sqlx::query(
+            r#"
+            INSERT INTO orders (id, order_date, amount_adjustment) VALUES
+                (1, '2025-03-01', 660),
+                (2, '2025-03-02', -300);
+            INSERT INTO items (order_id, item_name, price, quantity) VALUES
+                (1, 'A', 3000, 1), (2, 'B', 2000, 1);
+            "#,
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let spending = SqliteSpendingStatsRepository::new(pool.clone())
+            .get_monthly_spending(None, None)
+            .await
+            .unwrap();
+        // 3000 + 送料 660 / 2000 - 割引 300
+        assert_eq!(spending[0].total_amount, 5360);
+
+        let order_stats = SqliteOrderStatsRepository::new(pool)
+            .get_order_stats()
+            .await
+            .unwrap();
+        assert_eq!(order_stats.total_amount, 5360);
+    }
+
+    #[tokio::test]
+    async fn test_stats_exclude_soft_deleted_rows() {
+        let pool = setup_test_db().await;
+        sqlx::query(
+            r#"
+            INSERT INTO orders (id, order_date, deleted_at) VALUES
+                (1, '2025-03-01', NULL),
+                (2, '2025-03-02', '2025-04-01 00:00:00');
+            INSERT INTO items (order_id, item_name, price, quantity, deleted_at) VALUES
+                (1, 'A', 1000, 1, NULL),
+                (1, 'B', 500, 1, '2025-04-01 00:00:00'),
+                (2, 'C', 2000, 1, '2025-04-01 00:00:00');
+            "#,
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let spending = SqliteSpendingStatsRepository::new(pool.clone())
+            .get_monthly_spending(None, None)
+            .await
+            .unwrap();
+        assert_eq!(spending[0].total_amount, 1000);
+        assert_eq!(spending[0].order_count, 1);
+
+        let order_stats = SqliteOrderStatsRepository::new(pool)
+            .get_order_stats()
+            .await
+            .unwrap();
+        assert_eq!(order_stats.total_orders, 1);
+        assert_eq!(order_stats.total_items, 1);
+        assert_eq!(order_stats.total_amount, 1000);
+    }
+
+    #[test]
+    fn test_summarize_latencies() {
+        let stats = summarize_latencies(vec![10, 30, 20, -5, 40, 50, 60, 70, 80, 90]);
+        assert_eq!(stats.sample_count, 10);
+        assert_eq!(stats.p50_seconds, Some(40));
+        assert_eq!(stats.p90_seconds, Some(80));
+        assert_eq!(stats.max_seconds, Some(90));
+        assert_eq!(stats.avg_seconds, Some(44.0));
+
+        assert_eq!(summarize_latencies(vec![]), LatencyStats::default());
+    }
+
+    #[tokio::test]
+    async fn test_get_ingestion_latency() {
+        let pool = setup_test_db().await;
+        sqlx::query(
+            r#"
+            CREATE TABLE emails (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                analysis_status TEXT NOT NULL DEFAULT 'pending',
+                created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+                internal_date INTEGER
+            );
+            CREATE TABLE order_emails (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                order_id INTEGER NOT NULL,
+                email_id INTEGER NOT NULL,
+                created_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP
+            );
+            -- 2025-01-01 00:00:00 UTC = 1735689600
+            INSERT INTO emails (id, analysis_status, created_at, internal_date) VALUES
+                (1, 'completed', '2025-01-01 01:00:00', 1735689600000),
+                (2, 'pending', '2025-01-01 00:10:00', 1735689600000),
+                (3, 'completed', '2024-01-01 00:00:00', 1000);
+            INSERT INTO order_emails (order_id, email_id, created_at) VALUES (1, 1, '2025-01-01 01:30:00');
+            "#,
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let repo = SqliteLatencyMetricsRepository::new(pool);
+        let mut samples = repo.get_ingestion_latency(1735600000000).await.unwrap();
+        samples.sync.sort_unstable();
+        assert_eq!(samples.sync, vec![600, 3600]);
+        assert_eq!(samples.parse, vec![1800]);
+        assert_eq!(samples.total, vec![5400]);
+        assert_eq!(samples.pending_parse_count, 1);
+    }
+
+    #[tokio::test]
+    async fn test_get_today_overview_returns_out_for_delivery() {
+        let pool = setup_test_db().await;
+        sqlx::query(
+            r#"
+            INSERT INTO orders (id, shop_name, order_number) VALUES (1, 'ShopA', 'A-1'), (2, 'ShopB', 'B-1'), (3, 'ShopC', 'C-1');
+            INSERT INTO items (order_id, item_name) VALUES (1, 'Item 1'), (1, 'Item 2'), (2, 'Item 3'), (3, 'Item 4');
+            INSERT INTO deliveries (order_id, tracking_number, delivery_status, updated_at)
+                VALUES (1, '111', 'out_for_delivery', '2025-01-01 00:00:00'),
+                       (2, '222', 'in_transit', '2025-01-01 00:00:00');
+            INSERT INTO deliveries (order_id, tracking_number, delivery_status, estimated_delivery, updated_at)
+                VALUES (3, '333', 'shipped', '2025-01-10', '2025-01-01 00:00:00');
+            "#,
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let repo = SqliteOverviewRepository::new(pool);
+        let overview = repo
+            .get_today_overview("2025-01-10".to_string())
+            .await
+            .unwrap();
+
+        assert_eq!(overview.date, "2025-01-10");
+        let ids: Vec<i64> = overview
+            .deliveries_today
+            .iter()
+            .map(|d| d.order_id)
+            .collect();
+        assert_eq!(ids, vec![1, 3]);
+        assert_eq!(
+            overview.deliveries_today[0].item_names,
+            vec!["Item 1".to_string(), "Item 2".to_string()]
+        );
+        assert!(overview.releasing_this_week.is_empty());
+        assert!(overview.payment_due_soon.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_get_today_overview_releases_and_payments_due() {
+        let pool = setup_test_db().await;
+        sqlx::query(
+            r#"
+            INSERT INTO orders (id, shop_domain, order_number, deleted_at) VALUES
+                (1, 'a.example', 'A-1', NULL),
+                (2, 'b.example', 'B-1', NULL),
+                (3, NULL, 'C-1', NULL),
+                (4, 'd.example', 'D-1', '2025-01-01');
+            INSERT INTO items (order_id, item_name, release_date, deleted_at) VALUES
+                (1, '今週発売', '2025-01-12', NULL),
+                (1, '本日発売', '2025-01-10', NULL),
+                (1, '来週発売', '2025-01-17', NULL),
+                (1, '月のみ', '2025-01', NULL),
+                (1, '削除済み', '2025-01-11', '2025-01-01'),
+                (4, 'ゴミ箱の注文', '2025-01-11', NULL);
+            INSERT INTO payments (shop_domain, order_number, amount, due_date, status) VALUES
+                ('b.example', 'b-1', 1000, '2025-01-15', 'pending'),
+                ('', 'C-1', 1000, '2025-01-05', 'pending'),
+                ('a.example', 'A-1', 1000, '2025-01-12', 'paid'),
+                ('a.example', 'A-1', 1000, '2025-02-01', 'pending'),
+                ('d.example', 'D-1', 1000, '2025-01-11', 'pending');
+            "#,
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let repo = SqliteOverviewRepository::new(pool);
+        let overview = repo
+            .get_today_overview("2025-01-10".to_string())
+            .await
+            .unwrap();
+
+        assert_eq!(
+            overview.releasing_this_week,
+            vec!["本日発売".to_string(), "今週発売".to_string()]
+        );
+        // 期限切れの未払いを先頭に、期限が近い順
+        assert_eq!(
+            overview.payment_due_soon,
+            vec!["C-1".to_string(), "B-1".to_string()]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_get_cancel_reason_stats() {
+        let pool = setup_test_db().await;
+        sqlx::query(
+            r#"
+            CREATE TABLE cancelled_items (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                order_id INTEGER NOT NULL,
+                email_id INTEGER,
+                item_name TEXT NOT NULL,
+                maker TEXT,
+                quantity INTEGER NOT NULL DEFAULT 1,
+                cancel_reason TEXT,
+                created_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP
+            );
+            INSERT INTO orders (id, order_number, deleted_at) VALUES (1, 'A', NULL), (2, 'B', NULL), (3, 'C', '2025-01-01');
+            INSERT INTO cancelled_items (order_id, item_name, maker, quantity, cancel_reason) VALUES
+                (1, '商品1', 'メーカーA', 2, 'discontinued'),
+                (1, '商品2', 'メーカーA', 1, 'discontinued'),
+                (2, '商品3', 'メーカーB', 1, 'discontinued'),
+                (2, '商品4', 'メーカーB', 5, 'out_of_stock'),
+                (2, '商品5', NULL, 1, NULL),
+                (3, '商品6', 'メーカーC', 9, 'discontinued');
+            "#,
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let repo = SqliteCancelStatsRepository::new(pool);
+        let stats = repo
+            .get_cancel_reason_stats(Some(CancelReason::Discontinued), 10)
+            .await
+            .unwrap();
+        assert_eq!(
+            stats.by_reason,
+            vec![
+                CancelReasonCount {
+                    reason: Some(CancelReason::OutOfStock),
+                    quantity: 5,
+                    order_count: 1,
+                },
+                CancelReasonCount {
+                    reason: Some(CancelReason::Discontinued),
+                    quantity: 4,
+                    order_count: 2,
+                },
+                CancelReasonCount {
+                    reason: None,
+                    quantity: 1,
+                    order_count: 1,
+                },
+            ]
+        );
+        // 削除済み注文（メーカーC）は除外
+        assert_eq!(
+            stats.by_maker,
+            vec![
+                CancelMakerCount {
+                    maker: "メーカーA".to_string(),
+                    quantity: 3,
+                    item_count: 2,
+                },
+                CancelMakerCount {
+                    maker: "メーカーB".to_string(),
+                    quantity: 1,
+                    item_count: 1,
+                },
+            ]
+        );
+
+        let all = repo.get_cancel_reason_stats(None, 1).await.unwrap();
+        assert_eq!(all.by_maker.len(), 1);
+        assert_eq!(all.by_maker[0].maker, "メーカーB");
+    }
+
+    #[tokio::test]
+    async fn test_save_and_get_stats_snapshots() {
+        let pool = setup_test_db().await;
+        sqlx::query(include_str!("../../migrations/017_stats_snapshots.sql"))
+            .execute(&pool)
+            .await
+            .unwrap();
+        let repo = SqliteStatsSnapshotRepository::new(pool);
+
+        let order_stats = |total_items| OrderStats {
+            total_orders: 3,
+            total_items,
+            distinct_items_with_normalized: 0,
+            total_amount: 10_000,
+        };
+        let delivery_stats = DeliveryStats {
+            not_shipped: 2,
+            shipped: 1,
+            delivered: 5,
+            failed: 1,
+            cancelled: 4,
+            ..Default::default()
+        };
+
+        repo.save_snapshot(
+            "2026-10-01".to_string(),
+            order_stats(5),
+            delivery_stats.clone(),
+        )
+        .await
+        .unwrap();
+        repo.save_snapshot(
+            "2026-10-02".to_string(),
+            order_stats(6),
+            delivery_stats.clone(),
+        )
+        .await
+        .unwrap();
+        // 同じ日は上書き
+        repo.save_snapshot("2026-10-02".to_string(), order_stats(7), delivery_stats)
+            .await
+            .unwrap();
+
+        let all = repo.get_snapshots(None).await.unwrap();
+        assert_eq!(all.len(), 2);
+        assert_eq!(all[0].date, "2026-10-01");
+        assert_eq!(all[1].total_items, 7);
+        // 未着 = not_shipped + shipped + failed（配達完了・キャンセルは含まない）
+        assert_eq!(all[1].undelivered, 4);
+
+        let recent = repo
+            .get_snapshots(Some("2026-10-02".to_string()))
+            .await
+            .unwrap();
+        assert_eq!(recent.len(), 1);
+        assert_eq!(recent[0].date, "2026-10-02");
+    }
+}
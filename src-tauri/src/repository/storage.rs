@@ -0,0 +1,183 @@
+use serde::{Deserialize, Serialize};
+use sqlx::sqlite::SqlitePool;
+use std::collections::HashMap;
+use ts_rs::TS;
+
+/// テーブル別のストレージ使用量
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct TableStorage {
+    pub name: String,
+    pub row_count: i64,
+    /// テーブル本体とインデックスのページサイズ合計（dbstat が使えない環境では None）
+    pub approx_bytes: Option<i64>,
+}
+
+/// DB のページ使用状況（PRAGMA page_size / page_count / freelist_count）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct DbPageStats {
+    pub page_size: i64,
+    pub page_count: i64,
+    /// 未使用ページ数（VACUUM で回収できる量の目安）
+    pub freelist_count: i64,
+}
+
+/// ストレージ統計のDB操作
+pub struct SqliteStorageStatsRepository {
+    pool: SqlitePool,
+}
+
+impl SqliteStorageStatsRepository {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    pub async fn get_page_stats(&self) -> Result<DbPageStats, String> {
+        Ok(DbPageStats {
+            page_size: self.pragma_i64("page_size").await?,
+            page_count: self.pragma_i64("page_count").await?,
+            freelist_count: self.pragma_i64("freelist_count").await?,
+        })
+    }
+
+    async fn pragma_i64(&self, name: &str) -> Result<i64, String> {
+        sqlx::query_scalar(&format!("PRAGMA {name}"))
+            .fetch_one(&self.pool)
+            .await
+            .map_err(|e| format!("Failed to read PRAGMA {name}: {e}"))
+    }
+
+    /// 全テーブル（FTS のシャドウテーブルを含む）の行数と概算サイズを、サイズの大きい順に返す
+    pub async fn get_table_storage(&self) -> Result<Vec<TableStorage>, String> {
+        let tables: Vec<String> = sqlx::query_scalar(
+            "SELECT name FROM sqlite_master WHERE type = 'table' AND name NOT LIKE 'sqlite_%' ORDER BY name",
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| format!("Failed to list tables: {e}"))?;
+
+        let sizes = self.get_table_sizes().await;
+
+        let mut result = Vec::with_capacity(tables.len());
+        for name in tables {
+            let row_count: i64 = sqlx::query_scalar(&format!(
+                "SELECT COUNT(*) FROM \"{}\"",
+                name.replace('"', "\"\"")
+            ))
+            .fetch_one(&self.pool)
+            .await
+            .map_err(|e| format!("Failed to count rows in {name}: {e}"))?;
+            let approx_bytes = sizes.as_ref().map(|s| s.get(&name).copied().unwrap_or(0));
+            result.push(TableStorage {
+                name,
+                row_count,
+                approx_bytes,
+            });
+        }
+
+        result.sort_by(|a, b| {
+            b.approx_bytes
+                .cmp(&a.approx_bytes)
+                .then_with(|| b.row_count.cmp(&a.row_count))
+                .then_with(|| a.name.cmp(&b.name))
+        });
+        Ok(result)
+    }
+
+    /// dbstat 仮想テーブルからテーブルごとのサイズ（インデックスは親テーブルに合算）を取得する。
+    /// SQLite が dbstat なしでビルドされている場合は None。
+    async fn get_table_sizes(&self) -> Option<HashMap<String, i64>> {
+        let rows: Vec<(String, i64)> = match sqlx::query_as(
+            r#"
+            SELECT COALESCE(m.tbl_name, s.name) AS table_name, SUM(s.pgsize) AS bytes
+            FROM dbstat s
+            LEFT JOIN sqlite_master m ON m.name = s.name
+            GROUP BY table_name
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await
+        {
+            Ok(rows) => rows,
+            Err(e) => {
+                log::warn!("dbstat is not available, table sizes are omitted: {e}");
+                return None;
+            }
+        };
+        Some(rows.into_iter().collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sqlx::sqlite::SqlitePoolOptions;
+
+    async fn setup_test_db() -> SqlitePool {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .expect("Failed to create test database");
+
+        sqlx::query(
+            r#"
+            CREATE TABLE emails (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                body_plain TEXT
+            );
+            CREATE INDEX idx_emails_body ON emails(body_plain);
+            CREATE TABLE orders (id INTEGER PRIMARY KEY AUTOINCREMENT);
+            INSERT INTO orders DEFAULT VALUES;
+            "#,
+        )
+        .execute(&pool)
+        .await
+        .expect("Failed to create tables");
+
+        for i in 0..50 {
+            sqlx::query("INSERT INTO emails (body_plain) VALUES (?)")
+                .bind(format!("{i}:{}", "本文".repeat(200)))
+                .execute(&pool)
+                .await
+                .unwrap();
+        }
+
+        pool
+    }
+
+    #[tokio::test]
+    async fn test_get_table_storage_counts_rows() {
+        let pool = setup_test_db().await;
+        let repo = SqliteStorageStatsRepository::new(pool);
+
+        let tables = repo.get_table_storage().await.unwrap();
+        let row_count = |name: &str| tables.iter().find(|t| t.name == name).map(|t| t.row_count);
+        assert_eq!(row_count("emails"), Some(50));
+        assert_eq!(row_count("orders"), Some(1));
+        // AUTOINCREMENT 用の sqlite_sequence は除外
+        assert_eq!(row_count("sqlite_sequence"), None);
+        assert_eq!(tables[0].name, "emails");
+
+        if let Some(bytes) = tables[0].approx_bytes {
+            let orders_bytes = tables
+                .iter()
+                .find(|t| t.name == "orders")
+                .and_then(|t| t.approx_bytes)
+                .unwrap();
+            assert!(bytes > orders_bytes);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_get_page_stats() {
+        let pool = setup_test_db().await;
+        let repo = SqliteStorageStatsRepository::new(pool);
+
+        let stats = repo.get_page_stats().await.unwrap();
+        assert!(stats.page_size > 0);
+        assert!(stats.page_count > 0);
+        assert!(stats.freelist_count >= 0);
+    }
+}
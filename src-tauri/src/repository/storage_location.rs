@@ -0,0 +1,288 @@
+use serde::{Deserialize, Serialize};
+use sqlx::sqlite::SqlitePool;
+use ts_rs::TS;
+
+/// 商品の保管場所
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct ItemStorageLocation {
+    pub item_id: i64,
+    /// 棚番号（例: `押入れ上段`）
+    pub shelf_number: Option<String>,
+    /// 箱番号（例: `B-3`）
+    pub box_number: Option<String>,
+    pub updated_at: String,
+}
+
+/// 保管場所ごとの商品数
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS, sqlx::FromRow)]
+#[ts(export)]
+pub struct StorageLocationSummary {
+    pub shelf_number: Option<String>,
+    pub box_number: Option<String>,
+    pub item_count: i64,
+}
+
+/// 保管場所に入っている商品
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS, sqlx::FromRow)]
+#[ts(export)]
+pub struct StoredItem {
+    pub item_id: i64,
+    pub order_id: i64,
+    pub item_name: String,
+    pub shop_name: Option<String>,
+    pub order_number: Option<String>,
+    pub order_date: Option<String>,
+    pub shelf_number: Option<String>,
+    pub box_number: Option<String>,
+}
+
+/// 保管場所と商品の対応（items からビジネスキーで引く）
+const LOCATED_ITEMS: &str = r#"
+    FROM items i
+    INNER JOIN orders o ON o.id = i.order_id
+    INNER JOIN item_storage_locations l
+        ON l.shop_domain = COALESCE(o.shop_domain, '')
+       AND l.order_number = o.order_number
+       AND l.item_name = i.item_name
+    WHERE i.deleted_at IS NULL AND o.deleted_at IS NULL
+"#;
+
+/// 商品の保管場所のDB操作
+pub struct SqliteStorageLocationRepository {
+    pool: SqlitePool,
+}
+
+impl SqliteStorageLocationRepository {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    /// 商品の保管場所を取得する（未登録なら None）
+    pub async fn get_by_item(&self, item_id: i64) -> Result<Option<ItemStorageLocation>, String> {
+        let row: Option<(Option<String>, Option<String>, String)> = sqlx::query_as(&format!(
+            "SELECT l.shelf_number, l.box_number, l.updated_at {LOCATED_ITEMS} AND i.id = ?"
+        ))
+        .bind(item_id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| format!("Failed to fetch storage location: {e}"))?;
+
+        Ok(row.map(
+            |(shelf_number, box_number, updated_at)| ItemStorageLocation {
+                item_id,
+                shelf_number,
+                box_number,
+                updated_at,
+            },
+        ))
+    }
+
+    /// 商品の保管場所を記録する（記録済みなら上書き、棚番号・箱番号とも None なら削除）
+    pub async fn set(
+        &self,
+        item_id: i64,
+        shelf_number: Option<&str>,
+        box_number: Option<&str>,
+    ) -> Result<(), String> {
+        let target: Option<(String, String, String)> = sqlx::query_as(
+            r#"
+            SELECT COALESCE(o.shop_domain, ''), o.order_number, i.item_name
+            FROM items i
+            INNER JOIN orders o ON o.id = i.order_id
+            WHERE i.id = ? AND i.deleted_at IS NULL AND o.deleted_at IS NULL
+              AND o.order_number IS NOT NULL
+            "#,
+        )
+        .bind(item_id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| format!("Failed to fetch item: {e}"))?;
+        let Some((shop_domain, order_number, item_name)) = target else {
+            return Err(format!("Item not found: {item_id}"));
+        };
+
+        if shelf_number.is_none() && box_number.is_none() {
+            sqlx::query(
+                r#"
+                DELETE FROM item_storage_locations
+                WHERE shop_domain = ? AND order_number = ? AND item_name = ?
+                "#,
+            )
+            .bind(shop_domain)
+            .bind(order_number)
+            .bind(item_name)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| format!("Failed to delete storage location: {e}"))?;
+            return Ok(());
+        }
+
+        sqlx::query(
+            r#"
+            INSERT INTO item_storage_locations
+                (shop_domain, order_number, item_name, shelf_number, box_number)
+            VALUES (?, ?, ?, ?, ?)
+            ON CONFLICT(shop_domain, order_number, item_name) DO UPDATE SET
+                shelf_number = excluded.shelf_number,
+                box_number = excluded.box_number,
+                updated_at = CURRENT_TIMESTAMP
+            "#,
+        )
+        .bind(shop_domain)
+        .bind(order_number)
+        .bind(item_name)
+        .bind(shelf_number)
+        .bind(box_number)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| format!("Failed to save storage location: {e}"))?;
+        Ok(())
+    }
+
+    /// 使用中の保管場所と商品数を棚番号・箱番号順に返す
+    pub async fn list_locations(&self) -> Result<Vec<StorageLocationSummary>, String> {
+        sqlx::query_as(&format!(
+            r#"
+            SELECT l.shelf_number, l.box_number, COUNT(*) AS item_count
+            {LOCATED_ITEMS}
+            GROUP BY l.shelf_number, l.box_number
+            ORDER BY l.shelf_number IS NULL, l.shelf_number, l.box_number IS NULL, l.box_number
+            "#
+        ))
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| format!("Failed to fetch storage locations: {e}"))
+    }
+
+    /// 保管場所に入っている商品を返す（`shelf_number` / `box_number` は None なら絞り込まない）
+    pub async fn list_items(
+        &self,
+        shelf_number: Option<&str>,
+        box_number: Option<&str>,
+    ) -> Result<Vec<StoredItem>, String> {
+        sqlx::query_as(&format!(
+            r#"
+            SELECT i.id AS item_id, o.id AS order_id, i.item_name, o.shop_name, o.order_number,
+                   o.order_date, l.shelf_number, l.box_number
+            {LOCATED_ITEMS}
+              AND (?1 IS NULL OR l.shelf_number = ?1)
+              AND (?2 IS NULL OR l.box_number = ?2)
+            ORDER BY l.shelf_number IS NULL, l.shelf_number, l.box_number IS NULL, l.box_number,
+                     i.item_name, i.id
+            "#
+        ))
+        .bind(shelf_number)
+        .bind(box_number)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| format!("Failed to fetch stored items: {e}"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sqlx::sqlite::SqlitePoolOptions;
+
+    async fn setup_test_db() -> SqlitePool {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .expect("Failed to create test database");
+
+        sqlx::raw_sql(
+            r#"
+            CREATE TABLE orders (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                shop_domain TEXT,
+                shop_name TEXT,
+                order_number TEXT,
+                order_date DATETIME,
+                deleted_at DATETIME
+            );
+            CREATE TABLE items (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                order_id INTEGER NOT NULL,
+                item_name TEXT NOT NULL,
+                deleted_at DATETIME
+            );
+            INSERT INTO orders (id, shop_domain, shop_name, order_number, order_date) VALUES
+                (1, 'a.example.com', 'ショップA', 'A-1', '2025-03-10'),
+                (2, NULL, 'ショップB', 'B-1', '2025-03-11');
+            INSERT INTO items (id, order_id, item_name) VALUES
+                (1, 1, 'ねんどろいど 初音ミク'), (2, 1, 'figma 鏡音リン'), (3, 2, 'HG ザク');
+            "#,
+        )
+        .execute(&pool)
+        .await
+        .expect("Failed to create tables");
+        sqlx::raw_sql(include_str!(
+            "../../migrations/032_item_storage_locations.sql"
+        ))
+        .execute(&pool)
+        .await
+        .expect("Failed to create item_storage_locations table");
+
+        pool
+    }
+
+    #[tokio::test]
+    async fn test_set_and_get_survives_item_recreation() {
+        let pool = setup_test_db().await;
+        let repo = SqliteStorageLocationRepository::new(pool.clone());
+
+        assert!(repo.set(99, Some("押入れ上段"), None).await.is_err());
+        repo.set(1, Some("押入れ上段"), Some("B-3")).await.unwrap();
+        repo.set(1, Some("押入れ上段"), Some("B-4")).await.unwrap();
+
+        let location = repo.get_by_item(1).await.unwrap().unwrap();
+        assert_eq!(location.shelf_number.as_deref(), Some("押入れ上段"));
+        assert_eq!(location.box_number.as_deref(), Some("B-4"));
+        assert_eq!(repo.get_by_item(2).await.unwrap(), None);
+
+        // 再パースで items が作り直されても同じ商品の保管場所として引ける
+        sqlx::raw_sql(
+            "DELETE FROM items WHERE id = 1;
+             INSERT INTO items (id, order_id, item_name) VALUES (10, 1, 'ねんどろいど 初音ミク');",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+        assert!(repo.get_by_item(10).await.unwrap().is_some());
+
+        repo.set(10, None, None).await.unwrap();
+        assert_eq!(repo.get_by_item(10).await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn test_list_locations_and_items() {
+        let pool = setup_test_db().await;
+        let repo = SqliteStorageLocationRepository::new(pool);
+        repo.set(1, Some("押入れ上段"), Some("B-3")).await.unwrap();
+        repo.set(2, Some("押入れ上段"), Some("B-3")).await.unwrap();
+        repo.set(3, None, Some("A-1")).await.unwrap();
+
+        let locations = repo.list_locations().await.unwrap();
+        assert_eq!(locations.len(), 2);
+        assert_eq!(locations[0].shelf_number.as_deref(), Some("押入れ上段"));
+        assert_eq!(locations[0].item_count, 2);
+        assert_eq!(locations[1].shelf_number, None);
+
+        let items = repo
+            .list_items(Some("押入れ上段"), Some("B-3"))
+            .await
+            .unwrap();
+        assert_eq!(
+            items.iter().map(|i| i.item_id).collect::<Vec<_>>(),
+            vec![2, 1]
+        );
+        assert_eq!(items[0].shop_name.as_deref(), Some("ショップA"));
+
+        let boxed = repo.list_items(None, Some("A-1")).await.unwrap();
+        assert_eq!(boxed.len(), 1);
+        assert_eq!(boxed[0].item_name, "HG ザク");
+        assert_eq!(repo.list_items(None, None).await.unwrap().len(), 3);
+    }
+}
@@ -0,0 +1,434 @@
+use serde::{Deserialize, Serialize};
+use sqlx::sqlite::SqlitePool;
+use ts_rs::TS;
+
+/// ゴミ箱内の注文（論理削除済み）
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS, sqlx::FromRow)]
+#[ts(export)]
+pub struct TrashedOrder {
+    pub order_id: i64,
+    pub shop_name: Option<String>,
+    pub order_number: Option<String>,
+    pub order_date: Option<String>,
+    pub item_count: i64,
+    pub total_amount: i64,
+    pub deleted_at: String,
+}
+
+/// ゴミ箱内の商品（注文は残したまま商品のみ論理削除したもの）
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS, sqlx::FromRow)]
+#[ts(export)]
+pub struct TrashedItem {
+    pub item_id: i64,
+    pub order_id: i64,
+    pub shop_name: Option<String>,
+    pub order_number: Option<String>,
+    pub item_name: String,
+    pub price: i64,
+    pub quantity: i64,
+    pub deleted_at: String,
+}
+
+/// ゴミ箱一覧
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct Trash {
+    pub orders: Vec<TrashedOrder>,
+    pub items: Vec<TrashedItem>,
+}
+
+/// orders / items の論理削除（deleted_at）と復元のDB操作
+pub struct SqliteTrashRepository {
+    pool: SqlitePool,
+}
+
+impl SqliteTrashRepository {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    /// 注文を論理削除する。注文内の商品も同じ削除日時で論理削除する。
+    pub async fn delete_order(&self, order_id: i64) -> Result<(), String> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| format!("Failed to start transaction: {e}"))?;
+
+        let result = sqlx::query(
+            "UPDATE orders SET deleted_at = CURRENT_TIMESTAMP WHERE id = ? AND deleted_at IS NULL",
+        )
+        .bind(order_id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| format!("Failed to delete order: {e}"))?;
+        if result.rows_affected() == 0 {
+            return Err(format!("Order not found or already deleted: {order_id}"));
+        }
+
+        sqlx::query(
+            r#"
+            UPDATE items
+            SET deleted_at = (SELECT deleted_at FROM orders WHERE id = ?1)
+            WHERE order_id = ?1 AND deleted_at IS NULL
+            "#,
+        )
+        .bind(order_id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| format!("Failed to delete items: {e}"))?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO trashed_orders (shop_domain, order_number, deleted_at)
+            SELECT COALESCE(shop_domain, ''), order_number, deleted_at
+            FROM orders
+            WHERE id = ? AND order_number IS NOT NULL
+            ON CONFLICT(shop_domain, order_number) DO UPDATE SET deleted_at = excluded.deleted_at
+            "#,
+        )
+        .bind(order_id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| format!("Failed to record trashed order: {e}"))?;
+
+        tx.commit()
+            .await
+            .map_err(|e| format!("Failed to commit transaction: {e}"))
+    }
+
+    /// 注文を復元する。注文と同時に削除された商品も復元し、それ以前に個別削除された商品はゴミ箱に残す。
+    pub async fn restore_order(&self, order_id: i64) -> Result<(), String> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| format!("Failed to start transaction: {e}"))?;
+
+        sqlx::query(
+            r#"
+            UPDATE items
+            SET deleted_at = NULL
+            WHERE order_id = ?1
+              AND deleted_at = (SELECT deleted_at FROM orders WHERE id = ?1)
+            "#,
+        )
+        .bind(order_id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| format!("Failed to restore items: {e}"))?;
+
+        let result = sqlx::query(
+            "UPDATE orders SET deleted_at = NULL WHERE id = ? AND deleted_at IS NOT NULL",
+        )
+        .bind(order_id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| format!("Failed to restore order: {e}"))?;
+        if result.rows_affected() == 0 {
+            return Err(format!("Deleted order not found: {order_id}"));
+        }
+
+        sqlx::query(
+            r#"
+            DELETE FROM trashed_orders
+            WHERE EXISTS (
+                SELECT 1 FROM orders o
+                WHERE o.id = ?
+                  AND trashed_orders.shop_domain = COALESCE(o.shop_domain, '')
+                  AND trashed_orders.order_number = o.order_number
+            )
+            "#,
+        )
+        .bind(order_id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| format!("Failed to clear trashed order: {e}"))?;
+
+        tx.commit()
+            .await
+            .map_err(|e| format!("Failed to commit transaction: {e}"))
+    }
+
+    /// 商品を論理削除する
+    pub async fn delete_item(&self, item_id: i64) -> Result<(), String> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| format!("Failed to start transaction: {e}"))?;
+
+        let result = sqlx::query(
+            "UPDATE items SET deleted_at = CURRENT_TIMESTAMP WHERE id = ? AND deleted_at IS NULL",
+        )
+        .bind(item_id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| format!("Failed to delete item: {e}"))?;
+
+        if result.rows_affected() == 0 {
+            return Err(format!("Item not found or already deleted: {item_id}"));
+        }
+
+        sqlx::query(
+            r#"
+            INSERT INTO trashed_items (shop_domain, order_number, item_name, deleted_at)
+            SELECT COALESCE(o.shop_domain, ''), o.order_number, i.item_name, i.deleted_at
+            FROM items i
+            INNER JOIN orders o ON o.id = i.order_id
+            WHERE i.id = ? AND o.order_number IS NOT NULL
+            ON CONFLICT(shop_domain, order_number, item_name) DO UPDATE SET deleted_at = excluded.deleted_at
+            "#,
+        )
+        .bind(item_id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| format!("Failed to record trashed item: {e}"))?;
+
+        tx.commit()
+            .await
+            .map_err(|e| format!("Failed to commit transaction: {e}"))
+    }
+
+    /// 商品を復元する。注文ごと削除されている場合は注文を復元する必要がある。
+    pub async fn restore_item(&self, item_id: i64) -> Result<(), String> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| format!("Failed to start transaction: {e}"))?;
+
+        let result = sqlx::query(
+            r#"
+            UPDATE items
+            SET deleted_at = NULL
+            WHERE id = ?
+              AND deleted_at IS NOT NULL
+              AND EXISTS (
+                  SELECT 1 FROM orders o WHERE o.id = items.order_id AND o.deleted_at IS NULL
+              )
+            "#,
+        )
+        .bind(item_id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| format!("Failed to restore item: {e}"))?;
+
+        if result.rows_affected() == 0 {
+            return Err(format!(
+                "Deleted item not found or its order is deleted: {item_id}"
+            ));
+        }
+
+        sqlx::query(
+            r#"
+            DELETE FROM trashed_items
+            WHERE EXISTS (
+                SELECT 1 FROM items i
+                INNER JOIN orders o ON o.id = i.order_id
+                WHERE i.id = ?
+                  AND trashed_items.shop_domain = COALESCE(o.shop_domain, '')
+                  AND trashed_items.order_number = o.order_number
+                  AND trashed_items.item_name = i.item_name
+            )
+            "#,
+        )
+        .bind(item_id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| format!("Failed to clear trashed item: {e}"))?;
+
+        tx.commit()
+            .await
+            .map_err(|e| format!("Failed to commit transaction: {e}"))
+    }
+
+    /// ゴミ箱一覧（削除日時の新しい順）
+    pub async fn list(&self) -> Result<Trash, String> {
+        let orders: Vec<TrashedOrder> = sqlx::query_as(
+            r#"
+            SELECT o.id AS order_id, o.shop_name, o.order_number, o.order_date,
+                   COUNT(i.id) AS item_count,
+                   COALESCE(SUM(i.price * i.quantity), 0) AS total_amount,
+                   o.deleted_at
+            FROM orders o
+            LEFT JOIN items i ON i.order_id = o.id AND i.deleted_at = o.deleted_at
+            WHERE o.deleted_at IS NOT NULL
+            GROUP BY o.id
+            ORDER BY o.deleted_at DESC, o.id DESC
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| format!("Failed to fetch deleted orders: {e}"))?;
+
+        let items: Vec<TrashedItem> = sqlx::query_as(
+            r#"
+            SELECT i.id AS item_id, i.order_id, o.shop_name, o.order_number,
+                   i.item_name, i.price, i.quantity, i.deleted_at
+            FROM items i
+            INNER JOIN orders o ON o.id = i.order_id
+            WHERE i.deleted_at IS NOT NULL
+              AND o.deleted_at IS NULL
+            ORDER BY i.deleted_at DESC, i.id DESC
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| format!("Failed to fetch deleted items: {e}"))?;
+
+        Ok(Trash { orders, items })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sqlx::sqlite::SqlitePoolOptions;
+
+    async fn setup_test_db() -> SqlitePool {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .expect("Failed to create test database");
+
+        sqlx::query(
+            r#"
+            CREATE TABLE orders (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                shop_domain TEXT,
+                shop_name TEXT,
+                order_number TEXT,
+                order_date DATETIME,
+                deleted_at DATETIME
+            );
+            CREATE TABLE items (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                order_id INTEGER NOT NULL,
+                item_name TEXT NOT NULL,
+                price INTEGER NOT NULL DEFAULT 0,
+                quantity INTEGER NOT NULL DEFAULT 1,
+                deleted_at DATETIME
+            );
+            INSERT INTO orders (id, shop_domain, shop_name, order_number) VALUES
+                (1, 'a.example.com', 'ショップA', 'A-001'),
+                (2, 'b.example.com', 'ショップB', 'B-001');
+            INSERT INTO items (id, order_id, item_name, price, quantity) VALUES
+                (1, 1, '商品1', 1000, 1),
+                (2, 1, '商品2', 2000, 2),
+                (3, 2, '商品3', 500, 1);
+            "#,
+        )
+        .execute(&pool)
+        .await
+        .expect("Failed to create tables");
+
+        sqlx::raw_sql(include_str!("../../migrations/034_trashed_keys.sql"))
+            .execute(&pool)
+            .await
+            .expect("Failed to create trashed key tables");
+
+        pool
+    }
+
+    async fn trashed_keys(pool: &SqlitePool) -> (Vec<String>, Vec<String>) {
+        let orders =
+            sqlx::query_scalar("SELECT shop_domain || '/' || order_number FROM trashed_orders")
+                .fetch_all(pool)
+                .await
+                .unwrap();
+        let items = sqlx::query_scalar(
+            "SELECT shop_domain || '/' || order_number || '/' || item_name FROM trashed_items",
+        )
+        .fetch_all(pool)
+        .await
+        .unwrap();
+        (orders, items)
+    }
+
+    async fn live_item_ids(pool: &SqlitePool) -> Vec<i64> {
+        sqlx::query_scalar("SELECT id FROM items WHERE deleted_at IS NULL ORDER BY id")
+            .fetch_all(pool)
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_delete_and_restore_order() {
+        let pool = setup_test_db().await;
+        let repo = SqliteTrashRepository::new(pool.clone());
+
+        repo.delete_order(1).await.unwrap();
+        assert!(repo.delete_order(1).await.is_err());
+        assert_eq!(live_item_ids(&pool).await, vec![3]);
+
+        let trash = repo.list().await.unwrap();
+        assert_eq!(trash.orders.len(), 1);
+        assert_eq!(trash.orders[0].order_id, 1);
+        assert_eq!(trash.orders[0].item_count, 2);
+        assert_eq!(trash.orders[0].total_amount, 5000);
+        assert!(trash.items.is_empty());
+
+        repo.restore_order(1).await.unwrap();
+        assert!(repo.restore_order(1).await.is_err());
+        assert_eq!(live_item_ids(&pool).await, vec![1, 2, 3]);
+        assert_eq!(repo.list().await.unwrap(), Trash::default());
+    }
+
+    #[tokio::test]
+    async fn test_restore_order_keeps_individually_deleted_items() {
+        let pool = setup_test_db().await;
+        let repo = SqliteTrashRepository::new(pool.clone());
+
+        repo.delete_item(2).await.unwrap();
+        // 個別削除と注文削除の日時を区別できるよう、個別削除を過去日時にずらす
+        sqlx::query("UPDATE items SET deleted_at = '2024-01-01 00:00:00' WHERE id = 2")
+            .execute(&pool)
+            .await
+            .unwrap();
+        repo.delete_order(1).await.unwrap();
+        repo.restore_order(1).await.unwrap();
+
+        assert_eq!(live_item_ids(&pool).await, vec![1, 3]);
+        let trash = repo.list().await.unwrap();
+        assert!(trash.orders.is_empty());
+        assert_eq!(trash.items.len(), 1);
+        assert_eq!(trash.items[0].item_id, 2);
+        assert_eq!(trash.items[0].order_number.as_deref(), Some("A-001"));
+    }
+
+    #[tokio::test]
+    async fn test_restore_item_requires_live_order() {
+        let pool = setup_test_db().await;
+        let repo = SqliteTrashRepository::new(pool.clone());
+
+        repo.delete_item(3).await.unwrap();
+        assert!(repo.delete_item(3).await.is_err());
+        repo.restore_item(3).await.unwrap();
+        assert!(repo.restore_item(3).await.is_err());
+
+        repo.delete_order(2).await.unwrap();
+        assert!(repo.restore_item(3).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_trash_state_is_recorded_by_business_key() {
+        let pool = setup_test_db().await;
+        let repo = SqliteTrashRepository::new(pool.clone());
+
+        repo.delete_order(1).await.unwrap();
+        repo.delete_item(3).await.unwrap();
+        assert_eq!(
+            trashed_keys(&pool).await,
+            (
+                vec!["a.example.com/A-001".to_string()],
+                vec!["b.example.com/B-001/商品3".to_string()]
+            )
+        );
+
+        repo.restore_order(1).await.unwrap();
+        repo.restore_item(3).await.unwrap();
+        assert_eq!(trashed_keys(&pool).await, (vec![], vec![]));
+    }
+}
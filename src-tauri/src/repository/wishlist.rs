@@ -0,0 +1,437 @@
+use serde::{Deserialize, Serialize};
+use sqlx::sqlite::SqlitePool;
+use ts_rs::TS;
+
+use crate::gemini::product_parser::normalize_product_name;
+
+/// ウィッシュリストの状態（wishlist_items.status の CHECK 制約と同じ）
+const WISHLIST_STATUSES: &[&str] = &["considering", "purchased", "dropped"];
+
+/// 買うかどうか検討中の商品
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS, sqlx::FromRow)]
+#[ts(export)]
+pub struct WishlistItem {
+    pub id: i64,
+    pub name: String,
+    /// 価格ウォッチで監視するショップの商品ページ（購入時に注文商品のリンクで補完する）
+    pub item_url: Option<String>,
+    /// 希望価格（この価格以下になったら買う）
+    pub target_price: Option<i64>,
+    pub memo: Option<String>,
+    /// `considering` / `purchased` / `dropped`
+    pub status: String,
+    /// 突合した注文商品の items.id
+    pub purchased_item_id: Option<i64>,
+    pub purchased_order_id: Option<i64>,
+    /// 突合した注文商品の単価
+    pub purchased_price: Option<i64>,
+    pub purchased_at: Option<String>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+/// ウィッシュリストの登録・更新内容
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct WishlistItemInput {
+    pub name: String,
+    pub item_url: Option<String>,
+    pub target_price: Option<i64>,
+    pub memo: Option<String>,
+}
+
+impl WishlistItemInput {
+    /// 前後の空白を除き、空文字は None に揃えて検証する。正規化した商品名も返す。
+    fn normalized(&self) -> Result<(Self, String), String> {
+        let trimmed = |v: &Option<String>| {
+            v.as_deref()
+                .map(str::trim)
+                .filter(|s| !s.is_empty())
+                .map(str::to_string)
+        };
+        let input = Self {
+            name: self.name.trim().to_string(),
+            item_url: trimmed(&self.item_url),
+            target_price: self.target_price,
+            memo: trimmed(&self.memo),
+        };
+
+        let name_normalized = normalize_product_name(&input.name);
+        if name_normalized.is_empty() {
+            return Err("Wishlist item name is required".to_string());
+        }
+        if input.target_price.is_some_and(|price| price < 0) {
+            return Err("target_price must not be negative".to_string());
+        }
+        Ok((input, name_normalized))
+    }
+}
+
+/// 注文との突合で購入済みにした商品
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct WishlistPurchase {
+    pub wishlist_id: i64,
+    pub name: String,
+    pub order_id: i64,
+    pub item_id: i64,
+    pub purchased_price: i64,
+    pub target_price: Option<i64>,
+}
+
+impl WishlistPurchase {
+    /// 希望価格以下で購入できたか（希望価格がなければ None）
+    pub fn within_target(&self) -> Option<bool> {
+        self.target_price
+            .map(|target| self.purchased_price <= target)
+    }
+}
+
+const WISHLIST_SELECT: &str = r#"
+    SELECT w.id, w.name, w.item_url, w.target_price, w.memo, w.status,
+           w.purchased_item_id, i.order_id AS purchased_order_id, w.purchased_price,
+           w.purchased_at, w.created_at, w.updated_at
+    FROM wishlist_items w
+    LEFT JOIN items i ON i.id = w.purchased_item_id
+"#;
+
+type PurchaseCandidateRow = (i64, String, Option<i64>, i64, i64, i64, Option<String>);
+
+/// ウィッシュリストのDB操作
+pub struct SqliteWishlistRepository {
+    pool: SqlitePool,
+}
+
+impl SqliteWishlistRepository {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    /// 検討中の商品を先に、それぞれ新しい順に返す
+    pub async fn list(&self) -> Result<Vec<WishlistItem>, String> {
+        sqlx::query_as(&format!(
+            "{WISHLIST_SELECT} ORDER BY w.status = 'considering' DESC, w.created_at DESC, w.id DESC"
+        ))
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| format!("Failed to fetch wishlist items: {e}"))
+    }
+
+    /// 検討中の商品を登録し、wishlist_items.id を返す（同名の商品は重複登録できない）
+    pub async fn add(&self, input: &WishlistItemInput) -> Result<i64, String> {
+        let (input, name_normalized) = input.normalized()?;
+        sqlx::query_scalar(
+            r#"
+            INSERT INTO wishlist_items (name, name_normalized, item_url, target_price, memo)
+            VALUES (?, ?, ?, ?, ?)
+            RETURNING id
+            "#,
+        )
+        .bind(&input.name)
+        .bind(&name_normalized)
+        .bind(&input.item_url)
+        .bind(input.target_price)
+        .bind(&input.memo)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| match e {
+            sqlx::Error::Database(db) if db.is_unique_violation() => {
+                format!("Already in wishlist: {}", input.name)
+            }
+            e => format!("Failed to add wishlist item: {e}"),
+        })
+    }
+
+    pub async fn update(&self, id: i64, input: &WishlistItemInput) -> Result<(), String> {
+        let (input, name_normalized) = input.normalized()?;
+        let result = sqlx::query(
+            r#"
+            UPDATE wishlist_items
+            SET name = ?, name_normalized = ?, item_url = ?, target_price = ?, memo = ?
+            WHERE id = ?
+            "#,
+        )
+        .bind(&input.name)
+        .bind(&name_normalized)
+        .bind(&input.item_url)
+        .bind(input.target_price)
+        .bind(&input.memo)
+        .bind(id)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| match e {
+            sqlx::Error::Database(db) if db.is_unique_violation() => {
+                format!("Already in wishlist: {}", input.name)
+            }
+            e => format!("Failed to update wishlist item: {e}"),
+        })?;
+        if result.rows_affected() == 0 {
+            return Err(format!("Wishlist item not found: {id}"));
+        }
+        Ok(())
+    }
+
+    /// 状態を手動で変更する。購入済み以外に戻した場合は突合した注文商品の記録を消す
+    /// （検討中に戻すと、次回の突合で再び購入済みになりうる）。
+    pub async fn set_status(&self, id: i64, status: &str) -> Result<(), String> {
+        if !WISHLIST_STATUSES.contains(&status) {
+            return Err(format!("Unknown wishlist status: {status}"));
+        }
+        let result = sqlx::query(
+            r#"
+            UPDATE wishlist_items
+            SET status = ?1,
+                purchased_item_id = CASE WHEN ?1 = 'purchased' THEN purchased_item_id END,
+                purchased_price = CASE WHEN ?1 = 'purchased' THEN purchased_price END,
+                purchased_at = CASE WHEN ?1 = 'purchased' THEN COALESCE(purchased_at, CURRENT_TIMESTAMP) END
+            WHERE id = ?2
+            "#,
+        )
+        .bind(status)
+        .bind(id)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| format!("Failed to update wishlist status: {e}"))?;
+        if result.rows_affected() == 0 {
+            return Err(format!("Wishlist item not found: {id}"));
+        }
+        Ok(())
+    }
+
+    pub async fn delete(&self, id: i64) -> Result<(), String> {
+        let result = sqlx::query("DELETE FROM wishlist_items WHERE id = ?")
+            .bind(id)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| format!("Failed to delete wishlist item: {e}"))?;
+        if result.rows_affected() == 0 {
+            return Err(format!("Wishlist item not found: {id}"));
+        }
+        Ok(())
+    }
+
+    /// 検討中の商品と同名（正規化名が一致）の商品を含む注文があれば購入済みにして返す（バッチパース後に呼ぶ）
+    ///
+    /// 同名の商品が複数の注文にある場合は最も早い注文の商品と突合する。
+    /// 商品ページが未登録なら注文商品のリンクで補完し、購入したショップの商品ページを辿れるようにする。
+    pub async fn reconcile_purchases(&self) -> Result<Vec<WishlistPurchase>, String> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| format!("Failed to begin transaction: {e}"))?;
+
+        let rows: Vec<PurchaseCandidateRow> = sqlx::query_as(
+            r#"
+            SELECT w.id, w.name, w.target_price, i.id, i.order_id, i.price, i.item_url
+            FROM wishlist_items w
+            INNER JOIN items i ON i.item_name_normalized = w.name_normalized
+            INNER JOIN orders o ON o.id = i.order_id
+            WHERE w.status = 'considering'
+              AND i.deleted_at IS NULL
+              AND o.deleted_at IS NULL
+            ORDER BY w.id, COALESCE(o.order_date, o.created_at), i.id
+            "#,
+        )
+        .fetch_all(tx.as_mut())
+        .await
+        .map_err(|e| format!("Failed to fetch wishlist purchase candidates: {e}"))?;
+
+        let mut purchases: Vec<WishlistPurchase> = Vec::new();
+        for (wishlist_id, name, target_price, item_id, order_id, price, item_url) in rows {
+            if purchases
+                .last()
+                .is_some_and(|p| p.wishlist_id == wishlist_id)
+            {
+                continue;
+            }
+            sqlx::query(
+                r#"
+                UPDATE wishlist_items
+                SET status = 'purchased',
+                    purchased_item_id = ?,
+                    purchased_price = ?,
+                    purchased_at = CURRENT_TIMESTAMP,
+                    item_url = COALESCE(item_url, ?)
+                WHERE id = ?
+                "#,
+            )
+            .bind(item_id)
+            .bind(price)
+            .bind(&item_url)
+            .bind(wishlist_id)
+            .execute(tx.as_mut())
+            .await
+            .map_err(|e| format!("Failed to mark wishlist item purchased: {e}"))?;
+
+            purchases.push(WishlistPurchase {
+                wishlist_id,
+                name,
+                order_id,
+                item_id,
+                purchased_price: price,
+                target_price,
+            });
+        }
+
+        tx.commit()
+            .await
+            .map_err(|e| format!("Failed to commit transaction: {e}"))?;
+        Ok(purchases)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sqlx::sqlite::SqlitePoolOptions;
+
+    async fn setup_test_db() -> SqlitePool {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .expect("Failed to create test database");
+
+        sqlx::raw_sql(
+            r#"
+            CREATE TABLE orders (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                order_date DATETIME,
+                created_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+                deleted_at DATETIME
+            );
+            CREATE TABLE items (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                order_id INTEGER NOT NULL,
+                item_name TEXT NOT NULL,
+                item_name_normalized TEXT,
+                price INTEGER NOT NULL DEFAULT 0,
+                item_url TEXT,
+                deleted_at DATETIME
+            );
+            "#,
+        )
+        .execute(&pool)
+        .await
+        .expect("Failed to create tables");
+        sqlx::raw_sql(include_str!("../../migrations/031_wishlist.sql"))
+            .execute(&pool)
+            .await
+            .expect("Failed to create wishlist table");
+
+        pool
+    }
+
+    fn input(name: &str, target_price: Option<i64>) -> WishlistItemInput {
+        WishlistItemInput {
+            name: name.to_string(),
+            item_url: None,
+            target_price,
+            memo: Some("  ".to_string()),
+        }
+    }
+
+    async fn insert_item(pool: &SqlitePool, order_date: &str, name: &str, price: i64) -> i64 {
+        let order_id: i64 =
+            sqlx::query_scalar("INSERT INTO orders (order_date) VALUES (?) RETURNING id")
+                .bind(order_date)
+                .fetch_one(pool)
+                .await
+                .unwrap();
+        sqlx::query_scalar(
+            r#"
+            INSERT INTO items (order_id, item_name, item_name_normalized, price, item_url)
+            VALUES (?, ?, ?, ?, 'https://shop.example.com/item/1')
+            RETURNING id
+            "#,
+        )
+        .bind(order_id)
+        .bind(name)
+        .bind(normalize_product_name(name))
+        .bind(price)
+        .fetch_one(pool)
+        .await
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_add_validates_and_rejects_duplicates() {
+        let pool = setup_test_db().await;
+        let repo = SqliteWishlistRepository::new(pool);
+
+        assert!(repo.add(&input(" ・ ", None)).await.is_err());
+        assert!(repo.add(&input("figma 初音ミク", Some(-1))).await.is_err());
+
+        let id = repo
+            .add(&input("figma 初音ミク", Some(8000)))
+            .await
+            .unwrap();
+        let err = repo.add(&input("figma　初音ミク", None)).await.unwrap_err();
+        assert!(err.starts_with("Already in wishlist"), "{err}");
+
+        let items = repo.list().await.unwrap();
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].id, id);
+        assert_eq!(items[0].status, "considering");
+        assert_eq!(items[0].memo, None);
+    }
+
+    #[tokio::test]
+    async fn test_reconcile_purchases_marks_same_name_as_purchased() {
+        let pool = setup_test_db().await;
+        let repo = SqliteWishlistRepository::new(pool.clone());
+        let miku = repo
+            .add(&input("ねんどろいど 初音ミク", Some(5000)))
+            .await
+            .unwrap();
+        let rin = repo.add(&input("figma 鏡音リン", None)).await.unwrap();
+
+        assert!(repo.reconcile_purchases().await.unwrap().is_empty());
+
+        insert_item(&pool, "2025-03-02", "ねんどろいど　初音ミク", 5800).await;
+        let first = insert_item(&pool, "2025-03-01", "ねんどろいど 初音ミク", 4800).await;
+
+        let purchases = repo.reconcile_purchases().await.unwrap();
+        assert_eq!(purchases.len(), 1);
+        assert_eq!(purchases[0].wishlist_id, miku);
+        assert_eq!(purchases[0].item_id, first);
+        assert_eq!(purchases[0].within_target(), Some(true));
+        // 購入済みは再度突合しない
+        assert!(repo.reconcile_purchases().await.unwrap().is_empty());
+
+        let items = repo.list().await.unwrap();
+        assert_eq!(items[0].id, rin);
+        let purchased = items.iter().find(|w| w.id == miku).unwrap();
+        assert_eq!(purchased.status, "purchased");
+        assert_eq!(purchased.purchased_item_id, Some(first));
+        assert_eq!(purchased.purchased_price, Some(4800));
+        assert!(purchased.purchased_order_id.is_some());
+        assert_eq!(
+            purchased.item_url.as_deref(),
+            Some("https://shop.example.com/item/1")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_set_status_clears_purchase_when_reverted() {
+        let pool = setup_test_db().await;
+        let repo = SqliteWishlistRepository::new(pool.clone());
+        let id = repo.add(&input("HG 1/144 ザク", None)).await.unwrap();
+        insert_item(&pool, "2025-03-01", "HG 1/144 ザク", 1500).await;
+        repo.reconcile_purchases().await.unwrap();
+
+        assert!(repo.set_status(id, "bought").await.is_err());
+        repo.set_status(id, "dropped").await.unwrap();
+        let item = &repo.list().await.unwrap()[0];
+        assert_eq!(item.status, "dropped");
+        assert_eq!(item.purchased_item_id, None);
+        assert_eq!(item.purchased_at, None);
+
+        // 見送りにした商品は突合しない
+        assert!(repo.reconcile_purchases().await.unwrap().is_empty());
+
+        repo.delete(id).await.unwrap();
+        assert!(repo.delete(id).await.is_err());
+    }
+}
@@ -14,6 +14,7 @@ use std::sync::Arc;
 use std::time::Duration;
 use tauri::Emitter;
 use tokio::sync::Notify;
+use ts_rs::TS;
 
 /// スケジューラ実行間隔の最小値（分）
 pub const SCHEDULER_INTERVAL_MIN_MINUTES: i64 = 1;
@@ -25,7 +26,8 @@ pub const SCHEDULER_STATUS_EVENT: &str = "scheduler-status-changed";
 pub const SCHEDULER_PIPELINE_STARTED_EVENT: &str = "scheduler-pipeline-started";
 pub const SCHEDULER_PIPELINE_COMPLETED_EVENT: &str = "scheduler-pipeline-completed";
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export)]
 #[serde(rename_all = "camelCase")]
 pub struct SchedulerStatusPayload {
     pub enabled: bool,
@@ -169,6 +171,11 @@ pub async fn run_scheduler(app: tauri::AppHandle, state: SchedulerState, shutdow
             continue;
         }
 
+        if crate::db_lock::is_read_only(&app) {
+            log::debug!("[Scheduler] Database is read-only, skipping tick");
+            continue;
+        }
+
         if state.is_running() {
             log::info!("[Scheduler] Previous pipeline still running, skipping tick");
             continue;
@@ -0,0 +1,61 @@
+//! 統計の日次スナップショット
+//!
+//! 注文・配送の統計（`OrderStats` / `DeliveryStats`）を日本時間の日付ごとに `stats_snapshots` へ保存し、
+//! 「積み数の推移」「未着数の推移」の時系列グラフに使う。
+//! 常駐中は `run_stats_snapshot_recorder` が定期的に当日分を上書き保存するため、
+//! 各日の値はその日の最終確認時点の統計になる。
+
+use sqlx::sqlite::SqlitePool;
+use std::time::Duration;
+use tauri::Manager;
+
+use crate::repository::{
+    DeliveryStatsRepository, OrderStatsRepository, SqliteDeliveryStatsRepository,
+    SqliteOrderStatsRepository, SqliteStatsSnapshotRepository, StatsSnapshotRepository,
+};
+
+/// 起動直後の記録を遅らせる時間（起動処理・初回同期と競合させない）
+const INITIAL_RECORD_DELAY: Duration = Duration::from_secs(90);
+
+/// 当日分の上書き間隔（1時間）
+const RECORD_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+/// 現在の統計を `date`（JST, YYYY-MM-DD）のスナップショットとして保存する
+pub async fn record_snapshot(pool: &SqlitePool, date: String) -> Result<(), String> {
+    let order_repo = SqliteOrderStatsRepository::new(pool.clone());
+    let delivery_repo = SqliteDeliveryStatsRepository::new(pool.clone());
+    let (order_stats, delivery_stats) = tokio::try_join!(
+        order_repo.get_order_stats(),
+        delivery_repo.get_delivery_stats(),
+    )?;
+
+    SqliteStatsSnapshotRepository::new(pool.clone())
+        .save_snapshot(date, order_stats, delivery_stats)
+        .await
+}
+
+/// 日本時間の今日（YYYY-MM-DD）
+fn today_jst() -> String {
+    chrono::Utc::now()
+        .with_timezone(&chrono_tz::Asia::Tokyo)
+        .format("%Y-%m-%d")
+        .to_string()
+}
+
+/// 常駐中に当日のスナップショットを定期的に保存するループ。`setup()` から `tauri::async_runtime::spawn` で起動する。
+pub async fn run_stats_snapshot_recorder(app: tauri::AppHandle) {
+    tokio::time::sleep(INITIAL_RECORD_DELAY).await;
+    loop {
+        match app.try_state::<SqlitePool>() {
+            Some(pool) => {
+                let date = today_jst();
+                match record_snapshot(pool.inner(), date.clone()).await {
+                    Ok(()) => log::debug!("[StatsSnapshot] Recorded snapshot for {date}"),
+                    Err(e) => log::warn!("[StatsSnapshot] Failed to record snapshot: {e}"),
+                }
+            }
+            None => log::warn!("[StatsSnapshot] Database pool is not ready"),
+        }
+        tokio::time::sleep(RECORD_INTERVAL).await;
+    }
+}
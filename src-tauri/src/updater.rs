@@ -0,0 +1,235 @@
+//! アップデート確認（GitHub Releases）
+//!
+//! GitHub Releases の最新リリースを取得し、実行中のバージョンより新しければ通知する。
+//! - `check_for_updates` コマンドで手動確認（リリースノート付き）
+//! - 常駐中は `run_update_checker` が定期的に確認し、新バージョンを検知したらトレイ通知する
+//!
+//! インストーラーの自動適用（tauri-plugin-updater）は署名鍵と配布用エンドポイントの整備が必要なため、
+//! 現状はリリースページ（`release_url`）を開いて手動で更新する。
+
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+use tauri::{Emitter, Manager};
+use tauri_plugin_notification::NotificationExt;
+use ts_rs::TS;
+
+/// 最新リリース取得 API
+pub const GITHUB_LATEST_RELEASE_URL: &str =
+    "https://api.github.com/repos/hina0118/paa/releases/latest";
+
+/// 新バージョン検知時にフロントエンドへ送るイベント
+pub const UPDATE_AVAILABLE_EVENT: &str = "update-available";
+
+/// 起動直後の確認を遅らせる時間（起動処理・初回同期と競合させない）
+const INITIAL_CHECK_DELAY: Duration = Duration::from_secs(60);
+
+/// 定期確認の間隔（24時間）
+const UPDATE_CHECK_INTERVAL: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// GitHub API のタイムアウト秒数
+const GITHUB_API_TIMEOUT_SECS: u64 = 10;
+
+/// GitHub Releases API のレスポンス（必要なフィールドのみ）
+#[derive(Debug, Clone, Deserialize)]
+pub struct GithubRelease {
+    pub tag_name: String,
+    pub name: Option<String>,
+    pub body: Option<String>,
+    pub html_url: String,
+    pub published_at: Option<String>,
+}
+
+/// アップデート確認結果
+#[derive(Debug, Clone, PartialEq, Serialize, TS)]
+#[ts(export)]
+pub struct UpdateInfo {
+    pub current_version: String,
+    pub latest_version: String,
+    pub update_available: bool,
+    pub release_name: Option<String>,
+    /// リリースノート（Markdown）
+    pub release_notes: Option<String>,
+    pub release_url: String,
+    pub published_at: Option<String>,
+}
+
+/// `v1.2.3` / `1.2.3-beta.1` を (major, minor, patch) に変換する（プレリリース部は無視）
+pub fn parse_version(version: &str) -> Option<(u64, u64, u64)> {
+    let core = version.trim().trim_start_matches(['v', 'V']);
+    let core = core.split(['-', '+']).next()?;
+    let mut parts = core.split('.').map(|p| p.parse::<u64>().ok());
+    let major = parts.next()??;
+    let minor = parts.next().unwrap_or(Some(0))?;
+    let patch = parts.next().unwrap_or(Some(0))?;
+    Some((major, minor, patch))
+}
+
+/// `latest` が `current` より新しいバージョンか（解析できない場合は false）
+pub fn is_newer_version(latest: &str, current: &str) -> bool {
+    match (parse_version(latest), parse_version(current)) {
+        (Some(latest), Some(current)) => latest > current,
+        _ => false,
+    }
+}
+
+/// 最新リリースと実行中のバージョンから確認結果を組み立てる
+pub fn build_update_info(release: GithubRelease, current_version: &str) -> UpdateInfo {
+    let latest_version = release.tag_name.trim_start_matches(['v', 'V']).to_string();
+    UpdateInfo {
+        current_version: current_version.to_string(),
+        update_available: is_newer_version(&latest_version, current_version),
+        latest_version,
+        release_name: release.name.filter(|n| !n.trim().is_empty()),
+        release_notes: release.body.filter(|b| !b.trim().is_empty()),
+        release_url: release.html_url,
+        published_at: release.published_at,
+    }
+}
+
+/// GitHub Releases から最新リリースを取得する（ドラフト・プレリリースは API 側で除外される）
+pub async fn fetch_latest_release(
+    url: &str,
+    current_version: &str,
+) -> Result<GithubRelease, String> {
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_secs(GITHUB_API_TIMEOUT_SECS))
+        // GitHub API は User-Agent 必須
+        .user_agent(format!("paa/{current_version}"))
+        .build()
+        .map_err(|e| format!("Failed to build HTTP client: {e}"))?;
+
+    let response = client
+        .get(url)
+        .header("Accept", "application/vnd.github+json")
+        .send()
+        .await
+        .map_err(|e| format!("Failed to fetch latest release: {e}"))?;
+
+    let status = response.status();
+    if !status.is_success() {
+        return Err(format!("GitHub API returned {status}"));
+    }
+
+    response
+        .json::<GithubRelease>()
+        .await
+        .map_err(|e| format!("Invalid release response: {e}"))
+}
+
+/// 最新リリースを確認する
+pub async fn check_for_updates(current_version: &str) -> Result<UpdateInfo, String> {
+    let release = fetch_latest_release(GITHUB_LATEST_RELEASE_URL, current_version).await?;
+    Ok(build_update_info(release, current_version))
+}
+
+/// 自動確認が有効か（設定読み込みに失敗した場合は有効扱い）
+fn auto_check_enabled(app: &tauri::AppHandle) -> bool {
+    app.path()
+        .app_config_dir()
+        .map_err(|e| format!("Failed to get app config dir: {e}"))
+        .and_then(|dir| crate::config::load(&dir))
+        .map(|c| c.updater.auto_check)
+        .unwrap_or(true)
+}
+
+/// 常駐中に定期的にアップデートを確認するループ。`setup()` から `tauri::async_runtime::spawn` で起動する。
+///
+/// 同じバージョンの通知は1回のみ行う。
+pub async fn run_update_checker(app: tauri::AppHandle) {
+    let current_version = app.package_info().version.to_string();
+    let mut notified_version: Option<String> = None;
+
+    tokio::time::sleep(INITIAL_CHECK_DELAY).await;
+    loop {
+        if auto_check_enabled(&app) {
+            match check_for_updates(&current_version).await {
+                Ok(info) if info.update_available => {
+                    if notified_version.as_deref() != Some(info.latest_version.as_str()) {
+                        log::info!(
+                            "[Updater] New version available: {} (current {})",
+                            info.latest_version,
+                            current_version
+                        );
+                        let _ = app
+                            .notification()
+                            .builder()
+                            .title("アップデートがあります")
+                            .body(format!(
+                                "paa {} が公開されています（現在 {}）",
+                                info.latest_version, current_version
+                            ))
+                            .show();
+                        notified_version = Some(info.latest_version.clone());
+                        let _ = app.emit(UPDATE_AVAILABLE_EVENT, info);
+                    }
+                }
+                Ok(_) => log::debug!("[Updater] Already up to date ({current_version})"),
+                Err(e) => log::warn!("[Updater] Update check failed: {e}"),
+            }
+        }
+        tokio::time::sleep(UPDATE_CHECK_INTERVAL).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn release(tag: &str) -> GithubRelease {
+        GithubRelease {
+            tag_name: tag.to_string(),
+            name: Some("v0.2.0".to_string()),
+            body: Some("- 新機能".to_string()),
+            html_url: "https://github.com/hina0118/paa/releases/tag/v0.2.0".to_string(),
+            published_at: Some("2026-01-01T00:00:00Z".to_string()),
+        }
+    }
+
+    #[test]
+    fn test_parse_version() {
+        assert_eq!(parse_version("v1.2.3"), Some((1, 2, 3)));
+        assert_eq!(parse_version("0.10.0-beta.1"), Some((0, 10, 0)));
+        assert_eq!(parse_version("2"), Some((2, 0, 0)));
+        assert_eq!(parse_version("latest"), None);
+    }
+
+    #[test]
+    fn test_is_newer_version() {
+        assert!(is_newer_version("v0.2.0", "0.1.0"));
+        assert!(is_newer_version("0.10.0", "0.9.9"));
+        assert!(!is_newer_version("v0.1.0", "0.1.0"));
+        assert!(!is_newer_version("0.0.9", "0.1.0"));
+        assert!(!is_newer_version("nightly", "0.1.0"));
+    }
+
+    #[test]
+    fn test_build_update_info() {
+        let info = build_update_info(release("v0.2.0"), "0.1.0");
+        assert_eq!(info.latest_version, "0.2.0");
+        assert!(info.update_available);
+        assert_eq!(info.release_notes.as_deref(), Some("- 新機能"));
+
+        let mut same = release("v0.1.0");
+        same.body = Some("  ".to_string());
+        let info = build_update_info(same, "0.1.0");
+        assert!(!info.update_available);
+        assert_eq!(info.release_notes, None);
+    }
+
+    #[test]
+    fn test_github_release_deserializes_api_response() {
+        let json = r#"{
+            "tag_name": "v0.3.0",
+            "name": null,
+            "body": "notes",
+            "html_url": "https://github.com/hina0118/paa/releases/tag/v0.3.0",
+            "published_at": "2026-02-01T00:00:00Z",
+            "draft": false,
+            "prerelease": false,
+            "assets": []
+        }"#;
+        let release: GithubRelease = serde_json::from_str(json).unwrap();
+        assert_eq!(release.tag_name, "v0.3.0");
+        assert_eq!(release.name, None);
+    }
+}
@@ -0,0 +1,104 @@
+//! ウィッシュリスト（買うかどうか検討中の商品）
+//!
+//! バッチパース後に検討中の商品と注文商品を商品名で突合し、同名の商品を注文していれば
+//! 「購入済み」へ移してデスクトップ通知する。希望価格があれば購入時の単価と比較して本文に添える。
+
+use sqlx::sqlite::SqlitePool;
+
+use crate::batch_runner::BatchEventEmitter;
+use crate::orchestration::BatchCommandsApp;
+use crate::report::spending_chart::format_amount;
+use crate::repository::{SqliteWishlistRepository, WishlistPurchase};
+
+/// 購入済みにした商品をフロントエンドへ知らせるイベント
+pub const WISHLIST_PURCHASED_EVENT: &str = "wishlist-purchased";
+
+/// 通知本文に列挙する商品数の上限（超過分は件数のみ）
+const MAX_NOTIFIED_ITEMS: usize = 3;
+
+/// デスクトップ通知のタイトルと本文（購入済みにした商品がなければ None）
+pub fn build_notification(purchases: &[WishlistPurchase]) -> Option<(String, String)> {
+    if purchases.is_empty() {
+        return None;
+    }
+    let mut body = purchases
+        .iter()
+        .take(MAX_NOTIFIED_ITEMS)
+        .map(|p| {
+            let target = match (p.within_target(), p.target_price) {
+                (Some(true), _) => "・希望価格以下".to_string(),
+                (Some(false), Some(target)) => {
+                    format!("・希望価格 {}円を超過", format_amount(target))
+                }
+                _ => String::new(),
+            };
+            format!(
+                "{}（{}円{}）",
+                p.name,
+                format_amount(p.purchased_price),
+                target
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("／");
+    if purchases.len() > MAX_NOTIFIED_ITEMS {
+        body.push_str(&format!(" ほか {}件", purchases.len() - MAX_NOTIFIED_ITEMS));
+    }
+    Some(("検討中の商品を購入済みにしました".to_string(), body))
+}
+
+/// 検討中の商品を注文と突合して購入済みにし、通知する。バッチ処理の結果には影響させず、失敗はログのみ。
+pub(crate) async fn reconcile_purchases<A: BatchCommandsApp>(app: &A, pool: &SqlitePool) {
+    let purchases = match SqliteWishlistRepository::new(pool.clone())
+        .reconcile_purchases()
+        .await
+    {
+        Ok(purchases) => purchases,
+        Err(e) => {
+            log::warn!("[Wishlist] Failed to reconcile purchases: {e}");
+            return;
+        }
+    };
+    if let Some((title, body)) = build_notification(&purchases) {
+        log::info!(
+            "[Wishlist] {} wishlist item(s) marked as purchased",
+            purchases.len()
+        );
+        app.notify(&title, &body);
+        app.emit_event(WISHLIST_PURCHASED_EVENT, &purchases);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn purchase(name: &str, purchased_price: i64, target_price: Option<i64>) -> WishlistPurchase {
+        WishlistPurchase {
+            wishlist_id: 1,
+            name: name.to_string(),
+            order_id: 1,
+            item_id: 1,
+            purchased_price,
+            target_price,
+        }
+    }
+
+    #[test]
+    fn test_build_notification() {
+        assert_eq!(build_notification(&[]), None);
+
+        let (title, body) = build_notification(&[
+            purchase("ねんどろいど 初音ミク", 4800, Some(5000)),
+            purchase("figma 鏡音リン", 9900, Some(8000)),
+            purchase("HG ザク", 1500, None),
+            purchase("MG ガンダム", 5500, None),
+        ])
+        .unwrap();
+        assert_eq!(title, "検討中の商品を購入済みにしました");
+        assert_eq!(
+            body,
+            "ねんどろいど 初音ミク（4,800円・希望価格以下）／figma 鏡音リン（9,900円・希望価格 8,000円を超過）／HG ザク（1,500円） ほか 1件"
+        );
+    }
+}
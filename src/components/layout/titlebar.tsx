@@ -1,11 +1,14 @@
 import { useState, useEffect, useRef } from 'react';
 import { Minus, Square, X, Minimize2 } from 'lucide-react';
+import { invoke } from '@tauri-apps/api/core';
 import { getCurrentWindow } from '@tauri-apps/api/window';
 import { cn } from '@/lib/utils';
+import type { DbAccessStatus } from '@/lib/types';
 import appIcon from '@/assets/app-icon.png';
 
 export function TitleBar() {
   const [isMaximized, setIsMaximized] = useState(false);
+  const [dbAccess, setDbAccess] = useState<DbAccessStatus | null>(null);
 
   const isActiveRef = useRef(true);
 
@@ -14,6 +17,12 @@ export function TitleBar() {
     let cleanup: (() => void) | undefined;
 
     const init = async () => {
+      invoke<DbAccessStatus>('get_db_access_status')
+        .then((status) => {
+          if (isActiveRef.current) setDbAccess(status);
+        })
+        .catch(() => {});
+
       const win = getCurrentWindow();
       const maximized = await win.isMaximized();
       setIsMaximized(maximized);
@@ -71,7 +80,21 @@ export function TitleBar() {
       </div>
 
       {/* ドラッグ領域（フレックスで残りスペースを占有） */}
-      <div className="flex-1" data-tauri-drag-region />
+      <div className="flex flex-1 items-center px-3" data-tauri-drag-region>
+        {dbAccess?.read_only && (
+          <span
+            className="rounded bg-amber-100 px-2 py-0.5 text-xs text-amber-800 dark:bg-amber-900/40 dark:text-amber-200"
+            title={
+              dbAccess.holder
+                ? `${dbAccess.holder.hostname} が DB を使用中のため、変更は保存されません`
+                : undefined
+            }
+            data-tauri-drag-region
+          >
+            読み取り専用
+          </span>
+        )}
+      </div>
 
       {/* ウィンドウコントロール */}
       <div className="flex">
@@ -13,7 +13,12 @@ import { Input } from '@/components/ui/input';
 import { Button } from '@/components/ui/button';
 import { Textarea } from '@/components/ui/textarea';
 import { PageHeader } from '@/components/ui/page-header';
-import { toastSuccess, toastError, formatError } from '@/lib/toast';
+import {
+  toastSuccess,
+  toastError,
+  toastWarning,
+  formatError,
+} from '@/lib/toast';
 
 export function ApiKeys() {
   const { geminiApiKeyStatus, refreshGeminiApiKeyStatus } = useParse();
@@ -170,10 +175,16 @@ export function ApiKeys() {
 
     setIsSavingGmailOAuth(true);
     try {
-      await invoke('save_gmail_oauth_credentials', { jsonContent });
+      const warnings = await invoke<string[] | null>(
+        'save_gmail_oauth_credentials',
+        { jsonContent }
+      );
       toastSuccess(
         'Gmail OAuth認証情報を保存しました（OSのセキュアストレージに保存）'
       );
+      for (const warning of warnings ?? []) {
+        toastWarning('OAuth スコープの確認', warning);
+      }
       setGmailOAuthJson('');
       await refreshGmailOAuthStatus();
     } catch (error) {
@@ -5,10 +5,30 @@
  * すべてのバッチ処理（メール同期、メールパース、商品名パース）で使用されます
  */
 
+/**
+ * 進捗イベントのスキーマバージョン
+ * @see src-tauri/src/batch_runner.rs - BATCH_PROGRESS_SCHEMA_VERSION
+ */
+export const BATCH_PROGRESS_SCHEMA_VERSION = 1;
+
+/**
+ * 進捗イベントの種別
+ */
+export type BatchEventKind =
+  | 'progress'
+  | 'complete'
+  | 'error'
+  | 'cancelled'
+  | 'timeout';
+
 /**
  * バッチ処理の進捗情報
  */
 export interface BatchProgress {
+  /** スキーマバージョン（旧バックエンドからのイベントでは未設定） */
+  schema_version?: number;
+  /** イベント種別 */
+  kind?: BatchEventKind;
   /** タスク名（"メール同期", "メールパース", "商品名パース" など） */
   task_name: string;
   /** 現在のバッチ番号（1から開始） */
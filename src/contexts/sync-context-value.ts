@@ -9,6 +9,7 @@ export interface SyncMetadata {
   last_sync_started_at?: string;
   last_sync_completed_at?: string;
   last_error_message?: string | null;
+  misfiled_shop_mail?: { spam_count: number; promotions_count: number } | null;
   max_iterations: number;
   /** Gmail API の1ページあたり取得件数（最大500） */
   max_results_per_page: number;
@@ -2,6 +2,7 @@ import Database from '@tauri-apps/plugin-sql';
 import { appConfigDir, join } from '@tauri-apps/api/path';
 import { invoke } from '@tauri-apps/api/core';
 import { createE2EMockDb } from './e2e-mock-db';
+import type { DbAccessStatus } from './types';
 
 // Re-export for backward compatibility (table-viewer, etc.)
 export {
@@ -125,7 +126,12 @@ export class DatabaseManager {
       const appConfigDirPath = await appConfigDir();
       const dbFilename = await invoke<string>('get_db_filename');
       const dbPath = await join(appConfigDirPath, dbFilename);
-      const db = await Database.load(`sqlite:${dbPath}`);
+      // 他の端末が DB を使用中の場合は読み取り専用で開く（マイグレーション・書き込みを行わない）
+      const access = await invoke<DbAccessStatus>('get_db_access_status').catch(
+        () => null
+      );
+      const mode = access?.read_only ? '?mode=ro' : '';
+      const db = await Database.load(`sqlite:${dbPath}${mode}`);
 
       // Check again if we started closing while initializing
       if (this.isClosing) {
@@ -102,6 +102,13 @@ describe('loadOrderItems', () => {
     expect(sql).toContain('DESC');
   });
 
+  it('excludes soft-deleted orders and items', async () => {
+    const mockDb = { select: vi.fn().mockResolvedValue([]) };
+    await loadOrderItems(mockDb as never);
+    const [sql] = (mockDb.select as ReturnType<typeof vi.fn>).mock.calls[0];
+    expect(sql).toContain('i.deleted_at IS NULL AND o.deleted_at IS NULL');
+  });
+
   it('applies not_shipped delivery status filter', async () => {
     const mockDb = { select: vi.fn().mockResolvedValue([]) };
     await loadOrderItems(mockDb as never, { deliveryStatus: 'not_shipped' });
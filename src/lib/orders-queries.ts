@@ -202,6 +202,8 @@ export async function loadOrderItems(
     LEFT JOIN excluded_orders eo ON eo.shop_domain = o.shop_domain
         AND eo.order_number COLLATE NOCASE = o.order_number
     WHERE ei.id IS NULL AND eo.id IS NULL
+      -- ゴミ箱（deleted_at）に移動した注文・商品は表示しない
+      AND i.deleted_at IS NULL AND o.deleted_at IS NULL
       AND ${conditions.join(' AND ')}
     ORDER BY ${orderCol} ${orderDir}
   `;
@@ -226,6 +228,7 @@ export async function getOrderItemFilterOptions(db: {
           ON eo.shop_domain = o.shop_domain
          AND eo.order_number COLLATE NOCASE = o.order_number
         WHERE eo.id IS NULL
+          AND o.deleted_at IS NULL
           AND (o.shop_domain IS NOT NULL OR o.shop_name IS NOT NULL OR oo.shop_name IS NOT NULL)
         ORDER BY shop_display
       `
@@ -241,6 +244,7 @@ export async function getOrderItemFilterOptions(db: {
           ON eo.shop_domain = o.shop_domain
          AND eo.order_number COLLATE NOCASE = o.order_number
         WHERE eo.id IS NULL
+          AND o.deleted_at IS NULL
           AND COALESCE(oo.order_date, o.order_date) IS NOT NULL
           AND trim(strftime('%Y', COALESCE(oo.order_date, o.order_date))) != ''
         ORDER BY yr DESC
@@ -198,3 +198,18 @@ export type ExcludeOrderParams = {
   orderNumber: string;
   reason?: string | null;
 };
+
+/** DB を使用中の端末の情報（ロックファイルの内容） */
+export type DbLockInfo = {
+  instance_id: string;
+  hostname: string;
+  pid: number;
+  acquired_at: string;
+  heartbeat_at: string;
+};
+
+/** 起動時に決まった DB のアクセスモード */
+export type DbAccessStatus = {
+  read_only: boolean;
+  holder: DbLockInfo | null;
+};